@@ -0,0 +1,604 @@
+//! # Aggregation module
+//! 
+//! ## Description
+//! This module  contains  [`AttributeStats`],  the  summary  statistics  of  a  numeric
+//! attribute, and methods of [`Graph`] that aggregate attributes of vertices and  edges
+//! across the whole graph or within groups of vertices and edges that share  the  value
+//! of another attribute.
+//! 
+//! Besides,       it       contains       [`Graph::aggregate_neighbour_attr`]       and
+//! [`Graph::aggregate_neighbour_attr_into`] that reduce a numeric  attribute  over  the
+//! neighbourhoods of vertices, with [`NeighbourDirection`] selecting  which  neighbours
+//! count.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet};
+use crate::{attributes::{AttributeCollection, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, io::{AttributeCollectionIO, AttributeToken}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Key of the group of an attribute value, strings are used as they are, the other
+// values are formatted
+fn group_key(value: StaticDispatchAttributeValue) -> String {
+    match value {
+        StaticDispatchAttributeValue::Str(value) => value,
+        value => format!("{:?}", value),
+    }
+}
+
+
+
+// Statistics of the numeric attribute `name` grouped by the attribute `key`, or in a
+// single group with the empty key if `key` is `None`
+fn stats_by<'a, AttributeCollectionType, CollectionsType>(collections: CollectionsType, name: &str, key: Option<&str>, bins: usize, function_path: &str) -> CrabNetsResult<HashMap<String, AttributeStats>>
+where
+    AttributeCollectionType: AttributeCollectionIO + 'a,
+    CollectionsType: Iterator<Item = CrabNetsResult<&'a AttributeCollectionType>>,
+{
+    if bins == 0 {
+        return Err(CrabNetsError::new(function_path, ErrorKind::InvalidArgument { name: "bins".to_string(), reason: String::from("The number of bins of the histogram must be positive.") }));
+    }
+    let mut groups: HashMap<String, Vec<f64>> = HashMap::new();
+    for collection in collections {
+        let collection = collection?;
+        let Some(value) = numeric_value(collection, name, function_path)? else {
+            continue;
+        };
+        let group = match key {
+            Some(key) => match collection.io_query_contents(key) {
+                Some(key_value) => group_key(key_value),
+                None => continue,
+            },
+            None => String::new(),
+        };
+        groups.entry(group).or_default().push(value);
+    }
+    Ok(groups.into_iter().map(|(group, values)| (group, AttributeStats::from_values(&values, bins))).collect())
+}
+
+
+
+// Numeric value of the attribute `name`, `Ok(None)` if the collection doesn't have it
+// or it's `f64::NAN`
+fn numeric_value<AttributeCollectionType>(collection: &AttributeCollectionType, name: &str, function_path: &str) -> CrabNetsResult<Option<f64>>
+where
+    AttributeCollectionType: AttributeCollectionIO,
+{
+    match collection.io_query_contents(name) {
+        Some(value) => match value.as_f64() {
+            Some(value) => Ok((!value.is_nan()).then_some(value)),
+            None => Err(CrabNetsError::new(function_path, ErrorKind::AttributeTypeMismatch { name: name.to_string() })),
+        },
+        None => Ok(None),
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ATTRIBUTE STATISTICS                                                              *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Attribute statistics
+/// 
+/// ## Description
+/// Summary statistics of the values of a numeric attribute.
+/// 
+/// ## Fields
+/// * `count` - the number of values.
+/// * `histogram` - the numbers of values in the bins that split the range from `min` to
+/// `max` into equal parts, the last bin includes `max`; if all values are  equal,  they
+/// all fall into the first bin.
+/// * `max` - the largest value.
+/// * `mean` - the arithmetic mean of the values.
+/// * `min` - the smallest value.
+/// * `variance` - the population variance of the values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeStats {
+    pub count: usize,
+    pub histogram: Vec<usize>,
+    pub max: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub variance: f64,
+}
+
+// AttributeStats::AttributeStats
+impl AttributeStats {
+    // Statistics of non-empty values, the mean and the variance are accumulated by
+    // Welford's method to avoid the loss of precision
+    fn from_values(values: &[f64], bins: usize) -> Self {
+        let mut answer = AttributeStats { count: 0, histogram: vec![0; bins], max: f64::NEG_INFINITY, mean: 0f64, min: f64::INFINITY, variance: 0f64 };
+        for &value in values.iter() {
+            answer.count += 1;
+            let delta = value - answer.mean;
+            answer.mean += delta / answer.count as f64;
+            answer.variance += delta * (value - answer.mean);
+            answer.max = answer.max.max(value);
+            answer.min = answer.min.min(value);
+        }
+        answer.variance /= answer.count as f64;
+        let width = (answer.max - answer.min) / bins as f64;
+        for &value in values.iter() {
+            let bin = match width > 0f64 {
+                true => (((value - answer.min) / width) as usize).min(bins - 1),
+                false => 0,
+            };
+            answer.histogram[bin] += 1;
+        }
+        answer
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Statistics of edge attribute
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `bins` : `usize` - the number of bins of the histogram.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<AttributeStats>>` - `Ok(Some(value))`  is  returned  if
+    /// `bins` is positive, all values of the attribute are numeric  and  at  least  one
+    /// edge has it, `value`  in  this  case  contains  the  statistics;  `Ok(None)`  is
+    /// returned if no edge has the attribute; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::v_attr_stats`].
+    pub fn e_attr_stats(&self, name: &str, bins: usize) -> CrabNetsResult<Option<AttributeStats>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::e_attr_stats";
+        Ok(self.e_attr_stats_of(name, None, bins, FUNCTION_PATH)?.remove(""))
+    }
+
+    /// # Statistics of edge attribute by group
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `key` : `&str` - the name of the attribute that defines the groups.
+    /// * `bins` : `usize` - the number of bins of the histograms.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<String, AttributeStats>>` - `Ok(value)` is returned if
+    /// `bins` is positive and all values of the attribute `name` are  numeric,  `value`
+    /// in this case maps the values  of  `key`  to  the  statistics  of  their  groups;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::v_attr_stats_by`].
+    pub fn e_attr_stats_by(&self, name: &str, key: &str, bins: usize) -> CrabNetsResult<HashMap<String, AttributeStats>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::e_attr_stats_by";
+        self.e_attr_stats_of(name, Some(key), bins, FUNCTION_PATH)
+    }
+
+    fn e_attr_stats_of(&self, name: &str, key: Option<&str>, bins: usize, function_path: &str) -> CrabNetsResult<HashMap<String, AttributeStats>> {
+        stats_by(self.iter_e().map(|x| self.e_attrs(&x.id1, &x.id2, &x.edge_id)), name, key, bins, function_path)
+    }
+}
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Statistics of vertex attribute
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `bins` : `usize` - the number of bins of the histogram.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<AttributeStats>>` - `Ok(Some(value))`  is  returned  if
+    /// `bins` is positive, all values of the attribute are numeric  and  at  least  one
+    /// vertex has it, `value` in this  case  contains  the  statistics;  `Ok(None)`  is
+    /// returned if no vertex has the attribute; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The attribute collections are visited once. Values  of  all  numeric  types  are
+    /// converted into `f64`, vertices that don't  have  the  attribute  and  `f64::NAN`
+    /// values are skipped.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + `bins`).
+    /// 
+    /// Space: O(|V| + `bins`).
+    pub fn v_attr_stats(&self, name: &str, bins: usize) -> CrabNetsResult<Option<AttributeStats>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_attr_stats";
+        Ok(self.v_attr_stats_of(name, None, bins, FUNCTION_PATH)?.remove(""))
+    }
+
+    /// # Statistics of vertex attribute by group
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `key` : `&str` - the name of the attribute that defines the groups.
+    /// * `bins` : `usize` - the number of bins of the histograms.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<String, AttributeStats>>` - `Ok(value)` is returned if
+    /// `bins` is positive and all values of the attribute `name` are  numeric,  `value`
+    /// in this case maps the values  of  `key`  to  the  statistics  of  their  groups;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Vertices that share the value of the attribute  `key`  form  a  group,  and  the
+    /// statistics of `name` are computed within each group like [`Graph::v_attr_stats`]
+    /// does. String values of `key` are used as they are, values  of  other  types  are
+    /// formatted with [`Debug`]. Vertices that don't have one  of  the  attributes  are
+    /// skipped, so every group in the map has at least one value.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + `bins` * g), where g is the number of groups.
+    /// 
+    /// Space: O(|V| + `bins` * g).
+    pub fn v_attr_stats_by(&self, name: &str, key: &str, bins: usize) -> CrabNetsResult<HashMap<String, AttributeStats>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_attr_stats_by";
+        self.v_attr_stats_of(name, Some(key), bins, FUNCTION_PATH)
+    }
+
+    fn v_attr_stats_of(&self, name: &str, key: Option<&str>, bins: usize, function_path: &str) -> CrabNetsResult<HashMap<String, AttributeStats>> {
+        stats_by(self.iter_v().map(|x| self.v_attrs(&x)), name, key, bins, function_path)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * GROUPING                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Group vertices by attribute
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// 
+    /// ## Returns
+    /// * `HashMap<String, HashSet<VertexIdType>>` - maps the values of the attribute to
+    /// the IDs of vertices that have them.
+    /// 
+    /// ## Details
+    /// String values are used as they are, values of other  types  are  formatted  with
+    /// [`Debug`]. Vertices that don't have the attribute belong to no group.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|).
+    /// 
+    /// Space: O(|V|).
+    pub fn group_v_by_attr(&self, name: &str) -> HashMap<String, HashSet<VertexIdType>> {
+        let mut answer: HashMap<String, HashSet<VertexIdType>> = HashMap::new();
+        for id in self.iter_v() {
+            if let Some(value) = self.v_attrs(&id).unwrap().io_query_contents(name) {
+                answer.entry(group_key(value)).or_default().insert(id);
+            }
+        }
+        answer
+    }
+
+    /// # Quotient graph by attribute
+    /// 
+    /// ## Description
+    /// Collapse each group of vertices that share the value of  the  attribute  into  a
+    /// single vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` - `Ok(value)` is returned if the edges could  be  added
+    /// to the quotient graph, `value` in this case is the quotient graph;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// The quotient graph has one vertex for each group of  [`Graph::group_v_by_attr`],
+    /// the vertices are added in the ascending order of the keys of  their  groups  and
+    /// get automatic IDs and the attribute `name` with the  value  of  the  group.  Two
+    /// vertices of the quotient graph are connected if an edge connects the members  of
+    /// their groups, the edge is directed the same way, and edges that would repeat  an
+    /// existing one are skipped. Edges within groups and  edges  incident  to  vertices
+    /// that don't have the attribute are dropped.  Edges  of  the  quotient  graph  get
+    /// default attribute collections.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| + g log g), where g is the number of groups.
+    /// 
+    /// Space: O(|V| + |E|).
+    pub fn quotient_by_attr(&self, name: &str) -> CrabNetsResult<Self> {
+        let groups = self.group_v_by_attr(name);
+        let mut keys: Vec<&String> = groups.keys().collect();
+        keys.sort();
+        let mut answer = Self::new();
+        // The vertex of the quotient graph for each grouped vertex
+        let mut quotient_ids = HashMap::with_capacity(self.count_v());
+        for key in keys {
+            let quotient_id = answer.add_v(None);
+            let value = self.v_attrs(groups[key].iter().next().unwrap())?.io_query_contents(name).unwrap();
+            answer.v_attrs_mut(&quotient_id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value });
+            for id in groups[key].iter() {
+                quotient_ids.insert(id.clone(), quotient_id.clone());
+            }
+        }
+        let mut added_edges = HashSet::new();
+        for edge in self.iter_e() {
+            let (Some(id1), Some(id2)) = (quotient_ids.get(&edge.id1), quotient_ids.get(&edge.id2)) else {
+                continue;
+            };
+            if id1 == id2 {
+                continue;
+            }
+            let (id1, id2, directed) = match edge.direction {
+                EdgeDirection::Undirected => (id1.min(id2), id1.max(id2), false),
+                EdgeDirection::Directed1to2 => (id1, id2, true),
+                EdgeDirection::Directed2to1 => (id2, id1, true),
+            };
+            if added_edges.insert((id1.clone(), id2.clone(), directed)) {
+                answer.add_e(id1, id2, directed, None)?;
+            }
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * NEIGHBOURHOOD AGGREGATION                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Neighbour direction
+/// 
+/// ## Description
+/// The   neighbours   of   a    vertex    that    are    taken    into    account    by
+/// [`Graph::aggregate_neighbour_attr`] and [`Graph::aggregate_neighbour_attr_into`].
+/// 
+/// ## Variants
+/// * `All` - all adjacent vertices regardless of the direction of edges.
+/// * `In` - vertices connected with the given one by an incoming or an undirected edge.
+/// * `Out` - vertices connected with the given one by  an  outgoing  or  an  undirected
+/// edge.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NeighbourDirection {
+    All,
+    In,
+    Out,
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Aggregate attribute over neighbours
+    /// 
+    /// ## Description
+    /// Reduce the values of a numeric vertex attribute over the neighbours of a vertex,
+    /// e.g. compute their mean.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `direction` : `NeighbourDirection` - the neighbours to take into account.
+    /// * `reducer` : `ReducerType` -  a  function  that  receives  the  values  of  the
+    /// attribute of the neighbours and reduces them into one number.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<f64>>` - `Ok(Some(value))` is returned if at least  one
+    /// neighbour has the attribute, `value` in this case is the  result  of  `reducer`;
+    /// `Ok(None)` is returned if the vertex exists but none of its neighbours  has  the
+    /// attribute; `Err(_)` is returned if the vertex doesn't exist or a  value  of  the
+    /// attribute isn't numeric.
+    /// 
+    /// ## Details
+    /// Each neighbour contributes once, regardless of  the  number  of  parallel  edges
+    /// connecting it, and a vertex with a self-loop is its own neighbour. Values of all
+    /// numeric types are converted into `f64`, neighbours that don't have the attribute
+    /// and `f64::NAN` values are skipped, so `reducer` never receives an empty slice.
+    /// 
+    /// ## Generic type parameters
+    /// * `ReducerType: FnMut(&[f64]) -> f64` - the type of the reducer.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg(`id`)) in addition to the time of `reducer`.
+    /// 
+    /// Space: O(deg(`id`)).
+    pub fn aggregate_neighbour_attr<ReducerType>(&self, id: &VertexIdType, name: &str, direction: NeighbourDirection, mut reducer: ReducerType) -> CrabNetsResult<Option<f64>>
+    where
+        ReducerType: FnMut(&[f64]) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::aggregate_neighbour_attr";
+        let values = self.neighbour_values(id, name, direction, FUNCTION_PATH)?;
+        Ok((!values.is_empty()).then(|| reducer(&values)))
+    }
+
+    /// # Aggregate attribute over neighbours of all vertices
+    /// 
+    /// ## Description
+    /// Reduce the values of a numeric vertex attribute  over  the  neighbours  of  each
+    /// vertex and write the results into another (or the same) vertex attribute.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `source` : `&str` - the name of the attribute to aggregate.
+    /// * `target` : `&str` - the name of the attribute to write the results into.
+    /// * `direction` : `NeighbourDirection` - the neighbours to take into account.
+    /// * `reducer` : `ReducerType` -  a  function  that  receives  the  values  of  the
+    /// attribute of the neighbours and reduces them into one number.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if  all  values  of  `source`  are
+    /// numbers; `Err(_)` is returned otherwise, the attributes of the  graph  are  left
+    /// intact in this case.
+    /// 
+    /// ## Details
+    /// The neighbourhoods are aggregated like [`Graph::aggregate_neighbour_attr`] does.
+    /// All results are computed before any of them is written, so `target` may coincide
+    /// with `source`. Vertices whose neighbours don't have `source` don't get `target`,
+    /// the others get `target` of type `f64`.
+    /// 
+    /// ## Generic type parameters
+    /// * `ReducerType: FnMut(&[f64]) -> f64` - the type of the reducer.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|) in addition to the time of `reducer`.
+    /// 
+    /// Space: O(|V| + max deg).
+    pub fn aggregate_neighbour_attr_into<ReducerType>(&mut self, source: &str, target: &str, direction: NeighbourDirection, mut reducer: ReducerType) -> CrabNetsResult<()>
+    where
+        ReducerType: FnMut(&[f64]) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::aggregate_neighbour_attr_into";
+        let mut results = Vec::new();
+        for id in self.iter_v() {
+            let values = self.neighbour_values(&id, source, direction, FUNCTION_PATH)?;
+            if !values.is_empty() {
+                results.push((id, reducer(&values)));
+            }
+        }
+        for (id, value) in results {
+            self.v_attrs_mut(&id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name: target, value: StaticDispatchAttributeValue::Float64(value) });
+        }
+        Ok(())
+    }
+
+    fn neighbour_values(&self, id: &VertexIdType, name: &str, direction: NeighbourDirection, function_path: &str) -> CrabNetsResult<Vec<f64>> {
+        let neighbours: Box<dyn Iterator<Item = VertexIdType>> = match direction {
+            NeighbourDirection::All => self.iter_adjacent(id)?,
+            NeighbourDirection::In => Box::new(self.iter_adjacent_in(id)?.chain(self.iter_adjacent_undir(id)?).collect::<HashSet<_>>().into_iter()),
+            NeighbourDirection::Out => Box::new(self.iter_adjacent_out(id)?.chain(self.iter_adjacent_undir(id)?).collect::<HashSet<_>>().into_iter()),
+        };
+        let mut answer = Vec::new();
+        for neighbour in neighbours {
+            if let Some(value) = numeric_value(self.v_attrs(&neighbour)?, name, function_path)? {
+                answer.push(value);
+            }
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn attr_stats() {
+        let g: graph!(A ---A--- A) = "AV F8 Score\nAV S Team\nAE I4 Weight\n\nV 0 1.0 \"a\"\nV 1 2.0 \"a\"\nV 2 3.0 \"b\"\nV 3 6.0 \"b\"\n\nE 0 1 5\nE 1 2 5\nE 2 3 8\n".parse().unwrap();
+        let stats = g.v_attr_stats("Score", 2).unwrap().unwrap();
+        assert_eq!((stats.count, stats.min, stats.max, stats.mean, stats.variance), (4, 1.0, 6.0, 3.0, 3.5));
+        assert_eq!(stats.histogram, vec![3, 1]);
+        let groups = g.v_attr_stats_by("Score", "Team", 1).unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!((groups["a"].count, groups["a"].mean, groups["a"].variance, groups["a"].histogram.clone()), (2, 1.5, 0.25, vec![2]));
+        assert_eq!((groups["b"].min, groups["b"].max, groups["b"].mean), (3.0, 6.0, 4.5));
+        let stats = g.e_attr_stats("Weight", 3).unwrap().unwrap();
+        assert_eq!((stats.count, stats.min, stats.max, stats.mean, stats.histogram), (3, 5.0, 8.0, 6.0, vec![2, 0, 1]));
+        let groups = g.e_attr_stats_by("Weight", "Weight", 4).unwrap();
+        assert_eq!((groups["5"].count, groups["5"].variance, groups["5"].histogram.clone()), (2, 0.0, vec![2, 0, 0, 0]));
+        assert_eq!(g.v_attr_stats("Age", 2).unwrap(), None);
+        assert!(g.v_attr_stats_by("Score", "Age", 2).unwrap().is_empty());
+        assert!(g.v_attr_stats("Team", 2).is_err() && g.v_attr_stats("Score", 0).is_err());
+    }
+
+    #[test]
+    fn group_v_by_attr() {
+        let g: graph!(A ===A==> A) = "AV S Team\n\nV 0 \"a\"\nV 1 \"a\"\nV 2 \"b\"\nV 3 \"b\"\nV 4 \"c\"\n\nE 0 1\nA 1 2\nA 0 3\nE 2 3\nA 3 0\nE 2 4\nE 4 2\n".parse().unwrap();
+        let groups = g.group_v_by_attr("Team");
+        assert_eq!(groups.len(), 3);
+        assert_eq!((groups["a"].clone(), groups["b"].clone(), groups["c"].clone()), (HashSet::from([0, 1]), HashSet::from([2, 3]), HashSet::from([4])));
+        assert!(g.group_v_by_attr("Age").is_empty());
+        let h = g.quotient_by_attr("Team").unwrap();
+        assert_eq!((h.count_v(), h.count_e()), (3, 3));
+        assert_eq!((0..3).map(|x| h.v_attrs(&x).unwrap().io_query_contents("Team").unwrap().as_f64()).collect::<Vec<_>>(), vec![None; 3]);
+        assert_eq!(h.group_v_by_attr("Team"), HashMap::from([(String::from("a"), HashSet::from([0])), (String::from("b"), HashSet::from([1])), (String::from("c"), HashSet::from([2]))]));
+        let mut edges: Vec<_> = h.iter_e().map(|x| (x.id1, x.id2, x.direction)).collect();
+        edges.sort_by_key(|x| (x.0, x.1));
+        assert_eq!(edges, vec![(0, 1, EdgeDirection::Directed1to2), (1, 0, EdgeDirection::Directed1to2), (1, 2, EdgeDirection::Undirected)]);
+        assert_eq!(g.quotient_by_attr("Age").unwrap().count_v(), 0);
+    }
+
+    #[test]
+    fn aggregate_neighbour_attr() {
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for score in [Some(1.0), Some(2.0), Some(4.0), Some(f64::NAN), None] {
+            let id = g.add_v(None);
+            if let Some(score) = score {
+                g.v_attrs_mut(&id).unwrap().insert(String::from("Score"), Box::new(score));
+            }
+        }
+        g.v_attrs_mut(&0).unwrap().insert(String::from("Team"), Box::new(String::from("a")));
+        for (id1, id2, directed) in [(0, 1, true), (0, 1, true), (2, 0, true), (0, 3, false), (3, 4, false)] {
+            assert!(g.add_e(&id1, &id2, directed, None).is_ok());
+        }
+        let mean = |x: &[f64]| x.iter().sum::<f64>() / x.len() as f64;
+        assert_eq!(g.aggregate_neighbour_attr(&0, "Score", NeighbourDirection::All, mean).unwrap(), Some(3.0));
+        assert_eq!(g.aggregate_neighbour_attr(&0, "Score", NeighbourDirection::Out, |x| x.len() as f64).unwrap(), Some(1.0));
+        assert_eq!(g.aggregate_neighbour_attr(&0, "Score", NeighbourDirection::In, mean).unwrap(), Some(4.0));
+        assert_eq!(g.aggregate_neighbour_attr(&3, "Score", NeighbourDirection::All, mean).unwrap(), Some(1.0));
+        assert_eq!(g.aggregate_neighbour_attr(&4, "Score", NeighbourDirection::All, mean).unwrap(), None);
+        assert!(g.aggregate_neighbour_attr(&5, "Score", NeighbourDirection::All, mean).is_err());
+        assert!(g.aggregate_neighbour_attr(&1, "Team", NeighbourDirection::All, mean).is_err());
+        g.aggregate_neighbour_attr_into("Score", "Score", NeighbourDirection::All, mean).unwrap();
+        let score = |x: usize| g.v_attrs(&x).unwrap().io_query_contents("Score").and_then(|y| y.as_f64());
+        assert_eq!((0..5).map(score).collect::<Vec<_>>(), vec![Some(3.0), Some(1.0), Some(1.0), Some(1.0), None]);
+        assert!(g.aggregate_neighbour_attr_into("Team", "Mean", NeighbourDirection::All, mean).is_err());
+        assert!(g.v_attrs(&0).unwrap().io_query_contents("Mean").is_none());
+    }
+}
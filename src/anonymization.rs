@@ -0,0 +1,220 @@
+//! # Anonymization module
+//! 
+//! ## Description
+//! This module contains [`Graph::anonymize`] that builds a copy of the  graph  safe  to
+//! share: its vertex IDs are randomly permuted and its attributes are either removed or
+//! replaced with their digests, while the topology stays intact.
+//! 
+//! [`Graph::anonymize`]: crate::Graph::anonymize
+use std::{collections::{hash_map::DefaultHasher, HashSet}, fmt::Debug, hash::{Hash, Hasher}};
+use rand::{seq::SliceRandom, Rng};
+use crate::{attributes::StaticDispatchAttributeValue, conversions::IdMapping, io::{AttributeCollectionIO, AttributeToken}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Anonymization policy
+/// 
+/// ## Description
+/// Defines what [`Graph::anonymize`] does with the attributes of vertices and edges.
+/// 
+/// ## Variants
+/// * `Strip` - all attributes are removed.
+/// * `Hash` - the names of attributes are kept, and each value  is  replaced  with  the
+/// hexadecimal string of its salted 64-bit digest, so  that  equal  values  still  have
+/// equal digests.
+/// 
+/// [`Graph::anonymize`]: crate::Graph::anonymize
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnonymizationPolicy {
+    Strip,
+    Hash,
+}
+
+
+
+// Debug representations of the elements of a set in a fixed order
+fn sorted_debug<ElementType>(set: &HashSet<ElementType>) -> String
+where
+    ElementType: Debug,
+{
+    let mut elements: Vec<String> = set.iter().map(|x| format!("{:?}", x)).collect();
+    elements.sort();
+    elements.join(",")
+}
+
+// Salted digest of the value, independent of the iteration order of sets
+fn digest(value: &StaticDispatchAttributeValue, salt: u64) -> StaticDispatchAttributeValue {
+    let text = match value {
+        StaticDispatchAttributeValue::SetInt8(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetInt16(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetInt32(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetInt64(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetUInt8(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetUInt16(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetUInt32(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetUInt64(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetBool(x) => sorted_debug(x),
+        StaticDispatchAttributeValue::SetStr(x) => sorted_debug(x),
+        _ => format!("{:?}", value),
+    };
+    let mut hasher = DefaultHasher::new();
+    salt.hash(&mut hasher);
+    text.hash(&mut hasher);
+    StaticDispatchAttributeValue::Str(format!("{:016x}", hasher.finish()))
+}
+
+// New attribute collection made out of the given one according to the policy
+fn anonymize_attrs<AttributeCollectionType, EdgeIdType>(collection: &AttributeCollectionType, policy: AnonymizationPolicy, salt: u64) -> AttributeCollectionType
+where
+    AttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+{
+    let mut answer = AttributeCollectionType::new();
+    if policy == AnonymizationPolicy::Hash {
+        for token in collection.io_iter_contents() {
+            answer.io_reader_callback::<EdgeIdType, usize>(AttributeToken { name: token.name, value: digest(&token.value, salt) });
+        }
+    }
+    answer
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ANONYMIZATION                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Anonymize graph
+    /// 
+    /// ## Description
+    /// Build a copy of the  caller  with  randomly  permuted  vertex  IDs  and  without
+    /// readable attributes.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `policy` : `AnonymizationPolicy` - defines what happens to the  attributes  of
+    /// vertices and edges.
+    /// * `rng` : `&mut RngType` - a mutable reference to the random number generator.
+    /// 
+    /// ## Returns
+    /// * `(Graph, IdMapping<VertexIdType>)` - a new graph with the same topology as the
+    /// caller and the secret mapping from the vertex IDs of the caller  to  the  vertex
+    /// IDs of the new graph.
+    /// 
+    /// ## Details
+    /// The new vertex IDs are a random permutation of the numbers from 0 to |V| - 1, so
+    /// neither the old IDs nor their order can be recovered without the mapping.  Edges
+    /// are added in a random  order  and  get  new  IDs  for  the  same  reason,  their
+    /// directions are kept.
+    /// 
+    /// With [`AnonymizationPolicy::Hash`], all digests are salted with a  number  drawn
+    /// from `rng`, so equal values are recognisable within  one  anonymized  graph  but
+    /// can't be matched  against  digests  of  guessed  values.  The  digests  are  not
+    /// cryptographic, and distributions of values  (e.g.  how  many  vertices  share  a
+    /// value) are still visible.
+    /// 
+    /// The kind of the new graph  is  defined  by  `NewLocaleType`,  which  is  usually
+    /// inferred from the type annotation of the variable  the  result  is  assigned  to
+    /// (e.g. with the help of [`graph!`][graph]). It is expected  to  be  of  the  same
+    /// [kind][kinds] as the caller.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|) plus the time to hash the attributes.
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [graph]: crate::graph
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn anonymize<NewLocaleType, RngType>(&self, policy: AnonymizationPolicy, rng: &mut RngType) -> (Graph<EdgeAttributeCollectionType, EdgeIdType, NewLocaleType, VertexAttributeCollectionType, usize>, IdMapping<VertexIdType>)
+    where
+        NewLocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, usize>,
+        RngType: Rng,
+    {
+        // Vertices and edges are sorted first, so that the same generator always gives
+        // the same result
+        let salt: u64 = rng.gen();
+        let mut old_ids: Vec<VertexIdType> = self.iter_v().collect();
+        old_ids.sort();
+        let mut new_ids: Vec<usize> = (0..old_ids.len()).collect();
+        new_ids.shuffle(rng);
+        let mapping: IdMapping<VertexIdType> = old_ids.into_iter().zip(new_ids).collect();
+        let mut answer = Graph::new();
+        for (old_id, new_id) in mapping.iter() {
+            answer.add_v(Some(*new_id));
+            *answer.v_attrs_mut(new_id).unwrap() = anonymize_attrs::<_, EdgeIdType>(self.v_attrs(old_id).unwrap(), policy, salt);
+        }
+        let mut edges: Vec<_> = self.iter_e().collect();
+        edges.sort_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id)));
+        edges.shuffle(rng);
+        for edge in edges {
+            let (source, target, directed) = match edge.direction {
+                EdgeDirection::Directed2to1 => (mapping[&edge.id2], mapping[&edge.id1], true),
+                EdgeDirection::Directed1to2 => (mapping[&edge.id1], mapping[&edge.id2], true),
+                EdgeDirection::Undirected => (mapping[&edge.id1], mapping[&edge.id2], false),
+            };
+            let edge_id = answer.add_e(&source, &target, directed, None).unwrap();
+            *answer.e_attrs_mut(&source, &target, &edge_id).unwrap() = anonymize_attrs::<_, EdgeIdType>(self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap(), policy, salt);
+        }
+        (answer, mapping)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::{io::AttributeCollectionIO, rng::CrabNetsRng};
+    use super::*;
+
+    #[test]
+    fn anonymize() {
+        let mut g: graph!(A ---A--> A with VertexIdType = u32) = Graph::new();
+        for (id, city) in [(10, "Paris"), (20, "Paris"), (30, "Oslo")] {
+            g.add_v(Some(id));
+            g.v_attrs_mut(&id).unwrap().insert(String::from("City"), Box::new(String::from(city)));
+        }
+        g.add_e(&10, &20, true, None).unwrap();
+        g.add_e(&20, &30, true, None).unwrap();
+        let (h, mapping): (graph!(A ---A--> A), _) = g.anonymize(AnonymizationPolicy::Hash, &mut CrabNetsRng::new(3));
+        let mut new_ids: Vec<usize> = mapping.values().copied().collect();
+        new_ids.sort();
+        assert_eq!(new_ids, vec![0, 1, 2]);
+        assert_eq!(h.count_e(), 2);
+        assert!(h.iter_e().any(|x| x.direction == EdgeDirection::Directed1to2 && x.id1 == mapping[&10] && x.id2 == mapping[&20]));
+        assert!(h.iter_e().any(|x| x.direction == EdgeDirection::Directed1to2 && x.id1 == mapping[&20] && x.id2 == mapping[&30]));
+        let city = |id| h.v_attrs(&mapping[&id]).unwrap().io_query_contents("City").map(|x| format!("{:?}", x)).unwrap();
+        assert_eq!(city(10), city(20));
+        assert_ne!(city(10), city(30));
+        assert!(!city(10).contains("Paris"));
+        let (h, mapping): (graph!(A ---A--> A), _) = g.anonymize(AnonymizationPolicy::Strip, &mut CrabNetsRng::new(3));
+        assert!(h.v_attrs(&mapping[&10]).unwrap().io_query_contents("City").is_none());
+        let (other, other_mapping): (graph!(A ---A--> A), _) = g.anonymize(AnonymizationPolicy::Strip, &mut CrabNetsRng::new(3));
+        assert_eq!(mapping, other_mapping);
+        let edges = |x: &graph!(A ---A--> A)| x.iter_e().map(|x| (x.id1, x.id2, x.edge_id)).collect::<HashSet<_>>();
+        assert_eq!(edges(&other), edges(&h));
+    }
+}
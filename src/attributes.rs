@@ -1,5 +1,7 @@
-use std::{any::{Any, TypeId}, collections::{HashMap, HashSet, hash_map::Iter}, fmt::Debug, hash::Hash};
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{any::{Any, TypeId}, fmt::Debug, hash::Hash};
 use dyn_clone::{DynClone, clone_trait_object};
+use crate::{hash_map::Iter, HashMap, HashSet};
 
 
 
@@ -89,7 +91,7 @@ macro_rules! define_static_dispatch_attribute_value_enum {
         }
 
         impl Debug for StaticDispatchAttributeValue {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 f.write_str(match self {
                     $(StaticDispatchAttributeValue::$variant_name(value) => format!("{:?}", value),)+
                 }.as_ref())
@@ -112,6 +114,33 @@ define_static_dispatch_attribute_value_enum!(
     SetBool(HashSet<bool>), SetStr(HashSet<String>)
 );
 
+// StaticDispatchAttributeValue::StaticDispatchAttributeValue
+impl StaticDispatchAttributeValue {
+    /// # Numeric value
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<f64>` - `Some(value)` is returned if the caller is a  number,  `value`
+    /// in this case is the number converted into `f64`; `None` is returned otherwise.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            StaticDispatchAttributeValue::Int8(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::Int16(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::Int32(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::Int64(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::UInt8(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::UInt16(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::UInt32(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::UInt64(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::Float32(x) => Some(*x as f64),
+            StaticDispatchAttributeValue::Float64(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
 
 
 
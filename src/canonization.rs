@@ -0,0 +1,500 @@
+//! # Canonization module
+//! 
+//! ## Description
+//! This module contains [`Canonization`] trait and its  implementation  for  [`Graph`].
+//! Besides canonical forms, the trait gives the automorphism group of a graph  and  the
+//! orbits of its vertices.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::CrabNetsResult, execution::ExecutionControl, BasicImmutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Canonical form
+/// 
+/// ## Description
+/// The structure of a graph with its vertices renumbered in the  canonical  order.  Two
+/// graphs have equal canonical forms if and only if they are isomorphic,  so  canonical
+/// forms may be compared, hashed and stored in sets to find duplicate graphs.
+/// 
+/// ## Fields
+/// * `count_v` - the number of vertices.
+/// * `edges` - the sorted list of  edges  given  as  `(number1, number2, is_directed)`,
+/// where the numbers are the  positions  of  the  endpoints  in  the  canonical  order;
+/// directed  edges  go   from   `number1`   to   `number2`,   undirected   edges   have
+/// `number1 <= number2`. Parallel edges appear as many times as they are present in the
+/// graph.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct CanonicalForm {
+    pub count_v: usize,
+    pub edges: Vec<(usize, usize, bool)>,
+}
+
+
+
+// A leaf of the search tree, `labelling[v]` is the position of vertex `v` in the order
+// given by the leaf, `path` is the sequence of individualised vertices leading to it
+#[derive(Clone)]
+struct Leaf {
+    edges: Vec<(usize, usize, bool)>,
+    labelling: Vec<usize>,
+    path: Vec<usize>,
+}
+
+
+
+// Individualisation-refinement search over vertices numbered from 0, `adjacent[v]` holds
+// pairs (neighbour, kind), where kind is 0 for undirected edges, 1 for edges going from
+// `v` and 2 for edges coming to `v`
+pub(crate) struct Canonizer<'a> {
+    adjacent: Vec<Vec<(usize, usize)>>,
+    pub(crate) automorphisms: Vec<Vec<usize>>,
+    best: Option<Leaf>,
+    control: &'a ExecutionControl,
+    edges: Vec<(usize, usize, bool)>,
+    first: Option<Leaf>,
+    function_path: &'a str,
+}
+
+impl<'a> Canonizer<'a> {
+    pub(crate) fn new(count_v: usize, edges: Vec<(usize, usize, bool)>, control: &'a ExecutionControl, function_path: &'a str) -> Self {
+        let mut adjacent = vec![Vec::new(); count_v];
+        for &(i, j, is_directed) in edges.iter() {
+            if is_directed {
+                adjacent[i].push((j, 1));
+                adjacent[j].push((i, 2));
+            } else {
+                adjacent[i].push((j, 0));
+                if i != j {
+                    adjacent[j].push((i, 0));
+                }
+            }
+        }
+        Canonizer { adjacent, automorphisms: Vec::new(), best: None, control, edges, first: None, function_path }
+    }
+
+    // Returns the canonical form and the vertices in the canonical order
+    pub(crate) fn run(&mut self) -> CrabNetsResult<(CanonicalForm, Vec<usize>)> {
+        let count_v = self.adjacent.len();
+        let partition = if count_v == 0 { Vec::new() } else { vec![(0..count_v).collect()] };
+        self.search(partition, &mut Vec::new())?;
+        let best = self.best.take().unwrap();
+        let mut order = vec![0; count_v];
+        for (v, label) in best.labelling.into_iter().enumerate() {
+            order[label] = v;
+        }
+        Ok((CanonicalForm { count_v, edges: best.edges }, order))
+    }
+
+    // Split the cells until each of them is equitable, i.e. all vertices of a cell have the
+    // same numbers of neighbours of each kind in every cell
+    fn refine(&self, mut partition: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        let mut splitter = 0usize;
+        while splitter < partition.len() {
+            let mut counts = vec![[0usize; 3]; self.adjacent.len()];
+            for &v in partition[splitter].iter() {
+                for &(u, kind) in self.adjacent[v].iter() {
+                    counts[u][kind] += 1;
+                }
+            }
+            let cells_count = partition.len();
+            partition = partition.into_iter().flat_map(|mut cell| {
+                cell.sort_by_key(|x| counts[*x]);
+                cell.chunk_by(|x, y| counts[*x] == counts[*y]).map(|x| x.to_vec()).collect::<Vec<_>>()
+            }).collect();
+            splitter = if partition.len() > cells_count { 0 } else { splitter + 1 };
+        }
+        partition
+    }
+
+    // Returns the level of the search tree to return to
+    fn search(&mut self, partition: Vec<Vec<usize>>, path: &mut Vec<usize>) -> CrabNetsResult<usize> {
+        self.control.check(self.function_path)?;
+        let partition = self.refine(partition);
+        let Some(target) = partition.iter().position(|x| x.len() > 1) else {
+            return Ok(self.process_leaf(&partition, path));
+        };
+        let level = path.len();
+        let mut explored: Vec<usize> = Vec::new();
+        for &v in partition[target].iter() {
+            if self.is_equivalent_to_any(v, &explored, path) {
+                continue;
+            }
+            explored.push(v);
+            let mut next_partition = partition.clone();
+            next_partition[target].retain(|x| *x != v);
+            next_partition.insert(target, vec![v]);
+            path.push(v);
+            let jump_level = self.search(next_partition, path)?;
+            path.pop();
+            if jump_level < level {
+                return Ok(jump_level);
+            }
+        }
+        Ok(level)
+    }
+
+    // Automorphisms that fix the path map the subtree of `v` onto the subtree of its image,
+    // so only one vertex per orbit of these automorphisms needs to be explored
+    fn is_equivalent_to_any(&self, v: usize, explored: &[usize], path: &[usize]) -> bool {
+        if explored.is_empty() {
+            return false;
+        }
+        let orbits = orbits(self.adjacent.len(), self.automorphisms.iter().filter(|x| path.iter().all(|y| x[*y] == *y)));
+        explored.iter().any(|x| orbits[*x] == orbits[v])
+    }
+
+    fn process_leaf(&mut self, partition: &[Vec<usize>], path: &[usize]) -> usize {
+        let mut labelling = vec![0; self.adjacent.len()];
+        for (label, cell) in partition.iter().enumerate() {
+            labelling[cell[0]] = label;
+        }
+        let edges = self.edges.iter()
+            .map(|&(i, j, is_directed)| if is_directed { (labelling[i], labelling[j], true) } else { (labelling[i].min(labelling[j]), labelling[i].max(labelling[j]), false) })
+            .sorted()
+            .collect();
+        let leaf = Leaf { edges, labelling, path: path.to_vec() };
+        let Some(best) = self.best.as_ref() else {
+            self.first = Some(leaf.clone());
+            self.best = Some(leaf);
+            return path.len();
+        };
+        // Equal forms mean that some automorphism maps this leaf onto the earlier one, the
+        // subtrees containing them are then equivalent, and the current one can be left
+        let mut jump_level = path.len();
+        for other in [self.first.as_ref().unwrap(), best] {
+            if other.edges == leaf.edges {
+                let mut vertices = vec![0; leaf.labelling.len()];
+                for (v, label) in other.labelling.iter().enumerate() {
+                    vertices[*label] = v;
+                }
+                let automorphism = leaf.labelling.iter().map(|x| vertices[*x]).collect::<Vec<_>>();
+                if automorphism.iter().enumerate().any(|(x, y)| x != *y) && !self.automorphisms.contains(&automorphism) {
+                    self.automorphisms.push(automorphism);
+                }
+                jump_level = jump_level.min(leaf.path.iter().zip(other.path.iter()).take_while(|(x, y)| x == y).count());
+            }
+        }
+        if leaf.edges < best.edges {
+            self.best = Some(leaf);
+        }
+        jump_level
+    }
+}
+
+
+
+// Orbit of each vertex given by its smallest element
+pub(crate) fn orbits<'a, PermutationsType>(count_v: usize, permutations: PermutationsType) -> Vec<usize>
+where
+    PermutationsType: Iterator<Item = &'a Vec<usize>>,
+{
+    let mut roots: Vec<usize> = (0..count_v).collect();
+    fn find_root(roots: &mut [usize], v: usize) -> usize {
+        if roots[v] != v {
+            roots[v] = find_root(roots, roots[v]);
+        }
+        roots[v]
+    }
+    for permutation in permutations {
+        for (v, u) in permutation.iter().enumerate() {
+            let (root1, root2) = (find_root(&mut roots, v), find_root(&mut roots, *u));
+            roots[root1.max(root2)] = root1.min(root2);
+        }
+    }
+    (0..count_v).map(|x| find_root(&mut roots, x)).collect()
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CANONIZATION                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Canonization
+/// 
+/// ## Description
+/// A canonical labelling orders the vertices of  a  graph  so  that  isomorphic  graphs
+/// become identical once their vertices are renumbered in  this  order.  It  allows  to
+/// check graphs for isomorphism, to remove duplicates from a collection of graphs in  a
+/// single pass, and to detect symmetric structures.
+/// 
+/// The same search finds the automorphisms of  the  graph,  i.e.  the  permutations  of
+/// vertices that preserve all edges. Vertices  that  are  mapped  onto  each  other  by
+/// automorphisms form an orbit and play the same role in the  graph,  which  allows  to
+/// consider only one vertex per orbit when enumerating structures up to symmetry.
+/// 
+/// Attributes,  IDs  of  vertices  and  IDs  of  edges  are  ignored.  Directions   and
+/// multiplicities of edges as well as self-loops are taken into account.
+/// 
+/// All functions check the given [`ExecutionControl`] at each node of the  search  tree
+/// and  return  an  error  of  kind  [`Cancelled`][cancelled]  if  the  computation  is
+/// cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait Canonization<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Automorphism group generators
+    /// 
+    /// ## Description
+    /// Find a set of automorphisms that generates the automorphism group of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<HashMap<VertexIdType, VertexIdType>>>`  -  `Ok(value)`  is
+    /// returned if the computation wasn't cancelled, `value` in this case contains  the
+    /// generators, each of which maps every vertex to its image; `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// The generators are the automorphisms found by [`Canonization::canonize`].  Every
+    /// automorphism of the graph is a composition of the generators, and  the  identity
+    /// is never among them, so the empty list means that the graph is asymmetric.
+    /// 
+    /// ## Complexity
+    /// See [`Canonization::canonize`].
+    fn automorphism_generators(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<HashMap<VertexIdType, VertexIdType>>>;
+    /// # Canonize
+    /// 
+    /// ## Description
+    /// Find the canonical form of the graph and the canonical order of its vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<(CanonicalForm, Vec<VertexIdType>)>` - `Ok((form, order))`  is
+    /// returned if the computation  wasn't  cancelled,  `form`  in  this  case  is  the
+    /// canonical form of the graph, and `order` contains all vertices in the  canonical
+    /// order, i.e. the vertex `order[i]` has number `i` in `form`; `Err(_)` is returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// This function follows the individualisation-refinement scheme of  McKay's  nauty
+    /// [[source](https://doi.org/10.1016/j.jsc.2013.09.003)]. The  vertices  are  split
+    /// into cells by refinement until all vertices of each cell have the  same  numbers
+    /// of neighbours of each kind in every cell. While some cell has several  vertices,
+    /// each of them is individualised in turn, i.e. moved into a separate cell, and the
+    /// refinement is repeated. Each branch  of  this  search  ends  with  an  order  of
+    /// vertices, and the order that gives the lexicographically smallest list of  edges
+    /// is canonical.
+    /// 
+    /// Whenever  two  branches  give  the  same  list  of  edges,  they  differ  by  an
+    /// automorphism of the graph. The automorphisms found this way are used to skip the
+    /// branches that are known to be equivalent to the explored ones.
+    /// 
+    /// If the graph has several automorphisms, the canonical order  isn't  unique,  and
+    /// this function returns one of the canonical orders. The canonical form is  always
+    /// unique.
+    /// 
+    /// ## Complexity
+    /// Time: O(l · |V|² · (|V| + |E|)), where l is the  number  of  explored  branches,
+    /// which is small for most graphs but exponential in |V| in the worst case.
+    /// 
+    /// Space: O(|V|² + |E|).
+    fn canonize(&self, control: &ExecutionControl) -> CrabNetsResult<(CanonicalForm, Vec<VertexIdType>)>;
+    /// # Vertex orbits
+    /// 
+    /// ## Description
+    /// Split the vertices into orbits of the automorphism group.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<Vec<VertexIdType>>>` -  `Ok(value)`  is  returned  if  the
+    /// computation wasn't cancelled, `value` in this case contains the orbits, each  of
+    /// them sorted, in ascending order of their smallest vertices; `Err(_)` is returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Two vertices belong to the same orbit if some automorphism maps one of them onto
+    /// the other.  The  orbits  are  obtained  by  applying  the  generators  given  by
+    /// [`Canonization::automorphism_generators`] until no orbit grows.
+    /// 
+    /// ## Complexity
+    /// See [`Canonization::canonize`].
+    fn vertex_orbits(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<Vec<VertexIdType>>>;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Vertices are numbered by their positions in ascending order of IDs, each edge is given
+    // as in `CanonicalForm::edges`
+    pub(crate) fn canonizer<'a>(&self, control: &'a ExecutionControl, function_path: &'a str) -> (Vec<VertexIdType>, Canonizer<'a>) {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        // Each edge is seen from both of its endpoints, so it is collected into a set
+        let mut edges = HashSet::new();
+        for edge in self.iter_e() {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            match edge.direction {
+                EdgeDirection::Directed1to2 => {
+                    edges.insert((i, j, true, edge.edge_id));
+                },
+                EdgeDirection::Directed2to1 => {
+                    edges.insert((j, i, true, edge.edge_id));
+                },
+                EdgeDirection::Undirected => {
+                    edges.insert((i.min(j), i.max(j), false, edge.edge_id));
+                },
+            }
+        }
+        let edges = edges.into_iter().map(|(i, j, is_directed, _)| (i, j, is_directed)).collect();
+        let canonizer = Canonizer::new(vertices.len(), edges, control, function_path);
+        (vertices, canonizer)
+    }
+}
+
+// Graph::Canonization
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Canonization<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn automorphism_generators(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<HashMap<VertexIdType, VertexIdType>>> {
+        const FUNCTION_PATH: &str = "Graph::Canonization::automorphism_generators";
+        let (vertices, mut canonizer) = self.canonizer(control, FUNCTION_PATH);
+        canonizer.run()?;
+        Ok(canonizer.automorphisms.into_iter().map(|x| vertices.iter().cloned().zip(x.into_iter().map(|y| vertices[y].clone())).collect()).collect())
+    }
+
+    fn canonize(&self, control: &ExecutionControl) -> CrabNetsResult<(CanonicalForm, Vec<VertexIdType>)> {
+        const FUNCTION_PATH: &str = "Graph::Canonization::canonize";
+        let (vertices, mut canonizer) = self.canonizer(control, FUNCTION_PATH);
+        let (form, order) = canonizer.run()?;
+        Ok((form, order.into_iter().map(|x| vertices[x].clone()).collect()))
+    }
+
+    fn vertex_orbits(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<Vec<VertexIdType>>> {
+        const FUNCTION_PATH: &str = "Graph::Canonization::vertex_orbits";
+        let (vertices, mut canonizer) = self.canonizer(control, FUNCTION_PATH);
+        canonizer.run()?;
+        let roots = orbits(vertices.len(), canonizer.automorphisms.iter());
+        // Roots are the smallest vertices of the orbits, and vertices are sorted
+        let mut answer = vec![Vec::new(); vertices.len()];
+        for (v, root) in roots.into_iter().enumerate() {
+            answer[root].push(vertices[v].clone());
+        }
+        answer.retain(|x| !x.is_empty());
+        Ok(answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn canonize() {
+        let control = ExecutionControl::new();
+        // The same directed 5-cycle with a chord, numbered in two different ways
+        let mut g1: graph!(X ---X--> X) = Graph::new();
+        let mut g2: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..5 {
+            g1.add_v(None);
+            g2.add_v(None);
+        }
+        let relabelling = [3, 0, 4, 1, 2];
+        for (id1, id2, is_directed) in [(0, 1, true), (1, 2, true), (2, 3, true), (3, 4, true), (4, 0, true), (0, 2, false)] {
+            g1.add_e(&id1, &id2, is_directed, None).unwrap();
+            g2.add_e(&relabelling[id1], &relabelling[id2], is_directed, None).unwrap();
+        }
+        let (form1, order1) = g1.canonize(&control).unwrap();
+        let (form2, order2) = g2.canonize(&control).unwrap();
+        assert_eq!(form1, form2);
+        assert_eq!(form1.edges.len(), 6);
+        assert_eq!(order1.iter().map(|x| relabelling[*x]).collect::<Vec<_>>(), order2);
+        g2.remove_e(&relabelling[4], &relabelling[0], &0).unwrap();
+        g2.add_e(&relabelling[0], &relabelling[4], true, None).unwrap();
+        assert_ne!(g2.canonize(&control).unwrap().0, form1);
+        // Highly symmetric graphs are handled quickly thanks to the automorphisms
+        let mut g3: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..30 {
+            g3.add_v(None);
+        }
+        assert_eq!(g3.canonize(&control).unwrap().0, CanonicalForm { count_v: 30, edges: Vec::new() });
+        control.cancel();
+        assert!(g1.canonize(&control).is_err());
+    }
+
+    #[test]
+    fn automorphisms() {
+        let control = ExecutionControl::new();
+        // A star with one longer ray
+        let mut g1: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..5 {
+            g1.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (0, 2), (0, 3), (1, 4)] {
+            g1.add_e(&id1, &id2, false, None).unwrap();
+        }
+        assert_eq!(g1.vertex_orbits(&control).unwrap(), vec![vec![0], vec![1], vec![2, 3], vec![4]]);
+        assert_eq!(g1.automorphism_generators(&control).unwrap(), vec![HashMap::from([(0, 0), (1, 1), (2, 3), (3, 2), (4, 4)])]);
+        // The smallest asymmetric graph
+        let mut g2: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..6 {
+            g2.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (1, 2), (2, 3), (3, 4), (1, 5), (2, 5)] {
+            g2.add_e(&id1, &id2, false, None).unwrap();
+        }
+        assert!(g2.automorphism_generators(&control).unwrap().is_empty());
+        assert_eq!(g2.vertex_orbits(&control).unwrap().len(), 6);
+        // Two directed 4-cycles
+        let mut g3: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..8 {
+            g3.add_v(None);
+        }
+        for i in 0..4 {
+            g3.add_e(&i, &((i + 1) % 4), true, None).unwrap();
+            g3.add_e(&(i + 4), &((i + 1) % 4 + 4), true, None).unwrap();
+        }
+        assert_eq!(g3.vertex_orbits(&control).unwrap(), vec![(0..8).collect::<Vec<_>>()]);
+        let generators = g3.automorphism_generators(&control).unwrap();
+        assert!(generators.iter().all(|x| g3.iter_e().all(|y| g3.contains_directed_e(&x[&y.id1], &x[&y.id2]))));
+        control.cancel();
+        assert!(g3.vertex_orbits(&control).is_err());
+    }
+}
@@ -0,0 +1,613 @@
+//! # Centrality module
+//! 
+//! ## Description
+//! This module contains [`Centrality`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::{cmp::Ordering, collections::{BinaryHeap, HashMap, HashSet, VecDeque}};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use crate::{attributes::AttributeCollection, errors::CrabNetsResult, execution::ExecutionControl, workspace::Workspace, BasicImmutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY FUNCTIONS                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Number of pivots for given accuracy
+/// 
+/// ## Description
+/// Compute  the  number  of  pivots  that  [`Centrality::approximate_betweenness`]  and
+/// [`Centrality::approximate_closeness`] need to reach the given accuracy.
+/// 
+/// ## Arguments
+/// * `vertex_count` : `usize` - the number of vertices in the graph.
+/// * `epsilon` : `f64` - the maximum allowed error.
+/// * `delta` : `f64` - the maximum allowed probability of failure.
+/// 
+/// ## Returns
+/// * `usize` - the number of pivots.
+/// 
+/// ## Details
+/// With this many pivots, the  error  of  each  estimate  is  at  most  `epsilon`  (for
+/// betweenness) or `epsilon` times the diameter of the graph (for the average  distance
+/// underlying closeness) for all vertices  simultaneously  with  probability  at  least
+/// `1 - delta`. The bound follows from Hoeffding's inequality and the union bound  over
+/// all vertices, it's ⌈ln(2 · `vertex_count` / `delta`) / (2 · `epsilon`²)⌉.
+pub fn pivot_sample_size(vertex_count: usize, epsilon: f64, delta: f64) -> usize {
+    ((2.0 * vertex_count.max(1) as f64 / delta).ln() / (2.0 * epsilon * epsilon)).ceil() as usize
+}
+
+
+
+// Entries of the bounded priority queue of top-k queries, the worst entry (the smallest
+// score, the largest ID among equal scores) goes first
+struct RankedItem<ScoreType, VertexIdType> {
+    id: VertexIdType,
+    score: ScoreType,
+}
+
+impl<ScoreType: PartialOrd, VertexIdType: Ord> PartialEq for RankedItem<ScoreType, VertexIdType> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<ScoreType: PartialOrd, VertexIdType: Ord> Eq for RankedItem<ScoreType, VertexIdType> {}
+
+impl<ScoreType: PartialOrd, VertexIdType: Ord> PartialOrd for RankedItem<ScoreType, VertexIdType> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<ScoreType: PartialOrd, VertexIdType: Ord> Ord for RankedItem<ScoreType, VertexIdType> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.score.partial_cmp(&self.score).unwrap_or(Ordering::Equal).then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+
+
+// The k items with the largest scores from the best to the worst, items whose scores
+// aren't comparable with themselves (i.e. NaN) are skipped
+fn top_k_of<ItemsType, ScoreType, VertexIdType>(items: ItemsType, k: usize) -> Vec<(VertexIdType, ScoreType)>
+where
+    ItemsType: Iterator<Item = (VertexIdType, ScoreType)>,
+    ScoreType: PartialOrd,
+    VertexIdType: Ord,
+{
+    let mut heap = BinaryHeap::with_capacity(k + 1);
+    for (id, score) in items.filter(|(_, score)| score.partial_cmp(score).is_some()) {
+        heap.push(RankedItem { id, score });
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec().into_iter().map(|x| (x.id, x.score)).collect()
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CENTRALITY                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Centrality
+/// 
+/// ## Description
+/// Centrality measures estimate how important each vertex is for the structure  of  the
+/// graph. All of them respect the directions of edges and ignore  the  multiplicity  of
+/// edges, each edge has length 1.
+/// 
+/// Exact measures need a breadth-first search from every  vertex,  i.e.  O(|V|  ·  |E|)
+/// time, which is infeasible for graphs with millions of  edges.  Approximate  measures
+/// only run the search from a random sample of pivots,  see  [`pivot_sample_size`]  for
+/// how many pivots are needed to reach the desired accuracy.
+/// 
+/// All functions check the given [`ExecutionControl`] after processing each  pivot  and
+/// return an error of kind [`Cancelled`][cancelled] if the computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait Centrality<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Approximate betweenness centrality
+    /// 
+    /// ## Description
+    /// Estimate betweenness centrality of all vertices using pivot sampling.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `samples` : `usize` - the number of pivots.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, f64>>` - `Ok(value)` is returned if  the
+    /// computation wasn't cancelled, `value` in this  case  maps  each  vertex  to  the
+    /// estimate of its [betweenness][betweenness]; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Pivots are  chosen  uniformly  without  replacement.  The  dependencies  of  all
+    /// vertices  on   each   pivot   are   accumulated   as   in   Brandes'   algorithm
+    /// [[source](https://doi.org/10.1016/j.socnet.2007.11.001)] and extrapolated to the
+    /// whole graph. If `samples` is at least |V|, the result is exact.
+    /// 
+    /// ## Complexity
+    /// Time: O(`samples` · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [betweenness]: Centrality::betweenness
+    fn approximate_betweenness<R>(&self, samples: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>
+    where
+        R: Rng;
+    /// # Approximate closeness centrality
+    /// 
+    /// ## Description
+    /// Estimate closeness centrality of all vertices using pivot sampling.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `samples` : `usize` - the number of pivots.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, f64>>` - `Ok(value)` is returned if  the
+    /// computation wasn't cancelled, `value` in this  case  maps  each  vertex  to  the
+    /// estimate of its [closeness][closeness]; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Pivots are chosen uniformly without replacement. Both the  average  distance  to
+    /// the reachable vertices and the fraction of reachable vertices are estimated from
+    /// the distances to the pivots  [[source](https://doi.org/10.7155/jgaa.00081)].  If
+    /// `samples` is at least |V|, the result is exact.
+    /// 
+    /// ## Complexity
+    /// Time: O(`samples` · (|V| + |E|)).
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [closeness]: Centrality::closeness
+    fn approximate_closeness<R>(&self, samples: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>
+    where
+        R: Rng;
+    /// # Betweenness centrality
+    /// 
+    /// ## Description
+    /// Compute betweenness centrality of all vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, f64>>` - `Ok(value)` is returned if  the
+    /// computation wasn't cancelled, `value` in this  case  maps  each  vertex  to  its
+    /// betweenness; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Betweenness of vertex `v` is the sum of fractions of shortest paths from `s`  to
+    /// `t` that pass through `v` over all ordered pairs of distinct  vertices  `s`  and
+    /// `t` different from `v`. The result is normalised by (|V| - 1) · (|V|  -  2),  so
+    /// that all values lie in [0, 1].
+    /// 
+    /// Uses Brandes' algorithm [[source](https://doi.org/10.1080/0022250X.2001.9990249)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn betweenness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>;
+    /// # Closeness centrality
+    /// 
+    /// ## Description
+    /// Compute closeness centrality of all vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, f64>>` - `Ok(value)` is returned if  the
+    /// computation wasn't cancelled, `value` in this  case  maps  each  vertex  to  its
+    /// closeness; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Closeness of vertex `v` is the inverse of the average distance from `v`  to  the
+    /// vertices reachable from it, multiplied by the fraction of  other  vertices  that
+    /// are reachable from `v` (Wasserman and Faust's variant for disconnected  graphs).
+    /// Vertices that reach no other vertices have closeness 0.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V|).
+    fn closeness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>;
+    /// # Edge betweenness centrality
+    /// 
+    /// ## Description
+    /// Compute betweenness centrality of all edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<(VertexIdType, VertexIdType), f64>>` - `Ok(value)`  is
+    /// returned if the computation wasn't cancelled, `value` in  this  case  maps  each
+    /// pair of adjacent vertices to the betweenness of the edges between them; `Err(_)`
+    /// is returned otherwise.
+    /// 
+    /// ## Details
+    /// Betweenness of an edge is the sum of fractions of shortest paths from `s` to `t`
+    /// that pass through the edge over all ordered pairs of distinct vertices  `s`  and
+    /// `t`. The result is normalised by |V| · (|V| - 1), so  that  all  values  lie  in
+    /// [0, 1]. Edges with high betweenness carry most of  the  shortest  paths  between
+    /// different parts of the graph, i.e. they are bridges or close to being ones.
+    /// 
+    /// As parallel edges are ignored, keys are pairs of vertices. A pair joined  by  an
+    /// undirected edge has the smaller ID first and the key gets the paths that  go  in
+    /// both directions. Otherwise, the pair is `(from, to)` for an arc from  `from`  to
+    /// `to`. Self-loops lie on no shortest path and are missing from the map.
+    /// 
+    /// Uses Brandes' algorithm [[source](https://doi.org/10.1016/j.socnet.2007.11.001)]
+    /// with the same breadth-first searches as [`Centrality::betweenness`].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn edge_betweenness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<(VertexIdType, VertexIdType), f64>>;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Vertices are sorted, so that the same seed always gives the same pivots
+    fn sample_pivots<R>(&self, samples: usize, rng: &mut R) -> Vec<VertexIdType>
+    where
+        R: Rng,
+    {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        if samples >= vertices.len() {
+            return vertices;
+        }
+        vertices.choose_multiple(rng, samples).cloned().collect()
+    }
+
+    fn iter_distinct_adjacent_out(&self, id: &VertexIdType) -> HashSet<VertexIdType> {
+        let locale = self.edge_list.get(id).unwrap();
+        locale.iter_adjacent_out().chain(locale.iter_adjacent_undir()).collect()
+    }
+
+    fn sampled_betweenness(&self, pivots: &[VertexIdType], control: &ExecutionControl, function_path: &str) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        let mut answer = self.brandes(pivots, None, control, function_path)?;
+        let vertex_count = self.count_v() as f64;
+        let scale = if vertex_count < 3f64 || pivots.is_empty() { 0f64 } else { vertex_count / pivots.len() as f64 / ((vertex_count - 1f64) * (vertex_count - 2f64)) };
+        answer.values_mut().for_each(|x| *x *= scale);
+        Ok(answer)
+    }
+
+    // Brandes' algorithm from the given pivots, returns the unnormalised betweenness of
+    // vertices and also accumulates that of edges if `edge_answer` is given, the keys
+    // of edges are described in `Centrality::edge_betweenness`
+    fn brandes(&self, pivots: &[VertexIdType], mut edge_answer: Option<&mut HashMap<(VertexIdType, VertexIdType), f64>>, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        let mut answer: HashMap<VertexIdType, f64> = self.iter_v().map(|x| (x, 0f64)).collect();
+        // Pairs of vertices joined by an undirected edge, the smaller ID goes first
+        let undirected_pairs: HashSet<(VertexIdType, VertexIdType)> = match edge_answer {
+            Some(_) => self.iter_e().filter(|x| x.direction == EdgeDirection::Undirected).map(|x| if x.id1 <= x.id2 { (x.id1, x.id2) } else { (x.id2, x.id1) }).collect(),
+            None => HashSet::new(),
+        };
+        for pivot in pivots {
+            control.check(function_path)?;
+            let mut order = Vec::with_capacity(self.count_v());
+            let mut predecessors: HashMap<VertexIdType, Vec<VertexIdType>> = HashMap::new();
+            let mut path_counts = HashMap::from([(pivot.clone(), 1f64)]);
+            let mut distances = HashMap::from([(pivot.clone(), 0usize)]);
+            let mut unvisited_vertices = VecDeque::from([pivot.clone()]);
+            while let Some(curr_vertex_id) = unvisited_vertices.pop_front() {
+                let next_distance = distances[&curr_vertex_id] + 1;
+                for adjacent_id in self.iter_distinct_adjacent_out(&curr_vertex_id) {
+                    if !distances.contains_key(&adjacent_id) {
+                        distances.insert(adjacent_id.clone(), next_distance);
+                        unvisited_vertices.push_back(adjacent_id.clone());
+                    }
+                    if distances[&adjacent_id] == next_distance {
+                        *path_counts.entry(adjacent_id.clone()).or_insert(0f64) += path_counts[&curr_vertex_id];
+                        predecessors.entry(adjacent_id).or_default().push(curr_vertex_id.clone());
+                    }
+                }
+                order.push(curr_vertex_id);
+            }
+            let mut dependencies: HashMap<VertexIdType, f64> = HashMap::with_capacity(order.len());
+            for id in order.into_iter().rev() {
+                let dependency = dependencies.get(&id).copied().unwrap_or(0f64);
+                for predecessor_id in predecessors.get(&id).into_iter().flatten() {
+                    let edge_dependency = path_counts[predecessor_id] / path_counts[&id] * (1f64 + dependency);
+                    *dependencies.entry(predecessor_id.clone()).or_insert(0f64) += edge_dependency;
+                    if let Some(edge_answer) = edge_answer.as_deref_mut() {
+                        let pair = if *predecessor_id <= id { (predecessor_id.clone(), id.clone()) } else { (id.clone(), predecessor_id.clone()) };
+                        let key = if undirected_pairs.contains(&pair) { pair } else { (predecessor_id.clone(), id.clone()) };
+                        *edge_answer.entry(key).or_insert(0f64) += edge_dependency;
+                    }
+                }
+                if id != *pivot {
+                    *answer.get_mut(&id).unwrap() += dependency;
+                }
+            }
+        }
+        Ok(answer)
+    }
+
+    fn sampled_closeness(&self, pivots: &[VertexIdType], control: &ExecutionControl, function_path: &str) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        // For each vertex: the number of pivots reached from it and the total distance to them
+        let mut reached: HashMap<VertexIdType, (usize, usize)> = HashMap::with_capacity(self.count_v());
+        let mut workspace = Workspace::with_capacity(self.handle_bound());
+        for pivot in pivots {
+            control.check(function_path)?;
+            self.bfs_in_workspace(std::slice::from_ref(pivot), false, &mut workspace, function_path)?;
+            for handle in workspace.iter_visited() {
+                let distance = workspace.distance(handle).unwrap() as usize;
+                if distance > 0 {
+                    let entry = reached.entry(self.v_id(handle).unwrap().clone()).or_insert((0, 0));
+                    entry.0 += 1;
+                    entry.1 += distance;
+                }
+            }
+        }
+        let mut answer = HashMap::with_capacity(self.count_v());
+        for id in self.iter_v() {
+            let other_pivot_count = pivots.len() - pivots.contains(&id) as usize;
+            let value = match reached.get(&id) {
+                Some((count, total_distance)) if other_pivot_count > 0 => (*count as f64 / *total_distance as f64) * (*count as f64 / other_pivot_count as f64),
+                _ => 0f64,
+            };
+            answer.insert(id, value);
+        }
+        Ok(answer)
+    }
+}
+
+// Graph::Centrality
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Centrality<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn approximate_betweenness<R>(&self, samples: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::Centrality::approximate_betweenness";
+        self.sampled_betweenness(&self.sample_pivots(samples, rng), control, FUNCTION_PATH)
+    }
+
+    fn approximate_closeness<R>(&self, samples: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::Centrality::approximate_closeness";
+        self.sampled_closeness(&self.sample_pivots(samples, rng), control, FUNCTION_PATH)
+    }
+
+    fn betweenness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        const FUNCTION_PATH: &str = "Graph::Centrality::betweenness";
+        self.sampled_betweenness(&self.iter_v().collect::<Vec<_>>(), control, FUNCTION_PATH)
+    }
+
+    fn closeness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        const FUNCTION_PATH: &str = "Graph::Centrality::closeness";
+        self.sampled_closeness(&self.iter_v().collect::<Vec<_>>(), control, FUNCTION_PATH)
+    }
+
+    fn edge_betweenness(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<(VertexIdType, VertexIdType), f64>> {
+        const FUNCTION_PATH: &str = "Graph::Centrality::edge_betweenness";
+        let mut answer = HashMap::new();
+        self.brandes(&self.iter_v().collect::<Vec<_>>(), Some(&mut answer), control, FUNCTION_PATH)?;
+        let vertex_count = self.count_v() as f64;
+        answer.values_mut().for_each(|x| *x /= vertex_count * (vertex_count - 1f64));
+        Ok(answer)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * TOP-K QUERIES                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Top vertices by score
+    /// 
+    /// ## Description
+    /// Find `k` vertices with the largest scores.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `k` : `usize` - the maximum number of vertices to return.
+    /// * `score_fn` : `ScoreFnType` - a function that receives the ID of a  vertex  and
+    /// returns its score, e.g. a lookup into the result of [`Centrality::betweenness`].
+    /// 
+    /// ## Returns
+    /// * `Vec<(VertexIdType, f64)>` -  at  most  `k`  vertices  with  their  scores  in
+    /// descending order of scores, vertices with equal scores follow in ascending order
+    /// of IDs.
+    /// 
+    /// ## Details
+    /// Only `k` best vertices seen so far are kept in a priority queue, so the function
+    /// is much cheaper than sorting all vertices when `k` is small. Vertices  with  NaN
+    /// scores are skipped.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log k).
+    /// 
+    /// Space: O(k).
+    pub fn top_k_by<ScoreFnType>(&self, k: usize, score_fn: ScoreFnType) -> Vec<(VertexIdType, f64)>
+    where
+        ScoreFnType: Fn(&VertexIdType) -> f64,
+    {
+        top_k_of(self.iter_v().map(|id| {
+            let score = score_fn(&id);
+            (id, score)
+        }), k)
+    }
+
+    /// # Top vertices by degree
+    /// 
+    /// ## Description
+    /// Find `k` vertices with the largest degrees, i.e. the hubs of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `k` : `usize` - the maximum number of vertices to return.
+    /// 
+    /// ## Returns
+    /// * `Vec<(VertexIdType, usize)>` - at most `k`  vertices  with  their  degrees  in
+    /// descending order of degrees, vertices with equal  degrees  follow  in  ascending
+    /// order of IDs.
+    /// 
+    /// ## Details
+    /// Degrees are computed by [`BasicImmutableGraph::v_degree`], i.e.  all  edges  are
+    /// counted  regardless  of  their  directions,  each  parallel  edge   is   counted
+    /// separately.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log k).
+    /// 
+    /// Space: O(k).
+    pub fn top_k_by_degree(&self, k: usize) -> Vec<(VertexIdType, usize)> {
+        top_k_of(self.iter_v().map(|id| {
+            let degree = self.v_degree(&id).unwrap();
+            (id, degree)
+        }), k)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn exact_centrality() {
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, false, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        let control = ExecutionControl::new();
+        let betweenness = g.betweenness(&control).unwrap();
+        assert_eq!(betweenness[&1], 2.0 / 6.0);
+        assert_eq!(betweenness[&0], 0.0);
+        let closeness = g.closeness(&control).unwrap();
+        assert_eq!(closeness[&1], 2.0 / 3.0);
+        assert_eq!(closeness[&0], (2.0 / 3.0) * (2.0 / 3.0));
+        assert_eq!(closeness[&3], 0.0);
+        control.cancel();
+        assert!(matches!(g.betweenness(&control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn edge_betweenness() {
+        // Triangles 0 - 1 - 2 and 3 - 4 - 5 joined by bridge 2 - 3, arc 5 -> 6
+        let g: graph!(X ===X==> X) = "V 0\nV 1\nV 2\nV 3\nV 4\nV 5\nV 6\n\nE 0 1\nE 1 2\nE 2 0\nE 0 1\nE 3 2\nE 3 4\nE 4 5\nE 5 3\nA 5 6\nE 6 6\n".parse().unwrap();
+        let control = ExecutionControl::new();
+        let betweenness = g.edge_betweenness(&control).unwrap();
+        assert_eq!(betweenness.len(), 8);
+        // Paths between the triangles in both directions and from the first triangle to
+        // vertex 6
+        assert_eq!(betweenness[&(2, 3)], 21.0 / 42.0);
+        assert_eq!(betweenness[&(5, 6)], 6.0 / 42.0);
+        assert_eq!(betweenness[&(0, 1)], 2.0 / 42.0);
+        assert_eq!(betweenness[&(3, 5)], 12.0 / 42.0);
+        assert!(!betweenness.contains_key(&(6, 5)) && !betweenness.contains_key(&(6, 6)));
+        let best = betweenness.iter().max_by(|x, y| x.1.total_cmp(y.1)).unwrap();
+        assert_eq!(*best.0, (2, 3));
+        control.cancel();
+        assert!(matches!(g.edge_betweenness(&control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn approximate_centrality() {
+        // Star with 30 leaves
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        let center = g.add_v(None);
+        for _ in 0..30 {
+            let leaf = g.add_v(None);
+            g.add_e(&leaf, &center, false, None).unwrap();
+        }
+        let control = ExecutionControl::new();
+        let mut rng = StdRng::seed_from_u64(42);
+        let exact = g.betweenness(&control).unwrap();
+        assert_eq!(g.approximate_betweenness(100, &mut rng, &control).unwrap(), exact);
+        let approximate = g.approximate_closeness(10, &mut rng, &control).unwrap();
+        assert_eq!(approximate[&center], 1.0);
+        assert!(approximate.iter().filter(|x| *x.0 != center).all(|x| *x.1 > 0.0 && *x.1 < 1.0));
+        assert!(pivot_sample_size(1000, 0.1, 0.1) > pivot_sample_size(1000, 0.2, 0.1));
+    }
+
+    #[test]
+    fn top_k() {
+        // Star with 5 leaves, the first two leaves are also connected to each other
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        let center = g.add_v(None);
+        for _ in 0..5 {
+            let leaf = g.add_v(None);
+            g.add_e(&leaf, &center, false, None).unwrap();
+        }
+        g.add_e(&1, &2, false, None).unwrap();
+        assert_eq!(g.top_k_by_degree(3), vec![(0, 5), (1, 2), (2, 2)]);
+        assert_eq!(g.top_k_by_degree(10).len(), 6);
+        assert!(g.top_k_by_degree(0).is_empty());
+        let scores = HashMap::from([(0, 0.5), (1, f64::NAN), (2, 3.0), (3, -1.0), (4, 3.0), (5, 1.0)]);
+        assert_eq!(g.top_k_by(3, |id| scores[id]), vec![(2, 3.0), (4, 3.0), (5, 1.0)]);
+        assert_eq!(g.top_k_by(10, |id| scores[id]).len(), 5);
+    }
+}
@@ -0,0 +1,489 @@
+//! # Communities module
+//! 
+//! ## Description
+//! This module contains [`Communities`] trait and  its  implementation  for  [`Graph`].
+//! Communities are groups of vertices that are densely  connected  to  each  other  and
+//! sparsely connected to the rest of the graph.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet, VecDeque};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, io::AttributeCollectionIO, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Partition
+/// 
+/// ## Description
+/// Partition of the vertices of the graph into communities.
+/// 
+/// ## Fields
+/// * `communities` - the communities, each of them is sorted, communities are sorted by
+/// their smallest vertices.
+/// * `modularity` - the modularity of the partition (see [`Communities::modularity`]).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Partition<VertexIdType> {
+    pub communities: Vec<Vec<VertexIdType>>,
+    pub modularity: f64,
+}
+
+
+
+// Relative tolerance used to treat betweenness values as equal
+const BETWEENNESS_TOLERANCE: f64 = 1e-9;
+
+// Smallest gain of the objective for which a vertex is moved to another community
+const GAIN_TOLERANCE: f64 = 1e-12;
+
+
+
+// Community of every vertex of a simple undirected graph over vertices numbered from 0,
+// communities are numbered in ascending order of their smallest vertices
+fn components_of(neighbours: &[HashSet<usize>]) -> (Vec<usize>, usize) {
+    let mut membership = vec![usize::MAX; neighbours.len()];
+    let mut count = 0;
+    for start in 0..neighbours.len() {
+        if membership[start] != usize::MAX {
+            continue;
+        }
+        membership[start] = count;
+        let mut queue = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            for &u in neighbours[v].iter() {
+                if membership[u] == usize::MAX {
+                    membership[u] = count;
+                    queue.push_back(u);
+                }
+            }
+        }
+        count += 1;
+    }
+    (membership, count)
+}
+
+
+
+// Betweenness of every edge `(u, v)`, `u < v`, of a simple undirected graph over
+// vertices numbered from 0, each unordered pair of vertices is counted twice
+fn edge_betweenness_of(neighbours: &[HashSet<usize>]) -> HashMap<(usize, usize), f64> {
+    let mut answer = HashMap::new();
+    for source in 0..neighbours.len() {
+        let mut order = Vec::new();
+        let mut predecessors = vec![Vec::new(); neighbours.len()];
+        let mut path_counts = vec![0f64; neighbours.len()];
+        let mut distances = vec![usize::MAX; neighbours.len()];
+        path_counts[source] = 1f64;
+        distances[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        while let Some(v) = queue.pop_front() {
+            for &u in neighbours[v].iter() {
+                if distances[u] == usize::MAX {
+                    distances[u] = distances[v] + 1;
+                    queue.push_back(u);
+                }
+                if distances[u] == distances[v] + 1 {
+                    path_counts[u] += path_counts[v];
+                    predecessors[u].push(v);
+                }
+            }
+            order.push(v);
+        }
+        let mut dependencies = vec![0f64; neighbours.len()];
+        for &v in order.iter().rev() {
+            for &u in predecessors[v].iter() {
+                let dependency = path_counts[u] / path_counts[v] * (1f64 + dependencies[v]);
+                *answer.entry((u.min(v), u.max(v))).or_insert(0f64) += dependency;
+                dependencies[u] += dependency;
+            }
+        }
+    }
+    answer
+}
+
+
+
+// Modularity of a partition of a simple undirected graph over vertices numbered from 0
+fn modularity_of(neighbours: &[HashSet<usize>], membership: &[usize], community_count: usize) -> f64 {
+    let double_edge_count = neighbours.iter().map(|x| x.len()).sum::<usize>() as f64;
+    if double_edge_count == 0f64 {
+        return 0f64;
+    }
+    let mut inner_degrees = vec![0usize; community_count];
+    let mut total_degrees = vec![0usize; community_count];
+    for (v, adjacent) in neighbours.iter().enumerate() {
+        inner_degrees[membership[v]] += adjacent.iter().filter(|u| membership[**u] == membership[v]).count();
+        total_degrees[membership[v]] += adjacent.len();
+    }
+    inner_degrees.into_iter().zip(total_degrees).map(|(inner, total)| inner as f64 / double_edge_count - (total as f64 / double_edge_count).powi(2)).sum()
+}
+
+
+
+// Communities of a simple undirected graph over vertices numbered from 0 found by
+// moving single vertices to adjacent communities while the weighted sum of modularity
+// and the fraction of the variance of `values` explained by the communities grows
+fn attributed_local_moving_of(neighbours: &[HashSet<usize>], values: &[f64], attribute_weight: f64, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<(Vec<usize>, usize)> {
+    let double_edge_count = neighbours.iter().map(|x| x.len()).sum::<usize>() as f64;
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    let total_sum_of_squares = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>();
+    let structure_scale = if double_edge_count > 0f64 { 1f64 - attribute_weight } else { 0f64 };
+    let attribute_scale = if total_sum_of_squares > 0f64 { attribute_weight / total_sum_of_squares } else { 0f64 };
+    let within_sum_of_squares = |size: usize, sum: f64, sum_of_squares: f64| if size == 0 { 0f64 } else { sum_of_squares - sum * sum / size as f64 };
+    let mut membership = (0..neighbours.len()).collect::<Vec<_>>();
+    let mut sizes = vec![1usize; neighbours.len()];
+    let mut sums = values.to_vec();
+    let mut sums_of_squares = values.iter().map(|x| x * x).collect::<Vec<_>>();
+    let mut total_degrees = neighbours.iter().map(|x| x.len() as f64).collect::<Vec<_>>();
+    loop {
+        control.check(function_path)?;
+        let mut is_moved = false;
+        for v in 0..neighbours.len() {
+            let (a, value, degree) = (membership[v], values[v], neighbours[v].len() as f64);
+            let links = neighbours[v].iter().map(|u| membership[*u]).counts();
+            let a_links = links.get(&a).copied().unwrap_or(0) as f64;
+            let a_within_loss = within_sum_of_squares(sizes[a], sums[a], sums_of_squares[a]) - within_sum_of_squares(sizes[a] - 1, sums[a] - value, sums_of_squares[a] - value * value);
+            let mut best = (GAIN_TOLERANCE, a);
+            for (b, b_links) in links.into_iter().filter(|(b, _)| *b != a).sorted() {
+                let modularity_gain = 2f64 * (b_links as f64 - a_links) / double_edge_count - 2f64 * degree * (total_degrees[b] - total_degrees[a] + degree) / double_edge_count.powi(2);
+                let b_within_gain = within_sum_of_squares(sizes[b] + 1, sums[b] + value, sums_of_squares[b] + value * value) - within_sum_of_squares(sizes[b], sums[b], sums_of_squares[b]);
+                let gain = structure_scale * modularity_gain - attribute_scale * (b_within_gain - a_within_loss);
+                if gain > best.0 {
+                    best = (gain, b);
+                }
+            }
+            let b = best.1;
+            if b != a {
+                (sizes[a], sums[a], sums_of_squares[a], total_degrees[a]) = (sizes[a] - 1, sums[a] - value, sums_of_squares[a] - value * value, total_degrees[a] - degree);
+                (sizes[b], sums[b], sums_of_squares[b], total_degrees[b]) = (sizes[b] + 1, sums[b] + value, sums_of_squares[b] + value * value, total_degrees[b] + degree);
+                membership[v] = b;
+                is_moved = true;
+            }
+        }
+        if !is_moved {
+            break;
+        }
+    }
+    // Number the communities in ascending order of their smallest vertices
+    let mut labels = HashMap::new();
+    for community in membership.iter_mut() {
+        let next_label = labels.len();
+        *community = *labels.entry(*community).or_insert(next_label);
+    }
+    Ok((membership, labels.len()))
+}
+
+
+
+// Partition over the original vertex IDs
+fn partition_of<VertexIdType>(vertices: &[VertexIdType], neighbours: &[HashSet<usize>], membership: &[usize], community_count: usize) -> Partition<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    let mut communities = vec![Vec::new(); community_count];
+    for (v, community) in membership.iter().enumerate() {
+        communities[*community].push(vertices[v].clone());
+    }
+    Partition { communities, modularity: modularity_of(neighbours, membership, community_count) }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * COMMUNITIES                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Communities
+/// 
+/// ## Description
+/// Community detection.
+/// 
+/// All functions work with the underlying simple undirected graph: directions of  edges
+/// are ignored, parallel edges count once, self-loops are ignored.
+pub trait Communities<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Attributed communities
+    /// 
+    /// ## Description
+    /// Find communities that are both densely connected and homogeneous with respect to
+    /// a numeric vertex attribute.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `values` : `&HashMap<VertexIdType, f64>` - the values of the attribute.
+    /// * `attribute_weight` : `f64` -  the  weight  of  attribute  homogeneity  in  the
+    /// objective, from 0 to 1.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Partition<VertexIdType>>` - `Ok(value)` is returned  if  every
+    /// vertex has a value, `attribute_weight` is between 0 and 1  and  the  computation
+    /// wasn't cancelled, `value` in this case  is  the  found  partition;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// The objective is (1 - `attribute_weight`) · Q + `attribute_weight` · H, where  Q
+    /// is the modularity of the partition (see [`Communities::modularity`])  and  H  is
+    /// the fraction of the variance of the values explained by the communities, i.e.  1
+    /// minus the ratio of the within-community sum of  squares  to  the  total  sum  of
+    /// squares. Both terms  are  at  most  1.  The  objective  is  pure  modularity  if
+    /// `attribute_weight` is 0, any partition  into  communities  of  equal  values  is
+    /// optimal if it is 1.
+    /// 
+    /// The objective is maximised by the local moving heuristic of the  Louvain  method
+    /// [[source](https://doi.org/10.1088/1742-5468/2008/10/P10008)]:   starting    with
+    /// single vertices, each vertex in turn is moved to  the  adjacent  community  that
+    /// increases the objective the most, until no move  increases  it.  The  result  is
+    /// deterministic. The `modularity` field of the result holds  Q  alone.  The  given
+    /// [`ExecutionControl`] is checked before each pass over the vertices.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|) per pass.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn attributed_communities(&self, values: &HashMap<VertexIdType, f64>, attribute_weight: f64, control: &ExecutionControl) -> CrabNetsResult<Partition<VertexIdType>>;
+    /// # Girvan–Newman communities
+    /// 
+    /// ## Description
+    /// Build the hierarchy of communities by repeatedly  removing  the  edge  with  the
+    /// highest betweenness.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<Partition<VertexIdType>>>` - `Ok(value)`  is  returned  if
+    /// the computation wasn't cancelled, `value` in this case is the  dendrogram,  i.e.
+    /// the partitions obtained each time the removal of an  edge  splits  a  community;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The first partition consists of the connected components of the graph, each next
+    /// partition splits one community of the previous one into two, the last  partition
+    /// consists of single vertices. The modularity of every partition is computed  with
+    /// respect to the original graph, the partition  with  the  highest  modularity  is
+    /// usually taken as the answer.
+    /// 
+    /// Edge betweenness is recomputed after  each  removal,  ties  are  broken  by  the
+    /// ascending order of the IDs of the endpoints. The given  [`ExecutionControl`]  is
+    /// checked before each  removal,  an  error  of  kind  [`Cancelled`][cancelled]  is
+    /// returned if the computation is cancelled.
+    /// 
+    /// Uses Girvan–Newman algorithm [[source](https://doi.org/10.1073/pnas.122653799)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · |E|²).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    fn girvan_newman(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<Partition<VertexIdType>>>;
+    /// # Modularity
+    /// 
+    /// ## Description
+    /// Compute the modularity of a partition of the vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `communities` : `&[Vec<VertexIdType>]` - the communities of the partition.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<f64>` - `Ok(value)` is returned if every vertex of  the  graph
+    /// belongs to exactly one community,  `value`  in  this  case  is  the  modularity;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Modularity is the fraction of edges inside communities minus its expected  value
+    /// in a random graph with the same degrees, i.e. the sum of l<sub>c</sub> /  |E|  -
+    /// (d<sub>c</sub> / 2|E|)² over all  communities  c,  where  l<sub>c</sub>  is  the
+    /// number of edges inside c and d<sub>c</sub> is the total degree of  the  vertices
+    /// of c. The modularity of a graph without edges is 0.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn modularity(&self, communities: &[Vec<VertexIdType>]) -> CrabNetsResult<f64>;
+}
+
+// Graph::Communities
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Communities<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn attributed_communities(&self, values: &HashMap<VertexIdType, f64>, attribute_weight: f64, control: &ExecutionControl) -> CrabNetsResult<Partition<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Communities::attributed_communities";
+        if !(0f64..=1f64).contains(&attribute_weight) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "attribute_weight".to_string(), reason: format!("The attribute weight must be between 0 and 1, {} given.", attribute_weight) }));
+        }
+        let (vertices, structure) = self.motif_structure();
+        let mut vertex_values = Vec::with_capacity(vertices.len());
+        for id in vertices.iter() {
+            match values.get(id) {
+                Some(value) if value.is_finite() => vertex_values.push(*value),
+                _ => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "values".to_string(), reason: format!("Vertex {} has no finite value.", id) })),
+            }
+        }
+        let (membership, community_count) = attributed_local_moving_of(&structure.neighbours, &vertex_values, attribute_weight, control, FUNCTION_PATH)?;
+        Ok(partition_of(&vertices, &structure.neighbours, &membership, community_count))
+    }
+
+    fn girvan_newman(&self, control: &ExecutionControl) -> CrabNetsResult<Vec<Partition<VertexIdType>>> {
+        const FUNCTION_PATH: &str = "Graph::Communities::girvan_newman";
+        let (vertices, structure) = self.motif_structure();
+        let mut neighbours = structure.neighbours.clone();
+        let (mut membership, mut community_count) = components_of(&neighbours);
+        let mut answer = vec![partition_of(&vertices, &structure.neighbours, &membership, community_count)];
+        loop {
+            control.check(FUNCTION_PATH)?;
+            let betweenness = edge_betweenness_of(&neighbours);
+            let highest = match betweenness.values().copied().reduce(f64::max) {
+                Some(value) => value,
+                None => break,
+            };
+            let (u, v) = betweenness.into_iter().filter(|(_, value)| *value >= highest * (1f64 - BETWEENNESS_TOLERANCE)).map(|(edge, _)| edge).min().unwrap();
+            neighbours[u].remove(&v);
+            neighbours[v].remove(&u);
+            let (next_membership, next_community_count) = components_of(&neighbours);
+            if next_community_count > community_count {
+                (membership, community_count) = (next_membership, next_community_count);
+                answer.push(partition_of(&vertices, &structure.neighbours, &membership, community_count));
+            }
+        }
+        Ok(answer)
+    }
+
+    fn modularity(&self, communities: &[Vec<VertexIdType>]) -> CrabNetsResult<f64> {
+        const FUNCTION_PATH: &str = "Graph::Communities::modularity";
+        let (vertices, structure) = self.motif_structure();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut membership = vec![usize::MAX; vertices.len()];
+        for (community, ids) in communities.iter().enumerate() {
+            for id in ids {
+                let index = *indices.get(id).ok_or_else(|| CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))?;
+                if membership[index] != usize::MAX {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "communities".to_string(), reason: format!("Vertex {} belongs to more than one community.", id) }));
+                }
+                membership[index] = community;
+            }
+        }
+        if let Some(index) = membership.iter().position(|x| *x == usize::MAX) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "communities".to_string(), reason: format!("Vertex {} belongs to no community.", vertices[index]) }));
+        }
+        Ok(modularity_of(&structure.neighbours, &membership, communities.len()))
+    }
+}
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Attributed communities by attribute
+    /// 
+    /// ## Description
+    /// Find communities that are both densely connected and homogeneous with respect to
+    /// a numeric vertex attribute of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `name` : `&str` - the name of the attribute.
+    /// * `attribute_weight` : `f64` -  the  weight  of  attribute  homogeneity  in  the
+    /// objective, from 0 to 1.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Partition<VertexIdType>>` - `Ok(value)` is returned  if  every
+    /// vertex has the attribute `name` of a numeric type, `attribute_weight` is between
+    /// 0 and 1 and the computation wasn't cancelled, `value` in this case is the  found
+    /// partition; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Communities::attributed_communities`].
+    pub fn attributed_communities_by_attribute(&self, name: &str, attribute_weight: f64, control: &ExecutionControl) -> CrabNetsResult<Partition<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::attributed_communities_by_attribute";
+        let mut values = HashMap::with_capacity(self.count_v());
+        for id in self.iter_v() {
+            match self.v_attrs(&id)?.io_query_contents(name).map(|value| value.as_f64()) {
+                Some(Some(value)) => values.insert(id, value),
+                Some(None) => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: name.to_string() })),
+                None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "name".to_string(), reason: format!("Vertex {} has no attribute {}.", id, name) })),
+            };
+        }
+        self.attributed_communities(&values, attribute_weight, control)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn girvan_newman() {
+        // Triangles 0 - 1 - 2 and 3 - 4 - 5 joined by bridge 2 - 3, isolated vertex 6
+        let g: graph!(X ---X--- X) = "V 0\nV 1\nV 2\nV 3\nV 4\nV 5\nV 6\n\nE 0 1\nE 1 2\nE 2 0\nE 2 3\nE 3 4\nE 4 5\nE 5 3\n".parse().unwrap();
+        let dendrogram = g.girvan_newman(&ExecutionControl::new()).unwrap();
+        assert_eq!(dendrogram.len(), 6);
+        assert_eq!(dendrogram[0].communities, vec![vec![0, 1, 2, 3, 4, 5], vec![6]]);
+        assert_eq!(dendrogram[1].communities, vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]);
+        assert_eq!(dendrogram[5].communities, (0..7).map(|x| vec![x]).collect::<Vec<_>>());
+        assert!((dendrogram[1].modularity - 5f64 / 14f64).abs() < 1e-12);
+        let best = dendrogram.iter().max_by(|a, b| a.modularity.total_cmp(&b.modularity)).unwrap();
+        assert_eq!(best, &dendrogram[1]);
+        for partition in dendrogram.iter() {
+            assert_eq!(g.modularity(&partition.communities).unwrap(), partition.modularity);
+        }
+        assert!(g.modularity(&[vec![0, 1, 2, 3, 4, 5]]).is_err());
+        assert!(g.modularity(&[vec![0, 1, 2, 3, 4, 5, 6], vec![6]]).is_err());
+        assert!(g.modularity(&[vec![0, 1, 2, 3, 4, 5, 6, 7]]).is_err());
+    }
+
+    #[test]
+    fn attributed_communities() {
+        // Triangles 0 - 1 - 2 and 3 - 4 - 5 joined by bridge 2 - 3, vertex 3 has the
+        // value of the first triangle
+        let g: graph!(A ---X--- A) = "AV F8 Income\n\nV 0 1.0\nV 1 1.0\nV 2 1.0\nV 3 1.0\nV 4 9.0\nV 5 9.0\n\nE 0 1\nE 1 2\nE 2 0\nE 2 3\nE 3 4\nE 4 5\nE 5 3\n".parse().unwrap();
+        let control = ExecutionControl::new();
+        let structural = g.attributed_communities_by_attribute("Income", 0.0, &control).unwrap();
+        assert_eq!(structural.communities, vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert!((structural.modularity - 5f64 / 14f64).abs() < 1e-12);
+        let mixed = g.attributed_communities_by_attribute("Income", 0.5, &control).unwrap();
+        assert_eq!(mixed.communities, vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+        assert_eq!(g.modularity(&mixed.communities).unwrap(), mixed.modularity);
+        let values = (0..6).map(|id| (id, if id < 4 { 1.0 } else { 9.0 })).collect::<HashMap<_, _>>();
+        assert_eq!(g.attributed_communities(&values, 0.5, &control).unwrap(), mixed);
+        assert!(g.attributed_communities(&values, 1.5, &control).is_err());
+        assert!(g.attributed_communities(&HashMap::from([(0, 1.0)]), 0.5, &control).is_err());
+        assert!(g.attributed_communities_by_attribute("Age", 0.5, &control).is_err());
+    }
+}
@@ -0,0 +1,831 @@
+//! # Conversions module
+//! 
+//! ## Description
+//! This module contains functions that build new [`Graph`]s out of the existing ones by
+//! changing their kind, their attributes or the way their edges are organised.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BTreeMap, HashMap};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, locales::LocaleCounterparts, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, EdgeIteratorItem, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * POLICIES                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Function that merges attributes of parallel edges
+/// 
+/// ## Description
+/// A boxed function used by [`EdgeMergePolicy::Reduce`].
+pub type EdgeReducer<'a, EdgeAttributeCollectionType> = Box<dyn FnMut(&[&EdgeAttributeCollectionType]) -> EdgeAttributeCollectionType + 'a>;
+
+/// # Policy of merging parallel edges
+/// 
+/// ## Description
+/// This enum defines what happens to the [attributes][attrs] of parallel edges  when  a
+/// conversion  of  a  [`Graph`]  forces  them  to  become  a  single  edge  (e.g.   see
+/// [`Graph::to_simple`] and [`Graph::to_undirected`]).
+/// 
+/// ## Variants
+/// * `KeepMinId` - the attributes of the parallel edge with the smallest ID  are  kept;
+/// the attributes of all other parallel edges are dropped.
+/// * `KeepMaxId` - the attributes of the parallel edge with the largest  ID  are  kept;
+/// the attributes of all other parallel edges are dropped.
+/// * `Reduce(EdgeReducer)` - the given function receives the attribute  collections  of
+/// all parallel edges and returns the attribute collection of the merged edge.
+/// 
+/// [attrs]: crate::Graph#attributes
+pub enum EdgeMergePolicy<'a, EdgeAttributeCollectionType> {
+    KeepMinId,
+    KeepMaxId,
+    Reduce(EdgeReducer<'a, EdgeAttributeCollectionType>),
+}
+
+// EdgeMergePolicy::EdgeMergePolicy
+impl<'a, EdgeAttributeCollectionType> EdgeMergePolicy<'a, EdgeAttributeCollectionType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+{
+    fn merge<EdgeIdType: Id>(&mut self, edges: &[(EdgeIdType, &EdgeAttributeCollectionType)]) -> EdgeAttributeCollectionType {
+        match self {
+            EdgeMergePolicy::KeepMinId => edges.iter().min_by(|x, y| x.0.cmp(&y.0)).unwrap().1.clone(),
+            EdgeMergePolicy::KeepMaxId => edges.iter().max_by(|x, y| x.0.cmp(&y.0)).unwrap().1.clone(),
+            EdgeMergePolicy::Reduce(reducer) => reducer(&edges.iter().map(|x| x.1).collect::<Vec<_>>()),
+        }
+    }
+}
+
+
+
+/// # Function that merges attributes of colliding vertices
+/// 
+/// ## Description
+/// A boxed function used by [`VertexMergePolicy::Reduce`].
+pub type VertexReducer<'a, VertexAttributeCollectionType> = Box<dyn FnMut(&[&VertexAttributeCollectionType]) -> VertexAttributeCollectionType + 'a>;
+
+/// # Policy of merging colliding vertices
+/// 
+/// ## Description
+/// This  enum  defines   what   happens   when   a   relabelling   of   vertices   (see
+/// [`Graph::map_ids`]) assigns the same new ID to several vertices.
+/// 
+/// ## Variants
+/// * `Reject` - the relabelling fails.
+/// * `KeepMinId` - the vertices are merged, and the [attributes][attrs] of  the  vertex
+/// with the smallest old ID are kept.
+/// * `KeepMaxId` - the vertices are merged, and the attributes of the vertex  with  the
+/// largest old ID are kept.
+/// * `Reduce(VertexReducer)` - the vertices are merged, and the given function receives
+/// the attribute collections of all of them (in the ascending order of their  old  IDs)
+/// and returns the attribute collection of the merged vertex.
+/// 
+/// [attrs]: crate::Graph#attributes
+pub enum VertexMergePolicy<'a, VertexAttributeCollectionType> {
+    Reject,
+    KeepMinId,
+    KeepMaxId,
+    Reduce(VertexReducer<'a, VertexAttributeCollectionType>),
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY FUNCTIONS                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+type CollidingVertexBundles<'a, NewVertexIdType, VertexAttributeCollectionType, VertexIdType> = BTreeMap<NewVertexIdType, Vec<(VertexIdType, &'a VertexAttributeCollectionType)>>;
+
+type ParallelEdgeBundles<'a, EdgeAttributeCollectionType, EdgeIdType, VertexIdType> = HashMap<(VertexIdType, VertexIdType), (EdgeDirection, Vec<(EdgeIdType, &'a EdgeAttributeCollectionType)>)>;
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PARALLEL EDGES                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Collapse parallel edges
+    /// 
+    /// ## Description
+    /// Build a [simple][kinds] graph out of the caller  by  replacing  each  bundle  of
+    /// parallel edges with  a  single  edge  which  [attributes][attrs]  aggregate  the
+    /// attributes of the entire bundle.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `reducer` : `ReducerType` - a function that receives the attribute collections
+    /// of all parallel edges connecting the same  pair  of  vertices  and  returns  the
+    /// attribute collection of the edge that replaces them.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices (and the same vertex  attributes)
+    /// as the caller and with at most one edge between any 2 vertices.
+    /// 
+    /// ## Details
+    /// The kind of the new graph  is  defined  by  `NewLocaleType`,  which  is  usually
+    /// inferred from the type annotation of the variable  the  result  is  assigned  to
+    /// (e.g. with the help of [`graph!`][graph]).
+    /// 
+    /// Parallel edges are the edges that connect the same pair of  vertices  regardless
+    /// of their direction. If all edges of the bundle are directed the  same  way,  the
+    /// new edge will keep this direction; otherwise, the new edge will be undirected.
+    /// 
+    /// For example, `reducer` can be `|edges| edges.len()` if you  wish  to  count  the
+    /// parallel edges or it can sum up their weights.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [graph]: crate::graph
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn collapse_parallel_edges<NewEdgeAttributeCollectionType, NewEdgeIdType, NewLocaleType, ReducerType>(&self, mut reducer: ReducerType) -> Graph<NewEdgeAttributeCollectionType, NewEdgeIdType, NewLocaleType, VertexAttributeCollectionType, VertexIdType>
+    where
+        NewEdgeAttributeCollectionType: AttributeCollection,
+        NewEdgeIdType: Id,
+        NewLocaleType: Locale<NewEdgeAttributeCollectionType, NewEdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        ReducerType: FnMut(&[&EdgeAttributeCollectionType]) -> NewEdgeAttributeCollectionType,
+    {
+        let mut answer = self.copy_v();
+        for ((id1, id2), (direction, edges)) in self.group_parallel_e() {
+            let (source, target, directed) = match direction {
+                EdgeDirection::Undirected => (id1, id2, false),
+                EdgeDirection::Directed1to2 => (id1, id2, true),
+                EdgeDirection::Directed2to1 => (id2, id1, true),
+            };
+            let edge_id = answer.add_e(&source, &target, directed, None).unwrap();
+            *answer.e_attrs_mut(&source, &target, &edge_id).unwrap() = reducer(&edges.iter().map(|x| x.1).collect::<Vec<_>>());
+        }
+        answer
+    }
+
+    fn copy_v<NewEdgeAttributeCollectionType, NewEdgeIdType, NewLocaleType>(&self) -> Graph<NewEdgeAttributeCollectionType, NewEdgeIdType, NewLocaleType, VertexAttributeCollectionType, VertexIdType>
+    where
+        NewEdgeAttributeCollectionType: AttributeCollection,
+        NewEdgeIdType: Id,
+        NewLocaleType: Locale<NewEdgeAttributeCollectionType, NewEdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    {
+        let mut answer = Graph::new();
+        for id in self.iter_v() {
+            answer.add_v(Some(id.clone()));
+            *answer.v_attrs_mut(&id).unwrap() = self.v_attrs(&id).unwrap().clone();
+        }
+        answer
+    }
+
+    fn copy_e<NewLocaleType>(&self, answer: &mut Graph<EdgeAttributeCollectionType, EdgeIdType, NewLocaleType, VertexAttributeCollectionType, VertexIdType>, keep_direction: bool, keep_ids: bool)
+    where
+        NewLocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    {
+        for edge in self.iter_e() {
+            let (source, target, directed) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1, keep_direction),
+                EdgeDirection::Directed1to2 => (&edge.id1, &edge.id2, keep_direction),
+                EdgeDirection::Undirected => (&edge.id1, &edge.id2, false),
+            };
+            let edge_id = answer.add_e(source, target, directed, if keep_ids { Some(edge.edge_id.clone()) } else { None }).unwrap();
+            *answer.e_attrs_mut(source, target, &edge_id).unwrap() = self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap().clone();
+        }
+    }
+
+    fn group_parallel_e(&self) -> ParallelEdgeBundles<'_, EdgeAttributeCollectionType, EdgeIdType, VertexIdType> {
+        let mut bundles: ParallelEdgeBundles<EdgeAttributeCollectionType, EdgeIdType, VertexIdType> = HashMap::with_capacity(self.count_e());
+        for edge in self.iter_e() {
+            let attributes = self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap();
+            let (key, direction) = if edge.id1 <= edge.id2 {
+                ((edge.id1, edge.id2), edge.direction)
+            } else {
+                ((edge.id2, edge.id1), edge.direction.reversed())
+            };
+            match bundles.get_mut(&key) {
+                Some(bundle) => {
+                    if bundle.0 != direction {
+                        bundle.0 = EdgeDirection::Undirected;
+                    }
+                    bundle.1.push((edge.edge_id, attributes));
+                },
+                None => {
+                    bundles.insert(key, (direction, vec![(edge.edge_id, attributes)]));
+                },
+            }
+        }
+        bundles
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ATTRIBUTES                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Transform attributes
+    /// 
+    /// ## Description
+    /// Build a graph with the same topology as the caller but with different  types  of
+    /// [attribute collections][attrs] for vertices and edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `vertex_fn` : `VertexFnType` - a function that receives the ID of a vertex and
+    /// its attribute collection and returns the attribute collection of the  respective
+    /// vertex of the new graph.
+    /// * `edge_fn` : `EdgeFnType` - a function that receives the edge and its attribute
+    /// collection and returns the attribute collection of the respective  edge  of  the
+    /// new graph.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices and edges (with the same IDs  and
+    /// directions) as the caller.
+    /// 
+    /// ## Details
+    /// The kind of the new graph  is  defined  by  `NewLocaleType`,  which  is  usually
+    /// inferred from the type annotation of the variable  the  result  is  assigned  to
+    /// (e.g. with the help of [`graph!`][graph]). It is expected  to  be  of  the  same
+    /// [kind][kinds] as the caller.
+    /// 
+    /// A typical use case is converting a [`DynamicDispatchAttributeMap`] into a  typed
+    /// struct once the schema of your attributes is settled.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [graph]: crate::graph
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    /// [`DynamicDispatchAttributeMap`]: crate::attributes::DynamicDispatchAttributeMap
+    pub fn map_attrs<NewEdgeAttributeCollectionType, NewLocaleType, NewVertexAttributeCollectionType, VertexFnType, EdgeFnType>(&self, mut vertex_fn: VertexFnType, mut edge_fn: EdgeFnType) -> Graph<NewEdgeAttributeCollectionType, EdgeIdType, NewLocaleType, NewVertexAttributeCollectionType, VertexIdType>
+    where
+        NewEdgeAttributeCollectionType: AttributeCollection,
+        NewLocaleType: Locale<NewEdgeAttributeCollectionType, EdgeIdType, NewVertexAttributeCollectionType, VertexIdType>,
+        NewVertexAttributeCollectionType: AttributeCollection,
+        VertexFnType: FnMut(&VertexIdType, &VertexAttributeCollectionType) -> NewVertexAttributeCollectionType,
+        EdgeFnType: FnMut(&EdgeIteratorItem<EdgeIdType, VertexIdType>, &EdgeAttributeCollectionType) -> NewEdgeAttributeCollectionType,
+    {
+        let mut answer = Graph::new();
+        for id in self.iter_v() {
+            answer.add_v(Some(id.clone()));
+            *answer.v_attrs_mut(&id).unwrap() = vertex_fn(&id, self.v_attrs(&id).unwrap());
+        }
+        for edge in self.iter_e() {
+            let (source, target, directed) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1, true),
+                EdgeDirection::Directed1to2 => (&edge.id1, &edge.id2, true),
+                EdgeDirection::Undirected => (&edge.id1, &edge.id2, false),
+            };
+            let edge_id = answer.add_e(source, target, directed, Some(edge.edge_id.clone())).unwrap();
+            *answer.e_attrs_mut(source, target, &edge_id).unwrap() = edge_fn(&edge, self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap());
+        }
+        answer
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * VERTEX IDS                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Mapping of vertex IDs
+/// 
+/// ## Description
+/// The mapping from the old vertex IDs to the new ones built by  [`Graph::compact_ids`]
+/// and [`Graph::map_ids`].
+/// 
+/// [`Graph::compact_ids`]: crate::Graph::compact_ids
+/// [`Graph::map_ids`]: crate::Graph::map_ids
+pub type IdMapping<VertexIdType, NewVertexIdType = usize> = HashMap<VertexIdType, NewVertexIdType>;
+
+/// # Relabelling
+/// 
+/// ## Description
+/// The result of [`Graph::map_ids`]: the relabelled  graph  with  vertex  IDs  of  type
+/// `NewVertexIdType` and the [mapping][IdMapping] from the old vertex IDs  to  the  new
+/// ones.
+/// 
+/// [`Graph::map_ids`]: crate::Graph::map_ids
+pub type Relabelling<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType, NewVertexIdType> = (Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, NewVertexIdType>, IdMapping<VertexIdType, NewVertexIdType>);
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Compact vertex IDs
+    /// 
+    /// ## Description
+    /// Build a copy of the caller whose vertices are relabelled with dense IDs from 0 to
+    /// |V| - 1.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `(Graph, IdMapping<VertexIdType>)` - a new graph  with  the  same  attributes,
+    /// edge IDs and directions of edges as the caller and the mapping from  the  vertex
+    /// IDs of the caller to the vertex IDs of the new graph.
+    /// 
+    /// ## Details
+    /// New IDs are assigned in the ascending order of the old  ones,  i.e.  the  vertex
+    /// with the smallest ID receives ID 0, the next one receives ID 1 and so on.
+    /// 
+    /// The kind of the new graph  is  defined  by  `NewLocaleType`,  which  is  usually
+    /// inferred from the type annotation of the variable  the  result  is  assigned  to
+    /// (e.g. with the help of [`graph!`][graph]). It is expected  to  be  of  the  same
+    /// [kind][kinds] as the caller.
+    /// 
+    /// This function is useful after many deletions have left the range of  IDs  sparse
+    /// and before exporting the graph into formats that  index  vertices  by  position,
+    /// such as adjacency matrices.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [graph]: crate::graph
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn compact_ids<NewLocaleType>(&self) -> (Graph<EdgeAttributeCollectionType, EdgeIdType, NewLocaleType, VertexAttributeCollectionType, usize>, IdMapping<VertexIdType>)
+    where
+        NewLocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, usize>,
+    {
+        let mut ids: Vec<VertexIdType> = self.iter_v().collect();
+        ids.sort();
+        let mapping: IdMapping<VertexIdType> = ids.into_iter().enumerate().map(|(new_id, old_id)| (old_id, new_id)).collect();
+        let mut answer = Graph::new();
+        for (old_id, new_id) in mapping.iter() {
+            answer.add_v(Some(*new_id));
+            *answer.v_attrs_mut(new_id).unwrap() = self.v_attrs(old_id).unwrap().clone();
+        }
+        for edge in self.iter_e() {
+            let (source, target, directed) = match edge.direction {
+                EdgeDirection::Directed2to1 => (mapping[&edge.id2], mapping[&edge.id1], true),
+                EdgeDirection::Directed1to2 => (mapping[&edge.id1], mapping[&edge.id2], true),
+                EdgeDirection::Undirected => (mapping[&edge.id1], mapping[&edge.id2], false),
+            };
+            let edge_id = answer.add_e(&source, &target, directed, Some(edge.edge_id.clone())).unwrap();
+            *answer.e_attrs_mut(&source, &target, &edge_id).unwrap() = self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap().clone();
+        }
+        (answer, mapping)
+    }
+
+    /// # Relabel vertices
+    /// 
+    /// ## Description
+    /// Build a copy of the caller whose vertices are relabelled by the given  function,
+    /// possibly into IDs of another type.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `f` : `MapFnType` - a function that receives  the  old  ID  of  a  vertex  and
+    /// returns its new ID.
+    /// * `merge_policy` : `VertexMergePolicy` - defines what happens if `f` assigns the
+    /// same new ID to several vertices.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Relabelling<...>>` - a new graph with the relabelled  vertices
+    /// and the mapping from the vertex IDs of the caller to the vertex IDs of  the  new
+    /// graph,  or  an  error  if  `f`  is   not   injective   and   `merge_policy`   is
+    /// [`VertexMergePolicy::Reject`].
+    /// 
+    /// ## Details
+    /// The kind of the new graph  is  defined  by  `NewLocaleType`,  which  is  usually
+    /// inferred from the type annotation of the variable  the  result  is  assigned  to
+    /// (e.g. with the help of [`graph!`][graph]).
+    /// 
+    /// When several vertices are merged, their  edges  are  reattached  to  the  merged
+    /// vertex, and the edges between them  become  loops.  Edges  keep  their  IDs  and
+    /// [attributes][attrs] unless 2 edges with the same ID end up connecting  the  same
+    /// pair of vertices, in which case the latter receives a new ID. If the  new  graph
+    /// is simple, the  edges  that  become  parallel  are  collapsed  into  one,  which
+    /// attributes are taken from an unspecified edge of the bundle;  to  control  this,
+    /// build a multi-graph and call [`Graph::to_simple`] on it.
+    /// 
+    /// For example, `|id: &String| id.len()` relabels a graph with string  IDs  into  a
+    /// graph with integer IDs.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [graph]: crate::graph
+    pub fn map_ids<MapFnType, NewLocaleType, NewVertexIdType>(&self, f: MapFnType, merge_policy: VertexMergePolicy<VertexAttributeCollectionType>) -> CrabNetsResult<Relabelling<EdgeAttributeCollectionType, EdgeIdType, NewLocaleType, VertexAttributeCollectionType, VertexIdType, NewVertexIdType>>
+    where
+        MapFnType: Fn(&VertexIdType) -> NewVertexIdType,
+        NewLocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, NewVertexIdType>,
+        NewVertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::map_ids";
+        let mut merge_policy = merge_policy;
+        let mapping: IdMapping<VertexIdType, NewVertexIdType> = self.iter_v().map(|id| {
+            let new_id = f(&id);
+            (id, new_id)
+        }).collect();
+        // Old IDs are grouped in the ascending order to make the merge deterministic
+        let mut groups: CollidingVertexBundles<NewVertexIdType, VertexAttributeCollectionType, VertexIdType> = BTreeMap::new();
+        for (old_id, new_id) in mapping.iter() {
+            groups.entry(new_id.clone()).or_default().push((old_id.clone(), self.v_attrs(old_id).unwrap()));
+        }
+        let mut answer = Graph::new();
+        for (new_id, mut vertices) in groups {
+            vertices.sort_by(|x, y| x.0.cmp(&y.0));
+            let attrs = if vertices.len() == 1 {
+                vertices[0].1.clone()
+            } else {
+                match &mut merge_policy {
+                    VertexMergePolicy::Reject => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexAlreadyExists { id: new_id.to_string() })),
+                    VertexMergePolicy::KeepMinId => vertices[0].1.clone(),
+                    VertexMergePolicy::KeepMaxId => vertices[vertices.len() - 1].1.clone(),
+                    VertexMergePolicy::Reduce(reducer) => reducer(&vertices.iter().map(|x| x.1).collect::<Vec<_>>()),
+                }
+            };
+            answer.add_v(Some(new_id.clone()));
+            *answer.v_attrs_mut(&new_id).unwrap() = attrs;
+        }
+        for edge in self.iter_e() {
+            let (source, target, directed) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&mapping[&edge.id2], &mapping[&edge.id1], true),
+                EdgeDirection::Directed1to2 => (&mapping[&edge.id1], &mapping[&edge.id2], true),
+                EdgeDirection::Undirected => (&mapping[&edge.id1], &mapping[&edge.id2], false),
+            };
+            let edge_id = if answer.contains_e(source, target, &edge.edge_id).is_none() {
+                Some(edge.edge_id.clone())
+            } else if NewLocaleType::IS_MULTI {
+                None
+            } else {
+                continue;
+            };
+            let edge_id = answer.add_e(source, target, directed, edge_id).unwrap();
+            *answer.e_attrs_mut(source, target, &edge_id).unwrap() = self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap().clone();
+        }
+        Ok((answer, mapping))
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * KINDS OF GRAPHS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Convert into a directed graph
+    /// 
+    /// ## Description
+    /// Build  a  graph  of  the  same  multiplicity  as  the   caller   that   supports
+    /// [directed][kinds] edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices, edges and [attributes][attrs] as
+    /// the caller.
+    /// 
+    /// ## Details
+    /// The conversion is lossless, all edges keep their  IDs  and  directions.  If  the
+    /// caller is already directed, this function simply returns its copy.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn to_directed(&self) -> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Directed, VertexAttributeCollectionType, VertexIdType> {
+        let mut answer = self.copy_v();
+        self.copy_e(&mut answer, true, LocaleType::IS_MULTI);
+        answer
+    }
+
+    /// # Convert into a multi-graph
+    /// 
+    /// ## Description
+    /// Build a [multi-graph][kinds] with the same directedness as the caller.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices, edges and [attributes][attrs] as
+    /// the caller.
+    /// 
+    /// ## Details
+    /// The conversion is lossless, all edges keep their directions. If  the  caller  is
+    /// already a multi-graph, this function simply returns its copy; otherwise, new IDs
+    /// are assigned to the edges since [edge IDs][edgeids] of simple graphs are  purely
+    /// symbolic.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [edgeids]: crate::Graph#representation-of-graphs
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn to_multigraph(&self) -> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Multi, VertexAttributeCollectionType, VertexIdType> {
+        let mut answer = self.copy_v();
+        self.copy_e(&mut answer, true, LocaleType::IS_MULTI);
+        answer
+    }
+
+    /// # Convert into a simple graph
+    /// 
+    /// ## Description
+    /// Build a [simple][kinds] graph with the same directedness as the caller.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `dedupe_policy` : `EdgeMergePolicy` - defines how the  [attributes][attrs]  of
+    /// parallel edges are merged.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices as the caller and  with  at  most
+    /// one edge between any 2 vertices.
+    /// 
+    /// ## Details
+    /// Parallel edges are the edges that connect the same pair of  vertices  regardless
+    /// of their direction. If all parallel edges are directed the same way, the  merged
+    /// edge will keep this direction; otherwise, the merged edge will be undirected.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn to_simple(&self, mut dedupe_policy: EdgeMergePolicy<EdgeAttributeCollectionType>) -> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Simple, VertexAttributeCollectionType, VertexIdType> {
+        let mut answer = self.copy_v();
+        for ((id1, id2), (direction, edges)) in self.group_parallel_e() {
+            let (source, target, directed) = match direction {
+                EdgeDirection::Undirected => (id1, id2, false),
+                EdgeDirection::Directed1to2 => (id1, id2, true),
+                EdgeDirection::Directed2to1 => (id2, id1, true),
+            };
+            let edge_id = answer.add_e(&source, &target, directed, None).unwrap();
+            *answer.e_attrs_mut(&source, &target, &edge_id).unwrap() = dedupe_policy.merge(&edges);
+        }
+        answer
+    }
+
+    /// # Convert into an undirected graph
+    /// 
+    /// ## Description
+    /// Build an [undirected][kinds] graph of the same multiplicity as the caller.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `merge_policy` : `EdgeMergePolicy` - defines how  the  [attributes][attrs]  of
+    /// the edges that become parallel are merged.
+    /// 
+    /// ## Returns
+    /// * `Graph` - a new graph with the same vertices as the caller and all edges  made
+    /// undirected.
+    /// 
+    /// ## Details
+    /// If the caller is a multi-graph, all edges keep their  IDs  and  attributes,  and
+    /// `merge_policy` is not used. If the caller is simple, edges `(u, v)` and `(v, u)`
+    /// become parallel after they lose their directions, and,  thus,  they  are  merged
+    /// into one edge according to `merge_policy`.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn to_undirected(&self, mut merge_policy: EdgeMergePolicy<EdgeAttributeCollectionType>) -> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Undirected, VertexAttributeCollectionType, VertexIdType> {
+        let mut answer = self.copy_v();
+        if LocaleType::IS_MULTI {
+            self.copy_e(&mut answer, false, true);
+        } else {
+            for ((id1, id2), (_, edges)) in self.group_parallel_e() {
+                let edge_id = answer.add_e(&id1, &id2, false, None).unwrap();
+                *answer.e_attrs_mut(&id1, &id2, &edge_id).unwrap() = merge_policy.merge(&edges);
+            }
+        }
+        answer
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::{EdgeMergePolicy, VertexMergePolicy};
+
+    #[test]
+    fn collapse_parallel_edges() {
+        let mut g: graph!(X ===A==> X) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        let id3 = g.add_v(None);
+        // 3 parallel edges in the same direction and 2 opposite ones
+        for weight in [1.0f64, 2.0, 4.5] {
+            let edge_id = g.add_e(&id2, &id1, true, None).unwrap();
+            g.e_attrs_mut(&id2, &id1, &edge_id).unwrap().insert("weight".to_string(), Box::new(weight));
+        }
+        g.add_e(&id2, &id3, true, None).unwrap();
+        let edge_id = g.add_e(&id3, &id2, true, None).unwrap();
+        g.e_attrs_mut(&id3, &id2, &edge_id).unwrap().insert("weight".to_string(), Box::new(0.5f64));
+        assert_eq!(g.count_e(), 5);
+        let mut bundle_sizes = Vec::new();
+        let h: graph!(X ---A--> X) = g.collapse_parallel_edges(|edges| {
+            bundle_sizes.push(edges.len());
+            let mut answer = DynamicDispatchAttributeMap::new();
+            answer.insert("weight".to_string(), Box::new(edges.iter().filter_map(|x| x.get(&"weight".to_string())).filter_map(|x| x.downcast::<f64>()).sum::<f64>()));
+            answer
+        });
+        bundle_sizes.sort();
+        assert_eq!(bundle_sizes, vec![2, 3]);
+        assert_eq!(h.count_v(), 3);
+        assert_eq!(h.count_e(), 2);
+        assert!(h.contains_e(&id2, &id1, &0) == Some(EdgeDirection::Directed1to2));
+        assert!(h.contains_e(&id3, &id2, &0) == Some(EdgeDirection::Undirected));
+        let weight = |id1, id2| *h.e_attrs(&id1, &id2, &0).unwrap().get(&"weight".to_string()).unwrap().downcast::<f64>().unwrap();
+        assert_eq!(weight(id2, id1), 7.5);
+        assert_eq!(weight(id2, id3), 0.5);
+    }
+
+    #[test]
+    fn kind_conversions() {
+        let mut g: graph!(X ---A--> X) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        let id3 = g.add_v(None);
+        g.add_e(&id2, &id1, true, None).unwrap();
+        g.add_e(&id2, &id3, false, None).unwrap();
+        g.e_attrs_mut(&id2, &id1, &0).unwrap().insert("weight".to_string(), Box::new(2.0f64));
+        let h = g.to_undirected(EdgeMergePolicy::KeepMinId);
+        assert_eq!(h.count_e(), 2);
+        assert!(h.contains_e(&id1, &id2, &0) == Some(EdgeDirection::Undirected));
+        assert_eq!(h.e_attrs(&id1, &id2, &0).unwrap().get(&"weight".to_string()).unwrap().downcast::<f64>(), Some(&2.0));
+        let mut h = g.to_multigraph();
+        h.add_e(&id2, &id1, true, None).unwrap();
+        h.add_e(&id1, &id2, true, None).unwrap();
+        assert_eq!(h.count_e(), 4);
+        assert_eq!(h.v_degree_out(&id2).unwrap(), 2);
+        let h = h.to_simple(EdgeMergePolicy::Reduce(Box::new(|edges| {
+            let mut answer = DynamicDispatchAttributeMap::new();
+            answer.insert("count".to_string(), Box::new(edges.len()));
+            answer
+        })));
+        assert_eq!(h.count_e(), 2);
+        assert!(h.contains_e(&id1, &id2, &0) == Some(EdgeDirection::Undirected));
+        assert_eq!(h.e_attrs(&id1, &id2, &0).unwrap().get(&"count".to_string()).unwrap().downcast::<usize>(), Some(&3));
+        let mut g: graph!(X ===X=== X) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        g.add_e(&id1, &id2, false, Some(3)).unwrap();
+        g.add_e(&id1, &id2, false, Some(5)).unwrap();
+        let h = g.to_directed();
+        assert!(h.contains_e(&id2, &id1, &5) == Some(EdgeDirection::Undirected));
+        assert_eq!(h.to_undirected(EdgeMergePolicy::KeepMaxId).count_e(), 2);
+        assert_eq!(h.to_simple(EdgeMergePolicy::KeepMaxId).count_e(), 1);
+    }
+    #[test]
+    fn map_attrs() {
+        #[derive(Clone, Default)]
+        struct Weight(f64);
+        impl AttributeCollection for Weight {
+            fn new() -> Self {
+                Weight(0.0)
+            }
+        }
+        let mut g: graph!(A ---A--> A) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        g.add_e(&id1, &id2, true, None).unwrap();
+        g.e_attrs_mut(&id1, &id2, &0).unwrap().insert("weight".to_string(), Box::new(4.0f64));
+        g.v_attrs_mut(&id2).unwrap().insert("weight".to_string(), Box::new(1.5f64));
+        let h: graph!(A ---A--> A with EdgeAttributeCollectionType = Weight, VertexAttributeCollectionType = Weight) = g.map_attrs(
+            |_, x| Weight(x.get(&"weight".to_string()).and_then(|y| y.downcast::<f64>().cloned()).unwrap_or(0.0)),
+            |_, x| Weight(x.get(&"weight".to_string()).and_then(|y| y.downcast::<f64>().cloned()).unwrap_or(0.0)),
+        );
+        assert!(h.contains_e(&id1, &id2, &0) == Some(EdgeDirection::Directed1to2));
+        assert_eq!(h.e_attrs(&id1, &id2, &0).unwrap().0, 4.0);
+        assert_eq!(h.v_attrs(&id1).unwrap().0, 0.0);
+        assert_eq!(h.v_attrs(&id2).unwrap().0, 1.5);
+    }
+
+    #[test]
+    fn compact_ids() {
+        let mut g: graph!(X ===A==> X with VertexIdType = u16) = Graph::new();
+        g.add_v(Some(10));
+        g.add_v(Some(3));
+        g.add_v(Some(7));
+        g.add_e(&10, &3, true, None).unwrap();
+        g.add_e(&10, &3, false, Some(4)).unwrap();
+        g.e_attrs_mut(&10, &3, &4).unwrap().insert("weight".to_string(), Box::new(2u8));
+        let (h, mapping): (graph!(X ===A==> X), _) = g.compact_ids();
+        assert_eq!(mapping, HashMap::from([(3, 0), (7, 1), (10, 2)]));
+        assert_eq!(h.count_v(), 3);
+        assert!(h.contains_e(&2, &0, &0) == Some(EdgeDirection::Directed1to2));
+        assert!(h.contains_e(&2, &0, &4) == Some(EdgeDirection::Undirected));
+        assert!(h.e_attrs(&0, &2, &4).unwrap().get(&"weight".to_string()).is_some());
+        assert_eq!(h.count_e(), 2);
+    }
+
+    #[test]
+    fn map_ids() {
+        let mut g: graph!(A ===A==> A with EdgeIdType = u8, VertexIdType = String) = Graph::new();
+        for id in ["a", "bb", "cc"] {
+            g.add_v(Some(id.to_string()));
+            g.v_attrs_mut(&id.to_string()).unwrap().insert("name".to_string(), Box::new(id.to_string()));
+        }
+        g.add_e(&"a".to_string(), &"bb".to_string(), true, None).unwrap();
+        g.add_e(&"a".to_string(), &"cc".to_string(), true, None).unwrap();
+        g.add_e(&"bb".to_string(), &"cc".to_string(), false, None).unwrap();
+        g.e_attrs_mut(&"a".to_string(), &"cc".to_string(), &0).unwrap().insert("weight".to_string(), Box::new(3u8));
+        let (h, mapping): (graph!(A ===A==> A with EdgeIdType = u8), _) = g.map_ids(|id| id.len() * 10, VertexMergePolicy::KeepMaxId).unwrap();
+        assert_eq!(mapping, HashMap::from([("a".to_string(), 10), ("bb".to_string(), 20), ("cc".to_string(), 20)]));
+        assert_eq!(h.count_v(), 2);
+        assert_eq!(h.iter_e().count(), 3);
+        assert_eq!(h.v_attrs(&20).unwrap().get(&"name".to_string()).unwrap().downcast::<String>(), Some(&"cc".to_string()));
+        assert!(h.contains_e(&10, &20, &0) == Some(EdgeDirection::Directed1to2));
+        assert!(h.contains_e(&10, &20, &1) == Some(EdgeDirection::Directed1to2));
+        assert!(h.contains_e(&20, &20, &0) == Some(EdgeDirection::Undirected));
+        assert_eq!(h.e_attrs(&10, &20, &0).unwrap().get(&"weight".to_string()).is_some() as u8 + h.e_attrs(&10, &20, &1).unwrap().get(&"weight".to_string()).is_some() as u8, 1);
+        let (h, _): (graph!(A ---A--> A), _) = g.map_ids(|id| id.len(), VertexMergePolicy::Reduce(Box::new(|vertices| {
+            let mut answer = DynamicDispatchAttributeMap::new();
+            answer.insert("count".to_string(), Box::new(vertices.len()));
+            answer
+        }))).unwrap();
+        assert_eq!(h.iter_e().count(), 2);
+        assert_eq!(h.v_attrs(&2).unwrap().get(&"count".to_string()).unwrap().downcast::<usize>(), Some(&2));
+        let result: CrabNetsResult<(graph!(A ===A==> A with EdgeIdType = u8), _)> = g.map_ids(|id| id.len(), VertexMergePolicy::Reject);
+        assert!(result.is_err());
+        let (h, _): (graph!(A ===A==> A with EdgeIdType = u8, VertexIdType = u8), _) = g.map_ids(|id| id.as_bytes()[0], VertexMergePolicy::Reject).unwrap();
+        assert_eq!(h.count_v(), 3);
+        assert!(h.contains_e(&b'b', &b'c', &0) == Some(EdgeDirection::Undirected));
+    }
+}
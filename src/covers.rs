@@ -0,0 +1,254 @@
+//! # Covers module
+//! 
+//! ## Description
+//! This module contains [`Covers`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet};
+use crate::{attributes::AttributeCollection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Neighbours of each vertex except the vertex itself, sorted to make the heuristics
+// deterministic, and the set of vertices with self-loops
+type Neighbourhoods<VertexIdType> = (HashMap<VertexIdType, BTreeSet<VertexIdType>>, HashSet<VertexIdType>);
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * COVERS                                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Covers
+/// 
+/// ## Description
+/// Fast heuristics for  classical  NP-hard  covering  problems.  They  don't  guarantee
+/// optimal solutions but run in near-linear time and are deterministic: the same  graph
+/// always gives the same answer.
+/// 
+/// All functions ignore the directions and the multiplicity of edges, i.e.  they  treat
+/// the graph as simple and undirected.
+pub trait Covers<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Greedy dominating set
+    /// 
+    /// ## Description
+    /// Find a set of vertices such that  every  vertex  either  belongs  to  it  or  is
+    /// adjacent to a vertex from it.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `HashSet<VertexIdType>` - the dominating set.
+    /// 
+    /// ## Details
+    /// Repeatedly picks the vertex  that  dominates  the  largest  number  of  not  yet
+    /// dominated vertices (ties are broken by the smallest ID). The size of the  result
+    /// is at most H(Δ + 1) ≤ ln(Δ + 1) + 1 times the size  of  the  minimum  dominating
+    /// set, where Δ is the maximum degree.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn dominating_set(&self) -> HashSet<VertexIdType>;
+    /// # Greedy independent set
+    /// 
+    /// ## Description
+    /// Find a maximal set of pairwise non-adjacent vertices, i.e.  an  independent  set
+    /// that can't be extended.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `HashSet<VertexIdType>` - the independent set.
+    /// 
+    /// ## Details
+    /// Repeatedly picks the vertex with the smallest  number  of  remaining  neighbours
+    /// (ties are  broken  by  the  smallest  ID)  and  removes  it  together  with  its
+    /// neighbours. Vertices with self-loops are never picked.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn independent_set(&self) -> HashSet<VertexIdType>;
+    /// # 2-approximate vertex cover
+    /// 
+    /// ## Description
+    /// Find a set of vertices such that every edge has at least one endpoint in it.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `HashSet<VertexIdType>` - the vertex cover.
+    /// 
+    /// ## Details
+    /// Both endpoints of every edge of a greedily built maximal matching are taken,  so
+    /// the result is at most twice as large as the minimum vertex cover. Vertices  with
+    /// self-loops are always included.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn vertex_cover(&self) -> HashSet<VertexIdType>;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn neighbourhoods(&self) -> Neighbourhoods<VertexIdType> {
+        let mut neighbours = HashMap::with_capacity(self.edge_list.len());
+        let mut self_loops = HashSet::new();
+        for (id, locale) in self.edge_list.iter() {
+            let mut adjacent: BTreeSet<VertexIdType> = locale.iter_adjacent().collect();
+            if adjacent.remove(id) {
+                self_loops.insert(id.clone());
+            }
+            neighbours.insert(id.clone(), adjacent);
+        }
+        (neighbours, self_loops)
+    }
+}
+
+// Graph::Covers
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Covers<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn dominating_set(&self) -> HashSet<VertexIdType> {
+        let (neighbours, _) = self.neighbourhoods();
+        let mut answer = HashSet::new();
+        let mut dominated = HashSet::with_capacity(neighbours.len());
+        // Gains only decrease, so outdated entries of the queue can be re-evaluated lazily
+        let mut candidates: BinaryHeap<(usize, std::cmp::Reverse<VertexIdType>)> = neighbours.iter().map(|(id, adjacent)| (adjacent.len() + 1, std::cmp::Reverse(id.clone()))).collect();
+        while let Some((gain, std::cmp::Reverse(id))) = candidates.pop() {
+            if dominated.len() == neighbours.len() {
+                break;
+            }
+            let actual_gain = std::iter::once(&id).chain(neighbours[&id].iter()).filter(|x| !dominated.contains(*x)).count();
+            if actual_gain == 0 {
+                continue;
+            }
+            if actual_gain < gain {
+                candidates.push((actual_gain, std::cmp::Reverse(id)));
+                continue;
+            }
+            dominated.extend(std::iter::once(&id).chain(neighbours[&id].iter()).cloned());
+            answer.insert(id);
+        }
+        answer
+    }
+
+    fn independent_set(&self) -> HashSet<VertexIdType> {
+        let (mut neighbours, self_loops) = self.neighbourhoods();
+        let mut answer = HashSet::new();
+        let mut candidates: BTreeSet<(usize, VertexIdType)> = neighbours.iter().map(|(id, adjacent)| (adjacent.len(), id.clone())).collect();
+        for id in self_loops.iter() {
+            candidates.remove(&(neighbours[id].len(), id.clone()));
+        }
+        while let Some((_, id)) = candidates.pop_first() {
+            let removed: Vec<VertexIdType> = neighbours[&id].iter().cloned().collect();
+            for removed_id in removed.iter() {
+                candidates.remove(&(neighbours[removed_id].len(), removed_id.clone()));
+            }
+            // Neighbours of the removed vertices lose one remaining neighbour each
+            for removed_id in removed.iter() {
+                let second_neighbours = std::mem::take(neighbours.get_mut(removed_id).unwrap());
+                for second_id in second_neighbours {
+                    let adjacent = neighbours.get_mut(&second_id).unwrap();
+                    let was_candidate = candidates.remove(&(adjacent.len(), second_id.clone()));
+                    adjacent.remove(removed_id);
+                    if was_candidate {
+                        candidates.insert((adjacent.len(), second_id));
+                    }
+                }
+            }
+            answer.insert(id);
+        }
+        answer
+    }
+
+    fn vertex_cover(&self) -> HashSet<VertexIdType> {
+        let (neighbours, mut answer) = self.neighbourhoods();
+        for id in neighbours.keys().collect::<BTreeSet<_>>() {
+            if answer.contains(id) {
+                continue;
+            }
+            if let Some(adjacent_id) = neighbours[id].iter().find(|x| !answer.contains(*x)) {
+                answer.insert(adjacent_id.clone());
+                answer.insert(id.clone());
+            }
+        }
+        answer
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn covers() {
+        // Star with 4 leaves and a triangle attached to leaf 4
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        for i in 1..5 {
+            g.add_e(&0, &i, i % 2 == 0, None).unwrap();
+        }
+        g.add_e(&4, &5, false, None).unwrap();
+        g.add_e(&5, &6, false, None).unwrap();
+        g.add_e(&6, &4, true, None).unwrap();
+        g.add_e(&6, &4, true, None).unwrap();
+        g.add_e(&6, &6, false, None).unwrap();
+        let is_edge = |x: &usize, y: &usize| g.iter_e().any(|e| (e.id1 == *x && e.id2 == *y) || (e.id1 == *y && e.id2 == *x));
+        let cover = g.vertex_cover();
+        assert!(g.iter_e().all(|e| cover.contains(&e.id1) || cover.contains(&e.id2)));
+        assert!(cover.contains(&6) && cover.len() <= 6);
+        let independent = g.independent_set();
+        assert_eq!(independent, HashSet::from([1, 2, 3, 4]));
+        assert!(independent.iter().all(|x| independent.iter().all(|y| !is_edge(x, y))));
+        let dominating = g.dominating_set();
+        assert_eq!(dominating, HashSet::from([0, 4]));
+        let empty: graph!(X ---X--- X) = Graph::new();
+        assert!(empty.dominating_set().is_empty());
+    }
+}
@@ -0,0 +1,551 @@
+//! # Cut tree module
+//! 
+//! ## Description
+//! This module contains the construction of the Gomory–Hu tree of a [`Graph`]. The tree
+//! has the same vertices as the graph, and the minimum cut between any two vertices  of
+//! the graph equals the lightest edge on the path between them in the tree, so a single
+//! tree answers all-pairs minimum cut queries.
+//! 
+//! It also contains the edge and vertex connectivity of a [`Graph`], i.e. the  smallest
+//! numbers of edges and vertices whose removal disconnects it, computed with  the  same
+//! maximum flow machinery.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::{attributes::{AttributeCollection, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, io::{AttributeCollectionIO, AttributeToken}, locales::LocaleCounterparts, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Residual capacities below this value are treated as 0
+const CAPACITY_TOLERANCE: f64 = 1e-12;
+
+
+
+// Flow network over vertices numbered from 0, arcs are stored in pairs of opposite
+// arcs, so that the opposite of arc `i` is arc `i ^ 1`, an undirected edge is a pair
+// of opposite arcs with equal capacities
+struct FlowNetwork {
+    capacities: Vec<f64>,
+    heads: Vec<usize>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    fn new(vertex_count: usize) -> Self {
+        FlowNetwork { capacities: Vec::new(), heads: Vec::new(), outgoing: vec![Vec::new(); vertex_count] }
+    }
+
+    // Add an arc and its opposite arc with the given capacities
+    fn push_arcs(&mut self, from: usize, to: usize, capacity: f64, opposite_capacity: f64) {
+        for (tail, head, value) in [(from, to, capacity), (to, from, opposite_capacity)] {
+            self.outgoing[tail].push(self.heads.len());
+            self.heads.push(head);
+            self.capacities.push(value);
+        }
+    }
+
+    // Maximum flow from `source` to `target` computed by Dinic's algorithm and the
+    // vertices reachable from `source` in the residual network, i.e. the source side of
+    // a minimum cut
+    fn max_flow(&self, source: usize, target: usize) -> (f64, Vec<bool>) {
+        let mut residual = self.capacities.clone();
+        let mut answer = 0f64;
+        loop {
+            // Levels of the vertices in the residual network
+            let mut levels = vec![usize::MAX; self.outgoing.len()];
+            levels[source] = 0;
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                for &a in self.outgoing[v].iter() {
+                    if residual[a] > CAPACITY_TOLERANCE && levels[self.heads[a]] == usize::MAX {
+                        levels[self.heads[a]] = levels[v] + 1;
+                        queue.push_back(self.heads[a]);
+                    }
+                }
+            }
+            if levels[target] == usize::MAX {
+                return (answer, levels.into_iter().map(|x| x != usize::MAX).collect());
+            }
+            // Blocking flow along the arcs that go one level up
+            let mut next_arcs = vec![0; self.outgoing.len()];
+            loop {
+                let mut path = Vec::new();
+                let mut v = source;
+                while v != target {
+                    match self.outgoing[v][next_arcs[v]..].iter().position(|a| residual[*a] > CAPACITY_TOLERANCE && levels[self.heads[*a]] == levels[v] + 1) {
+                        Some(offset) => {
+                            next_arcs[v] += offset;
+                            let a = self.outgoing[v][next_arcs[v]];
+                            path.push(a);
+                            v = self.heads[a];
+                        },
+                        None => {
+                            // Dead end: never visit `v` again in this phase
+                            next_arcs[v] = self.outgoing[v].len();
+                            levels[v] = usize::MAX;
+                            match path.pop() {
+                                Some(a) => v = self.heads[a ^ 1],
+                                None => break,
+                            }
+                        },
+                    }
+                }
+                if v != target {
+                    break;
+                }
+                let bottleneck = path.iter().map(|a| residual[*a]).fold(f64::INFINITY, f64::min);
+                for &a in path.iter() {
+                    residual[a] -= bottleneck;
+                    residual[a ^ 1] += bottleneck;
+                }
+                answer += bottleneck;
+            }
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * GOMORY–HU TREE                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Gomory–Hu tree
+    /// 
+    /// ## Description
+    /// Build the tree that encodes the minimum cuts between all pairs of vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `capacity_fn` : `CapacityFnType` - a function that receives  the  IDs  of  the
+    /// endpoints of an edge and the ID of the edge, and returns  the  capacity  of  the
+    /// edge.
+    /// * `name` : `&str` - the name of the edge attribute to write cut values into.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Graph>` -  `Ok(value)`  is  returned  if  all  capacities  are
+    /// non-negative and the computation wasn't cancelled, `value` in this case  is  the
+    /// Gomory–Hu tree; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The tree is an [undirected][kinds] graph with the  same  multiplicity,  vertices
+    /// and vertex attributes as the caller and |V| - 1 new edges. Every  edge  has  the
+    /// attribute `name` of type `f64` that holds the value of the minimum  cut  between
+    /// its endpoints in the caller. The minimum cut between any two vertices equals the
+    /// smallest value on the path between them in the tree, and removing that edge from
+    /// the tree splits the vertices into the two sides  of  such  a  cut.  Vertices  of
+    /// different connected components are joined by edges of value 0.
+    /// 
+    /// Directions of edges  are  ignored,  parallel  edges  add  up  their  capacities,
+    /// self-loops are ignored.
+    /// 
+    /// Uses  Gusfield's  algorithm  [[source](https://doi.org/10.1137/0219009)],  which
+    /// needs only |V| - 1 maximum flow computations (with  Dinic's  algorithm)  and  no
+    /// contraction of vertices. The given [`ExecutionControl`] is checked  before  each
+    /// maximum flow computation, an error of kind [`Cancelled`][cancelled] is  returned
+    /// if the computation is cancelled.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³ · |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn gomory_hu_tree<CapacityFnType>(&self, capacity_fn: CapacityFnType, name: &str, control: &ExecutionControl) -> CrabNetsResult<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Undirected, VertexAttributeCollectionType, VertexIdType>>
+    where
+        CapacityFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::gomory_hu_tree";
+        let mut ids: Vec<VertexIdType> = self.iter_v().collect();
+        ids.sort();
+        let indices: HashMap<&VertexIdType, usize> = ids.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut network = FlowNetwork::new(ids.len());
+        for edge in self.iter_e().filter(|x| x.id1 != x.id2) {
+            let capacity = capacity_fn(&edge.id1, &edge.id2, &edge.edge_id);
+            if capacity.is_nan() || capacity < 0f64 {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: edge.id1.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight: capacity }));
+            }
+            network.push_arcs(indices[&edge.id1], indices[&edge.id2], capacity, capacity);
+        }
+        // Gusfield's algorithm: `parents[i]` and `values[i]` describe the tree edge from
+        // vertex `i`
+        let mut parents = vec![0; ids.len()];
+        let mut values = vec![0f64; ids.len()];
+        for s in 1..ids.len() {
+            control.check(FUNCTION_PATH)?;
+            let t = parents[s];
+            let (value, source_side) = network.max_flow(s, t);
+            values[s] = value;
+            for i in 0..ids.len() {
+                if i != s && source_side[i] && parents[i] == t {
+                    parents[i] = s;
+                }
+            }
+            if source_side[parents[t]] {
+                parents[s] = parents[t];
+                parents[t] = s;
+                values[s] = values[t];
+                values[t] = value;
+            }
+        }
+        let mut answer: Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Undirected, VertexAttributeCollectionType, VertexIdType> = Graph::new();
+        for id in ids.iter() {
+            answer.add_v(Some(id.clone()));
+            *answer.v_attrs_mut(id)? = self.v_attrs(id)?.clone();
+        }
+        for i in 1..ids.len() {
+            let edge_id = answer.add_e(&ids[i], &ids[parents[i]], false, None)?;
+            answer.e_attrs_mut(&ids[i], &ids[parents[i]], &edge_id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value: StaticDispatchAttributeValue::Float64(values[i]) });
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CONNECTIVITY NUMBERS                                                              *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Vertex IDs in ascending order and the arcs between their positions that can be
+    // traversed, self-loops are skipped, parallel arcs are kept
+    fn arcs_of(&self) -> (Vec<VertexIdType>, Vec<(usize, usize)>) {
+        let mut ids: Vec<VertexIdType> = self.iter_v().collect();
+        ids.sort();
+        let indices: HashMap<&VertexIdType, usize> = ids.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut arcs = Vec::new();
+        for edge in self.iter_e().filter(|x| x.id1 != x.id2) {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            match edge.direction {
+                EdgeDirection::Directed1to2 => arcs.push((i, j)),
+                EdgeDirection::Directed2to1 => arcs.push((j, i)),
+                EdgeDirection::Undirected => arcs.extend([(i, j), (j, i)]),
+            }
+        }
+        (ids, arcs)
+    }
+
+    /// # Edge connectivity
+    /// 
+    /// ## Description
+    /// Find the smallest number of edges whose removal disconnects the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if  the  computation  wasn't
+    /// cancelled, `value` in this case is  the  edge  connectivity  λ(G);  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Edges respect their directions, so for  [directed][kinds]  graphs  λ(G)  is  the
+    /// smallest number of edges whose removal makes the graph not  strongly  connected.
+    /// Parallel edges are counted separately, self-loops are ignored. Graphs with fewer
+    /// than 2 vertices and disconnected graphs have λ(G) = 0.
+    /// 
+    /// Every minimum cut separates the first vertex from some other vertex, so λ(G)  is
+    /// the smallest of the maximum flows with unit capacities from the first vertex  to
+    /// every other vertex  and  back  [[source](https://doi.org/10.1137/0204043)].  The
+    /// given [`ExecutionControl`] is checked before each maximum flow computation.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · |E| · min(|V|^(2/3), |E|^(1/2))).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn edge_connectivity(&self, control: &ExecutionControl) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "Graph::Graph::edge_connectivity";
+        let (ids, arcs) = self.arcs_of();
+        if ids.len() < 2 {
+            return Ok(0);
+        }
+        let mut network = FlowNetwork::new(ids.len());
+        for &(i, j) in arcs.iter() {
+            network.push_arcs(i, j, 1f64, 0f64);
+        }
+        let is_directed = self.iter_e().any(|x| x.direction != EdgeDirection::Undirected);
+        let mut answer = usize::MAX;
+        for v in 1..ids.len() {
+            for (source, target) in [(0, v), (v, 0)].into_iter().take(if is_directed { 2 } else { 1 }) {
+                control.check(FUNCTION_PATH)?;
+                answer = answer.min(network.max_flow(source, target).0.round() as usize);
+                if answer == 0 {
+                    return Ok(0);
+                }
+            }
+        }
+        Ok(answer)
+    }
+
+    /// # Check if graph is k-connected
+    /// 
+    /// ## Description
+    /// Check if the graph stays connected after the removal of any k - 1 vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `k` : `usize` - the required vertex connectivity.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<bool>` - `Ok(value)` is returned  if  the  computation  wasn't
+    /// cancelled, `value` in this case is `true` if κ(G) ≥ `k` and  `false`  otherwise;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::vertex_connectivity`].
+    /// 
+    /// ## Complexity
+    /// Time: O(`k` · |V| · |E| · |V|^(1/2)).
+    /// 
+    /// Space: O(|V| + |E|).
+    pub fn is_k_connected(&self, k: usize, control: &ExecutionControl) -> CrabNetsResult<bool> {
+        Ok(self.vertex_connectivity_of(Some(k), control, "Graph::Graph::is_k_connected")? >= k)
+    }
+
+    /// # Check if graph is k-edge-connected
+    /// 
+    /// ## Description
+    /// Check if the graph stays connected after the removal of any k - 1 edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `k` : `usize` - the required edge connectivity.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<bool>` - `Ok(value)` is returned  if  the  computation  wasn't
+    /// cancelled, `value` in this case is `true` if λ(G) ≥ `k` and  `false`  otherwise;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::edge_connectivity`].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · |E| · min(|V|^(2/3), |E|^(1/2))).
+    /// 
+    /// Space: O(|V| + |E|).
+    pub fn is_k_edge_connected(&self, k: usize, control: &ExecutionControl) -> CrabNetsResult<bool> {
+        Ok(self.edge_connectivity(control)? >= k)
+    }
+
+    /// # Vertex connectivity
+    /// 
+    /// ## Description
+    /// Find the smallest number of vertices whose removal disconnects the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if  the  computation  wasn't
+    /// cancelled, `value` in this case is the vertex  connectivity  κ(G);  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Edges respect their directions, so for  [directed][kinds]  graphs  κ(G)  is  the
+    /// smallest  number  of  vertices  whose  removal  makes  the  graph  not  strongly
+    /// connected. If every vertex can be reached from every other vertex  by  a  single
+    /// edge, no set of vertices separates the rest, and κ(G) = |V| - 1  by  convention.
+    /// Graphs with fewer than 2 vertices and disconnected graphs have κ(G) = 0.
+    /// 
+    /// Uses Even's algorithm [[source](https://doi.org/10.1137/0204043)]:  each  vertex
+    /// is split into an entry and an exit joined  by  an  arc  of  unit  capacity,  and
+    /// maximum flows are computed from each of the first  κ(G)  +  1  vertices  to  the
+    /// non-adjacent vertices after it. The given [`ExecutionControl`] is checked before
+    /// each maximum flow computation.
+    /// 
+    /// ## Complexity
+    /// Time: O(κ(G) · |V| · |E| · |V|^(1/2)).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn vertex_connectivity(&self, control: &ExecutionControl) -> CrabNetsResult<usize> {
+        self.vertex_connectivity_of(None, control, "Graph::Graph::vertex_connectivity")
+    }
+
+    // Vertex connectivity computed by Even's algorithm, if `k` is given, the
+    // computation stops as soon as it's known whether the answer is less than `k`, and
+    // the returned value is only guaranteed to be on the correct side of `k`
+    fn vertex_connectivity_of(&self, k: Option<usize>, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<usize> {
+        let (ids, arcs) = self.arcs_of();
+        let n = ids.len();
+        if n < 2 {
+            return Ok(0);
+        }
+        // Vertex `i` enters at node `2 * i` and leaves at node `2 * i + 1`
+        let mut network = FlowNetwork::new(2 * n);
+        for i in 0..n {
+            network.push_arcs(2 * i, 2 * i + 1, 1f64, 0f64);
+        }
+        for &(i, j) in arcs.iter() {
+            network.push_arcs(2 * i + 1, 2 * j, n as f64, 0f64);
+        }
+        let adjacent_pairs: HashSet<(usize, usize)> = arcs.into_iter().collect();
+        let is_directed = self.iter_e().any(|x| x.direction != EdgeDirection::Undirected);
+        let mut answer = n - 1;
+        // Among the first κ(G) + 1 vertices, at least one lies outside a minimum
+        // separator
+        let mut i = 0;
+        while i <= answer && k.is_none_or(|k| i < k) {
+            for j in i + 1..n {
+                for (source, target) in [(i, j), (j, i)].into_iter().take(if is_directed { 2 } else { 1 }) {
+                    if adjacent_pairs.contains(&(source, target)) {
+                        continue;
+                    }
+                    control.check(function_path)?;
+                    answer = answer.min(network.max_flow(2 * source + 1, 2 * target).0.round() as usize);
+                    if k.is_some_and(|k| answer < k) {
+                        return Ok(answer);
+                    }
+                }
+            }
+            i += 1;
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::shortest_paths::ShortestPaths;
+    use super::*;
+
+    #[test]
+    fn gomory_hu_tree() {
+        let g: graph!(A ===A==> A) = "AE F8 Capacity\n\nV 0\nV 1\nV 2\nV 3\nV 4\nV 5\nV 6\n\nE 0 1 1.0\nE 0 2 7.0\nA 1 2 1.0\nE 1 3 3.0\nE 1 4 2.0\nE 2 4 4.0\nE 3 4 1.0\nE 3 5 6.0\nA 4 5 2.0\nE 4 5 1.5\nE 5 5 9.0\n".parse().unwrap();
+        let capacity_fn = |x: &usize, y: &usize, z: &usize| g.e_attrs(x, y, z).unwrap().io_query_contents("Capacity").unwrap().as_f64().unwrap();
+        let tree = g.gomory_hu_tree(capacity_fn, "Cut", &ExecutionControl::new()).unwrap();
+        assert_eq!((tree.count_v(), tree.count_e()), (7, 6));
+        let cut_fn = |x: &usize, y: &usize, z: &usize| tree.e_attrs(x, y, z).unwrap().io_query_contents("Cut").unwrap().as_f64().unwrap();
+        // The smallest cut value on the tree path matches the minimum cut found by brute
+        // force
+        for s in 0..7 {
+            for t in s + 1..7 {
+                let tree_cut = tree.bidirectional_bfs(&s, &t).unwrap().map(|path| path.edges().map(|(x, y, z)| cut_fn(x, y, z)).fold(f64::INFINITY, f64::min)).unwrap();
+                let brute_force_cut = (0..1 << 7).filter(|mask: &usize| mask >> s & 1 == 1 && mask >> t & 1 == 0)
+                    .map(|mask| g.iter_e().filter(|x| (mask >> x.id1 & 1) != (mask >> x.id2 & 1)).map(|x| capacity_fn(&x.id1, &x.id2, &x.edge_id)).sum::<f64>())
+                    .fold(f64::INFINITY, f64::min);
+                assert!((tree_cut - brute_force_cut).abs() < 1e-9);
+            }
+        }
+        assert!(g.gomory_hu_tree(|_, _, _| -1.0, "Cut", &ExecutionControl::new()).is_err());
+    }
+
+    #[test]
+    fn connectivity_numbers() {
+        let control = ExecutionControl::new();
+        let numbers = |g: &graph!(X ===X==> X)| (g.vertex_connectivity(&control).unwrap(), g.edge_connectivity(&control).unwrap());
+        // Two complete graphs on 4 vertices joined by 2 disjoint edges
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..8 {
+            g.add_v(None);
+        }
+        for offset in [0, 4] {
+            for i in 0..4 {
+                for j in i + 1..4 {
+                    g.add_e(&(offset + i), &(offset + j), false, None).unwrap();
+                }
+            }
+        }
+        g.add_e(&3, &4, false, None).unwrap();
+        g.add_e(&2, &5, false, None).unwrap();
+        assert_eq!(numbers(&g), (2, 2));
+        assert!(g.is_k_connected(2, &control).unwrap() && !g.is_k_connected(3, &control).unwrap());
+        assert!(g.is_k_edge_connected(2, &control).unwrap() && !g.is_k_edge_connected(3, &control).unwrap());
+        g.add_v(None);
+        g.add_e(&8, &0, false, None).unwrap();
+        assert_eq!(numbers(&g), (1, 1));
+        g.add_v(None);
+        assert_eq!(numbers(&g), (0, 0));
+        assert!(g.is_k_connected(0, &control).unwrap() && !g.is_k_connected(1, &control).unwrap());
+        // Complete graphs have no separators, parallel edges only add to the edge
+        // connectivity
+        let mut h: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            h.add_v(None);
+        }
+        for i in 0..4 {
+            for j in i + 1..4 {
+                h.add_e(&i, &j, false, None).unwrap();
+            }
+        }
+        assert_eq!(numbers(&h), (3, 3));
+        h.add_e(&0, &1, false, None).unwrap();
+        assert_eq!(numbers(&h), (3, 3));
+        h.add_e(&2, &3, false, None).unwrap();
+        h.add_e(&0, &2, false, None).unwrap();
+        h.add_e(&1, &3, false, None).unwrap();
+        h.add_e(&0, &3, false, None).unwrap();
+        h.add_e(&1, &2, false, None).unwrap();
+        assert_eq!(numbers(&h), (3, 6));
+        // Directed cycles
+        let mut f: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            f.add_v(None);
+        }
+        for i in 0..4 {
+            f.add_e(&i, &((i + 1) % 4), true, None).unwrap();
+        }
+        assert_eq!(numbers(&f), (1, 1));
+        f.add_e(&1, &0, true, None).unwrap();
+        assert_eq!(numbers(&f), (1, 1));
+        for i in 1..4 {
+            f.add_e(&((i + 1) % 4), &i, true, None).unwrap();
+        }
+        assert_eq!(numbers(&f), (2, 2));
+        let single: graph!(X ===X==> X) = "V 0\n".parse().unwrap();
+        assert_eq!(numbers(&single), (0, 0));
+    }
+}
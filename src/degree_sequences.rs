@@ -0,0 +1,165 @@
+//! # Degree sequences module
+//! 
+//! ## Description
+//! This module contains [`is_graphical`] that checks whether a sequence of non-negative
+//! integers  is   the   degree   sequence   of   a   simple   undirected   graph,   and
+//! [`Graph::havel_hakimi`] that deterministically builds such a graph.
+//! 
+//! [`Graph`]: crate::Graph
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicMutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * DEGREE SEQUENCES                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Test if degree sequence is graphical
+/// 
+/// ## Description
+/// Check whether there is a simple undirected graph, i.e. one  without  parallel  edges
+/// and self-loops, whose vertices have the given degrees.
+/// 
+/// ## Arguments
+/// * `sequence` : `&[usize]` - an immutable reference to the degrees of vertices in any
+/// order.
+/// 
+/// ## Returns
+/// * `bool` - `true` if the sequence is graphical, `false` otherwise.
+/// 
+/// ## Details
+/// The sequence is tested against the Erdős–Gallai theorem: a sequence d1 ≥ d2 ≥ ...  ≥
+/// dn is graphical if and only if its sum is even and, for every k from 1 to  n,  d1  +
+/// ...   +   dk   ≤   k(k   -   1)   +   min(d(k+1),   k)   +   ...   +   min(dn,    k)
+/// [[source](https://doi.org/10.1016/S0012-365X(02)00783-1)].  The  empty  sequence  is
+/// graphical.
+/// 
+/// ## Complexity
+/// Time: O(n log n), where n is the length of the sequence.
+/// 
+/// Space: O(n).
+pub fn is_graphical(sequence: &[usize]) -> bool {
+    let mut degrees = sequence.to_vec();
+    degrees.sort_unstable_by(|x, y| y.cmp(x));
+    let n = degrees.len();
+    if degrees.iter().sum::<usize>() % 2 != 0 || degrees.first().is_some_and(|&x| x >= n) {
+        return false;
+    }
+    // `suffix_sums[i]` is the sum of all degrees starting from the `i`-th one
+    let mut suffix_sums = vec![0; n + 1];
+    for i in (0..n).rev() {
+        suffix_sums[i] = suffix_sums[i + 1] + degrees[i];
+    }
+    // All degrees before `bound` are at least `k`, all degrees after are less
+    let mut bound = n;
+    let mut prefix_sum = 0;
+    for k in 1..=n {
+        prefix_sum += degrees[k - 1];
+        while bound > 0 && degrees[bound - 1] < k {
+            bound -= 1;
+        }
+        let tail_start = bound.max(k);
+        let tail_sum = k * (tail_start - k) + suffix_sums[tail_start];
+        if prefix_sum > k * (k - 1) + tail_sum {
+            return false;
+        }
+    }
+    true
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Havel–Hakimi graph
+    /// 
+    /// ## Description
+    /// Build a simple undirected graph whose vertices have the given degrees.
+    /// 
+    /// ## Arguments
+    /// * `sequence` : `&[usize]` - an immutable reference to the degrees of vertices.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` - `Ok(value)` is returned if the sequence is graphical,
+    /// `value` in this  case  is  the  graph  whose  `i`-th  added  vertex  has  degree
+    /// `sequence[i]`; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The     graph     is     built      by      the      Havel–Hakimi      algorithm
+    /// [[source](https://doi.org/10.1137/0110037)]:  the  vertex   with   the   largest
+    /// remaining degree is connected to the vertices with the  next  largest  remaining
+    /// degrees, and this is repeated until all degrees are exhausted. Ties  are  broken
+    /// in favour of vertices that come earlier in the sequence, so the result is  fully
+    /// determined by the sequence. Vertices and edges get  automatic  IDs  and  default
+    /// attribute collections.
+    /// 
+    /// ## Complexity
+    /// Time: O(n² log n), where n is the length of the sequence.
+    /// 
+    /// Space: O(n + m), where m is the number of edges.
+    pub fn havel_hakimi(sequence: &[usize]) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::havel_hakimi";
+        if !is_graphical(sequence) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "sequence".to_string(), reason: String::from("The degree sequence is not graphical.") }));
+        }
+        let mut answer = Self::new();
+        let ids: Vec<VertexIdType> = sequence.iter().map(|_| answer.add_v(None)).collect();
+        // Pairs of remaining degrees and positions of vertices in the sequence
+        let mut remaining: Vec<(usize, usize)> = sequence.iter().copied().zip(0..).collect();
+        loop {
+            remaining.retain(|x| x.0 > 0);
+            remaining.sort_unstable_by(|x, y| y.0.cmp(&x.0).then(x.1.cmp(&y.1)));
+            let Some(&(degree, position)) = remaining.first() else {
+                break;
+            };
+            remaining[0].0 = 0;
+            for neighbour in remaining[1..=degree].iter_mut() {
+                neighbour.0 -= 1;
+                answer.add_e(&ids[position], &ids[neighbour.1], false, None)?;
+            }
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn havel_hakimi() {
+        assert!(is_graphical(&[]) && is_graphical(&[0, 0]) && is_graphical(&[1, 1]));
+        assert!(is_graphical(&[3, 3, 3, 3]) && is_graphical(&[2, 2, 2, 2, 2]) && is_graphical(&[1, 3, 2, 2, 1, 1]));
+        assert!(!is_graphical(&[1]) && !is_graphical(&[2, 2]) && !is_graphical(&[3, 3, 1, 1]) && !is_graphical(&[4, 4, 4, 1, 1]));
+        let sequence = [1, 3, 2, 2, 1, 1];
+        let g: graph!(X ---X--> X) = Graph::havel_hakimi(&sequence).unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (6, 5));
+        assert!((0..6).all(|x| g.v_degree(&x).unwrap() == sequence[x]));
+        assert!(g.iter_e().all(|x| x.id1 != x.id2 && x.direction == EdgeDirection::Undirected));
+        let h: graph!(X ---X--> X) = Graph::havel_hakimi(&sequence).unwrap();
+        let edges = |x: &graph!(X ---X--> X)| {
+            let mut answer: Vec<(usize, usize)> = x.iter_e().map(|y| (y.id1, y.id2)).collect();
+            answer.sort();
+            answer
+        };
+        assert_eq!(edges(&g), edges(&h));
+        assert!(<graph!(X ---X--> X)>::havel_hakimi(&[3, 3, 1, 1]).is_err());
+        assert_eq!(<graph!(X ---X--> X)>::havel_hakimi(&[]).unwrap().count_v(), 0);
+    }
+}
@@ -0,0 +1,219 @@
+//! # Diffusion module
+//! 
+//! ## Description
+//! This module contains [`Diffusion`]  trait  and  its  implementation  for  [`Graph`].
+//! Diffusion spreads a numeric signal defined on the vertices over  the  edges  of  the
+//! graph, which smooths the  signal  or  propagates  known  labels  to  the  unlabelled
+//! vertices.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::HashMap;
+use crate::{attributes::{AttributeCollection, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, io::{AttributeCollectionIO, AttributeToken}, BasicImmutableGraph, BasicMutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Diffusion operator
+/// 
+/// ## Description
+/// Operator applied  by  [`Diffusion::diffuse`].  Both  operators  repeatedly  mix  the
+/// current signal x with P x, where P averages the values of  the  neighbours  of  each
+/// vertex.
+/// 
+/// ## Variants
+/// * `Heat` - the heat kernel exp(-`time` L) x, where L = I -  P  is  the  random  walk
+/// Laplacian matrix, approximated by `steps` iterations x ← h P x + (1 - h) x with h  =
+/// `time` / `steps`, so `time` must not exceed `steps`.
+/// * `PersonalisedPageRank` - `iterations` iterations x ← `alpha` P x + (1  -  `alpha`)
+/// x₀, where x₀ is the initial signal and `alpha` is between 0 and  1,  the  iterations
+/// converge to the personalised PageRank of the signal,  i.e.  label  propagation  with
+/// restarts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffusionOperator {
+    Heat { time: f64, steps: usize },
+    PersonalisedPageRank { alpha: f64, iterations: usize },
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * DIFFUSION                                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Diffusion
+/// 
+/// ## Description
+/// Diffusion of per-vertex numeric signals over the graph.
+/// 
+/// Diffusion treats graphs as undirected: directions of  edges  are  ignored,  parallel
+/// edges strengthen the connection between their  endpoints,  self-loops  are  ignored.
+/// Isolated   vertices   keep   their   values.   All   functions   check   the   given
+/// [`ExecutionControl`]  before  each  iteration  and   return   an   error   of   kind
+/// [`Cancelled`][cancelled] if the computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait Diffusion<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Diffuse signal
+    /// 
+    /// ## Description
+    /// Apply the diffusion operator to the signal.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `values`  :  `&HashMap<VertexIdType, f64>`  -  the  initial  signal,  vertices
+    /// missing from the map get 0.
+    /// * `operator` : `DiffusionOperator` - the operator to apply.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, f64>>` - `Ok(value)` is returned if  all
+    /// keys of `values` are vertices of the graph, the  parameters  of  `operator`  are
+    /// valid and the computation wasn't cancelled,  `value`  in  this  case  maps  each
+    /// vertex to its diffused value; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|) per iteration.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn diffuse(&self, values: &HashMap<VertexIdType, f64>, operator: DiffusionOperator, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>>;
+}
+
+// Graph::Diffusion
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Diffusion<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn diffuse(&self, values: &HashMap<VertexIdType, f64>, operator: DiffusionOperator, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, f64>> {
+        const FUNCTION_PATH: &str = "Graph::Diffusion::diffuse";
+        let (iterations, alpha, restart) = match operator {
+            DiffusionOperator::Heat { time, steps } if time >= 0.0 && time <= steps as f64 => (steps, if steps > 0 { time / steps as f64 } else { 0.0 }, false),
+            DiffusionOperator::PersonalisedPageRank { alpha, iterations } if (0.0..=1.0).contains(&alpha) => (iterations, alpha, true),
+            _ => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "operator".to_string(), reason: format!("Invalid parameters of the diffusion operator {:?}.", operator) })),
+        };
+        let mut ids = self.iter_v().collect::<Vec<_>>();
+        ids.sort();
+        let indices = ids.iter().enumerate().map(|(index, id)| (id.clone(), index)).collect::<HashMap<_, _>>();
+        let mut initial = vec![0.0; ids.len()];
+        for (id, value) in values {
+            match indices.get(id) {
+                Some(index) => initial[*index] = *value,
+                None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() })),
+            }
+        }
+        let mut neighbours = vec![Vec::new(); ids.len()];
+        for edge in self.iter_e().filter(|edge| edge.id1 != edge.id2) {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            neighbours[i].push(j);
+            neighbours[j].push(i);
+        }
+        let mut current = initial.clone();
+        for _ in 0..iterations {
+            control.check(FUNCTION_PATH)?;
+            current = (0..ids.len()).map(|i| {
+                let average = match neighbours[i].is_empty() {
+                    true => current[i],
+                    false => neighbours[i].iter().map(|j| current[*j]).sum::<f64>() / neighbours[i].len() as f64,
+                };
+                alpha * average + (1.0 - alpha) * if restart { initial[i] } else { current[i] }
+            }).collect();
+        }
+        Ok(ids.into_iter().zip(current).collect())
+    }
+}
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Diffuse attribute
+    /// 
+    /// ## Description
+    /// Apply the diffusion operator to a numeric vertex attribute and write the  result
+    /// into another (or the same) vertex attribute.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `source` : `&str` - the name of the attribute holding the initial signal.
+    /// * `target` : `&str` - the name of the attribute to write the result into.
+    /// * `operator` : `DiffusionOperator` - the operator to apply.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if  all  values  of  `source`  are
+    /// numbers, the parameters of `operator`  are  valid  and  the  computation  wasn't
+    /// cancelled; `Err(_)` is returned otherwise, the attributes of the graph are  left
+    /// intact in this case.
+    /// 
+    /// ## Details
+    /// Vertices that don't have the `source` attribute start with 0, which is the usual
+    /// convention for unlabelled vertices in label propagation. Every vertex  gets  the
+    /// `target` attribute of type `f64`.
+    pub fn diffuse_attribute(&mut self, source: &str, target: &str, operator: DiffusionOperator, control: &ExecutionControl) -> CrabNetsResult<()> {
+        const FUNCTION_PATH: &str = "Graph::Graph::diffuse_attribute";
+        let mut values = HashMap::new();
+        for id in self.iter_v() {
+            if let Some(value) = self.v_attrs(&id)?.io_query_contents(source) {
+                match value.as_f64() {
+                    Some(value) => values.insert(id, value),
+                    None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: source.to_string() })),
+                };
+            }
+        }
+        for (id, value) in self.diffuse(&values, operator, control)? {
+            self.v_attrs_mut(&id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name: target, value: StaticDispatchAttributeValue::Float64(value) });
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn diffusion() {
+        let mut g: graph!(A ---X--- A) = "AV F8 Label\n\nV 0 1.0\nV 1 0.0\nV 2 0.0\n\nE 0 1\nE 1 2\n".parse().unwrap();
+        let control = ExecutionControl::new();
+        let values = HashMap::from([(0, 1.0)]);
+        let ppr = g.diffuse(&values, DiffusionOperator::PersonalisedPageRank { alpha: 0.5, iterations: 1 }, &control).unwrap();
+        assert_eq!((0..3).map(|id| ppr[&id]).collect::<Vec<_>>(), vec![0.5, 0.25, 0.0]);
+        let heat = g.diffuse(&values, DiffusionOperator::Heat { time: 1.0, steps: 2 }, &control).unwrap();
+        assert_eq!((0..3).map(|id| heat[&id]).collect::<Vec<_>>(), vec![0.375, 0.25, 0.125]);
+        assert!(g.diffuse(&values, DiffusionOperator::Heat { time: 2.0, steps: 1 }, &control).is_err());
+        assert!(g.diffuse(&values, DiffusionOperator::PersonalisedPageRank { alpha: 1.5, iterations: 1 }, &control).is_err());
+        assert!(g.diffuse(&HashMap::from([(7, 1.0)]), DiffusionOperator::Heat { time: 1.0, steps: 1 }, &control).is_err());
+        g.diffuse_attribute("Label", "Smoothed", DiffusionOperator::Heat { time: 1.0, steps: 2 }, &control).unwrap();
+        assert_eq!(g.v_attrs(&2).unwrap().io_query_contents("Smoothed").and_then(|value| value.as_f64()), Some(0.125));
+        assert_eq!(g.v_attrs(&0).unwrap().io_query_contents("Label").and_then(|value| value.as_f64()), Some(1.0));
+    }
+}
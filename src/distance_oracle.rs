@@ -0,0 +1,453 @@
+//! # Distance oracle module
+//! 
+//! ## Description
+//! This module contains [`DistanceOracle`], a preprocessed index of  a  [`Graph`]  that
+//! answers point-to-point distance queries much faster than Dijkstra's  algorithm,  and
+//! [`Graph::distance_oracle`] that builds it.
+//! 
+//! The oracle implements the ALT technique  (A*,  landmarks  and  triangle  inequality)
+//! [[source](https://doi.org/10.5555/1070432.1070455)]: distances from  and  to  a  few
+//! landmark vertices are computed in advance and give lower  bounds  on  the  remaining
+//! distance that steer the search towards the target. The oracle keeps its own copy  of
+//! the weighted structure of the graph, so it stays valid and consistent  even  if  the
+//! graph   is   changed   or   dropped   afterwards,   and   can    be    saved    with
+//! [`DistanceOracle::into_bytes`]  and   loaded   with   [`DistanceOracle::from_bytes`]
+//! instead of being rebuilt.
+//! 
+//! [`Graph`]: crate::Graph
+use std::{collections::{BinaryHeap, HashMap}, str::FromStr};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, shortest_paths::HeapItem, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// First bytes of every serialised oracle
+const MAGIC: &[u8; 4] = b"CNDO";
+// Version of the serialisation format
+const VERSION: u8 = 1;
+
+
+
+// Distances from the source to all vertices numbered from 0, unreachable vertices get
+// infinity
+fn dijkstra_of(adjacency: &[Vec<(usize, f64)>], source: usize) -> Vec<f64> {
+    let mut answer = vec![f64::INFINITY; adjacency.len()];
+    answer[source] = 0f64;
+    let mut heap = BinaryHeap::from([HeapItem { distance: 0f64, id: source }]);
+    while let Some(HeapItem { distance, id }) = heap.pop() {
+        if distance > answer[id] {
+            continue;
+        }
+        for &(adjacent_id, weight) in adjacency[id].iter() {
+            if distance + weight < answer[adjacent_id] {
+                answer[adjacent_id] = distance + weight;
+                heap.push(HeapItem { distance: distance + weight, id: adjacent_id });
+            }
+        }
+    }
+    answer
+}
+
+
+
+// Little-endian reader of the fields of a serialised oracle
+struct FieldReader<'a> {
+    bytes: &'a [u8],
+    function_path: &'a str,
+}
+
+impl<'a> FieldReader<'a> {
+    fn error(&self, message: &str) -> CrabNetsError {
+        CrabNetsError::new(self.function_path, ErrorKind::CorruptedData { reason: format!("Malformed distance oracle: {}.", message) })
+    }
+
+    fn take(&mut self, length: usize) -> CrabNetsResult<&'a [u8]> {
+        if self.bytes.len() < length {
+            return Err(self.error("unexpected end of data"));
+        }
+        let (answer, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(answer)
+    }
+
+    fn f64(&mut self) -> CrabNetsResult<f64> {
+        Ok(f64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn usize(&mut self) -> CrabNetsResult<usize> {
+        let value = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        usize::try_from(value).map_err(|_| self.error("value is too large"))
+    }
+
+    fn index(&mut self, vertex_count: usize) -> CrabNetsResult<usize> {
+        match self.usize()? {
+            index if index < vertex_count => Ok(index),
+            _ => Err(self.error("vertex index is out of range")),
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * DISTANCE ORACLE                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Distance oracle
+/// 
+/// ## Description
+/// Preprocessed index of a graph answering point-to-point distance  queries,  built  by
+/// [`Graph::distance_oracle`].
+/// 
+/// ## Details
+/// Vertices inside the oracle are numbered by their positions  in  ascending  order  of
+/// IDs. The oracle respects the directions of  edges  the  same  way  [`ShortestPaths`]
+/// does, among parallel edges, the lightest one is used.
+/// 
+/// [`ShortestPaths`]: crate::shortest_paths::ShortestPaths
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceOracle<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    adjacency: Vec<Vec<(usize, f64)>>,
+    // Distances from each landmark to all vertices
+    distances_from: Vec<Vec<f64>>,
+    // Distances from all vertices to each landmark
+    distances_to: Vec<Vec<f64>>,
+    ids: Vec<VertexIdType>,
+    indices: HashMap<VertexIdType, usize>,
+    landmarks: Vec<usize>,
+}
+
+// DistanceOracle::DistanceOracle
+impl<VertexIdType> DistanceOracle<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    // Assemble the oracle and compute the distances from and to the chosen landmarks,
+    // each next landmark is the vertex farthest from the already chosen ones
+    fn build(ids: Vec<VertexIdType>, adjacency: Vec<Vec<(usize, f64)>>, landmark_count: usize, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<Self> {
+        let mut reversed = vec![Vec::new(); adjacency.len()];
+        for (id, arcs) in adjacency.iter().enumerate() {
+            for &(adjacent_id, weight) in arcs.iter() {
+                reversed[adjacent_id].push((id, weight));
+            }
+        }
+        let indices = ids.iter().enumerate().map(|(index, id)| (id.clone(), index)).collect();
+        let mut answer = Self { adjacency, distances_from: Vec::new(), distances_to: Vec::new(), ids, indices, landmarks: Vec::new() };
+        let mut separation = vec![f64::INFINITY; answer.ids.len()];
+        while answer.landmarks.len() < landmark_count.min(answer.ids.len()) {
+            control.check(function_path)?;
+            let landmark = (0..answer.ids.len()).filter(|x| !answer.landmarks.contains(x)).max_by(|x, y| separation[*x].total_cmp(&separation[*y]).then(y.cmp(x))).unwrap();
+            answer.distances_from.push(dijkstra_of(&answer.adjacency, landmark));
+            answer.distances_to.push(dijkstra_of(&reversed, landmark));
+            for (vertex, value) in separation.iter_mut().enumerate() {
+                *value = value.min(answer.distances_from.last().unwrap()[vertex] + answer.distances_to.last().unwrap()[vertex]);
+            }
+            answer.landmarks.push(landmark);
+        }
+        Ok(answer)
+    }
+
+    // Lower bound on the distance from the vertex to the target given by the landmarks
+    fn lower_bound(&self, vertex: usize, target: usize) -> f64 {
+        let mut answer = 0f64;
+        for (from, to) in self.distances_from.iter().zip(self.distances_to.iter()) {
+            if from[target].is_finite() && from[vertex].is_finite() {
+                answer = answer.max(from[target] - from[vertex]);
+            }
+            if to[vertex].is_finite() && to[target].is_finite() {
+                answer = answer.max(to[vertex] - to[target]);
+            }
+        }
+        answer
+    }
+
+    /// # Query distance
+    /// 
+    /// ## Description
+    /// Find the total weight of the shortest path from one vertex to another.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `target` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<f64>>` - `Ok(Some(value))` is returned if the target is
+    /// reachable from the source, `value` in this case  is  the  total  weight  of  the
+    /// shortest path from `source` to `target`; `Ok(None)` is returned if the target is
+    /// unreachable; `Err(_)` is returned if either of the vertices wasn't in the  graph
+    /// when the oracle was built.
+    /// 
+    /// ## Details
+    /// The query runs A* search guided by the lower bounds derived from  the  distances
+    /// to and from the landmarks. Targets  unreachable  from  the  source  are  usually
+    /// detected by the landmarks without any search.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|) in the worst case, queries on road networks with 16
+    /// landmarks typically visit a small fraction of the vertices.
+    /// 
+    /// Space: O(|V|).
+    pub fn distance(&self, source: &VertexIdType, target: &VertexIdType) -> CrabNetsResult<Option<f64>> {
+        const FUNCTION_PATH: &str = "DistanceOracle::DistanceOracle::distance";
+        let index = |id: &VertexIdType| self.indices.get(id).copied().ok_or_else(|| CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }));
+        let (source, target) = (index(source)?, index(target)?);
+        let unreachable = self.distances_from.iter().any(|x| x[source].is_finite() && x[target].is_infinite()) || self.distances_to.iter().any(|x| x[target].is_finite() && x[source].is_infinite());
+        if unreachable {
+            return Ok(None);
+        }
+        let mut distances = HashMap::from([(source, 0f64)]);
+        let mut lower_bounds = HashMap::new();
+        let mut heap = BinaryHeap::from([HeapItem { distance: self.lower_bound(source, target), id: source }]);
+        while let Some(HeapItem { distance: estimate, id }) = heap.pop() {
+            let distance = distances[&id];
+            if id == target {
+                return Ok(Some(distance));
+            }
+            if estimate > distance + *lower_bounds.entry(id).or_insert_with(|| self.lower_bound(id, target)) {
+                continue;
+            }
+            for &(adjacent_id, weight) in self.adjacency[id].iter() {
+                let next_distance = distance + weight;
+                if distances.get(&adjacent_id).is_none_or(|x| next_distance < *x) {
+                    distances.insert(adjacent_id, next_distance);
+                    let lower_bound = *lower_bounds.entry(adjacent_id).or_insert_with(|| self.lower_bound(adjacent_id, target));
+                    heap.push(HeapItem { distance: next_distance + lower_bound, id: adjacent_id });
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// # Encode oracle
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<u8>` - the binary representation of the oracle that can  be  loaded  with
+    /// [`DistanceOracle::from_bytes`].
+    /// 
+    /// ## Details
+    /// Vertex IDs are stored as their string representations,  numbers  are  stored  in
+    /// little-endian byte order.
+    pub fn into_bytes(&self) -> Vec<u8> {
+        let mut answer = MAGIC.to_vec();
+        answer.push(VERSION);
+        let write_usize = |answer: &mut Vec<u8>, value: usize| answer.extend((value as u64).to_le_bytes());
+        write_usize(&mut answer, self.ids.len());
+        for id in self.ids.iter() {
+            let id = id.to_string();
+            write_usize(&mut answer, id.len());
+            answer.extend(id.as_bytes());
+        }
+        for arcs in self.adjacency.iter() {
+            write_usize(&mut answer, arcs.len());
+            for &(adjacent_id, weight) in arcs.iter() {
+                write_usize(&mut answer, adjacent_id);
+                answer.extend(weight.to_le_bytes());
+            }
+        }
+        write_usize(&mut answer, self.landmarks.len());
+        for (landmark, (from, to)) in self.landmarks.iter().zip(self.distances_from.iter().zip(self.distances_to.iter())) {
+            write_usize(&mut answer, *landmark);
+            answer.extend(from.iter().chain(to.iter()).flat_map(|x| x.to_le_bytes()));
+        }
+        answer
+    }
+
+    /// # Landmarks
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<&VertexIdType>` - the IDs of the landmarks in the order they were chosen.
+    pub fn landmarks(&self) -> Vec<&VertexIdType> {
+        self.landmarks.iter().map(|x| &self.ids[*x]).collect()
+    }
+}
+
+// DistanceOracle::DistanceOracle
+impl<VertexIdType> DistanceOracle<VertexIdType>
+where
+    VertexIdType: FromStr + Id,
+{
+    /// # Decode oracle
+    /// 
+    /// ## Arguments
+    /// * `bytes` : `&[u8]` - the binary representation of the oracle.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<DistanceOracle<VertexIdType>>` - `Ok(value)`  is  returned  if
+    /// the oracle was decoded  successfully,  `value`  in  this  case  is  the  oracle;
+    /// `Err(_)` is returned otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "DistanceOracle::DistanceOracle::from_bytes";
+        let mut reader = FieldReader { bytes, function_path: FUNCTION_PATH };
+        if reader.take(MAGIC.len())? != MAGIC || reader.take(1)?[0] != VERSION {
+            return Err(reader.error("unknown format or version"));
+        }
+        let vertex_count = reader.usize()?;
+        let mut ids = Vec::new();
+        let mut indices = HashMap::new();
+        for index in 0..vertex_count {
+            let length = reader.usize()?;
+            let id = std::str::from_utf8(reader.take(length)?).ok().and_then(|x| x.parse::<VertexIdType>().ok()).ok_or_else(|| reader.error("invalid vertex ID"))?;
+            if indices.insert(id.clone(), index).is_some() {
+                return Err(reader.error("duplicate vertex ID"));
+            }
+            ids.push(id);
+        }
+        let mut adjacency = Vec::new();
+        for _ in 0..vertex_count {
+            let arc_count = reader.usize()?;
+            let mut arcs = Vec::new();
+            for _ in 0..arc_count {
+                arcs.push((reader.index(vertex_count)?, reader.f64()?));
+            }
+            adjacency.push(arcs);
+        }
+        let landmark_count = reader.usize()?;
+        let (mut landmarks, mut distances_from, mut distances_to) = (Vec::new(), Vec::new(), Vec::new());
+        for _ in 0..landmark_count {
+            landmarks.push(reader.index(vertex_count)?);
+            distances_from.push((0..vertex_count).map(|_| reader.f64()).collect::<CrabNetsResult<Vec<_>>>()?);
+            distances_to.push((0..vertex_count).map(|_| reader.f64()).collect::<CrabNetsResult<Vec<_>>>()?);
+        }
+        if !reader.bytes.is_empty() {
+            return Err(reader.error("unexpected data after the end"));
+        }
+        Ok(Self { adjacency, distances_from, distances_to, ids, indices, landmarks })
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Build distance oracle
+    /// 
+    /// ## Description
+    /// Preprocess the graph for fast point-to-point distance queries.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `landmark_count` : `usize` - the number  of  landmarks,  more  landmarks  give
+    /// tighter lower bounds and faster queries at the cost of memory and  preprocessing
+    /// time, 8 to 16 landmarks work well for road networks.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<DistanceOracle<VertexIdType>>` - `Ok(value)`  is  returned  if
+    /// all weights are valid and the computation wasn't cancelled, `value` in this case
+    /// is the oracle; `Err(_)` is returned if a negative or NaN weight  is  encountered
+    /// or the computation is cancelled.
+    /// 
+    /// ## Details
+    /// The first landmark is the vertex with the smallest ID,  each  next  one  is  the
+    /// vertex farthest from the already chosen landmarks. The function checks the given
+    /// [`ExecutionControl`] before processing each landmark.
+    /// 
+    /// ## Complexity
+    /// Time: O(`landmark_count` (|V| + |E|) log |V|).
+    /// 
+    /// Space: O(`landmark_count` |V| + |E|).
+    pub fn distance_oracle<WeightFnType>(&self, landmark_count: usize, weight_fn: WeightFnType, control: &ExecutionControl) -> CrabNetsResult<DistanceOracle<VertexIdType>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::distance_oracle";
+        let mut ids = self.iter_v().collect::<Vec<_>>();
+        ids.sort();
+        let indices = ids.iter().enumerate().map(|(index, id)| (id.clone(), index)).collect::<HashMap<_, _>>();
+        let mut adjacency = Vec::with_capacity(ids.len());
+        for id in ids.iter() {
+            let mut lightest = HashMap::new();
+            for edge in self.iter_traversable_e(id, true) {
+                let weight = weight_fn(id, &edge.id2, &edge.edge_id);
+                if weight.is_nan() || weight < 0f64 {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: id.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                }
+                let entry = lightest.entry(indices[&edge.id2]).or_insert(weight);
+                *entry = entry.min(weight);
+            }
+            let mut arcs = lightest.into_iter().collect::<Vec<_>>();
+            arcs.sort_by_key(|x| x.0);
+            adjacency.push(arcs);
+        }
+        DistanceOracle::build(ids, adjacency, landmark_count, control, FUNCTION_PATH)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::shortest_paths::ShortestPaths;
+    use super::*;
+
+    #[test]
+    fn distance_oracle() {
+        // Directed 5x5 grid with varying weights and a separate vertex 25
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..26 {
+            g.add_v(None);
+        }
+        for i in 0..25 {
+            if i % 5 < 4 {
+                g.add_e(&i, &(i + 1), i % 3 != 0, None).unwrap();
+            }
+            if i < 20 {
+                g.add_e(&i, &(i + 5), true, None).unwrap();
+            }
+        }
+        let weight_fn = |x: &usize, y: &usize, _: &u8| 1.0 + ((x * 7 + y * 3) % 5) as f64;
+        let control = ExecutionControl::new();
+        let oracle = g.distance_oracle(4, weight_fn, &control).unwrap();
+        assert_eq!(oracle.landmarks().len(), 4);
+        assert_eq!(oracle.landmarks()[0], &0);
+        let decoded = DistanceOracle::<usize>::from_bytes(&oracle.into_bytes()).unwrap();
+        assert_eq!(decoded, oracle);
+        for source in 0..26 {
+            for target in 0..26 {
+                let expected = g.bidirectional_dijkstra(&source, &target, weight_fn).unwrap().map(|x| x.0);
+                assert_eq!(oracle.distance(&source, &target).unwrap(), expected);
+                assert_eq!(decoded.distance(&source, &target).unwrap(), expected);
+            }
+        }
+        assert!(oracle.distance(&0, &26).is_err());
+        assert!(g.distance_oracle(4, |_, _, _| -1.0, &control).is_err());
+        let bytes = oracle.into_bytes();
+        assert!(DistanceOracle::<usize>::from_bytes(&bytes[..bytes.len() - 1]).is_err_and(|x| matches!(x.kind(), ErrorKind::CorruptedData { .. })));
+        assert!(DistanceOracle::<usize>::from_bytes(b"CNDO\x02").is_err());
+    }
+}
@@ -0,0 +1,539 @@
+//! # Dynamics module
+//! 
+//! ## Description
+//! This module contains [`Dynamics`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use itertools::Itertools;
+use rand::Rng;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, shortest_paths::HeapItem, BasicImmutableGraph, EdgeIteratorItem, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Epidemic model
+/// 
+/// ## Description
+/// Compartmental model simulated  by  [`Dynamics::simulate_epidemic`].  In  each  step,
+/// every infected vertex infects each of its susceptible neighbours with the  infection
+/// probability, then every vertex that was  infected  at  the  beginning  of  the  step
+/// recovers with the recovery probability.
+/// 
+/// ## Variants
+/// * `SI` - infected vertices stay infected forever.
+/// * `SIS` - recovered vertices become susceptible again.
+/// * `SIR` - recovered vertices are immune and never get infected again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EpidemicModel {
+    SI { infection_probability: f64 },
+    SIS { infection_probability: f64, recovery_probability: f64 },
+    SIR { infection_probability: f64, recovery_probability: f64 },
+}
+
+/// # Cascade model
+/// 
+/// ## Description
+/// Influence model simulated by [`Dynamics::simulate_cascade`].
+/// 
+/// ## Variants
+/// * `IndependentCascade` - each  newly  activated  vertex  gets  a  single  chance  to
+/// activate each of its inactive neighbours, the weight of the edge is the  probability
+/// of success.
+/// * `LinearThreshold` - each vertex draws a random threshold uniformly from [0, 1] and
+/// is activated once the total weight of edges  leading  to  it  from  active  vertices
+/// reaches the threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CascadeModel {
+    IndependentCascade,
+    LinearThreshold,
+}
+
+/// # State of a vertex in a spreading process
+/// 
+/// ## Description
+/// The compartment a vertex belongs to.
+/// 
+/// ## Variants
+/// * `Susceptible` - the vertex can be infected (or activated).
+/// * `Infected` - the vertex is infected (or active) and spreads further.
+/// * `Recovered` - the vertex is immune, only used by [`EpidemicModel::SIR`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpreadingState {
+    Susceptible,
+    Infected,
+    Recovered,
+}
+
+/// # Outcome of a spreading process
+/// 
+/// ## Description
+/// The result of [`Dynamics::simulate_epidemic`] and [`Dynamics::simulate_cascade`].
+/// 
+/// ## Fields
+/// * `ever_infected` - vertices that were infected at least once, including the seeds.
+/// * `states` - the final state of each vertex.
+/// * `steps` - the number of performed steps.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpreadingOutcome<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    pub ever_infected: HashSet<VertexIdType>,
+    pub states: HashMap<VertexIdType, SpreadingState>,
+    pub steps: usize,
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * DYNAMICS                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Dynamics
+/// 
+/// ## Description
+/// Stochastic simulations of processes spreading over the graph, such as epidemics  and
+/// the adoption of innovations, and the choice of vertices from which a process spreads
+/// best.
+/// 
+/// All functions respect the directions of edges: an infected vertex only spreads along
+/// edges that can be traversed from it,  each  of  parallel  edges  is  an  independent
+/// channel of spreading. Vertices and edges are processed in ascending order  of  their
+/// IDs, so a seeded  random  number  generator  (e.g.  [`CrabNetsRng::new(seed)`][rng])
+/// always gives the same outcome.
+/// 
+/// After the initial state and after each  step,  the  simulation  functions  pass  the
+/// number of the step (0 for the initial state) and the states of all vertices  to  the
+/// given callback. All functions check the given [`ExecutionControl`] before each  step
+/// of a simulation  and  return  an  error  of  kind  [`Cancelled`][cancelled]  if  the
+/// computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+/// [rng]: crate::rng::CrabNetsRng::new
+pub trait Dynamics<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Influence maximisation
+    /// 
+    /// ## Description
+    /// Choose the given number of seeds that maximise the expected number  of  vertices
+    /// activated by a cascade.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `model` : `CascadeModel` - the cascade model.
+    /// * `seed_count` : `usize` - the number of seeds to choose.
+    /// * `simulations` : `usize` - the number of  simulations  used  to  estimate  each
+    /// expected spread.
+    /// * `weight_fn` : `WeightFnType` - a function that receives the IDs of the  source
+    /// and the target of an edge and the ID of the edge, and returns the weight of  the
+    /// edge.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<(VertexIdType, f64)>>`  -  `Ok(value)`  is   returned   if
+    /// `simulations` is positive, all weights are  valid  and  the  computation  wasn't
+    /// cancelled, `value` in this case contains  the  chosen  seeds  in  the  order  of
+    /// choice, each together with the estimated  expected  spread  of  itself  and  all
+    /// previously chosen seeds; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The expected spread, i.e. the expected number of active vertices at the  end  of
+    /// the cascade (see [`Dynamics::simulate_cascade`]), is estimated by averaging over
+    /// `simulations` simulations. Seeds are  chosen  greedily,  each  time  taking  the
+    /// vertex with the largest marginal gain, which is at least 1 - 1/e of the  optimum
+    /// for both cascade models up to the estimation error. Since  marginal  gains  only
+    /// decrease as the seed set grows, outdated gains are  re-evaluated  lazily  as  in
+    /// CELF [[source](https://doi.org/10.1145/1281192.1281239)],  which  usually  saves
+    /// most of the simulations. If `seed_count` exceeds |V|, all vertices are chosen.
+    /// 
+    /// ## Complexity
+    /// Time: O(`seed_count` · |V| · `simulations` · (|V| + |E|)) in the worst case.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn maximise_influence<R, WeightFnType>(&self, model: CascadeModel, seed_count: usize, simulations: usize, weight_fn: WeightFnType, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<Vec<(VertexIdType, f64)>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+    /// # Simulate influence cascade
+    /// 
+    /// ## Description
+    /// Simulate the independent cascade or the linear threshold  model  until  no  more
+    /// vertices can be activated.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `model` : `CascadeModel` - the simulated model.
+    /// * `seeds` : `&[VertexIdType]` - an immutable reference to the  initially  active
+    /// vertices.
+    /// * `weight_fn` : `WeightFnType` - a function that receives the IDs of the  source
+    /// and the target of an edge and the ID of the edge, and returns the weight of  the
+    /// edge.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `callback` : `CallbackFnType` - a function that receives  the  number  of  the
+    /// step and the states of all vertices.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<SpreadingOutcome<VertexIdType>>` - `Ok(value)` is returned  if
+    /// all seeds exist, all weights are valid  and  the  simulation  wasn't  cancelled,
+    /// `value` in this case is the outcome of  the  simulation;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Active vertices are in state [`SpreadingState::Infected`] and never  deactivate.
+    /// For the independent  cascade,  weights  must  lie  in  [0, 1].  For  the  linear
+    /// threshold model [[source](https://doi.org/10.1145/956750.956769)], weights  must
+    /// be non-negative, a common choice is 1 divided by the number of edges leading  to
+    /// the target.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · C + |E|), where C is the complexity of `callback`.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn simulate_cascade<R, WeightFnType, CallbackFnType>(&self, model: CascadeModel, seeds: &[VertexIdType], weight_fn: WeightFnType, rng: &mut R, callback: CallbackFnType, control: &ExecutionControl) -> CrabNetsResult<SpreadingOutcome<VertexIdType>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+        CallbackFnType: FnMut(usize, &HashMap<VertexIdType, SpreadingState>);
+    /// # Simulate epidemic
+    /// 
+    /// ## Description
+    /// Simulate the SI, SIS or SIR model in discrete time.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `model` : `EpidemicModel` - the simulated model.
+    /// * `seeds` : `&[VertexIdType]` - an immutable reference to the initially infected
+    /// vertices.
+    /// * `max_steps` : `usize` - the maximum number of steps.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `callback` : `CallbackFnType` - a function that receives  the  number  of  the
+    /// step and the states of all vertices.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<SpreadingOutcome<VertexIdType>>` - `Ok(value)` is returned  if
+    /// all seeds exist, all probabilities lie  in  [0, 1]  and  the  simulation  wasn't
+    /// cancelled, `value` in this case is the outcome of the  simulation;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// The simulation stops after `max_steps` steps, when no vertex is infected or, for
+    /// the SI model, when no susceptible vertex can be infected anymore.
+    /// 
+    /// ## Complexity
+    /// Time: O(`max_steps` · (|V| + |E| + C)), where C is the complexity of `callback`.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn simulate_epidemic<R, CallbackFnType>(&self, model: EpidemicModel, seeds: &[VertexIdType], max_steps: usize, rng: &mut R, callback: CallbackFnType, control: &ExecutionControl) -> CrabNetsResult<SpreadingOutcome<VertexIdType>>
+    where
+        R: Rng,
+        CallbackFnType: FnMut(usize, &HashMap<VertexIdType, SpreadingState>);
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Edges are sorted, so that the same seed always gives the same outcome
+    fn sorted_traversable_e(&self, id: &VertexIdType) -> Vec<EdgeIteratorItem<EdgeIdType, VertexIdType>> {
+        self.iter_traversable_e(id, true).sorted_by(|x, y| (&x.id2, &x.edge_id).cmp(&(&y.id2, &y.edge_id))).collect()
+    }
+
+    fn initial_spreading_states(&self, seeds: &[VertexIdType], function_path: &str) -> CrabNetsResult<HashMap<VertexIdType, SpreadingState>> {
+        let mut states: HashMap<VertexIdType, SpreadingState> = self.iter_v().map(|x| (x, SpreadingState::Susceptible)).collect();
+        for seed in seeds {
+            match states.get_mut(seed) {
+                Some(state) => *state = SpreadingState::Infected,
+                None => return Err(CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: seed.to_string() })),
+            }
+        }
+        Ok(states)
+    }
+}
+
+// Graph::Dynamics
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Dynamics<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn maximise_influence<R, WeightFnType>(&self, model: CascadeModel, seed_count: usize, simulations: usize, weight_fn: WeightFnType, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<Vec<(VertexIdType, f64)>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Dynamics::maximise_influence";
+        if simulations == 0 {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "simulations".to_string(), reason: "At least 1 simulation is required to estimate the expected spread.".to_string() }));
+        }
+        let expected_spread = |seeds: &[VertexIdType], rng: &mut R| -> CrabNetsResult<f64> {
+            let mut total = 0usize;
+            for _ in 0..simulations {
+                control.check(FUNCTION_PATH)?;
+                total += self.simulate_cascade(model, seeds, &weight_fn, rng, |_, _| (), control)?.ever_infected.len();
+            }
+            Ok(total as f64 / simulations as f64)
+        };
+        // Gains are stored with negated sign, since the heap pops the smallest distance,
+        // together with the number of seeds they were computed for
+        let mut candidates = BinaryHeap::new();
+        for id in self.iter_v().sorted() {
+            let gain = expected_spread(std::slice::from_ref(&id), rng)?;
+            candidates.push(HeapItem { distance: -gain, id: (id, 0usize) });
+        }
+        let mut seeds = Vec::with_capacity(seed_count);
+        let mut answer = Vec::with_capacity(seed_count);
+        let mut spread = 0f64;
+        while seeds.len() < seed_count {
+            let Some(HeapItem { distance, id: (id, computed_for) }) = candidates.pop() else {
+                break;
+            };
+            if computed_for == seeds.len() {
+                spread -= distance;
+                seeds.push(id.clone());
+                answer.push((id, spread));
+                continue;
+            }
+            seeds.push(id);
+            let gain = expected_spread(&seeds, rng)? - spread;
+            let id = seeds.pop().unwrap();
+            candidates.push(HeapItem { distance: -gain, id: (id, seeds.len()) });
+        }
+        Ok(answer)
+    }
+
+    fn simulate_cascade<R, WeightFnType, CallbackFnType>(&self, model: CascadeModel, seeds: &[VertexIdType], weight_fn: WeightFnType, rng: &mut R, mut callback: CallbackFnType, control: &ExecutionControl) -> CrabNetsResult<SpreadingOutcome<VertexIdType>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+        CallbackFnType: FnMut(usize, &HashMap<VertexIdType, SpreadingState>),
+    {
+        const FUNCTION_PATH: &str = "Graph::Dynamics::simulate_cascade";
+        let mut states = self.initial_spreading_states(seeds, FUNCTION_PATH)?;
+        let thresholds: HashMap<VertexIdType, f64> = match model {
+            CascadeModel::IndependentCascade => HashMap::new(),
+            CascadeModel::LinearThreshold => self.iter_v().sorted().map(|x| (x, rng.gen::<f64>())).collect(),
+        };
+        let mut influences: HashMap<VertexIdType, f64> = HashMap::new();
+        let mut frontier: Vec<VertexIdType> = seeds.iter().cloned().sorted().dedup().collect();
+        let mut steps = 0;
+        callback(steps, &states);
+        while !frontier.is_empty() {
+            control.check(FUNCTION_PATH)?;
+            let mut activated = Vec::new();
+            for id in frontier.iter() {
+                for edge in self.sorted_traversable_e(id) {
+                    if states[&edge.id2] != SpreadingState::Susceptible {
+                        continue;
+                    }
+                    let weight = weight_fn(&edge.id1, &edge.id2, &edge.edge_id);
+                    match model {
+                        CascadeModel::IndependentCascade => {
+                            if !(0f64..=1f64).contains(&weight) {
+                                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: edge.id1.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                            }
+                            if rng.gen_bool(weight) {
+                                states.insert(edge.id2.clone(), SpreadingState::Infected);
+                                activated.push(edge.id2);
+                            }
+                        },
+                        CascadeModel::LinearThreshold => {
+                            if weight < 0f64 || weight.is_nan() {
+                                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: edge.id1.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                            }
+                            *influences.entry(edge.id2.clone()).or_insert(0f64) += weight;
+                            activated.push(edge.id2);
+                        },
+                    }
+                }
+            }
+            if model == CascadeModel::LinearThreshold {
+                activated = activated.into_iter().sorted().dedup().filter(|x| influences[x] >= thresholds[x]).collect();
+                for id in activated.iter() {
+                    states.insert(id.clone(), SpreadingState::Infected);
+                }
+            }
+            if activated.is_empty() {
+                break;
+            }
+            steps += 1;
+            callback(steps, &states);
+            frontier = activated.into_iter().sorted().collect();
+        }
+        let ever_infected = states.iter().filter(|x| *x.1 == SpreadingState::Infected).map(|x| x.0.clone()).collect();
+        Ok(SpreadingOutcome { ever_infected, states, steps })
+    }
+
+    fn simulate_epidemic<R, CallbackFnType>(&self, model: EpidemicModel, seeds: &[VertexIdType], max_steps: usize, rng: &mut R, mut callback: CallbackFnType, control: &ExecutionControl) -> CrabNetsResult<SpreadingOutcome<VertexIdType>>
+    where
+        R: Rng,
+        CallbackFnType: FnMut(usize, &HashMap<VertexIdType, SpreadingState>),
+    {
+        const FUNCTION_PATH: &str = "Graph::Dynamics::simulate_epidemic";
+        let (infection_probability, recovery_probability, recovered_state) = match model {
+            EpidemicModel::SI { infection_probability } => (infection_probability, 0f64, SpreadingState::Infected),
+            EpidemicModel::SIS { infection_probability, recovery_probability } => (infection_probability, recovery_probability, SpreadingState::Susceptible),
+            EpidemicModel::SIR { infection_probability, recovery_probability } => (infection_probability, recovery_probability, SpreadingState::Recovered),
+        };
+        for probability in [infection_probability, recovery_probability] {
+            if !(0f64..=1f64).contains(&probability) {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "model".to_string(), reason: format!("Probabilities of infection and recovery must lie in [0, 1], got {}.", probability) }));
+            }
+        }
+        let mut states = self.initial_spreading_states(seeds, FUNCTION_PATH)?;
+        let mut ever_infected: HashSet<VertexIdType> = seeds.iter().cloned().collect();
+        let mut steps = 0;
+        callback(steps, &states);
+        while steps < max_steps {
+            let infected: Vec<VertexIdType> = states.iter().filter(|x| *x.1 == SpreadingState::Infected).map(|x| x.0.clone()).sorted().collect();
+            if infected.is_empty() {
+                break;
+            }
+            control.check(FUNCTION_PATH)?;
+            let mut newly_infected = HashSet::new();
+            let mut exposed = false;
+            for id in infected.iter() {
+                for edge in self.sorted_traversable_e(id) {
+                    if states[&edge.id2] != SpreadingState::Susceptible || newly_infected.contains(&edge.id2) {
+                        continue;
+                    }
+                    exposed = true;
+                    if rng.gen_bool(infection_probability) {
+                        newly_infected.insert(edge.id2);
+                    }
+                }
+            }
+            if !exposed && matches!(model, EpidemicModel::SI { .. }) {
+                break;
+            }
+            for id in infected {
+                if recovery_probability > 0f64 && rng.gen_bool(recovery_probability) {
+                    states.insert(id, recovered_state);
+                }
+            }
+            for id in newly_infected {
+                states.insert(id.clone(), SpreadingState::Infected);
+                ever_infected.insert(id);
+            }
+            steps += 1;
+            callback(steps, &states);
+        }
+        Ok(SpreadingOutcome { ever_infected, states, steps })
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn maximise_influence() {
+        // Star 0 -> 1..=5, edge 6 -> 7 and a path 8 -> 9 -> 10 with a weak edge
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..11 {
+            g.add_v(None);
+        }
+        for i in 1..6 {
+            g.add_e(&0, &i, true, None).unwrap();
+        }
+        g.add_e(&6, &7, true, None).unwrap();
+        g.add_e(&8, &9, true, None).unwrap();
+        g.add_e(&9, &10, true, None).unwrap();
+        let control = ExecutionControl::new();
+        let mut rng = StdRng::seed_from_u64(5);
+        let weight_fn = |x: &usize, _: &usize, _: &usize| if *x == 8 { 0.0 } else { 1.0 };
+        let seeds = g.maximise_influence(CascadeModel::IndependentCascade, 3, 10, weight_fn, &mut rng, &control).unwrap();
+        assert_eq!(seeds, vec![(0, 6.0), (6, 8.0), (9, 10.0)]);
+        assert_eq!(g.maximise_influence(CascadeModel::LinearThreshold, 20, 1, weight_fn, &mut rng, &control).unwrap().len(), 11);
+        assert!(g.maximise_influence(CascadeModel::IndependentCascade, 1, 0, weight_fn, &mut rng, &control).is_err());
+    }
+
+    #[test]
+    fn simulate_epidemic() {
+        // Directed path 0 -> 1 -> 2 -> 3 and an isolated vertex 4
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        for i in 0..3 {
+            g.add_e(&i, &(i + 1), true, None).unwrap();
+        }
+        let control = ExecutionControl::new();
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut history = Vec::new();
+        let outcome = g.simulate_epidemic(EpidemicModel::SI { infection_probability: 1.0 }, &[1], 10, &mut rng, |step, states| history.push((step, states[&3])), &control).unwrap();
+        assert_eq!(outcome.ever_infected, HashSet::from([1, 2, 3]));
+        assert_eq!(outcome.steps, 2);
+        assert_eq!(history, vec![(0, SpreadingState::Susceptible), (1, SpreadingState::Susceptible), (2, SpreadingState::Infected)]);
+        let outcome = g.simulate_epidemic(EpidemicModel::SIR { infection_probability: 1.0, recovery_probability: 1.0 }, &[0], 10, &mut rng, |_, _| (), &control).unwrap();
+        assert_eq!(outcome.steps, 4);
+        assert!(outcome.states.iter().all(|x| *x.1 == if *x.0 == 4 { SpreadingState::Susceptible } else { SpreadingState::Recovered }));
+        let run = |seed| g.simulate_epidemic(EpidemicModel::SIS { infection_probability: 0.5, recovery_probability: 0.3 }, &[0], 20, &mut StdRng::seed_from_u64(seed), |_, _| (), &control).unwrap();
+        assert_eq!(run(7), run(7));
+        assert!(g.simulate_epidemic(EpidemicModel::SI { infection_probability: 1.5 }, &[0], 1, &mut rng, |_, _| (), &control).is_err());
+        assert!(matches!(g.simulate_epidemic(EpidemicModel::SI { infection_probability: 1.0 }, &[5], 1, &mut rng, |_, _| (), &control).unwrap_err().kind(), ErrorKind::VertexNotFound { .. }));
+    }
+
+    #[test]
+    fn simulate_cascade() {
+        // Vertex 2 needs both 0 and 1 to be active under the linear threshold model
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&2, &3, true, None).unwrap();
+        let control = ExecutionControl::new();
+        let mut rng = StdRng::seed_from_u64(3);
+        let outcome = g.simulate_cascade(CascadeModel::IndependentCascade, &[0], |_, _, _| 1.0, &mut rng, |_, _| (), &control).unwrap();
+        assert_eq!(outcome.ever_infected, HashSet::from([0, 2, 3]));
+        assert_eq!(outcome.steps, 2);
+        let outcome = g.simulate_cascade(CascadeModel::IndependentCascade, &[0], |_, _, _| 0.0, &mut rng, |_, _| (), &control).unwrap();
+        assert_eq!(outcome.ever_infected, HashSet::from([0]));
+        let half = |_: &usize, y: &usize, _: &u8| if *y == 2 { 0.5 } else { 1.0 };
+        let outcome = g.simulate_cascade(CascadeModel::LinearThreshold, &[0, 1], half, &mut rng, |_, _| (), &control).unwrap();
+        assert_eq!(outcome.ever_infected, HashSet::from([0, 1, 2, 3]));
+        assert!(g.simulate_cascade(CascadeModel::LinearThreshold, &[0], |_, _, _| -1.0, &mut rng, |_, _| (), &control).is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidWeight { .. })));
+        control.cancel();
+        assert!(matches!(g.maximise_influence(CascadeModel::IndependentCascade, 1, 1, |_, _, _| 1.0, &mut rng, &control).unwrap_err().kind(), ErrorKind::Cancelled));
+        assert!(matches!(g.simulate_cascade(CascadeModel::IndependentCascade, &[0], |_, _, _| 1.0, &mut rng, |_, _| (), &control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+}
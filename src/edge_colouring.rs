@@ -0,0 +1,278 @@
+//! # Edge colouring module
+//! 
+//! ## Description
+//! This module contains [`EdgeColouring`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BTreeSet, HashMap};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Edge colours
+/// 
+/// ## Description
+/// A map from edges to their colours, each edge is given by the IDs  of  its  endpoints
+/// (the smaller one goes first) and its own ID, colours are numbered from 0.
+pub type EdgeColours<EdgeIdType, VertexIdType> = HashMap<(VertexIdType, VertexIdType, EdgeIdType), usize>;
+
+
+
+// Misra and Gries' colouring over vertices numbered from 0, `at[v]` maps each colour
+// used at vertex `v` to the neighbour the edge of this colour leads to
+struct MisraGries {
+    at: Vec<HashMap<usize, usize>>,
+    colours: HashMap<(usize, usize), usize>,
+}
+
+impl MisraGries {
+    fn set_colour(&mut self, a: usize, b: usize, colour: usize) {
+        self.at[a].insert(colour, b);
+        self.at[b].insert(colour, a);
+        self.colours.insert((a.min(b), a.max(b)), colour);
+    }
+
+    fn unset_colour(&mut self, a: usize, b: usize) {
+        if let Some(colour) = self.colours.remove(&(a.min(b), a.max(b))) {
+            self.at[a].remove(&colour);
+            self.at[b].remove(&colour);
+        }
+    }
+
+    fn colour(&self, a: usize, b: usize) -> Option<usize> {
+        self.colours.get(&(a.min(b), a.max(b))).copied()
+    }
+
+    fn is_free(&self, v: usize, colour: usize) -> bool {
+        !self.at[v].contains_key(&colour)
+    }
+
+    fn free_colour(&self, v: usize) -> usize {
+        (0..).find(|&x| self.is_free(v, x)).unwrap()
+    }
+
+    fn colour_edge(&mut self, u: usize, v: usize) {
+        // Maximal fan of `u` starting with `v`: each edge from `u` to a fan vertex has a
+        // colour that is free on the previous fan vertex
+        let mut fan = vec![v];
+        loop {
+            let last = *fan.last().unwrap();
+            match self.at[u].iter().filter(|(colour, w)| self.is_free(last, **colour) && !fan.contains(w)).map(|x| *x.1).min() {
+                Some(w) => fan.push(w),
+                None => break,
+            }
+        }
+        let c = self.free_colour(u);
+        let d = self.free_colour(*fan.last().unwrap());
+        // Invert the cd-path starting at `u`
+        let mut path = Vec::new();
+        let (mut x, mut colour) = (u, d);
+        while let Some(&y) = self.at[x].get(&colour) {
+            path.push((x, y, colour));
+            x = y;
+            colour = if colour == c { d } else { c };
+        }
+        for &(x, y, _) in path.iter() {
+            self.unset_colour(x, y);
+        }
+        for (x, y, colour) in path {
+            self.set_colour(x, y, if colour == c { d } else { c });
+        }
+        // The longest prefix of the fan that is still a fan and ends with a vertex where
+        // `d` is free, then rotate it
+        let mut end = 0;
+        for i in 0..fan.len() {
+            if i > 0 && self.colour(u, fan[i]).is_none_or(|x| !self.is_free(fan[i - 1], x)) {
+                break;
+            }
+            if self.is_free(fan[i], d) {
+                end = i;
+                break;
+            }
+        }
+        let shifted_colours: Vec<usize> = (0..end).map(|i| self.colour(u, fan[i + 1]).unwrap()).collect();
+        for &w in fan[..=end].iter() {
+            self.unset_colour(u, w);
+        }
+        for (i, colour) in shifted_colours.into_iter().enumerate() {
+            self.set_colour(u, fan[i], colour);
+        }
+        self.set_colour(u, fan[end], d);
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EDGE COLOURING                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Edge colouring
+/// 
+/// ## Description
+/// A proper edge colouring assigns colours  to  edges  so  that  edges  with  a  common
+/// endpoint have different  colours,  e.g.  to  schedule  pairwise  meetings  into  the
+/// smallest number of time slots.
+/// 
+/// All functions ignore the directions of edges and require the graph to  have  neither
+/// self-loops nor parallel edges (a pair of opposite directed edges counts as  parallel
+/// edges).
+pub trait EdgeColouring<EdgeAttributeCollectionType, EdgeIdType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Colour edges
+    /// 
+    /// ## Description
+    /// Find a proper edge colouring with at most Δ + 1 colours.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<EdgeColours<EdgeIdType, VertexIdType>>`   -   `Ok(value)`   is
+    /// returned if the graph has neither self-loops nor parallel edges, `value` in this
+    /// case is the colour of each edge; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// By Vizing's theorem, every simple graph with maximum degree Δ  can  be  coloured
+    /// with Δ + 1 colours. This function uses the constructive proof of Misra and Gries
+    /// [[source](https://doi.org/10.1016/0020-0190(92)90041-S)], edges are coloured one
+    /// by one, recolouring a fan and an alternating path when no colour is free at both
+    /// endpoints. Note that Δ colours aren't always enough and finding out whether they
+    /// are is NP-hard.
+    /// 
+    /// ## Complexity
+    /// Time: O(|E| · |V| · Δ).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn colour_edges(&self) -> CrabNetsResult<EdgeColours<EdgeIdType, VertexIdType>>;
+    /// # Colour edges in place
+    /// 
+    /// ## Description
+    /// Find a proper edge colouring with at most Δ + 1 colours and  write  the  colours
+    /// into the [attribute collections][attrs] of the edges.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `setter` : `SetterFnType` - a function that receives a  mutable  reference  to
+    /// the attribute collection of an edge and the colour of the edge, and  stores  the
+    /// colour.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if  the  graph  has  neither
+    /// self-loops nor parallel edges, `value` in  this  case  is  the  number  of  used
+    /// colours; `Err(_)` is returned  otherwise,  in  which  case  the  caller  remains
+    /// unchanged.
+    /// 
+    /// ## Details
+    /// See [`EdgeColouring::colour_edges`].
+    /// 
+    /// ## Complexity
+    /// Time: O(|E| · |V| · Δ).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [attrs]: crate::Graph#attributes
+    fn colour_edges_into<SetterFnType>(&mut self, setter: SetterFnType) -> CrabNetsResult<usize>
+    where
+        SetterFnType: FnMut(&mut EdgeAttributeCollectionType, usize);
+}
+
+
+
+// Graph::EdgeColouring
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> EdgeColouring<EdgeAttributeCollectionType, EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn colour_edges(&self) -> CrabNetsResult<EdgeColours<EdgeIdType, VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::EdgeColouring::colour_edges";
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut edges = HashMap::new();
+        for edge in self.iter_e() {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            if i == j {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: format!("Vertex {} has a self-loop, edge colouring is impossible.", edge.id1) }));
+            }
+            let key = (i.min(j), i.max(j));
+            if edges.get(&key).is_some_and(|x| *x != edge.edge_id) {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: format!("Vertices {} and {} are connected by parallel edges.", edge.id1, edge.id2) }));
+            }
+            edges.insert(key, edge.edge_id);
+        }
+        let mut state = MisraGries { at: vec![HashMap::new(); vertices.len()], colours: HashMap::with_capacity(edges.len()) };
+        for &(i, j) in edges.keys().collect::<BTreeSet<_>>() {
+            state.colour_edge(i, j);
+        }
+        Ok(state.colours.into_iter().map(|((i, j), colour)| ((vertices[i].clone(), vertices[j].clone(), edges[&(i, j)].clone()), colour)).collect())
+    }
+
+    fn colour_edges_into<SetterFnType>(&mut self, mut setter: SetterFnType) -> CrabNetsResult<usize>
+    where
+        SetterFnType: FnMut(&mut EdgeAttributeCollectionType, usize),
+    {
+        let colours = self.colour_edges()?;
+        for ((id1, id2, edge_id), colour) in colours.iter() {
+            setter(self.e_attrs_mut(id1, id2, edge_id)?, *colour);
+        }
+        Ok(colours.values().max().map_or(0, |x| x + 1))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn edge_colouring() {
+        // Petersen graph needs Δ + 1 = 4 colours
+        let mut g: graph!(X ---A--> X) = Graph::new();
+        for _ in 0..10 {
+            g.add_v(None);
+        }
+        for i in 0..5 {
+            g.add_e(&i, &((i + 1) % 5), i % 2 == 0, None).unwrap();
+            g.add_e(&(i + 5), &((i + 2) % 5 + 5), false, None).unwrap();
+            g.add_e(&(i + 5), &i, true, None).unwrap();
+        }
+        let colours = g.colour_edges().unwrap();
+        assert_eq!(colours.len(), 15);
+        assert!(colours.values().all(|x| *x < 4));
+        for v in 0..10 {
+            let mut incident_colours: Vec<usize> = colours.iter().filter(|x| x.0.0 == v || x.0.1 == v).map(|x| *x.1).collect();
+            incident_colours.sort();
+            incident_colours.dedup();
+            assert_eq!(incident_colours.len(), 3);
+        }
+        assert_eq!(g.colour_edges_into(|attrs, colour| { attrs.insert("colour".to_string(), Box::new(colour)); }).unwrap(), 4);
+        assert_eq!(g.e_attrs(&5, &0, &0).unwrap().get(&"colour".to_string()).unwrap().downcast::<usize>(), Some(&colours[&(0, 5, 0)]));
+        g.add_e(&3, &3, false, None).unwrap();
+        assert!(g.colour_edges().is_err_and(|x| matches!(x.kind(), ErrorKind::UnsupportedGraph { .. })));
+    }
+}
@@ -0,0 +1,241 @@
+//! # Edge filter module
+//! 
+//! ## Description
+//! This module contains the methods of [`Graph`] that manage its optional edge  filter,
+//! a Bloom filter [[source](https://doi.org/10.1145/362686.362692)] over the  edges  of
+//! the graph that lets [`contains_e`][contains_e] answer  most  queries  about  missing
+//! edges without looking into the locales.
+//! 
+//! [`Graph`]: crate::Graph
+//! [contains_e]: crate::BasicImmutableGraph::contains_e
+use std::{collections::hash_map::DefaultHasher, hash::Hasher};
+use crate::{attributes::AttributeCollection, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EDGE FILTER                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Bloom filter over the hashes of edges, it's rebuilt with twice the capacity as soon
+// as more edges than `capacity` have been inserted
+#[derive(Clone)]
+pub(crate) struct EdgeFilter {
+    bits: Vec<u64>,
+    bits_per_edge: usize,
+    capacity: usize,
+    count: usize,
+    hash_count: u64,
+}
+
+// EdgeFilter::EdgeFilter
+impl EdgeFilter {
+    fn new(capacity: usize, bits_per_edge: usize) -> Self {
+        let capacity = capacity.max(64);
+        // The number of hash functions that minimises the false positive rate
+        let hash_count = ((bits_per_edge as f64 * std::f64::consts::LN_2).round() as u64).max(1);
+        EdgeFilter { bits: vec![0u64; (capacity * bits_per_edge).div_ceil(64)], bits_per_edge, capacity, count: 0, hash_count }
+    }
+
+    // Positions of the bits of the edge with the given hash, double hashing is used to
+    // get all of them out of a single hash
+    fn positions(&self, hash: u64) -> impl Iterator<Item = usize> + '_ {
+        let bit_count = self.bits.len() as u64 * 64;
+        let step = hash.rotate_left(32) | 1;
+        (0..self.hash_count).map(move |i| (hash.wrapping_add(i.wrapping_mul(step)) % bit_count) as usize)
+    }
+
+    fn insert(&mut self, hash: u64) {
+        for position in self.positions(hash).collect::<Vec<_>>() {
+            self.bits[position / 64] |= 1 << (position % 64);
+        }
+        self.count += 1;
+    }
+
+    fn may_contain(&self, hash: u64) -> bool {
+        self.positions(hash).all(|position| self.bits[position / 64] & (1 << (position % 64)) != 0)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EDGE FILTER MANAGEMENT                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Disable edge filter
+    /// 
+    /// ## Description
+    /// Drop the edge filter of the graph and free its memory.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    pub fn disable_edge_filter(&mut self) {
+        self.edge_filter = None;
+    }
+
+    /// # Enable edge filter
+    /// 
+    /// ## Description
+    /// Build  a  Bloom  filter  over  the  edges  of  the  graph  that  is  checked  by
+    /// [`contains_e`][contains_e] before the locales.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `bits_per_edge` : `usize` - the number of bits of the  filter  per  edge,  the
+    /// more bits are spent the fewer false positives occur.
+    /// 
+    /// ## Details
+    /// If the filter says that an edge is  absent,  [`contains_e`][contains_e]  returns
+    /// `None` right away. Otherwise, the edge may or may not exist (a false  positive),
+    /// and   the   locales   are   checked   as    usual,    so    the    answers    of
+    /// [`contains_e`][contains_e] never change. The  filter  pays  off  for  algorithms
+    /// dominated by queries about edges that don't exist, e.g. counting of triangles or
+    /// motifs  in  huge  sparse  graphs.  The  rate  of  false   positives   is   about
+    /// 0.6185<sup>`bits_per_edge`</sup>, i.e. about 1% for 10 bits per edge.
+    /// 
+    /// The filter is kept up to date as edges are added and  grows  together  with  the
+    /// graph. Removed edges, however, stay in the filter and  become  false  positives,
+    /// call this function again after many removals to rebuild the filter from scratch.
+    /// For  [simple graphs][kinds],   the   filter   ignores   edge   IDs   just   like
+    /// [`contains_e`][contains_e] does.
+    /// 
+    /// ## Complexity
+    /// Time: O(|E| · `bits_per_edge`).
+    /// 
+    /// Space: O(|E| · `bits_per_edge`).
+    /// 
+    /// [contains_e]: crate::BasicImmutableGraph::contains_e
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn enable_edge_filter(&mut self, bits_per_edge: usize) {
+        self.rebuild_edge_filter(bits_per_edge.max(1), 1);
+    }
+
+    /// # Check if edge filter is enabled
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if the graph has an [edge filter][enable], `false` otherwise.
+    /// 
+    /// [enable]: Graph::enable_edge_filter
+    #[inline]
+    pub fn has_edge_filter(&self) -> bool {
+        self.edge_filter.is_some()
+    }
+
+    // Build the edge filter for `growth` times as many edges as the graph has
+    fn rebuild_edge_filter(&mut self, bits_per_edge: usize, growth: usize) {
+        let edges: Vec<_> = self.iter_e().collect();
+        let mut filter = EdgeFilter::new(growth * edges.len(), bits_per_edge);
+        for edge in edges {
+            filter.insert(self.e_hash(&edge.id1, &edge.id2, &edge.edge_id));
+        }
+        self.edge_filter = Some(filter);
+    }
+
+    // Hash of the edge that doesn't depend on the order of its ends
+    fn e_hash(&self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        id1.min(id2).hash(&mut hasher);
+        id1.max(id2).hash(&mut hasher);
+        if LocaleType::IS_MULTI {
+            edge_id.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    // `false` if the edge filter is enabled and the edge is definitely absent
+    #[inline]
+    pub(crate) fn may_contain_e(&self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> bool {
+        self.edge_filter.as_ref().is_none_or(|x| x.may_contain(self.e_hash(id1, id2, edge_id)))
+    }
+
+    // Add the new edge to the edge filter if it's enabled
+    pub(crate) fn record_e_in_filter(&mut self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) {
+        if self.edge_filter.is_none() {
+            return;
+        }
+        let hash = self.e_hash(id1, id2, edge_id);
+        if let Some(filter) = self.edge_filter.as_mut() {
+            if filter.count < filter.capacity {
+                filter.insert(hash);
+            } else {
+                let bits_per_edge = filter.bits_per_edge;
+                self.rebuild_edge_filter(bits_per_edge, 2);
+            }
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn edge_filter() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        let mut h: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..100 {
+            g.add_v(None);
+            h.add_v(None);
+        }
+        g.enable_edge_filter(10);
+        assert!(g.has_edge_filter());
+        for i in 0..100 {
+            for j in [1, 7, 31] {
+                g.add_e(&i, &((i + j) % 100), i % 2 == 0, None).unwrap();
+                h.add_e(&i, &((i + j) % 100), false, None).unwrap();
+            }
+        }
+        h.enable_edge_filter(4);
+        for i in 0..100 {
+            for j in 0..100 {
+                for edge_id in 0..3 {
+                    let expected = (j + 100 - i) % 100;
+                    let is_present = [1, 7, 31].contains(&expected) || [1, 7, 31].contains(&((i + 100 - j) % 100));
+                    assert_eq!(h.contains_e(&i, &j, &edge_id).is_some(), is_present);
+                }
+            }
+        }
+        let edges: Vec<_> = g.iter_e().collect();
+        for edge in edges.iter() {
+            assert_eq!(g.contains_e(&edge.id1, &edge.id2, &edge.edge_id), Some(edge.direction));
+            assert_eq!(g.contains_e(&edge.id2, &edge.id1, &edge.edge_id), Some(edge.direction.reversed()));
+        }
+        let rejected = (0..100).flat_map(|i| (0..100).map(move |j| (i, j))).filter(|(i, j)| !g.may_contain_e(i, j, &0)).count();
+        assert!(rejected > 9000);
+        g.remove_e(&edges[0].id1, &edges[0].id2, &edges[0].edge_id).unwrap();
+        assert!(g.contains_e(&edges[0].id1, &edges[0].id2, &edges[0].edge_id).is_none());
+        g.disable_edge_filter();
+        assert!(!g.has_edge_filter());
+        assert!(g.contains_e(&edges[1].id1, &edges[1].id2, &edges[1].edge_id).is_some());
+    }
+}
@@ -1,4 +1,5 @@
-use std::{error::Error, fmt::Display};
+use alloc::{format, string::{String, ToString}};
+use core::{error::Error, fmt::Display};
 
 
 
@@ -22,27 +23,160 @@ pub type CrabNetsResult<OkType> = Result<OkType, CrabNetsError>;
 
 
 
+/// # Kind of error
+/// 
+/// ## Description
+/// This enum tells what exactly went wrong, so that the caller can react to  the  error
+/// programmatically instead of parsing its message.
+/// 
+/// IDs are stored in their textual form, since [`CrabNetsError`] is  shared  by  graphs
+/// with all kinds of ID types.
+/// 
+/// ## Variants
+/// * `VertexNotFound` - the vertex with ID `id` doesn't exist.
+/// * `VertexAlreadyExists` - the vertex with ID `id` already exists.
+/// * `EdgeNotFound` - the edge between vertices  `id1`  and  `id2`  with  ID  `edge_id`
+/// doesn't exist.
+/// * `EdgeAlreadyExists` - the edge between vertices `id1` and `id2` already exists.
+/// * `ParseError` - the input couldn't be parsed at line `line` (and column `column` if
+/// it's known): `expected` was expected but `found` was found.
+/// * `AttributeTypeMismatch` - the attribute with name `name` has  different  types  in
+/// different attribute collections.
+/// * `UnsupportedFormat` - the  format  of  the  file  with  name  `file_name`  is  not
+/// supported.
+/// * `InvalidArgument` - the argument with  name  `name`  has  an  unacceptable  value,
+/// `reason` tells why.
+/// * `InvalidWeight` - the edge between vertices `id1` and `id2` with ID `edge_id`  has
+/// weight `weight` which the algorithm can't work with (e.g. a negative or a NaN one).
+/// * `UnsupportedGraph` - the algorithm can't be applied to the graph,  `reason`  tells
+/// why.
+/// * `Cancelled` - the algorithm was stopped by its [`ExecutionControl`][control].
+/// * `CorruptedData` - the input is malformed or failed an  integrity  check,  `reason`
+/// tells why.
+/// * `LimitExceeded` - the `limit` of the container, e.g. the number of vertices,  went
+/// beyond its `maximum`.
+/// * `Io` - an I/O operation failed, only available with `std` feature.
+/// * `Other` - any other error described by the message.
+/// 
+/// [control]: crate::execution::ExecutionControl
+#[derive(Debug)]
+pub enum ErrorKind {
+    VertexNotFound { id: String },
+    VertexAlreadyExists { id: String },
+    EdgeNotFound { id1: String, id2: String, edge_id: String },
+    EdgeAlreadyExists { id1: String, id2: String },
+    ParseError { line: usize, column: Option<usize>, expected: String, found: Option<String> },
+    AttributeTypeMismatch { name: String },
+    UnsupportedFormat { file_name: String },
+    InvalidArgument { name: String, reason: String },
+    InvalidWeight { id1: String, id2: String, edge_id: String, weight: f64 },
+    UnsupportedGraph { reason: String },
+    Cancelled,
+    CorruptedData { reason: String },
+    LimitExceeded { limit: String, maximum: usize },
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+    Other(String),
+}
+
+// ErrorKind::Display
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ErrorKind::VertexNotFound { id } => write!(f, "Vertex with ID {} doesn't exist.", id),
+            ErrorKind::VertexAlreadyExists { id } => write!(f, "Vertex with ID {} already exists.", id),
+            ErrorKind::EdgeNotFound { id1, id2, edge_id } => write!(f, "Edge between vertices {} and {} with edge ID {} doesn't exist.", id1, id2, edge_id),
+            ErrorKind::EdgeAlreadyExists { id1, id2 } => write!(f, "Edge between vertices {} and {} already exists.", id1, id2),
+            ErrorKind::ParseError { line, column, expected, found } => {
+                write!(f, "Line {}", line)?;
+                if let Some(column) = column {
+                    write!(f, ", column {}", column)?;
+                }
+                write!(f, ". Expected {}", expected)?;
+                if let Some(found) = found {
+                    write!(f, ", found {}", found)?;
+                }
+                f.write_str(".")
+            },
+            ErrorKind::AttributeTypeMismatch { name } => write!(f, "The type of attribute '{}' differs across different attribute collections.", name),
+            ErrorKind::UnsupportedFormat { file_name } => write!(f, "Unsupported format of the file with name '{}'.", file_name),
+            ErrorKind::InvalidArgument { name, reason } => write!(f, "Invalid argument '{}'. {}", name, reason),
+            ErrorKind::InvalidWeight { id1, id2, edge_id, weight } => write!(f, "Edge between vertices {} and {} with edge ID {} has invalid weight {}.", id1, id2, edge_id, weight),
+            ErrorKind::UnsupportedGraph { reason } => f.write_str(reason),
+            ErrorKind::Cancelled => f.write_str("The execution was cancelled."),
+            ErrorKind::CorruptedData { reason } => f.write_str(reason),
+            ErrorKind::LimitExceeded { limit, maximum } => write!(f, "The {} exceeds {}.", limit, maximum),
+            #[cfg(feature = "std")]
+            ErrorKind::Io(error) => write!(f, "{}", error),
+            ErrorKind::Other(message) => f.write_str(message),
+        }
+    }
+}
+
+// ErrorKind::From<String>
+impl From<String> for ErrorKind {
+    fn from(message: String) -> Self {
+        ErrorKind::Other(message)
+    }
+}
+
+// ErrorKind::From<&str>
+impl From<&str> for ErrorKind {
+    fn from(message: &str) -> Self {
+        ErrorKind::Other(message.to_string())
+    }
+}
+
+// ErrorKind::From<std::io::Error>
+#[cfg(feature = "std")]
+impl From<std::io::Error> for ErrorKind {
+    fn from(error: std::io::Error) -> Self {
+        ErrorKind::Io(error)
+    }
+}
+
+
+
 #[derive(Debug)]
 pub struct CrabNetsError {
-    message: String,
+    function_path: String,
+    kind: ErrorKind,
 }
 
 // Implementation of CrabNetsError
 impl CrabNetsError {
-    pub fn new<StringType>(function_path: &str, message: StringType) -> Self
+    pub fn new<KindType>(function_path: &str, kind: KindType) -> Self
     where
-        StringType: Into<String>
+        KindType: Into<ErrorKind>
     {
-        CrabNetsError{ message: format!("{}. {}", function_path, message.into()) }
+        CrabNetsError{ function_path: function_path.to_string(), kind: kind.into() }
+    }
+
+    #[inline]
+    pub fn function_path(&self) -> &str {
+        &self.function_path
+    }
+
+    #[inline]
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
     }
 }
 
 // Implementation of Display
 impl Display for CrabNetsError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(format!("CrabNetsError: {}", self.message).as_str())
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(format!("CrabNetsError: {}. {}", self.function_path, self.kind).as_str())
     }
 }
 
 // Implementation of Error
-impl Error for CrabNetsError {}
+impl Error for CrabNetsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            #[cfg(feature = "std")]
+            ErrorKind::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
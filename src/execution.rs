@@ -0,0 +1,156 @@
+//! # Execution module
+//! 
+//! ## Description
+//! This  module  contains  [`ExecutionControl`]  that  allows  to  cancel  long-running
+//! algorithms or to limit the time they may take.
+use std::{sync::{atomic::{AtomicBool, Ordering}, Arc}, time::{Duration, Instant}};
+use crate::errors::{CrabNetsError, CrabNetsResult, ErrorKind};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EXECUTION CONTROL                                                                 *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Execution control
+/// 
+/// ## Description
+/// A handle passed to expensive  algorithms  that  tells  them  when  to  give  up.  An
+/// algorithm periodically checks the handle and, once it's cancelled  or  its  deadline
+/// has  passed,   returns   either   a   partial   result   or   an   error   of   kind
+/// [`ErrorKind::Cancelled`], as described in its documentation.
+/// 
+/// Clones of the handle share the cancellation flag, so a clone can be moved to another
+/// thread (e.g. the UI thread of an interactive application) and  used  to  cancel  the
+/// computation running with the original handle.
+/// 
+/// `ExecutionControl::default()` never cancels anything and is the value to  pass  when
+/// no control is needed.
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionControl {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+// ExecutionControl::ExecutionControl
+impl ExecutionControl {
+    /// # Create execution control
+    /// 
+    /// ## Description
+    /// Create a handle without a deadline that isn't cancelled.
+    /// 
+    /// ## Arguments
+    /// None.
+    /// 
+    /// ## Returns
+    /// * `ExecutionControl` - a new handle.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Create execution control with deadline
+    /// 
+    /// ## Description
+    /// Create a handle that is cancelled automatically at the given moment.
+    /// 
+    /// ## Arguments
+    /// * `deadline` : `Instant` - the moment after which the algorithm must stop.
+    /// 
+    /// ## Returns
+    /// * `ExecutionControl` - a new handle.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        ExecutionControl { cancelled: Arc::default(), deadline: Some(deadline) }
+    }
+
+    /// # Create execution control with time budget
+    /// 
+    /// ## Description
+    /// Create a handle that is cancelled automatically once the given  amount  of  time
+    /// has passed since its creation.
+    /// 
+    /// ## Arguments
+    /// * `budget` : `Duration` - the time the algorithm may take.
+    /// 
+    /// ## Returns
+    /// * `ExecutionControl` - a new handle.
+    pub fn with_time_budget(budget: Duration) -> Self {
+        Self::with_deadline(Instant::now() + budget)
+    }
+
+    /// # Cancel execution
+    /// 
+    /// ## Description
+    /// Request the algorithms using this handle or any of its clones to stop.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// None.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// # Check for cancellation
+    /// 
+    /// ## Description
+    /// Check whether the algorithm may proceed. This function is meant to be called  by
+    /// the algorithms periodically.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `function_path` : `&str` - the path of the function that performs  the  check,
+    /// it is reported in the error.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned  if  the  algorithm  may  proceed;
+    /// `Err(_)` of kind [`ErrorKind::Cancelled`] is returned otherwise.
+    pub fn check(&self, function_path: &str) -> CrabNetsResult<()> {
+        if self.is_cancelled() {
+            return Err(CrabNetsError::new(function_path, ErrorKind::Cancelled));
+        }
+        Ok(())
+    }
+
+    /// # Check if cancelled
+    /// 
+    /// ## Description
+    /// Check whether the execution has been cancelled or its deadline has passed.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the algorithm must stop,  `false`  is  returned
+    /// otherwise.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed) || self.deadline.is_some_and(|x| Instant::now() >= x)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+    use crate::*;
+    use super::ExecutionControl;
+
+    #[test]
+    fn execution_control() {
+        let control = ExecutionControl::new();
+        assert!(control.check("test").is_ok());
+        let clone = control.clone();
+        std::thread::spawn(move || clone.cancel()).join().unwrap();
+        assert!(control.is_cancelled());
+        assert!(matches!(control.check("test").unwrap_err().kind(), ErrorKind::Cancelled));
+        assert!(ExecutionControl::with_deadline(Instant::now()).is_cancelled());
+        assert!(!ExecutionControl::with_time_budget(Duration::from_secs(3600)).is_cancelled());
+    }
+}
@@ -0,0 +1,311 @@
+//! # Handles module
+//! 
+//! ## Description
+//! This module contains [`VertexHandle`], the dense internal index of a vertex, and the
+//! methods of [`Graph`] that convert between vertex IDs and handles.
+//! 
+//! Handles let algorithms keep their per-vertex state in a `Vec` indexed by the  handle
+//! instead of a `HashMap` keyed by the vertex ID, which saves both memory  and  hashing
+//! time on large graphs.
+//! 
+//! [`Graph`]: crate::Graph
+use alloc::vec::Vec;
+use core::fmt::Display;
+use crate::{attributes::AttributeCollection, Graph, HashMap, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * VERTEX HANDLE                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Vertex handle
+/// 
+/// ## Description
+/// A dense internal index of a vertex, independent of the type and the  values  of  the
+/// vertex IDs.
+/// 
+/// ## Details
+/// Every vertex of a [`Graph`] gets a handle when it's added and keeps  it  until  it's
+/// removed. Handles of removed vertices are reused by the vertices added later, so  all
+/// handles of a graph stay  below  [`Graph::handle_bound`],  which  never  exceeds  the
+/// largest number of vertices the graph has ever had.
+/// 
+/// Handles are only meaningful for the graph that  issued  them,  and  a  handle  of  a
+/// removed vertex may be given to a different vertex afterwards.
+/// 
+/// Handles are 32-bit, so a graph can't have more than 2³² vertices at the  same  time.
+/// [`BasicMutableGraph::try_add_v`]  returns  [`ErrorKind::UnsupportedGraph`]  when   a
+/// vertex is added beyond this cap, while the  infallible  [`BasicMutableGraph::add_v`]
+/// panics.
+/// 
+/// [`BasicMutableGraph::try_add_v`]: crate::BasicMutableGraph::try_add_v
+/// [`BasicMutableGraph::add_v`]: crate::BasicMutableGraph::add_v
+/// [`ErrorKind::UnsupportedGraph`]: crate::errors::ErrorKind::UnsupportedGraph
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct VertexHandle(u32);
+
+// VertexHandle::VertexHandle
+impl VertexHandle {
+    /// # Index of the handle
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `usize`  -  the  position   of   the   vertex   in   any   `Vec`   of   length
+    /// [`Graph::handle_bound`] that stores per-vertex state.
+    #[inline]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+// VertexHandle::Display
+impl Display for VertexHandle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// VertexHandle::From
+impl From<VertexHandle> for usize {
+    #[inline]
+    fn from(value: VertexHandle) -> Self {
+        value.index()
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * HANDLE TABLE                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Reason of the error returned when a vertex is added to a graph with all handles taken
+pub(crate) const HANDLES_EXHAUSTED: &str = "The graph already has 2^32 vertices, which is the maximum number of vertex handles.";
+
+// Two-way correspondence between vertex IDs and handles stored inside every graph
+#[derive(Clone)]
+pub(crate) struct HandleTable<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    free: Vec<VertexHandle>,
+    handles: HashMap<VertexIdType, VertexHandle>,
+    ids: Vec<Option<VertexIdType>>,
+}
+
+// HandleTable::HandleTable
+impl<VertexIdType> HandleTable<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    pub(crate) fn new() -> Self {
+        HandleTable { free: Vec::new(), handles: HashMap::new(), ids: Vec::new() }
+    }
+
+    #[inline]
+    pub(crate) fn get(&self, id: &VertexIdType) -> Option<VertexHandle> {
+        self.handles.get(id).copied()
+    }
+
+    #[inline]
+    pub(crate) fn id(&self, handle: VertexHandle) -> Option<&VertexIdType> {
+        self.ids.get(handle.index()).and_then(|x| x.as_ref())
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    #[inline]
+    pub(crate) fn bound(&self) -> usize {
+        self.ids.len()
+    }
+
+    #[inline]
+    pub(crate) fn is_full(&self) -> bool {
+        self.free.is_empty() && u32::try_from(self.ids.len()).is_err()
+    }
+
+    // `None` is returned and nothing is changed if all handles are taken
+    pub(crate) fn insert(&mut self, id: VertexIdType) -> Option<VertexHandle> {
+        if let Some(value) = self.handles.get(&id) {
+            return Some(*value);
+        }
+        let handle = match self.free.pop() {
+            Some(value) => {
+                self.ids[value.index()] = Some(id.clone());
+                value
+            },
+            None => {
+                let value = VertexHandle(u32::try_from(self.ids.len()).ok()?);
+                self.ids.push(Some(id.clone()));
+                value
+            },
+        };
+        self.handles.insert(id, handle);
+        Some(handle)
+    }
+
+    pub(crate) fn remove(&mut self, id: &VertexIdType) -> Option<VertexHandle> {
+        let handle = self.handles.remove(id)?;
+        self.ids[handle.index()] = None;
+        self.free.push(handle);
+        Some(handle)
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.handles.reserve(additional);
+        self.ids.reserve(additional);
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * HANDLE CONVERSIONS                                                                *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Handle bound
+    /// 
+    /// ## Description
+    /// Get the exclusive upper bound of the indices of all handles currently  given  to
+    /// the vertices of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the length of a `Vec` that can store a value for every vertex at the
+    /// [index][index] of its handle.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    /// 
+    /// [index]: VertexHandle::index
+    #[inline]
+    pub fn handle_bound(&self) -> usize {
+        self.handles.bound()
+    }
+
+    /// # Iterate over vertex handles
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `impl Iterator<Item = (VertexHandle, &VertexIdType)>` -  the  handles  of  all
+    /// vertices of the graph together  with  their  IDs,  in  the  ascending  order  of
+    /// handles.
+    /// 
+    /// ## Complexity
+    /// Time: O([`Graph::handle_bound`]) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    pub fn iter_v_handles(&self) -> impl Iterator<Item = (VertexHandle, &VertexIdType)> {
+        self.handles.ids.iter().enumerate().filter_map(|(index, id)| id.as_ref().map(|id| (VertexHandle(index as u32), id)))
+    }
+
+    /// # Get vertex ID by its handle
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `handle` - the handle of the vertex.
+    /// 
+    /// ## Returns
+    /// * `Option<&VertexIdType>` - the ID of the vertex with the  specified  handle  or
+    /// `None` if no vertex currently has it.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    #[inline]
+    pub fn v_id(&self, handle: VertexHandle) -> Option<&VertexIdType> {
+        self.handles.id(handle)
+    }
+
+    /// # Get vertex handle by its ID
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` - the ID of the vertex.
+    /// 
+    /// ## Returns
+    /// * `Option<VertexHandle>` - the handle of the vertex or `None` if the vertex with
+    /// the specified ID doesn't exist.
+    /// 
+    /// ## Complexity
+    /// Time: O(1) on average.
+    /// 
+    /// Space: O(1).
+    #[inline]
+    pub fn v_handle(&self, id: &VertexIdType) -> Option<VertexHandle> {
+        self.handles.get(id)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn vertex_handles() {
+        let mut g: graph!(X ---X--- X with VertexIdType = u8) = Graph::new();
+        for id in [7, 3, 5] {
+            g.add_v(Some(id));
+        }
+        assert_eq!(g.handle_bound(), 3);
+        for id in [7, 3, 5] {
+            assert_eq!(g.v_id(g.v_handle(&id).unwrap()), Some(&id));
+        }
+        let handle = g.v_handle(&3).unwrap();
+        g.remove_v(&3);
+        assert_eq!(g.v_handle(&3), None);
+        assert_eq!(g.v_id(handle), None);
+        g.add_v(Some(9));
+        assert_eq!(g.v_handle(&9), Some(handle));
+        assert_eq!(g.handle_bound(), 3);
+        let mut state = vec![0u8; g.handle_bound()];
+        for (handle, id) in g.iter_v_handles() {
+            state[handle.index()] = *id;
+        }
+        state.sort();
+        assert_eq!(state, vec![5, 7, 9]);
+        let copy = g.clone();
+        assert_eq!(copy.v_handle(&7), g.v_handle(&7));
+        assert_eq!(VertexHandle(2).to_string(), "#2");
+    }
+}
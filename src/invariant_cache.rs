@@ -0,0 +1,240 @@
+//! # Invariant cache module
+//! 
+//! ## Description
+//! This module contains the methods of [`Graph`] that  manage  its  optional  invariant
+//! cache, which remembers the values of expensive invariants of the graph  (the  number
+//! of components, acyclicity and the extreme degrees) until the topology of  the  graph
+//! changes.
+//! 
+//! [`Graph`]: crate::Graph
+use std::sync::OnceLock;
+use crate::{attributes::AttributeCollection, topology_tests::TopologyTests, traversal::Traversal, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * INVARIANT CACHE                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Lazily computed invariants, `OnceLock` keeps the graph `Sync` while letting the
+// values be filled in through an immutable reference
+#[derive(Clone, Default)]
+pub(crate) struct InvariantCache {
+    component_count: OnceLock<usize>,
+    degree_extremes: OnceLock<Option<(usize, usize)>>,
+    is_dag: OnceLock<bool>,
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * INVARIANT CACHE MANAGEMENT                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Count components (cached)
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` -  the  number  of  [weakly connected components][components]  of  the
+    /// graph.
+    /// 
+    /// ## Details
+    /// If the [invariant cache][enable] is enabled, the value is computed on the  first
+    /// call after the  last  change  of  the  topology  and  returned  from  the  cache
+    /// afterwards. Otherwise, it's computed on every call.
+    /// 
+    /// ## Complexity
+    /// Time: O(1) if the value is cached, O(|V| * log(|V|) + |E|) otherwise.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [components]: crate::traversal::Traversal::weakly_connected_components
+    /// [enable]: Graph::enable_invariant_cache
+    pub fn cached_count_components(&self) -> usize {
+        let compute = || self.weakly_connected_components().len();
+        match &self.invariant_cache {
+            Some(cache) => *cache.component_count.get_or_init(compute),
+            None => compute(),
+        }
+    }
+
+    /// # Degree extremes (cached)
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<(usize, usize)>` - the smallest and the largest  [degrees][degree]  of
+    /// the vertices of the graph, `None` is returned if the graph has no vertices.
+    /// 
+    /// ## Details
+    /// Caching works the same way as in [`Graph::cached_count_components`].
+    /// 
+    /// ## Complexity
+    /// Time: O(1) if the value is cached, O(|V|) otherwise.
+    /// 
+    /// Space: O(1).
+    /// 
+    /// [degree]: crate::BasicImmutableGraph::v_degree
+    pub fn cached_degree_extremes(&self) -> Option<(usize, usize)> {
+        let compute = || self.iter_v().map(|x| self.v_degree(&x).unwrap()).fold(None, |extremes, x| match extremes {
+            Some((min, max)) => Some((x.min(min), x.max(max))),
+            None => Some((x, x)),
+        });
+        match &self.invariant_cache {
+            Some(cache) => *cache.degree_extremes.get_or_init(compute),
+            None => compute(),
+        }
+    }
+
+    /// # Check if graph is DAG (cached)
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if  the  graph  is  a  [DAG][is_dag],  `false`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Caching works the same way as in [`Graph::cached_count_components`].
+    /// 
+    /// ## Complexity
+    /// Time: O(1) if the value is cached, O(|V| + |E| * α(|V|)) otherwise.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [is_dag]: crate::topology_tests::TopologyTests::is_dag
+    pub fn cached_is_dag(&self) -> bool {
+        let compute = || self.is_dag();
+        match &self.invariant_cache {
+            Some(cache) => *cache.is_dag.get_or_init(compute),
+            None => compute(),
+        }
+    }
+
+    /// # Disable invariant cache
+    /// 
+    /// ## Description
+    /// Drop the invariant cache of the graph together with all cached values.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    pub fn disable_invariant_cache(&mut self) {
+        self.invariant_cache = None;
+    }
+
+    /// # Enable invariant cache
+    /// 
+    /// ## Description
+    /// Make [`Graph::cached_count_components`],  [`Graph::cached_degree_extremes`]  and
+    /// [`Graph::cached_is_dag`] remember their results.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Details
+    /// The cache is empty right after it's enabled, the values are computed on  demand.
+    /// Every addition or removal of a vertex or an edge clears the cache, while changes
+    /// of attributes keep  it  intact  since  they  don't  affect  any  of  the  cached
+    /// invariants. The cache pays off in read-heavy phases, when  the  same  invariants
+    /// are queried many times between rare mutations.
+    /// 
+    /// Calling this function on a graph that already has the cache does nothing.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    pub fn enable_invariant_cache(&mut self) {
+        self.invariant_cache.get_or_insert_with(InvariantCache::default);
+    }
+
+    /// # Check if invariant cache is enabled
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` -  `true`  if  the  graph  has  an  [invariant cache][enable],  `false`
+    /// otherwise.
+    /// 
+    /// [enable]: Graph::enable_invariant_cache
+    #[inline]
+    pub fn has_invariant_cache(&self) -> bool {
+        self.invariant_cache.is_some()
+    }
+
+    // Forget all cached invariants after a change of the topology
+    #[inline]
+    pub(crate) fn invalidate_invariants(&mut self) {
+        if let Some(cache) = self.invariant_cache.as_mut() {
+            *cache = InvariantCache::default();
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn invariant_cache() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        assert_eq!(g.cached_degree_extremes(), None);
+        g.enable_invariant_cache();
+        assert!(g.has_invariant_cache());
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        assert_eq!(g.cached_count_components(), 2);
+        assert!(g.cached_is_dag());
+        assert_eq!(g.cached_degree_extremes(), Some((0, 2)));
+        let h = g.clone();
+        g.add_e(&2, &0, true, None).unwrap();
+        assert!(!g.cached_is_dag());
+        assert!(h.cached_is_dag());
+        g.add_e(&2, &3, false, None).unwrap();
+        assert_eq!(g.cached_count_components(), 1);
+        assert_eq!(g.cached_degree_extremes(), Some((1, 3)));
+        g.remove_e(&2, &3, &0).unwrap();
+        assert_eq!(g.cached_count_components(), 2);
+        g.remove_v(&3);
+        assert_eq!(g.cached_count_components(), 1);
+        assert_eq!(g.cached_degree_extremes(), Some((2, 2)));
+        g.disable_invariant_cache();
+        assert!(!g.has_invariant_cache());
+        g.add_v(None);
+        assert_eq!(g.cached_count_components(), 2);
+    }
+}
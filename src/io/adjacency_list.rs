@@ -0,0 +1,368 @@
+//! # Adjacency list module
+//! 
+//! ## Description
+//! This module contains [`AdjacencyListReader`] and [`AdjacencyListWriter`]  that  read
+//! and write graphs without attributes as adjacency lists, i.e. text files  where  each
+//! line holds the ID of a vertex followed by the IDs of its neighbours:
+//! 
+//! ```text
+//! # Comment
+//! 0: 1 2
+//! 1: 2
+//! 2:
+//! ```
+//! 
+//! The colon after the first ID is optional, so files like `0 1 2`  are  understood  as
+//! well. Vertices without neighbours may have lines of their own.  Only  vertices  that
+//! appear in the file are added to the graph.
+#[cfg(feature = "std")]
+use std::fs::File;
+use std::{collections::HashMap, io::{BufRead, BufReader, BufWriter, Read, Write}, str::FromStr};
+use itertools::Itertools;
+use crate::{
+    errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id
+};
+use super::{AttributeProjection, AttributeCollectionIO, ExportFilter, Reader, Writer};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ADJACENCY LIST READER                                                             *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Adjacency list reader
+/// 
+/// ## Description
+/// Reads graphs from adjacency lists.
+/// 
+/// ## Details
+/// In undirected mode, an edge may be listed either in the lines of both its  endpoints
+/// (as [`AdjacencyListWriter`] does) or in the line of only one of them.  Each  mention
+/// of `v` in the line of `u` is paired with a mention of `u` in  the  line  of  `v`  if
+/// there is one, and every pair or unpaired mention becomes a separate edge.
+pub struct AdjacencyListReader {
+    comment_marker: Option<char>,
+    directed: bool,
+}
+
+// AdjacencyListReader::AdjacencyListReader
+impl AdjacencyListReader {
+    /// # Create adjacency list reader
+    /// 
+    /// ## Description
+    /// Create a reader that skips the lines starting with `#`.
+    /// 
+    /// ## Arguments
+    /// * `directed` : `bool` - whether the edges should be directed from the  vertex  a
+    /// line starts with to the neighbours listed in it.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyListReader` - the reader.
+    pub fn new(directed: bool) -> Self {
+        AdjacencyListReader { comment_marker: Some('#'), directed }
+    }
+
+    /// # Read graph from file
+    /// 
+    /// ## Description
+    /// Read a graph from the adjacency list file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    #[cfg(feature = "std")]
+    pub fn read_file<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, file_name: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyListReader::AdjacencyListReader::read_file";
+        let file = match File::open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.read_graph(BufReader::new(file))
+    }
+
+    /// # Read graph from string
+    /// 
+    /// ## Description
+    /// Read a graph from the adjacency list stored in the given string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `string` : `&str` - the adjacency list.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn read_string<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, string: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        self.read_graph(BufReader::new(string.as_bytes()))
+    }
+
+    /// # Set comment marker
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `comment_marker` : `Option<char>` - `Some(value)` if  the  lines  whose  first
+    /// non-blank character is `value` should be  skipped;  `None`  if  comments  aren't
+    /// allowed.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyListReader` - the updated reader.
+    pub fn with_comment_marker(mut self, comment_marker: Option<char>) -> Self {
+        self.comment_marker = comment_marker;
+        self
+    }
+}
+
+// AdjacencyListReader::Reader
+impl Reader for AdjacencyListReader {
+    fn read_projected_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>, _projection: &AttributeProjection<'_>) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        R: Read,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyListReader::Reader::read_projected_graph";
+        let mut new_graph = G::default();
+        // Mentions of undirected edges still waiting for their mirrored mentions
+        let mut unpaired_mentions: HashMap<(VertexIdType, VertexIdType), usize> = HashMap::new();
+        for (line_index, line_result) in buffer_reader.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line = match line_result {
+                Ok(value) => value,
+                Err(error) => {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Couldn't read line {} of the input file: {}", line_number, error))));
+                },
+            };
+            let trimmed_line = line.trim_start();
+            if trimmed_line.is_empty() || self.comment_marker.is_some_and(|x| trimmed_line.starts_with(x)) {
+                continue;
+            }
+            let mut ids = Vec::new();
+            let mut has_colon = false;
+            for token in line.split_whitespace() {
+                // Tokens are subslices of the line, so their offsets give the columns
+                let column = token.as_ptr() as usize - line.as_ptr() as usize;
+                let token = if ids.len() == 1 && !has_colon && token.starts_with(':') {
+                    has_colon = true;
+                    &token[1..]
+                } else if ids.is_empty() && token.ends_with(':') {
+                    has_colon = true;
+                    &token[..token.len() - 1]
+                } else {
+                    token
+                };
+                if token.is_empty() {
+                    continue;
+                }
+                match VertexIdType::from_str(token) {
+                    Ok(value) => ids.push(value),
+                    Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::ParseError { line: line_number, column: Some(column + 1), expected: "vertex ID".to_string(), found: Some(format!("'{}'", token)) })),
+                }
+            }
+            let mut ids = ids.into_iter();
+            let source = match ids.next() {
+                Some(value) => value,
+                None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::ParseError { line: line_number, column: None, expected: "vertex ID".to_string(), found: Some("':'".to_string()) })),
+            };
+            if !new_graph.contains_v(&source) {
+                new_graph.add_v(Some(source.clone()));
+            }
+            for target in ids {
+                if !new_graph.contains_v(&target) {
+                    new_graph.add_v(Some(target.clone()));
+                }
+                if !self.directed {
+                    if let Some(count) = unpaired_mentions.get_mut(&(target.clone(), source.clone())).filter(|x| **x > 0) {
+                        *count -= 1;
+                        continue;
+                    }
+                    *unpaired_mentions.entry((source.clone(), target.clone())).or_default() += 1;
+                }
+                new_graph.add_e(&source, &target, self.directed, None)?;
+            }
+        }
+        Ok(new_graph)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ADJACENCY LIST WRITER                                                             *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Adjacency list writer
+/// 
+/// ## Description
+/// Writes  graphs  as  adjacency  lists.  Every  vertex  gets  a  line  of   the   form
+/// `id: n1 n2 ...` with its neighbours sorted by ID, lines are sorted  by  the  IDs  of
+/// vertices as well. A directed edge is listed only in  the  line  of  its  source,  an
+/// undirected edge is listed in the lines of both its endpoints. Parallel edges make  a
+/// neighbour appear several times.
+/// 
+/// ## Details
+/// Adjacency lists don't store attributes and can't mix directed and undirected  edges,
+/// so graphs that have both kinds of edges can't be read back in their original form.
+pub struct AdjacencyListWriter;
+
+// AdjacencyListWriter::AdjacencyListWriter
+impl AdjacencyListWriter {
+    /// # Write graph into file
+    /// 
+    /// ## Description
+    /// Write the graph into the file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if the graph  was  written  successfully;
+    /// `Err(_)` is returned otherwise.
+    #[cfg(feature = "std")]
+    pub fn into_file<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, file_name: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyListWriter::AdjacencyListWriter::into_file";
+        let file = match File::options().create(true).write(true).truncate(true).open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.write_graph(graph, &mut BufWriter::new(file))
+    }
+
+    /// # Write graph into string
+    /// 
+    /// ## Description
+    /// Return the adjacency list of the graph as a string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if the  graph  was  written
+    /// successfully, `value` in this case is the adjacency list; `Err(_)` is returned
+    /// otherwise.
+    pub fn into_string<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<String>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyListWriter::AdjacencyListWriter::into_string";
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        self.write_graph(graph, &mut buffer_writer)?;
+        let bytes = buffer_writer.into_inner().map_err(|x| CrabNetsError::new(FUNCTION_PATH, x.into_error()))?;
+        // The list is built from strings, so it is always valid UTF-8
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+}
+
+// AdjacencyListWriter::Writer
+impl Writer for AdjacencyListWriter {
+    fn write_filtered_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyListWriter::Writer::write_filtered_graph";
+        let mut neighbours: HashMap<VertexIdType, Vec<VertexIdType>> = HashMap::with_capacity(graph.count_v());
+        for edge in filter.iter_e(graph) {
+            match edge.direction {
+                EdgeDirection::Directed1to2 => neighbours.entry(edge.id1).or_default().push(edge.id2),
+                EdgeDirection::Directed2to1 => neighbours.entry(edge.id2).or_default().push(edge.id1),
+                EdgeDirection::Undirected => {
+                    if edge.id1 != edge.id2 {
+                        neighbours.entry(edge.id2.clone()).or_default().push(edge.id1.clone());
+                    }
+                    neighbours.entry(edge.id1).or_default().push(edge.id2);
+                },
+            }
+        }
+        let mut output = String::new();
+        for id in filter.iter_v(graph).sorted() {
+            output += &format!("{}:", id);
+            for neighbour in neighbours.remove(&id).unwrap_or_default().into_iter().sorted() {
+                output += &format!(" {}", neighbour);
+            }
+            output += "\n";
+        }
+        buffer_writer.write_all(output.as_bytes()).and_then(|_| buffer_writer.flush()).map_err(|error| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to write the adjacency list: {}", error))))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn adjacency_list() {
+        let g: graph!(X ===X==> X) = AdjacencyListReader::new(true).read_string("# Comment\n0: 1 2 2\n1 :2\n\n  2\n3:").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (4, 4));
+        assert!(g.contains_e(&1, &2, &0).is_some_and(|x| x == EdgeDirection::Directed1to2));
+        let output = AdjacencyListWriter.into_string(&g).unwrap();
+        assert_eq!(output, "0: 1 2 2\n1: 2\n2:\n3:\n");
+        // Undirected edges are read once no matter whether they are listed on both sides
+        let g: graph!(X ===X=== X) = AdjacencyListReader::new(false).read_string("0: 1 1 2\n1: 0\n2: 2").unwrap();
+        assert_eq!((g.count_v(), g.iter_e().count()), (3, 4));
+        let output = AdjacencyListWriter.into_string(&g).unwrap();
+        assert_eq!(output, "0: 1 1 2\n1: 0 0\n2: 0 2\n");
+        let h: graph!(X ===X=== X) = AdjacencyListReader::new(false).read_string(&output).unwrap();
+        assert_eq!(AdjacencyListWriter.into_string(&h).unwrap(), output);
+        let g: graph!(X ---X--- X) = AdjacencyListReader::new(false).with_comment_marker(Some('%')).read_string("% 0: 1\n0 1").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (2, 1));
+        assert!(AdjacencyListReader::new(false).with_comment_marker(None).read_string::<graph!(X ---X--- X), _, _, _, _>("# 0: 1").is_err());
+        match AdjacencyListReader::new(true).read_string::<graph!(X ---X--> X), _, _, _, _>("0: 1\n1: x") {
+            Err(error) => assert!(matches!(error.kind(), ErrorKind::ParseError { line: 2, column: Some(4), .. })),
+            Ok(_) => panic!(),
+        }
+    }
+}
@@ -0,0 +1,490 @@
+//! # Adjacency matrix module
+//! 
+//! ## Description
+//! This module contains [`AdjacencyMatrixReader`]  and  [`AdjacencyMatrixWriter`]  that
+//! read and write graphs as square CSV tables whose first row and first column hold the
+//! IDs of vertices:
+//! 
+//! ```text
+//! ,0,1,2
+//! 0,0,1,1
+//! 1,0,0,1
+//! 2,0,0,0
+//! ```
+//! 
+//! The cell in row `u` and column `v` holds the number of edges from `u` to `v` or,  if
+//! the name of a weight attribute is set, their total weight. Empty cells  are  treated
+//! as zeros, which makes tables saved by spreadsheet editors easy to import.
+#[cfg(feature = "std")]
+use std::fs::File;
+use std::{collections::HashMap, io::{BufRead, BufReader, BufWriter, Read, Write}, str::FromStr};
+use itertools::Itertools;
+use crate::{
+    attributes::StaticDispatchAttributeValue, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id
+};
+use super::{AttributeProjection, csv::escape_csv, AttributeCollectionIO, AttributeToken, ExportFilter, Reader, Writer};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// CSV fields along with the (1-based) columns they start at
+type Fields = Vec<(usize, String)>;
+
+
+
+// Split the line into CSV fields
+fn split_csv_line(line: &str, line_number: usize, function_path: &str) -> CrabNetsResult<Fields> {
+    let mut answer = Vec::new();
+    let mut characters = line.char_indices().peekable();
+    loop {
+        let column = characters.peek().map_or(line.len(), |x| x.0) + 1;
+        let mut field = String::new();
+        if characters.next_if(|x| x.1 == '"').is_some() {
+            loop {
+                match characters.next() {
+                    Some((_, '"')) if characters.next_if(|x| x.1 == '"').is_some() => field.push('"'),
+                    Some((_, '"')) => break,
+                    Some((_, x)) => field.push(x),
+                    None => return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line: line_number, column: Some(line.len() + 1), expected: "'\"'".to_string(), found: None })),
+                }
+            }
+        } else {
+            while let Some((_, x)) = characters.next_if(|x| x.1 != ',') {
+                field.push(x);
+            }
+        }
+        answer.push((column, field.trim().to_string()));
+        match characters.next() {
+            Some((_, ',')) => (),
+            Some((index, x)) => return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line: line_number, column: Some(index + 1), expected: "','".to_string(), found: Some(format!("'{}'", x)) })),
+            None => return Ok(answer),
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ADJACENCY MATRIX READER                                                           *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Adjacency matrix reader
+/// 
+/// ## Description
+/// Reads graphs from CSV adjacency matrices.
+/// 
+/// ## Details
+/// Rows may go in any order, but there must be exactly one row per  column.  Without  a
+/// weight attribute, every cell  must  hold  a  non-negative  integer,  and  that  many
+/// parallel edges are added; with a weight attribute, every non-zero cell produces  one
+/// edge whose attribute is set to the value of the cell as `f64`.
+/// 
+/// In undirected mode, the matrix must be symmetric, and only the cells  on  and  above
+/// the main diagonal are used to build the edges.
+pub struct AdjacencyMatrixReader {
+    directed: bool,
+    weight_attribute: Option<String>,
+}
+
+// AdjacencyMatrixReader::AdjacencyMatrixReader
+impl AdjacencyMatrixReader {
+    /// # Create adjacency matrix reader
+    /// 
+    /// ## Description
+    /// Create a reader that treats the cells as the numbers of edges.
+    /// 
+    /// ## Arguments
+    /// * `directed` : `bool` - whether the cell in row `u` and column `v` describes the
+    /// edges directed from `u` to `v` rather than undirected ones.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyMatrixReader` - the reader.
+    pub fn new(directed: bool) -> Self {
+        AdjacencyMatrixReader { directed, weight_attribute: None }
+    }
+
+    /// # Read graph from file
+    /// 
+    /// ## Description
+    /// Read a graph from the CSV file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    #[cfg(feature = "std")]
+    pub fn read_file<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, file_name: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyMatrixReader::AdjacencyMatrixReader::read_file";
+        let file = match File::open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.read_graph(BufReader::new(file))
+    }
+
+    /// # Read graph from string
+    /// 
+    /// ## Description
+    /// Read a graph from the CSV adjacency matrix stored in the given string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `string` : `&str` - the adjacency matrix.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn read_string<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, string: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        self.read_graph(BufReader::new(string.as_bytes()))
+    }
+
+    /// # Set weight attribute
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `weight_attribute` : `&str` - the name of the edge attribute  that  will  hold
+    /// the values of the cells.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyMatrixReader` - the updated reader.
+    pub fn with_weight_attribute(mut self, weight_attribute: &str) -> Self {
+        self.weight_attribute = Some(weight_attribute.to_string());
+        self
+    }
+}
+
+// AdjacencyMatrixReader::Reader
+impl Reader for AdjacencyMatrixReader {
+    fn read_projected_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>, projection: &AttributeProjection<'_>) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        R: Read,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyMatrixReader::Reader::read_projected_graph";
+        let parse_error = |line: usize, column: usize, expected: &str, found: &str| {
+            CrabNetsError::new(FUNCTION_PATH, ErrorKind::ParseError { line, column: Some(column), expected: expected.to_string(), found: Some(format!("'{}'", found)) })
+        };
+        let parse_id = |line: usize, (column, field): &(usize, String)| {
+            VertexIdType::from_str(field).map_err(|_| parse_error(line, *column, "vertex ID", field))
+        };
+        let mut header = None;
+        // Cells of each row along with the number of its line, indexed by the column of the row's vertex
+        let mut rows: Vec<Option<(usize, Fields)>> = Vec::new();
+        let mut column_indices = HashMap::new();
+        let mut last_line_number = 0;
+        for (line_index, line_result) in buffer_reader.lines().enumerate() {
+            let line_number = line_index + 1;
+            last_line_number = line_number;
+            let line = match line_result {
+                Ok(value) => value,
+                Err(error) => {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Couldn't read line {} of the input file: {}", line_number, error))));
+                },
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let mut fields = split_csv_line(&line, line_number, FUNCTION_PATH)?;
+            if header.is_none() {
+                let ids = fields.iter().skip(1).map(|x| parse_id(line_number, x)).collect::<CrabNetsResult<Vec<VertexIdType>>>()?;
+                for (index, id) in ids.iter().enumerate() {
+                    if column_indices.insert(id.clone(), index).is_some() {
+                        return Err(parse_error(line_number, fields[index + 1].0, "unique vertex ID", &fields[index + 1].1));
+                    }
+                }
+                rows.resize(ids.len(), None);
+                header = Some(ids);
+                continue;
+            }
+            if fields.len() != rows.len() + 1 {
+                let column = fields.get(rows.len() + 1).map_or(line.len() + 1, |x| x.0);
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::ParseError { line: line_number, column: Some(column), expected: format!("{} cells", rows.len() + 1), found: Some(format!("{} cells", fields.len())) }));
+            }
+            let id = parse_id(line_number, &fields[0])?;
+            match column_indices.get(&id) {
+                Some(&index) if rows[index].is_none() => rows[index] = Some((line_number, fields.split_off(1))),
+                _ => return Err(parse_error(line_number, 1, "ID of a vertex from the header without a row", &fields[0].1)),
+            }
+        }
+        let ids = header.unwrap_or_default();
+        if let Some(index) = rows.iter().position(Option::is_none) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::ParseError { line: last_line_number + 1, column: None, expected: format!("row of vertex '{}'", ids[index]), found: None }));
+        }
+        let rows = rows.into_iter().map(Option::unwrap).collect_vec();
+        let mut new_graph = G::default();
+        for id in ids.iter() {
+            new_graph.add_v(Some(id.clone()));
+        }
+        for (row_index, (line_number, cells)) in rows.iter().enumerate() {
+            for (column_index, (column, cell)) in cells.iter().enumerate() {
+                if !self.directed && column_index < row_index {
+                    let (_, mirrored_cell) = &rows[column_index].1[row_index];
+                    if cell != mirrored_cell {
+                        return Err(parse_error(*line_number, *column, &format!("value of the symmetric cell '{}'", mirrored_cell), cell));
+                    }
+                    continue;
+                }
+                if cell.is_empty() {
+                    continue;
+                }
+                let (id1, id2) = (&ids[row_index], &ids[column_index]);
+                match &self.weight_attribute {
+                    Some(weight_attribute) => {
+                        let weight = f64::from_str(cell).map_err(|_| parse_error(*line_number, *column, "number", cell))?;
+                        if weight != 0.0 {
+                            let edge_id = new_graph.add_e(id1, id2, self.directed, None)?;
+                            if let Some(token) = projection.project(AttributeToken { name: weight_attribute, value: StaticDispatchAttributeValue::Float64(weight) }) {
+                                new_graph.e_attrs_mut(id1, id2, &edge_id)?.io_reader_callback::<EdgeIdType, VertexIdType>(token);
+                            }
+                        }
+                    },
+                    None => {
+                        let count = usize::from_str(cell).map_err(|_| parse_error(*line_number, *column, "non-negative integer", cell))?;
+                        for _ in 0..count {
+                            new_graph.add_e(id1, id2, self.directed, None)?;
+                        }
+                    },
+                }
+            }
+        }
+        Ok(new_graph)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ADJACENCY MATRIX WRITER                                                           *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Adjacency matrix writer
+/// 
+/// ## Description
+/// Writes graphs as CSV adjacency matrices with vertices sorted by ID.
+/// 
+/// ## Details
+/// A directed edge from `u` to `v` contributes to the cell in row `u` and  column  `v`,
+/// an undirected edge contributes to both cells of its endpoints (once if it's a loop).
+/// Without a weight attribute, each edge contributes 1; with a weight  attribute,  each
+/// edge contributes the value of this attribute or 1 if the edge doesn't have it.
+pub struct AdjacencyMatrixWriter {
+    weight_attribute: Option<String>,
+}
+
+// AdjacencyMatrixWriter::AdjacencyMatrixWriter
+impl AdjacencyMatrixWriter {
+    /// # Create adjacency matrix writer
+    /// 
+    /// ## Description
+    /// Create a writer that puts the numbers of edges into the cells.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyMatrixWriter` - the writer.
+    pub fn new() -> Self {
+        AdjacencyMatrixWriter { weight_attribute: None }
+    }
+
+    /// # Write graph into file
+    /// 
+    /// ## Description
+    /// Write the adjacency matrix of the graph into the file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if the graph  was  written  successfully;
+    /// `Err(_)` is returned otherwise.
+    #[cfg(feature = "std")]
+    pub fn into_file<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, file_name: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyMatrixWriter::AdjacencyMatrixWriter::into_file";
+        let file = match File::options().create(true).write(true).truncate(true).open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.write_graph(graph, &mut BufWriter::new(file))
+    }
+
+    /// # Write graph into string
+    /// 
+    /// ## Description
+    /// Return the adjacency matrix of the graph as a CSV string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if the  graph  was  written
+    /// successfully, `value` in this case is the adjacency matrix; `Err(_)` is returned
+    /// otherwise.
+    pub fn into_string<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<String>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyMatrixWriter::AdjacencyMatrixWriter::into_string";
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        self.write_graph(graph, &mut buffer_writer)?;
+        let bytes = buffer_writer.into_inner().map_err(|x| CrabNetsError::new(FUNCTION_PATH, x.into_error()))?;
+        // The matrix is built from strings, so it is always valid UTF-8
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+
+    /// # Set weight attribute
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `weight_attribute` : `&str` - the name of the  numeric  edge  attribute  whose
+    /// values should be summed up in the cells.
+    /// 
+    /// ## Returns
+    /// * `AdjacencyMatrixWriter` - the updated writer.
+    pub fn with_weight_attribute(mut self, weight_attribute: &str) -> Self {
+        self.weight_attribute = Some(weight_attribute.to_string());
+        self
+    }
+}
+
+// AdjacencyMatrixWriter::Default
+impl Default for AdjacencyMatrixWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// AdjacencyMatrixWriter::Writer
+impl Writer for AdjacencyMatrixWriter {
+    fn write_filtered_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "AdjacencyMatrixWriter::Writer::write_filtered_graph";
+        let ids = filter.iter_v(graph).sorted().collect_vec();
+        let indices: HashMap<&VertexIdType, usize> = ids.iter().enumerate().map(|(index, id)| (id, index)).collect();
+        let mut matrix = vec![vec![0.0; ids.len()]; ids.len()];
+        for edge in filter.iter_e(graph) {
+            let weight = match &self.weight_attribute {
+                Some(weight_attribute) => match graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?.io_query_contents(weight_attribute) {
+                    Some(value) => match value.as_f64() {
+                        Some(value) => value,
+                        None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: weight_attribute.clone() })),
+                    },
+                    None => 1.0,
+                },
+                None => 1.0,
+            };
+            let (index1, index2) = (indices[&edge.id1], indices[&edge.id2]);
+            match edge.direction {
+                EdgeDirection::Directed1to2 => matrix[index1][index2] += weight,
+                EdgeDirection::Directed2to1 => matrix[index2][index1] += weight,
+                EdgeDirection::Undirected => {
+                    matrix[index1][index2] += weight;
+                    if index1 != index2 {
+                        matrix[index2][index1] += weight;
+                    }
+                },
+            }
+        }
+        let mut output = String::new();
+        for id in ids.iter() {
+            output += &format!(",{}", escape_csv(&id.to_string()));
+        }
+        output += "\n";
+        for (id, row) in ids.iter().zip(matrix) {
+            output += &escape_csv(&id.to_string());
+            for cell in row {
+                output += &format!(",{}", cell);
+            }
+            output += "\n";
+        }
+        buffer_writer.write_all(output.as_bytes()).and_then(|_| buffer_writer.flush()).map_err(|error| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to write the adjacency matrix: {}", error))))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn adjacency_matrix() {
+        let g: graph!(X ===X==> X) = AdjacencyMatrixReader::new(true).read_string(",0,1,2\n1, 0, 0, 1\n0,0,1,2\n\n2,,,\n").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (3, 4));
+        let output = AdjacencyMatrixWriter::new().into_string(&g).unwrap();
+        assert_eq!(output, ",0,1,2\n0,0,1,2\n1,0,0,1\n2,0,0,0\n");
+        // Undirected matrices must be symmetric
+        let g: graph!(X ---X--- X) = AdjacencyMatrixReader::new(false).read_string(",0,1\n0,0,1\n1,1,0").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (2, 1));
+        match AdjacencyMatrixReader::new(false).read_string::<graph!(X ---X--- X), _, _, _, _>(",0,1\n0,0,1\n1,0,0") {
+            Err(error) => assert!(matches!(error.kind(), ErrorKind::ParseError { line: 3, column: Some(3), .. })),
+            Ok(_) => panic!(),
+        }
+        assert!(AdjacencyMatrixReader::new(true).read_string::<graph!(X ---X--> X), _, _, _, _>(",0,1\n0,0,1").is_err());
+        assert!(AdjacencyMatrixReader::new(true).read_string::<graph!(X ---X--> X), _, _, _, _>(",0,1\n0,0,1\n0,0,1").is_err());
+        // Weights are stored in the edge attribute
+        let string = ",\"a,b\",c\n\"a,b\",0,2.5\nc,0,0\n";
+        let g: graph!(X ---A--> X with VertexIdType = String) = AdjacencyMatrixReader::new(true).with_weight_attribute("w").read_string(string).unwrap();
+        assert!(matches!(g.e_attrs(&"a,b".to_string(), &"c".to_string(), &0).unwrap().io_query_contents("w"), Some(StaticDispatchAttributeValue::Float64(x)) if x == 2.5));
+        assert_eq!(AdjacencyMatrixWriter::new().with_weight_attribute("w").into_string(&g).unwrap(), string);
+    }
+}
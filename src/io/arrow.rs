@@ -0,0 +1,583 @@
+//! # Arrow module
+//! 
+//! ## Description
+//! This module contains [`ArrowTableWriter`]  and  [`ArrowTableReader`]  that  exchange
+//! graphs with Apache Arrow record batches, which makes it easy to pass graphs  to  and
+//! from dataframe libraries. As with [CSV tables][csv], a graph  is  represented  by  2
+//! tables: the table of vertices and the table of edges. With  `parquet`  feature,  the
+//! tables can also be stored in Parquet files.
+//! 
+//! Each attribute gets its own column typed after its  [value][value]:  scalars  become
+//! primitive, Boolean or UTF-8 columns, lists and sets become list  columns.  Sets  are
+//! written  with  sorted  elements,  their  fields  are  marked   with   metadata   key
+//! `crabnets.container` set to `set`, so they are read back as sets.
+//! 
+//! [csv]: super::csv
+//! [value]: crate::attributes::StaticDispatchAttributeValue
+use std::{mem::discriminant, str::FromStr, sync::Arc};
+#[cfg(feature = "parquet")]
+use std::{fs::File, path::Path};
+use arrow_array::{
+    builder::{BooleanBuilder, Float32Builder, Float64Builder, Int16Builder, Int32Builder, Int64Builder, Int8Builder, ListBuilder, StringBuilder, UInt16Builder, UInt32Builder, UInt64Builder, UInt8Builder}, cast::AsArray, types::{Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type}, Array, ArrayRef, BooleanArray, RecordBatch, StringArray
+};
+use arrow_schema::{DataType, Field, Schema};
+use itertools::Itertools;
+#[cfg(feature = "parquet")]
+use parquet::arrow::{arrow_reader::ParquetRecordBatchReaderBuilder, ArrowWriter};
+use crate::{
+    errors::{CrabNetsError, CrabNetsResult, ErrorKind}, attributes::StaticDispatchAttributeValue, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id
+};
+use super::{csv::attribute_columns, AttributeCollectionIO, AttributeToken};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Metadata of the fields of list columns that hold sets
+const SET_METADATA: (&str, &str) = ("crabnets.container", "set");
+
+
+
+fn table_error(function_path: &str, table: &str, reason: String) -> CrabNetsError {
+    CrabNetsError::new(function_path, ErrorKind::InvalidArgument { name: table.to_string(), reason })
+}
+
+
+
+#[cfg(feature = "parquet")]
+fn external_error<ErrorType>(function_path: &str, error: ErrorType) -> CrabNetsError
+where
+    ErrorType: std::error::Error + Send + Sync + 'static,
+{
+    CrabNetsError::new(function_path, std::io::Error::other(error))
+}
+
+
+
+#[cfg(feature = "parquet")]
+fn open_file(function_path: &str, directory: &str, file_name: &str, write: bool) -> CrabNetsResult<File> {
+    let path = Path::new(directory).join(file_name);
+    match File::options().create(write).write(write).truncate(write).read(!write).open(&path) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", path.display(), error)))),
+    }
+}
+
+
+
+// Build the column of an attribute out of its values, all the present values must be of
+// the same variant and at least 1 of them must be present; the flag tells whether the
+// column holds sets
+fn build_column(values: Vec<Option<StaticDispatchAttributeValue>>) -> (ArrayRef, bool) {
+    macro_rules! build_column_variants {
+        ($($scalar:ident, $vec:ident $(, $set:ident)? => $builder:ident),+) => {
+            match values.iter().flatten().next().unwrap() {
+                $(
+                    StaticDispatchAttributeValue::$scalar(_) => {
+                        let mut builder = $builder::new();
+                        builder.extend(values.into_iter().map(|x| match x {
+                            Some(StaticDispatchAttributeValue::$scalar(x)) => Some(x),
+                            _ => None,
+                        }));
+                        (Arc::new(builder.finish()) as ArrayRef, false)
+                    },
+                    StaticDispatchAttributeValue::$vec(_) => {
+                        let mut builder = ListBuilder::new($builder::new());
+                        builder.extend(values.into_iter().map(|x| match x {
+                            Some(StaticDispatchAttributeValue::$vec(x)) => Some(x.into_iter().map(Some)),
+                            _ => None,
+                        }));
+                        (Arc::new(builder.finish()) as ArrayRef, false)
+                    },
+                    $(
+                        StaticDispatchAttributeValue::$set(_) => {
+                            let mut builder = ListBuilder::new($builder::new());
+                            builder.extend(values.into_iter().map(|x| match x {
+                                Some(StaticDispatchAttributeValue::$set(x)) => Some(x.into_iter().sorted().map(Some)),
+                                _ => None,
+                            }));
+                            (Arc::new(builder.finish()) as ArrayRef, true)
+                        },
+                    )?
+                )+
+            }
+        };
+    }
+    build_column_variants!(
+        Int8, VecInt8, SetInt8 => Int8Builder, Int16, VecInt16, SetInt16 => Int16Builder,
+        Int32, VecInt32, SetInt32 => Int32Builder, Int64, VecInt64, SetInt64 => Int64Builder,
+        UInt8, VecUInt8, SetUInt8 => UInt8Builder, UInt16, VecUInt16, SetUInt16 => UInt16Builder,
+        UInt32, VecUInt32, SetUInt32 => UInt32Builder, UInt64, VecUInt64, SetUInt64 => UInt64Builder,
+        Float32, VecFloat32 => Float32Builder, Float64, VecFloat64 => Float64Builder,
+        Bool, VecBool, SetBool => BooleanBuilder, Str, VecStr, SetStr => StringBuilder
+    )
+}
+
+
+
+// Put the ID columns and the attribute columns together, the fields of the schema are
+// made from the arrays
+fn make_record_batch(id_columns: Vec<(&str, ArrayRef)>, attribute_columns: Vec<(String, (ArrayRef, bool))>) -> RecordBatch {
+    let mut fields = Vec::new();
+    let mut arrays = Vec::new();
+    for (name, array) in id_columns {
+        fields.push(Field::new(name, array.data_type().clone(), false));
+        arrays.push(array);
+    }
+    for (name, (array, is_set)) in attribute_columns {
+        let field = Field::new(name, array.data_type().clone(), true);
+        fields.push(if is_set { field.with_metadata([SET_METADATA]) } else { field });
+        arrays.push(array);
+    }
+    // The fields are made from the arrays, and each array has one element per row
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays).unwrap()
+}
+
+
+
+// The elements of a string array of any flavour, `None` is returned if the array
+// doesn't hold strings
+fn string_values(array: &dyn Array) -> Option<Vec<Option<&str>>> {
+    match array.data_type() {
+        DataType::Utf8 => Some(array.as_string::<i32>().iter().collect()),
+        DataType::LargeUtf8 => Some(array.as_string::<i64>().iter().collect()),
+        DataType::Utf8View => Some(array.as_string_view().iter().collect()),
+        _ => None,
+    }
+}
+
+
+
+// Read the IDs of the vertices from the column with the given name, the column must hold
+// either strings or integers
+fn read_ids<VertexIdType>(batch: &RecordBatch, column: &str, table: &str, function_path: &str) -> CrabNetsResult<Vec<VertexIdType>>
+where
+    VertexIdType: FromStr,
+{
+    macro_rules! integer_ids {
+        ($array:expr, $($data_type:ident => $arrow_type:ident),+) => {
+            match $array.data_type() {
+                $(DataType::$data_type => $array.as_primitive::<$arrow_type>().iter().map(|x| x.map(|x| x.to_string())).collect_vec(),)+
+                data_type => return Err(table_error(function_path, table, format!("Column '{}' of type {} can't hold vertex IDs.", column, data_type))),
+            }
+        };
+    }
+    let array = match batch.column_by_name(column) {
+        Some(value) => value,
+        None => return Err(table_error(function_path, table, format!("Column '{}' is missing.", column))),
+    };
+    let ids = match string_values(array.as_ref()) {
+        Some(value) => value.into_iter().map(|x| x.map(str::to_string)).collect_vec(),
+        None => integer_ids!(array, Int8 => Int8Type, Int16 => Int16Type, Int32 => Int32Type, Int64 => Int64Type, UInt8 => UInt8Type, UInt16 => UInt16Type, UInt32 => UInt32Type, UInt64 => UInt64Type),
+    };
+    ids.into_iter().enumerate().map(|(row, id)| match id {
+        Some(id) => VertexIdType::from_str(&id).map_err(|_| table_error(function_path, table, format!("'{}' in row {} of column '{}' isn't a valid vertex ID.", id, row, column))),
+        None => Err(table_error(function_path, table, format!("Row {} of column '{}' has no vertex ID.", row, column))),
+    }).collect()
+}
+
+
+
+// Turn the elements of a list into an attribute value, `None` is returned if the type of
+// the elements isn't supported
+fn read_list(values: &dyn Array, is_set: bool) -> Option<StaticDispatchAttributeValue> {
+    macro_rules! read_list_variants {
+        ($($data_type:ident, $arrow_type:ident => $vec:ident $(, $set:ident)?);+) => {
+            match values.data_type() {
+                $(
+                    DataType::$data_type => {
+                        let elements = values.as_primitive::<$arrow_type>().iter().flatten();
+                        $(
+                            if is_set {
+                                return Some(StaticDispatchAttributeValue::$set(elements.collect()));
+                            }
+                        )?
+                        Some(StaticDispatchAttributeValue::$vec(elements.collect()))
+                    },
+                )+
+                DataType::Boolean => {
+                    let elements = values.as_boolean().iter().flatten();
+                    Some(if is_set { StaticDispatchAttributeValue::SetBool(elements.collect()) } else { StaticDispatchAttributeValue::VecBool(elements.collect()) })
+                },
+                _ => {
+                    let elements = string_values(values)?.into_iter().flatten().map(str::to_string);
+                    Some(if is_set { StaticDispatchAttributeValue::SetStr(elements.collect()) } else { StaticDispatchAttributeValue::VecStr(elements.collect()) })
+                },
+            }
+        };
+    }
+    read_list_variants!(
+        Int8, Int8Type => VecInt8, SetInt8; Int16, Int16Type => VecInt16, SetInt16;
+        Int32, Int32Type => VecInt32, SetInt32; Int64, Int64Type => VecInt64, SetInt64;
+        UInt8, UInt8Type => VecUInt8, SetUInt8; UInt16, UInt16Type => VecUInt16, SetUInt16;
+        UInt32, UInt32Type => VecUInt32, SetUInt32; UInt64, UInt64Type => VecUInt64, SetUInt64;
+        Float32, Float32Type => VecFloat32; Float64, Float64Type => VecFloat64
+    )
+}
+
+
+
+// Read the values of an attribute from its column, `None` is returned if the type of the
+// column isn't supported
+fn read_column(array: &dyn Array, is_set: bool) -> Option<Vec<Option<StaticDispatchAttributeValue>>> {
+    macro_rules! read_column_variants {
+        ($($data_type:ident, $arrow_type:ident => $scalar:ident),+) => {
+            match array.data_type() {
+                $(DataType::$data_type => Some(array.as_primitive::<$arrow_type>().iter().map(|x| x.map(StaticDispatchAttributeValue::$scalar)).collect()),)+
+                DataType::Boolean => Some(array.as_boolean().iter().map(|x| x.map(StaticDispatchAttributeValue::Bool)).collect()),
+                DataType::List(_) => array.as_list::<i32>().iter().map(|x| match x {
+                    Some(values) => read_list(values.as_ref(), is_set).map(Some),
+                    None => Some(None),
+                }).collect(),
+                DataType::LargeList(_) => array.as_list::<i64>().iter().map(|x| match x {
+                    Some(values) => read_list(values.as_ref(), is_set).map(Some),
+                    None => Some(None),
+                }).collect(),
+                _ => Some(string_values(array)?.into_iter().map(|x| x.map(|x| StaticDispatchAttributeValue::Str(x.to_string()))).collect()),
+            }
+        };
+    }
+    read_column_variants!(
+        Int8, Int8Type => Int8, Int16, Int16Type => Int16, Int32, Int32Type => Int32, Int64, Int64Type => Int64,
+        UInt8, UInt8Type => UInt8, UInt16, UInt16Type => UInt16, UInt32, UInt32Type => UInt32, UInt64, UInt64Type => UInt64,
+        Float32, Float32Type => Float32, Float64, Float64Type => Float64
+    )
+}
+
+
+
+// Read the values of all the columns of the batch except for the given ID columns
+fn read_attribute_columns(batch: &RecordBatch, id_columns: &[&str], table: &str, function_path: &str) -> CrabNetsResult<Vec<(String, Vec<Option<StaticDispatchAttributeValue>>)>> {
+    let schema = batch.schema();
+    let mut answer = Vec::new();
+    for (field, array) in schema.fields().iter().zip(batch.columns()) {
+        if id_columns.contains(&field.name().as_str()) {
+            continue;
+        }
+        let is_set = field.metadata().get(SET_METADATA.0).is_some_and(|x| x == SET_METADATA.1);
+        match read_column(array.as_ref(), is_set) {
+            Some(values) => answer.push((field.name().clone(), values)),
+            None => return Err(table_error(function_path, table, format!("Column '{}' has type {} that can't be turned into attribute values.", field.name(), field.data_type()))),
+        }
+    }
+    Ok(answer)
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * READER/WRITER                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Arrow table writer
+/// 
+/// ## Description
+/// Exports a graph into 2 Arrow record batches: the table of vertices with column  `id`
+/// and the table of edges with columns  `source`,  `target`  and  `directed`.  IDs  are
+/// written as strings. Each attribute  met  in  the  [attribute collections][attrs]  of
+/// vertices (edges) gets its own nullable column in the respective table, the cells  of
+/// the vertices (edges) that don't have this attribute are null. Rows are sorted by the
+/// IDs of vertices.
+/// 
+/// [attrs]: crate::Graph#attributes
+pub struct ArrowTableWriter;
+
+// ArrowTableWriter::ArrowTableWriter
+impl ArrowTableWriter {
+    /// # Write table of edges
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RecordBatch>` - `Ok(value)` is returned if the table was built
+    /// successfully, `value` in this case is the table of edges; `Err(_)`  is  returned
+    /// if an attribute has different types in different edges.
+    pub fn write_edges<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<RecordBatch>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ArrowTableWriter::ArrowTableWriter::write_edges";
+        let edges = graph.iter_e().sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id))).collect_vec();
+        let columns = attribute_columns(edges.iter().map(|x| graph.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap()), discriminant, FUNCTION_PATH)?;
+        let ends = edges.iter().map(|x| match x.direction {
+            EdgeDirection::Directed2to1 => (&x.id2, &x.id1),
+            _ => (&x.id1, &x.id2),
+        }).collect_vec();
+        let sources: ArrayRef = Arc::new(StringArray::from_iter_values(ends.iter().map(|x| x.0.to_string())));
+        let targets: ArrayRef = Arc::new(StringArray::from_iter_values(ends.iter().map(|x| x.1.to_string())));
+        let directed: ArrayRef = Arc::new(BooleanArray::from(edges.iter().map(|x| x.direction != EdgeDirection::Undirected).collect_vec()));
+        let attributes = columns.into_iter().map(|column| {
+            let values = edges.iter().map(|x| graph.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap().io_query_contents(&column.name)).collect();
+            (column.name, build_column(values))
+        }).collect();
+        Ok(make_record_batch(vec![("source", sources), ("target", targets), ("directed", directed)], attributes))
+    }
+
+    /// # Write table of vertices
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RecordBatch>` - `Ok(value)` is returned if the table was built
+    /// successfully, `value` in this  case  is  the  table  of  vertices;  `Err(_)`  is
+    /// returned if an attribute has different types in different vertices.
+    pub fn write_vertices<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<RecordBatch>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ArrowTableWriter::ArrowTableWriter::write_vertices";
+        let vertices = graph.iter_v().sorted().collect_vec();
+        let columns = attribute_columns(vertices.iter().map(|x| graph.v_attrs(x).unwrap()), discriminant, FUNCTION_PATH)?;
+        let ids: ArrayRef = Arc::new(StringArray::from_iter_values(vertices.iter().map(ToString::to_string)));
+        let attributes = columns.into_iter().map(|column| {
+            let values = vertices.iter().map(|x| graph.v_attrs(x).unwrap().io_query_contents(&column.name)).collect();
+            (column.name, build_column(values))
+        }).collect();
+        Ok(make_record_batch(vec![("id", ids)], attributes))
+    }
+
+    /// # Write tables into Parquet files
+    /// 
+    /// ## Description
+    /// Write  the  tables  of  vertices   and   edges   of   the   graph   into   files
+    /// `vertices.parquet` and `edges.parquet` in the given  directory,  existing  files
+    /// are overwritten.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `directory` : `&str` - the name of an existing directory.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())`  is  returned  if  both  tables  were  written
+    /// successfully; `Err(_)` is returned otherwise.
+    #[cfg(feature = "parquet")]
+    pub fn write_parquet_files<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, directory: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ArrowTableWriter::ArrowTableWriter::write_parquet_files";
+        let write = |file_name: &str, batch: RecordBatch| {
+            let file = open_file(FUNCTION_PATH, directory, file_name, true)?;
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), None).map_err(|x| external_error(FUNCTION_PATH, x))?;
+            writer.write(&batch).map_err(|x| external_error(FUNCTION_PATH, x))?;
+            writer.close().map_err(|x| external_error(FUNCTION_PATH, x)).map(|_| ())
+        };
+        write("vertices.parquet", self.write_vertices(graph)?)?;
+        write("edges.parquet", self.write_edges(graph)?)
+    }
+}
+
+
+
+/// # Arrow table reader
+/// 
+/// ## Description
+/// Builds a graph out of Arrow record batches of vertices and edges  laid  out  as  the
+/// ones made by [`ArrowTableWriter`]. IDs of vertices may be stored either  as  strings
+/// or as integers. All the other columns are treated  as  attributes,  null  cells  are
+/// skipped.
+/// 
+/// Supported types of attribute columns are integers, floating-point numbers, Booleans,
+/// UTF-8 strings and lists of these, null elements of lists are skipped. Lists are read
+/// as    sets    if    their    fields    are    marked    as    described    in    the
+/// [module documentation][module].
+/// 
+/// The vertices met only in  the  table  of  edges  are  added  to  the  graph  without
+/// attributes.
+/// 
+/// [module]: crate::io::arrow
+pub struct ArrowTableReader;
+
+// ArrowTableReader::ArrowTableReader
+impl ArrowTableReader {
+    /// # Read graph from record batches
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `vertices` : `&[RecordBatch]` -  the  table  of  vertices  split  into  record
+    /// batches.
+    /// * `edges` : `&[RecordBatch]` - the table of edges split into record batches.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>`  -  `Ok(value)`  is  returned  if  the  graph  was   built
+    /// successfully, `value` in this case is the  graph;  `Err(_)`  is  returned  if  a
+    /// required column is missing, if a cell of an ID column is null or invalid, or  if
+    /// a column has an unsupported type.
+    pub fn read_graph<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, vertices: &[RecordBatch], edges: &[RecordBatch]) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "ArrowTableReader::ArrowTableReader::read_graph";
+        let mut new_graph = G::default();
+        for batch in vertices {
+            let ids = read_ids::<VertexIdType>(batch, "id", "vertices", FUNCTION_PATH)?;
+            let mut columns = read_attribute_columns(batch, &["id"], "vertices", FUNCTION_PATH)?;
+            for (row, id) in ids.into_iter().enumerate() {
+                new_graph.add_v(Some(id.clone()));
+                let attrs = new_graph.v_attrs_mut(&id).unwrap();
+                for (name, values) in columns.iter_mut() {
+                    if let Some(value) = values[row].take() {
+                        attrs.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value });
+                    }
+                }
+            }
+        }
+        for batch in edges {
+            let sources = read_ids::<VertexIdType>(batch, "source", "edges", FUNCTION_PATH)?;
+            let targets = read_ids::<VertexIdType>(batch, "target", "edges", FUNCTION_PATH)?;
+            let directed = match batch.column_by_name("directed") {
+                Some(value) if value.data_type() == &DataType::Boolean => value.as_boolean().iter().enumerate().map(|(row, x)|
+                    x.ok_or_else(|| table_error(FUNCTION_PATH, "edges", format!("Row {} of column 'directed' is null.", row)))
+                ).collect::<CrabNetsResult<Vec<_>>>()?,
+                Some(value) => return Err(table_error(FUNCTION_PATH, "edges", format!("Column 'directed' must be Boolean, not {}.", value.data_type()))),
+                None => return Err(table_error(FUNCTION_PATH, "edges", "Column 'directed' is missing.".to_string())),
+            };
+            let mut columns = read_attribute_columns(batch, &["source", "target", "directed"], "edges", FUNCTION_PATH)?;
+            for (row, (source, target)) in sources.into_iter().zip(targets).enumerate() {
+                new_graph.get_or_add_v(source.clone());
+                new_graph.get_or_add_v(target.clone());
+                let edge_id = new_graph.add_e(&source, &target, directed[row], None)?;
+                let attrs = new_graph.e_attrs_mut(&source, &target, &edge_id).unwrap();
+                for (name, values) in columns.iter_mut() {
+                    if let Some(value) = values[row].take() {
+                        attrs.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value });
+                    }
+                }
+            }
+        }
+        Ok(new_graph)
+    }
+
+    /// # Read graph from Parquet files
+    /// 
+    /// ## Description
+    /// Read the  tables  of  vertices  and  edges  from  files  `vertices.parquet`  and
+    /// `edges.parquet`    in     the     given     directory,     as     written     by
+    /// [`ArrowTableWriter::write_parquet_files`].
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `directory` : `&str` - the name of the directory with the files.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>`  -  `Ok(value)`  is  returned  if  the  graph   was   read
+    /// successfully, `value` in this case is the graph; `Err(_)` is returned otherwise.
+    #[cfg(feature = "parquet")]
+    pub fn read_parquet_files<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, directory: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "ArrowTableReader::ArrowTableReader::read_parquet_files";
+        let read = |file_name: &str| {
+            let file = open_file(FUNCTION_PATH, directory, file_name, false)?;
+            let reader = ParquetRecordBatchReaderBuilder::try_new(file).and_then(|x| x.build()).map_err(|x| external_error(FUNCTION_PATH, x))?;
+            reader.collect::<Result<Vec<_>, _>>().map_err(|x| external_error(FUNCTION_PATH, x))
+        };
+        self.read_graph(&read("vertices.parquet")?, &read("edges.parquet")?)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::*;
+    use crate::io::csv::{CSVDialect, CSVWriter};
+    use super::*;
+
+    #[test]
+    fn arrow_round_trip() {
+        let mut g: graph!(A ---A--> A) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&0).unwrap().insert("name".to_string(), Box::new("zero".to_string()));
+        g.v_attrs_mut(&1).unwrap().insert("tags".to_string(), Box::new(vec!["a".to_string(), "b".to_string()]));
+        g.v_attrs_mut(&2).unwrap().insert("codes".to_string(), Box::new(HashSet::from([3u16, 1, 2])));
+        g.v_attrs_mut(&2).unwrap().insert("name".to_string(), Box::new("two".to_string()));
+        g.add_e(&1, &0, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.e_attrs_mut(&1, &2, &0).unwrap().insert("weight".to_string(), Box::new(2.5f64));
+        let vertices = ArrowTableWriter.write_vertices(&g).unwrap();
+        let edges = ArrowTableWriter.write_edges(&g).unwrap();
+        assert_eq!((vertices.num_rows(), edges.num_rows()), (4, 2));
+        let schema = vertices.schema();
+        assert_eq!(schema.fields().iter().map(|x| x.name().as_str()).collect_vec(), ["id", "codes", "name", "tags"]);
+        assert_eq!(schema.field(1).metadata().get(SET_METADATA.0).map(String::as_str), Some(SET_METADATA.1));
+        assert!(matches!(schema.field(3).data_type(), DataType::List(x) if x.data_type() == &DataType::Utf8));
+        assert_eq!(edges.column_by_name("source").unwrap().as_string::<i32>().value(0), "1");
+        let h: graph!(A ---A--> A) = ArrowTableReader.read_graph(&[vertices.clone()], &[edges.clone()]).unwrap();
+        assert!(h.v_attrs(&2).unwrap().get(&"codes".to_string()).unwrap().downcast::<HashSet<u16>>().is_some_and(|x| x.len() == 3));
+        let writer = CSVWriter::new(CSVDialect::SQL);
+        assert_eq!(writer.into_strings(&h).unwrap(), writer.into_strings(&g).unwrap());
+        // Edges may mention vertices missing from the table of vertices
+        let h: graph!(A ---A--> A) = ArrowTableReader.read_graph(&[], &[edges.clone()]).unwrap();
+        assert_eq!((h.count_v(), h.count_e()), (3, 2));
+        let missing = edges.project(&[0, 1]).unwrap();
+        assert!(ArrowTableReader.read_graph::<graph!(A ---A--> A), _, _, _, _>(&[vertices], &[missing]).is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidArgument { .. })));
+        g.v_attrs_mut(&3).unwrap().insert("name".to_string(), Box::new(3u8));
+        assert!(ArrowTableWriter.write_vertices(&g).is_err_and(|x| matches!(x.kind(), ErrorKind::AttributeTypeMismatch { .. })));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_files() {
+        let directory = std::env::temp_dir().join(format!("crabnets_parquet_{}", std::process::id()));
+        let directory_name = directory.to_str().unwrap();
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for i in 0..5 {
+            g.add_v(None);
+            g.v_attrs_mut(&i).unwrap().insert("weights".to_string(), Box::new(vec![i as i64, -1]));
+            g.v_attrs_mut(&i).unwrap().insert("labels".to_string(), Box::new(HashSet::from([format!("v{}", i)])));
+        }
+        for i in 0..5 {
+            let edge_id = g.add_e(&i, &((i + 2) % 5), i % 2 == 0, None).unwrap();
+            g.e_attrs_mut(&i, &((i + 2) % 5), &edge_id).unwrap().insert("active".to_string(), Box::new(i % 3 == 0));
+        }
+        ArrowTableWriter.write_parquet_files(&g, directory_name).unwrap();
+        let h: graph!(A ===A==> A) = ArrowTableReader.read_parquet_files(directory_name).unwrap();
+        let writer = CSVWriter::new(CSVDialect::SQL);
+        assert_eq!(writer.into_strings(&h).unwrap(), writer.into_strings(&g).unwrap());
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert!(ArrowTableReader.read_parquet_files::<graph!(A ===A==> A), _, _, _, _>(directory_name).is_err_and(|x| matches!(x.kind(), ErrorKind::Io(_))));
+    }
+}
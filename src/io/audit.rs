@@ -0,0 +1,284 @@
+//! # Import audit module
+//! 
+//! ## Description
+//! This  module  contains  [`AuditedGraph`],  a  graph  container  that   records   the
+//! data-quality issues, or [`ImportAnomaly`]s, found while a graph is being read.
+//! 
+//! Input files often contain duplicate vertices and edges, self-loops  and  edges  that
+//! refer  to  vertices  that  were  never   declared.   Readers   build   graphs   with
+//! [`add_v`][add_v] and [`add_e`][add_e], which silently replace  duplicates,  so  such
+//! issues go unnoticed unless the graph is read into an [`AuditedGraph`].
+//! 
+//! [add_e]: crate::BasicMutableGraph::add_e
+//! [add_v]: crate::BasicMutableGraph::add_v
+use crate::{errors::CrabNetsResult, locales::LocaleTopology, BasicImmutableGraph, BasicMutableGraph, Graph, Id, ImmutableGraphContainer, MutableGraphContainer, RemovedVertex};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * IMPORT ANOMALY                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Anomaly found on import
+/// 
+/// ## Variants
+/// * `DanglingEdge { id1, id2 }` - an edge between `id1` and `id2` refers to  a  vertex
+/// that doesn't exist.
+/// * `DuplicateEdge { id1, id2 }` -  an  edge  between  `id1`  and  `id2`  replaces  an
+/// existing edge, which happens when a [simple][kinds] graph receives parallel edges or
+/// when an edge ID is given twice.
+/// * `DuplicateVertex { id }` - a vertex with ID `id` replaces an existing one together
+/// with its [attributes][attrs] and incident edges.
+/// * `SelfLoop { id }` - an edge connects the vertex with ID `id` to itself,  the  edge
+/// is added.
+/// 
+/// [attrs]: crate::Graph#attributes
+/// [kinds]: crate::Graph#different-kinds-of-graphs
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ImportAnomaly<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    DanglingEdge { id1: VertexIdType, id2: VertexIdType },
+    DuplicateEdge { id1: VertexIdType, id2: VertexIdType },
+    DuplicateVertex { id: VertexIdType },
+    SelfLoop { id: VertexIdType },
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUDITED GRAPH                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Graph that records import anomalies
+/// 
+/// ## Description
+/// A graph container that  wraps  another  [mutable graph container][mgc]  and  records
+/// every [`ImportAnomaly`] it is asked to make.
+/// 
+/// ## Details
+/// Duplicates still replace  the  existing  vertices  and  edges,  as  in  the  wrapped
+/// container. The only difference is that [`add_e`][add_e] creates the missing vertices
+/// of dangling edges with empty [attributes][attrs]  instead  of  failing,  so  that  a
+/// reader  can  go   on   and   report   all   anomalies   of   the   file   at   once;
+/// [`try_add_e`][try_add_e] still fails. The anomalies are recorded in the  order  they
+/// occur and can be inspected with [`AuditedGraph::anomalies`].
+/// 
+/// Readers create graphs on their own, so any reader of the [`io`][io] module can  read
+/// straight into an `AuditedGraph`, e.g. into `AuditedGraph<Box<graph!(A ---A--> A)>>`.
+/// 
+/// [add_e]: crate::BasicMutableGraph::add_e
+/// [attrs]: crate::Graph#attributes
+/// [io]: crate::io
+/// [mgc]: crate::MutableGraphContainer
+/// [try_add_e]: crate::BasicMutableGraph::try_add_e
+#[derive(Clone)]
+pub struct AuditedGraph<ContainerType>
+where
+    ContainerType: MutableGraphContainer,
+{
+    anomalies: Vec<ImportAnomaly<ContainerType::VertexIdType>>,
+    container: ContainerType,
+}
+
+// AuditedGraph::AuditedGraph
+impl<ContainerType> AuditedGraph<ContainerType>
+where
+    ContainerType: MutableGraphContainer,
+{
+    /// # Wrap container
+    /// 
+    /// ## Arguments
+    /// * `container` : `ContainerType` - the container to wrap.
+    /// 
+    /// ## Returns
+    /// * `AuditedGraph` - the audited container with no anomalies recorded.
+    pub fn new(container: ContainerType) -> Self {
+        AuditedGraph { anomalies: Vec::new(), container }
+    }
+
+    /// # Recorded anomalies
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&[ImportAnomaly]` - all anomalies recorded so far in the order they occurred.
+    pub fn anomalies(&self) -> &[ImportAnomaly<ContainerType::VertexIdType>] {
+        &self.anomalies
+    }
+
+    /// # Unwrap container
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `(ContainerType, Vec<ImportAnomaly>)`  -  the  wrapped   container   and   all
+    /// anomalies recorded in the order they occurred.
+    pub fn into_parts(self) -> (ContainerType, Vec<ImportAnomaly<ContainerType::VertexIdType>>) {
+        (self.container, self.anomalies)
+    }
+
+    // Record the anomalies of a new edge and tell whether both its endpoints exist
+    fn audit_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &Option<ContainerType::EdgeIdType>) -> bool {
+        let endpoints_exist = self.contains_v(id1) && self.contains_v(id2);
+        let is_duplicate = endpoints_exist && match (edge_id, ContainerType::LocaleType::IS_MULTI) {
+            (Some(value), true) => self.contains_e(id1, id2, value).is_some(),
+            (None, true) => false,
+            (_, false) => self.contains_e(id1, id2, &Id::default()).is_some(),
+        };
+        if !endpoints_exist {
+            self.anomalies.push(ImportAnomaly::DanglingEdge { id1: id1.clone(), id2: id2.clone() });
+        }
+        if is_duplicate {
+            self.anomalies.push(ImportAnomaly::DuplicateEdge { id1: id1.clone(), id2: id2.clone() });
+        }
+        if id1 == id2 {
+            self.anomalies.push(ImportAnomaly::SelfLoop { id: id1.clone() });
+        }
+        endpoints_exist
+    }
+}
+
+// AuditedGraph::Default
+impl<ContainerType> Default for AuditedGraph<ContainerType>
+where
+    ContainerType: MutableGraphContainer,
+{
+    fn default() -> Self {
+        AuditedGraph::new(ContainerType::default())
+    }
+}
+
+// AuditedGraph::ImmutableGraphContainer
+impl<ContainerType> ImmutableGraphContainer for AuditedGraph<ContainerType>
+where
+    ContainerType: MutableGraphContainer,
+{
+    type EdgeAttributeCollectionType = ContainerType::EdgeAttributeCollectionType;
+    type EdgeIdType = ContainerType::EdgeIdType;
+    type LocaleType = ContainerType::LocaleType;
+    type VertexAttributeCollectionType = ContainerType::VertexAttributeCollectionType;
+    type VertexIdType = ContainerType::VertexIdType;
+
+    #[inline]
+    fn unwrap(&self) -> &Graph<Self::EdgeAttributeCollectionType, Self::EdgeIdType, Self::LocaleType, Self::VertexAttributeCollectionType, Self::VertexIdType> {
+        ImmutableGraphContainer::unwrap(&self.container)
+    }
+}
+
+// AuditedGraph::BasicMutableGraph
+impl<ContainerType> BasicMutableGraph<ContainerType::EdgeAttributeCollectionType, ContainerType::EdgeIdType, ContainerType::VertexAttributeCollectionType, ContainerType::VertexIdType> for AuditedGraph<ContainerType>
+where
+    ContainerType: MutableGraphContainer,
+{
+    fn add_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, directed: bool, edge_id: Option<ContainerType::EdgeIdType>) -> CrabNetsResult<ContainerType::EdgeIdType> {
+        if !self.audit_e(id1, id2, &edge_id) {
+            let graph = MutableGraphContainer::unwrap(&mut self.container);
+            for id in [id1, id2] {
+                if !graph.contains_v(id) {
+                    graph.add_v(Some(id.clone()));
+                }
+            }
+        }
+        MutableGraphContainer::unwrap(&mut self.container).add_e(id1, id2, directed, edge_id)
+    }
+
+    fn add_v(&mut self, id: Option<ContainerType::VertexIdType>) -> ContainerType::VertexIdType {
+        if let Some(value) = id.as_ref().filter(|value| self.contains_v(value)) {
+            self.anomalies.push(ImportAnomaly::DuplicateVertex { id: value.clone() });
+        }
+        MutableGraphContainer::unwrap(&mut self.container).add_v(id)
+    }
+
+    #[inline]
+    fn e_attrs_mut(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<&mut ContainerType::EdgeAttributeCollectionType> {
+        MutableGraphContainer::unwrap(&mut self.container).e_attrs_mut(id1, id2, edge_id)
+    }
+
+    #[inline]
+    fn get_or_add_v(&mut self, id: ContainerType::VertexIdType) -> &mut ContainerType::VertexAttributeCollectionType {
+        MutableGraphContainer::unwrap(&mut self.container).get_or_add_v(id)
+    }
+
+    #[inline]
+    fn remove_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<bool> {
+        MutableGraphContainer::unwrap(&mut self.container).remove_e(id1, id2, edge_id)
+    }
+
+    #[inline]
+    fn remove_v(&mut self, id: &ContainerType::VertexIdType) -> Option<RemovedVertex<ContainerType::EdgeAttributeCollectionType, ContainerType::EdgeIdType, ContainerType::VertexAttributeCollectionType, ContainerType::VertexIdType>> {
+        MutableGraphContainer::unwrap(&mut self.container).remove_v(id)
+    }
+
+    #[inline]
+    fn take_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<Option<ContainerType::EdgeAttributeCollectionType>> {
+        MutableGraphContainer::unwrap(&mut self.container).take_e(id1, id2, edge_id)
+    }
+
+    fn try_add_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, directed: bool, edge_id: Option<ContainerType::EdgeIdType>) -> CrabNetsResult<ContainerType::EdgeIdType> {
+        self.audit_e(id1, id2, &edge_id);
+        MutableGraphContainer::unwrap(&mut self.container).try_add_e(id1, id2, directed, edge_id)
+    }
+
+    fn try_add_v(&mut self, id: Option<ContainerType::VertexIdType>) -> CrabNetsResult<ContainerType::VertexIdType> {
+        if let Some(value) = id.as_ref().filter(|value| self.contains_v(value)) {
+            self.anomalies.push(ImportAnomaly::DuplicateVertex { id: value.clone() });
+        }
+        MutableGraphContainer::unwrap(&mut self.container).try_add_v(id)
+    }
+
+    #[inline]
+    fn v_attrs_mut(&mut self, id: &ContainerType::VertexIdType) -> CrabNetsResult<&mut ContainerType::VertexAttributeCollectionType> {
+        MutableGraphContainer::unwrap(&mut self.container).v_attrs_mut(id)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use crate::*;
+    use crate::io::{gnbs::GNBSReader, Reader};
+    use super::*;
+
+    #[test]
+    fn audited_graph() {
+        let string = "AV S Name\n\nV 0 \"a\"\nV 1 \"b\"\nV 0 \"c\"\nA 0 1\nA 1 0\nE 1 1\nA 1 5\n";
+        let g: AuditedGraph<Box<graph!(A ---A--> A)>> = GNBSReader.read_graph(BufReader::new(string.as_bytes())).ok().unwrap();
+        assert_eq!(g.anomalies(), &[
+            ImportAnomaly::DuplicateVertex { id: 0 },
+            ImportAnomaly::DuplicateEdge { id1: 1, id2: 0 },
+            ImportAnomaly::SelfLoop { id: 1 },
+            ImportAnomaly::DanglingEdge { id1: 1, id2: 5 },
+        ]);
+        let (h, anomalies) = g.into_parts();
+        assert_eq!(anomalies.len(), 4);
+        assert_eq!(h.count_v(), 3);
+        assert!(h.contains_e(&1, &5, &0) == Some(EdgeDirection::Directed1to2));
+        let mut g: AuditedGraph<Box<graph!(A ===A==> A)>> = AuditedGraph::default();
+        g.add_v(Some(0));
+        g.add_v(Some(1));
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &1, true, Some(1)).unwrap();
+        assert!(g.try_add_e(&0, &2, true, None).is_err());
+        assert_eq!(g.anomalies(), &[
+            ImportAnomaly::DuplicateEdge { id1: 0, id2: 1 },
+            ImportAnomaly::DanglingEdge { id1: 0, id2: 2 },
+        ]);
+    }
+}
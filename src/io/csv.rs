@@ -0,0 +1,428 @@
+//! # CSV module
+//! 
+//! ## Description
+//! This module contains [`CSVWriter`] that exports graphs into a pair  of  CSV  tables,
+//! one for vertices and one for edges, ready to be imported into  graph  or  relational
+//! databases.
+#[cfg(feature = "std")]
+use std::{fs::File, path::Path};
+use std::io::{BufWriter, Write};
+use itertools::Itertools;
+use crate::{
+    errors::{CrabNetsError, CrabNetsResult, ErrorKind}, attributes::StaticDispatchAttributeValue, BasicImmutableGraph, EdgeDirection, Id
+};
+use super::AttributeCollectionIO;
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Split the value into the name of its (element) type in Neo4j, the textual
+// representations of its elements and the flag that tells whether it's an array
+fn decompose(value: &StaticDispatchAttributeValue) -> (&'static str, Vec<String>, bool) {
+    macro_rules! decompose_variants {
+        ($($scalar:ident, $vec:ident, $set:ident => $type_name:literal),+) => {
+            match value {
+                $(
+                    StaticDispatchAttributeValue::$scalar(x) => ($type_name, vec![x.to_string()], false),
+                    StaticDispatchAttributeValue::$vec(x) => ($type_name, x.iter().map(ToString::to_string).collect(), true),
+                    StaticDispatchAttributeValue::$set(x) => ($type_name, x.iter().sorted().map(ToString::to_string).collect(), true),
+                )+
+                StaticDispatchAttributeValue::Float32(x) => ("float", vec![x.to_string()], false),
+                StaticDispatchAttributeValue::Float64(x) => ("double", vec![x.to_string()], false),
+                StaticDispatchAttributeValue::VecFloat32(x) => ("float", x.iter().map(ToString::to_string).collect(), true),
+                StaticDispatchAttributeValue::VecFloat64(x) => ("double", x.iter().map(ToString::to_string).collect(), true),
+            }
+        };
+    }
+    decompose_variants!(
+        Int8, VecInt8, SetInt8 => "byte", Int16, VecInt16, SetInt16 => "short",
+        Int32, VecInt32, SetInt32 => "int", Int64, VecInt64, SetInt64 => "long",
+        UInt8, VecUInt8, SetUInt8 => "short", UInt16, VecUInt16, SetUInt16 => "int",
+        UInt32, VecUInt32, SetUInt32 => "long", UInt64, VecUInt64, SetUInt64 => "long",
+        Bool, VecBool, SetBool => "boolean", Str, VecStr, SetStr => "string"
+    )
+}
+
+// Name of the (element) type of the value in Neo4j and the flag that tells whether it's
+// an array
+type CSVType = (&'static str, bool);
+
+fn csv_type(value: &StaticDispatchAttributeValue) -> CSVType {
+    let (type_name, _, is_array) = decompose(value);
+    (type_name, is_array)
+}
+
+
+
+// Quote the field if it contains characters that have special meaning in CSV
+pub(super) fn escape_csv(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+
+
+// Quote the string as a JSON string literal
+fn escape_json(text: &str) -> String {
+    let mut answer = String::with_capacity(text.len() + 2);
+    answer.push('"');
+    for character in text.chars() {
+        match character {
+            '"' => answer += "\\\"",
+            '\\' => answer += "\\\\",
+            '\n' => answer += "\\n",
+            '\r' => answer += "\\r",
+            '\t' => answer += "\\t",
+            x if x.is_control() => answer += &format!("\\u{:04x}", x as u32),
+            x => answer.push(x),
+        }
+    }
+    answer.push('"');
+    answer
+}
+
+fn io_error(function_path: &str, error: std::io::Error) -> CrabNetsError {
+    CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to write the CSV table: {}", error)))
+}
+
+
+
+pub(super) struct AttributeColumn<KindType> {
+    pub(super) kind: KindType,
+    pub(super) name: String,
+}
+
+
+
+// Collect the attributes met in the given collections into columns sorted by name, the
+// kind of the values of an attribute (e.g. their type in the target format) must be the
+// same in all collections
+pub(super) fn attribute_columns<'a, AttributeCollectionType, IteratorType, KindType, KindFunctionType>(collections: IteratorType, kind: KindFunctionType, function_path: &str) -> CrabNetsResult<Vec<AttributeColumn<KindType>>>
+where
+    AttributeCollectionType: AttributeCollectionIO + 'a,
+    IteratorType: Iterator<Item = &'a AttributeCollectionType>,
+    KindType: PartialEq,
+    KindFunctionType: Fn(&StaticDispatchAttributeValue) -> KindType,
+{
+    let mut answer: Vec<AttributeColumn<KindType>> = Vec::new();
+    for collection in collections {
+        for token in collection.io_iter_contents() {
+            let value_kind = kind(&token.value);
+            match answer.iter().find(|x| x.name == token.name) {
+                Some(column) if column.kind != value_kind => {
+                    return Err(CrabNetsError::new(function_path, ErrorKind::AttributeTypeMismatch { name: token.name.to_string() }));
+                },
+                Some(_) => (),
+                None => answer.push(AttributeColumn { kind: value_kind, name: token.name.to_string() }),
+            }
+        }
+    }
+    answer.sort_by(|x, y| x.name.cmp(&y.name));
+    Ok(answer)
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CSV WRITER                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # CSV dialect
+/// 
+/// ## Description
+/// Defines the conventions of the CSV tables written by [`CSVWriter`].
+/// 
+/// ## Variants
+/// * `Neo4j` - the header convention expected  by  `neo4j-admin import`.  Vertices  are
+/// written into `nodes.csv` with columns `id:ID` and `name:type`  for  each  attribute.
+/// Edges are written into  `relationships.csv`  with  columns  `:START_ID`,  `:END_ID`,
+/// `:TYPE` and `name:type` for  each  attribute.  The  type  of  each  edge  is  either
+/// `DIRECTED` or `UNDIRECTED`. Elements of lists and sets are separated by `;`.
+/// * `SQL` - plain tables suitable  for  `COPY ... FROM ... WITH (FORMAT csv, HEADER)`.
+/// Vertices are written into `vertices.csv` with columns `id` and one column  for  each
+/// attribute. Edges are written  into  `edges.csv`  with  columns  `source`,  `target`,
+/// `directed` and one column for each attribute. Lists and sets  are  written  as  JSON
+/// arrays.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CSVDialect {
+    Neo4j,
+    SQL,
+}
+
+
+
+/// # CSV writer
+/// 
+/// ## Description
+/// Exports a graph into 2 CSV tables: the table of vertices and  the  table  of  edges.
+/// Each attribute met in the [attribute collections][attrs] of  vertices  (edges)  gets
+/// its own column in the respective table, the cells of the vertices (edges) that don't
+/// have this attribute are left empty, which both  Neo4j  and  SQL  databases  read  as
+/// missing values. Rows are sorted by the IDs of vertices.
+/// 
+/// Neo4j has no unsigned integer types, so unsigned attributes are  exported  with  the
+/// smallest signed type that can hold them  (except  for  `u64`  that  is  exported  as
+/// `long`).
+/// 
+/// [attrs]: crate::Graph#attributes
+pub struct CSVWriter {
+    dialect: CSVDialect,
+}
+
+// CSVWriter::CSVWriter
+impl CSVWriter {
+    /// # Create CSV writer
+    /// 
+    /// ## Arguments
+    /// * `dialect` : `CSVDialect` - the conventions of the written tables.
+    /// 
+    /// ## Returns
+    /// * `CSVWriter` - the writer.
+    pub fn new(dialect: CSVDialect) -> Self {
+        CSVWriter { dialect }
+    }
+
+    // Turn the value of the attribute into a CSV field
+    fn format_value(&self, value: Option<StaticDispatchAttributeValue>) -> String {
+        let (type_name, elements, is_array) = match value {
+            Some(value) => decompose(&value),
+            None => return String::new(),
+        };
+        let field = match (self.dialect, is_array) {
+            (_, false) => elements.into_iter().next().unwrap(),
+            (CSVDialect::Neo4j, true) => elements.join(";"),
+            (CSVDialect::SQL, true) if type_name == "string" => format!("[{}]", elements.iter().map(|x| escape_json(x)).join(",")),
+            (CSVDialect::SQL, true) => format!("[{}]", elements.join(",")),
+        };
+        escape_csv(&field)
+    }
+
+    // Build the header of the table
+    fn header(&self, id_columns: &[&str], columns: &[AttributeColumn<CSVType>]) -> String {
+        id_columns.iter().map(|x| x.to_string()).chain(columns.iter().map(|x| match self.dialect {
+            CSVDialect::Neo4j => escape_csv(&format!("{}:{}{}", x.name, x.kind.0, if x.kind.1 { "[]" } else { "" })),
+            CSVDialect::SQL => escape_csv(&x.name),
+        })).join(",") + "\n"
+    }
+
+    /// # Write table of edges
+    /// 
+    /// ## Description
+    /// Write the table of edges of the graph into the given buffer.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `buffer_writer` : `&mut BufWriter<W>` - a mutable reference to the buffer  the
+    /// table is written into.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  table  was   written
+    /// successfully; `Err(_)` is returned  if  an  attribute  has  different  types  in
+    /// different edges or if the table couldn't be written.
+    pub fn write_edges<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "CSVWriter::CSVWriter::write_edges";
+        let edges = graph.iter_e().sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id))).collect_vec();
+        let columns = attribute_columns(edges.iter().map(|x| graph.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap()), csv_type, FUNCTION_PATH)?;
+        let mut output = match self.dialect {
+            CSVDialect::Neo4j => self.header(&[":START_ID", ":END_ID", ":TYPE"], &columns),
+            CSVDialect::SQL => self.header(&["source", "target", "directed"], &columns),
+        };
+        for edge in edges {
+            let attrs = graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?;
+            let (source, target) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1),
+                _ => (&edge.id1, &edge.id2),
+            };
+            let directed = edge.direction != EdgeDirection::Undirected;
+            output += &escape_csv(&source.to_string());
+            output += ",";
+            output += &escape_csv(&target.to_string());
+            output += match (self.dialect, directed) {
+                (CSVDialect::Neo4j, true) => ",DIRECTED",
+                (CSVDialect::Neo4j, false) => ",UNDIRECTED",
+                (CSVDialect::SQL, true) => ",true",
+                (CSVDialect::SQL, false) => ",false",
+            };
+            for column in columns.iter() {
+                output += ",";
+                output += &self.format_value(attrs.io_query_contents(&column.name));
+            }
+            output += "\n";
+        }
+        buffer_writer.write_all(output.as_bytes()).map_err(|x| io_error(FUNCTION_PATH, x))?;
+        buffer_writer.flush().map_err(|x| io_error(FUNCTION_PATH, x))
+    }
+
+    /// # Write table of vertices
+    /// 
+    /// ## Description
+    /// Write the table of vertices of the graph into the given buffer.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `buffer_writer` : `&mut BufWriter<W>` - a mutable reference to the buffer  the
+    /// table is written into.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  table  was   written
+    /// successfully; `Err(_)` is returned  if  an  attribute  has  different  types  in
+    /// different vertices or if the table couldn't be written.
+    pub fn write_vertices<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "CSVWriter::CSVWriter::write_vertices";
+        let vertices = graph.iter_v().sorted().collect_vec();
+        let columns = attribute_columns(vertices.iter().map(|x| graph.v_attrs(x).unwrap()), csv_type, FUNCTION_PATH)?;
+        let mut output = match self.dialect {
+            CSVDialect::Neo4j => self.header(&["id:ID"], &columns),
+            CSVDialect::SQL => self.header(&["id"], &columns),
+        };
+        for id in vertices {
+            let attrs = graph.v_attrs(&id)?;
+            output += &escape_csv(&id.to_string());
+            for column in columns.iter() {
+                output += ",";
+                output += &self.format_value(attrs.io_query_contents(&column.name));
+            }
+            output += "\n";
+        }
+        buffer_writer.write_all(output.as_bytes()).map_err(|x| io_error(FUNCTION_PATH, x))?;
+        buffer_writer.flush().map_err(|x| io_error(FUNCTION_PATH, x))
+    }
+
+    /// # Write tables into files
+    /// 
+    /// ## Description
+    /// Write the tables of vertices and edges of the graph into the files in the  given
+    /// directory. The names of the files are defined by the [dialect][dialect]  of  the
+    /// caller, existing files are overwritten.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `directory` : `&str` - the name of an existing directory.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())`  is  returned  if  both  tables  were  written
+    /// successfully; `Err(_)` is returned otherwise.
+    /// 
+    /// [dialect]: CSVDialect
+    #[cfg(feature = "std")]
+    pub fn into_files<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, directory: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "CSVWriter::CSVWriter::into_files";
+        let (vertices_file_name, edges_file_name) = match self.dialect {
+            CSVDialect::Neo4j => ("nodes.csv", "relationships.csv"),
+            CSVDialect::SQL => ("vertices.csv", "edges.csv"),
+        };
+        let open = |file_name: &str| {
+            let path = Path::new(directory).join(file_name);
+            match File::options().create(true).write(true).truncate(true).open(&path) {
+                Ok(value) => Ok(BufWriter::new(value)),
+                Err(error) => Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", path.display(), error)))),
+            }
+        };
+        self.write_vertices(graph, &mut open(vertices_file_name)?)?;
+        self.write_edges(graph, &mut open(edges_file_name)?)
+    }
+
+    /// # Write tables into strings
+    /// 
+    /// ## Description
+    /// Return the tables of vertices and edges of the graph as strings.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<(String, String)>` - `Ok((vertices, edges))`  is  returned  if
+    /// both tables were built successfully, `vertices` and `edges` in this case are the
+    /// tables of vertices and edges respectively; `Err(_)` is returned otherwise.
+    pub fn into_strings<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<(String, String)>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "CSVWriter::CSVWriter::into_strings";
+        let mut vertices_writer = BufWriter::new(Vec::new());
+        self.write_vertices(graph, &mut vertices_writer)?;
+        let mut edges_writer = BufWriter::new(Vec::new());
+        self.write_edges(graph, &mut edges_writer)?;
+        let vertices = vertices_writer.into_inner().map_err(|x| io_error(FUNCTION_PATH, x.into_error()))?;
+        let edges = edges_writer.into_inner().map_err(|x| io_error(FUNCTION_PATH, x.into_error()))?;
+        // The tables are built from strings, so they are always valid UTF-8
+        Ok((String::from_utf8(vertices).unwrap(), String::from_utf8(edges).unwrap()))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn write_csv() {
+        let mut g: graph!(A ---A--> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&0).unwrap().insert("name".to_string(), Box::new("Smith, \"J\"".to_string()));
+        g.v_attrs_mut(&1).unwrap().insert("tags".to_string(), Box::new(vec!["a".to_string(), "b".to_string()]));
+        g.v_attrs_mut(&2).unwrap().insert("name".to_string(), Box::new("Doe".to_string()));
+        g.add_e(&1, &0, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.e_attrs_mut(&1, &2, &0).unwrap().insert("weight".to_string(), Box::new(2.5f64));
+        let (vertices, edges) = CSVWriter::new(CSVDialect::Neo4j).into_strings(&g).unwrap();
+        assert_eq!(vertices, "id:ID,name:string,tags:string[]\n0,\"Smith, \"\"J\"\"\",\n1,,a;b\n2,Doe,\n");
+        assert_eq!(edges, ":START_ID,:END_ID,:TYPE,weight:double\n1,0,DIRECTED,\n1,2,UNDIRECTED,2.5\n");
+        let (vertices, edges) = CSVWriter::new(CSVDialect::SQL).into_strings(&g).unwrap();
+        assert_eq!(vertices, "id,name,tags\n0,\"Smith, \"\"J\"\"\",\n1,,\"[\"\"a\"\",\"\"b\"\"]\"\n2,Doe,\n");
+        assert_eq!(edges, "source,target,directed,weight\n1,0,true,\n1,2,false,2.5\n");
+        g.e_attrs_mut(&1, &0, &0).unwrap().insert("weight".to_string(), Box::new("heavy".to_string()));
+        assert!(CSVWriter::new(CSVDialect::SQL).into_strings(&g).is_err_and(|x| matches!(x.kind(), ErrorKind::AttributeTypeMismatch { .. })));
+    }
+}
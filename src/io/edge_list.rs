@@ -0,0 +1,275 @@
+//! # Edge list module
+//! 
+//! ## Description
+//! This module contains a specialised loader for plain  edge  lists,  i.e.  text  files
+//! where each line holds the IDs of the 2 vertices connected by an  edge  separated  by
+//! spaces or tabs:
+//! 
+//! ```text
+//! # Comment lines start with '#' or '%'
+//! 0 1
+//! 1 2
+//! 2 0
+//! ```
+//! 
+//! Such files carry no attributes, so the loader doesn't  use  the  tokenizer  and  the
+//! attribute machinery of [GNBS][gnbs]. Instead,  it  parses  vertex  IDs  as  unsigned
+//! integers right from the bytes of the file and builds the  graph  with  pre-allocated
+//! storage, which makes it much faster for big topology-only datasets.
+//! 
+//! [gnbs]: super::gnbs
+#[cfg(all(feature = "std", not(all(feature = "mmap", unix))))]
+use std::fs::read;
+use crate::{
+    errors::{CrabNetsError, CrabNetsResult, ErrorKind}, handles::HANDLES_EXHAUSTED, locales::EdgeToVertexRelation, AttributeCollection, Graph, Id, Locale
+};
+#[cfg(all(feature = "mmap", unix))]
+use super::MappedFile;
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY FUNCTIONS                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Describe the byte at the given position for error messages
+fn describe_byte(bytes: &[u8], position: usize) -> Option<String> {
+    match bytes.get(position) {
+        Some(b'\n') | Some(b'\r') | None => Some("end of line".to_string()),
+        Some(value) => Some(format!("'{}'", value.escape_ascii())),
+    }
+}
+
+
+
+// Parse the edge list and pass each pair of integer vertex IDs to the handler as soon as
+// it's read
+fn parse_edge_list<EdgeHandlerType>(bytes: &[u8], function_path: &str, mut edge_handler: EdgeHandlerType) -> CrabNetsResult<()>
+where
+    EdgeHandlerType: FnMut(u64, u64) -> CrabNetsResult<()>,
+{
+    let mut position = 0;
+    let mut line = 1;
+    while position < bytes.len() {
+        let line_start = position;
+        while position < bytes.len() && (bytes[position] == b' ' || bytes[position] == b'\t') {
+            position += 1;
+        }
+        // Skip empty lines and comments
+        if position == bytes.len() || matches!(bytes[position], b'\n' | b'\r' | b'#' | b'%') {
+            while position < bytes.len() && bytes[position] != b'\n' {
+                position += 1;
+            }
+            position += 1;
+            line += 1;
+            continue;
+        }
+        let mut ids = [0u64; 2];
+        for (i, id) in ids.iter_mut().enumerate() {
+            if i == 1 {
+                let separator_start = position;
+                while position < bytes.len() && (bytes[position] == b' ' || bytes[position] == b'\t') {
+                    position += 1;
+                }
+                if position == separator_start {
+                    return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line, column: Some(position - line_start + 1), expected: "whitespace".to_string(), found: describe_byte(bytes, position) }));
+                }
+            }
+            let digits_start = position;
+            while position < bytes.len() && bytes[position].is_ascii_digit() {
+                *id = match id.checked_mul(10).and_then(|x| x.checked_add((bytes[position] - b'0') as u64)) {
+                    Some(value) => value,
+                    None => return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line, column: Some(digits_start - line_start + 1), expected: "vertex ID that fits into 64 bits".to_string(), found: None })),
+                };
+                position += 1;
+            }
+            if position == digits_start {
+                return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line, column: Some(position - line_start + 1), expected: "vertex ID".to_string(), found: describe_byte(bytes, position) }));
+            }
+        }
+        while position < bytes.len() && matches!(bytes[position], b' ' | b'\t' | b'\r') {
+            position += 1;
+        }
+        if position < bytes.len() && bytes[position] != b'\n' {
+            return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line, column: Some(position - line_start + 1), expected: "end of line".to_string(), found: describe_byte(bytes, position) }));
+        }
+        position += 1;
+        line += 1;
+        edge_handler(ids[0], ids[1])?;
+    }
+    Ok(())
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EDGE LIST LOADER                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id + TryFrom<u64>,
+{
+    // Build a graph out of the edge list stored in the given bytes
+    pub(crate) fn from_edge_list_bytes(bytes: &[u8], directed: bool, function_path: &str) -> CrabNetsResult<Self> {
+        // The first pass only validates the input and estimates the number of vertices,
+        // so that no intermediate storage for edges is needed
+        let (mut count_e, mut max_id) = (0usize, 0u64);
+        parse_edge_list(bytes, function_path, |id1, id2| {
+            count_e += 1;
+            max_id = max_id.max(id1).max(id2);
+            Ok(())
+        })?;
+        let mut answer = Self::new();
+        let capacity = (max_id as usize).saturating_add(1).min(2 * count_e);
+        answer.edge_list.reserve(capacity);
+        answer.handles.reserve(capacity);
+        let convert = |id: u64| VertexIdType::try_from(id).map_err(|_| CrabNetsError::new(function_path, ErrorKind::InvalidArgument { name: "file_name".to_string(), reason: format!("Vertex ID {} doesn't fit into the vertex ID type.", id) }));
+        parse_edge_list(bytes, function_path, |id1, id2| {
+            let (id1, id2) = (convert(id1)?, convert(id2)?);
+            if !answer.edge_list.contains_key(&id1) {
+                answer.handles.insert(id1.clone()).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::UnsupportedGraph { reason: HANDLES_EXHAUSTED.to_string() }))?;
+                answer.edge_list.insert(id1.clone(), LocaleType::new(id1.clone()));
+            }
+            if !answer.edge_list.contains_key(&id2) {
+                answer.handles.insert(id2.clone()).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::UnsupportedGraph { reason: HANDLES_EXHAUSTED.to_string() }))?;
+                answer.edge_list.insert(id2.clone(), LocaleType::new(id2.clone()));
+            }
+            let (relation1, relation2) = if directed {
+                (EdgeToVertexRelation::Outgoing, EdgeToVertexRelation::Incoming)
+            } else {
+                (EdgeToVertexRelation::Undirected, EdgeToVertexRelation::Undirected)
+            };
+            let edge_id = answer.edge_list.get_mut(&id1).unwrap().add_e(id2.clone(), relation1, None, directed || id1 <= id2);
+            answer.edge_list.get_mut(&id2).unwrap().add_e(id1.clone(), relation2, Some(edge_id), !directed && id2 <= id1);
+            Ok(())
+        })?;
+        while answer.edge_list.contains_key(&answer.min_free_vertex_id) {
+            answer.min_free_vertex_id.increment();
+        }
+        #[cfg(all(feature = "validate", debug_assertions))]
+        answer.debug_validate();
+        Ok(answer)
+    }
+
+    /// # Read graph from edge list file
+    /// 
+    /// ## Description
+    /// Read a graph without attributes from a [plain edge list][edge_list] file.  Every
+    /// vertex mentioned in the file is added to the graph, vertex IDs  are  taken  from
+    /// the file as is.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the file to read from.
+    /// * `directed` : `bool` - whether the edges should be directed from the first vertex
+    /// of each line to the second one.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// This function is only available with  `std`  feature.  With  `mmap`  feature  on
+    /// Unix-like systems, the file is mapped into memory  and  parsed  right  from  the
+    /// mapped region instead of being copied into a buffer first,  which  reduces  both
+    /// the load time and the peak memory usage for huge files. In this case,  the  file
+    /// must not be modified while it's being read.
+    /// 
+    /// Vertex and edge attribute collections of  the  read  graph  have  their  default
+    /// values. Unlike [`IO::from_file`][from_file], the function doesn't  look  at  the
+    /// extension of the file.
+    /// 
+    /// For simple graphs, repeated lines and  lines  with  the  same  vertices  in  the
+    /// opposite order produce a single edge.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [edge_list]: crate::io::edge_list
+    /// [from_file]: crate::io::IO::from_file
+    #[cfg(feature = "std")]
+    pub fn from_edge_list_file(file_name: &str, directed: bool) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::from_edge_list_file";
+        #[cfg(all(feature = "mmap", unix))]
+        let file = MappedFile::open(file_name);
+        #[cfg(not(all(feature = "mmap", unix)))]
+        let file = read(file_name);
+        let file = match file {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        #[cfg(all(feature = "mmap", unix))]
+        let bytes = file.as_bytes();
+        #[cfg(not(all(feature = "mmap", unix)))]
+        let bytes = &file;
+        Self::from_edge_list_bytes(bytes, directed, FUNCTION_PATH)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * TESTS                                                                             *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn edge_list() {
+        let g: graph!(X ---X--> X) = Graph::from_edge_list_bytes(b"# Comment\n0 1\n\n1\t2  \r\n% Another comment\n2 0\n5 4", true, "").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (5, 4));
+        assert!(g.contains_e(&0, &1, &0).is_some_and(|x| x == EdgeDirection::Directed1to2));
+        assert!(g.contains_e(&4, &5, &0).is_some_and(|x| x == EdgeDirection::Directed2to1));
+        assert!(!g.contains_v(&3));
+        let g: graph!(X ===X=== X) = Graph::from_edge_list_bytes(b"0 1\n1 0\n0 1", false, "").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (2, 3));
+        let g: graph!(X ---X--- X) = Graph::from_edge_list_bytes(b"0 1\n1 0\n0 1", false, "").unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (2, 1));
+        let g: graph!(X ---X--- X) = Graph::from_edge_list_bytes(b"", false, "").unwrap();
+        assert_eq!(g.count_v(), 0);
+        for (input, (line, column)) in [(&b"0 1\n1 2 3"[..], (2, 5)), (b"0 1\n\n1", (3, 2)), (b"0 x", (1, 3)), (b"01", (1, 3)), (b"-1 0", (1, 1))] {
+            match <graph!(X ---X--- X)>::from_edge_list_bytes(input, false, "") {
+                Err(error) => assert!(matches!(error.kind(), ErrorKind::ParseError { line: x, column: Some(y), .. } if (*x, *y) == (line, column))),
+                Ok(_) => panic!(),
+            }
+        }
+        assert!(<graph!(X ---X--- X with VertexIdType = u8)>::from_edge_list_bytes(b"0 256", false, "").is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidArgument { .. })));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn edge_list_file() {
+        let file_name = std::env::temp_dir().join(format!("crabnets_edge_list_{}.txt", std::process::id())).to_string_lossy().into_owned();
+        std::fs::write(&file_name, "0 1\n1 2\n").unwrap();
+        let g: graph!(X ---X--> X) = Graph::from_edge_list_file(&file_name, true).unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (3, 2));
+        std::fs::write(&file_name, "").unwrap();
+        let g: graph!(X ---X--> X) = Graph::from_edge_list_file(&file_name, true).unwrap();
+        assert_eq!(g.count_v(), 0);
+        std::fs::remove_file(&file_name).unwrap();
+        assert!(<graph!(X ---X--> X)>::from_edge_list_file(&file_name, true).is_err_and(|x| matches!(x.kind(), ErrorKind::Io(_))));
+    }
+}
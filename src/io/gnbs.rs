@@ -1,10 +1,10 @@
-use std::{cmp::Ordering, collections::HashSet, fmt::Display, io::{BufRead, BufReader, BufWriter, Read, Write}, str::FromStr};
+use std::{borrow::Cow, cmp::Ordering, collections::HashSet, fmt::Display, io::{BufRead, BufReader, BufWriter, Read, Write}, str::FromStr};
 use itertools::Itertools;
 use regex::Regex;
 use crate::{
-    BasicImmutableGraph, BasicMutableGraph, CrabNetsError, CrabNetsResult, EdgeDirection, Id, StaticDispatchAttributeValue
+    BasicImmutableGraph, BasicMutableGraph, CrabNetsError, CrabNetsResult, EdgeDirection, ErrorKind, Id, StaticDispatchAttributeValue
 };
-use super::{AttributeCollectionIO, AttributeToken, Reader, Writer};
+use super::{AttributeProjection, AttributeCollectionIO, ExportFilter, AttributeToken, Reader, Writer};
 
 
 
@@ -108,7 +108,7 @@ enum Token<'a> {
     Integer(&'a str),
     Float(&'a str),
     Boolean(&'a str),
-    String(&'a str),
+    String(Cow<'a, str>),
     List(Vec<Token<'a>>),
     Collection(Vec<Token<'a>>),
 }
@@ -134,8 +134,17 @@ enum DocumentState {
 
 
 
+fn parse_error<ExpectedType>(function_path: &str, line_number: usize, expected: ExpectedType, found: Option<String>) -> CrabNetsError
+where
+    ExpectedType: Into<String>,
+{
+    CrabNetsError::new(function_path, ErrorKind::ParseError { line: line_number, column: None, expected: expected.into(), found })
+}
+
+
+
 fn identify_atomic_value_type(value: &str, line_number: usize) -> CrabNetsResult<Token> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     let integer_regex = Regex::new(r"^[+-]?(0|[1-9][0-9]*)$").unwrap();
     let float_regex = Regex::new(r"^[+-]?[0-9]*\.?[0-9]+([eE][+-]?[0-9]+)?$").unwrap();
     let token = if value == "X" {
@@ -147,13 +156,13 @@ fn identify_atomic_value_type(value: &str, line_number: usize) -> CrabNetsResult
     } else if value == "T" || value == "F" {
         Token::Boolean(value)
     } else {
-        return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value, found '{}'.", line_number, value)));
+        return Err(parse_error(FUNCTION_PATH, line_number, "value", Some(format!("'{}'", value))));
     };
     Ok(token)
 }
 
 fn extract_declaration_specifier(line: &str, line_number: usize) -> CrabNetsResult<(Token, &str, TokeniserState)> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     let mut split = line.trim_start().splitn(2, char::is_whitespace);
     let target = split.next().unwrap();
     let declaration_specifier = match target {
@@ -163,7 +172,7 @@ fn extract_declaration_specifier(line: &str, line_number: usize) -> CrabNetsResu
         "E" => DeclarationSpecifierName::E,
         "A" => DeclarationSpecifierName::A,
         "#" | "" => DeclarationSpecifierName::Comment,
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected declaration specifier, found '{}'.", line_number, target))),
+        _ => return Err(parse_error(FUNCTION_PATH, line_number, "declaration specifier", Some(format!("'{}'", target)))),
     };
     let next_state = match declaration_specifier {
         DeclarationSpecifierName::AV | DeclarationSpecifierName::AE => TokeniserState::ExpectingTypeName,
@@ -174,7 +183,7 @@ fn extract_declaration_specifier(line: &str, line_number: usize) -> CrabNetsResu
 }
 
 fn extract_type_name(line: &str, line_number: usize) -> CrabNetsResult<(Token, &str, TokeniserState)> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     let mut split = line.trim_start().splitn(2, char::is_whitespace);
     let target = split.next().unwrap();
     let type_name = match target {
@@ -212,18 +221,18 @@ fn extract_type_name(line: &str, line_number: usize) -> CrabNetsResult<(Token, &
         "CU8" => GNBSAttributeType::CU8,
         "CB" => GNBSAttributeType::CB,
         "CS" => GNBSAttributeType::CS,
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected type name, found '{}'.", line_number, target))),
+        _ => return Err(parse_error(FUNCTION_PATH, line_number, "type name", Some(format!("'{}'", target)))),
     };
     Ok((Token::AttributeType(type_name), split.next().unwrap_or(""), TokeniserState::ExpectingAttributeName))
 }
 
 fn extract_attribute_name(line: &str, line_number: usize) -> CrabNetsResult<(Token, &str, TokeniserState)> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     let target = line.trim_start();
     if target == "" {
-        return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected attribute name, found ''.", line_number)));
+        return Err(parse_error(FUNCTION_PATH, line_number, "attribute name", Some("''".to_string())));
     }
-    Ok((Token::String(target), "", TokeniserState::Terminated))
+    Ok((Token::String(Cow::Borrowed(target)), "", TokeniserState::Terminated))
 }
 
 fn split_complex_literal_into_atomics(values: &str) -> Vec<&str> {
@@ -231,8 +240,17 @@ fn split_complex_literal_into_atomics(values: &str) -> Vec<&str> {
     let mut curr_value_start_i = 0;
     let mut curr_value_end_i = 0;
     let mut inside_string = false;
+    let mut escaped = false;
     for symbol in values.chars() {
         match symbol {
+            _ if escaped => {
+                escaped = false;
+                curr_value_end_i += symbol.len_utf8();
+            },
+            '\\' if inside_string => {
+                escaped = true;
+                curr_value_end_i += 1;
+            },
             '"' => {
                 inside_string = !inside_string;
                 curr_value_end_i += 1;
@@ -259,15 +277,45 @@ fn split_complex_literal_into_atomics(values: &str) -> Vec<&str> {
     answer
 }
 
+// Split the contents of a string literal (without the opening quotation mark) at its
+// closing quotation mark, a backslash makes the next character literal
+fn split_string_literal(line: &str) -> Option<(Cow<'_, str>, &str)> {
+    let mut unescaped: Option<String> = None;
+    let mut escaped = false;
+    for (i, symbol) in line.char_indices() {
+        match symbol {
+            _ if escaped => {
+                escaped = false;
+                unescaped.as_mut().unwrap().push(symbol);
+            },
+            '\\' => {
+                escaped = true;
+                unescaped.get_or_insert_with(|| line[..i].to_string());
+            },
+            '"' => {
+                let value = match unescaped {
+                    Some(value) => Cow::Owned(value),
+                    None => Cow::Borrowed(&line[..i]),
+                };
+                return Some((value, &line[i + 1..]));
+            },
+            _ => if let Some(value) = unescaped.as_mut() {
+                value.push(symbol);
+            },
+        }
+    }
+    None
+}
+
 fn extract_value(mut line: &str, line_number: usize) -> CrabNetsResult<(Token, &str, TokeniserState)> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     line = line.trim_start();
     let token: Token;
     let remainder = match line.chars().next() {
         Some('"') => {
-            let split = match line.get(1..).unwrap().split_once('"') {
+            let split = match split_string_literal(line.get(1..).unwrap()) {
                 Some(value) => value,
-                None => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Closing quotation mark (\") wasn't found for a string value.", line_number))),
+                None => return Err(parse_error(FUNCTION_PATH, line_number, "closing quotation mark (\") of a string value", None)),
             };
             token = Token::String(split.0);
             split.1.trim_start()
@@ -275,7 +323,7 @@ fn extract_value(mut line: &str, line_number: usize) -> CrabNetsResult<(Token, &
         Some('[') => {
             let split = match line.get(1..).unwrap().split_once(']') {
                 Some(value) => value,
-                None => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Closing bracket (]) wasn't found for a list value.", line_number))),
+                None => return Err(parse_error(FUNCTION_PATH, line_number, "closing bracket (]) of a list value", None)),
             };
             let elements = split_complex_literal_into_atomics(split.0)
                 .into_iter()
@@ -287,7 +335,7 @@ fn extract_value(mut line: &str, line_number: usize) -> CrabNetsResult<(Token, &
         Some('{') => {
             let split = match line.get(1..).unwrap().split_once('}') {
                 Some(value) => value,
-                None => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Closing bracket (}}) wasn't found for a collection value.", line_number))),
+                None => return Err(parse_error(FUNCTION_PATH, line_number, "closing bracket (}) of a collection value", None)),
             };
             let elements = split_complex_literal_into_atomics(split.0)
                 .into_iter()
@@ -302,7 +350,7 @@ fn extract_value(mut line: &str, line_number: usize) -> CrabNetsResult<(Token, &
             token = identify_atomic_value_type(target, line_number)?;
             split.next().unwrap_or("").trim_start()
         },
-        None => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value, found '{}'.", line_number, line))),
+        None => return Err(parse_error(FUNCTION_PATH, line_number, "value", Some(format!("'{}'", line)))),
     };
     let next_state = if remainder == "" {
         TokeniserState::Terminated
@@ -334,11 +382,11 @@ fn parse_numeric_value<IntoType>(original_value: &str, value_type: &GNBSAttribut
 where
     IntoType: FromStr,
 {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     match original_value.parse::<IntoType>()
     {
         Ok(value) => Ok(value),
-        Err(_) => Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value of type {}, found '{}'.", line_number, value_type, original_value))) 
+        Err(_) => Err(parse_error(FUNCTION_PATH, line_number, format!("value of type {}", value_type), Some(format!("'{}'", original_value)))) 
     }
 }
 
@@ -347,7 +395,7 @@ macro_rules! convert_token_to_static_dispatch_attribute_value {
     (atomic value: $function_path: ident, $line_number: ident, $value: ident, $given_gnbs_value_type: ident, $($origin_gnbs_value_type: ident --> $target_static_dispatch_attribute_value_variant: ident),+) => {
         match $given_gnbs_value_type {
             $(GNBSAttributeType::$origin_gnbs_value_type => Ok(Some(StaticDispatchAttributeValue::$target_static_dispatch_attribute_value_variant(parse_numeric_value($value, &$given_gnbs_value_type, $line_number)?))),)+
-            _ => Err(CrabNetsError::new($function_path, format!("Line {}. Expected value of type {}, found '{}'.", $line_number, $given_gnbs_value_type, $value))),
+            _ => Err(parse_error($function_path, $line_number, format!("value of type {}", $given_gnbs_value_type), Some(format!("'{}'", $value)))),
         }
     };
 
@@ -359,18 +407,18 @@ macro_rules! convert_token_to_static_dispatch_attribute_value {
                 for atomic_value_token in $value.iter() {
                     core_container.$target_core_container_insert_function(match parse_value(atomic_value_token.clone(), GNBSAttributeType::$atomic_gnbs_value_type, $line_number) {
                         Ok(Some(StaticDispatchAttributeValue::$atomic_static_dispatch_attribute_value_variant(atomic_value))) => atomic_value,
-                        _ => return Err(CrabNetsError::new($function_path, format!("Line {}. Expected value of type {}.", $line_number, $given_gnbs_value_type))),
+                        _ => return Err(parse_error($function_path, $line_number, format!("value of type {}", $given_gnbs_value_type), None)),
                     });
                 }
                 Ok(Some(StaticDispatchAttributeValue::$target_static_dispatch_attribute_value_variant(core_container)))
             },)+
-            _ => Err(CrabNetsError::new($function_path, format!("Line {}. Expected value of type {}.", $line_number, $given_gnbs_value_type))),
+            _ => Err(parse_error($function_path, $line_number, format!("value of type {}", $given_gnbs_value_type), None)),
         }
     };
 }
 
 fn parse_value(token: Token, gnbs_value_type: GNBSAttributeType, line_number: usize) -> CrabNetsResult<Option<StaticDispatchAttributeValue>> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     match token {
         Token::Integer(value) => convert_token_to_static_dispatch_attribute_value!(
             atomic value:
@@ -386,13 +434,13 @@ fn parse_value(token: Token, gnbs_value_type: GNBSAttributeType, line_number: us
             GNBSAttributeType::B => Ok(Some(StaticDispatchAttributeValue::Bool(match value {
                 "T" => true,
                 "F" => false,
-                _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value of type B, found '{}'.", line_number, value))),
+                _ => return Err(parse_error(FUNCTION_PATH, line_number, "value of type B", Some(format!("'{}'", value)))),
             }))),
-            _ => Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value of type {}, found '{}'.", line_number, gnbs_value_type, value))),
+            _ => Err(parse_error(FUNCTION_PATH, line_number, format!("value of type {}", gnbs_value_type), Some(format!("'{}'", value)))),
         },
         Token::String(value) => match gnbs_value_type {
-            GNBSAttributeType::S => Ok(Some(StaticDispatchAttributeValue::Str(value.to_string()))),
-            _ => Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value of type {}, found '{}'.", line_number, gnbs_value_type, value))),
+            GNBSAttributeType::S => Ok(Some(StaticDispatchAttributeValue::Str(value.into_owned()))),
+            _ => Err(parse_error(FUNCTION_PATH, line_number, format!("value of type {}", gnbs_value_type), Some(format!("'{}'", value)))),
         },
         Token::List(value) => convert_token_to_static_dispatch_attribute_value!(
             complex value (Vec, push):
@@ -410,81 +458,85 @@ fn parse_value(token: Token, gnbs_value_type: GNBSAttributeType, line_number: us
             CB --[B --[bool]--> Bool]--> SetBool, CS --[S --[String]--> Str]--> SetStr
         ),
         Token::Empty => Ok(None),
-        _ => Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected value.", line_number))),
+        _ => Err(parse_error(FUNCTION_PATH, line_number, "value", None)),
     }
 }
 
 fn parse_attribute_declaration(tokens: Vec<Token>, line_number: usize) -> CrabNetsResult<AttributeMetadata> {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     if tokens.len() != 3 {
-        return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected attribute declaration in the form 'AV <type> <name>' or 'AE <type> <name>', found statement with {} token(s).", line_number, tokens.len())));
+        return Err(parse_error(FUNCTION_PATH, line_number, "attribute declaration in the form 'AV <type> <name>' or 'AE <type> <name>'", Some(format!("statement with {} token(s)", tokens.len()))));
     }
     let type_name = match &tokens[1] {
         Token::AttributeType(value) => value.clone(),
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected type in the attribute declaration.", line_number))),
+        _ => return Err(parse_error(FUNCTION_PATH, line_number, "type in the attribute declaration", None)),
     };
-    let name = match tokens[2] {
+    let name = match &tokens[2] {
         Token::String(value) => value.to_string(),
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected name in the attribute declaration.", line_number))),
+        _ => return Err(parse_error(FUNCTION_PATH, line_number, "name in the attribute declaration", None)),
     };
     Ok(AttributeMetadata { name, gnbs_type: type_name })
 }
 
-fn parse_vertex_declaration<'a, VertexIdType>(tokens: Vec<Token<'a>>, attributes: &'a Vec<AttributeMetadata>, line_number: usize) -> CrabNetsResult<VertexMetadata<'a, VertexIdType>>
+fn parse_vertex_id<VertexIdType>(token: &Token, line_number: usize) -> CrabNetsResult<VertexIdType>
+where
+    VertexIdType: FromStr + Id,
+{
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
+    let value: &str = match token {
+        Token::Integer(value) => value,
+        Token::String(value) => value,
+        _ => return Err(parse_error(FUNCTION_PATH, line_number, "vertex ID", Some("token of neither integral nor string type".to_string()))),
+    };
+    match value.parse() {
+        Ok(parsed_value) => Ok(parsed_value),
+        Err(_) => Err(parse_error(FUNCTION_PATH, line_number, "vertex ID", Some(format!("'{}'", value)))),
+    }
+}
+
+fn parse_vertex_declaration<'a, VertexIdType>(tokens: Vec<Token<'a>>, attributes: &'a Vec<AttributeMetadata>, projection: &AttributeProjection<'_>, line_number: usize) -> CrabNetsResult<VertexMetadata<'a, VertexIdType>>
 where
     VertexIdType: FromStr + Id,
 {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     if tokens.len() != attributes.len() + 2 {
-        return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex declaration in the form 'V <id> <attribute values>' with {} token(s) in <attribute values>, found statement with {} token(s) in total.", line_number, attributes.len(), tokens.len())));
+        return Err(parse_error(FUNCTION_PATH, line_number, format!("vertex declaration in the form 'V <id> <attribute values>' with {} token(s) in <attribute values>", attributes.len()), Some(format!("statement with {} token(s) in total", tokens.len()))));
     }
-    let id: VertexIdType = match tokens[1] {
-        Token::Integer(value) => match value.parse() {
-            Ok(parsed_value) => parsed_value,
-            Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found {}.", line_number, value))),
-        },
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found token of non-integral type.", line_number))),
-    };
+    let id: VertexIdType = parse_vertex_id(&tokens[1], line_number)?;
     let mut attribute_tokens: Vec<AttributeToken> = Vec::with_capacity(attributes.len());
     for attribute_i in 0..attributes.len() {
         let curr_attribute = &attributes[attribute_i];
+        if !projection.keeps(&curr_attribute.name) {
+            continue;
+        }
         let curr_token = tokens[attribute_i + 2].clone();
-        match parse_value(curr_token, curr_attribute.gnbs_type, line_number)? {
-            Some(value) => attribute_tokens.push(AttributeToken { name: &curr_attribute.name, value }),
+        match parse_value(curr_token, curr_attribute.gnbs_type, line_number)?.and_then(|value| projection.project(AttributeToken { name: &curr_attribute.name, value })) {
+            Some(attribute_token) => attribute_tokens.push(attribute_token),
             None => (),
         };
     }
     Ok(VertexMetadata { id, attribute_tokens })
 }
 
-fn parse_edge_declaration<'a, VertexIdType>(tokens: Vec<Token<'a>>, attributes: &'a Vec<AttributeMetadata>, line_number: usize) -> CrabNetsResult<EdgeMetadata<'a, VertexIdType>>
+fn parse_edge_declaration<'a, VertexIdType>(tokens: Vec<Token<'a>>, attributes: &'a Vec<AttributeMetadata>, projection: &AttributeProjection<'_>, line_number: usize) -> CrabNetsResult<EdgeMetadata<'a, VertexIdType>>
 where
     VertexIdType: FromStr + Id,
 {
-    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+    const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
     if tokens.len() != attributes.len() + 3 {
-        return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex declaration in the form 'V <id> <attribute values>' with {} token(s) in <attribute values>, found statement with {} token(s) in total.", line_number, attributes.len(), tokens.len())));
+        return Err(parse_error(FUNCTION_PATH, line_number, format!("edge declaration in the form 'E <id1> <id2> <attribute values>' or 'A <id1> <id2> <attribute values>' with {} token(s) in <attribute values>", attributes.len()), Some(format!("statement with {} token(s) in total", tokens.len()))));
     }
-    let id1: VertexIdType = match tokens[1] {
-        Token::Integer(value) => match value.parse() {
-            Ok(parsed_value) => parsed_value,
-            Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found {}.", line_number, value))),
-        },
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found token of non-integral type.", line_number))),
-    };
-    let id2: VertexIdType = match tokens[2] {
-        Token::Integer(value) => match value.parse() {
-            Ok(parsed_value) => parsed_value,
-            Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found {}.", line_number, value))),
-        },
-        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Expected vertex ID, found token of non-integral type.", line_number))),
-    };
+    let id1: VertexIdType = parse_vertex_id(&tokens[1], line_number)?;
+    let id2: VertexIdType = parse_vertex_id(&tokens[2], line_number)?;
     let mut attribute_tokens: Vec<AttributeToken> = Vec::with_capacity(attributes.len());
     for attribute_i in 0..attributes.len() {
         let curr_attribute = &attributes[attribute_i];
+        if !projection.keeps(&curr_attribute.name) {
+            continue;
+        }
         let curr_token = tokens[attribute_i + 3].clone();
-        match parse_value(curr_token, curr_attribute.gnbs_type, line_number)? {
-            Some(value) => attribute_tokens.push(AttributeToken { name: &curr_attribute.name, value }),
+        match parse_value(curr_token, curr_attribute.gnbs_type, line_number)?.and_then(|value| projection.project(AttributeToken { name: &curr_attribute.name, value })) {
+            Some(attribute_token) => attribute_tokens.push(attribute_token),
             None => (),
         }
     }
@@ -501,7 +553,20 @@ where
 
 
 
-fn collect_vertex_attributes<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(graph: &G) -> CrabNetsResult<Vec<AttributeMetadata>>
+fn format_vertex_id<VertexIdType>(id: &VertexIdType) -> String
+where
+    VertexIdType: Id,
+{
+    let answer = id.to_string();
+    let digits = answer.strip_prefix(['+', '-']).unwrap_or(&answer);
+    if digits == "0" || !digits.is_empty() && !digits.starts_with('0') && digits.bytes().all(|x| x.is_ascii_digit()) {
+        answer
+    } else {
+        format!("\"{}\"", answer.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+fn collect_vertex_attributes<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(graph: &G, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<Vec<AttributeMetadata>>
 where
     G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
     EdgeAttributeCollectionType: AttributeCollectionIO,
@@ -509,13 +574,13 @@ where
     VertexAttributeCollectionType: AttributeCollectionIO,
     VertexIdType: Id,
 {
-    const FUNCTION_PATH: &str = "GNBSWriter::Writer::write_graph";
+    const FUNCTION_PATH: &str = "GNBSWriter::Writer::write_filtered_graph";
     let mut answer = Vec::new();
-    for attribute_collection in graph.iter_v().map(|x| graph.v_attrs(&x).unwrap()) {
+    for attribute_collection in filter.iter_v(graph).map(|x| graph.v_attrs(&x).unwrap()) {
         for attribute_token in attribute_collection.io_iter_contents() {
             match answer.binary_search_by_key(&attribute_token.name, |x: &AttributeMetadata| &x.name) {
                 Ok(value) => if answer[value].gnbs_type != attribute_token.value.into() {
-                    return Err(CrabNetsError::new(FUNCTION_PATH, format!("The type of vertex attribute '{}' differs across different attribute collections.", attribute_token.name)));
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: attribute_token.name.to_string() }));
                 },
                 Err(value) => answer.insert(value, AttributeMetadata { name: attribute_token.name.to_string(), gnbs_type: attribute_token.value.into() }),
             }
@@ -524,7 +589,7 @@ where
     Ok(answer)
 }
 
-fn collect_edge_attributes<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(graph: &G) -> CrabNetsResult<Vec<AttributeMetadata>>
+fn collect_edge_attributes<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(graph: &G, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<Vec<AttributeMetadata>>
 where
     G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
     EdgeAttributeCollectionType: AttributeCollectionIO,
@@ -532,13 +597,13 @@ where
     VertexAttributeCollectionType: AttributeCollectionIO,
     VertexIdType: Id,
 {
-    const FUNCTION_PATH: &str = "GNBSWriter::Writer::write_graph";
+    const FUNCTION_PATH: &str = "GNBSWriter::Writer::write_filtered_graph";
     let mut answer = Vec::new();
-    for attribute_collection in graph.iter_e().map(|x| graph.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap()) {
+    for attribute_collection in filter.iter_e(graph).map(|x| graph.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap()) {
         for attribute_token in attribute_collection.io_iter_contents() {
             match answer.binary_search_by_key(&attribute_token.name, |x: &AttributeMetadata| &x.name) {
                 Ok(value) => if answer[value].gnbs_type != attribute_token.value.into() {
-                    return Err(CrabNetsError::new(FUNCTION_PATH, format!("The type of edge attribute '{}' differs across different attribute collections.", attribute_token.name)));
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: attribute_token.name.to_string() }));
                 },
                 Err(value) => answer.insert(value, AttributeMetadata { name: attribute_token.name.to_string(), gnbs_type: attribute_token.value.into() }),
             }
@@ -561,7 +626,7 @@ pub struct GNBSReader;
 
 // GNBSReader::Reader
 impl<'a> Reader for GNBSReader {
-    fn read_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>) -> CrabNetsResult<G>
+    fn read_projected_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>, projection: &AttributeProjection<'_>) -> CrabNetsResult<G>
     where
         G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
         R: Read,
@@ -570,7 +635,7 @@ impl<'a> Reader for GNBSReader {
         VertexAttributeCollectionType: AttributeCollectionIO,
         VertexIdType: FromStr + Id,
     {
-        const FUNCTION_PATH: &str = "GNBSReader::Reader::read_graph";
+        const FUNCTION_PATH: &str = "GNBSReader::Reader::read_projected_graph";
         let mut new_graph = G::default();
         let mut state = DocumentState::ExpectingVertexAttributeOrEdgeAttributeOrVertex;
         let mut vertex_attributes = Vec::new();
@@ -580,8 +645,8 @@ impl<'a> Reader for GNBSReader {
             line_number += 1;
             let line = match line_result {
                 Ok(value) => value,
-                Err(_) => {
-                    return Err(CrabNetsError::new(FUNCTION_PATH, format!("Couldn't read line {} of the input file.", line_number)));
+                Err(error) => {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Couldn't read line {} of the input file: {}", line_number, error))));
                 },
             };
             let tokens = tokenise_line(line.as_ref(), line_number)?;
@@ -589,44 +654,44 @@ impl<'a> Reader for GNBSReader {
                 match declaration_specifier {
                     DeclarationSpecifierName::AV => match state {
                         DocumentState::ExpectingVertexAttributeOrEdgeAttributeOrVertex => vertex_attributes.push(parse_attribute_declaration(tokens, line_number)?),
-                        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Vertex attribute declaration after a vertex declaration.", line_number))),
+                        _ => return Err(parse_error(FUNCTION_PATH, line_number, "vertex attribute declarations to precede vertex declarations", None)),
                     },
                     DeclarationSpecifierName::AE => match state {
                         DocumentState::ExpectingVertexAttributeOrEdgeAttributeOrVertex | DocumentState::ExpectingVertexOrEdgeAttributeOrEdge => edge_attributes.push(parse_attribute_declaration(tokens, line_number)?),
-                        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Edge attribute declaration after an edge declaration.", line_number))),
+                        _ => return Err(parse_error(FUNCTION_PATH, line_number, "edge attribute declarations to precede edge declarations", None)),
                     },
                     DeclarationSpecifierName::V => match state {
                         DocumentState::ExpectingVertexAttributeOrEdgeAttributeOrVertex | DocumentState::ExpectingVertexOrEdgeAttributeOrEdge => {
                             state = DocumentState::ExpectingVertexOrEdgeAttributeOrEdge;
-                            let vertex_metadata: VertexMetadata<'_, VertexIdType> = parse_vertex_declaration(tokens, &vertex_attributes, line_number)?;
+                            let vertex_metadata: VertexMetadata<'_, VertexIdType> = parse_vertex_declaration(tokens, &vertex_attributes, projection, line_number)?;
                             new_graph.add_v(Some(vertex_metadata.id.clone()));
                             for attribute_token in vertex_metadata.attribute_tokens {
                                 new_graph.v_attrs_mut(&vertex_metadata.id).unwrap().io_reader_callback::<EdgeIdType, VertexIdType>(attribute_token);
                             }
                         },
-                        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Vertex declaration after an edge declaration.", line_number))),
+                        _ => return Err(parse_error(FUNCTION_PATH, line_number, "vertex declarations to precede edge declarations", None)),
                     },
                     DeclarationSpecifierName::A => match state {
                         DocumentState::ExpectingVertexOrEdgeAttributeOrEdge | DocumentState::ExpectingEdge => {
                             state = DocumentState::ExpectingEdge;
-                            let edge_metadata: EdgeMetadata<'_, VertexIdType> = parse_edge_declaration(tokens, &edge_attributes, line_number)?;
+                            let edge_metadata: EdgeMetadata<'_, VertexIdType> = parse_edge_declaration(tokens, &edge_attributes, projection, line_number)?;
                             let edge_id = new_graph.add_e(&edge_metadata.id1, &edge_metadata.id2, true, None)?;
                             for attribute_token in edge_metadata.attribute_tokens {
                                 new_graph.e_attrs_mut(&edge_metadata.id1, &edge_metadata.id2, &edge_id).unwrap().io_reader_callback::<EdgeIdType, VertexIdType>(attribute_token);
                             }
                         },
-                        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Edge declaration before a vertex declaration.", line_number))),
+                        _ => return Err(parse_error(FUNCTION_PATH, line_number, "vertex declarations to precede edge declarations", None)),
                     },
                     DeclarationSpecifierName::E => match state {
                         DocumentState::ExpectingVertexOrEdgeAttributeOrEdge | DocumentState::ExpectingEdge => {
                             state = DocumentState::ExpectingEdge;
-                            let edge_metadata: EdgeMetadata<'_, VertexIdType> = parse_edge_declaration(tokens, &edge_attributes, line_number)?;
+                            let edge_metadata: EdgeMetadata<'_, VertexIdType> = parse_edge_declaration(tokens, &edge_attributes, projection, line_number)?;
                             let edge_id = new_graph.add_e(&edge_metadata.id1, &edge_metadata.id2, false, None)?;
                             for attribute_token in edge_metadata.attribute_tokens {
                                 new_graph.e_attrs_mut(&edge_metadata.id1, &edge_metadata.id2, &edge_id).unwrap().io_reader_callback::<EdgeIdType, VertexIdType>(attribute_token);
                             }
                         },
-                        _ => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Line {}. Edge declaration before a vertex declaration.", line_number))),
+                        _ => return Err(parse_error(FUNCTION_PATH, line_number, "vertex declarations to precede edge declarations", None)),
                     },
                     DeclarationSpecifierName::Comment => (),
                 };
@@ -642,7 +707,7 @@ pub struct GNBSWriter;
 
 // GNBSWriter::Writer
 impl Writer for GNBSWriter {
-    fn write_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    fn write_filtered_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()>
     where
         G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
         W: std::io::prelude::Write,
@@ -659,8 +724,8 @@ b"# GNBS serialisation of a graph
 
 # VERTEX ATTRIBUTES
 ";
-        let vertex_attributes = collect_vertex_attributes(graph)?;
-        let edge_attributes = collect_edge_attributes(graph)?;
+        let vertex_attributes = collect_vertex_attributes(graph, filter)?;
+        let edge_attributes = collect_edge_attributes(graph, filter)?;
         buffer_writer.write(HEADER).unwrap();
         if vertex_attributes.len() == 0 {
             buffer_writer.write(b"# No vertex attributes have been defined for this graph\n\n# VERTICES\n").unwrap();
@@ -670,11 +735,12 @@ b"# GNBS serialisation of a graph
             }
             buffer_writer.write(b"\n# VERTICES\n").unwrap();
         }
-        if graph.count_v() == 0 {
+        let ids = filter.iter_v(graph).sorted().collect_vec();
+        if ids.is_empty() {
             buffer_writer.write(b"# This graph doesn't contain any vertices\n").unwrap();
         }
-        for id in graph.iter_v().sorted() {
-            buffer_writer.write(format!("V {}", id).as_bytes()).unwrap();
+        for id in ids {
+            buffer_writer.write(format!("V {}", format_vertex_id(&id)).as_bytes()).unwrap();
             for attribute in vertex_attributes.iter() {
                 buffer_writer.write(match graph.v_attrs(&id).unwrap().io_query_contents(&attribute.name) {
                     Some(StaticDispatchAttributeValue::Bool(value)) => format!(" {}", if value { "T" } else { "F" }),
@@ -693,11 +759,12 @@ b"# GNBS serialisation of a graph
             }
             buffer_writer.write(b"\n# EDGES\n").unwrap();
         }
-        if graph.count_e() == 0 {
+        let edges = filter.iter_e(graph).sorted_by(|x, y| match x.id1.cmp(&y.id1) { Ordering::Equal => x.id2.cmp(&y.id2), value => value, }).collect_vec();
+        if edges.is_empty() {
             buffer_writer.write(b"# This graph doesn't contain any edges\n").unwrap();
         }
-        for edge in graph.iter_e().sorted_by(|x, y| match x.id1.cmp(&y.id1) { Ordering::Equal => x.id2.cmp(&y.id2), value => value, }) {
-            buffer_writer.write(if edge.direction == EdgeDirection::Undirected { format!("E {} {}", edge.id1, edge.id2) } else { format!("A {} {}", edge.id1, edge.id2) }.as_bytes()).unwrap();
+        for edge in edges {
+            buffer_writer.write(if edge.direction == EdgeDirection::Undirected { format!("E {} {}", format_vertex_id(&edge.id1), format_vertex_id(&edge.id2)) } else { format!("A {} {}", format_vertex_id(&edge.id1), format_vertex_id(&edge.id2)) }.as_bytes()).unwrap();
             for attribute in edge_attributes.iter() {
                 buffer_writer.write(match graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap().io_query_contents(&attribute.name) {
                     Some(StaticDispatchAttributeValue::Bool(value)) => format!(" {}", if value { "T" } else { "F" }),
@@ -718,6 +785,7 @@ b"# GNBS serialisation of a graph
 #[cfg(test)]
 mod tests {
     use std::io::{BufWriter, Cursor};
+    use crate::io::{AttributeProjection, ExportFilter, SupportedFormats, IO};
     use crate::*;
     use super::*;
 
@@ -802,8 +870,8 @@ V 2 X
 # No edge attributes have been defined for this graph
 
 # EDGES
-A 0 1
-E 1 2
+E 0 1
+A 1 2
 ";
         let mut g: graph!(A ---X--> A) = Graph::new();
         g.add_v(None);
@@ -822,4 +890,91 @@ E 1 2
         }
         assert_eq!(&output[0..OUTPUT.len()], OUTPUT);
     }
+
+    #[test]
+    fn string_ids_round_trip() {
+        let mut g: graph!(X ---X--> X with VertexIdType = String) = Graph::new();
+        g.add_v(Some("Romy".to_string()));
+        g.add_v(Some("42".to_string()));
+        g.add_v(Some("a\"b".to_string()));
+        g.add_v(Some("c\\ d".to_string()));
+        g.add_e(&"Romy".to_string(), &"42".to_string(), true, None).unwrap();
+        g.add_e(&"a\"b".to_string(), &"c\\ d".to_string(), false, None).unwrap();
+        let mut output = Vec::new();
+        {
+            let mut buffer_writer = BufWriter::new(&mut output);
+            let gnbs_writer = GNBSWriter;
+            gnbs_writer.write_graph(&g, &mut buffer_writer).unwrap();
+        }
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("V \"Romy\"\n"));
+        assert!(output.contains("A \"Romy\" 42\n"));
+        assert!(output.contains("E \"a\\\"b\" \"c\\\\ d\"\n"));
+        let gnbs_reader = GNBSReader;
+        let g: graph!(X ---X--> X with VertexIdType = String) = gnbs_reader.read_graph(BufReader::new(output.as_bytes())).unwrap();
+        assert_eq!(g.count_v(), 4);
+        assert!(g.contains_e(&"Romy".to_string(), &"42".to_string(), &0) == Some(EdgeDirection::Directed1to2));
+        assert!(g.contains_e(&"a\"b".to_string(), &"c\\ d".to_string(), &0) == Some(EdgeDirection::Undirected));
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let mut g: graph!(A ---X--> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&2).unwrap().insert("Weight".to_string(), Box::new(7i32));
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        let output = g.to_string_as(SupportedFormats::GNBS).unwrap();
+        assert_eq!(output.as_bytes(), g.to_bytes(SupportedFormats::GNBS).unwrap());
+        assert!(output.contains("AV I4 Weight\n") && output.contains("V 2 7\n") && output.contains("A 0 1\n"));
+        let g: graph!(A ---X--> A) = IO::from_string(&output, SupportedFormats::GNBS).unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (3, 2));
+        assert_eq!(g.v_attrs(&2).unwrap().get(&"Weight".to_string()).unwrap().downcast::<i32>(), Some(&7));
+        assert!(<graph!(X ---X--> X)>::from_bytes(b"E 0 1", SupportedFormats::GNBS).is_err());
+    }
+
+    #[test]
+    fn parse_and_to_gnbs_string() {
+        let g: graph!(A ---X--> A) = "AV I4 Weight\n\nV 0 1\nV 1 2\n\nA 0 1\n".parse().unwrap();
+        assert_eq!((g.count_v(), g.count_e()), (2, 1));
+        assert_eq!(g.v_attrs(&1).unwrap().get(&"Weight".to_string()).unwrap().downcast::<i32>(), Some(&2));
+        let output = g.to_gnbs_string().unwrap();
+        let h: graph!(A ---X--> A) = output.parse().unwrap();
+        assert_eq!(h.to_gnbs_string().unwrap(), output);
+        assert!("E 0 1".parse::<graph!(X ---X--> X)>().is_err());
+    }
+
+    #[test]
+    fn filtered_export() {
+        let g: graph!(A ---X--> A) = "AV I4 Weight\n\nV 0 1\nV 1 2\nV 2 3\n\nA 0 1\nA 1 2\nE 2 0\n".parse().unwrap();
+        // Edges are dropped along with their endpoints
+        let filter = ExportFilter::new().with_vertex_predicate(|_, attrs: &DynamicDispatchAttributeMap<String>| attrs.get(&"Weight".to_string()).unwrap().downcast::<i32>() != Some(&3));
+        let h: graph!(A ---X--> A) = IO::from_bytes(&g.to_bytes_filtered(SupportedFormats::GNBS, &filter).unwrap(), SupportedFormats::GNBS).unwrap();
+        assert_eq!((h.count_v(), h.count_e()), (2, 1));
+        let filter = ExportFilter::new().with_edge_predicate(|edge, _| edge.direction == EdgeDirection::Undirected);
+        let h: graph!(A ---X--> A) = IO::from_bytes(&g.to_bytes_filtered(SupportedFormats::Protobuf, &filter).unwrap(), SupportedFormats::Protobuf).unwrap();
+        assert_eq!((h.count_v(), h.count_e()), (3, 1));
+        assert!(h.contains_e(&0, &2, &0).is_some_and(|x| x == EdgeDirection::Undirected));
+        assert_eq!(g.to_bytes_filtered(SupportedFormats::GNBS, &ExportFilter::new()).unwrap(), g.to_bytes(SupportedFormats::GNBS).unwrap());
+    }
+
+    #[test]
+    fn projected_import() {
+        let g: graph!(A ---A--> A) = "AV I4 Weight\nAV S Name\nAE I8 Weight\n\nV 0 1 \"A\"\nV 1 2 X\n\nA 0 1 300\n".parse().unwrap();
+        let projection = AttributeProjection::new().with_blacklist(&["Name"]).with_coercion("Weight", |value| match value {
+            StaticDispatchAttributeValue::Int64(x) => i16::try_from(x).ok().map(StaticDispatchAttributeValue::Int16),
+            value => Some(value),
+        });
+        for format in [SupportedFormats::GNBS, SupportedFormats::Protobuf] {
+            let h: graph!(A ---A--> A) = Graph::from_bytes_projected(&g.to_bytes(format).unwrap(), format, &projection).unwrap();
+            assert!(h.v_attrs(&0).unwrap().get(&"Name".to_string()).is_none());
+            assert_eq!(h.v_attrs(&0).unwrap().get(&"Weight".to_string()).unwrap().downcast::<i32>(), Some(&1));
+            assert_eq!(h.e_attrs(&0, &1, &0).unwrap().get(&"Weight".to_string()).unwrap().downcast::<i16>(), Some(&300));
+        }
+        let h: graph!(A ---A--> A) = Graph::from_bytes_projected(&g.to_bytes(SupportedFormats::GNBS).unwrap(), SupportedFormats::GNBS, &AttributeProjection::new().with_whitelist(&["Name"])).unwrap();
+        assert_eq!(h.v_attrs(&0).unwrap().io_iter_contents().count(), 1);
+        assert_eq!(h.e_attrs(&0, &1, &0).unwrap().io_iter_contents().count(), 0);
+    }
 }
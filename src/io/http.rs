@@ -0,0 +1,479 @@
+//! # HTTP module
+//! 
+//! ## Description
+//! This module allows to load published datasets directly from  their  URLs  without  a
+//! manual download step, see [`Graph::from_url`]. The response  is  parsed  while  it's
+//! being received, so the whole file is never stored in memory or on disk.
+//! 
+//! Both `http://` and `https://` URLs are supported, the latter are  secured  with  TLS
+//! (`rustls` with the root certificates  of  Mozilla  from  `webpki-roots`).  Responses
+//! compressed with `gzip` or `deflate` content encoding are decompressed  on  the  fly,
+//! other encodings are rejected with an error.
+//! 
+//! Up to 5 redirects are followed, both absolute and relative  `Location`  headers  are
+//! accepted. Connecting to the server times out after 30  seconds,  a  single  read  or
+//! write on the established connection times out after 60 seconds.
+use std::{io::{BufRead, BufReader, Read, Write}, net::{TcpStream, ToSocketAddrs}, str::FromStr, sync::{Arc, OnceLock}, time::Duration};
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use rustls::{pki_types::ServerName, ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use crate::{errors::{CrabNetsError, CrabNetsResult, ErrorKind}, Graph, Id, Locale};
+use super::{gnbs::GNBSReader, protobuf::ProtobufReader, AttributeCollectionIO, AttributeProjection, Reader, SupportedFormats};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * HTTP CLIENT                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Maximum number of redirects followed before giving up
+const MAX_REDIRECTS: usize = 5;
+
+// Maximum time spent on establishing the connection to a single address
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Maximum time a single read from or write to the connection may block
+const READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+
+
+// Connection to the server, either plain or secured with TLS
+enum Connection {
+    Plain(TcpStream),
+    Tls(Box<StreamOwned<ClientConnection, TcpStream>>),
+}
+
+// Connection::Read
+impl Read for Connection {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.read(buffer),
+            // Many servers close the connection without TLS `close_notify`, truncated
+            // bodies are still detected by `ResponseBody` when their length is known
+            Connection::Tls(stream) => match stream.read(buffer) {
+                Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => Ok(0),
+                answer => answer,
+            },
+        }
+    }
+}
+
+// Connection::Write
+impl Write for Connection {
+    fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Connection::Plain(stream) => stream.write(buffer),
+            Connection::Tls(stream) => stream.write(buffer),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Connection::Plain(stream) => stream.flush(),
+            Connection::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+
+
+// Body of the response, it's read directly from the connection
+enum ResponseBody {
+    Chunked { stream: BufReader<Connection>, remaining: usize, finished: bool },
+    Sized { stream: BufReader<Connection>, remaining: usize },
+    UntilClosed { stream: BufReader<Connection> },
+}
+
+// ResponseBody::Read
+impl Read for ResponseBody {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            ResponseBody::Chunked { stream, remaining, finished } => {
+                if *finished {
+                    return Ok(0);
+                }
+                if *remaining == 0 {
+                    let mut line = String::new();
+                    stream.read_line(&mut line)?;
+                    let size = line.split(';').next().unwrap_or("").trim();
+                    *remaining = usize::from_str_radix(size, 16).map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid chunk size '{}'", size)))?;
+                    if *remaining == 0 {
+                        *finished = true;
+                        return Ok(0);
+                    }
+                }
+                let answer = stream.take(*remaining as u64).read(buffer)?;
+                if answer == 0 && !buffer.is_empty() {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                *remaining -= answer;
+                if *remaining == 0 {
+                    let mut line = String::new();
+                    stream.read_line(&mut line)?;
+                }
+                Ok(answer)
+            },
+            ResponseBody::Sized { stream, remaining } => {
+                let answer = stream.take(*remaining as u64).read(buffer)?;
+                if answer == 0 && *remaining != 0 && !buffer.is_empty() {
+                    return Err(std::io::ErrorKind::UnexpectedEof.into());
+                }
+                *remaining -= answer;
+                Ok(answer)
+            },
+            ResponseBody::UntilClosed { stream } => stream.read(buffer),
+        }
+    }
+}
+
+
+
+// Body of the response with its content encoding removed while it's being read
+enum DecodedBody {
+    Deflate(ZlibDecoder<ResponseBody>),
+    Gzip(MultiGzDecoder<ResponseBody>),
+    Identity(ResponseBody),
+}
+
+// DecodedBody::Read
+impl Read for DecodedBody {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            DecodedBody::Deflate(body) => body.read(buffer),
+            DecodedBody::Gzip(body) => body.read(buffer),
+            DecodedBody::Identity(body) => body.read(buffer),
+        }
+    }
+}
+
+
+
+// Split the URL into the flag of TLS usage, the host with the port and the path
+fn split_url(url: &str) -> std::io::Result<(bool, &str, &str)> {
+    let (secure, rest) = match url.split_once("://") {
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("http") => (false, rest),
+        Some((scheme, rest)) if scheme.eq_ignore_ascii_case("https") => (true, rest),
+        _ => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "only 'http://' and 'https://' URLs are supported")),
+    };
+    Ok(match rest.find('/') {
+        Some(position) => (secure, &rest[..position], &rest[position..]),
+        None => (secure, rest, "/"),
+    })
+}
+
+
+
+// Split the host into the name and the port if the latter is given
+fn split_host(host: &str) -> (&str, Option<&str>) {
+    let boundary = host.rfind(']').unwrap_or(0);
+    match host[boundary..].rfind(':') {
+        Some(position) => (&host[..boundary + position], Some(&host[boundary + position + 1..])),
+        None => (host, None),
+    }
+}
+
+
+
+// Resolve the value of `Location` header against the URL of the request
+fn resolve_location(url: &str, location: &str) -> String {
+    if location.find("://").is_some_and(|position| !location[..position].contains(['/', '?', '#'])) {
+        return location.to_string();
+    }
+    let (scheme, rest) = url.split_once("://").unwrap_or(("http", url));
+    if location.starts_with("//") {
+        return format!("{}:{}", scheme, location);
+    }
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let path = path.split('#').next().unwrap_or("");
+    let query_start = path.find('?').unwrap_or(path.len());
+    match location.chars().next() {
+        Some('/') => format!("{}://{}{}", scheme, authority, location),
+        Some('?') => format!("{}://{}{}{}", scheme, authority, &path[..query_start], location),
+        None | Some('#') => format!("{}://{}{}", scheme, authority, path),
+        Some(_) => {
+            let directory = path[..query_start].rfind('/').map_or("/", |position| &path[..=position]);
+            format!("{}://{}{}{}", scheme, authority, directory, location)
+        },
+    }
+}
+
+
+
+// Open the connection to the host, TLS handshake is performed for HTTPS
+fn connect(host: &str, secure: bool) -> std::io::Result<Connection> {
+    static TLS_CONFIG: OnceLock<Result<Arc<ClientConfig>, String>> = OnceLock::new();
+    let (name, port) = split_host(host);
+    let port = match port {
+        Some(value) => value.parse::<u16>().map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("invalid port '{}'", value)))?,
+        None if secure => 443,
+        None => 80,
+    };
+    let mut last_error = std::io::Error::new(std::io::ErrorKind::NotFound, format!("no addresses found for '{}'", name));
+    let mut stream = None;
+    for address in (name.trim_start_matches('[').trim_end_matches(']'), port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+            Ok(value) => {
+                stream = Some(value);
+                break;
+            },
+            Err(error) => last_error = error,
+        }
+    }
+    let stream = stream.ok_or(last_error)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    stream.set_write_timeout(Some(READ_TIMEOUT))?;
+    if !secure {
+        return Ok(Connection::Plain(stream));
+    }
+    let config = TLS_CONFIG.get_or_init(|| {
+        let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map(|x| Arc::new(x.with_root_certificates(roots).with_no_client_auth()))
+            .map_err(|error| error.to_string())
+    });
+    let config = config.clone().map_err(std::io::Error::other)?;
+    let server_name = ServerName::try_from(name.trim_start_matches('[').trim_end_matches(']').to_string()).map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidInput, error))?;
+    let connection = ClientConnection::new(config, server_name).map_err(std::io::Error::other)?;
+    Ok(Connection::Tls(Box::new(StreamOwned::new(connection, stream))))
+}
+
+
+
+// Send GET request and read the headers of the response, redirects are followed
+fn get(url: &str, function_path: &str) -> CrabNetsResult<DecodedBody> {
+    let mut url = url.to_string();
+    let io_error = |url: &str, error: std::io::Error| CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to load '{}': {}", url, error)));
+    for _ in 0..=MAX_REDIRECTS {
+        let (secure, host, path) = split_url(&url).map_err(|error| io_error(&url, error))?;
+        let mut stream = connect(host, secure).map_err(|error| io_error(&url, error))?;
+        write!(stream, "GET {} HTTP/1.1\r\nHost: {}\r\nAccept-Encoding: gzip, deflate\r\nConnection: close\r\nUser-Agent: crabnets\r\n\r\n", path, host)
+            .and_then(|_| stream.flush())
+            .map_err(|error| io_error(&url, error))?;
+        let mut stream = BufReader::new(stream);
+        let mut status_line = String::new();
+        stream.read_line(&mut status_line).map_err(|error| io_error(&url, error))?;
+        let status = match status_line.split_whitespace().nth(1).and_then(|x| x.parse::<u16>().ok()) {
+            Some(value) => value,
+            None => return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line: 1, column: None, expected: "HTTP status line".to_string(), found: Some(status_line.trim().to_string()) })),
+        };
+        let (mut content_length, mut chunked, mut encoding, mut location) = (None, false, None, None);
+        let mut line_number = 1;
+        loop {
+            let mut line = String::new();
+            stream.read_line(&mut line).map_err(|error| io_error(&url, error))?;
+            line_number += 1;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = match line.split_once(':') {
+                Some((name, value)) => (name.trim().to_ascii_lowercase(), value.trim()),
+                None => continue,
+            };
+            match name.as_str() {
+                "content-encoding" => encoding = match value.to_ascii_lowercase().as_str() {
+                    "deflate" => Some(true),
+                    "gzip" | "x-gzip" => Some(false),
+                    "identity" => None,
+                    _ => return Err(CrabNetsError::new(function_path, ErrorKind::ParseError { line: line_number, column: Some(name.len() + 2), expected: "'gzip', 'deflate' or 'identity' content encoding".to_string(), found: Some(value.to_string()) })),
+                },
+                "content-length" => content_length = value.parse::<usize>().ok(),
+                "location" => location = Some(value.to_string()),
+                "transfer-encoding" => chunked = value.to_ascii_lowercase().contains("chunked"),
+                _ => (),
+            }
+        }
+        match (status, location) {
+            (200..=299, _) => {
+                let body = match (chunked, content_length) {
+                    (true, _) => ResponseBody::Chunked { stream, remaining: 0, finished: false },
+                    (false, Some(remaining)) => ResponseBody::Sized { stream, remaining },
+                    (false, None) => ResponseBody::UntilClosed { stream },
+                };
+                return Ok(match encoding {
+                    Some(true) => DecodedBody::Deflate(ZlibDecoder::new(body)),
+                    Some(false) => DecodedBody::Gzip(MultiGzDecoder::new(body)),
+                    None => DecodedBody::Identity(body),
+                });
+            },
+            (301 | 302 | 303 | 307 | 308, Some(location)) => url = resolve_location(&url, &location),
+            _ => {
+                let kind = match status {
+                    401 | 403 => std::io::ErrorKind::PermissionDenied,
+                    404 | 410 => std::io::ErrorKind::NotFound,
+                    _ => std::io::ErrorKind::Other,
+                };
+                return Err(io_error(&url, std::io::Error::new(kind, format!("the server responded with status {}", status))));
+            },
+        }
+    }
+    Err(io_error(&url, std::io::Error::other("too many redirects")))
+}
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * LOADING                                                                           *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: FromStr + Id,
+{
+    /// # Read graph from URL
+    /// 
+    /// ## Description
+    /// Download the graph from the given `http://` or `https://` URL and parse it while
+    /// it's being downloaded. The format of the graph is deduced from the extension  of
+    /// the path in the URL as in [`IO::from_file`][from_file].
+    /// 
+    /// ## Arguments
+    /// * `url` : `&str` - the URL of the serialised graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Redirects  are  followed,  `gzip`  and  `deflate`   compressed   responses   are
+    /// decompressed on the fly. Both connecting and reading are  bounded  by  timeouts,
+    /// see the [module documentation][http] for details.
+    /// 
+    /// [from_file]: super::IO::from_file
+    /// [http]: super::http
+    pub fn from_url(url: &str) -> CrabNetsResult<Self> {
+        Self::from_url_projected(url, &AttributeProjection::new())
+    }
+
+    /// # Read graph with projected attributes from URL
+    /// 
+    /// ## Description
+    /// Download the graph from the given `http://` or `https://` URL loading  only  the
+    /// attributes selected by the projection, see [`Graph::from_url`].
+    /// 
+    /// ## Arguments
+    /// * `url` : `&str` - the URL of the serialised graph.
+    /// * `projection` : `&AttributeProjection` - the attributes to load.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn from_url_projected(url: &str, projection: &AttributeProjection<'_>) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::from_url_projected";
+        let path = url.split(['?', '#']).next().unwrap_or(url);
+        let format = match SupportedFormats::from_file_name(path) {
+            Some(value) => value,
+            None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedFormat { file_name: url.to_string() })),
+        };
+        let buffer_reader = BufReader::new(get(url, FUNCTION_PATH)?);
+        match format {
+            SupportedFormats::GNBS => GNBSReader.read_projected_graph(buffer_reader, projection),
+            SupportedFormats::Protobuf => ProtobufReader.read_projected_graph(buffer_reader, projection),
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, thread};
+    use flate2::{write::{GzEncoder, ZlibEncoder}, Compression};
+    use crate::*;
+    use crate::io::IO;
+    use super::*;
+
+    // Serve the given responses to the consecutive connections
+    fn serve(responses: Vec<Vec<u8>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = BufReader::new(stream.try_clone().unwrap());
+                let mut line = String::new();
+                while request.read_line(&mut line).unwrap() > 2 {
+                    line.clear();
+                }
+                stream.write_all(&response).unwrap();
+            }
+        });
+        address
+    }
+
+    #[test]
+    fn from_url() {
+        let g: graph!(A ---X--> A) = "AV I4 Weight\n\nV 0 1\nV 1 2\n\nA 0 1\nE 1 1\n".parse().unwrap();
+        let bytes = g.to_bytes(SupportedFormats::Protobuf).unwrap();
+        // Chunked body behind a redirect
+        let mut chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_vec();
+        for chunk in bytes.chunks(7) {
+            chunked.extend_from_slice(format!("{:x}\r\n", chunk.len()).as_bytes());
+            chunked.extend_from_slice(chunk);
+            chunked.extend_from_slice(b"\r\n");
+        }
+        chunked.extend_from_slice(b"0\r\n\r\n");
+        let address = serve(vec![b"HTTP/1.1 301 Moved Permanently\r\nLocation: /graph.pb?v=2\r\nContent-Length: 0\r\n\r\n".to_vec(), chunked]);
+        let h: graph!(A ---X--> A) = Graph::from_url(&format!("http://{}/old.pb", address)).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::Protobuf).unwrap(), bytes);
+        // Sized body
+        let gnbs = g.to_bytes(SupportedFormats::GNBS).unwrap();
+        let mut sized = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n", gnbs.len()).into_bytes();
+        sized.extend_from_slice(&gnbs);
+        let address = serve(vec![sized, b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_vec()]);
+        let h: graph!(A ---X--> A) = Graph::from_url(&format!("http://{}/graph.gnbs", address)).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::GNBS).unwrap(), gnbs);
+        assert!(<graph!(A ---X--> A)>::from_url(&format!("http://{}/graph.gnbs", address)).is_err_and(|x| matches!(x.kind(), ErrorKind::Io(error) if error.kind() == std::io::ErrorKind::NotFound)));
+        // Compressed bodies behind a relative redirect
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&gnbs).unwrap();
+        let gzip = encoder.finish().unwrap();
+        let mut gzipped = format!("HTTP/1.1 200 OK\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\n\r\n", gzip.len()).into_bytes();
+        gzipped.extend_from_slice(&gzip);
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&bytes).unwrap();
+        let mut deflated = b"HTTP/1.1 200 OK\r\nContent-Encoding: deflate\r\n\r\n".to_vec();
+        deflated.extend_from_slice(&encoder.finish().unwrap());
+        let address = serve(vec![b"HTTP/1.1 302 Found\r\nLocation: new/graph.gnbs\r\nContent-Length: 0\r\n\r\n".to_vec(), gzipped, deflated]);
+        let h: graph!(A ---X--> A) = Graph::from_url(&format!("http://{}/data/graph.gnbs", address)).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::GNBS).unwrap(), gnbs);
+        let h: graph!(A ---X--> A) = Graph::from_url(&format!("http://{}/graph.pb", address)).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::Protobuf).unwrap(), bytes);
+        // Malformed responses
+        let address = serve(vec![b"HTTP/1.1 200 OK\r\nContent-Encoding: br\r\n\r\n".to_vec(), b"garbage\r\n\r\n".to_vec()]);
+        assert!(<graph!(A ---X--> A)>::from_url(&format!("http://{}/graph.gnbs", address)).is_err_and(|x| matches!(x.kind(), ErrorKind::ParseError { line: 2, .. })));
+        assert!(<graph!(A ---X--> A)>::from_url(&format!("http://{}/graph.gnbs", address)).is_err_and(|x| matches!(x.kind(), ErrorKind::ParseError { line: 1, .. })));
+        // Unsupported URLs
+        assert!(<graph!(A ---X--> A)>::from_url("ftp://example.com/graph.gnbs").is_err_and(|x| matches!(x.kind(), ErrorKind::Io(_))));
+        assert!(<graph!(A ---X--> A)>::from_url("http://example.com/graph.txt").is_err_and(|x| matches!(x.kind(), ErrorKind::UnsupportedFormat { .. })));
+    }
+    #[test]
+    fn resolve_location() {
+        let url = "https://example.com:8443/data/old.pb?v=1#top";
+        assert_eq!(super::resolve_location(url, "http://mirror.org/graph.pb"), "http://mirror.org/graph.pb");
+        assert_eq!(super::resolve_location(url, "//mirror.org/graph.pb"), "https://mirror.org/graph.pb");
+        assert_eq!(super::resolve_location(url, "/graph.pb"), "https://example.com:8443/graph.pb");
+        assert_eq!(super::resolve_location(url, "new/graph.pb?v=2"), "https://example.com:8443/data/new/graph.pb?v=2");
+        assert_eq!(super::resolve_location(url, "?v=2"), "https://example.com:8443/data/old.pb?v=2");
+        assert_eq!(super::resolve_location("http://example.com", "graph.pb"), "http://example.com/graph.pb");
+        assert_eq!(split_host("[::1]:8080"), ("[::1]", Some("8080")));
+        assert_eq!(split_host("[::1]"), ("[::1]", None));
+        assert_eq!(split_host("example.com"), ("example.com", None));
+    }
+}
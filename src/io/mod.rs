@@ -1,29 +1,112 @@
 //! # Module to handle input/output of graphs
 //! 
 //! ## Description
-//! This module contains items that allow users to read/write graphs from/to files.  The
-//! main component of the module that most users will most often interact  with  is  the
-//! [`IO`] trait that defines 2 functions: [`IO::from_file`] and [`IO::into_file`]. This
-//! trait   is   implemented   for   [`Graph`],   [`ImmutableGraphContainer`][igc]   and
-//! [`MutableGraphContainer`][mgc].
+//! This module contains items that allow users  to  read/write  graphs  from/to  files,
+//! strings and byte slices. The main component of the module that most users will  most
+//! often  interact  with  is   the   [`IO`]   trait   that   defines   functions   like
+//! [`IO::from_file`] and [`IO::into_file`]. This trait is  implemented  for  [`Graph`],
+//! [`ImmutableGraphContainer`][igc] and [`MutableGraphContainer`][mgc].
+//! 
+//! The functions that work with files are only available with `std` feature. The  other
+//! ones  don't  need  a  file  system,  so  they  can   be   used   on   targets   like
+//! `wasm32-unknown-unknown`, e.g. in browser-based tools.
+//! 
+//! Graphs can also be parsed from GNBS strings with `str::parse` (via [`FromStr`])  and
+//! written into them with [`Graph::to_gnbs_string`], which is handy  for  configuration
+//! files, test literals and logs.
+//! 
+//! Parts of huge graphs can be written without making a copy  of  the  subgraph  first:
+//! writers accept an [`ExportFilter`] with predicates on vertices and edges, which  can
+//! also be passed to [`Graph::to_bytes_filtered`].
+//! 
+//! Wide datasets can be read partially: readers accept an [`AttributeProjection`]  that
+//! tells which attributes to load and how to convert  their  values,  it  can  also  be
+//! passed to [`Graph::from_bytes_projected`].
+//! 
+//! Graphs too big for a single file can be split into several files with  the  help  of
+//! the `shards` module (available with `std` feature).
+//! 
+//! Graphs that change often can be persisted incrementally as append-only logs of their
+//! mutations, see the [`mutation_log`] module and `Graph::replay`.
+//! 
+//! Differences between graphs can be  computed,  stored  and  applied  on  the  fly  to
+//! replicate graphs between processes, see the [`patch`] module.
+//! 
+//! Published datasets can be loaded directly from their  URLs  with  the  help  of  the
+//! `http` module (available with `net` feature).
 //! 
 //! ## Supported formats
 //! Graph file formats currently supported are:
 //! * GNBS
+//! * Protobuf (binary)
 //! 
 //! Graph file formats support of which may appear in the future releases:
 //! * GEXF
 //! * GR
 //! 
+//! Laid-out graphs can also be rendered into SVG images with  [`SVGWriter`][svg],  this
+//! format is write-only and isn't handled by [`IO`].
+//! 
+//! Graphs can also be exported into CSV tables for graph and relational databases  with
+//! [`CSVWriter`][csv], this export is write-only as well.
+//! 
+//! The same tables of vertices and edges can be exchanged with dataframe  libraries  as
+//! Apache Arrow record batches, and stored in Parquet  files,  with  the  help  of  the
+//! `arrow` module (available with `arrow` and `parquet` features respectively).
+//! 
+//! Topology-only datasets stored as plain edge lists can be read much faster  with  the
+//! loader from  the  [`edge_list`]  module,  which  bypasses  the  attribute  machinery
+//! altogether.
+//! 
+//! Plain adjacency lists, one line per vertex as in SNAP datasets, are read and written
+//! by the [`adjacency_list`] module.
+//! 
+//! Square CSV tables made in spreadsheet editors can be treated as  adjacency  matrices
+//! with the help of the [`adjacency_matrix`] module.
+//! 
+//! Data-quality issues of input files, such as duplicate vertices and edges, self-loops
+//! and  dangling  edges,  can   be   collected   by   reading   the   files   into   an
+//! [`AuditedGraph`][audit] from the [`audit`] module.
+//! 
+//! Human-readable analytical reports of graphs, e.g.  dataset  cards,  are  written  in
+//! Markdown or plain text by [`ReportWriter`][report].
+//! 
 //! [igc]: crate::ImmutableGraphContainer
 //! [mgc]: crate::MutableGraphContainer
+//! [csv]: csv::CSVWriter
+//! [svg]: svg::SVGWriter
+//! [audit]: audit::AuditedGraph
+//! [report]: report::ReportWriter
+pub mod adjacency_list;
+pub mod adjacency_matrix;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod audit;
+pub mod csv;
+pub mod edge_list;
 pub mod gnbs;
-
-use std::{fs::File, hash::Hash, io::{BufReader, BufWriter, Read, Write}, iter::empty, str::FromStr};
+#[cfg(feature = "net")]
+pub mod http;
+pub mod mutation_log;
+pub mod patch;
+pub mod protobuf;
+pub mod report;
+#[cfg(feature = "std")]
+pub mod shards;
+pub mod svg;
+
+use std::{collections::{HashMap, HashSet}, hash::Hash, io::{BufReader, BufWriter, Read, Write}, iter::empty, str::FromStr};
+#[cfg(feature = "std")]
+use std::fs::File;
+#[cfg(all(feature = "mmap", unix))]
+use std::{os::fd::AsRawFd, ptr::null_mut, slice::from_raw_parts};
 use crate::{
-    attributes::{AttributeCollection, DynamicDispatchAttributeValue, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult}, io::gnbs::GNBSWriter, BasicImmutableGraph, BasicMutableGraph, DynamicDispatchAttributeMap, Graph, Id, Locale
+    attributes::{AttributeCollection, DynamicDispatchAttributeValue, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult}, io::gnbs::GNBSWriter, BasicImmutableGraph, BasicMutableGraph, DynamicDispatchAttributeMap, EdgeIteratorItem, Graph, Id, Locale
 };
+#[cfg(feature = "std")]
+use crate::errors::ErrorKind;
 use gnbs::GNBSReader;
+use protobuf::{ProtobufReader, ProtobufWriter};
 
 
 
@@ -122,6 +205,288 @@ where
 
 
 
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ATTRIBUTE PROJECTION                                                              *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+type Coercion<'a> = Box<dyn Fn(StaticDispatchAttributeValue) -> Option<StaticDispatchAttributeValue> + 'a>;
+
+
+
+/// # Attribute projection
+/// 
+/// ## Description
+/// Selects the attributes that a [`Reader`] should load and converts  their  values  on
+/// the fly. Attributes that aren't selected are skipped without parsing  their  values,
+/// so reading only a few columns of a wide dataset  takes  neither  the  time  nor  the
+/// memory for the rest of them.
+/// 
+/// An attribute is loaded if it's in the whitelist (or there is no whitelist) and isn't
+/// in the blacklist. The same projection applies to both vertex and edge attributes.
+pub struct AttributeProjection<'a> {
+    blacklist: HashSet<String>,
+    coercions: HashMap<String, Coercion<'a>>,
+    whitelist: Option<HashSet<String>>,
+}
+
+// AttributeProjection::AttributeProjection
+impl<'a> AttributeProjection<'a> {
+    /// # Create attribute projection
+    /// 
+    /// ## Description
+    /// Create a projection that loads all attributes as they are.
+    /// 
+    /// ## Returns
+    /// * `AttributeProjection` - the projection.
+    pub fn new() -> Self {
+        AttributeProjection { blacklist: HashSet::new(), coercions: HashMap::new(), whitelist: None }
+    }
+
+    /// # Check attribute
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `attribute_name` : `&str` - the name of the attribute.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if the attribute should be loaded, `false` otherwise.
+    pub fn keeps(&self, attribute_name: &str) -> bool {
+        self.whitelist.as_ref().is_none_or(|x| x.contains(attribute_name)) && !self.blacklist.contains(attribute_name)
+    }
+
+    /// # Project attribute
+    /// 
+    /// ## Description
+    /// Apply the projection to the read attribute.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `token` : `AttributeToken` - the read attribute.
+    /// 
+    /// ## Returns
+    /// * `Option<AttributeToken>` - `Some(value)` is returned if the  attribute  should
+    /// be loaded, `value` in this case is the attribute with its value coerced;  `None`
+    /// is returned otherwise.
+    pub fn project<'b>(&self, token: AttributeToken<'b>) -> Option<AttributeToken<'b>> {
+        if !self.keeps(token.name) {
+            return None;
+        }
+        match self.coercions.get(token.name) {
+            Some(coercion) => coercion(token.value).map(|value| AttributeToken { name: token.name, value }),
+            None => Some(token),
+        }
+    }
+
+    /// # Add blacklist
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `attribute_names` : `&[&str]` - the names of the attributes that shouldn't be loaded.
+    /// 
+    /// ## Returns
+    /// * `AttributeProjection` - the updated projection.
+    pub fn with_blacklist(mut self, attribute_names: &[&str]) -> Self {
+        self.blacklist.extend(attribute_names.iter().map(ToString::to_string));
+        self
+    }
+
+    /// # Add coercion
+    /// 
+    /// ## Description
+    /// Convert the values of the attribute with the given name when  they  are  loaded,
+    /// e.g. to store `Int64` values that are known to be small as `Int8`.
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `attribute_name` : `&str` - the name of the attribute.
+    /// * `coercion`  :  `F`  -  a  function  that  gets  the  read  value  and  returns
+    /// `Some(value)` with the value to load  or  `None`  if  the  attribute  should  be
+    /// dropped.
+    /// 
+    /// ## Returns
+    /// * `AttributeProjection` - the updated projection.
+    pub fn with_coercion<F>(mut self, attribute_name: &str, coercion: F) -> Self
+    where
+        F: Fn(StaticDispatchAttributeValue) -> Option<StaticDispatchAttributeValue> + 'a,
+    {
+        self.coercions.insert(attribute_name.to_string(), Box::new(coercion));
+        self
+    }
+
+    /// # Add whitelist
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `attribute_names` : `&[&str]` - the names of the  attributes  that  should  be
+    /// loaded, the other attributes are skipped.
+    /// 
+    /// ## Returns
+    /// * `AttributeProjection` - the updated projection.
+    pub fn with_whitelist(mut self, attribute_names: &[&str]) -> Self {
+        self.whitelist.get_or_insert_with(HashSet::new).extend(attribute_names.iter().map(ToString::to_string));
+        self
+    }
+}
+
+// AttributeProjection::Default
+impl Default for AttributeProjection<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * EXPORT FILTER                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+type EdgePredicate<'a, EdgeAttributeCollectionType, EdgeIdType, VertexIdType> = Box<dyn Fn(&EdgeIteratorItem<EdgeIdType, VertexIdType>, &EdgeAttributeCollectionType) -> bool + 'a>;
+type VertexPredicate<'a, VertexAttributeCollectionType, VertexIdType> = Box<dyn Fn(&VertexIdType, &VertexAttributeCollectionType) -> bool + 'a>;
+
+
+
+/// # Export filter
+/// 
+/// ## Description
+/// Selects the part of a graph that a [`Writer`] should write. A vertex is  written  if
+/// it satisfies the vertex predicate, an edge is  written  if  it  satisfies  the  edge
+/// predicate and both its endpoints are written. The predicates are checked  while  the
+/// graph is being written, so no copy of the selected subgraph is ever made.
+/// 
+/// A filter without predicates selects the whole graph.
+pub struct ExportFilter<'a, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    edge_predicate: Option<EdgePredicate<'a, EdgeAttributeCollectionType, EdgeIdType, VertexIdType>>,
+    vertex_predicate: Option<VertexPredicate<'a, VertexAttributeCollectionType, VertexIdType>>,
+}
+
+// ExportFilter::ExportFilter
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> ExportFilter<'a, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Create export filter
+    /// 
+    /// ## Description
+    /// Create a filter that selects the whole graph.
+    /// 
+    /// ## Returns
+    /// * `ExportFilter` - the filter.
+    pub fn new() -> Self {
+        ExportFilter { edge_predicate: None, vertex_predicate: None }
+    }
+
+    /// # Set edge predicate
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `predicate` : `F` - a function that  gets  an  edge  and  its  attributes  and
+    /// returns `true` if the edge should be written.
+    /// 
+    /// ## Returns
+    /// * `ExportFilter` - the updated filter.
+    pub fn with_edge_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&EdgeIteratorItem<EdgeIdType, VertexIdType>, &EdgeAttributeCollectionType) -> bool + 'a,
+    {
+        self.edge_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// # Set vertex predicate
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `predicate` : `F` - a function that gets the ID of a vertex and its attributes
+    /// and returns `true` if the vertex should be written.
+    /// 
+    /// ## Returns
+    /// * `ExportFilter` - the updated filter.
+    pub fn with_vertex_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&VertexIdType, &VertexAttributeCollectionType) -> bool + 'a,
+    {
+        self.vertex_predicate = Some(Box::new(predicate));
+        self
+    }
+
+    /// # Iterate over selected edges
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator>` - an iterator over the selected edges of the graph.
+    pub fn iter_e<'b, G>(&'b self, graph: &'b G) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'b>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    {
+        if self.edge_predicate.is_none() && self.vertex_predicate.is_none() {
+            return graph.iter_e();
+        }
+        Box::new(graph.iter_e().filter(|edge|
+            self.keeps_v(graph, &edge.id1) && self.keeps_v(graph, &edge.id2)
+            && self.edge_predicate.as_ref().is_none_or(|predicate| predicate(edge, graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap()))
+        ))
+    }
+
+    /// # Iterate over selected vertices
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator>` - an iterator over the IDs of the selected vertices of the graph.
+    pub fn iter_v<'b, G>(&'b self, graph: &'b G) -> Box<dyn Iterator<Item = VertexIdType> + 'b>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    {
+        if self.vertex_predicate.is_none() {
+            return graph.iter_v();
+        }
+        Box::new(graph.iter_v().filter(|id| self.keeps_v(graph, id)))
+    }
+
+    fn keeps_v<G>(&self, graph: &G, id: &VertexIdType) -> bool
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    {
+        self.vertex_predicate.as_ref().is_none_or(|predicate| predicate(id, graph.v_attrs(id).unwrap()))
+    }
+}
+
+// ExportFilter::Default
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> Default for ExportFilter<'a, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+
+
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
 // * READER/WRITER TRAITS                                                              *
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
@@ -130,6 +495,32 @@ where
 
 pub trait Reader {
     fn read_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        R: Read,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        self.read_projected_graph(buffer_reader, &AttributeProjection::new())
+    }
+    /// # Read graph with projected attributes
+    /// 
+    /// ## Description
+    /// Read a graph loading only the attributes selected by the projection.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `buffer_reader` : `BufReader<R>` - the source.
+    /// * `projection` :  `&AttributeProjection`  -  the  projection  that  selects  and
+    /// converts the attributes.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    fn read_projected_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, buffer_reader: BufReader<R>, projection: &AttributeProjection<'_>) -> CrabNetsResult<G>
     where
         G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
         R: Read,
@@ -143,6 +534,31 @@ pub trait Reader {
 
 pub trait Writer {
     fn write_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        self.write_filtered_graph(graph, buffer_writer, &ExportFilter::new())
+    }
+    /// # Write part of graph
+    /// 
+    /// ## Description
+    /// Write only the part of the graph selected by the filter.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `buffer_writer` : `&mut BufWriter<W>` - the destination.
+    /// * `filter` : `&ExportFilter` - the filter that selects vertices and edges.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if the graph  was  written  successfully;
+    /// `Err(_)` is returned otherwise.
+    fn write_filtered_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()>
     where
         G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
         W: Write,
@@ -157,22 +573,197 @@ pub trait Writer {
 
 
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MEMORY-MAPPED FILES                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Read-only memory mapping of an entire file
+#[cfg(all(feature = "mmap", unix))]
+struct MappedFile {
+    length: usize,
+    pointer: *mut libc::c_void,
+}
+
+// MappedFile::MappedFile
+#[cfg(all(feature = "mmap", unix))]
+impl MappedFile {
+    fn open(file_name: &str) -> std::io::Result<Self> {
+        let file = File::open(file_name)?;
+        let length = match usize::try_from(file.metadata()?.len()) {
+            Ok(value) => value,
+            Err(_) => return Err(std::io::Error::new(std::io::ErrorKind::OutOfMemory, "the file doesn't fit into the address space")),
+        };
+        // Empty regions can't be mapped
+        if length == 0 {
+            return Ok(MappedFile { length, pointer: null_mut() });
+        }
+        // SAFETY: the region is mapped read-only and privately, it stays valid after the
+        // file is closed and is unmapped only when the MappedFile is dropped
+        let pointer = unsafe { libc::mmap(null_mut(), length, libc::PROT_READ, libc::MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if pointer == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        // The advice is a mere optimisation, so its failure is ignored
+        unsafe { libc::madvise(pointer, length, libc::MADV_SEQUENTIAL) };
+        Ok(MappedFile { length, pointer })
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        if self.length == 0 {
+            return &[];
+        }
+        // SAFETY: the pointer refers to a live mapping of exactly `length` bytes
+        unsafe { from_raw_parts(self.pointer as *const u8, self.length) }
+    }
+}
+
+// MappedFile::Drop
+#[cfg(all(feature = "mmap", unix))]
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        if self.length != 0 {
+            // SAFETY: the mapping was created in MappedFile::open and is unmapped only once
+            unsafe { libc::munmap(self.pointer, self.length) };
+        }
+    }
+}
+
+
+
+
 // * MAIN IO TRAIT                                                                     *
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
 
 
 
-enum SupportedFormats {
+/// # Supported formats
+/// 
+/// ## Description
+/// Formats handled by [`IO`], the format of a file is given by its extension.
+/// 
+/// ## Variants
+/// * `GNBS` - GNBS format, files with extension `.gnbs`.
+/// * `Protobuf` -  Protocol  Buffers  messages  defined  in  [`protobuf`],  files  with
+/// extension `.pb`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SupportedFormats {
     GNBS,
+    Protobuf,
+}
+
+// SupportedFormats::SupportedFormats
+impl SupportedFormats {
+    /// # Extension of files
+    /// 
+    /// ## Description
+    /// Get the extension of the files in this format.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&str` - the extension without the leading dot.
+    pub fn extension(&self) -> &str {
+        match self {
+            SupportedFormats::GNBS => "gnbs",
+            SupportedFormats::Protobuf => "pb",
+        }
+    }
+
+    /// # Format of file
+    /// 
+    /// ## Description
+    /// Find out the format of the file with the given name by its extension.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `Option<SupportedFormats>` - `Some(value)` is returned if  the  extension  (in
+    /// any case) belongs to a supported format, `value` in this  case  is  the  format;
+    /// `None` is returned otherwise.
+    pub fn from_file_name(file_name: &str) -> Option<Self> {
+        [SupportedFormats::GNBS, SupportedFormats::Protobuf].into_iter().find(|x| file_name.to_lowercase().ends_with(&format!(".{}", x.extension())))
+    }
 }
 
 
 
 pub trait IO {
+    /// # Read graph from bytes
+    /// 
+    /// ## Description
+    /// Read a graph from a byte slice in the given format.
+    /// 
+    /// ## Arguments
+    /// * `bytes` : `&[u8]` - the serialised graph.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    fn from_bytes(bytes: &[u8], format: SupportedFormats) -> CrabNetsResult<Self>
+    where
+        Self: Sized;
+    #[cfg(feature = "std")]
     fn from_file(file_name: &str) -> CrabNetsResult<Self>
     where
         Self: Sized;
+    /// # Read graph from string
+    /// 
+    /// ## Description
+    /// Read a graph from a string in the given format.
+    /// 
+    /// ## Arguments
+    /// * `string` : `&str` - the serialised graph.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    fn from_string(string: &str, format: SupportedFormats) -> CrabNetsResult<Self>
+    where
+        Self: Sized,
+    {
+        Self::from_bytes(string.as_bytes(), format)
+    }
+    /// # Write graph into bytes
+    /// 
+    /// ## Description
+    /// Write the graph into a vector of bytes in the given format.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<u8>>` - `Ok(value)` is returned if the graph  was  written
+    /// successfully, `value` in this case is the serialised graph; `Err(_)` is returned
+    /// otherwise.
+    fn to_bytes(&self, format: SupportedFormats) -> CrabNetsResult<Vec<u8>>;
+    #[cfg(feature = "std")]
     fn into_file(&self, file_name: &str) -> CrabNetsResult<()>;
+    /// # Write graph into string
+    /// 
+    /// ## Description
+    /// Write the graph into  a  string  in  the  given  format.  Binary  formats  (e.g.
+    /// Protobuf) don't produce valid UTF-8, use [`IO::to_bytes`] for them instead.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if the  graph  was  written
+    /// successfully, `value` in this case is the serialised graph; `Err(_)` is returned
+    /// otherwise.
+    fn to_string_as(&self, format: SupportedFormats) -> CrabNetsResult<String> {
+        const FUNCTION_PATH: &str = "IO::to_string_as";
+        String::from_utf8(self.to_bytes(format)?).map_err(|x| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(std::io::ErrorKind::InvalidData, x)))
+    }
 }
 
 
@@ -184,45 +775,218 @@ where
     EdgeIdType: Id,
     LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
     VertexAttributeCollectionType: AttributeCollectionIO,
-    VertexIdType: FromStr + Id + Into<usize>,
+    VertexIdType: FromStr + Id,
 {
+    fn from_bytes(bytes: &[u8], format: SupportedFormats) -> CrabNetsResult<Self> {
+        Self::from_bytes_projected(bytes, format, &AttributeProjection::new())
+    }
+
+    #[cfg(feature = "std")]
     fn from_file(file_name: &str) -> CrabNetsResult<Self> {
-        const FUNCTION_PATH: &str = "Graph::IO::from_file";
-        let file_format: SupportedFormats;
-        if file_name.to_lowercase().ends_with(".gnbs") {
-            file_format = SupportedFormats::GNBS;
-        } else {
-            return Err(CrabNetsError::new(FUNCTION_PATH, format!("Unsupported format of the file with name '{}'.", file_name)));
+        Self::from_file_projected(file_name, &AttributeProjection::new())
+    }
+
+    fn to_bytes(&self, format: SupportedFormats) -> CrabNetsResult<Vec<u8>> {
+        self.to_bytes_filtered(format, &ExportFilter::new())
+    }
+
+    #[cfg(feature = "std")]
+    fn into_file(&self, file_name: &str) -> CrabNetsResult<()> {
+        self.into_file_filtered(file_name, &ExportFilter::new())
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: FromStr + Id,
+{
+    /// # Read graph with projected attributes from bytes
+    /// 
+    /// ## Description
+    /// Read a graph from a byte slice in the given format loading only  the  attributes
+    /// selected by the projection.
+    /// 
+    /// ## Arguments
+    /// * `bytes` : `&[u8]` - the serialised graph.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// * `projection` :  `&AttributeProjection`  -  the  projection  that  selects  and
+    /// converts the attributes.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn from_bytes_projected(bytes: &[u8], format: SupportedFormats, projection: &AttributeProjection<'_>) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::from_bytes_projected";
+        match format {
+            SupportedFormats::GNBS => {
+                GNBSReader.read_projected_graph(BufReader::new(bytes), projection)
+            },
+            SupportedFormats::Protobuf => {
+                protobuf::decode_graph(bytes, projection, FUNCTION_PATH)
+            },
+        }
+    }
+
+    /// # Read graph with projected attributes from file
+    /// 
+    /// ## Description
+    /// Read a graph from the file with the  given  name  loading  only  the  attributes
+    /// selected by the projection, the format is chosen by the extension of the file.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the file.
+    /// * `projection` :  `&AttributeProjection`  -  the  projection  that  selects  and
+    /// converts the attributes.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// With `mmap` feature on Unix-like systems, Protobuf files are mapped into  memory
+    /// and decoded right from the mapped region instead of being copied into  a  buffer
+    /// first. In this case, the file must not be modified while it's being read.
+    #[cfg(feature = "std")]
+    pub fn from_file_projected(file_name: &str, projection: &AttributeProjection<'_>) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::from_file_projected";
+        let file_format = match SupportedFormats::from_file_name(file_name) {
+            Some(value) => value,
+            None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedFormat { file_name: file_name.to_string() })),
+        };
+        // Protobuf messages are decoded as a whole, so they're read right from the mapped
+        // region instead of being copied into a buffer first
+        #[cfg(all(feature = "mmap", unix))]
+        if file_format == SupportedFormats::Protobuf {
+            return match MappedFile::open(file_name) {
+                Ok(value) => protobuf::decode_graph(value.as_bytes(), projection, FUNCTION_PATH),
+                Err(error) => Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+            };
         }
         let file = match File::open(file_name) {
             Ok(value) => value,
-            Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Failed to open the file with name '{}'.", file_name))),
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
         };
         let buffer_reader = BufReader::new(file);
         match file_format {
             SupportedFormats::GNBS => {
-                GNBSReader.read_graph(buffer_reader)
+                GNBSReader.read_projected_graph(buffer_reader, projection)
+            },
+            SupportedFormats::Protobuf => {
+                ProtobufReader.read_projected_graph(buffer_reader, projection)
             },
         }
     }
 
-    fn into_file(&self, file_name: &str) -> CrabNetsResult<()> {
-        const FUNCTION_PATH: &str = "Graph::IO::into_file";
-        let file_format: SupportedFormats;
-        if file_name.to_lowercase().ends_with(".gnbs") {
-            file_format = SupportedFormats::GNBS;
-        } else {
-            return Err(CrabNetsError::new(FUNCTION_PATH, format!("Unsupported format of the file with name '{}'.", file_name)));
+    /// # Write part of graph into bytes
+    /// 
+    /// ## Description
+    /// Write the part of the caller selected by the filter into a vector  of  bytes  in
+    /// the given format. This is [`IO::to_bytes`] for the cases when only a part  of  a
+    /// huge graph is needed.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `format` : `SupportedFormats` - the format of the serialised graph.
+    /// * `filter` : `&ExportFilter` - the filter that selects vertices and edges.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<u8>>` - `Ok(value)` is returned if the graph  was  written
+    /// successfully, `value` in this case is the serialised graph; `Err(_)` is returned
+    /// otherwise.
+    pub fn to_bytes_filtered(&self, format: SupportedFormats, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<Vec<u8>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::to_bytes_filtered";
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        match format {
+            SupportedFormats::GNBS => {
+                GNBSWriter.write_filtered_graph(self, &mut buffer_writer, filter)?
+            },
+            SupportedFormats::Protobuf => {
+                ProtobufWriter.write_filtered_graph(self, &mut buffer_writer, filter)?
+            },
         }
+        buffer_writer.into_inner().map_err(|x| CrabNetsError::new(FUNCTION_PATH, x.into_error()))
+    }
+
+    /// # Write part of graph into file
+    /// 
+    /// ## Description
+    /// Write the part of the caller selected by the filter into the file with the given
+    /// name, the format is chosen by the extension of the file as in [`IO::into_file`].
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `file_name` : `&str` - the name of the file.
+    /// * `filter` : `&ExportFilter` - the filter that selects vertices and edges.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if the graph  was  written  successfully;
+    /// `Err(_)` is returned otherwise.
+    #[cfg(feature = "std")]
+    pub fn into_file_filtered(&self, file_name: &str, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()> {
+        const FUNCTION_PATH: &str = "Graph::Graph::into_file_filtered";
+        let file_format = match SupportedFormats::from_file_name(file_name) {
+            Some(value) => value,
+            None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedFormat { file_name: file_name.to_string() })),
+        };
         let file = match File::options().create(true).write(true).truncate(true).open(file_name) {
             Ok(value) => value,
-            Err(_) => return Err(CrabNetsError::new(FUNCTION_PATH, format!("Failed to open the file with name '{}'.", file_name))),
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
         };
         let mut buffer_writer = BufWriter::new(file);
         match file_format {
             SupportedFormats::GNBS => {
-                GNBSWriter.write_graph(self, &mut buffer_writer)
+                GNBSWriter.write_filtered_graph(self, &mut buffer_writer, filter)
+            },
+            SupportedFormats::Protobuf => {
+                ProtobufWriter.write_filtered_graph(self, &mut buffer_writer, filter)
             },
         }
     }
+
+    /// # Write graph into GNBS string
+    /// 
+    /// ## Description
+    /// Write the caller into a string in [GNBS format][gnbs]. This is a  shorthand  for
+    /// [`IO::to_string_as`] with [`SupportedFormats::GNBS`]; the result can be turned
+    /// back into a graph with [`str::parse`].
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if the  graph  was  written
+    /// successfully, `value` in this case is the serialised graph; `Err(_)` is returned
+    /// otherwise.
+    /// 
+    /// [gnbs]: crate::io::gnbs
+    pub fn to_gnbs_string(&self) -> CrabNetsResult<String> {
+        self.to_string_as(SupportedFormats::GNBS)
+    }
+}
+
+
+
+// Graph::FromStr
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> FromStr for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: FromStr + Id,
+{
+    type Err = CrabNetsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_string(s, SupportedFormats::GNBS)
+    }
 }
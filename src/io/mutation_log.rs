@@ -0,0 +1,610 @@
+//! # Mutation log module
+//! 
+//! ## Description
+//! This module contains [`MutationLog`] that persists  evolving  graphs  incrementally.
+//! Instead of serialising the whole graph after  every  change,  each  [`Mutation`]  is
+//! appended  to  the  log  as  a  small  record,  and  the  graph   is   rebuilt   with
+//! [`Graph::replay`] when needed.
+//! 
+//! Records are `LogRecord` messages of the schema  in  `proto/crabnets.proto`  prefixed
+//! with their lengths and followed by their CRC-32 checksums. A record cut short  by  a
+//! crash in the middle of an append is ignored during replay, while  a  record  with  a
+//! wrong checksum is reported as an error.
+//! 
+//! Logs only grow, so from time to time they should be compacted, i.e. replaced by  the
+//! shortest log that produces the current graph (see [`MutationLog::append_graph`]).
+#[cfg(feature = "std")]
+use std::fs::{self, File};
+use std::{io::{BufWriter, Read, Write}, str::FromStr};
+use itertools::Itertools;
+use crate::{
+    attributes::StaticDispatchAttributeValue, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Graph, Id, Locale
+};
+use super::{
+    protobuf::{decode_attribute, decode_error, encode_value, read_fields, read_string, write_length_delimited, write_singular_string, write_singular_varint, write_varint, Checksum, WireValue},
+    AttributeCollectionIO, AttributeProjection, AttributeToken
+};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MUTATION                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Mutation
+/// 
+/// ## Description
+/// A change of a graph that can be recorded in a [`MutationLog`].
+/// 
+/// ## Variants
+/// * `AddEdge` - add an edge between `id1` and `id2` (directed from `id1` to  `id2`  if
+/// `directed`),   `edge_id`   is   the   desired    ID    of    the    edge    as    in
+/// [`BasicMutableGraph::add_e`].
+/// * `AddVertex` - add a vertex with ID `id` or replace the existing one.
+/// * `RemoveEdge` - remove the edge with ID `edge_id` between `id1` and `id2`.
+/// * `RemoveVertex` - remove the vertex with ID `id` and all its incident edges.
+/// * `SetEdgeAttribute` - set the attribute with  name  `name`  of  the  edge  with  ID
+/// `edge_id` between `id1` and `id2` to `value`.
+/// * `SetVertexAttribute` - set the attribute with name `name` of the  vertex  with  ID
+/// `id` to `value`.
+pub enum Mutation<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    AddEdge { id1: VertexIdType, id2: VertexIdType, directed: bool, edge_id: Option<EdgeIdType> },
+    AddVertex { id: VertexIdType },
+    RemoveEdge { id1: VertexIdType, id2: VertexIdType, edge_id: EdgeIdType },
+    RemoveVertex { id: VertexIdType },
+    SetEdgeAttribute { id1: VertexIdType, id2: VertexIdType, edge_id: EdgeIdType, name: String, value: StaticDispatchAttributeValue },
+    SetVertexAttribute { id: VertexIdType, name: String, value: StaticDispatchAttributeValue },
+}
+
+// Mutation::Mutation
+impl<EdgeIdType, VertexIdType> Mutation<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Apply mutation
+    /// 
+    /// ## Description
+    /// Apply the mutation to the graph.
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `graph` : `&mut G` - a mutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<EdgeIdType>>` - `Ok(value)` is returned if the mutation
+    /// was applied successfully, `value` in this case is the ID of the added  edge  for
+    /// `AddEdge` and `None` for the  other  mutations;  `Err(_)`  is  returned  if  the
+    /// vertices or edges the mutation refers to don't exist.
+    pub fn apply<G, EdgeAttributeCollectionType, VertexAttributeCollectionType>(self, graph: &mut G) -> CrabNetsResult<Option<EdgeIdType>>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+    {
+        const FUNCTION_PATH: &str = "Mutation::Mutation::apply";
+        match self {
+            Mutation::AddEdge { id1, id2, directed, edge_id } => return graph.add_e(&id1, &id2, directed, edge_id).map(Some),
+            Mutation::AddVertex { id } => {
+                graph.add_v(Some(id));
+            },
+            Mutation::RemoveEdge { id1, id2, edge_id } => {
+                if !graph.remove_e(&id1, &id2, &edge_id)? {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::EdgeNotFound { id1: id1.to_string(), id2: id2.to_string(), edge_id: edge_id.to_string() }));
+                }
+            },
+            Mutation::RemoveVertex { id } => {
+                if graph.remove_v(&id).is_none() {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }));
+                }
+            },
+            Mutation::SetEdgeAttribute { id1, id2, edge_id, name, value } => {
+                graph.e_attrs_mut(&id1, &id2, &edge_id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name: &name, value });
+            },
+            Mutation::SetVertexAttribute { id, name, value } => {
+                graph.v_attrs_mut(&id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name: &name, value });
+            },
+        }
+        Ok(None)
+    }
+
+    // Encode the mutation as a record of the log including its length and checksum
+    pub(super) fn encode(&self) -> Vec<u8> {
+        let encode_attribute = |message: &mut Vec<u8>, name: &str, value: &StaticDispatchAttributeValue| {
+            let mut attribute = Vec::new();
+            write_singular_string(&mut attribute, 1, name);
+            write_length_delimited(&mut attribute, 2, &encode_value(value));
+            write_length_delimited(message, 6, &attribute);
+        };
+        let mut message = Vec::new();
+        match self {
+            Mutation::AddEdge { id1, id2, directed, edge_id } => {
+                write_singular_varint(&mut message, 1, 2);
+                write_singular_string(&mut message, 2, &id1.to_string());
+                write_singular_string(&mut message, 3, &id2.to_string());
+                if let Some(edge_id) = edge_id {
+                    write_singular_string(&mut message, 4, &edge_id.to_string());
+                }
+                write_singular_varint(&mut message, 5, *directed as u64);
+            },
+            Mutation::AddVertex { id } => write_singular_string(&mut message, 2, &id.to_string()),
+            Mutation::RemoveEdge { id1, id2, edge_id } => {
+                write_singular_varint(&mut message, 1, 3);
+                write_singular_string(&mut message, 2, &id1.to_string());
+                write_singular_string(&mut message, 3, &id2.to_string());
+                write_singular_string(&mut message, 4, &edge_id.to_string());
+            },
+            Mutation::RemoveVertex { id } => {
+                write_singular_varint(&mut message, 1, 1);
+                write_singular_string(&mut message, 2, &id.to_string());
+            },
+            Mutation::SetEdgeAttribute { id1, id2, edge_id, name, value } => {
+                write_singular_varint(&mut message, 1, 5);
+                write_singular_string(&mut message, 2, &id1.to_string());
+                write_singular_string(&mut message, 3, &id2.to_string());
+                write_singular_string(&mut message, 4, &edge_id.to_string());
+                encode_attribute(&mut message, name, value);
+            },
+            Mutation::SetVertexAttribute { id, name, value } => {
+                write_singular_varint(&mut message, 1, 4);
+                write_singular_string(&mut message, 2, &id.to_string());
+                encode_attribute(&mut message, name, value);
+            },
+        }
+        let mut answer = Vec::with_capacity(message.len() + 9);
+        write_varint(&mut answer, message.len() as u64);
+        answer.extend_from_slice(&message);
+        let mut checksum = Checksum::new();
+        checksum.update(&message);
+        answer.extend_from_slice(&checksum.value().to_le_bytes());
+        answer
+    }
+}
+
+// Mutation::Mutation
+impl<EdgeIdType, VertexIdType> Mutation<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: FromStr + Id,
+    VertexIdType: FromStr + Id,
+{
+    // Decode the `LogRecord` message
+    fn decode(bytes: &[u8], function_path: &str) -> CrabNetsResult<Self> {
+        let (mut kind, mut directed) = (0, false);
+        let (mut id1, mut id2, mut edge_id) = ("", "", None);
+        let mut attribute = None;
+        for (field_number, field_value) in read_fields(bytes, function_path)? {
+            match (field_number, &field_value) {
+                (1, WireValue::Varint(x)) => kind = *x,
+                (2, _) => id1 = read_string(&field_value, function_path)?,
+                (3, _) => id2 = read_string(&field_value, function_path)?,
+                (4, _) => edge_id = Some(read_string(&field_value, function_path)?),
+                (5, WireValue::Varint(x)) => directed = *x != 0,
+                (6, WireValue::LengthDelimited(x)) => attribute = decode_attribute(x, &AttributeProjection::new(), function_path)?,
+                (1..=6, _) => return Err(decode_error(function_path, "log record field of wrong type")),
+                _ => (),
+            }
+        }
+        let parse_vertex_id = |id: &str| VertexIdType::from_str(id).map_err(|_| decode_error(function_path, &format!("'{}' isn't a valid vertex ID", id)));
+        let parse_edge_id = |id: Option<&str>| match id {
+            Some(id) => EdgeIdType::from_str(id).map_err(|_| decode_error(function_path, &format!("'{}' isn't a valid edge ID", id))),
+            None => Err(decode_error(function_path, "log record has no edge ID")),
+        };
+        let attribute = || attribute.ok_or_else(|| decode_error(function_path, "log record has no attribute"));
+        Ok(match kind {
+            0 => Mutation::AddVertex { id: parse_vertex_id(id1)? },
+            1 => Mutation::RemoveVertex { id: parse_vertex_id(id1)? },
+            2 => Mutation::AddEdge { id1: parse_vertex_id(id1)?, id2: parse_vertex_id(id2)?, directed, edge_id: edge_id.map(|x| parse_edge_id(Some(x))).transpose()? },
+            3 => Mutation::RemoveEdge { id1: parse_vertex_id(id1)?, id2: parse_vertex_id(id2)?, edge_id: parse_edge_id(edge_id)? },
+            4 => {
+                let attribute = attribute()?;
+                Mutation::SetVertexAttribute { id: parse_vertex_id(id1)?, name: attribute.name.to_string(), value: attribute.value }
+            },
+            5 => {
+                let attribute = attribute()?;
+                Mutation::SetEdgeAttribute { id1: parse_vertex_id(id1)?, id2: parse_vertex_id(id2)?, edge_id: parse_edge_id(edge_id)?, name: attribute.name.to_string(), value: attribute.value }
+            },
+            _ => return Err(decode_error(function_path, &format!("unknown mutation kind {}", kind))),
+        })
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MUTATION LOG                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Mutation log
+/// 
+/// ## Description
+/// Appends mutations of a graph to the underlying writer.
+/// 
+/// ## Details
+/// Every record is flushed as  soon  as  it's  appended.  [`MutationLog::record_count`]
+/// tells how many records were appended through this log, which can be used  to  decide
+/// when it's time to compact it.
+pub struct MutationLog<W>
+where
+    W: Write,
+{
+    buffer_writer: BufWriter<W>,
+    record_count: usize,
+}
+
+// MutationLog::MutationLog
+impl<W> MutationLog<W>
+where
+    W: Write,
+{
+    /// # Create mutation log
+    /// 
+    /// ## Arguments
+    /// * `writer` : `W` - the destination of the records.
+    /// 
+    /// ## Returns
+    /// * `MutationLog<W>` - the log.
+    pub fn new(writer: W) -> Self {
+        MutationLog { buffer_writer: BufWriter::new(writer), record_count: 0 }
+    }
+
+    /// # Append mutation
+    /// 
+    /// ## Description
+    /// Append the mutation to the log without applying it to any graph, e.g. if it  has
+    /// already been applied.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `mutation` : `&Mutation<EdgeIdType, VertexIdType>` - the mutation.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  mutation  was  appended
+    /// successfully; `Err(_)` is returned otherwise.
+    pub fn append<EdgeIdType, VertexIdType>(&mut self, mutation: &Mutation<EdgeIdType, VertexIdType>) -> CrabNetsResult<()>
+    where
+        EdgeIdType: Id,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "MutationLog::MutationLog::append";
+        self.append_record(&mutation.encode(), FUNCTION_PATH)
+    }
+
+    /// # Append graph
+    /// 
+    /// ## Description
+    /// Append the mutations that build the graph from scratch. Written  into  an  empty
+    /// log, they make the compacted version of any log of this graph.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the   graph   was   appended
+    /// successfully; `Err(_)` is returned otherwise.
+    pub fn append_graph<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&mut self, graph: &G) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        for id in graph.iter_v().sorted() {
+            self.append(&Mutation::<EdgeIdType, VertexIdType>::AddVertex { id: id.clone() })?;
+            for token in graph.v_attrs(&id)?.io_iter_contents().sorted_by(|x, y| x.name.cmp(y.name)) {
+                self.append(&Mutation::<EdgeIdType, VertexIdType>::SetVertexAttribute { id: id.clone(), name: token.name.to_string(), value: token.value })?;
+            }
+        }
+        for edge in graph.iter_e().sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id))) {
+            let (id1, id2) = match edge.direction {
+                EdgeDirection::Directed2to1 => (edge.id2.clone(), edge.id1.clone()),
+                _ => (edge.id1.clone(), edge.id2.clone()),
+            };
+            self.append(&Mutation::AddEdge { id1: id1.clone(), id2: id2.clone(), directed: edge.direction != EdgeDirection::Undirected, edge_id: Some(edge.edge_id.clone()) })?;
+            for token in graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?.io_iter_contents().sorted_by(|x, y| x.name.cmp(y.name)) {
+                self.append(&Mutation::SetEdgeAttribute { id1: id1.clone(), id2: id2.clone(), edge_id: edge.edge_id.clone(), name: token.name.to_string(), value: token.value })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// # Unwrap writer
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<W>` - `Ok(value)` is returned if  the  buffered  records  were
+    /// flushed successfully, `value` in this case is the underlying writer; `Err(_)` is
+    /// returned otherwise.
+    pub fn into_inner(self) -> CrabNetsResult<W> {
+        const FUNCTION_PATH: &str = "MutationLog::MutationLog::into_inner";
+        self.buffer_writer.into_inner().map_err(|x| CrabNetsError::new(FUNCTION_PATH, x.into_error()))
+    }
+
+    /// # Number of records
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of records appended through this log.
+    pub fn record_count(&self) -> usize {
+        self.record_count
+    }
+
+    /// # Apply and record mutation
+    /// 
+    /// ## Description
+    /// Apply the mutation to the graph and append it to  the  log  if  it  was  applied
+    /// successfully. Added edges are recorded with their actual IDs, so  replaying  the
+    /// log gives the same IDs.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `graph` : `&mut G` - a mutable reference to the graph.
+    /// * `mutation` : `Mutation<EdgeIdType, VertexIdType>` - the mutation.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<EdgeIdType>>` - `Ok(value)` is returned if the mutation
+    /// was applied and recorded successfully, `value` in this case is  the  ID  of  the
+    /// added edge for `AddEdge`  and  `None`  for  the  other  mutations;  `Err(_)`  is
+    /// returned otherwise.
+    pub fn record<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&mut self, graph: &mut G, mutation: Mutation<EdgeIdType, VertexIdType>) -> CrabNetsResult<Option<EdgeIdType>>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        if let Mutation::AddEdge { id1, id2, directed, .. } = &mutation {
+            let (id1, id2, directed) = (id1.clone(), id2.clone(), *directed);
+            let edge_id = mutation.apply(graph)?;
+            self.append(&Mutation::AddEdge { id1, id2, directed, edge_id: edge_id.clone() })?;
+            return Ok(edge_id);
+        }
+        const FUNCTION_PATH: &str = "MutationLog::MutationLog::record";
+        let record = mutation.encode();
+        mutation.apply(graph)?;
+        self.append_record(&record, FUNCTION_PATH)?;
+        Ok(None)
+    }
+
+    // Write the encoded record and flush it
+    fn append_record(&mut self, record: &[u8], function_path: &str) -> CrabNetsResult<()> {
+        self.buffer_writer.write_all(record).and_then(|_| self.buffer_writer.flush()).map_err(|error| CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to append to the mutation log: {}", error))))?;
+        self.record_count += 1;
+        Ok(())
+    }
+}
+
+// MutationLog::MutationLog
+#[cfg(feature = "std")]
+impl MutationLog<File> {
+    /// # Compact log file
+    /// 
+    /// ## Description
+    /// Replace the log file with the given name by the compacted log of the  graph  and
+    /// open it for appending. The new log is written into a temporary  file  first  and
+    /// then renamed, so the old log stays intact if anything goes wrong.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the log file.
+    /// * `graph` : `&G` - an immutable reference to the graph the log describes.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<MutationLog<File>>` - `Ok(value)` is  returned  if  the  log  was
+    /// compacted successfully, `value` in this case is  the  compacted  log;  `Err(_)`  is
+    /// returned otherwise.
+    pub fn compact<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(file_name: &str, graph: &G) -> CrabNetsResult<Self>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "MutationLog::MutationLog::compact";
+        let temporary_file_name = format!("{}.tmp", file_name);
+        let file = match File::options().create(true).write(true).truncate(true).open(&temporary_file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", temporary_file_name, error)))),
+        };
+        let mut log = MutationLog::new(file);
+        log.append_graph(graph)?;
+        log.into_inner()?.sync_all().and_then(|_| fs::rename(&temporary_file_name, file_name)).map_err(|error| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to replace the file with name '{}': {}", file_name, error))))?;
+        Self::open(file_name)
+    }
+
+    /// # Open log file
+    /// 
+    /// ## Description
+    /// Open the log file with the given name for appending, create it if it doesn't exist.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the log file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<MutationLog<File>>` - `Ok(value)` is returned  if  the  file  was
+    /// opened successfully, `value`  in  this  case  is  the  log;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn open(file_name: &str) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "MutationLog::MutationLog::open";
+        match File::options().create(true).append(true).open(file_name) {
+            Ok(value) => Ok(MutationLog::new(value)),
+            Err(error) => Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        }
+    }
+}
+
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * REPLAY                                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Read the next record, `None` means the end of the log or an incomplete last record
+pub(super) fn read_record<R, EdgeIdType, VertexIdType>(reader: &mut R, function_path: &str) -> CrabNetsResult<Option<Mutation<EdgeIdType, VertexIdType>>>
+where
+    R: Read,
+    EdgeIdType: FromStr + Id,
+    VertexIdType: FromStr + Id,
+{
+    let io_error = |error: std::io::Error| CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to read the mutation log: {}", error)));
+    let mut length = 0u64;
+    let mut byte = [0u8];
+    for shift in (0..64).step_by(7) {
+        match reader.read_exact(&mut byte) {
+            Ok(_) => (),
+            Err(error) if error.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+            Err(error) => return Err(io_error(error)),
+        }
+        length |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            break;
+        }
+    }
+    let length = usize::try_from(length).map_err(|_| decode_error(function_path, "log record is too long"))?;
+    let mut record = Vec::new();
+    match reader.take(length as u64 + 4).read_to_end(&mut record) {
+        Ok(value) if value < length + 4 => return Ok(None),
+        Ok(_) => (),
+        Err(error) => return Err(io_error(error)),
+    }
+    let (message, checksum) = record.split_at(length);
+    let mut actual_checksum = Checksum::new();
+    actual_checksum.update(message);
+    if actual_checksum.value().to_le_bytes() != checksum {
+        return Err(CrabNetsError::new(function_path, ErrorKind::CorruptedData { reason: "Integrity check of the mutation log failed: checksum of a record doesn't match.".to_string() }));
+    }
+    Mutation::decode(message, function_path).map(Some)
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: FromStr + Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: FromStr + Id,
+{
+    /// # Replay mutation log
+    /// 
+    /// ## Description
+    /// Build a graph by applying all mutations of the log to an empty graph.
+    /// 
+    /// ## Arguments
+    /// * `log` : `&[u8]` - the contents of the log.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>`  -  `Ok(value)`  is  returned  if  the  log  was  replayed
+    /// successfully, `value` in this case is the resulting graph; `Err(_)` is returned  if
+    /// a record is corrupted or can't be applied.
+    /// 
+    /// ## Details
+    /// An incomplete record at the end of the log is ignored.
+    pub fn replay(log: &[u8]) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::replay";
+        let mut answer = Self::default();
+        let mut reader = log;
+        while let Some(mutation) = read_record(&mut reader, FUNCTION_PATH)? {
+            mutation.apply(&mut answer)?;
+        }
+        Ok(answer)
+    }
+
+    /// # Replay mutation log file
+    /// 
+    /// ## Description
+    /// Build a graph by replaying the log file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `file_name` : `&str` - the name of the log file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Self>`  -  `Ok(value)`  is  returned  if  the  log  was  replayed
+    /// successfully, `value` in this case is the resulting  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    #[cfg(feature = "std")]
+    pub fn replay_file(file_name: &str) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "Graph::Graph::replay_file";
+        match fs::read(file_name) {
+            Ok(value) => Self::replay(&value),
+            Err(error) => Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to read the file with name '{}': {}", file_name, error)))),
+        }
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::io::{SupportedFormats, IO};
+    use super::*;
+
+    #[test]
+    fn mutation_log() {
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        let mut log = MutationLog::new(Vec::new());
+        for id in 0..4 {
+            log.record(&mut g, Mutation::AddVertex { id }).unwrap();
+        }
+        log.record(&mut g, Mutation::SetVertexAttribute { id: 1, name: "Name".to_string(), value: StaticDispatchAttributeValue::Str("one".to_string()) }).unwrap();
+        let edge_id = log.record(&mut g, Mutation::AddEdge { id1: 0, id2: 1, directed: true, edge_id: None }).unwrap().unwrap();
+        log.record(&mut g, Mutation::AddEdge { id1: 0, id2: 1, directed: true, edge_id: None }).unwrap();
+        log.record(&mut g, Mutation::AddEdge { id1: 2, id2: 3, directed: false, edge_id: None }).unwrap();
+        log.record(&mut g, Mutation::SetEdgeAttribute { id1: 0, id2: 1, edge_id, name: "Length".to_string(), value: StaticDispatchAttributeValue::Float64(0.5) }).unwrap();
+        log.record(&mut g, Mutation::RemoveEdge { id1: 0, id2: 1, edge_id }).unwrap();
+        log.record(&mut g, Mutation::RemoveVertex { id: 3 }).unwrap();
+        // Failed mutations aren't recorded
+        assert!(log.record(&mut g, Mutation::RemoveVertex { id: 3 }).is_err());
+        assert_eq!(log.record_count(), 11);
+        let bytes = log.into_inner().unwrap();
+        let h: graph!(A ===A==> A) = Graph::replay(&bytes).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::GNBS).unwrap(), g.to_bytes(SupportedFormats::GNBS).unwrap());
+        // An incomplete record is ignored, a corrupted one isn't
+        let h: graph!(A ===A==> A) = Graph::replay(&bytes[..bytes.len() - 1]).unwrap();
+        assert!(h.contains_v(&3));
+        let mut corrupted = bytes.clone();
+        corrupted[3] ^= 1;
+        assert!(<graph!(A ===A==> A)>::replay(&corrupted).is_err_and(|x| matches!(x.kind(), ErrorKind::CorruptedData { .. })));
+        // Compaction
+        #[cfg(feature = "std")]
+        {
+            let file_name = std::env::temp_dir().join(format!("crabnets_mutation_log_{}.log", std::process::id()));
+            let file_name = file_name.to_str().unwrap();
+            fs::write(file_name, &bytes).unwrap();
+            let mut log = MutationLog::compact(file_name, &g).unwrap();
+            assert!(fs::metadata(file_name).unwrap().len() < bytes.len() as u64);
+            log.record(&mut g, Mutation::AddVertex { id: 7 }).unwrap();
+            let h: graph!(A ===A==> A) = Graph::replay_file(file_name).unwrap();
+            assert_eq!(h.to_bytes(SupportedFormats::GNBS).unwrap(), g.to_bytes(SupportedFormats::GNBS).unwrap());
+            fs::remove_file(file_name).unwrap();
+        }
+    }
+}
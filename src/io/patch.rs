@@ -0,0 +1,341 @@
+//! # Graph patch module
+//! 
+//! ## Description
+//! This module contains [`GraphPatch`] that  describes  how  to  turn  one  graph  into
+//! another. Patches are computed with [`GraphPatch::diff`] and stored as  sequences  of
+//! [`Mutation`]s in the same compact binary form as mutation logs,  so  a  patch  is  a
+//! valid [mutation log][log] by itself.
+//! 
+//! Patches make it possible to replicate a graph between processes: the source  process
+//! writes patches of its graph into a pipe or a  socket,  and  the  replica  reads  and
+//! applies them as they arrive with [`GraphPatch::apply_stream`]  without  waiting  for
+//! the whole stream.
+//! 
+//! [log]: super::mutation_log
+use std::{collections::{HashMap, HashSet}, io::Read, str::FromStr};
+use itertools::Itertools;
+use crate::{errors::CrabNetsResult, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id};
+use super::{
+    mutation_log::{read_record, Mutation}, protobuf::encode_value, AttributeCollectionIO, AttributeToken
+};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * GRAPH PATCH                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Attributes by their names along with their encoded values
+type EncodedAttributes<'a> = HashMap<&'a str, (AttributeToken<'a>, Vec<u8>)>;
+// Edges by their sources (or smaller endpoints), targets (or  larger  endpoints),  IDs  and
+// directedness along with their encoded attributes
+type EncodedEdges<'a, EdgeIdType, VertexIdType> = HashMap<(VertexIdType, VertexIdType, EdgeIdType, bool), EncodedAttributes<'a>>;
+
+
+
+// Encode the attributes to compare their values
+fn encode_attributes<'a>(attributes: Box<dyn Iterator<Item = AttributeToken<'a>> + 'a>) -> EncodedAttributes<'a> {
+    attributes.map(|token| {
+        let value = encode_value(&token.value);
+        (token.name, (token, value))
+    }).collect()
+}
+
+
+
+// Collect all edges of the graph along with their encoded attributes
+fn encode_edges<'a, G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(graph: &'a G) -> CrabNetsResult<EncodedEdges<'a, EdgeIdType, VertexIdType>>
+where
+    G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    EdgeAttributeCollectionType: AttributeCollectionIO + 'a,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    let mut answer = HashMap::new();
+    for edge in graph.iter_e() {
+        let attributes = encode_attributes(graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?.io_iter_contents());
+        let key = match edge.direction {
+            EdgeDirection::Directed1to2 => (edge.id1, edge.id2, edge.edge_id, true),
+            EdgeDirection::Directed2to1 => (edge.id2, edge.id1, edge.edge_id, true),
+            EdgeDirection::Undirected => (edge.id1.clone().min(edge.id2.clone()), edge.id1.max(edge.id2), edge.edge_id, false),
+        };
+        answer.insert(key, attributes);
+    }
+    Ok(answer)
+}
+
+
+
+/// # Graph patch
+/// 
+/// ## Description
+/// A sequence of mutations that turns one graph into another.
+/// 
+/// ## Details
+/// Attribute collections can't lose single attributes, so vertices and edges that  lost
+/// some of their attributes are replaced as a whole, all their remaining attributes are
+/// written again. A replaced vertex loses its incident edges, so they are  added  again
+/// too.
+pub struct GraphPatch<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    mutations: Vec<Mutation<EdgeIdType, VertexIdType>>,
+}
+
+// GraphPatch::GraphPatch
+impl<EdgeIdType, VertexIdType> GraphPatch<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Create empty patch
+    /// 
+    /// ## Returns
+    /// * `GraphPatch<EdgeIdType, VertexIdType>` - the patch that doesn't change anything.
+    pub fn new() -> Self {
+        GraphPatch { mutations: Vec::new() }
+    }
+
+    /// # Apply patch
+    /// 
+    /// ## Description
+    /// Apply all mutations of the patch to the graph in order.
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `graph` : `&mut G` - a mutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  patch  was   applied
+    /// successfully; `Err(_)` is returned if the graph isn't  the  one  the  patch  was
+    /// computed for. Mutations that precede the failed one remain applied.
+    pub fn apply<G, EdgeAttributeCollectionType, VertexAttributeCollectionType>(self, graph: &mut G) -> CrabNetsResult<()>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+    {
+        for mutation in self.mutations {
+            mutation.apply(graph)?;
+        }
+        Ok(())
+    }
+
+    /// # Compute patch
+    /// 
+    /// ## Description
+    /// Compute the patch that turns graph `old` into graph `new`.
+    /// 
+    /// ## Arguments
+    /// * `old` : `&G1` - an immutable reference to the initial graph.
+    /// * `new` : `&G2` - an immutable reference to the desired graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<GraphPatch<EdgeIdType, VertexIdType>>`   -   `Ok(value)`    is
+    /// returned if the graphs were compared successfully, `value` in this case  is  the
+    /// patch; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Vertices are matched by their IDs, edges are matched by their endpoints, IDs and
+    /// directions. Attribute values are considered equal  if  their  encodings  in  the
+    /// protobuf format are equal. Patches of equal graphs are empty.
+    pub fn diff<G1, G2, EdgeAttributeCollectionType, VertexAttributeCollectionType>(old: &G1, new: &G2) -> CrabNetsResult<Self>
+    where
+        G1: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        G2: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+    {
+        let mut answer = Self::new();
+        // Vertices
+        let mut replaced_vertices = HashSet::new();
+        for id in old.iter_v().filter(|id| !new.contains_v(id)).sorted() {
+            answer.mutations.push(Mutation::RemoveVertex { id });
+        }
+        for id in new.iter_v().sorted() {
+            let new_attributes = encode_attributes(new.v_attrs(&id)?.io_iter_contents());
+            let old_attributes = match old.contains_v(&id) {
+                true => Some(encode_attributes(old.v_attrs(&id)?.io_iter_contents())),
+                false => None,
+            };
+            let replace = old_attributes.as_ref().is_none_or(|x| x.keys().any(|name| !new_attributes.contains_key(name)));
+            if replace {
+                answer.mutations.push(Mutation::AddVertex { id: id.clone() });
+                if old_attributes.is_some() {
+                    replaced_vertices.insert(id.clone());
+                }
+            }
+            for (name, (token, value)) in new_attributes.into_iter().sorted_by(|x, y| x.0.cmp(y.0)) {
+                if replace || old_attributes.as_ref().is_some_and(|x| x.get(name).is_none_or(|x| x.1 != value)) {
+                    answer.mutations.push(Mutation::SetVertexAttribute { id: id.clone(), name: name.to_string(), value: token.value });
+                }
+            }
+        }
+        // Edges
+        let (old_edges, new_edges) = (encode_edges(old)?, encode_edges(new)?);
+        let is_kept = |id: &VertexIdType| new.contains_v(id) && !replaced_vertices.contains(id);
+        for (id1, id2, edge_id, _) in old_edges.keys().filter(|x| !new_edges.contains_key(x) && is_kept(&x.0) && is_kept(&x.1)).sorted() {
+            answer.mutations.push(Mutation::RemoveEdge { id1: id1.clone(), id2: id2.clone(), edge_id: edge_id.clone() });
+        }
+        for ((id1, id2, edge_id, directed), new_attributes) in new_edges.into_iter().sorted_by(|x, y| x.0.cmp(&y.0)) {
+            let old_attributes = match is_kept(&id1) && is_kept(&id2) {
+                true => old_edges.get(&(id1.clone(), id2.clone(), edge_id.clone(), directed)),
+                false => None,
+            };
+            let replace = old_attributes.is_none_or(|x| x.keys().any(|name| !new_attributes.contains_key(name)));
+            if replace {
+                answer.mutations.push(Mutation::AddEdge { id1: id1.clone(), id2: id2.clone(), directed, edge_id: Some(edge_id.clone()) });
+            }
+            for (name, (token, value)) in new_attributes.into_iter().sorted_by(|x, y| x.0.cmp(y.0)) {
+                if replace || old_attributes.is_some_and(|x| x.get(name).is_none_or(|x| x.1 != value)) {
+                    answer.mutations.push(Mutation::SetEdgeAttribute { id1: id1.clone(), id2: id2.clone(), edge_id: edge_id.clone(), name: name.to_string(), value: token.value });
+                }
+            }
+        }
+        Ok(answer)
+    }
+
+    /// # Encode patch
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<u8>` - the records of all mutations of the patch, they can be appended to
+    /// a mutation log or sent to a replica.
+    pub fn into_bytes(&self) -> Vec<u8> {
+        self.mutations.iter().flat_map(|x| x.encode()).collect()
+    }
+
+    /// # Check if patch is empty
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if the patch doesn't change anything, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+
+    /// # Number of mutations
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of mutations in the patch.
+    pub fn len(&self) -> usize {
+        self.mutations.len()
+    }
+}
+
+// GraphPatch::GraphPatch
+impl<EdgeIdType, VertexIdType> GraphPatch<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: FromStr + Id,
+    VertexIdType: FromStr + Id,
+{
+    /// # Apply streamed patches
+    /// 
+    /// ## Description
+    /// Read mutation records from the reader and apply each of them  to  the  graph  as
+    /// soon as it's read, until the reader is exhausted.
+    /// 
+    /// ## Arguments
+    /// * `reader` : `R` - the source of the records, e.g. a pipe or a socket.
+    /// * `graph` : `&mut G` - a mutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if all records were  applied
+    /// successfully, `value` in this case is the number of applied mutations;  `Err(_)`
+    /// is returned if the records are corrupted or can't be applied.
+    /// 
+    /// ## Details
+    /// An incomplete record at the end of  the  stream,  e.g.  if  the  source  process
+    /// crashed while writing it, is ignored.
+    pub fn apply_stream<R, G, EdgeAttributeCollectionType, VertexAttributeCollectionType>(mut reader: R, graph: &mut G) -> CrabNetsResult<usize>
+    where
+        R: Read,
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+    {
+        const FUNCTION_PATH: &str = "GraphPatch::GraphPatch::apply_stream";
+        let mut answer = 0;
+        while let Some(mutation) = read_record::<R, EdgeIdType, VertexIdType>(&mut reader, FUNCTION_PATH)? {
+            mutation.apply(graph)?;
+            answer += 1;
+        }
+        Ok(answer)
+    }
+
+    /// # Decode patch
+    /// 
+    /// ## Arguments
+    /// * `bytes` : `&[u8]` - the records of the mutations.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<GraphPatch<EdgeIdType, VertexIdType>>`   -   `Ok(value)`    is
+    /// returned if the records were decoded successfully, `value` in this case  is  the
+    /// patch; `Err(_)` is returned otherwise.
+    pub fn from_bytes(mut bytes: &[u8]) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "GraphPatch::GraphPatch::from_bytes";
+        let mut answer = Self::new();
+        while let Some(mutation) = read_record(&mut bytes, FUNCTION_PATH)? {
+            answer.mutations.push(mutation);
+        }
+        Ok(answer)
+    }
+}
+
+// GraphPatch::Default
+impl<EdgeIdType, VertexIdType> Default for GraphPatch<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn graph_patch() {
+        let old: graph!(A ===A==> A) = "AV I4 Weight\nAE S Label\n\nV 0 1\nV 1 2\nV 2 3\n\nA 0 1 \"x\"\nA 0 1 \"y\"\nE 1 2 \"z\"\n".parse().unwrap();
+        let new: graph!(A ===A==> A) = "AV I4 Weight\nAE S Label\n\nV 0 1\nV 1 5\nV 3 4\n\nA 0 1 \"x\"\nA 1 0 \"y\"\nE 1 3 \"z\"\n".parse().unwrap();
+        assert!(GraphPatch::diff(&old, &old).unwrap().is_empty());
+        let patch = GraphPatch::diff(&old, &new).unwrap();
+        let bytes = patch.into_bytes();
+        let mut g = old.clone();
+        GraphPatch::from_bytes(&bytes).unwrap().apply(&mut g).unwrap();
+        assert!(GraphPatch::diff(&g, &new).unwrap().is_empty());
+        assert!(!g.contains_v(&2) && g.contains_e(&1, &3, &0).is_some());
+        assert_eq!(g.v_attrs(&1).unwrap().get(&"Weight".to_string()).unwrap().downcast::<i32>(), Some(&5));
+        // Streamed patches are applied record by record
+        let mut g = old.clone();
+        assert_eq!(GraphPatch::apply_stream(&bytes[..], &mut g).unwrap(), patch.len());
+        assert!(GraphPatch::diff(&g, &new).unwrap().is_empty());
+        // Lost attributes are handled by replacement
+        let new: graph!(A ===A==> A) = "AE S Label\n\nV 0\nV 1\nV 2\n\nA 0 1 \"x\"\nA 0 1 \"y\"\nE 1 2 \"z\"\n".parse().unwrap();
+        let mut g = old.clone();
+        GraphPatch::diff(&old, &new).unwrap().apply(&mut g).unwrap();
+        assert!(GraphPatch::diff(&g, &new).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,751 @@
+//! # Protobuf module
+//! 
+//! ## Description
+//! This module contains [`ProtobufReader`] and [`ProtobufWriter`] that read  and  write
+//! graphs as Protocol Buffers messages. The schema of the messages is shipped with  the
+//! crate in `proto/crabnets.proto`, so graphs can be exchanged with services written in
+//! any language supported by Protocol Buffers. The wire  format  is  implemented  right
+//! here, no protobuf runtime is required.
+//! 
+//! Just like in GNBS, vertex IDs are stored in their textual form and edge  IDs  aren't
+//! stored at all. Unknown fields are skipped while reading,  which  keeps  old  readers
+//! compatible with the future versions of the schema.
+//! 
+//! [`ProtobufWriter`] ends every message with  a  footer  that  holds  the  numbers  of
+//! vertices and edges along with the checksums  of  both  sections.  [`ProtobufReader`]
+//! verifies the footer before building the graph,  and  [`ProtobufReader::verify_only`]
+//! checks archives without building graphs at all.
+//! 
+//! Messages are decoded as a  whole,  so  with  `mmap`  feature  on  Unix-like  systems
+//! [`IO::from_file`][from_file] maps Protobuf files into memory and decodes them  right
+//! from the mapped region without an intermediate buffer.
+//! 
+//! [from_file]: super::IO::from_file
+use std::{io::{BufReader, BufWriter, Read, Write}, str::FromStr};
+use itertools::Itertools;
+use crate::{
+    attributes::StaticDispatchAttributeValue, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id
+};
+use super::{AttributeProjection, AttributeCollectionIO, ExportFilter, AttributeToken, Reader, Writer};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * WIRE FORMAT                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+pub(super) enum WireValue<'a> {
+    Varint(u64),
+    Fixed64(u64),
+    LengthDelimited(&'a [u8]),
+    Fixed32(u32),
+}
+
+
+
+pub(super) fn decode_error(function_path: &str, message: &str) -> CrabNetsError {
+    CrabNetsError::new(function_path, ErrorKind::CorruptedData { reason: format!("Malformed protobuf message: {}.", message) })
+}
+
+fn read_varint(bytes: &[u8], position: &mut usize) -> Option<u64> {
+    let mut answer = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*position)?;
+        *position += 1;
+        answer |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Some(answer);
+        }
+    }
+    None
+}
+
+// Split the message into the numbers and the values of its fields
+pub(super) fn read_fields<'a>(bytes: &'a [u8], function_path: &str) -> CrabNetsResult<Vec<(u64, WireValue<'a>)>> {
+    let mut answer = Vec::new();
+    let mut position = 0;
+    while position < bytes.len() {
+        let key = read_varint(bytes, &mut position).ok_or_else(|| decode_error(function_path, "truncated field key"))?;
+        let value = match key & 7 {
+            0 => WireValue::Varint(read_varint(bytes, &mut position).ok_or_else(|| decode_error(function_path, "truncated varint"))?),
+            1 => {
+                let value = bytes.get(position..position + 8).ok_or_else(|| decode_error(function_path, "truncated 64-bit value"))?;
+                position += 8;
+                WireValue::Fixed64(u64::from_le_bytes(value.try_into().unwrap()))
+            },
+            2 => {
+                let length = read_varint(bytes, &mut position).ok_or_else(|| decode_error(function_path, "truncated length"))?;
+                let value = usize::try_from(length).ok().and_then(|x| bytes.get(position..position.checked_add(x)?)).ok_or_else(|| decode_error(function_path, "truncated length-delimited value"))?;
+                position += value.len();
+                WireValue::LengthDelimited(value)
+            },
+            5 => {
+                let value = bytes.get(position..position + 4).ok_or_else(|| decode_error(function_path, "truncated 32-bit value"))?;
+                position += 4;
+                WireValue::Fixed32(u32::from_le_bytes(value.try_into().unwrap()))
+            },
+            _ => return Err(decode_error(function_path, "unsupported wire type")),
+        };
+        answer.push((key >> 3, value));
+    }
+    Ok(answer)
+}
+
+pub(super) fn read_string<'a>(value: &WireValue<'a>, function_path: &str) -> CrabNetsResult<&'a str> {
+    match value {
+        WireValue::LengthDelimited(bytes) => std::str::from_utf8(bytes).map_err(|_| decode_error(function_path, "string isn't valid UTF-8")),
+        _ => Err(decode_error(function_path, "string expected")),
+    }
+}
+
+pub(super) fn read_message<'a>(value: &WireValue<'a>, function_path: &str) -> CrabNetsResult<&'a [u8]> {
+    match value {
+        WireValue::LengthDelimited(bytes) => Ok(bytes),
+        _ => Err(decode_error(function_path, "embedded message expected")),
+    }
+}
+
+// Read repeated varints that may be either packed or not
+fn read_varints(value: &WireValue, answer: &mut Vec<u64>, function_path: &str) -> CrabNetsResult<()> {
+    match value {
+        WireValue::Varint(x) => answer.push(*x),
+        WireValue::LengthDelimited(bytes) => {
+            let mut position = 0;
+            while position < bytes.len() {
+                answer.push(read_varint(bytes, &mut position).ok_or_else(|| decode_error(function_path, "truncated packed varint"))?);
+            }
+        },
+        _ => return Err(decode_error(function_path, "varint expected")),
+    }
+    Ok(())
+}
+
+// Read repeated 32-bit or 64-bit values that may be either packed or not
+fn read_fixed<const SIZE: usize>(value: &WireValue, answer: &mut Vec<[u8; SIZE]>, function_path: &str) -> CrabNetsResult<()> {
+    match (value, SIZE) {
+        (WireValue::Fixed32(x), 4) => answer.push(x.to_le_bytes()[..].try_into().unwrap()),
+        (WireValue::Fixed64(x), 8) => answer.push(x.to_le_bytes()[..].try_into().unwrap()),
+        (WireValue::LengthDelimited(bytes), _) if bytes.len() % SIZE == 0 => answer.extend(bytes.chunks_exact(SIZE).map(|x| <[u8; SIZE]>::try_from(x).unwrap())),
+        _ => return Err(decode_error(function_path, "fixed-size value expected")),
+    }
+    Ok(())
+}
+
+
+
+pub(super) fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+pub(super) fn write_key(output: &mut Vec<u8>, field_number: u64, wire_type: u64) {
+    write_varint(output, (field_number << 3) | wire_type);
+}
+
+pub(super) fn write_length_delimited(output: &mut Vec<u8>, field_number: u64, bytes: &[u8]) {
+    write_key(output, field_number, 2);
+    write_varint(output, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+// Singular fields with default values aren't written, just like in proto3
+pub(super) fn write_singular_varint(output: &mut Vec<u8>, field_number: u64, value: u64) {
+    if value != 0 {
+        write_key(output, field_number, 0);
+        write_varint(output, value);
+    }
+}
+
+pub(super) fn write_singular_string(output: &mut Vec<u8>, field_number: u64, value: &str) {
+    if !value.is_empty() {
+        write_length_delimited(output, field_number, value.as_bytes());
+    }
+}
+
+fn write_packed<T, F>(output: &mut Vec<u8>, field_number: u64, values: &[T], mut write_value: F)
+where
+    F: FnMut(&mut Vec<u8>, &T),
+{
+    if !values.is_empty() {
+        let mut packed = Vec::new();
+        for value in values {
+            write_value(&mut packed, value);
+        }
+        write_length_delimited(output, field_number, &packed);
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ATTRIBUTE VALUES                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Contents of the AttributeValue message
+#[derive(Default)]
+struct AttributeValueMessage {
+    bool_values: Vec<bool>,
+    container: u64,
+    element_type: u64,
+    float32_values: Vec<f32>,
+    float64_values: Vec<f64>,
+    signed_values: Vec<i64>,
+    str_values: Vec<String>,
+    unsigned_values: Vec<u64>,
+}
+
+const CONTAINER_SCALAR: u64 = 0;
+const CONTAINER_LIST: u64 = 1;
+const CONTAINER_SET: u64 = 2;
+
+// Pairs of (element type, variants, sequence of the message, conversions) for all integer,
+// boolean and string types
+macro_rules! for_each_non_float_type {
+    ($macro_name:ident!($($arguments:tt)*)) => {
+        $macro_name!($($arguments)*
+            0, Int8, VecInt8, SetInt8, signed_values, |x: &i8| *x as i64, |x: &i64| i8::try_from(*x).ok();
+            1, Int16, VecInt16, SetInt16, signed_values, |x: &i16| *x as i64, |x: &i64| i16::try_from(*x).ok();
+            2, Int32, VecInt32, SetInt32, signed_values, |x: &i32| *x as i64, |x: &i64| i32::try_from(*x).ok();
+            3, Int64, VecInt64, SetInt64, signed_values, |x: &i64| *x, |x: &i64| Some(*x);
+            4, UInt8, VecUInt8, SetUInt8, unsigned_values, |x: &u8| *x as u64, |x: &u64| u8::try_from(*x).ok();
+            5, UInt16, VecUInt16, SetUInt16, unsigned_values, |x: &u16| *x as u64, |x: &u64| u16::try_from(*x).ok();
+            6, UInt32, VecUInt32, SetUInt32, unsigned_values, |x: &u32| *x as u64, |x: &u64| u32::try_from(*x).ok();
+            7, UInt64, VecUInt64, SetUInt64, unsigned_values, |x: &u64| *x, |x: &u64| Some(*x);
+            10, Bool, VecBool, SetBool, bool_values, |x: &bool| *x, |x: &bool| Some(*x);
+            11, Str, VecStr, SetStr, str_values, |x: &String| x.clone(), |x: &String| Some(x.clone())
+        )
+    };
+}
+
+fn split_value(value: &StaticDispatchAttributeValue) -> AttributeValueMessage {
+    macro_rules! split_variants {
+        ($($element_type:literal, $scalar:ident, $vec:ident, $set:ident, $values:ident, $into:expr, $from:expr);+) => {
+            match value {
+                $(
+                    StaticDispatchAttributeValue::$scalar(x) => AttributeValueMessage { element_type: $element_type, container: CONTAINER_SCALAR, $values: vec![$into(x)], ..Default::default() },
+                    StaticDispatchAttributeValue::$vec(x) => AttributeValueMessage { element_type: $element_type, container: CONTAINER_LIST, $values: x.iter().map($into).collect(), ..Default::default() },
+                    StaticDispatchAttributeValue::$set(x) => AttributeValueMessage { element_type: $element_type, container: CONTAINER_SET, $values: x.iter().sorted().map($into).collect(), ..Default::default() },
+                )+
+                StaticDispatchAttributeValue::Float32(x) => AttributeValueMessage { element_type: 8, container: CONTAINER_SCALAR, float32_values: vec![*x], ..Default::default() },
+                StaticDispatchAttributeValue::VecFloat32(x) => AttributeValueMessage { element_type: 8, container: CONTAINER_LIST, float32_values: x.clone(), ..Default::default() },
+                StaticDispatchAttributeValue::Float64(x) => AttributeValueMessage { element_type: 9, container: CONTAINER_SCALAR, float64_values: vec![*x], ..Default::default() },
+                StaticDispatchAttributeValue::VecFloat64(x) => AttributeValueMessage { element_type: 9, container: CONTAINER_LIST, float64_values: x.clone(), ..Default::default() },
+            }
+        };
+    }
+    for_each_non_float_type!(split_variants!())
+}
+
+fn join_value(message: AttributeValueMessage, function_path: &str) -> CrabNetsResult<StaticDispatchAttributeValue> {
+    fn single<T>(values: Vec<T>, function_path: &str) -> CrabNetsResult<T> {
+        match <[T; 1]>::try_from(values) {
+            Ok([value]) => Ok(value),
+            Err(_) => Err(decode_error(function_path, "scalar attribute value must have exactly 1 element")),
+        }
+    }
+    macro_rules! join_variants {
+        ($($element_type:literal, $scalar:ident, $vec:ident, $set:ident, $values:ident, $into:expr, $from:expr);+) => {
+            match (message.element_type, message.container) {
+                $(
+                    ($element_type, container) => {
+                        let values = message.$values.iter().map($from).collect::<Option<Vec<_>>>().ok_or_else(|| decode_error(function_path, "attribute value is out of range of its type"))?;
+                        match container {
+                            CONTAINER_SCALAR => Ok(StaticDispatchAttributeValue::$scalar(single(values, function_path)?)),
+                            CONTAINER_LIST => Ok(StaticDispatchAttributeValue::$vec(values)),
+                            CONTAINER_SET => Ok(StaticDispatchAttributeValue::$set(values.into_iter().collect())),
+                            _ => Err(decode_error(function_path, "unknown container")),
+                        }
+                    },
+                )+
+                (8, CONTAINER_SCALAR) => Ok(StaticDispatchAttributeValue::Float32(single(message.float32_values, function_path)?)),
+                (8, CONTAINER_LIST) => Ok(StaticDispatchAttributeValue::VecFloat32(message.float32_values)),
+                (9, CONTAINER_SCALAR) => Ok(StaticDispatchAttributeValue::Float64(single(message.float64_values, function_path)?)),
+                (9, CONTAINER_LIST) => Ok(StaticDispatchAttributeValue::VecFloat64(message.float64_values)),
+                (8 | 9, _) => Err(decode_error(function_path, "floating-point attribute values can only be scalars or lists")),
+                _ => Err(decode_error(function_path, "unknown element type")),
+            }
+        };
+    }
+    for_each_non_float_type!(join_variants!())
+}
+
+pub(super) fn encode_value(value: &StaticDispatchAttributeValue) -> Vec<u8> {
+    let message = split_value(value);
+    let mut output = Vec::new();
+    write_singular_varint(&mut output, 1, message.element_type);
+    write_singular_varint(&mut output, 2, message.container);
+    write_packed(&mut output, 3, &message.signed_values, |output, x| write_varint(output, ((x << 1) ^ (x >> 63)) as u64));
+    write_packed(&mut output, 4, &message.unsigned_values, |output, x| write_varint(output, *x));
+    write_packed(&mut output, 5, &message.float32_values, |output, x| output.extend_from_slice(&x.to_le_bytes()));
+    write_packed(&mut output, 6, &message.float64_values, |output, x| output.extend_from_slice(&x.to_le_bytes()));
+    write_packed(&mut output, 7, &message.bool_values, |output, x| write_varint(output, *x as u64));
+    for value in message.str_values.iter() {
+        write_length_delimited(&mut output, 8, value.as_bytes());
+    }
+    output
+}
+
+fn decode_value(bytes: &[u8], function_path: &str) -> CrabNetsResult<StaticDispatchAttributeValue> {
+    let mut message = AttributeValueMessage::default();
+    let (mut varints, mut fixed32, mut fixed64) = (Vec::new(), Vec::new(), Vec::new());
+    for (field_number, value) in read_fields(bytes, function_path)? {
+        match (field_number, &value) {
+            (1, WireValue::Varint(x)) => message.element_type = *x,
+            (2, WireValue::Varint(x)) => message.container = *x,
+            (3, _) => {
+                varints.clear();
+                read_varints(&value, &mut varints, function_path)?;
+                message.signed_values.extend(varints.iter().map(|x| ((x >> 1) as i64) ^ -((x & 1) as i64)));
+            },
+            (4, _) => read_varints(&value, &mut message.unsigned_values, function_path)?,
+            (5, _) => {
+                fixed32.clear();
+                read_fixed(&value, &mut fixed32, function_path)?;
+                message.float32_values.extend(fixed32.iter().map(|x| f32::from_le_bytes(*x)));
+            },
+            (6, _) => {
+                fixed64.clear();
+                read_fixed(&value, &mut fixed64, function_path)?;
+                message.float64_values.extend(fixed64.iter().map(|x| f64::from_le_bytes(*x)));
+            },
+            (7, _) => {
+                varints.clear();
+                read_varints(&value, &mut varints, function_path)?;
+                message.bool_values.extend(varints.iter().map(|x| *x != 0));
+            },
+            (8, _) => message.str_values.push(read_string(&value, function_path)?.to_string()),
+            (1..=8, _) => return Err(decode_error(function_path, "unexpected wire type of an attribute value field")),
+            _ => (),
+        }
+    }
+    join_value(message, function_path)
+}
+
+
+
+fn encode_attributes<AttributeCollectionType>(output: &mut Vec<u8>, field_number: u64, attribute_collection: &AttributeCollectionType)
+where
+    AttributeCollectionType: AttributeCollectionIO,
+{
+    for token in attribute_collection.io_iter_contents().sorted_by(|x, y| x.name.cmp(y.name)) {
+        let mut attribute = Vec::new();
+        write_singular_string(&mut attribute, 1, token.name);
+        write_length_delimited(&mut attribute, 2, &encode_value(&token.value));
+        write_length_delimited(output, field_number, &attribute);
+    }
+}
+
+// Values of the attributes that the projection skips aren't decoded at all
+pub(super) fn decode_attribute<'a>(bytes: &'a [u8], projection: &AttributeProjection<'_>, function_path: &str) -> CrabNetsResult<Option<AttributeToken<'a>>> {
+    let mut name = "";
+    let mut value = None;
+    for (field_number, field_value) in read_fields(bytes, function_path)? {
+        match field_number {
+            1 => name = read_string(&field_value, function_path)?,
+            2 => value = Some(read_message(&field_value, function_path)?),
+            _ => (),
+        }
+    }
+    match value {
+        Some(_) if !projection.keeps(name) => Ok(None),
+        Some(value) => Ok(projection.project(AttributeToken { name, value: decode_value(value, function_path)? })),
+        None => Err(decode_error(function_path, "attribute has no value")),
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * INTEGRITY                                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut value = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 1 == 1 { (value >> 1) ^ 0xedb88320 } else { value >> 1 };
+            bit += 1;
+        }
+        table[i] = value;
+        i += 1;
+    }
+    table
+};
+
+
+
+// Running CRC-32 (IEEE) of a sequence of byte slices
+#[derive(Clone, Copy)]
+pub(super) struct Checksum(u32);
+
+impl Checksum {
+    pub(super) fn new() -> Self {
+        Checksum(0xffffffff)
+    }
+
+    pub(super) fn update(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = CRC32_TABLE[((self.0 ^ *byte as u32) & 0xff) as usize] ^ (self.0 >> 8);
+        }
+    }
+
+    pub(super) fn value(&self) -> u32 {
+        !self.0
+    }
+}
+
+
+
+struct Footer {
+    edge_count: u64,
+    edges_checksum: u32,
+    vertex_count: u64,
+    vertices_checksum: u32,
+}
+
+impl Footer {
+    // Compute the footer of the vertices and edges of the message
+    fn compute(fields: &[(u64, WireValue)], function_path: &str) -> CrabNetsResult<Self> {
+        let mut answer = Footer { edge_count: 0, edges_checksum: 0, vertex_count: 0, vertices_checksum: 0 };
+        let (mut vertices_checksum, mut edges_checksum) = (Checksum::new(), Checksum::new());
+        for (field_number, field_value) in fields {
+            match field_number {
+                1 => {
+                    answer.vertex_count += 1;
+                    vertices_checksum.update(read_message(field_value, function_path)?);
+                },
+                2 => {
+                    answer.edge_count += 1;
+                    edges_checksum.update(read_message(field_value, function_path)?);
+                },
+                _ => (),
+            }
+        }
+        answer.vertices_checksum = vertices_checksum.value();
+        answer.edges_checksum = edges_checksum.value();
+        Ok(answer)
+    }
+
+    fn decode(bytes: &[u8], function_path: &str) -> CrabNetsResult<Self> {
+        let mut answer = Footer { edge_count: 0, edges_checksum: 0, vertex_count: 0, vertices_checksum: 0 };
+        for (field_number, field_value) in read_fields(bytes, function_path)? {
+            match (field_number, field_value) {
+                (1, WireValue::Varint(x)) => answer.vertex_count = x,
+                (2, WireValue::Varint(x)) => answer.edge_count = x,
+                (3, WireValue::Fixed32(x)) => answer.vertices_checksum = x,
+                (4, WireValue::Fixed32(x)) => answer.edges_checksum = x,
+                (1..=4, _) => return Err(decode_error(function_path, "footer field of wrong type")),
+                _ => (),
+            }
+        }
+        Ok(answer)
+    }
+
+    fn encode(&self, output: &mut Vec<u8>) {
+        let mut footer = Vec::new();
+        write_singular_varint(&mut footer, 1, self.vertex_count);
+        write_singular_varint(&mut footer, 2, self.edge_count);
+        for (field_number, checksum) in [(3, self.vertices_checksum), (4, self.edges_checksum)] {
+            write_key(&mut footer, field_number, 5);
+            footer.extend_from_slice(&checksum.to_le_bytes());
+        }
+        write_length_delimited(output, 3, &footer);
+    }
+}
+
+
+
+// Compare the stored footer (the last one if there are several) with the contents of the
+// message, `Ok(false)` means that there is no footer to compare with
+fn verify_footer(fields: &[(u64, WireValue)], function_path: &str) -> CrabNetsResult<bool> {
+    let stored = match fields.iter().rev().find(|x| x.0 == 3) {
+        Some((_, value)) => Footer::decode(read_message(value, function_path)?, function_path)?,
+        None => return Ok(false),
+    };
+    let actual = Footer::compute(fields, function_path)?;
+    let integrity_error = |message: &str| CrabNetsError::new(function_path, ErrorKind::CorruptedData { reason: format!("Integrity check of the protobuf message failed: {}.", message) });
+    if (stored.vertex_count, stored.edge_count) != (actual.vertex_count, actual.edge_count) {
+        return Err(integrity_error(&format!("the footer declares {} vertices and {} edges, but {} vertices and {} edges were found", stored.vertex_count, stored.edge_count, actual.vertex_count, actual.edge_count)));
+    }
+    if stored.vertices_checksum != actual.vertices_checksum {
+        return Err(integrity_error("checksum of the vertices doesn't match"));
+    }
+    if stored.edges_checksum != actual.edges_checksum {
+        return Err(integrity_error("checksum of the edges doesn't match"));
+    }
+    Ok(true)
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * READER/WRITER                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Build a graph out of the message stored in the given bytes
+pub(super) fn decode_graph<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(bytes: &[u8], projection: &AttributeProjection<'_>, function_path: &str) -> CrabNetsResult<G>
+where
+    G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: FromStr + Id,
+{
+    let parse_id = |id: &str| VertexIdType::from_str(id).map_err(|_| decode_error(function_path, &format!("'{}' isn't a valid vertex ID", id)));
+    let mut new_graph = G::default();
+    let fields = read_fields(bytes, function_path)?;
+    verify_footer(&fields, function_path)?;
+    // Vertices must be added before the edges regardless of the order of fields
+    for (_, value) in fields.iter().filter(|x| x.0 == 1) {
+        let mut id = "";
+        let mut attribute_tokens = Vec::new();
+        for (field_number, field_value) in read_fields(read_message(value, function_path)?, function_path)? {
+            match field_number {
+                1 => id = read_string(&field_value, function_path)?,
+                2 => attribute_tokens.extend(decode_attribute(read_message(&field_value, function_path)?, projection, function_path)?),
+                _ => (),
+            }
+        }
+        let id = parse_id(id)?;
+        new_graph.add_v(Some(id.clone()));
+        for attribute_token in attribute_tokens {
+            new_graph.v_attrs_mut(&id).unwrap().io_reader_callback::<EdgeIdType, VertexIdType>(attribute_token);
+        }
+    }
+    for (_, value) in fields.iter().filter(|x| x.0 == 2) {
+        let (mut id1, mut id2, mut directed) = ("", "", false);
+        let mut attribute_tokens = Vec::new();
+        for (field_number, field_value) in read_fields(read_message(value, function_path)?, function_path)? {
+            match (field_number, &field_value) {
+                (1, _) => id1 = read_string(&field_value, function_path)?,
+                (2, _) => id2 = read_string(&field_value, function_path)?,
+                (3, WireValue::Varint(x)) => directed = *x != 0,
+                (3, _) => return Err(decode_error(function_path, "varint expected")),
+                (4, _) => attribute_tokens.extend(decode_attribute(read_message(&field_value, function_path)?, projection, function_path)?),
+                _ => (),
+            }
+        }
+        let (id1, id2) = (parse_id(id1)?, parse_id(id2)?);
+        let edge_id = new_graph.add_e(&id1, &id2, directed, None)?;
+        for attribute_token in attribute_tokens {
+            new_graph.e_attrs_mut(&id1, &id2, &edge_id).unwrap().io_reader_callback::<EdgeIdType, VertexIdType>(attribute_token);
+        }
+    }
+    Ok(new_graph)
+}
+
+
+
+pub struct ProtobufReader;
+
+// ProtobufReader::ProtobufReader
+impl ProtobufReader {
+    /// # Verify message
+    /// 
+    /// ## Description
+    /// Check the footer of the message against its contents without building the graph.
+    /// Only the outer structure of the message is decoded, so this is much faster  than
+    /// reading.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `buffer_reader` : `BufReader<R>` - the source.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<(usize, usize)>`   -    `Ok((vertex_count, edge_count))`    is
+    /// returned if the message has a footer that matches its  contents,  `vertex_count`
+    /// and `edge_count` in this case are the numbers  of  vertices  and  edges  in  the
+    /// message; `Err(_)` is returned otherwise.
+    pub fn verify_only<R>(&self, mut buffer_reader: BufReader<R>) -> CrabNetsResult<(usize, usize)>
+    where
+        R: Read,
+    {
+        const FUNCTION_PATH: &str = "ProtobufReader::ProtobufReader::verify_only";
+        let mut bytes = Vec::new();
+        if let Err(error) = buffer_reader.read_to_end(&mut bytes) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Couldn't read the input: {}", error))));
+        }
+        let fields = read_fields(&bytes, FUNCTION_PATH)?;
+        if !verify_footer(&fields, FUNCTION_PATH)? {
+            return Err(decode_error(FUNCTION_PATH, "message has no footer"));
+        }
+        let footer = Footer::compute(&fields, FUNCTION_PATH)?;
+        Ok((footer.vertex_count as usize, footer.edge_count as usize))
+    }
+}
+
+// ProtobufReader::Reader
+impl Reader for ProtobufReader {
+    fn read_projected_graph<G, R, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, mut buffer_reader: BufReader<R>, projection: &AttributeProjection<'_>) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        R: Read,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "ProtobufReader::Reader::read_projected_graph";
+        let mut bytes = Vec::new();
+        if let Err(error) = buffer_reader.read_to_end(&mut bytes) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Couldn't read the input: {}", error))));
+        }
+        decode_graph(&bytes, projection, FUNCTION_PATH)
+    }
+}
+
+
+
+pub struct ProtobufWriter;
+
+// ProtobufWriter::Writer
+impl Writer for ProtobufWriter {
+    fn write_filtered_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>, filter: &ExportFilter<'_, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ProtobufWriter::Writer::write_filtered_graph";
+        let mut output = Vec::new();
+        let mut footer = Footer { edge_count: 0, edges_checksum: 0, vertex_count: 0, vertices_checksum: 0 };
+        let (mut vertices_checksum, mut edges_checksum) = (Checksum::new(), Checksum::new());
+        for id in filter.iter_v(graph).sorted() {
+            let mut vertex = Vec::new();
+            write_singular_string(&mut vertex, 1, &id.to_string());
+            encode_attributes(&mut vertex, 2, graph.v_attrs(&id)?);
+            write_length_delimited(&mut output, 1, &vertex);
+            footer.vertex_count += 1;
+            vertices_checksum.update(&vertex);
+        }
+        for edge in filter.iter_e(graph).sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id))) {
+            let (id1, id2) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1),
+                _ => (&edge.id1, &edge.id2),
+            };
+            let mut message = Vec::new();
+            write_singular_string(&mut message, 1, &id1.to_string());
+            write_singular_string(&mut message, 2, &id2.to_string());
+            write_singular_varint(&mut message, 3, (edge.direction != EdgeDirection::Undirected) as u64);
+            encode_attributes(&mut message, 4, graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?);
+            write_length_delimited(&mut output, 2, &message);
+            footer.edge_count += 1;
+            edges_checksum.update(&message);
+        }
+        footer.vertices_checksum = vertices_checksum.value();
+        footer.edges_checksum = edges_checksum.value();
+        footer.encode(&mut output);
+        buffer_writer.write_all(&output).and_then(|_| buffer_writer.flush()).map_err(|error| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to write the protobuf message: {}", error))))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use crate::*;
+    use crate::io::{SupportedFormats, IO};
+    use super::*;
+
+    #[test]
+    fn protobuf_round_trip() {
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&0).unwrap().insert("Name".to_string(), Box::new("zero".to_string()));
+        g.v_attrs_mut(&1).unwrap().insert("Weights".to_string(), Box::new(vec![-1i16, 300]));
+        g.v_attrs_mut(&2).unwrap().insert("Tags".to_string(), Box::new([1u64, 1 << 40].into_iter().collect::<HashSet<_>>()));
+        let edge_id = g.add_e(&2, &0, true, None).unwrap();
+        g.e_attrs_mut(&2, &0, &edge_id).unwrap().insert("Length".to_string(), Box::new(0.5f32));
+        g.add_e(&0, &1, false, None).unwrap();
+        g.add_e(&0, &1, false, None).unwrap();
+        let bytes = g.to_bytes(SupportedFormats::Protobuf).unwrap();
+        let h: graph!(A ===A==> A) = IO::from_bytes(&bytes, SupportedFormats::Protobuf).unwrap();
+        assert_eq!((h.count_v(), h.count_e()), (3, 3));
+        assert_eq!(h.v_attrs(&0).unwrap().get(&"Name".to_string()).unwrap().downcast::<String>().unwrap(), "zero");
+        assert_eq!(h.v_attrs(&1).unwrap().get(&"Weights".to_string()).unwrap().downcast::<Vec<i16>>(), Some(&vec![-1, 300]));
+        assert_eq!(h.v_attrs(&2).unwrap().get(&"Tags".to_string()).unwrap().downcast::<HashSet<u64>>().unwrap().len(), 2);
+        assert_eq!(h.e_attrs(&2, &0, &0).unwrap().get(&"Length".to_string()).unwrap().downcast::<f32>(), Some(&0.5));
+        assert!(h.contains_e(&2, &0, &0).is_some_and(|x| x == EdgeDirection::Directed1to2));
+        assert_eq!(h.to_bytes(SupportedFormats::Protobuf).unwrap(), bytes);
+        // A graph with vertex "1" and an unknown field 15 written by a newer schema
+        let h: graph!(X ---X--- X) = IO::from_bytes(&[0x0a, 0x03, 0x0a, 0x01, b'1', 0x78, 0x05], SupportedFormats::Protobuf).unwrap();
+        assert!(h.contains_v(&1));
+        assert!(<graph!(X ---X--- X)>::from_bytes(&bytes[..bytes.len() - 1], SupportedFormats::Protobuf).is_err_and(|x| matches!(x.kind(), ErrorKind::CorruptedData { .. })));
+        assert!(<graph!(X ---X--- X)>::from_bytes(&[0x0a, 0x03, 0x0a, 0x01, b'x'], SupportedFormats::Protobuf).is_err());
+    }
+
+    #[test]
+    fn protobuf_file() {
+        let mut g: graph!(A ---X--> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&2).unwrap().insert("Name".to_string(), Box::new("two".to_string()));
+        g.add_e(&0, &1, true, None).unwrap();
+        let file_name = std::env::temp_dir().join(format!("crabnets_protobuf_{}.pb", std::process::id())).to_string_lossy().into_owned();
+        g.into_file(&file_name).unwrap();
+        let h: graph!(A ---X--> A) = Graph::from_file(&file_name).unwrap();
+        assert_eq!(h.to_bytes(SupportedFormats::Protobuf).unwrap(), g.to_bytes(SupportedFormats::Protobuf).unwrap());
+        std::fs::write(&file_name, "").unwrap();
+        let h: graph!(A ---X--> A) = Graph::from_file(&file_name).unwrap();
+        assert_eq!(h.count_v(), 0);
+        std::fs::remove_file(&file_name).unwrap();
+        assert!(<graph!(A ---X--> A)>::from_file(&file_name).is_err_and(|x| matches!(x.kind(), ErrorKind::Io(_))));
+    }
+
+    #[test]
+    fn protobuf_integrity() {
+        let mut g: graph!(A ---X--> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.v_attrs_mut(&2).unwrap().insert("Name".to_string(), Box::new("two".to_string()));
+        g.add_e(&0, &1, true, None).unwrap();
+        let bytes = g.to_bytes(SupportedFormats::Protobuf).unwrap();
+        assert_eq!(ProtobufReader.verify_only(BufReader::new(&bytes[..])).unwrap(), (3, 1));
+        // The check value of CRC-32 (IEEE)
+        let mut checksum = Checksum::new();
+        checksum.update(b"123456789");
+        assert_eq!(checksum.value(), 0xcbf43926);
+        // Corrupt a character of the name of vertex 2 without breaking the structure
+        let position = bytes.windows(3).position(|x| x == b"two").unwrap();
+        let mut corrupted = bytes.clone();
+        corrupted[position] = b'T';
+        assert!(ProtobufReader.verify_only(BufReader::new(&corrupted[..])).is_err());
+        assert!(<graph!(A ---X--> A)>::from_bytes(&corrupted, SupportedFormats::Protobuf).is_err_and(|x| matches!(x.kind(), ErrorKind::CorruptedData { .. })));
+        // Messages without footers can still be read, but can't be verified
+        let h: graph!(X ---X--- X) = IO::from_bytes(&[0x0a, 0x03, 0x0a, 0x01, b'1'], SupportedFormats::Protobuf).unwrap();
+        assert!(h.contains_v(&1));
+        assert!(ProtobufReader.verify_only(BufReader::new(&[0x0a, 0x03, 0x0a, 0x01, b'1'][..])).is_err());
+    }
+}
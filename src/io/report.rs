@@ -0,0 +1,337 @@
+//! # Report module
+//! 
+//! ## Description
+//! This module contains [`ReportWriter`] that writes human-readable analytical  reports
+//! of graphs in Markdown or plain text, e.g. to accompany published datasets.
+#[cfg(feature = "std")]
+use std::fs::File;
+use std::{collections::BTreeMap, io::{BufWriter, Write}};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult}, union_find::UnionFind, BasicImmutableGraph, EdgeDirection, Id};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Format of report
+/// 
+/// ## Variants
+/// * `Markdown` - sections are Markdown headings, and tables are Markdown tables.
+/// * `PlainText` - sections are underlined titles, and tables are aligned columns.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    PlainText,
+}
+
+
+
+fn io_error(function_path: &str, error: std::io::Error) -> CrabNetsError {
+    CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to write the report: {}", error)))
+}
+
+fn write_section(output: &mut String, format: ReportFormat, title: &str) {
+    match format {
+        ReportFormat::Markdown => *output += &format!("\n## {}\n\n", title),
+        ReportFormat::PlainText => *output += &format!("\n{}\n{}\n\n", title, "-".repeat(title.chars().count())),
+    }
+}
+
+// The first column is aligned to the left, all others contain numbers and are aligned
+// to the right
+fn write_table(output: &mut String, format: ReportFormat, header: &[&str], rows: &[Vec<String>]) {
+    match format {
+        ReportFormat::Markdown => {
+            *output += &format!("| {} |\n", header.join(" | "));
+            *output += &format!("|{}|\n", (0..header.len()).map(|i| if i == 0 { ":---" } else { "---:" }).join("|"));
+            for row in rows {
+                *output += &format!("| {} |\n", row.join(" | "));
+            }
+        },
+        ReportFormat::PlainText => {
+            let widths: Vec<usize> = (0..header.len()).map(|i| rows.iter().map(|row| row[i].chars().count()).chain([header[i].chars().count()]).max().unwrap()).collect();
+            let format_row = |row: &[&str]| row.iter().zip(widths.iter()).enumerate().map(|(i, (cell, width))| if i == 0 {
+                format!("{:<width$}", cell, width = width)
+            } else {
+                format!("{:>width$}", cell, width = width)
+            }).join("  ").trim_end().to_string();
+            *output += &format!("{}\n", format_row(header));
+            *output += &format!("{}\n", widths.iter().map(|width| "-".repeat(*width)).join("  "));
+            for row in rows {
+                *output += &format!("{}\n", format_row(&row.iter().map(String::as_str).collect::<Vec<_>>()));
+            }
+        },
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * REPORT WRITER                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Report writer
+/// 
+/// ## Description
+/// Writes an analytical report of a graph that consists of the following sections:
+/// * Summary - the numbers of vertices, edges of each  kind,  self-loops  and  isolated
+/// vertices, the density and the minimum, mean and maximum degrees.
+/// * Degree distribution - the number of vertices of each degree.
+/// * Top hubs - the vertices with the largest degrees.
+/// * Components - the number of (weakly) connected components, the share of vertices in
+/// the largest one and the number of components of each size.
+/// 
+/// The density is the number of edges divided by the maximum possible number of  edges,
+/// where an undirected edge counts as 2 arcs. The report is deterministic: ties between
+/// hubs are broken by their IDs.
+pub struct ReportWriter {
+    format: ReportFormat,
+    title: String,
+    top_hubs: usize,
+}
+
+// ReportWriter::ReportWriter
+impl ReportWriter {
+    /// # Create report writer
+    /// 
+    /// ## Description
+    /// Create a writer of reports titled "Graph report" that list 10 top hubs.
+    /// 
+    /// ## Arguments
+    /// * `format` : `ReportFormat` - the format of reports.
+    /// 
+    /// ## Returns
+    /// * `ReportWriter` - the writer.
+    pub fn new(format: ReportFormat) -> Self {
+        ReportWriter { format, title: "Graph report".to_string(), top_hubs: 10 }
+    }
+
+    /// # Set title of report
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `title` : `&str` - the title, e.g. the name of the dataset.
+    /// 
+    /// ## Returns
+    /// * `ReportWriter` - the updated writer.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// # Set number of top hubs
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `count` : `usize` - the number of vertices listed in the top hubs  section,  0
+    /// omits the section.
+    /// 
+    /// ## Returns
+    /// * `ReportWriter` - the updated writer.
+    pub fn with_top_hubs(mut self, count: usize) -> Self {
+        self.top_hubs = count;
+        self
+    }
+
+    /// # Write report
+    /// 
+    /// ## Description
+    /// Analyse the graph and write the report into the given buffer.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `buffer_writer` : `&mut BufWriter<W>` - a mutable reference to the buffer  the
+    /// report is written into.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  report  was  written
+    /// successfully; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E| α(|V|)).
+    /// 
+    /// Space: O(|V|).
+    pub fn write_graph<G, W, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ReportWriter::ReportWriter::write_graph";
+        let count_v = graph.count_v();
+        let mut count_directed = 0usize;
+        let mut count_undirected = 0usize;
+        let mut count_loops = 0usize;
+        let mut components = UnionFind::new();
+        for id in graph.iter_v() {
+            components.insert(id);
+        }
+        for edge in graph.iter_e() {
+            match edge.direction {
+                EdgeDirection::Undirected => count_undirected += 1,
+                _ => count_directed += 1,
+            }
+            if edge.id1 == edge.id2 {
+                count_loops += 1;
+            }
+            components.union(&edge.id1, &edge.id2)?;
+        }
+        let mut degrees = Vec::with_capacity(count_v);
+        for id in graph.iter_v() {
+            degrees.push((graph.v_degree(&id)?, id));
+        }
+        degrees.sort_by(|x, y| y.0.cmp(&x.0).then_with(|| x.1.cmp(&y.1)));
+        let mut degree_counts = BTreeMap::new();
+        for (degree, _) in degrees.iter() {
+            *degree_counts.entry(*degree).or_insert(0usize) += 1;
+        }
+        let mut component_counts = BTreeMap::new();
+        for component in components.sets() {
+            *component_counts.entry(component.len()).or_insert(0usize) += 1;
+        }
+        let max_edges = count_v as f64 * (count_v as f64 - 1f64);
+        let density = if max_edges > 0f64 { (2 * count_undirected + count_directed) as f64 / max_edges } else { 0f64 };
+        let mean_degree = if count_v > 0 { degrees.iter().map(|x| x.0).sum::<usize>() as f64 / count_v as f64 } else { 0f64 };
+        let largest_component = component_counts.keys().next_back().copied().unwrap_or(0);
+        let mut output = match self.format {
+            ReportFormat::Markdown => format!("# {}\n", self.title),
+            ReportFormat::PlainText => format!("{}\n{}\n", self.title, "=".repeat(self.title.chars().count())),
+        };
+        write_section(&mut output, self.format, "Summary");
+        write_table(&mut output, self.format, &["Statistic", "Value"], &[
+            vec!["Vertices".to_string(), count_v.to_string()],
+            vec!["Edges".to_string(), (count_directed + count_undirected).to_string()],
+            vec!["Directed edges".to_string(), count_directed.to_string()],
+            vec!["Undirected edges".to_string(), count_undirected.to_string()],
+            vec!["Self-loops".to_string(), count_loops.to_string()],
+            vec!["Isolated vertices".to_string(), degree_counts.get(&0).unwrap_or(&0).to_string()],
+            vec!["Density".to_string(), format!("{:.4}", density)],
+            vec!["Minimum degree".to_string(), degrees.last().map_or(0, |x| x.0).to_string()],
+            vec!["Mean degree".to_string(), format!("{:.2}", mean_degree)],
+            vec!["Maximum degree".to_string(), degrees.first().map_or(0, |x| x.0).to_string()],
+        ]);
+        write_section(&mut output, self.format, "Degree distribution");
+        write_table(&mut output, self.format, &["Degree", "Vertices"], &degree_counts.iter().map(|(degree, count)| vec![degree.to_string(), count.to_string()]).collect::<Vec<_>>());
+        if self.top_hubs > 0 {
+            write_section(&mut output, self.format, "Top hubs");
+            write_table(&mut output, self.format, &["Vertex", "Degree"], &degrees.iter().take(self.top_hubs).map(|(degree, id)| vec![id.to_string(), degree.to_string()]).collect::<Vec<_>>());
+        }
+        write_section(&mut output, self.format, "Components");
+        write_table(&mut output, self.format, &["Statistic", "Value"], &[
+            vec!["Components".to_string(), component_counts.values().sum::<usize>().to_string()],
+            vec!["Largest component".to_string(), largest_component.to_string()],
+            vec!["Share of largest component".to_string(), format!("{:.4}", if count_v > 0 { largest_component as f64 / count_v as f64 } else { 0f64 })],
+        ]);
+        output += "\n";
+        write_table(&mut output, self.format, &["Size", "Components"], &component_counts.iter().rev().map(|(size, count)| vec![size.to_string(), count.to_string()]).collect::<Vec<_>>());
+        buffer_writer.write_all(output.as_bytes()).map_err(|x| io_error(FUNCTION_PATH, x))?;
+        buffer_writer.flush().map_err(|x| io_error(FUNCTION_PATH, x))
+    }
+
+    /// # Write report into file
+    /// 
+    /// ## Description
+    /// Analyse the graph and write the report into the file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  report  was  written
+    /// successfully; `Err(_)` is returned otherwise.
+    #[cfg(feature = "std")]
+    pub fn into_file<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, file_name: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ReportWriter::ReportWriter::into_file";
+        let file = match File::options().create(true).write(true).truncate(true).open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.write_graph(graph, &mut BufWriter::new(file))
+    }
+
+    /// # Write report into string
+    /// 
+    /// ## Description
+    /// Analyse the graph and return the report as a string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if the report  was  written
+    /// successfully,  `value`  in  this  case  is  the  report;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn into_string<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G) -> CrabNetsResult<String>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ReportWriter::ReportWriter::into_string";
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        self.write_graph(graph, &mut buffer_writer)?;
+        let bytes = buffer_writer.into_inner().map_err(|x| io_error(FUNCTION_PATH, x.into_error()))?;
+        // The report is built from strings, so it is always valid UTF-8
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn write_report() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &2, false, None).unwrap();
+        g.add_e(&3, &3, false, None).unwrap();
+        let report = ReportWriter::new(ReportFormat::Markdown).with_title("Toy").with_top_hubs(2).into_string(&g).unwrap();
+        assert!(report.starts_with("# Toy\n\n## Summary\n\n| Statistic | Value |\n|:---|---:|\n| Vertices | 5 |\n| Edges | 3 |\n"));
+        assert!(report.contains("| Self-loops | 1 |\n| Isolated vertices | 1 |\n| Density | 0.2500 |\n"));
+        assert!(report.contains("## Degree distribution\n\n| Degree | Vertices |\n|:---|---:|\n| 0 | 1 |\n| 1 | 3 |\n| 2 | 1 |\n"));
+        assert!(report.contains("## Top hubs\n\n| Vertex | Degree |\n|:---|---:|\n| 0 | 2 |\n| 1 | 1 |\n\n"));
+        assert!(report.ends_with("| Share of largest component | 0.6000 |\n\n| Size | Components |\n|:---|---:|\n| 3 | 1 |\n| 1 | 2 |\n"));
+        let report = ReportWriter::new(ReportFormat::PlainText).with_top_hubs(0).into_string(&g).unwrap();
+        assert!(report.starts_with("Graph report\n============\n\nSummary\n-------\n\nStatistic           Value\n-----------------  ------\nVertices                5\n"));
+        assert!(report.contains("Degree  Vertices\n------  --------\n0              1\n"));
+        assert!(!report.contains("Top hubs"));
+        let g: graph!(X ---X--- X) = Graph::new();
+        let report = ReportWriter::new(ReportFormat::PlainText).into_string(&g).unwrap();
+        assert!(report.lines().any(|line| line.split_whitespace().eq(["Density", "0.0000"])));
+    }
+}
@@ -0,0 +1,299 @@
+//! # Shards module
+//! 
+//! ## Description
+//! This module contains [`ShardedWriter`] and [`ShardedReader`] that store a graph in a
+//! directory as several files (shards) in one of the [supported formats][formats] along
+//! with a manifest that lists them. Graphs that are too big for a single  file  can  be
+//! split this way, and their shards can be parsed in parallel.
+//! 
+//! Vertices are sorted by ID and divided into contiguous ranges, one range  per  shard.
+//! Every edge is stored in the shard of its first endpoint, and the other  endpoint  is
+//! copied into this shard along with its attributes if it belongs to a  different  one,
+//! so each shard is a valid graph file on its own.
+//! 
+//! The manifest is a text file named `manifest.txt` that holds the names of the shards,
+//! one per line, relative to the directory; lines starting with `#` are comments.
+//! 
+//! [formats]: super::SupportedFormats
+use std::{collections::{HashMap, HashSet}, fs::{self, File}, io::{BufReader, BufWriter, Write}, path::Path, str::FromStr};
+use itertools::Itertools;
+use crate::{
+    errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Id
+};
+use super::{gnbs::{GNBSReader, GNBSWriter}, protobuf::{ProtobufReader, ProtobufWriter}, AttributeCollectionIO, ExportFilter, Reader, SupportedFormats, Writer};
+
+
+
+const MANIFEST_FILE_NAME: &str = "manifest.txt";
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * SHARDED WRITER                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Sharded writer
+/// 
+/// ## Description
+/// Writes graphs as shards with a manifest.
+pub struct ShardedWriter {
+    format: SupportedFormats,
+    shard_count: usize,
+}
+
+// ShardedWriter::ShardedWriter
+impl ShardedWriter {
+    /// # Create sharded writer
+    /// 
+    /// ## Arguments
+    /// * `shard_count` : `usize` - the number of shards, graphs with fewer vertices are
+    /// split into as many shards as they have vertices (at least one).
+    /// * `format` : `SupportedFormats` - the format of the shards.
+    /// 
+    /// ## Returns
+    /// * `ShardedWriter` - the writer.
+    pub fn new(shard_count: usize, format: SupportedFormats) -> Self {
+        ShardedWriter { format, shard_count }
+    }
+
+    /// # Write graph into directory
+    /// 
+    /// ## Description
+    /// Write the shards of the graph and the manifest into  the  given  directory.  The
+    /// shards are named `shard-<number>.<extension>`,  existing  files  with  the  same
+    /// names are overwritten.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `directory` : `&str` - the name of an existing directory.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if all files  were  written  successfully;
+    /// `Err(_)` is returned otherwise.
+    pub fn into_directory<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &G, directory: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "ShardedWriter::ShardedWriter::into_directory";
+        if self.shard_count == 0 {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "shard_count".to_string(), reason: "The number of shards must be positive.".to_string() }));
+        }
+        let open = |file_name: &str| {
+            let path = Path::new(directory).join(file_name);
+            match File::options().create(true).write(true).truncate(true).open(&path) {
+                Ok(value) => Ok(BufWriter::new(value)),
+                Err(error) => Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", path.display(), error)))),
+            }
+        };
+        let vertices = graph.iter_v().sorted().collect_vec();
+        let shard_count = self.shard_count.min(vertices.len()).max(1);
+        let shard_size = vertices.len().div_ceil(shard_count);
+        let owners: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(index, id)| (id, index / shard_size.max(1))).collect();
+        // Vertices of each shard including the copies of the endpoints of its edges
+        let mut shard_vertices: Vec<HashSet<VertexIdType>> = vec![HashSet::new(); shard_count];
+        for (id, owner) in owners.iter() {
+            shard_vertices[*owner].insert((*id).clone());
+        }
+        for edge in graph.iter_e() {
+            shard_vertices[owners[&edge.id1]].insert(edge.id2);
+        }
+        let mut manifest = format!("# {} shards of a CrabNets graph\n", shard_count);
+        for (shard, vertices) in shard_vertices.iter().enumerate() {
+            let file_name = format!("shard-{}.{}", shard, self.format.extension());
+            let filter = ExportFilter::new()
+                .with_vertex_predicate(|id, _| vertices.contains(id))
+                .with_edge_predicate(|edge, _| owners[&edge.id1] == shard);
+            let mut buffer_writer = open(&file_name)?;
+            match self.format {
+                SupportedFormats::GNBS => GNBSWriter.write_filtered_graph(graph, &mut buffer_writer, &filter)?,
+                SupportedFormats::Protobuf => ProtobufWriter.write_filtered_graph(graph, &mut buffer_writer, &filter)?,
+            }
+            manifest += &file_name;
+            manifest += "\n";
+        }
+        let mut buffer_writer = open(MANIFEST_FILE_NAME)?;
+        buffer_writer.write_all(manifest.as_bytes()).and_then(|_| buffer_writer.flush()).map_err(|error| CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to write the manifest: {}", error))))
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * SHARDED READER                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Sharded reader
+/// 
+/// ## Description
+/// Reads graphs stored as shards with a manifest.
+pub struct ShardedReader {
+    threads: usize,
+}
+
+// ShardedReader::ShardedReader
+impl ShardedReader {
+    /// # Create sharded reader
+    /// 
+    /// ## Description
+    /// Create a reader that parses the shards one by one.
+    /// 
+    /// ## Returns
+    /// * `ShardedReader` - the reader.
+    pub fn new() -> Self {
+        ShardedReader { threads: 1 }
+    }
+
+    /// # Read graph from directory
+    /// 
+    /// ## Description
+    /// Read the shards listed in the manifest of the given  directory  and  merge  them
+    /// into one graph. The format of each shard is chosen by its extension.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `directory` : `&str` - the name of the directory.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<G>` -  `Ok(value)`  is  returned  if  the  graph  was  read
+    /// successfully, `value` in this case is  the  read  graph;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn read_directory<G, EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>(&self, directory: &str) -> CrabNetsResult<G>
+    where
+        G: BasicMutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> + Send,
+        EdgeAttributeCollectionType: AttributeCollectionIO,
+        EdgeIdType: Id,
+        VertexAttributeCollectionType: AttributeCollectionIO,
+        VertexIdType: FromStr + Id,
+    {
+        const FUNCTION_PATH: &str = "ShardedReader::ShardedReader::read_directory";
+        let manifest_path = Path::new(directory).join(MANIFEST_FILE_NAME);
+        let manifest = match fs::read_to_string(&manifest_path) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to read the manifest '{}': {}", manifest_path.display(), error)))),
+        };
+        let file_names = manifest.lines().map(str::trim).filter(|x| !x.is_empty() && !x.starts_with('#')).collect_vec();
+        let read_shard = |file_name: &str| -> CrabNetsResult<G> {
+            let path = Path::new(directory).join(file_name);
+            let format = match SupportedFormats::from_file_name(file_name) {
+                Some(value) => value,
+                None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedFormat { file_name: path.display().to_string() })),
+            };
+            let buffer_reader = match File::open(&path) {
+                Ok(value) => BufReader::new(value),
+                Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", path.display(), error)))),
+            };
+            match format {
+                SupportedFormats::GNBS => GNBSReader.read_graph(buffer_reader),
+                SupportedFormats::Protobuf => ProtobufReader.read_graph(buffer_reader),
+            }
+        };
+        let threads = match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |x| x.get()),
+            value => value,
+        };
+        let chunk_size = file_names.len().div_ceil(threads).max(1);
+        // Shards are merged in the order of the manifest regardless of the number of threads
+        let shards = std::thread::scope(|scope| {
+            let handles = file_names.chunks(chunk_size).map(|chunk| {
+                let read_shard = &read_shard;
+                scope.spawn(move || chunk.iter().map(|x| read_shard(x)).collect::<CrabNetsResult<Vec<G>>>())
+            }).collect_vec();
+            handles.into_iter().map(|x| x.join().unwrap()).collect::<Vec<_>>()
+        });
+        let mut new_graph = G::default();
+        for shard in shards {
+            for shard in shard? {
+                for id in shard.iter_v() {
+                    // Copies of the same vertex in different shards have the same attributes
+                    if !new_graph.contains_v(&id) {
+                        new_graph.add_v(Some(id.clone()));
+                        *new_graph.v_attrs_mut(&id)? = shard.v_attrs(&id)?.clone();
+                    }
+                }
+                for edge in shard.iter_e() {
+                    let edge_id = match edge.direction {
+                        EdgeDirection::Directed1to2 => new_graph.add_e(&edge.id1, &edge.id2, true, None)?,
+                        EdgeDirection::Directed2to1 => new_graph.add_e(&edge.id2, &edge.id1, true, None)?,
+                        EdgeDirection::Undirected => new_graph.add_e(&edge.id1, &edge.id2, false, None)?,
+                    };
+                    *new_graph.e_attrs_mut(&edge.id1, &edge.id2, &edge_id)? = shard.e_attrs(&edge.id1, &edge.id2, &edge.edge_id)?.clone();
+                }
+            }
+        }
+        Ok(new_graph)
+    }
+
+    /// # Set number of threads
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `threads` : `usize` - the number of threads that parse the  shards,  0  stands
+    /// for the number of available CPU cores.
+    /// 
+    /// ## Returns
+    /// * `ShardedReader` - the updated reader.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+}
+
+// ShardedReader::Default
+impl Default for ShardedReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::io::{SupportedFormats, IO};
+    use super::*;
+
+    #[test]
+    fn shards() {
+        let directory = std::env::temp_dir().join(format!("crabnets_shards_{}", std::process::id()));
+        let directory_name = directory.to_str().unwrap();
+        fs::create_dir_all(&directory).unwrap();
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for i in 0..10 {
+            g.add_v(None);
+            g.v_attrs_mut(&i).unwrap().insert("Index".to_string(), Box::new(i as u32));
+        }
+        for i in 0..10 {
+            let edge_id = g.add_e(&i, &((i * 7 + 3) % 10), i % 2 == 0, None).unwrap();
+            g.e_attrs_mut(&i, &((i * 7 + 3) % 10), &edge_id).unwrap().insert("Index".to_string(), Box::new(i as u32));
+        }
+        g.add_e(&9, &0, false, None).unwrap();
+        for format in [SupportedFormats::GNBS, SupportedFormats::Protobuf] {
+            ShardedWriter::new(3, format).into_directory(&g, directory_name).unwrap();
+            for threads in [1, 0] {
+                let h: graph!(A ===A==> A) = ShardedReader::new().with_threads(threads).read_directory(directory_name).unwrap();
+                assert_eq!(h.to_bytes(format).unwrap(), g.to_bytes(format).unwrap());
+            }
+        }
+        let h: graph!(A ===A==> A) = IO::from_file(directory.join("shard-1.pb").to_str().unwrap()).unwrap();
+        assert!(h.count_v() < g.count_v());
+        assert!(ShardedWriter::new(0, SupportedFormats::GNBS).into_directory(&g, directory_name).is_err());
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(ShardedReader::new().read_directory::<graph!(X ---X--- X), _, _, _, _>(directory_name).is_err());
+    }
+}
@@ -0,0 +1,349 @@
+//! # SVG module
+//! 
+//! ## Description
+//! This module contains [`SVGWriter`] that renders laid-out graphs into SVG images.
+#[cfg(feature = "std")]
+use std::fs::File;
+use std::{collections::HashMap, io::{BufWriter, Write}};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, EdgeDirection, Id};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Vertex style function
+/// 
+/// ## Description
+/// A boxed function that receives the ID and the attribute collection of a  vertex  and
+/// returns a property of the vertex used by [`SVGWriter`].
+pub type VertexStyleFn<'a, VertexAttributeCollectionType, VertexIdType, T> = Box<dyn Fn(&VertexIdType, &VertexAttributeCollectionType) -> T + 'a>;
+
+
+
+// Escape the characters that can't appear in the text of an SVG element
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn io_error(function_path: &str, error: std::io::Error) -> CrabNetsError {
+    CrabNetsError::new(function_path, std::io::Error::new(error.kind(), format!("Failed to write the SVG image: {}", error)))
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * SVG WRITER                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # SVG writer
+/// 
+/// ## Description
+/// Renders a graph into an SVG image. Vertices are drawn as circles  at  the  positions
+/// read   from    their    [attributes][attrs],    e.g.    the    ones    written    by
+/// [`Layout::store_layout`][store]. Edges are drawn as straight lines,  directed  edges
+/// end with arrows and self-loops are drawn as small circles above their vertices.
+/// 
+/// The positions are scaled uniformly to fit into the image, the y-axis points upwards.
+/// The colour, the radius and the label of each vertex are given by style functions, so
+/// they can be derived from the attributes of the vertex. By default, all vertices  are
+/// grey, have radius 8 and no label.
+/// 
+/// [attrs]: crate::Graph#attributes
+/// [store]: crate::layout::Layout::store_layout
+pub struct SVGWriter<'a, VertexAttributeCollectionType, VertexIdType>
+where
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    colour_fn: VertexStyleFn<'a, VertexAttributeCollectionType, VertexIdType, String>,
+    height: f64,
+    label_fn: VertexStyleFn<'a, VertexAttributeCollectionType, VertexIdType, Option<String>>,
+    position_fn: VertexStyleFn<'a, VertexAttributeCollectionType, VertexIdType, Option<(f64, f64)>>,
+    radius_fn: VertexStyleFn<'a, VertexAttributeCollectionType, VertexIdType, f64>,
+    width: f64,
+}
+
+// SVGWriter::SVGWriter
+impl<'a, VertexAttributeCollectionType, VertexIdType> SVGWriter<'a, VertexAttributeCollectionType, VertexIdType>
+where
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Create SVG writer
+    /// 
+    /// ## Description
+    /// Create a writer for images of 800 × 600 pixels.
+    /// 
+    /// ## Arguments
+    /// * `position_fn` : `PositionFnType` - a function that receives  the  ID  and  the
+    /// attribute collection of a vertex and returns the stored position of the vertex.
+    /// 
+    /// ## Returns
+    /// * `SVGWriter` - the writer.
+    pub fn new<PositionFnType>(position_fn: PositionFnType) -> Self
+    where
+        PositionFnType: Fn(&VertexIdType, &VertexAttributeCollectionType) -> Option<(f64, f64)> + 'a,
+    {
+        SVGWriter {
+            colour_fn: Box::new(|_, _| "grey".to_string()),
+            height: 600f64,
+            label_fn: Box::new(|_, _| None),
+            position_fn: Box::new(position_fn),
+            radius_fn: Box::new(|_, _| 8f64),
+            width: 800f64,
+        }
+    }
+
+    /// # Set colour of vertices
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `colour_fn` : `ColourFnType` -  a  function  that  receives  the  ID  and  the
+    /// attribute collection of a vertex and returns its colour in any  form  understood
+    /// by SVG (e.g. `"red"` or `"#ff0000"`).
+    /// 
+    /// ## Returns
+    /// * `SVGWriter` - the updated writer.
+    pub fn with_colour<ColourFnType>(mut self, colour_fn: ColourFnType) -> Self
+    where
+        ColourFnType: Fn(&VertexIdType, &VertexAttributeCollectionType) -> String + 'a,
+    {
+        self.colour_fn = Box::new(colour_fn);
+        self
+    }
+
+    /// # Set labels of vertices
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `label_fn` : `LabelFnType` - a function that receives the ID and the attribute
+    /// collection of a vertex and returns its label, `None` stands for no label.
+    /// 
+    /// ## Returns
+    /// * `SVGWriter` - the updated writer.
+    pub fn with_label<LabelFnType>(mut self, label_fn: LabelFnType) -> Self
+    where
+        LabelFnType: Fn(&VertexIdType, &VertexAttributeCollectionType) -> Option<String> + 'a,
+    {
+        self.label_fn = Box::new(label_fn);
+        self
+    }
+
+    /// # Set radius of vertices
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `radius_fn` : `RadiusFnType` -  a  function  that  receives  the  ID  and  the
+    /// attribute collection of a vertex and returns its radius in pixels.
+    /// 
+    /// ## Returns
+    /// * `SVGWriter` - the updated writer.
+    pub fn with_radius<RadiusFnType>(mut self, radius_fn: RadiusFnType) -> Self
+    where
+        RadiusFnType: Fn(&VertexIdType, &VertexAttributeCollectionType) -> f64 + 'a,
+    {
+        self.radius_fn = Box::new(radius_fn);
+        self
+    }
+
+    /// # Set size of image
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `width` : `f64` - the width of the image in pixels.
+    /// * `height` : `f64` - the height of the image in pixels.
+    /// 
+    /// ## Returns
+    /// * `SVGWriter` - the updated writer.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// # Write graph
+    /// 
+    /// ## Description
+    /// Render the graph and write the SVG image into the given buffer.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `buffer_writer` : `&mut BufWriter<W>` - a mutable reference to the buffer  the
+    /// image is written into.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())`  is  returned  if  all  vertices  have  stored
+    /// positions  and  the  image  was  written  successfully;  `Err(_)`  is   returned
+    /// otherwise.
+    pub fn write_graph<G, W, EdgeAttributeCollectionType, EdgeIdType>(&self, graph: &G, buffer_writer: &mut BufWriter<W>) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        W: Write,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "SVGWriter::SVGWriter::write_graph";
+        let mut positions = HashMap::with_capacity(graph.count_v());
+        for id in graph.iter_v().sorted() {
+            let attrs = graph.v_attrs(&id)?;
+            let position = match (self.position_fn)(&id, attrs) {
+                Some(value) if value.0.is_finite() && value.1.is_finite() => value,
+                _ => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: format!("Vertex {} has no valid stored position.", id) })),
+            };
+            positions.insert(id, position);
+        }
+        // Uniform scaling into the image with a margin, the y-axis is flipped
+        let margin = 20f64 + positions.keys().map(|id| (self.radius_fn)(id, graph.v_attrs(id).unwrap())).fold(0f64, f64::max);
+        let (min_x, max_x) = positions.values().fold((f64::INFINITY, f64::NEG_INFINITY), |x, y| (x.0.min(y.0), x.1.max(y.0)));
+        let (min_y, max_y) = positions.values().fold((f64::INFINITY, f64::NEG_INFINITY), |x, y| (x.0.min(y.1), x.1.max(y.1)));
+        let scale = [((self.width - 2f64 * margin) / (max_x - min_x)).abs(), ((self.height - 2f64 * margin) / (max_y - min_y)).abs()].into_iter().filter(|x| x.is_finite()).fold(f64::INFINITY, f64::min);
+        let scale = if scale.is_finite() { scale } else { 0f64 };
+        let screen: HashMap<&VertexIdType, (f64, f64)> = positions.iter().map(|(id, (x, y))| {
+            (id, ((self.width - (max_x - min_x) * scale) / 2f64 + (x - min_x) * scale, (self.height + (max_y - min_y) * scale) / 2f64 - (y - min_y) * scale))
+        }).collect();
+        let mut output = String::new();
+        output += &format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\">\n", self.width, self.height, self.width, self.height);
+        output += "<defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"black\"/></marker></defs>\n";
+        let edges = graph.iter_e().sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id)));
+        for edge in edges {
+            let (from, to) = match edge.direction {
+                EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1),
+                _ => (&edge.id1, &edge.id2),
+            };
+            let (x1, y1) = screen[from];
+            let (x2, y2) = screen[to];
+            let radius = (self.radius_fn)(to, graph.v_attrs(to)?);
+            if from == to {
+                output += &format!("<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"none\" stroke=\"black\"/>\n", x1, y1 - radius, radius);
+                continue;
+            }
+            // Directed edges end at the boundary of their target, so that arrows are visible
+            let (x2, y2, marker) = match edge.direction {
+                EdgeDirection::Undirected => (x2, y2, ""),
+                _ => {
+                    let length = (x2 - x1).hypot(y2 - y1).max(f64::EPSILON);
+                    (x2 - (x2 - x1) * radius / length, y2 - (y2 - y1) * radius / length, " marker-end=\"url(#arrow)\"")
+                },
+            };
+            output += &format!("<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"black\"{}/>\n", x1, y1, x2, y2, marker);
+        }
+        for (id, (x, y)) in screen.iter().sorted_by(|a, b| a.0.cmp(b.0)) {
+            let attrs = graph.v_attrs(id)?;
+            let radius = (self.radius_fn)(id, attrs);
+            output += &format!("<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\" stroke=\"black\"/>\n", x, y, radius, escape_xml(&(self.colour_fn)(id, attrs)));
+            if let Some(label) = (self.label_fn)(id, attrs) {
+                output += &format!("<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\" font-family=\"sans-serif\" font-size=\"12\">{}</text>\n", x, y - radius - 4f64, escape_xml(&label));
+            }
+        }
+        output += "</svg>\n";
+        buffer_writer.write_all(output.as_bytes()).map_err(|x| io_error(FUNCTION_PATH, x))?;
+        buffer_writer.flush().map_err(|x| io_error(FUNCTION_PATH, x))
+    }
+
+    /// # Write graph into file
+    /// 
+    /// ## Description
+    /// Render the graph and write the SVG image into the file with the given name.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// * `file_name` : `&str` - the name of the file.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())`  is  returned  if  all  vertices  have  stored
+    /// positions  and  the  image  was  written  successfully;  `Err(_)`  is   returned
+    /// otherwise.
+    #[cfg(feature = "std")]
+    pub fn into_file<G, EdgeAttributeCollectionType, EdgeIdType>(&self, graph: &G, file_name: &str) -> CrabNetsResult<()>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "SVGWriter::SVGWriter::into_file";
+        let file = match File::options().create(true).write(true).truncate(true).open(file_name) {
+            Ok(value) => value,
+            Err(error) => return Err(CrabNetsError::new(FUNCTION_PATH, std::io::Error::new(error.kind(), format!("Failed to open the file with name '{}': {}", file_name, error)))),
+        };
+        self.write_graph(graph, &mut BufWriter::new(file))
+    }
+
+    /// # Write graph into string
+    /// 
+    /// ## Description
+    /// Render the graph and return the SVG image as a string.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<String>` - `Ok(value)` is returned if all vertices have stored
+    /// positions, `value` in this case is the image; `Err(_)` is returned otherwise.
+    pub fn into_string<G, EdgeAttributeCollectionType, EdgeIdType>(&self, graph: &G) -> CrabNetsResult<String>
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+    {
+        const FUNCTION_PATH: &str = "SVGWriter::SVGWriter::into_string";
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        self.write_graph(graph, &mut buffer_writer)?;
+        let bytes = buffer_writer.into_inner().map_err(|x| io_error(FUNCTION_PATH, x.into_error()))?;
+        // The image is built from strings, so it is always valid UTF-8
+        Ok(String::from_utf8(bytes).unwrap())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufWriter;
+    use crate::*;
+    use crate::layout::Layout;
+    use super::*;
+
+    #[test]
+    fn write_svg() {
+        let mut g: graph!(A ---X--> A) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&2, &2, false, None).unwrap();
+        let layout = g.circular_layout();
+        g.store_layout(&layout, |attrs, position| { attrs.insert("position".to_string(), Box::new(position)); }).unwrap();
+        let writer = SVGWriter::new(|_, attrs: &DynamicDispatchAttributeMap<String>| attrs.get(&"position".to_string())?.downcast::<(f64, f64)>().copied())
+            .with_colour(|id, _| if *id == 0 { "red".to_string() } else { "blue".to_string() })
+            .with_label(|id, _| Some(format!("<{}>", id)))
+            .with_size(200.0, 100.0);
+        let mut buffer_writer = BufWriter::new(Vec::new());
+        writer.write_graph(&g, &mut buffer_writer).unwrap();
+        let svg = String::from_utf8(buffer_writer.into_inner().unwrap()).unwrap();
+        assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<line").count(), 2);
+        assert_eq!(svg.matches("marker-end").count(), 1);
+        assert_eq!(svg.matches("<circle").count(), 4);
+        assert!(svg.contains("fill=\"red\"") && svg.contains("&lt;2&gt;"));
+        assert_eq!(writer.into_string(&g).unwrap(), svg);
+        g.v_attrs_mut(&1).unwrap().insert("position".to_string(), Box::new("unknown".to_string()));
+        assert!(writer.write_graph(&g, &mut BufWriter::new(Vec::new())).is_err());
+    }
+}
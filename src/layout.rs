@@ -0,0 +1,437 @@
+//! # Layout module
+//! 
+//! ## Description
+//! This module contains [`Layout`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BTreeSet, HashMap};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Coordinates of vertices
+/// 
+/// ## Description
+/// A map from vertices to their positions on the plane, each position is given by its
+/// x- and y-coordinates.
+pub type Coordinates<VertexIdType> = HashMap<VertexIdType, (f64, f64)>;
+
+
+
+// The number of barycentric sweeps of the layered layout
+const LAYERED_SWEEPS: usize = 24;
+
+// Eigenvalues and eigenvectors (as columns of the second matrix) of a symmetric matrix
+// found by the cyclic Jacobi method, eigenvalues are not sorted
+pub(crate) fn symmetric_eigen(mut matrix: Vec<Vec<f64>>, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<(Vec<f64>, Vec<Vec<f64>>)> {
+    let n = matrix.len();
+    let mut vectors: Vec<Vec<f64>> = (0..n).map(|i| (0..n).map(|j| if i == j { 1f64 } else { 0f64 }).collect()).collect();
+    let norm: f64 = matrix.iter().flatten().map(|x| x * x).sum();
+    for _ in 0..100 {
+        control.check(function_path)?;
+        let off_diagonal: f64 = (0..n).flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j))).map(|(i, j)| matrix[i][j] * matrix[i][j]).sum();
+        if off_diagonal <= 1e-24 * norm {
+            break;
+        }
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if matrix[p][q] == 0f64 {
+                    continue;
+                }
+                // Rotation in the (p, q) plane that zeroes the (p, q) element
+                let theta = (matrix[q][q] - matrix[p][p]) / (2f64 * matrix[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1f64).sqrt());
+                let c = 1f64 / (t * t + 1f64).sqrt();
+                let s = t * c;
+                for row in matrix.iter_mut().chain(vectors.iter_mut()) {
+                    let (x, y) = (row[p], row[q]);
+                    row[p] = c * x - s * y;
+                    row[q] = s * x + c * y;
+                }
+                let (head, tail) = matrix.split_at_mut(q);
+                for (x, y) in head[p].iter_mut().zip(tail[0].iter_mut()) {
+                    (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+                }
+            }
+        }
+    }
+    Ok(((0..n).map(|i| matrix[i][i]).collect(), vectors))
+}
+
+// Scale the values, so that the largest absolute value is 1
+fn normalise(values: &mut [f64]) {
+    let scale = values.iter().fold(0f64, |x, y| x.max(y.abs()));
+    if scale > 0f64 {
+        values.iter_mut().for_each(|x| *x /= scale);
+    }
+}
+
+// The number of crossings of edges between two consecutive layers, `edges` holds pairs
+// of positions in the upper and the lower layer
+fn count_crossings(edges: &[(usize, usize)]) -> usize {
+    let mut answer = 0;
+    for (i, (a1, b1)) in edges.iter().enumerate() {
+        for (a2, b2) in edges[(i + 1)..].iter() {
+            if (a1 < a2 && b1 > b2) || (a1 > a2 && b1 < b2) {
+                answer += 1;
+            }
+        }
+    }
+    answer
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * LAYOUT                                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Layout
+/// 
+/// ## Description
+/// Layout functions place the vertices of the graph on the plane for  drawing.  All  of
+/// them  return  [`Coordinates`],  which  [`Layout::store_layout`]  writes   into   the
+/// [attribute collections][attrs] of the vertices, so that every layout is  stored  the
+/// same way.
+/// 
+/// Vertices are processed in ascending order of their IDs, so  the  same  graph  always
+/// gets the same layout.
+/// 
+/// [attrs]: crate::Graph#attributes
+pub trait Layout<VertexAttributeCollectionType, VertexIdType>
+where
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Circular layout
+    /// 
+    /// ## Description
+    /// Place the vertices evenly on the unit circle.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Coordinates<VertexIdType>` - the position of each vertex.
+    /// 
+    /// ## Details
+    /// The vertex with the smallest ID is placed at (1, 0), the others  follow  counter
+    /// clockwise in ascending order of their IDs. A single vertex is placed at (0, 0).
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V|).
+    /// 
+    /// Space: O(|V|).
+    fn circular_layout(&self) -> Coordinates<VertexIdType>;
+    /// # Layered layout
+    /// 
+    /// ## Description
+    /// Place the vertices of a directed acyclic graph on horizontal layers, so that all
+    /// edges point downwards.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Coordinates<VertexIdType>>` - `Ok(value)` is returned  if  the
+    /// graph is directed and acyclic, `value` in this case  is  the  position  of  each
+    /// vertex; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Each vertex is assigned to the layer equal to the length  of  the  longest  path
+    /// leading to it, so sources are on layer 0.  Edges  spanning  several  layers  are
+    /// split by invisible dummy vertices, then the order of vertices within each  layer
+    /// is improved by sweeps of the barycenter heuristic, keeping the  order  with  the
+    /// fewest  crossings.  This  is  the  framework  of  Sugiyama,  Tagawa   and   Toda
+    /// [[source](https://doi.org/10.1109/TSMC.1981.4308636)].
+    /// 
+    /// The y-coordinate of a vertex is minus the number of its layer, the x-coordinates
+    /// of the vertices of a layer are consecutive integers (or halves)  centred  at  0.
+    /// The multiplicity of edges is ignored.
+    /// 
+    /// ## Complexity
+    /// Time: O(V' + E'²), where V' and E' are the numbers of vertices and  edges  after
+    /// inserting the dummy vertices.
+    /// 
+    /// Space: O(V' + E').
+    fn layered_layout(&self) -> CrabNetsResult<Coordinates<VertexIdType>>;
+    /// # Spectral layout
+    /// 
+    /// ## Description
+    /// Place the vertices according to the eigenvectors of the Laplacian matrix of  the
+    /// graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Coordinates<VertexIdType>>` - `Ok(value)` is returned  if  the
+    /// computation wasn't cancelled, `value` in this  case  is  the  position  of  each
+    /// vertex; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The x- and y-coordinates are the components of the eigenvectors  of  the  second
+    /// and the third smallest eigenvalues of the Laplacian matrix,  scaled  to  [-1, 1]
+    /// [[source](https://doi.org/10.1007/BF02289526)]. Adjacent vertices end  up  close
+    /// to each other. Directions and the multiplicity of edges as  well  as  self-loops
+    /// are ignored. Missing coordinates of graphs with fewer than 3 vertices are 0.
+    /// 
+    /// Eigenvectors are found by the cyclic Jacobi  method,  the  function  checks  the
+    /// given [`ExecutionControl`] before each sweep of the method and returns an  error
+    /// of kind [`Cancelled`][cancelled] if the computation is cancelled.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³) per sweep, the number of sweeps is usually below 10.
+    /// 
+    /// Space: O(|V|²).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    fn spectral_layout(&self, control: &ExecutionControl) -> CrabNetsResult<Coordinates<VertexIdType>>;
+    /// # Store layout
+    /// 
+    /// ## Description
+    /// Write the coordinates of the vertices into their attribute collections.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `coordinates` : `&Coordinates<VertexIdType>` - an immutable reference  to  the
+    /// coordinates.
+    /// * `setter` : `SetterFnType` - a function that receives a  mutable  reference  to
+    /// the attribute collection of a vertex and the  coordinates  of  the  vertex,  and
+    /// stores the coordinates.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if all vertices from `coordinates`
+    /// exist; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|).
+    /// 
+    /// Space: O(1).
+    fn store_layout<SetterFnType>(&mut self, coordinates: &Coordinates<VertexIdType>, setter: SetterFnType) -> CrabNetsResult<()>
+    where
+        SetterFnType: FnMut(&mut VertexAttributeCollectionType, (f64, f64));
+}
+
+
+
+// Graph::Layout
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Layout<VertexAttributeCollectionType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn circular_layout(&self) -> Coordinates<VertexIdType> {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        if vertices.len() == 1 {
+            return vertices.into_iter().map(|x| (x, (0f64, 0f64))).collect();
+        }
+        let step = 2f64 * std::f64::consts::PI / vertices.len() as f64;
+        vertices.into_iter().enumerate().map(|(i, x)| (x, ((i as f64 * step).cos(), (i as f64 * step).sin()))).collect()
+    }
+
+    fn layered_layout(&self) -> CrabNetsResult<Coordinates<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Layout::layered_layout";
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut arcs = BTreeSet::new();
+        for edge in self.iter_e() {
+            match edge.direction {
+                EdgeDirection::Directed1to2 => arcs.insert((indices[&edge.id1], indices[&edge.id2])),
+                EdgeDirection::Directed2to1 => arcs.insert((indices[&edge.id2], indices[&edge.id1])),
+                EdgeDirection::Undirected => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: format!("Edge {} between vertices {} and {} is undirected, layered layout requires a directed acyclic graph.", edge.edge_id, edge.id1, edge.id2) })),
+            };
+        }
+        // Longest path layering in topological order
+        let mut successors = vec![Vec::new(); vertices.len()];
+        let mut in_degrees = vec![0usize; vertices.len()];
+        for &(i, j) in arcs.iter() {
+            successors[i].push(j);
+            in_degrees[j] += 1;
+        }
+        let mut layers = vec![0usize; vertices.len()];
+        let mut ready: Vec<usize> = (0..vertices.len()).rev().filter(|&i| in_degrees[i] == 0).collect();
+        let mut processed_count = 0;
+        while let Some(i) = ready.pop() {
+            processed_count += 1;
+            for &j in successors[i].iter() {
+                layers[j] = layers[j].max(layers[i] + 1);
+                in_degrees[j] -= 1;
+                if in_degrees[j] == 0 {
+                    ready.push(j);
+                }
+            }
+        }
+        if processed_count < vertices.len() {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: "The graph has a cycle, layered layout requires a directed acyclic graph.".to_string() }));
+        }
+        // Split long edges by dummy vertices, which get indices after the real ones
+        let layer_count = layers.iter().max().map_or(0, |x| x + 1);
+        let mut ordering: Vec<Vec<usize>> = vec![Vec::new(); layer_count];
+        for (i, &layer) in layers.iter().enumerate() {
+            ordering[layer].push(i);
+        }
+        let mut upper = vec![Vec::new(); vertices.len()];
+        let mut lower = vec![Vec::new(); vertices.len()];
+        for &(i, j) in arcs.iter() {
+            let mut previous = i;
+            for layer_vertices in ordering[(layers[i] + 1)..layers[j]].iter_mut() {
+                let dummy = upper.len();
+                upper.push(vec![previous]);
+                lower.push(Vec::new());
+                lower[previous].push(dummy);
+                layer_vertices.push(dummy);
+                previous = dummy;
+            }
+            lower[previous].push(j);
+            upper[j].push(previous);
+        }
+        let crossings = |ordering: &Vec<Vec<usize>>| {
+            let mut positions = vec![0usize; upper.len()];
+            for layer in ordering.iter() {
+                for (position, &x) in layer.iter().enumerate() {
+                    positions[x] = position;
+                }
+            }
+            let positions = &positions;
+            ordering.iter().map(|layer| count_crossings(&layer.iter().flat_map(|&x| lower[x].iter().map(move |&y| (positions[x], positions[y]))).collect::<Vec<_>>())).sum::<usize>()
+        };
+        let mut best = (crossings(&ordering), ordering.clone());
+        for sweep in 0..LAYERED_SWEEPS {
+            let downwards = sweep % 2 == 0;
+            let layer_order: Vec<usize> = if downwards { (1..layer_count).collect() } else { (0..layer_count.saturating_sub(1)).rev().collect() };
+            for layer in layer_order {
+                let reference = if downwards { layer - 1 } else { layer + 1 };
+                let positions: HashMap<usize, usize> = ordering[reference].iter().enumerate().map(|(i, &x)| (x, i)).collect();
+                let neighbours = if downwards { &upper } else { &lower };
+                // Vertices without neighbours in the reference layer keep their positions
+                let barycenters: Vec<f64> = ordering[layer].iter().enumerate().map(|(i, &x)| match neighbours[x].len() {
+                    0 => i as f64,
+                    count => neighbours[x].iter().map(|y| positions[y] as f64).sum::<f64>() / count as f64,
+                }).collect();
+                ordering[layer] = ordering[layer].iter().copied().zip(barycenters).sorted_by(|x, y| x.1.total_cmp(&y.1)).map(|x| x.0).collect();
+            }
+            let crossing_count = crossings(&ordering);
+            if crossing_count < best.0 {
+                best = (crossing_count, ordering.clone());
+            }
+        }
+        let mut answer = HashMap::with_capacity(vertices.len());
+        for (layer, layer_vertices) in best.1.into_iter().enumerate() {
+            let offset = (layer_vertices.len() as f64 - 1f64) / 2f64;
+            for (position, x) in layer_vertices.into_iter().enumerate() {
+                if x < vertices.len() {
+                    answer.insert(vertices[x].clone(), (position as f64 - offset, -(layer as f64)));
+                }
+            }
+        }
+        Ok(answer)
+    }
+
+    fn spectral_layout(&self, control: &ExecutionControl) -> CrabNetsResult<Coordinates<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Layout::spectral_layout";
+        let (vertices, structure) = self.motif_structure();
+        let n = vertices.len();
+        let mut laplacian = vec![vec![0f64; n]; n];
+        for (i, adjacent) in structure.neighbours.iter().enumerate() {
+            laplacian[i][i] = adjacent.len() as f64;
+            for &j in adjacent.iter() {
+                laplacian[i][j] = -1f64;
+            }
+        }
+        let (values, vectors) = symmetric_eigen(laplacian, control, FUNCTION_PATH)?;
+        let order: Vec<usize> = (0..n).sorted_by(|&i, &j| values[i].total_cmp(&values[j])).collect();
+        let mut axes = [vec![0f64; n], vec![0f64; n]];
+        for (axis, &k) in axes.iter_mut().zip(order.iter().skip(1)) {
+            *axis = vectors.iter().map(|row| row[k]).collect();
+            // Eigenvectors are defined up to their sign, the first non-zero component is
+            // made positive
+            if axis.iter().find(|x| x.abs() > 1e-9).is_some_and(|x| *x < 0f64) {
+                axis.iter_mut().for_each(|x| *x = -*x);
+            }
+            normalise(axis);
+        }
+        Ok(vertices.into_iter().enumerate().map(|(i, x)| (x, (axes[0][i], axes[1][i]))).collect())
+    }
+
+    fn store_layout<SetterFnType>(&mut self, coordinates: &Coordinates<VertexIdType>, mut setter: SetterFnType) -> CrabNetsResult<()>
+    where
+        SetterFnType: FnMut(&mut VertexAttributeCollectionType, (f64, f64)),
+    {
+        for (id, position) in coordinates.iter() {
+            setter(self.v_attrs_mut(id)?, *position);
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn circular_and_spectral_layout() {
+        // Path 0 - 1 - 2 - 3
+        let mut g: graph!(A ---X--- A) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        for i in 0..3 {
+            g.add_e(&i, &(i + 1), false, None).unwrap();
+        }
+        let circular = g.circular_layout();
+        assert!((circular[&0].0 - 1.0).abs() < 1e-12 && circular[&0].1.abs() < 1e-12);
+        assert!((circular[&1].0).abs() < 1e-12 && (circular[&1].1 - 1.0).abs() < 1e-12);
+        // The Fiedler vector of a path is monotone, ends are mapped to -1 and 1
+        let spectral = g.spectral_layout(&ExecutionControl::new()).unwrap();
+        let x: Vec<f64> = (0..4).map(|i| spectral[&i].0).collect();
+        assert!((x[0] - 1.0).abs() < 1e-9 && (x[3] + 1.0).abs() < 1e-9);
+        assert!(x.windows(2).all(|w| w[0] > w[1]));
+        g.store_layout(&spectral, |attrs, (x, y)| {
+            attrs.insert("x".to_string(), Box::new(x));
+            attrs.insert("y".to_string(), Box::new(y));
+        }).unwrap();
+        assert_eq!(g.v_attrs(&3).unwrap().get(&"x".to_string()).unwrap().downcast::<f64>(), Some(&spectral[&3].0));
+    }
+
+    #[test]
+    fn layered_layout() {
+        // 0 -> 1 -> 2 -> 3 and 0 -> 3, 4 -> 2
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        for (x, y) in [(0, 1), (1, 2), (2, 3), (0, 3), (4, 2), (4, 2)] {
+            g.add_e(&x, &y, true, None).unwrap();
+        }
+        let layout = g.layered_layout().unwrap();
+        assert_eq!((0..5).map(|i| layout[&i].1).collect::<Vec<_>>(), vec![0.0, -1.0, -2.0, -3.0, 0.0]);
+        assert_eq!(layout[&3].0, 0.0);
+        g.add_e(&3, &0, true, None).unwrap();
+        assert!(g.layered_layout().is_err());
+    }
+}
@@ -32,30 +32,152 @@
 //! use crabnets::prelude::*;
 //! ```
 //! 
+//! ## Cargo features
+//! * `arrow` - exchange of vertex and edge tables with Apache Arrow record batches with
+//! `io::arrow` module, requires `std`.
+//! * `linalg` - spectral embedding and  spectral  clustering  with  `spectral`  module,
+//! requires `std`.
+//! * `mmap` - memory-mapped reading of [edge list][edge_list]  and  Protobuf  files  on
+//! Unix-like systems, requires `std`.
+//! * `net` - loading graphs from `http://` and `https://` URLs with `io::http`  module,
+//! requires `std`.
+//! * `parquet` - reading and writing the tables of `io::arrow` module  from/to  Parquet
+//! files, requires `arrow`.
+//! * `proptest` - proptest strategies for graphs with `testing` module.
+//! * `quickcheck` - quickcheck's `Arbitrary` for graphs with `testing` module.
+//! * `std` (enabled by default) - the [`io`] module, graph  algorithms  and  everything
+//! else that needs the standard library. Without it, the crate  is  `no_std`  and  only
+//! needs  `alloc`:  graphs,  locales,  attribute  collections  and  errors  are   still
+//! available,  but  their   hash   maps   and   sets   come   from   `hashbrown`.   The
+//! `wasm32-unknown-unknown` target has the standard library, so the  feature  may  stay
+//! enabled there, although the functions working with files fail at runtime.
+//! * `testing` - property-based testing and round-trip checks with `testing` module,
+//! requires `std`.
+//! * `uuid` - UUIDs as IDs of vertices and edges.
+//! * `validate` - checks of the internal invariants after each modification of a  graph
+//! in debug builds.
+//! 
+//! [edge_list]: io::edge_list
 //! [kinds]: Graph#different-kinds-of-graphs
 //! [attrs]: Graph#attributes
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
 
 
 
 
+extern crate alloc;
 
+#[cfg(feature = "std")]
+pub mod aggregation;
+#[cfg(feature = "std")]
+pub mod anonymization;
 pub mod attributes;
+#[cfg(feature = "std")]
+pub mod canonization;
+#[cfg(feature = "std")]
+pub mod centrality;
+#[cfg(feature = "std")]
+pub mod communities;
+#[cfg(feature = "std")]
+pub mod conversions;
+#[cfg(feature = "std")]
+pub mod covers;
+#[cfg(feature = "std")]
+pub mod cut_tree;
+#[cfg(feature = "std")]
+pub mod degree_sequences;
+#[cfg(feature = "std")]
+pub mod diffusion;
+#[cfg(feature = "std")]
+pub mod distance_oracle;
+#[cfg(feature = "std")]
+pub mod dynamics;
+#[cfg(feature = "std")]
+pub mod edge_colouring;
+#[cfg(feature = "std")]
+pub mod edge_filter;
 pub mod errors;
+#[cfg(feature = "std")]
+pub mod execution;
+pub mod handles;
+#[cfg(feature = "std")]
+pub mod invariant_cache;
+#[cfg(feature = "std")]
 pub mod io;
+#[cfg(feature = "std")]
+pub mod layout;
+#[cfg(feature = "std")]
+pub mod limits;
 pub mod locales;
+#[cfg(feature = "std")]
+pub mod matrices;
+#[cfg(feature = "std")]
+pub mod matching;
+#[cfg(feature = "std")]
+pub mod metrics;
+#[cfg(feature = "std")]
+pub mod motifs;
+#[cfg(feature = "std")]
+pub mod orderings;
+#[cfg(feature = "std")]
+pub mod partitioning;
+#[cfg(feature = "std")]
+pub mod path;
+#[cfg(feature = "std")]
+pub mod percolation;
 pub mod prelude;
+#[cfg(feature = "std")]
+pub mod query;
+#[cfg(feature = "std")]
+pub mod random_walks;
+#[cfg(feature = "std")]
+pub mod rng;
+#[cfg(feature = "std")]
+pub mod rooted_tree;
+#[cfg(feature = "std")]
+pub mod shortest_paths;
+#[cfg(feature = "linalg")]
+pub mod spectral;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std")]
 pub mod topology_tests;
-
-use std::{
-    collections::HashMap,
-    fmt::Display,
+#[cfg(feature = "std")]
+pub mod traversal;
+#[cfg(feature = "std")]
+pub mod travelling_salesman;
+#[cfg(feature = "std")]
+pub mod union_find;
+pub mod validation;
+#[cfg(feature = "std")]
+pub mod workspace;
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{
+    cell::{Ref, RefCell, RefMut},
+    fmt::{Debug, Display},
     hash::Hash,
     marker::PhantomData,
-    ops::AddAssign,
+    ops::{AddAssign, Deref, DerefMut},
 };
+// Hash maps and sets come from `std` when it's available and from `hashbrown` otherwise
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{hash_map, HashMap, HashSet};
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{hash_map, HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use bitflags::bitflags;
+#[cfg_attr(not(feature = "std"), allow(unused_imports))]
 use attributes::{AttributeCollection, DynamicDispatchAttributeMap, StaticDispatchAttributeValue};
-use errors::{CrabNetsError, CrabNetsResult};
+use errors::{CrabNetsError, CrabNetsResult, ErrorKind};
 use locales::*;
 
 
@@ -114,7 +236,9 @@ pub type ConditionalType<const C: bool, T, F> = <ConditionalTypeCore<C, T, F> as
 /// ## Description
 /// Types that implement `Id` can be used as unique identifiers of vertices and parallel
 /// edges within a [`Graph`]. This trait is already implemented for all standard integer
-/// types (both signed and unsigned).
+/// types (both signed and unsigned), for `String` and, if the crate  is  compiled  with
+/// feature `uuid`, for `uuid::Uuid`. To implement it for newtypes around  these  types,
+/// use [`implement_id_for_newtype!`].
 /// 
 /// Types  that  implement  `Id`  must  be  linearly   ordered,   hashable,   cloneable,
 /// displayable.
@@ -127,7 +251,8 @@ where
     /// ## Description
     /// This function returns the default value for the first possible ID. For integers,
     /// this function is implemented to return  the  minimum  value  of  the  respective
-    /// integer type.
+    /// integer type. For strings, it returns the empty string. For  UUIDs,  it  returns
+    /// the nil UUID.
     /// 
     /// ## Arguments
     /// None.
@@ -139,7 +264,11 @@ where
     /// 
     /// ## Description
     /// This function assigns to the callee the following element in  the  linear  order
-    /// defined for the type. For integers, this function increases the value by 1.
+    /// defined for the type. For integers and UUIDs, this function increases the  value
+    /// by 1. For strings, it appends the `'\0'` character, which  gives  the  immediate
+    /// successor in the lexicographic order.  Thus,  IDs  generated  automatically  for
+    /// graphs with string IDs are not human-readable,  and  it  is  advised  to  always
+    /// provide the IDs explicitly for such graphs.
     /// 
     /// ## Arguments
     /// * `&mut self` - a mutable reference to the caller.
@@ -176,6 +305,72 @@ implement_vertex_id_trait_for!(i64);
 implement_vertex_id_trait_for!(i128);
 implement_vertex_id_trait_for!(isize);
 
+// String::Id
+impl Id for String {
+    fn default() -> Self {
+        String::new()
+    }
+
+    fn increment(&mut self) {
+        self.push('\0')
+    }
+}
+
+// uuid::Uuid::Id
+#[cfg(feature = "uuid")]
+impl Id for uuid::Uuid {
+    fn default() -> Self {
+        uuid::Uuid::nil()
+    }
+
+    fn increment(&mut self) {
+        *self = uuid::Uuid::from_u128(self.as_u128().wrapping_add(1))
+    }
+}
+
+/// # Implement `Id` for newtypes
+/// 
+/// ## Description
+/// This macro implements [`Id`] for one or more tuple structs that wrap a single  value
+/// of a type that  already  implements  [`Id`].  This  allows  to  use  domain-specific
+/// wrappers like `UserId(u64)` as IDs without writing the implementation by hand.
+/// 
+/// The wrapper itself must still satisfy the requirements of [`Id`],  i.e.  it  has  to
+/// implement `Clone`, `Display`, `Eq`, `Hash` and `Ord`. Both `default` and `increment`
+/// are forwarded to the wrapped value.
+/// 
+/// ## Example
+/// ```ignore
+/// #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+/// struct UserId(u64);
+/// 
+/// impl Display for UserId {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+/// 
+/// implement_id_for_newtype!(UserId);
+/// 
+/// let g: graph!(X ---X--- X with VertexIdType = UserId) = Graph::new();
+/// ```
+#[macro_export]
+macro_rules! implement_id_for_newtype {
+    ($($t: ty),+ $(,)?) => {
+        $(
+            impl $crate::Id for $t {
+                fn default() -> Self {
+                    Self(<_ as $crate::Id>::default())
+                }
+
+                fn increment(&mut self) {
+                    $crate::Id::increment(&mut self.0)
+                }
+            }
+        )+
+    };
+}
+
 
 
 
@@ -186,13 +381,36 @@ implement_vertex_id_trait_for!(isize);
 
 
 
-#[derive(Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum EdgeDirection {
     Undirected,
     Directed1to2,
     Directed2to1,
 }
 
+// EdgeDirection::EdgeDirection
+impl EdgeDirection {
+    /// # Reverse direction
+    /// 
+    /// ## Description
+    /// Get the direction of the same edge when its endpoints are listed in the opposite
+    /// order.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `EdgeDirection`  -  `Directed2to1`  for   `Directed1to2`   and   vice   versa,
+    /// `Undirected` for `Undirected`.
+    pub fn reversed(&self) -> EdgeDirection {
+        match self {
+            EdgeDirection::Undirected => EdgeDirection::Undirected,
+            EdgeDirection::Directed1to2 => EdgeDirection::Directed2to1,
+            EdgeDirection::Directed2to1 => EdgeDirection::Directed1to2,
+        }
+    }
+}
+
 
 
 #[derive(Hash, PartialEq, Eq)]
@@ -209,6 +427,19 @@ where
 
 
 
+pub struct RemovedVertex<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    pub attributes: VertexAttributeCollectionType,
+    pub incident_edges: Vec<(EdgeIteratorItem<EdgeIdType, VertexIdType>, EdgeAttributeCollectionType)>,
+}
+
+
+
 
 
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
@@ -259,6 +490,12 @@ bitflags! {
     /// argument of the called function to `true`. Otherwise, set it to `false`.
     /// 
     /// If you don't give any hints, the value of `that_are_forced` will be ignored.
+    /// 
+    /// Algorithms obtain the hints they may rely on with [`Graph::verify_hints`]. Hints
+    /// at planarity and low treewidth can't be checked efficiently and are always taken
+    /// on trust. In debug builds, forced hints are verified anyway,  and  a  misleading
+    /// hint causes a panic.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
     pub struct Hints: u32 {
         /// Hints at the fact that there're no cycles in the graph.
         /// Implies `Hints::LOW_TREEWIDTH`.
@@ -279,6 +516,8 @@ bitflags! {
         /// Hints at the fact that the graph is a tree.
         /// Implies `Hints::FOREST` and `Hints::STRONGLY_CONNECTED`.
         const TREE = Hints::FOREST.bits() + Hints::STRONGLY_CONNECTED.bits();
+        /// Hints at the fact that the graph is bipartite.
+        const BIPARTITE = 1 << 6;
     }
 }
 
@@ -324,6 +563,11 @@ where
         self.unwrap().count_e()
     }
 
+    #[inline]
+    fn count_parallel_e(&self, id1: &T::VertexIdType, id2: &T::VertexIdType) -> CrabNetsResult<usize> {
+        self.unwrap().count_parallel_e(id1, id2)
+    }
+
     #[inline]
     fn count_v(&self) -> usize {
         self.unwrap().count_v()
@@ -419,22 +663,237 @@ where
         self.unwrap().e_attrs_mut(id1, id2, edge_id)
     }
 
+    #[inline]
+    fn get_or_add_v(&mut self, id: T::VertexIdType) -> &mut T::VertexAttributeCollectionType {
+        self.unwrap().get_or_add_v(id)
+    }
+
     #[inline]
     fn remove_e(&mut self, id1: &T::VertexIdType, id2: &T::VertexIdType, edge_id: &T::EdgeIdType) -> CrabNetsResult<bool> {
         self.unwrap().remove_e(id1, id2, edge_id)
     }
 
     #[inline]
-    fn remove_v(&mut self, id: &T::VertexIdType) -> bool {
+    fn remove_v(&mut self, id: &T::VertexIdType) -> Option<RemovedVertex<T::EdgeAttributeCollectionType, T::EdgeIdType, T::VertexAttributeCollectionType, T::VertexIdType>> {
         self.unwrap().remove_v(id)
     }
 
+    #[inline]
+    fn take_e(&mut self, id1: &T::VertexIdType, id2: &T::VertexIdType, edge_id: &T::EdgeIdType) -> CrabNetsResult<Option<T::EdgeAttributeCollectionType>> {
+        self.unwrap().take_e(id1, id2, edge_id)
+    }
+
+    #[inline]
+    fn try_add_e(&mut self, id1: &T::VertexIdType, id2: &T::VertexIdType, directed: bool, edge_id: Option<T::EdgeIdType>) -> CrabNetsResult<T::EdgeIdType> {
+        self.unwrap().try_add_e(id1, id2, directed, edge_id)
+    }
+
+    #[inline]
+    fn try_add_v(&mut self, id: Option<T::VertexIdType>) -> CrabNetsResult<T::VertexIdType> {
+        self.unwrap().try_add_v(id)
+    }
+
     #[inline]
     fn v_attrs_mut(&mut self, id: &T::VertexIdType) -> CrabNetsResult<&mut T::VertexAttributeCollectionType> {
         self.unwrap().v_attrs_mut(id)
     }
 }
 
+// Box<Graph>::ImmutableGraphContainer
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> ImmutableGraphContainer for Box<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type EdgeAttributeCollectionType = EdgeAttributeCollectionType;
+    type EdgeIdType = EdgeIdType;
+    type LocaleType = LocaleType;
+    type VertexAttributeCollectionType = VertexAttributeCollectionType;
+    type VertexIdType = VertexIdType;
+
+    #[inline]
+    fn unwrap(&self) -> &Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> {
+        self
+    }
+}
+
+// Box<Graph>::MutableGraphContainer
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> MutableGraphContainer for Box<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    #[inline]
+    fn unwrap(&mut self) -> &mut Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> {
+        self
+    }
+}
+
+
+
+/// # Shared graph container
+/// 
+/// ## Description
+/// A graph container that shares a [`Graph`] between several owners and  gives  it  out
+/// through borrow guards.
+/// 
+/// ## Details
+/// [`ImmutableGraphContainer`] and [`MutableGraphContainer`] give out plain  references
+/// to the graph, which can't outlive the borrow guard of a  `RefCell`  or  a  `RwLock`.
+/// Graphs behind those are given out through the guards instead, which  dereference  to
+/// [`Graph`],   so    the    whole    graph    interface    stays    available,    e.g.
+/// `graph.borrow_graph().count_v()` or `graph.borrow_graph_mut().add_v(None)`.
+/// 
+/// This trait is implemented for `Rc<RefCell<Graph>>`  and,  with  `std`  feature,  for
+/// `Arc<RwLock<Graph>>`. The former panics if the graph is  borrowed  mutably  and  any
+/// other way at the same time, just like [`RefCell`] does. The latter blocks until  the
+/// lock is free and ignores poisoning, since a panic while the  lock  is  held  doesn't
+/// invalidate the graph any more than a panic while a `Box<Graph>` is borrowed.
+pub trait SharedGraphContainer
+where
+    Self: Clone + Default,
+{
+    type EdgeAttributeCollectionType: AttributeCollection;
+    type EdgeIdType: Id;
+    type LocaleType: Locale<Self::EdgeAttributeCollectionType, Self::EdgeIdType, Self::VertexAttributeCollectionType, Self::VertexIdType>;
+    type VertexAttributeCollectionType: AttributeCollection;
+    type VertexIdType: Id;
+    type Ref<'a>: Deref<Target = Graph<Self::EdgeAttributeCollectionType, Self::EdgeIdType, Self::LocaleType, Self::VertexAttributeCollectionType, Self::VertexIdType>>
+    where
+        Self: 'a;
+    type RefMut<'a>: DerefMut<Target = Graph<Self::EdgeAttributeCollectionType, Self::EdgeIdType, Self::LocaleType, Self::VertexAttributeCollectionType, Self::VertexIdType>>
+    where
+        Self: 'a;
+    fn borrow_graph(&self) -> Self::Ref<'_>;
+    fn borrow_graph_mut(&self) -> Self::RefMut<'_>;
+}
+
+// Rc<RefCell<Graph>>::SharedGraphContainer
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> SharedGraphContainer for Rc<RefCell<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type EdgeAttributeCollectionType = EdgeAttributeCollectionType;
+    type EdgeIdType = EdgeIdType;
+    type LocaleType = LocaleType;
+    type VertexAttributeCollectionType = VertexAttributeCollectionType;
+    type VertexIdType = VertexIdType;
+    type Ref<'a> = Ref<'a, Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+    where
+        Self: 'a;
+    type RefMut<'a> = RefMut<'a, Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn borrow_graph(&self) -> Self::Ref<'_> {
+        self.as_ref().borrow()
+    }
+
+    #[inline]
+    fn borrow_graph_mut(&self) -> Self::RefMut<'_> {
+        self.as_ref().borrow_mut()
+    }
+}
+
+// Arc<RwLock<Graph>>::SharedGraphContainer
+#[cfg(feature = "std")]
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> SharedGraphContainer for Arc<RwLock<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type EdgeAttributeCollectionType = EdgeAttributeCollectionType;
+    type EdgeIdType = EdgeIdType;
+    type LocaleType = LocaleType;
+    type VertexAttributeCollectionType = VertexAttributeCollectionType;
+    type VertexIdType = VertexIdType;
+    type Ref<'a> = RwLockReadGuard<'a, Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+    where
+        Self: 'a;
+    type RefMut<'a> = RwLockWriteGuard<'a, Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+    where
+        Self: 'a;
+
+    #[inline]
+    fn borrow_graph(&self) -> Self::Ref<'_> {
+        self.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    fn borrow_graph_mut(&self) -> Self::RefMut<'_> {
+        self.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+
+
+/// # Borrowed or owned graph
+/// 
+/// ## Description
+/// A graph container that holds either an immutable  reference  to  a  [`Graph`]  or  a
+/// [`Graph`] itself.
+/// 
+/// ## Details
+/// `GraphCow`  is  [`Cow`]  specialised   for   graphs,   so   it   is   created   with
+/// `Cow::Borrowed(&graph)`    or    `Cow::Owned(graph)`.     It     implements     both
+/// [`ImmutableGraphContainer`]  and  [`MutableGraphContainer`]:  reading  never  copies
+/// anything while the first mutation of a borrowed graph clones  it,  and  all  further
+/// operations use the clone. The borrowed graph itself is never changed.
+/// 
+/// This is useful for functions that usually only  read  the  graph  they  receive  but
+/// sometimes need to adjust it, e.g. to add a super source.
+pub type GraphCow<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> = Cow<'a, Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>;
+
+// GraphCow::ImmutableGraphContainer
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> ImmutableGraphContainer for GraphCow<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type EdgeAttributeCollectionType = EdgeAttributeCollectionType;
+    type EdgeIdType = EdgeIdType;
+    type LocaleType = LocaleType;
+    type VertexAttributeCollectionType = VertexAttributeCollectionType;
+    type VertexIdType = VertexIdType;
+
+    #[inline]
+    fn unwrap(&self) -> &Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> {
+        self
+    }
+}
+
+// GraphCow::MutableGraphContainer
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> MutableGraphContainer for GraphCow<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    #[inline]
+    fn unwrap(&mut self) -> &mut Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> {
+        self.to_mut()
+    }
+}
+
 
 
 
@@ -461,6 +920,32 @@ where
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    /// # Check existence of directed edge
+    /// 
+    /// ## Description
+    /// Check whether there is a directed edge going from one given vertex to the other.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `from` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `to` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if both vertices exist and at least  one  directed  edge  goes
+    /// from `from` to `to`, `false` otherwise.
+    /// 
+    /// ## Details
+    /// Unlike [`BasicImmutableGraph::contains_e`], this function doesn't need  an  edge
+    /// ID and doesn't require the caller to interpret the  direction  relative  to  the
+    /// order of arguments. Undirected edges are not taken into account.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg(`from`)).
+    /// 
+    /// Space: O(1).
+    fn contains_directed_e(&self, from: &VertexIdType, to: &VertexIdType) -> bool {
+        self.iter_adjacent_out(from).is_ok_and(|mut x| x.any(|y| y == *to))
+    }
     /// # Check existence of edge
     /// 
     /// ## Description
@@ -518,6 +1003,41 @@ where
     /// ## Returns
     /// * `usize` - the number of edges in the graph.
     fn count_e(&self) -> usize;
+    /// # Count parallel edges
+    /// 
+    /// ## Description
+    /// Get the number of edges connecting two vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id1` : `&VertexIdType` - an immutable  reference  to  the  ID  of  the  first
+    /// vertex.
+    /// * `id2` : `&VertexIdType` - an immutable reference  to  the  ID  of  the  second
+    /// vertex.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(usize)` is returned when  both  vertices  exist;
+    /// `Err(CrabNetsError)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Edges  of  all  directions  are  counted.  If  the   underlying   [`Graph`]   is
+    /// [simple](Graph#different-kinds-of-graphs), the result is either 0 or 1.
+    /// 
+    /// The    default    implementation    counts     the     matching     edges     of
+    /// [`BasicImmutableGraph::iter_e`], [`Graph`] overrides it with  a  lookup  in  the
+    /// locale of `id1`.
+    /// 
+    /// ## Complexity
+    /// Time: O(1) for [`Graph`], O(|E|) for the default implementation.
+    fn count_parallel_e(&self, id1: &VertexIdType, id2: &VertexIdType) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "BasicImmutableGraph::count_parallel_e";
+        for id in [id1, id2] {
+            if !self.contains_v(id) {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }));
+            }
+        }
+        Ok(self.iter_e().filter(|x| (x.id1 == *id1 && x.id2 == *id2) || (x.id1 == *id2 && x.id2 == *id1)).count())
+    }
     /// # Count vertices
     /// 
     /// ## Description
@@ -564,6 +1084,47 @@ where
     /// [Details]: #e-attrs-details
     /// [kinds]: Graph#different-kinds-of-graphs
     fn e_attrs(&self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<&EdgeAttributeCollectionType>;
+    /// # Direction of edges between vertices
+    /// 
+    /// ## Description
+    /// Get the direction of the edges connecting the given vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id1` : `&VertexIdType` - an immutable  reference  to  the  ID  of  the  first
+    /// vertex.
+    /// * `id2` : `&VertexIdType` - an immutable reference  to  the  ID  of  the  second
+    /// vertex.
+    /// 
+    /// ## Returns
+    /// * `Option<EdgeDirection>` - `Some(value)` is returned if both vertices exist and
+    /// are connected by at least one edge, `value` indicates the direction of the edges
+    /// relative to the order of `id1` and `id2`; `None` is returned otherwise.
+    /// 
+    /// ## Details
+    /// If all edges between `id1` and `id2` are directed the same way, their  direction
+    /// is returned. Otherwise (i.e. if there is an undirected edge or edges going  both
+    /// ways), `EdgeDirection::Undirected` is returned, which matches the convention  of
+    /// [`Graph::collapse_parallel_edges`].
+    /// 
+    /// `g.e_direction_between(&id2, &id1)`       is       always        equal        to
+    /// `g.e_direction_between(&id1, &id2).map(|x| x.reversed())`.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg(`id1`) + deg(`id2`)).
+    /// 
+    /// Space: O(1).
+    fn e_direction_between(&self, id1: &VertexIdType, id2: &VertexIdType) -> Option<EdgeDirection> {
+        let is_forward = self.contains_directed_e(id1, id2);
+        let is_backward = id1 != id2 && self.contains_directed_e(id2, id1);
+        let is_undirected = self.iter_adjacent_undir(id1).is_ok_and(|mut x| x.any(|y| y == *id2));
+        match (is_forward, is_backward) {
+            _ if is_undirected || (is_forward && is_backward) => Some(EdgeDirection::Undirected),
+            (true, false) => Some(EdgeDirection::Directed1to2),
+            (false, true) => Some(EdgeDirection::Directed2to1),
+            _ => None,
+        }
+    }
     /// # Iterate over neighbours
     /// 
     /// ## Description
@@ -636,6 +1197,111 @@ where
     /// *  `Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>>>`  -  an
     /// interator over the edges of the graph.
     fn iter_e<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>;
+    /// # Iterate over isolated vertices
+    /// 
+    /// ## Description
+    /// Iterate over all vertices of the graph that have no incident edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = VertexIdType>>`  -  an  iterator  over  the  isolated
+    /// vertices.
+    /// 
+    /// ## Details
+    /// A vertex whose only incident edge is a self-loop is not isolated.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    fn iter_isolated_v<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a>
+    where
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_v().filter(move |x| self.v_degree(x).is_ok_and(|y| y == 0)))
+    }
+    /// # Iterate over leaves
+    /// 
+    /// ## Description
+    /// Iterate over all vertices of the graph that have exactly one incident edge.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = VertexIdType>>` - an iterator over the leaves.
+    /// 
+    /// ## Details
+    /// Edges    of    all     directions     are     taken     into     account.     In
+    /// [multi-graphs](Graph#different-kinds-of-graphs), a vertex connected to its  only
+    /// neighbour by several parallel edges is not a leaf.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    fn iter_leaves<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a>
+    where
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_v().filter(move |x| self.v_degree(x).is_ok_and(|y| y == 1)))
+    }
+    /// # Iterate over sinks
+    /// 
+    /// ## Description
+    /// Iterate over all vertices of the graph that have at least one incoming edge  and
+    /// neither outgoing nor undirected ones.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = VertexIdType>>` - an iterator over the sinks.
+    /// 
+    /// ## Details
+    /// Isolated      vertices      are      not       considered       sinks,       see
+    /// [`BasicImmutableGraph::iter_isolated_v`] for them.  Undirected  graphs  have  no
+    /// sinks.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    fn iter_sinks<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a>
+    where
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_v().filter(move |x| self.v_degree_in(x).is_ok_and(|y| y > 0) && self.v_degree_out(x).is_ok_and(|y| y == 0) && self.v_degree_undir(x).is_ok_and(|y| y == 0)))
+    }
+    /// # Iterate over sources
+    /// 
+    /// ## Description
+    /// Iterate over all vertices of the graph that have at least one outgoing edge  and
+    /// neither incoming nor undirected ones.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = VertexIdType>>` - an iterator over the sources.
+    /// 
+    /// ## Details
+    /// Isolated      vertices      are      not      considered      sources,       see
+    /// [`BasicImmutableGraph::iter_isolated_v`] for them.  Undirected  graphs  have  no
+    /// sources.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    fn iter_sources<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a>
+    where
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_v().filter(move |x| self.v_degree_out(x).is_ok_and(|y| y > 0) && self.v_degree_in(x).is_ok_and(|y| y == 0) && self.v_degree_undir(x).is_ok_and(|y| y == 0)))
+    }
     /// # Iterate over vertices
     /// 
     /// ## Description
@@ -835,8 +1501,18 @@ where
     /// If there's already a vertex with ID `id`,  then  the  existing  vertex  will  be
     /// removed and replaced with the new one. This means that  all  properties  of  the
     /// existing vertex (e.g. [attributes][attrs], incident edges) will be lost.
+    /// The  incident  edges  are  removed  from  both  of  their   ends,   exactly   as
+    /// [`remove_v`][remove_v] would do, so no  neighbour  keeps  an  edge  to  the  new
+    /// vertex.
+    /// 
+    /// A  graph  can't  have  more  than  2³²  vertices   at   the   same   time,   see
+    /// [`VertexHandle`][handle]. This function panics if a new vertex is  added  beyond
+    /// this cap, use [`try_add_v`][try_add_v] to get an error instead.
     /// 
     /// [attrs]: Graph#attributes
+    /// [remove_v]: BasicMutableGraph::remove_v
+    /// [handle]: handles::VertexHandle
+    /// [try_add_v]: BasicMutableGraph::try_add_v
     fn add_v(&mut self, id: Option<VertexIdType>) -> VertexIdType;
     /// # Mutable reference to edge attributes
     /// ## Description
@@ -873,6 +1549,31 @@ where
     /// [Details]: #e-attrs-mut-details
     /// [kinds]: Graph#different-kinds-of-graphs
     fn e_attrs_mut(&mut self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<&mut EdgeAttributeCollectionType>;
+    /// # Get existing or add new vertex
+    /// 
+    /// ## Description
+    /// Get a mutable reference to the [attribute collection][attrs] of the vertex  with
+    /// the given ID, creating this vertex first if it doesn't exist.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `VertexIdType` - the ID of the vertex.
+    /// 
+    /// ## Returns
+    /// * `&mut VertexAttributeCollectionType` - a mutable reference  to  the  attribute
+    /// collection of the vertex with ID `id`.
+    /// 
+    /// ## Details
+    /// Unlike [`BasicMutableGraph::add_v`], this function never  destroys  an  existing
+    /// vertex.
+    /// 
+    /// [attrs]: Graph#attributes
+    fn get_or_add_v(&mut self, id: VertexIdType) -> &mut VertexAttributeCollectionType {
+        if !self.contains_v(&id) {
+            self.add_v(Some(id.clone()));
+        }
+        self.v_attrs_mut(&id).unwrap()
+    }
     /// # Remove edge
     /// 
     /// ## Description
@@ -918,9 +1619,121 @@ where
     /// * `id` : `&VertexIdType` - the ID of a vertex to be removed.
     /// 
     /// ## Returns
-    /// * `bool` - shows whether the vertex with ID `id` existed when this function  was
-    /// called.
-    fn remove_v(&mut self, id: &VertexIdType) -> bool;
+    /// * `Option<RemovedVertex>` - `None` is returned if the vertex with ID `id` didn't
+    /// exist when this function was called; `Some(value)` is returned otherwise,  where
+    /// `value` holds the [attribute collection][attrs] of the removed  vertex  and  the
+    /// list of its removed incident edges together with their attribute collections.
+    /// 
+    /// ## Details
+    /// The removed attribute collections are moved into  the  returned  value,  so  you
+    /// don't need to clone them before the deletion. Use `g.remove_v(&id).is_some()` if
+    /// you only need to know whether the vertex existed.
+    /// 
+    /// [attrs]: Graph#attributes
+    fn remove_v(&mut self, id: &VertexIdType) -> Option<RemovedVertex<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>>;
+    /// # Remove edge and take its attributes
+    /// 
+    /// ## Description
+    /// Delete an edge with the given ID between the given vertices and  give  away  its
+    /// [attribute collection][attrs].
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id1` : `&VertexIdType` - an immutable  reference  to  the  ID  of  the  first
+    /// vertex.
+    /// * `id2` : `&VertexIdType` - an immutable reference  to  the  ID  of  the  second
+    /// vertex.
+    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of the edge to be
+    /// removed.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<EdgeAttributeCollectionType>>` -  `Ok(Some(value))`  is
+    /// returned if the edge existed, `value` is its attribute collection; `Ok(None)` is
+    /// returned if the edge didn't exist;  `Err(CrabNetsError)`  is  returned  when  at
+    /// least 1 of the vertices `id1` and `id2` doesn't exist.
+    /// 
+    /// ## Details
+    /// This function behaves  exactly  like  [`BasicMutableGraph::remove_e`],  see  its
+    /// documentation for more details.
+    /// 
+    /// The   default   implementation   clones   the    attribute    collection    with
+    /// [`BasicImmutableGraph::e_attrs`] before calling [`BasicMutableGraph::remove_e`],
+    /// [`Graph`] overrides it to move the collection out instead.
+    /// 
+    /// [attrs]: Graph#attributes
+    fn take_e(&mut self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<Option<EdgeAttributeCollectionType>> {
+        let answer = match self.contains_e(id1, id2, edge_id) {
+            Some(_) => Some(self.e_attrs(id1, id2, edge_id)?.clone()),
+            None => None,
+        };
+        self.remove_e(id1, id2, edge_id)?;
+        Ok(answer)
+    }
+    /// # Add edge without replacement
+    /// 
+    /// ## Description
+    /// Add a new edge between two vertices unless it would replace an existing edge.
+    /// 
+    /// ## Arguments
+    /// The arguments are the same as for [`BasicMutableGraph::add_e`].
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<EdgeIdType>` - `Ok(value)` is returned when the edge was added
+    /// successfully with `value` being the ID of the new edge; `Err(CrabNetsError)`  is
+    /// returned when at least 1 of the vertices `id1` and `id2` doesn't exist  or  when
+    /// [`BasicMutableGraph::add_e`] would replace an existing edge.
+    /// 
+    /// ## Details
+    /// If the underlying [`Graph`] is [simple][kinds], an error is returned if  there's
+    /// any edge between vertices `id1` and `id2`. If  the  underlying  [`Graph`]  is  a
+    /// [multi-graph][kinds], an error is returned if there's an edge  between  vertices
+    /// `id1` and `id2` with ID `edge_id`.
+    /// 
+    /// The default implementation can't tell simple graphs  from  multi-graphs,  so  if
+    /// `edge_id` is `None`, it returns an error whenever any edge  connects  `id1`  and
+    /// `id2`. Multi-graph implementations should override it, as [`Graph`] does.
+    /// 
+    /// [kinds]: Graph#different-kinds-of-graphs
+    fn try_add_e(&mut self, id1: &VertexIdType, id2: &VertexIdType, directed: bool, edge_id: Option<EdgeIdType>) -> CrabNetsResult<EdgeIdType> {
+        const FUNCTION_PATH: &str = "BasicMutableGraph::try_add_e";
+        let is_occupied = match &edge_id {
+            Some(value) => self.contains_e(id1, id2, value).is_some(),
+            None => self.e_direction_between(id1, id2).is_some(),
+        };
+        if is_occupied {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::EdgeAlreadyExists { id1: id1.to_string(), id2: id2.to_string() }));
+        }
+        self.add_e(id1, id2, directed, edge_id)
+    }
+    /// # Add vertex without replacement
+    /// 
+    /// ## Description
+    /// Add a new isolated vertex to the graph unless a vertex with the same ID  already
+    /// exists.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `Option<VertexIdType>` - if `Some(value)` is passed, a new vertex  with
+    /// ID `value` will be created; if `None` is passed, the ID for the new vertex  will
+    /// be chosen automatically.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<VertexIdType>` - `Ok(value)` is returned when the  vertex  was
+    /// added  successfully  with  `value`   being   the   ID   of   the   new   vertex;
+    /// `Err(CrabNetsError)` is returned if the vertex with ID `id` already exists or if
+    /// the   graph   already   has   2³²    vertices,    the    maximum    number    of
+    /// [vertex handles][handle].
+    /// 
+    /// [handle]: handles::VertexHandle
+    fn try_add_v(&mut self, id: Option<VertexIdType>) -> CrabNetsResult<VertexIdType> {
+        const FUNCTION_PATH: &str = "BasicMutableGraph::try_add_v";
+        if let Some(value) = &id {
+            if self.contains_v(value) {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexAlreadyExists { id: value.to_string() }));
+            }
+        }
+        Ok(self.add_v(id))
+    }
     /// # Get a mutable reference to vertex attributes
     /// 
     /// ## Description
@@ -967,8 +1780,6 @@ where
 /// label from the second classification, thus making 4 possible combinations.  CrabNets
 /// supports all of them.
 /// 
-/// > ⚠️ **Warning!** Currently, multi-graphs are under development and not available.
-/// 
 /// Furthermore,  CrabNets  implements  certain  optimisations   for   each   of   these
 /// combinations, hence, it makes sense for you to carefully evaluate which exactly kind
 /// of graphs you're going to be dealing in your program with to enjoy the best possible
@@ -1016,7 +1827,12 @@ where
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    #[cfg(feature = "std")]
+    edge_filter: Option<edge_filter::EdgeFilter>,
     edge_list: HashMap<VertexIdType, LocaleType>,
+    handles: handles::HandleTable<VertexIdType>,
+    #[cfg(feature = "std")]
+    invariant_cache: Option<invariant_cache::InvariantCache>,
     min_free_vertex_id: VertexIdType,
     phantom: PhantomData<(EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType)>,
 }
@@ -1031,7 +1847,16 @@ where
     VertexIdType: Id,
 {
     pub fn new() -> Self {
-        Graph { edge_list: HashMap::new(), min_free_vertex_id: VertexIdType::default(), phantom: PhantomData }
+        Graph {
+            #[cfg(feature = "std")]
+            edge_filter: None,
+            edge_list: HashMap::new(),
+            handles: handles::HandleTable::new(),
+            #[cfg(feature = "std")]
+            invariant_cache: None,
+            min_free_vertex_id: VertexIdType::default(),
+            phantom: PhantomData,
+        }
     }
 }
 
@@ -1046,6 +1871,10 @@ where
 {
     #[inline]
     fn contains_e(&self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<EdgeDirection> {
+        #[cfg(feature = "std")]
+        if !self.may_contain_e(id1, id2, edge_id) {
+            return None;
+        }
         match self.edge_list.get(id1) {
             Some(value) => value.e_direction(id2, edge_id),
             None => None,
@@ -1070,6 +1899,16 @@ where
         answer / 2
     }
 
+    #[inline]
+    fn count_parallel_e(&self, id1: &VertexIdType, id2: &VertexIdType) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "Graph::BasicImmutableGraph::count_parallel_e";
+        match self.edge_list.get(id1) {
+            Some(value) if self.edge_list.contains_key(id2) => Ok(value.count_parallel_e(id2)),
+            Some(_) => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id2.to_string() })),
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id1.to_string() })),
+        }
+    }
+
     fn e_attrs(&self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<&EdgeAttributeCollectionType> {
         const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::e_attrs_mut";
         if self.edge_list.contains_key(id1) {
@@ -1080,13 +1919,13 @@ where
                         EdgeDirection::Directed1to2 => Ok(self.edge_list.get(id1).unwrap().e_attrs(id2, edge_id).unwrap()),
                         EdgeDirection::Directed2to1 => Ok(self.edge_list.get(id2).unwrap().e_attrs(id1, edge_id).unwrap()),
                     },
-                    None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Accessing attributes of a non-existing edge between vertices {} and {} with edge ID {}.", id1, id2, edge_id))),
+                    None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::EdgeNotFound { id1: id1.to_string(), id2: id2.to_string(), edge_id: edge_id.to_string() })),
                 }
             } else {
-                Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id2)))
+                Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id2.to_string() }))
             }
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id1)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id1.to_string() }))
         }
     }
 
@@ -1095,7 +1934,7 @@ where
         if self.contains_v(id) {
             Ok(self.edge_list.get(id).unwrap().iter_adjacent())
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 
@@ -1104,7 +1943,7 @@ where
         if self.contains_v(id) {
             Ok(self.edge_list.get(id).unwrap().iter_adjacent_in())
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 
@@ -1113,7 +1952,7 @@ where
         if self.contains_v(id) {
             Ok(self.edge_list.get(id).unwrap().iter_adjacent_out())
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 
@@ -1122,7 +1961,7 @@ where
         if self.contains_v(id) {
             Ok(self.edge_list.get(id).unwrap().iter_adjacent_undir())
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 
@@ -1141,7 +1980,7 @@ where
         const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::v_attrs_mut";
         match self.edge_list.get(id) {
             Some(value) => Ok(value.v_attrs()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 
@@ -1149,8 +1988,8 @@ where
     fn v_degree(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
         const FUNCTION_PATH: &str = "Graph::BasicImmutableGraph::v_degree";
         match self.edge_list.get(id) {
-            Some(value) => Ok(value.count_adjacent()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id))),
+            Some(value) => Ok(value.count_incident_e()),
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() })),
         }
     }
 
@@ -1158,8 +1997,8 @@ where
     fn v_degree_in(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
         const FUNCTION_PATH: &str = "Graph::BasicImmutableGraph::v_degree_in";
         match self.edge_list.get(id) {
-            Some(value) => Ok(value.count_adjacent_in()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id))),
+            Some(value) => Ok(value.count_incident_e_in()),
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() })),
         }
     }
 
@@ -1167,8 +2006,8 @@ where
     fn v_degree_out(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
         const FUNCTION_PATH: &str = "Graph::BasicImmutableGraph::v_degree_out";
         match self.edge_list.get(id) {
-            Some(value) => Ok(value.count_adjacent_out()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id))),
+            Some(value) => Ok(value.count_incident_e_out()),
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() })),
         }
     }
 
@@ -1176,8 +2015,8 @@ where
     fn v_degree_undir(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
         const FUNCTION_PATH: &str = "Graph::BasicImmutableGraph::v_degree_undir";
         match self.edge_list.get(id) {
-            Some(value) => Ok(value.count_adjacent_undir()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id))),
+            Some(value) => Ok(value.count_incident_e_undir()),
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() })),
         }
     }
 }
@@ -1211,12 +2050,18 @@ where
                     } else {
                         EdgeToVertexRelation::Undirected
                     }, Some(actual_edge_id.clone()), !directed && id2 <= id1);
+                #[cfg(feature = "std")]
+                self.record_e_in_filter(id1, id2, &actual_edge_id);
+                #[cfg(feature = "std")]
+                self.invalidate_invariants();
+                #[cfg(all(feature = "validate", debug_assertions))]
+                self.debug_validate();
                 Ok(actual_edge_id)
             } else {
-                Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id2)))
+                Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id2.to_string() }))
             }
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id1)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id1.to_string() }))
         }
     }
 
@@ -1224,10 +2069,13 @@ where
         let return_value: VertexIdType;
         match id {
             Some(value) => {
+                self.remove_v(&value);
+                self.handles.insert(value.clone()).expect(handles::HANDLES_EXHAUSTED);
                 self.edge_list.insert(value.clone(), LocaleType::new(value.clone()));
                 return_value = value;
             },
             None => {
+                self.handles.insert(self.min_free_vertex_id.clone()).expect(handles::HANDLES_EXHAUSTED);
                 self.edge_list.insert(self.min_free_vertex_id.clone(), LocaleType::new(self.min_free_vertex_id.clone()));
                 return_value = self.min_free_vertex_id.clone();
             },
@@ -1235,6 +2083,10 @@ where
         while self.edge_list.contains_key(&self.min_free_vertex_id) {
             self.min_free_vertex_id.increment();
         }
+        #[cfg(feature = "std")]
+        self.invalidate_invariants();
+        #[cfg(all(feature = "validate", debug_assertions))]
+        self.debug_validate();
         return_value
     }
 
@@ -1248,53 +2100,141 @@ where
                         EdgeDirection::Directed1to2 => Ok(self.edge_list.get_mut(id1).unwrap().e_attrs_mut(id2, edge_id).unwrap()),
                         EdgeDirection::Directed2to1 => Ok(self.edge_list.get_mut(id2).unwrap().e_attrs_mut(id1, edge_id).unwrap()),
                     },
-                    None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Accessing attributes of a non-existing edge between vertices {} and {} with edge ID {}.", id1, id2, edge_id))),
+                    None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::EdgeNotFound { id1: id1.to_string(), id2: id2.to_string(), edge_id: edge_id.to_string() })),
                 }
             } else {
-                Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id2)))
+                Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id2.to_string() }))
             }
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id1)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id1.to_string() }))
+        }
+    }
+
+    fn get_or_add_v(&mut self, id: VertexIdType) -> &mut VertexAttributeCollectionType {
+        if !self.edge_list.contains_key(&id) {
+            self.add_v(Some(id.clone()));
         }
+        self.edge_list.get_mut(&id).unwrap().v_attrs_mut()
     }
 
+    #[inline]
     fn remove_e(&mut self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<bool> {
-        const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::delete_e";
+        self.take_e(id1, id2, edge_id).map(|x| x.is_some())
+    }
+
+    fn remove_v(&mut self, id: &VertexIdType) -> Option<RemovedVertex<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>> {
+        if !self.edge_list.contains_key(id) {
+            return None;
+        }
+        let mut incident_edges = Vec::with_capacity(self.edge_list[id].count_incident_e());
+        for edge in self.edge_list[id].iter_incident_e().collect::<Vec<_>>() {
+            // The second half of a directed self-loop is gone together with the first one
+            if let Some(attributes) = self.take_e(&edge.id1, &edge.id2, &edge.edge_id).unwrap() {
+                incident_edges.push((edge, attributes));
+            }
+        }
+        let mut locale = self.edge_list.remove(id).unwrap();
+        self.handles.remove(id);
+        #[cfg(feature = "std")]
+        self.invalidate_invariants();
+        if self.min_free_vertex_id > *id {
+            self.min_free_vertex_id = id.clone();
+        }
+        #[cfg(all(feature = "validate", debug_assertions))]
+        self.debug_validate();
+        Some(RemovedVertex { attributes: core::mem::take(locale.v_attrs_mut()), incident_edges })
+    }
+
+    fn take_e(&mut self, id1: &VertexIdType, id2: &VertexIdType, edge_id: &EdgeIdType) -> CrabNetsResult<Option<EdgeAttributeCollectionType>> {
+        const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::take_e";
         if self.edge_list.contains_key(id1) {
             if self.edge_list.contains_key(id2) {
-                self.edge_list.get_mut(id1).unwrap().remove_e(id2, edge_id);
-                Ok(self.edge_list.get_mut(id2).unwrap().remove_e(id1, edge_id))
+                let answer1 = self.edge_list.get_mut(id1).unwrap().take_e(id2, edge_id);
+                let answer2 = self.edge_list.get_mut(id2).unwrap().take_e(id1, edge_id);
+                #[cfg(feature = "std")]
+                self.invalidate_invariants();
+                #[cfg(all(feature = "validate", debug_assertions))]
+                self.debug_validate();
+                Ok(match (answer1, answer2) {
+                    (None, None) => None,
+                    (answer1, answer2) => Some(answer1.flatten().or(answer2.flatten()).unwrap_or_default()),
+                })
             } else {
-                Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id2)))
+                Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id2.to_string() }))
             }
         } else {
-            Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id1)))
+            Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id1.to_string() }))
         }
     }
 
-    fn remove_v(&mut self, id: &VertexIdType) -> bool {
-        if !self.edge_list.contains_key(id) {
-            return false;
+    fn try_add_e(&mut self, id1: &VertexIdType, id2: &VertexIdType, directed: bool, edge_id: Option<EdgeIdType>) -> CrabNetsResult<EdgeIdType> {
+        const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::try_add_e";
+        // Simple graphs ignore edge IDs, so any edge between the vertices is in the way
+        let is_occupied = match &edge_id {
+            Some(value) => self.contains_e(id1, id2, value).is_some(),
+            None => !LocaleType::IS_MULTI && self.contains_e(id1, id2, &EdgeIdType::default()).is_some(),
+        };
+        if is_occupied {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::EdgeAlreadyExists { id1: id1.to_string(), id2: id2.to_string() }));
         }
-        for edge in self.edge_list[id].iter_incident_e().collect::<Vec<_>>() {
-            self.remove_e(&edge.id1, &edge.id2, &edge.edge_id).unwrap();
+        self.add_e(id1, id2, directed, edge_id)
+    }
+
+    fn try_add_v(&mut self, id: Option<VertexIdType>) -> CrabNetsResult<VertexIdType> {
+        const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::try_add_v";
+        if let Some(value) = &id {
+            if self.edge_list.contains_key(value) {
+                return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexAlreadyExists { id: value.to_string() }));
+            }
         }
-        self.edge_list.remove(id);
-        if self.min_free_vertex_id > *id {
-            self.min_free_vertex_id = id.clone();
+        if self.handles.is_full() {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: handles::HANDLES_EXHAUSTED.to_string() }));
         }
-        true
+        Ok(self.add_v(id))
     }
 
     fn v_attrs_mut(&mut self, id: &VertexIdType) -> CrabNetsResult<&mut VertexAttributeCollectionType> {
         const FUNCTION_PATH: &str = "Graph::BasicMutableGraph::v_attrs_mut";
         match self.edge_list.get_mut(id) {
             Some(value) => Ok(value.v_attrs_mut()),
-            None => Err(CrabNetsError::new(FUNCTION_PATH, format!("Vertex with ID {} doesn't exist.", id)))
+            None => Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: id.to_string() }))
         }
     }
 }
 
+// Graph::Debug
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Debug for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Only the topology is printed, since attribute collections don't have to implement
+    // Debug
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut vertices: Vec<_> = self.iter_v().collect();
+        vertices.sort();
+        let mut edges: Vec<_> = self.iter_e().collect();
+        edges.sort_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id)));
+        f.write_str("Graph { vertices: [")?;
+        for (i, id) in vertices.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { "" } else { ", " }, id)?;
+        }
+        f.write_str("], edges: [")?;
+        for (i, edge) in edges.iter().enumerate() {
+            let arrow = match edge.direction {
+                EdgeDirection::Undirected => "---",
+                EdgeDirection::Directed1to2 => "-->",
+                EdgeDirection::Directed2to1 => "<--",
+            };
+            write!(f, "{}{} {} {} ({})", if i == 0 { "" } else { ", " }, edge.id1, arrow, edge.id2, edge.edge_id)?;
+        }
+        f.write_str("] }")
+    }
+}
+
 // Graph::Default
 impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Default for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
 where
@@ -1305,7 +2245,7 @@ where
     VertexIdType: Id,
 {
     fn default() -> Self {
-        Graph { edge_list: HashMap::new(), min_free_vertex_id: VertexIdType::default(), phantom: PhantomData }
+        Self::new()
     }
 }
 
@@ -1404,8 +2344,9 @@ macro_rules! graph_type_recognition_assistant {
 /// `graph!(A ---X--> A)` will stand for a simple directed  graph  where  only  vertices
 /// have attributes.
 /// 
-/// > ⚠️ **Warning!**  Milti-graphs  are  currently  under  development,  so  structural
-/// patterns with 'double arrows' won't work at this point.
+/// Structural  patterns   with   'double   arrows'   stand   for   multi-graphs,   e.g.
+/// `graph!(X ===A=== X)` will stand for an undirected multi-graph where only edges have
+/// attributes.
 /// 
 /// ## Values of generic type parameters
 /// When you create a graph will this macro,  the  [generic type parameters][typeparams]
@@ -1421,10 +2362,8 @@ macro_rules! graph_type_recognition_assistant {
 /// * `LocaleType` will be substituted with:
 ///     * [`SimpleUndirectedLocale`] if the graph is simple and undirected.
 ///     * [`SimpleDirectedLocale`] if the graph is simple and directed.
-///     * `MultiUndirectedLocale` if the graph is a multi-graph  and  undirected  (under
-///       development).
-///     * `MultiDirectedLocale` if the  graph  is  a  multi-graph  and  directed  (under
-///       development).
+///     * [`MultiUndirectedLocale`] if the graph is a multi-graph and undirected.
+///     * [`MultiDirectedLocale`] if the graph is a multi-graph and directed.
 /// * `VertexAttributeCollectionType` will be substituted with:
 ///     * [`DynamicDispatchAttributeMap<String>`] if the attribute marker  for  vertices
 ///       is '`A`'.
@@ -1703,6 +2642,246 @@ macro_rules! graph {
             /* VertexIdType */ usize
         >
     };
+
+    (X ===X=== X with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiUndirectedLocale<
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (X ===X=== X) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiUndirectedLocale<
+                (),
+                usize,
+                (),
+                usize
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ usize
+        >
+    };
+
+    (A ===X=== A with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiUndirectedLocale<
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (A ===X=== A) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiUndirectedLocale<
+                (),
+                usize,
+                DynamicDispatchAttributeMap<String>,
+                usize
+            >,
+            /* VertexAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* VertexIdType */ usize
+        >
+    };
+
+    (X ===A=== X with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiUndirectedLocale<
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (X ===A=== X) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiUndirectedLocale<
+                DynamicDispatchAttributeMap<String>,
+                usize,
+                (),
+                usize
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ usize
+        >
+    };
+
+    (A ===A=== A with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiUndirectedLocale<
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (A ===A=== A) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiUndirectedLocale<
+                DynamicDispatchAttributeMap<String>,
+                usize,
+                DynamicDispatchAttributeMap<String>,
+                usize
+            >,
+            /* VertexAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* VertexIdType */ usize
+        >
+    };
+
+    (X ===X==> X with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiDirectedLocale<
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (X ===X==> X) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiDirectedLocale<
+                (),
+                usize,
+                (),
+                usize
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ usize
+        >
+    };
+
+    (A ===X==> A with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiDirectedLocale<
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (A ===X==> A) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ (),
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiDirectedLocale<
+                (),
+                usize,
+                DynamicDispatchAttributeMap<String>,
+                usize
+            >,
+            /* VertexAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* VertexIdType */ usize
+        >
+    };
+
+    (X ===A==> X with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiDirectedLocale<
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                (),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (X ===A==> X) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiDirectedLocale<
+                DynamicDispatchAttributeMap<String>,
+                usize,
+                (),
+                usize
+            >,
+            /* VertexAttributeCollectionType */ (),
+            /* VertexIdType */ usize
+        >
+    };
+
+    (A ===A==> A with $($property:ident = $value:ty),+) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* EdgeIdType */ graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+            /* LocaleType */ MultiDirectedLocale<
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], EdgeIdType, usize),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+                graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+            >,
+            /* VertexAttributeCollectionType */ graph_type_recognition_assistant!([$($property = $value),+], VertexAttributeCollectionType, DynamicDispatchAttributeMap<String>),
+            /* VertexIdType */ graph_type_recognition_assistant!([$($property = $value),+], VertexIdType, usize)
+        >
+    };
+
+    (A ===A==> A) => {
+        Graph<
+            /* EdgeAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* EdgeIdType */ usize,
+            /* LocaleType */ MultiDirectedLocale<
+                DynamicDispatchAttributeMap<String>,
+                usize,
+                DynamicDispatchAttributeMap<String>,
+                usize
+            >,
+            /* VertexAttributeCollectionType */ DynamicDispatchAttributeMap<String>,
+            /* VertexIdType */ usize
+        >
+    };
 }
 
 
@@ -1785,7 +2964,7 @@ mod tests {
         assert!(g.add_e(&0, &2, false, None).is_ok());
         assert!(g.add_e(&218, &2, true, None).is_ok_and(|x| x == 0));
         // Remove vertex
-        assert!(g.remove_v(&0));
+        assert!(g.remove_v(&0).is_some());
         assert!(g.v_degree(&0).is_err());
         assert!(g.v_degree(&1).is_ok_and(|x| x == 0));
         assert!(g.v_degree(&2).is_ok_and(|x| x == 1));
@@ -1800,4 +2979,549 @@ mod tests {
         assert!(g.iter_adjacent(&3).is_ok_and(|x| x.collect::<Vec<_>>() == vec![0usize; 0]));
         assert!(g.iter_adjacent_out(&2).is_ok_and(|x| x.collect::<Vec<_>>() == vec![0usize; 0]));
     }
+
+    #[test]
+    fn edge_direction_queries() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&2, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&2, &3, false, None).unwrap();
+        assert!(g.contains_directed_e(&0, &1) && !g.contains_directed_e(&1, &0));
+        assert!(!g.contains_directed_e(&2, &3) && !g.contains_directed_e(&0, &7));
+        assert_eq!(g.e_direction_between(&0, &1), Some(EdgeDirection::Directed1to2));
+        assert_eq!(g.e_direction_between(&1, &0), Some(EdgeDirection::Directed2to1));
+        assert_eq!(g.e_direction_between(&1, &2), Some(EdgeDirection::Undirected));
+        assert_eq!(g.e_direction_between(&3, &2), Some(EdgeDirection::Undirected));
+        assert_eq!(g.e_direction_between(&0, &3), None);
+        assert_eq!(EdgeDirection::Directed1to2.reversed(), EdgeDirection::Directed2to1);
+    }
+
+    #[test]
+    fn graph_cow() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        g.add_e(&0, &1, true, None).unwrap();
+        let mut h: GraphCow<_, _, _, _, _> = Cow::Borrowed(&g);
+        assert_eq!(h.iter_adjacent_out(&0).unwrap().collect::<Vec<_>>(), vec![1]);
+        assert!(matches!(h, Cow::Borrowed(_)));
+        h.add_v(None);
+        h.add_e(&1, &2, true, None).unwrap();
+        assert!(matches!(h, Cow::Owned(_)));
+        assert_eq!((h.count_v(), h.count_e()), (3, 2));
+        assert_eq!((g.count_v(), g.count_e()), (2, 1));
+    }
+
+    #[test]
+    fn smart_pointer_containers() {
+        let mut g: Box<graph!(X ---X--- X)> = Box::default();
+        g.add_v(None);
+        g.add_v(None);
+        g.add_v(None);
+        g.add_e(&0, &1, false, None).unwrap();
+        assert_eq!(g.count_e(), 1);
+        g.remove_v(&0);
+        assert!(g.iter_adjacent(&1).unwrap().next().is_none());
+        assert!(g.contains_v(&2) && !g.contains_v(&0));
+        let h = Rc::new(RefCell::new(g.as_ref().clone()));
+        let h_copy = h.clone();
+        h.borrow_graph_mut().add_e(&1, &2, false, None).unwrap();
+        assert_eq!(h_copy.borrow_graph().count_e(), 1);
+        assert!(h_copy.borrow_graph().iter_adjacent(&1).unwrap().eq([2]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn shared_graph_across_threads() {
+        let g: Arc<RwLock<graph!(X ---X--- X)>> = Arc::default();
+        let workers = (0..4).map(|_| {
+            let g = g.clone();
+            std::thread::spawn(move || {
+                let id = g.borrow_graph_mut().add_v(None);
+                g.borrow_graph().contains_v(&id)
+            })
+        }).collect::<Vec<_>>();
+        assert!(workers.into_iter().all(|x| x.join().unwrap()));
+        assert_eq!(g.borrow_graph().count_v(), 4);
+    }
+
+    #[test]
+    fn take_attributes_on_removal() {
+        let mut g: graph!(A ---A--> A) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        let id3 = g.add_v(None);
+        g.add_e(&id1, &id2, true, None).unwrap();
+        g.add_e(&id3, &id2, false, None).unwrap();
+        g.v_attrs_mut(&id2).unwrap().insert("name".to_string(), Box::new("hub".to_string()));
+        g.e_attrs_mut(&id1, &id2, &0).unwrap().insert("weight".to_string(), Box::new(2u8));
+        // Take edge
+        let attributes = g.take_e(&id2, &id1, &0).unwrap().unwrap();
+        assert!(attributes.get(&"weight".to_string()).is_some_and(|x| x.downcast::<u8>() == Some(&2)));
+        assert!(g.take_e(&id2, &id1, &0).is_ok_and(|x| x.is_none()));
+        assert!(g.take_e(&id2, &5, &0).is_err());
+        // Remove vertex
+        let removed = g.remove_v(&id2).unwrap();
+        assert!(removed.attributes.get(&"name".to_string()).is_some());
+        assert_eq!(removed.incident_edges.len(), 1);
+        assert!(removed.incident_edges[0].0.id2 == id3);
+        assert!(g.remove_v(&id2).is_none());
+        assert_eq!(g.count_e(), 0);
+    }
+
+    #[test]
+    fn non_destructive_additions() {
+        let mut g: graph!(A ---X--- A) = Graph::new();
+        assert!(g.try_add_v(Some(3)).is_ok_and(|x| x == 3));
+        assert!(g.try_add_v(Some(3)).is_err());
+        assert!(g.try_add_v(None).is_ok_and(|x| x == 0));
+        g.get_or_add_v(3).insert("name".to_string(), Box::new(1u8));
+        assert!(g.get_or_add_v(3).get(&"name".to_string()).is_some());
+        assert!(g.get_or_add_v(1).get(&"name".to_string()).is_none());
+        assert_eq!(g.count_v(), 3);
+        assert!(g.try_add_e(&0, &3, false, None).is_ok());
+        assert!(g.try_add_e(&3, &0, false, Some(5)).is_err());
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        assert!(g.try_add_e(&0, &1, true, None).is_ok_and(|x| x == 0));
+        assert!(g.try_add_e(&0, &1, true, None).is_ok_and(|x| x == 1));
+        assert!(g.try_add_e(&1, &0, true, Some(1)).is_err());
+    }
+
+    #[test]
+    fn multi_graph_self_loops() {
+        let mut g: graph!(X ===A==> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        // Parallel directed self-loops keep both of their halves and their attributes
+        assert!(g.add_e(&0, &0, true, None).is_ok_and(|x| x == 0));
+        assert!(g.add_e(&0, &0, true, None).is_ok_and(|x| x == 1));
+        g.e_attrs_mut(&0, &0, &1).unwrap().insert("weight".to_string(), Box::new(2.0f64));
+        assert_eq!(g.count_e(), 2);
+        assert!(g.v_degree_out(&0).is_ok_and(|x| x == 2));
+        assert!(g.v_degree_in(&0).is_ok_and(|x| x == 2));
+        assert_eq!(g.iter_e().filter(|x| x.id1 == 0 && x.id2 == 0).count(), 2);
+        assert!(g.e_attrs(&0, &0, &1).unwrap().get(&"weight".to_string()).is_some());
+        // Replacing a self-loop by its ID
+        assert!(g.add_e(&0, &0, true, Some(1)).is_ok_and(|x| x == 1));
+        assert_eq!(g.count_e(), 2);
+        assert!(g.e_attrs(&0, &0, &1).unwrap().get(&"weight".to_string()).is_none());
+        assert!(g.take_e(&0, &0, &0).is_ok_and(|x| x.is_some()));
+        assert_eq!(g.count_e(), 1);
+        assert!(g.v_degree(&0).is_ok_and(|x| x == 2));
+    }
+
+    #[test]
+    fn multi_graph_vertex_and_edge_operations() {
+        // Directed multi-graph with edge attributes
+        let mut g: graph!(X ===A==> X) = Graph::new();
+        assert_eq!(g.add_v(None), 0);
+        assert_eq!(g.add_v(None), 1);
+        // Parallel edges
+        assert!(g.add_e(&0, &1, true, None).is_ok_and(|x| x == 0));
+        assert!(g.add_e(&1, &0, true, None).is_ok_and(|x| x == 1));
+        assert!(g.add_e(&0, &1, false, Some(7)).is_ok_and(|x| x == 7));
+        assert_eq!(g.count_e(), 3);
+        assert!(g.v_degree(&0).is_ok_and(|x| x == 3));
+        assert!(g.v_degree_out(&1).is_ok_and(|x| x == 1));
+        assert!(g.count_parallel_e(&1, &0).is_ok_and(|x| x == 3));
+        assert!(g.count_parallel_e(&0, &2).is_err());
+        assert!(g.iter_adjacent(&0).is_ok_and(|x| x.collect::<Vec<_>>() == vec![1]));
+        assert!(g.e_attrs(&1, &0, &7).is_ok());
+        assert!(g.e_attrs(&1, &0, &3).is_err());
+        // Remove edges
+        assert!(g.remove_e(&0, &1, &1).is_ok_and(|x| x));
+        assert_eq!(g.count_e(), 2);
+        assert!(g.v_degree_in(&0).is_ok_and(|x| x == 0));
+        assert!(g.count_parallel_e(&0, &1).is_ok_and(|x| x == 2));
+        // Replacing an edge keeps the degrees intact
+        assert!(g.add_e(&1, &0, false, Some(7)).is_ok_and(|x| x == 7));
+        assert!(g.v_degree(&0).is_ok_and(|x| x == 2));
+        assert!(g.v_degree_undir(&1).is_ok_and(|x| x == 1));
+        // Remove vertex
+        assert!(g.remove_v(&1).is_some());
+        assert!(g.v_degree(&0).is_ok_and(|x| x == 0));
+        // Undirected multi-graph
+        let mut g: graph!(X ===X=== X) = Graph::new();
+        assert_eq!(g.add_v(None), 0);
+        assert_eq!(g.add_v(None), 1);
+        assert_eq!(g.add_v(None), 2);
+        assert!(g.add_e(&0, &1, false, None).is_ok_and(|x| x == 0));
+        assert!(g.add_e(&1, &0, false, None).is_ok_and(|x| x == 1));
+        assert!(g.add_e(&1, &0, false, Some(1)).is_ok_and(|x| x == 1));
+        assert!(g.add_e(&1, &2, false, None).is_ok());
+        assert!(g.count_parallel_e(&0, &1).is_ok_and(|x| x == 2));
+        assert!(g.v_degree(&1).is_ok_and(|x| x == 3));
+        assert!(g.remove_e(&0, &1, &0).is_ok_and(|x| x));
+        assert!(g.v_degree(&1).is_ok_and(|x| x == 2));
+        assert!(g.remove_v(&0).is_some());
+        assert!(g.v_degree(&1).is_ok_and(|x| x == 1));
+        assert!(g.count_parallel_e(&2, &1).is_ok_and(|x| x == 1));
+    }
+
+    #[test]
+    fn vertex_selection() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        // 0 -> 1 -> 2, 3 --- 4, and 5 is isolated
+        assert!(g.add_e(&0, &1, true, None).is_ok());
+        assert!(g.add_e(&1, &2, true, None).is_ok());
+        assert!(g.add_e(&3, &4, false, None).is_ok());
+        assert_eq!(g.iter_isolated_v().collect::<Vec<_>>(), vec![5]);
+        let mut leaves = g.iter_leaves().collect::<Vec<_>>();
+        leaves.sort();
+        assert_eq!(leaves, vec![0, 2, 3, 4]);
+        assert_eq!(g.iter_sources().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(g.iter_sinks().collect::<Vec<_>>(), vec![2]);
+        // Self-loops count as incident edges
+        assert!(g.add_e(&5, &5, true, None).is_ok());
+        assert_eq!(g.iter_isolated_v().count(), 0);
+    }
+
+    #[test]
+    fn common_and_union_neighbours() {
+        fn neighbours<G: ImmutableGraphContainer>(g: &G, id1: &G::VertexIdType, id2: &G::VertexIdType) -> (bool, usize, usize) {
+            let edge_list = &ImmutableGraphContainer::unwrap(g).edge_list;
+            let (locale1, locale2) = (&edge_list[id1], &edge_list[id2]);
+            assert_eq!(locale1.count_common_adjacent(locale2), locale2.count_common_adjacent(locale1));
+            (locale1.contains_adjacent(id2), locale1.count_common_adjacent(locale2), locale1.count_union_adjacent(locale2))
+        }
+        // Simple undirected graph
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 4)] {
+            assert!(g.add_e(&id1, &id2, false, None).is_ok());
+        }
+        let g = Box::new(g);
+        assert_eq!(neighbours(&g, &0, &1), (true, 2, 4));
+        assert_eq!(neighbours(&g, &3, &4), (false, 0, 3));
+        // Directed multi-graph with neighbours reachable in several directions
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        for (id1, id2, directed) in [(0, 2, true), (2, 0, true), (0, 2, true), (0, 3, false), (1, 2, false), (3, 1, true)] {
+            assert!(g.add_e(&id1, &id2, directed, None).is_ok());
+        }
+        let g = Box::new(g);
+        assert_eq!(neighbours(&g, &0, &1), (false, 2, 2));
+        assert_eq!(neighbours(&g, &2, &3), (false, 2, 2));
+        assert_eq!(neighbours(&g, &0, &2), (true, 0, 4));
+    }
+
+    #[test]
+    fn attributeless_locale() {
+        // Simple undirected locale for up to 64 vertices with a bitset of neighbours
+        #[derive(Clone)]
+        struct BitsetLocale {
+            id: u8,
+            neighbours: u64,
+        }
+
+        impl BitsetLocale {
+            fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+                (0..64).filter(|x| self.neighbours & (1 << x) != 0)
+            }
+
+            fn edge(&self, id2: u8) -> EdgeIteratorItem<u8, u8> {
+                EdgeIteratorItem { direction: EdgeDirection::Undirected, edge_id: 0, id1: self.id, id2 }
+            }
+        }
+
+        impl AttributelessLocale for BitsetLocale {}
+
+        impl LocaleTopology<(), u8, (), u8> for BitsetLocale {
+            fn add_e(&mut self, id2: u8, _relation: EdgeToVertexRelation, _edge_id: Option<u8>, _store_edge_attributes: bool) -> u8 {
+                self.neighbours |= 1 << id2;
+                0
+            }
+
+            fn associated_v(&self) -> &u8 {
+                &self.id
+            }
+
+            fn count_adjacent(&self) -> usize {
+                self.neighbours.count_ones() as usize
+            }
+
+            fn count_adjacent_in(&self) -> usize {
+                0
+            }
+
+            fn count_adjacent_out(&self) -> usize {
+                0
+            }
+
+            fn count_adjacent_undir(&self) -> usize {
+                self.count_adjacent()
+            }
+
+            fn count_incident_e(&self) -> usize {
+                self.count_adjacent()
+            }
+
+            fn count_incident_e_in(&self) -> usize {
+                0
+            }
+
+            fn count_incident_e_out(&self) -> usize {
+                0
+            }
+
+            fn count_incident_e_undir(&self) -> usize {
+                self.count_adjacent()
+            }
+
+            fn iter_adjacent<'a>(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
+                Box::new(self.iter())
+            }
+
+            fn iter_adjacent_in<'a>(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
+                Box::new(core::iter::empty())
+            }
+
+            fn iter_adjacent_out<'a>(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
+                Box::new(core::iter::empty())
+            }
+
+            fn iter_adjacent_undir<'a>(&'a self) -> Box<dyn Iterator<Item = u8> + 'a> {
+                Box::new(self.iter())
+            }
+
+            fn e_direction(&self, id2: &u8, _edge_id: &u8) -> Option<EdgeDirection> {
+                self.contains_adjacent(id2).then_some(EdgeDirection::Undirected)
+            }
+
+            fn iter_incident_e<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<u8, u8>> + 'a>
+            where
+                u8: 'a,
+            {
+                Box::new(self.iter().map(|x| self.edge(x)))
+            }
+
+            fn iter_incident_e_in<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<u8, u8>> + 'a>
+            where
+                u8: 'a,
+            {
+                Box::new(core::iter::empty())
+            }
+
+            fn iter_incident_e_out<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<u8, u8>> + 'a>
+            where
+                u8: 'a,
+            {
+                Box::new(core::iter::empty())
+            }
+
+            fn iter_incident_e_undir<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<u8, u8>> + 'a>
+            where
+                u8: 'a,
+            {
+                self.iter_incident_e()
+            }
+
+            fn iter_incident_e_with_attrs<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<u8, u8>> + 'a>
+            where
+                u8: 'a,
+            {
+                Box::new(self.iter().filter(|&x| self.id <= x).map(|x| self.edge(x)))
+            }
+
+            fn new(associated_vertex_id: u8) -> Self {
+                Self { id: associated_vertex_id, neighbours: 0 }
+            }
+
+            fn remove_e(&mut self, id2: &u8, _edge_id: &u8) -> bool {
+                let answer = self.contains_adjacent(id2);
+                self.neighbours &= !(1 << id2);
+                answer
+            }
+
+            fn remove_neighbour(&mut self, id2: &u8) -> bool {
+                self.remove_e(id2, &0)
+            }
+        }
+
+        let mut g: Graph<(), u8, BitsetLocale, (), u8> = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (0, 2), (1, 2), (2, 3)] {
+            assert!(g.add_e(&id1, &id2, false, None).is_ok());
+        }
+        assert!(g.iter_adjacent(&2).is_ok_and(|x| x.collect::<Vec<_>>() == vec![0, 1, 3]));
+        // Default implementations of the locale
+        assert!(g.count_parallel_e(&2, &3).is_ok_and(|x| x == 1));
+        assert!(g.count_parallel_e(&1, &3).is_ok_and(|x| x == 0));
+        assert_eq!(g.edge_list[&0].count_common_adjacent(&g.edge_list[&1]), 1);
+        assert_eq!(g.edge_list[&2].count_union_adjacent(&g.edge_list[&3]), 4);
+        assert!(g.try_add_e(&3, &2, false, None).is_err_and(|x| matches!(x.kind(), ErrorKind::EdgeAlreadyExists { .. })));
+        assert!(g.take_e(&3, &2, &0).is_ok_and(|x| x == Some(())));
+        assert!(g.take_e(&3, &2, &0).is_ok_and(|x| x.is_none()));
+        assert!(g.add_e(&2, &3, false, None).is_ok());
+        assert!(g.e_attrs(&2, &0, &0).is_ok());
+        assert!(g.e_attrs_mut(&0, &2, &0).is_ok());
+        assert!(g.v_attrs(&1).is_ok());
+        assert!(g.validate().is_valid());
+        assert!(g.remove_e(&1, &0, &0).is_ok_and(|x| x));
+        assert_eq!(g.iter_e().count(), 3);
+        assert!(g.remove_v(&2).is_some());
+        assert_eq!(g.iter_e().count(), 0);
+    }
+
+    #[test]
+    fn default_graph_methods() {
+        // Container that only implements the required methods of `BasicMutableGraph`
+        #[derive(Clone, Default)]
+        struct Plain(graph!(X ---A--> X));
+
+        impl ImmutableGraphContainer for Plain {
+            type EdgeAttributeCollectionType = DynamicDispatchAttributeMap<String>;
+            type EdgeIdType = u8;
+            type LocaleType = SimpleDirectedLocale<DynamicDispatchAttributeMap<String>, (), usize>;
+            type VertexAttributeCollectionType = ();
+            type VertexIdType = usize;
+
+            fn unwrap(&self) -> &graph!(X ---A--> X) {
+                &self.0
+            }
+        }
+
+        impl BasicMutableGraph<DynamicDispatchAttributeMap<String>, u8, (), usize> for Plain {
+            fn add_e(&mut self, id1: &usize, id2: &usize, directed: bool, edge_id: Option<u8>) -> CrabNetsResult<u8> {
+                self.0.add_e(id1, id2, directed, edge_id)
+            }
+
+            fn add_v(&mut self, id: Option<usize>) -> usize {
+                self.0.add_v(id)
+            }
+
+            fn e_attrs_mut(&mut self, id1: &usize, id2: &usize, edge_id: &u8) -> CrabNetsResult<&mut DynamicDispatchAttributeMap<String>> {
+                self.0.e_attrs_mut(id1, id2, edge_id)
+            }
+
+            fn remove_e(&mut self, id1: &usize, id2: &usize, edge_id: &u8) -> CrabNetsResult<bool> {
+                self.0.remove_e(id1, id2, edge_id)
+            }
+
+            fn remove_v(&mut self, id: &usize) -> Option<RemovedVertex<DynamicDispatchAttributeMap<String>, u8, (), usize>> {
+                self.0.remove_v(id)
+            }
+
+            fn v_attrs_mut(&mut self, id: &usize) -> CrabNetsResult<&mut ()> {
+                self.0.v_attrs_mut(id)
+            }
+        }
+
+        let mut g = Plain::default();
+        assert!(g.try_add_v(None).is_ok_and(|x| x == 0));
+        assert!(g.try_add_v(Some(0)).is_err_and(|x| matches!(x.kind(), ErrorKind::VertexAlreadyExists { .. })));
+        g.get_or_add_v(1);
+        g.get_or_add_v(1);
+        assert_eq!(g.count_v(), 2);
+        assert!(g.try_add_e(&0, &1, true, None).is_ok());
+        assert!(g.try_add_e(&1, &0, false, None).is_err_and(|x| matches!(x.kind(), ErrorKind::EdgeAlreadyExists { .. })));
+        assert!(g.try_add_e(&0, &2, true, None).is_err_and(|x| matches!(x.kind(), ErrorKind::VertexNotFound { .. })));
+        g.e_attrs_mut(&0, &1, &0).unwrap().insert("weight".to_string(), Box::new(2.0f64));
+        assert!(g.take_e(&1, &0, &0).is_ok_and(|x| x.is_some_and(|y| y.get(&"weight".to_string()).is_some())));
+        assert!(g.take_e(&1, &0, &0).is_ok_and(|x| x.is_none()));
+        assert!(g.take_e(&1, &2, &0).is_err());
+        assert_eq!(g.count_e(), 0);
+    }
+
+    #[test]
+    fn replace_vertex() {
+        let mut g: graph!(X ---A--> X) = Graph::new();
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        assert_eq!(g.add_v(Some(1)), 1);
+        assert_eq!(g.count_v(), 3);
+        assert_eq!(g.count_e(), 1);
+        assert!(g.v_degree(&1).is_ok_and(|x| x == 0));
+        assert!(g.v_degree_out(&0).is_ok_and(|x| x == 1));
+        assert!(g.iter_adjacent(&2).is_ok_and(|x| x.collect::<Vec<_>>() == vec![0]));
+        assert!(g.contains_e(&0, &1, &0).is_none());
+    }
+
+    #[test]
+    fn simple_graph_self_loops() {
+        let mut g: graph!(X ---A--> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        assert!(g.add_e(&1, &1, true, None).is_ok());
+        g.e_attrs_mut(&1, &1, &0).unwrap().insert("weight".to_string(), Box::new(2.0f64));
+        assert_eq!(g.count_e(), 1);
+        assert!(g.v_degree_out(&1).is_ok_and(|x| x == 1));
+        assert!(g.v_degree_in(&1).is_ok_and(|x| x == 1));
+        assert_eq!(g.iter_e().count(), 1);
+        assert!(g.validate().is_valid());
+        // Adding the self-loop again replaces it
+        assert!(g.add_e(&1, &1, true, None).is_ok());
+        assert_eq!(g.count_e(), 1);
+        assert!(g.e_attrs(&1, &1, &0).unwrap().get(&"weight".to_string()).is_none());
+        g.e_attrs_mut(&1, &1, &0).unwrap().insert("weight".to_string(), Box::new(3.0f64));
+        g.add_e(&0, &1, true, None).unwrap();
+        let removed = g.remove_v(&1).unwrap();
+        assert_eq!(removed.incident_edges.len(), 2);
+        assert!(removed.incident_edges.iter().any(|(x, y)| x.id2 == 1 && y.get(&"weight".to_string()).is_some()));
+        assert_eq!(g.count_e(), 0);
+    }
+
+    #[test]
+    fn debug_format() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        g.add_v(None);
+        assert!(g.add_e(&2, &0, true, None).is_ok());
+        assert!(g.add_e(&0, &1, false, None).is_ok());
+        assert_eq!(format!("{:?}", g), "Graph { vertices: [0, 1, 2], edges: [0 --- 1 (0), 2 --> 0 (0)] }");
+    }
+
+    #[test]
+    fn structured_errors() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        g.add_v(None);
+        g.add_v(None);
+        g.add_e(&0, &1, true, None).unwrap();
+        let error = g.v_degree(&5).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::VertexNotFound { id } if id == "5"));
+        assert_eq!(error.to_string(), "CrabNetsError: Graph::BasicImmutableGraph::v_degree. Vertex with ID 5 doesn't exist.");
+        assert!(matches!(g.try_add_e(&1, &0, true, None).unwrap_err().kind(), ErrorKind::EdgeAlreadyExists { .. }));
+        assert!(matches!(g.e_attrs(&0, &2, &0).unwrap_err().kind(), ErrorKind::VertexNotFound { .. }));
+    }
+
+    #[test]
+    fn newtype_ids() {
+        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+        struct UserId(u64);
+
+        impl Display for UserId {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "user{}", self.0)
+            }
+        }
+
+        implement_id_for_newtype!(UserId);
+
+        let mut g: graph!(X ---X--- X with VertexIdType = UserId) = Graph::new();
+        assert_eq!(g.add_v(None), UserId(0));
+        assert_eq!(g.add_v(None), UserId(1));
+        g.add_v(Some(UserId(5)));
+        assert!(g.add_e(&UserId(1), &UserId(5), false, None).is_ok());
+        assert!(matches!(g.v_degree(&UserId(3)).unwrap_err().kind(), ErrorKind::VertexNotFound { id } if id == "user3"));
+    }
 }
@@ -0,0 +1,444 @@
+//! # Limits module
+//! 
+//! ## Description
+//! This module contains [`GuardedGraph`], a graph container that enforces  the  maximum
+//! numbers of vertices and edges and the maximum size of  [attributes][attrs]  of  each
+//! vertex and edge.
+//! 
+//! Graphs read from untrusted files can be arbitrarily large. Reading such a file  into
+//! a [`GuardedGraph`] instead of a plain [`Graph`] stops the import with  an  error  as
+//! soon as one of the limits is exceeded, before the graph exhausts the memory.
+//! 
+//! [attrs]: crate::Graph#attributes
+//! [`Graph`]: crate::Graph
+use std::mem::{size_of, size_of_val};
+use crate::{attributes::{AttributeCollection, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, io::AttributeCollectionIO, locales::LocaleTopology, BasicImmutableGraph, BasicMutableGraph, Graph, Id, ImmutableGraphContainer, MutableGraphContainer, RemovedVertex};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY FUNCTIONS                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+fn attrs_size<AttributeCollectionType>(attrs: &AttributeCollectionType) -> usize
+where
+    AttributeCollectionType: AttributeCollectionIO,
+{
+    attrs.io_iter_contents().map(|token| token.name.len() + value_size(&token.value)).sum()
+}
+
+fn value_size(value: &StaticDispatchAttributeValue) -> usize {
+    match value {
+        StaticDispatchAttributeValue::Int8(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Int16(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Int32(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Int64(x) => size_of_val(x),
+        StaticDispatchAttributeValue::UInt8(x) => size_of_val(x),
+        StaticDispatchAttributeValue::UInt16(x) => size_of_val(x),
+        StaticDispatchAttributeValue::UInt32(x) => size_of_val(x),
+        StaticDispatchAttributeValue::UInt64(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Float32(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Float64(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Bool(x) => size_of_val(x),
+        StaticDispatchAttributeValue::Str(x) => x.len(),
+        StaticDispatchAttributeValue::VecInt8(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecInt16(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecInt32(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecInt64(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecUInt8(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecUInt16(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecUInt32(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecUInt64(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecFloat32(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecFloat64(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecBool(x) => size_of_val(x.as_slice()),
+        StaticDispatchAttributeValue::VecStr(x) => x.iter().map(String::len).sum(),
+        StaticDispatchAttributeValue::SetInt8(x) => x.len() * size_of::<i8>(),
+        StaticDispatchAttributeValue::SetInt16(x) => x.len() * size_of::<i16>(),
+        StaticDispatchAttributeValue::SetInt32(x) => x.len() * size_of::<i32>(),
+        StaticDispatchAttributeValue::SetInt64(x) => x.len() * size_of::<i64>(),
+        StaticDispatchAttributeValue::SetUInt8(x) => x.len() * size_of::<u8>(),
+        StaticDispatchAttributeValue::SetUInt16(x) => x.len() * size_of::<u16>(),
+        StaticDispatchAttributeValue::SetUInt32(x) => x.len() * size_of::<u32>(),
+        StaticDispatchAttributeValue::SetUInt64(x) => x.len() * size_of::<u64>(),
+        StaticDispatchAttributeValue::SetBool(x) => x.len() * size_of::<bool>(),
+        StaticDispatchAttributeValue::SetStr(x) => x.iter().map(String::len).sum(),
+    }
+}
+
+
+
+#[derive(Clone, Copy)]
+enum Limit {
+    AttrsSize,
+    CountE,
+    CountV,
+}
+
+
+
+#[derive(Clone)]
+enum UncheckedAttrs<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    Edge(VertexIdType, VertexIdType, EdgeIdType),
+    Vertex(VertexIdType),
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * GUARDED GRAPH                                                                     *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Graph with enforced limits
+/// 
+/// ## Description
+/// A graph container that wraps another [mutable graph container][mgc] and  refuses  to
+/// grow beyond the given limits.
+/// 
+/// ## Generic type parameters
+/// * `ContainerType` - the wrapped container, e.g. `Box<Graph>`.
+/// * `MAX_COUNT_V` - the maximum number of vertices.
+/// * `MAX_COUNT_E` - the maximum number of edges.
+/// * `MAX_ATTRS_SIZE` - the maximum size of the [attributes][attrs] of a single  vertex
+/// or edge in bytes, i.e. the total length of their names and values.
+/// 
+/// ## Details
+/// The limits are parts of the type, so the readers of  the  [`io`][io]  module,  which
+/// create graphs on their own, can read  straight  into  a  `GuardedGraph`,  e.g.  into
+/// `GuardedGraph<Box<graph!(A ---A--> A)>, 1000, 10000, 256>`.   Set   a    limit    to
+/// `usize::MAX` to disable it.
+/// 
+/// All operations that return [`CrabNetsResult`] fail with an error once  a  limit  has
+/// been exceeded. The error is recorded, so that all further fallible  operations  fail
+/// as well, and readers, which propagate the errors of [`add_e`][add_e], stop  reading.
+/// Operations that can't  fail,  namely  [`add_v`][add_v]  and  [`get_or_add_v`][goav],
+/// still add the vertex that exceeds the limit but then replace it with the  next  such
+/// vertex, so the memory consumption stays bounded. The size of attributes  is  checked
+/// lazily, on the next operation after the attributes were given out for  modification,
+/// and the attributes that are too big are cleared.  Call  [`GuardedGraph::into_inner`]
+/// to get the wrapped container or the recorded error.
+/// 
+/// The numbers of vertices  and  edges  are  compared  with  the  limits  before  every
+/// addition, which is O(1).
+/// 
+/// [add_e]: crate::BasicMutableGraph::add_e
+/// [add_v]: crate::BasicMutableGraph::add_v
+/// [attrs]: crate::Graph#attributes
+/// [goav]: crate::BasicMutableGraph::get_or_add_v
+/// [io]: crate::io
+/// [mgc]: crate::MutableGraphContainer
+#[derive(Clone)]
+pub struct GuardedGraph<ContainerType, const MAX_COUNT_V: usize, const MAX_COUNT_E: usize, const MAX_ATTRS_SIZE: usize>
+where
+    ContainerType: MutableGraphContainer,
+{
+    container: ContainerType,
+    count_e: usize,
+    overflow_v: Option<ContainerType::VertexIdType>,
+    unchecked_attrs: Option<UncheckedAttrs<ContainerType::EdgeIdType, ContainerType::VertexIdType>>,
+    violation: Option<Limit>,
+}
+
+// GuardedGraph::GuardedGraph
+impl<ContainerType, const MAX_COUNT_V: usize, const MAX_COUNT_E: usize, const MAX_ATTRS_SIZE: usize> GuardedGraph<ContainerType, MAX_COUNT_V, MAX_COUNT_E, MAX_ATTRS_SIZE>
+where
+    ContainerType: MutableGraphContainer,
+    ContainerType::EdgeAttributeCollectionType: AttributeCollectionIO,
+    ContainerType::VertexAttributeCollectionType: AttributeCollectionIO,
+{
+    /// # Wrap container
+    /// 
+    /// ## Arguments
+    /// * `container` : `ContainerType` - the container to wrap.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<GuardedGraph>` - `Ok(value)` is  returned  if  `container`  is
+    /// within  the  limits,  `value`  in  this   case   is   the   guarded   container;
+    /// `Err(CrabNetsError)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(1).
+    pub fn new(container: ContainerType) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "GuardedGraph::GuardedGraph::new";
+        let mut answer = GuardedGraph { count_e: 0, container, overflow_v: None, unchecked_attrs: None, violation: None };
+        answer.count_e = answer.iter_e().count();
+        if answer.count_v() > MAX_COUNT_V {
+            answer.violate(Limit::CountV);
+        } else if answer.count_e > MAX_COUNT_E {
+            answer.violate(Limit::CountE);
+        } else if answer.iter_v().any(|id| attrs_size(answer.v_attrs(&id).unwrap()) > MAX_ATTRS_SIZE) || answer.iter_e().any(|edge| attrs_size(answer.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap()) > MAX_ATTRS_SIZE) {
+            answer.violate(Limit::AttrsSize);
+        }
+        answer.check(FUNCTION_PATH)?;
+        Ok(answer)
+    }
+
+    /// # Unwrap container
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<ContainerType>` - `Ok(value)` is returned if no limit has ever
+    /// been   exceeded,   `value`   in   this   case   is   the   wrapped    container;
+    /// `Err(CrabNetsError)` with  the  description  of  the  first  exceeded  limit  is
+    /// returned otherwise.
+    pub fn into_inner(mut self) -> CrabNetsResult<ContainerType> {
+        const FUNCTION_PATH: &str = "GuardedGraph::GuardedGraph::into_inner";
+        self.check_attrs();
+        self.check(FUNCTION_PATH)?;
+        Ok(self.container)
+    }
+
+    fn check(&self, function_path: &str) -> CrabNetsResult<()> {
+        match &self.violation {
+            Some(limit) => {
+                let (limit, maximum) = match limit {
+                    Limit::AttrsSize => ("size of attributes in bytes", MAX_ATTRS_SIZE),
+                    Limit::CountE => ("number of edges", MAX_COUNT_E),
+                    Limit::CountV => ("number of vertices", MAX_COUNT_V),
+                };
+                Err(CrabNetsError::new(function_path, ErrorKind::LimitExceeded { limit: limit.to_string(), maximum }))
+            },
+            None => Ok(()),
+        }
+    }
+
+    fn check_attrs(&mut self) {
+        let too_big = match self.unchecked_attrs.take() {
+            Some(UncheckedAttrs::Edge(id1, id2, edge_id)) => match MutableGraphContainer::unwrap(&mut self.container).e_attrs_mut(&id1, &id2, &edge_id) {
+                Ok(attrs) if attrs_size(attrs) > MAX_ATTRS_SIZE => {
+                    *attrs = AttributeCollection::new();
+                    true
+                },
+                _ => false,
+            },
+            Some(UncheckedAttrs::Vertex(id)) => match MutableGraphContainer::unwrap(&mut self.container).v_attrs_mut(&id) {
+                Ok(attrs) if attrs_size(attrs) > MAX_ATTRS_SIZE => {
+                    *attrs = AttributeCollection::new();
+                    true
+                },
+                _ => false,
+            },
+            None => false,
+        };
+        if too_big {
+            self.violate(Limit::AttrsSize);
+        }
+    }
+
+    fn is_new_e(&self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &Option<ContainerType::EdgeIdType>) -> bool {
+        match (edge_id, ContainerType::LocaleType::IS_MULTI) {
+            (Some(value), true) => self.contains_e(id1, id2, value).is_none(),
+            (None, true) => true,
+            (_, false) => self.contains_e(id1, id2, &Id::default()).is_none(),
+        }
+    }
+
+    fn violate(&mut self, limit: Limit) {
+        if self.violation.is_none() {
+            self.violation = Some(limit);
+        }
+    }
+}
+
+// GuardedGraph::Default
+impl<ContainerType, const MAX_COUNT_V: usize, const MAX_COUNT_E: usize, const MAX_ATTRS_SIZE: usize> Default for GuardedGraph<ContainerType, MAX_COUNT_V, MAX_COUNT_E, MAX_ATTRS_SIZE>
+where
+    ContainerType: MutableGraphContainer,
+{
+    fn default() -> Self {
+        GuardedGraph { container: ContainerType::default(), count_e: 0, overflow_v: None, unchecked_attrs: None, violation: None }
+    }
+}
+
+// GuardedGraph::ImmutableGraphContainer
+impl<ContainerType, const MAX_COUNT_V: usize, const MAX_COUNT_E: usize, const MAX_ATTRS_SIZE: usize> ImmutableGraphContainer for GuardedGraph<ContainerType, MAX_COUNT_V, MAX_COUNT_E, MAX_ATTRS_SIZE>
+where
+    ContainerType: MutableGraphContainer,
+{
+    type EdgeAttributeCollectionType = ContainerType::EdgeAttributeCollectionType;
+    type EdgeIdType = ContainerType::EdgeIdType;
+    type LocaleType = ContainerType::LocaleType;
+    type VertexAttributeCollectionType = ContainerType::VertexAttributeCollectionType;
+    type VertexIdType = ContainerType::VertexIdType;
+
+    #[inline]
+    fn unwrap(&self) -> &Graph<Self::EdgeAttributeCollectionType, Self::EdgeIdType, Self::LocaleType, Self::VertexAttributeCollectionType, Self::VertexIdType> {
+        ImmutableGraphContainer::unwrap(&self.container)
+    }
+}
+
+// GuardedGraph::BasicMutableGraph
+impl<ContainerType, const MAX_COUNT_V: usize, const MAX_COUNT_E: usize, const MAX_ATTRS_SIZE: usize> BasicMutableGraph<ContainerType::EdgeAttributeCollectionType, ContainerType::EdgeIdType, ContainerType::VertexAttributeCollectionType, ContainerType::VertexIdType> for GuardedGraph<ContainerType, MAX_COUNT_V, MAX_COUNT_E, MAX_ATTRS_SIZE>
+where
+    ContainerType: MutableGraphContainer,
+    ContainerType::EdgeAttributeCollectionType: AttributeCollectionIO,
+    ContainerType::VertexAttributeCollectionType: AttributeCollectionIO,
+{
+    fn add_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, directed: bool, edge_id: Option<ContainerType::EdgeIdType>) -> CrabNetsResult<ContainerType::EdgeIdType> {
+        const FUNCTION_PATH: &str = "GuardedGraph::BasicMutableGraph::add_e";
+        self.check_attrs();
+        self.check(FUNCTION_PATH)?;
+        // Existing edges are replaced, so they don't count towards the limit
+        let is_new = self.is_new_e(id1, id2, &edge_id);
+        if is_new && self.count_e >= MAX_COUNT_E {
+            self.violate(Limit::CountE);
+            self.check(FUNCTION_PATH)?;
+        }
+        let answer = MutableGraphContainer::unwrap(&mut self.container).add_e(id1, id2, directed, edge_id)?;
+        if is_new {
+            self.count_e += 1;
+        }
+        self.unchecked_attrs = Some(UncheckedAttrs::Edge(id1.clone(), id2.clone(), answer.clone()));
+        Ok(answer)
+    }
+
+    fn add_v(&mut self, id: Option<ContainerType::VertexIdType>) -> ContainerType::VertexIdType {
+        self.check_attrs();
+        let is_new = id.as_ref().is_none_or(|value| !self.contains_v(value));
+        let is_overflow = is_new && self.count_v() >= MAX_COUNT_V;
+        if is_overflow {
+            self.violate(Limit::CountV);
+            if let Some(value) = self.overflow_v.take() {
+                self.remove_v(&value);
+            }
+        }
+        let answer = MutableGraphContainer::unwrap(&mut self.container).add_v(id);
+        if is_overflow {
+            self.overflow_v = Some(answer.clone());
+        }
+        self.unchecked_attrs = Some(UncheckedAttrs::Vertex(answer.clone()));
+        answer
+    }
+
+    fn e_attrs_mut(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<&mut ContainerType::EdgeAttributeCollectionType> {
+        self.check_attrs();
+        self.unchecked_attrs = Some(UncheckedAttrs::Edge(id1.clone(), id2.clone(), edge_id.clone()));
+        MutableGraphContainer::unwrap(&mut self.container).e_attrs_mut(id1, id2, edge_id)
+    }
+
+    fn get_or_add_v(&mut self, id: ContainerType::VertexIdType) -> &mut ContainerType::VertexAttributeCollectionType {
+        let id = if self.contains_v(&id) {
+            self.check_attrs();
+            self.unchecked_attrs = Some(UncheckedAttrs::Vertex(id.clone()));
+            id
+        } else {
+            self.add_v(Some(id))
+        };
+        MutableGraphContainer::unwrap(&mut self.container).get_or_add_v(id)
+    }
+
+    fn remove_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<bool> {
+        let answer = MutableGraphContainer::unwrap(&mut self.container).remove_e(id1, id2, edge_id)?;
+        if answer {
+            self.count_e -= 1;
+        }
+        Ok(answer)
+    }
+
+    fn remove_v(&mut self, id: &ContainerType::VertexIdType) -> Option<RemovedVertex<ContainerType::EdgeAttributeCollectionType, ContainerType::EdgeIdType, ContainerType::VertexAttributeCollectionType, ContainerType::VertexIdType>> {
+        let answer = MutableGraphContainer::unwrap(&mut self.container).remove_v(id)?;
+        self.count_e = self.count_e.saturating_sub(answer.incident_edges.len());
+        if self.overflow_v.as_ref() == Some(id) {
+            self.overflow_v = None;
+        }
+        Some(answer)
+    }
+
+    fn take_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, edge_id: &ContainerType::EdgeIdType) -> CrabNetsResult<Option<ContainerType::EdgeAttributeCollectionType>> {
+        let answer = MutableGraphContainer::unwrap(&mut self.container).take_e(id1, id2, edge_id)?;
+        if answer.is_some() {
+            self.count_e -= 1;
+        }
+        Ok(answer)
+    }
+
+    fn try_add_e(&mut self, id1: &ContainerType::VertexIdType, id2: &ContainerType::VertexIdType, directed: bool, edge_id: Option<ContainerType::EdgeIdType>) -> CrabNetsResult<ContainerType::EdgeIdType> {
+        const FUNCTION_PATH: &str = "GuardedGraph::BasicMutableGraph::try_add_e";
+        self.check_attrs();
+        self.check(FUNCTION_PATH)?;
+        if self.is_new_e(id1, id2, &edge_id) && self.count_e >= MAX_COUNT_E {
+            self.violate(Limit::CountE);
+            self.check(FUNCTION_PATH)?;
+        }
+        let answer = MutableGraphContainer::unwrap(&mut self.container).try_add_e(id1, id2, directed, edge_id)?;
+        self.count_e += 1;
+        self.unchecked_attrs = Some(UncheckedAttrs::Edge(id1.clone(), id2.clone(), answer.clone()));
+        Ok(answer)
+    }
+
+    fn try_add_v(&mut self, id: Option<ContainerType::VertexIdType>) -> CrabNetsResult<ContainerType::VertexIdType> {
+        const FUNCTION_PATH: &str = "GuardedGraph::BasicMutableGraph::try_add_v";
+        self.check_attrs();
+        self.check(FUNCTION_PATH)?;
+        if id.as_ref().is_none_or(|value| !self.contains_v(value)) && self.count_v() >= MAX_COUNT_V {
+            self.violate(Limit::CountV);
+            self.check(FUNCTION_PATH)?;
+        }
+        let answer = MutableGraphContainer::unwrap(&mut self.container).try_add_v(id)?;
+        self.unchecked_attrs = Some(UncheckedAttrs::Vertex(answer.clone()));
+        Ok(answer)
+    }
+
+    fn v_attrs_mut(&mut self, id: &ContainerType::VertexIdType) -> CrabNetsResult<&mut ContainerType::VertexAttributeCollectionType> {
+        self.check_attrs();
+        self.unchecked_attrs = Some(UncheckedAttrs::Vertex(id.clone()));
+        MutableGraphContainer::unwrap(&mut self.container).v_attrs_mut(id)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+    use crate::*;
+    use crate::io::{gnbs::GNBSReader, Reader};
+    use super::*;
+
+    type Guarded = GuardedGraph<Box<graph!(A ---A--> A)>, 3, 2, 16>;
+
+    #[test]
+    fn guarded_graph() {
+        let read = |string: &str| GNBSReader.read_graph::<Guarded, _, _, _, _, _>(BufReader::new(string.as_bytes()));
+        let g = read("AV S Name\n\nV 0 \"a\"\nV 1 \"b\"\nV 2 \"c\"\nA 0 1\nA 1 2\n").ok().unwrap();
+        assert_eq!(g.into_inner().ok().unwrap().count_e(), 2);
+        assert!(read("AV S Name\n\nV 0 \"a\"\nV 1 \"b\"\nV 2 \"c\"\nA 0 1\nA 1 2\nA 2 0\n").is_err_and(|x| matches!(x.kind(), ErrorKind::LimitExceeded { maximum: 2, .. })));
+        let g = read("AV S Name\n\nV 0 \"a\"\nV 1 \"b\"\nV 2 \"c\"\nV 3 \"d\"\nV 4 \"e\"\n").ok().unwrap();
+        assert_eq!(g.count_v(), 4);
+        assert!(g.into_inner().is_err_and(|x| matches!(x.kind(), ErrorKind::LimitExceeded { maximum: 3, .. })));
+        let g = read("AV S Name\n\nV 0 \"a\"\nV 1 \"abcdefghijklmnopqrstuvwxyz\"\n").ok().unwrap();
+        assert!(g.into_inner().is_err_and(|x| matches!(x.kind(), ErrorKind::LimitExceeded { maximum: 16, .. })));
+        let mut g = Guarded::new(Box::new(Graph::new())).ok().unwrap();
+        for _ in 0..3 {
+            g.try_add_v(None).unwrap();
+        }
+        g.add_e(&0, &1, false, None).unwrap();
+        g.add_e(&0, &1, false, Some(0)).unwrap();
+        g.remove_v(&2);
+        g.try_add_v(None).unwrap();
+        assert!(g.try_add_v(None).is_err());
+        assert!(g.add_e(&0, &1, false, None).is_err());
+        let mut h: Box<graph!(A ---A--> A)> = Box::new(Graph::new());
+        h.add_v(None);
+        h.get_or_add_v(1).insert("Name".to_string(), Box::new("abcdefghijklmnopqrstuvwxyz".to_string()));
+        assert!(Guarded::new(h).is_err());
+    }
+}
@@ -1,26 +1,27 @@
-use std::{collections::{HashMap, HashSet}, iter::empty};
-use crate::{attributes::AttributeCollection, EdgeDirection, EdgeIteratorItem, Id};
+use alloc::boxed::Box;
+use core::iter::empty;
+use crate::{attributes::AttributeCollection, EdgeDirection, EdgeIteratorItem, HashMap, HashSet, Id};
 
 
 
 
 
-/// # Vertex and edges incident on it
+/// # Local topology of a vertex
 /// 
 /// ## Description
-/// This trait defines an interface for **locales**. Locales  are  typically  associated
-/// with each vertex of a [`Graph`][graph]. They capture local topology of  the  network
-/// by storing all vertices adjacent to the given one and, furthermore, they  store  all
-/// [attributes][attrs] of the given vertex and edges incident on it.
+/// This trait defines the  topological  half  of  a  [locale][locale]:  it  stores  all
+/// vertices adjacent to the associated vertex together with the edges incident  on  it,
+/// and it answers all structural queries about them.
 /// 
-/// [Structural features][kinds] may differ from network to  network.  Hence,  it  might
-/// make sense to use a locale with data structures optimised for the specific needs  of
-/// your case.
+/// [Attributes][attrs] are kept out of this trait and handled  by  [`LocaleAttributes`]
+/// instead. Thanks to that, a custom locale with a compact backend, e.g.  a  CSR  or  a
+/// bitset,   only   needs   to   implement   this   trait   and   the   marker    trait
+/// [`AttributelessLocale`] to be usable in a [`Graph`][graph].
 /// 
-/// [graph]: crate::Graph
 /// [attrs]: crate::Graph#attributes
-/// [kinds]: crate::Graph#different-kinds-of-graphs
-pub trait Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+/// [graph]: crate::Graph
+/// [locale]: Locale
+pub trait LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
 where
     Self: Clone,
     EdgeAttributeCollectionType: AttributeCollection,
@@ -28,6 +29,22 @@ where
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    /// # Support of parallel edges
+    /// 
+    /// ## Description
+    /// `true` if  this  locale  allows  parallel  edges,  i.e.  if  it's  designed  for
+    /// [multi-graphs][kinds]; `false` otherwise.
+    /// 
+    /// ## Details
+    /// Defaults to `false`, so locales of  simple  graphs  don't  have  to  define  it.
+    /// Locales of multi-graphs must  set  it  to  `true`,  otherwise  some  methods  of
+    /// [`Graph`][graph] will assume that  there's  at  most  one  edge  between  any  2
+    /// vertices.
+    /// 
+    /// [graph]: crate::Graph
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    const IS_MULTI: bool = false;
+
     /// # Add edge
     /// 
     /// ## Description
@@ -71,6 +88,45 @@ where
     /// [add_e]: crate::BasicMutableGraph::add_e
     /// [kinds]: crate::Graph#different-kinds-of-graphs
     fn add_e(&mut self, id2: VertexIdType, relation: EdgeToVertexRelation, edge_id: Option<EdgeIdType>, store_edge_attributes: bool) -> EdgeIdType;
+    /// # Associated vertex
+    /// 
+    /// ## Description
+    /// Get the ID of the vertex associated with this locale.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&VertexIdType`   -   an   immutable   reference   to   the   ID   passed   to
+    /// [`LocaleTopology::new`].
+    /// 
+    /// ## Details
+    /// Unlike most other methods of this trait, this one has no default implementation:
+    /// the ID can't be recovered from the edges of an isolated vertex. Its introduction
+    /// in version 0.2.0 is a breaking change for custom locales, which should keep  the
+    /// ID passed to [`LocaleTopology::new`] and return it here.
+    fn associated_v(&self) -> &VertexIdType;
+    /// # Check adjacency
+    /// 
+    /// ## Description
+    /// Check whether the vertex `id2` is adjacent to the vertex  associated  with  this
+    /// locale.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id2` : `&VertexIdType` - the ID of a vertex to look for.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if at least one edge of any direction connects both  vertices,
+    /// `false` otherwise.
+    /// 
+    /// ## Details
+    /// The      default      implementation      looks      for       `id2`       among
+    /// [`LocaleTopology::iter_adjacent`], locales with a faster lookup should  override
+    /// it.
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.iter_adjacent().any(|x| x == *id2)
+    }
     /// # Number of neighbours
     /// 
     /// ## Description
@@ -85,7 +141,7 @@ where
     /// ## Details
     /// Each adjacent vertex is only counted once, regardless of the number of  parallel
     /// edges connecting it and their direction. Mind the difference between this method
-    /// and [`Locale::count_incident_e`].
+    /// and [`LocaleTopology::count_incident_e`].
     fn count_adjacent(&self) -> usize;
     /// # Number of 'incoming' neighbours
     /// 
@@ -135,6 +191,31 @@ where
     /// Each adjacent vertex is only counted once, regardless of the number of  parallel
     /// edges connecting it.
     fn count_adjacent_undir(&self) -> usize;
+    /// # Number of common neighbours
+    /// 
+    /// ## Description
+    /// Get the number of vertices adjacent both to  the  vertex  associated  with  this
+    /// locale and to the vertex associated with `other`.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `other` : `&Self` - a locale of another vertex.
+    /// 
+    /// ## Returns
+    /// * `usize` - the size of the intersection of both neighbourhoods.
+    /// 
+    /// ## Details
+    /// Each adjacent vertex is only counted once, regardless of the number of  parallel
+    /// edges connecting it and their direction. Every  implementation  walks  over  the
+    /// neighbours of the smaller locale and probes the larger  one,  which  makes  this
+    /// method a cheap building block for  triangle  counting,  Jaccard  similarity  and
+    /// clustering   coefficients.    The    default    implementation    probes    with
+    /// [`LocaleTopology::contains_adjacent`], the  built-in  locales  look  into  their
+    /// storage directly.
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        let (smaller, larger) = if self.count_adjacent() <= other.count_adjacent() { (self, other) } else { (other, self) };
+        smaller.iter_adjacent().filter(|x| larger.contains_adjacent(x)).count()
+    }
     /// # Number of incident edges
     /// 
     /// ## Description
@@ -148,7 +229,7 @@ where
     /// 
     /// ## Details
     /// Each parallel edge is counted  separately.  Mind  the  difference  between  this
-    /// method and [`Locale::count_adjacent`].
+    /// method and [`LocaleTopology::count_adjacent`].
     fn count_incident_e(&self) -> usize;
     /// # Number of incoming incident edges
     /// 
@@ -164,7 +245,7 @@ where
     /// 
     /// ## Details
     /// Each parallel edge is counted  separately.  Mind  the  difference  between  this
-    /// method and [`Locale::count_adjacent_in`].
+    /// method and [`LocaleTopology::count_adjacent_in`].
     fn count_incident_e_in(&self) -> usize;
     /// # Number of outgoing incident edges
     /// 
@@ -180,7 +261,7 @@ where
     /// 
     /// ## Details
     /// Each parallel edge is counted  separately.  Mind  the  difference  between  this
-    /// method and [`Locale::count_adjacent_out`].
+    /// method and [`LocaleTopology::count_adjacent_out`].
     fn count_incident_e_out(&self) -> usize;
     /// # Number of undirected incident edges
     /// 
@@ -196,51 +277,50 @@ where
     /// 
     /// ## Details
     /// Each parallel edge is counted  separately.  Mind  the  difference  between  this
-    /// method and [`Locale::count_adjacent_undir`].
+    /// method and [`LocaleTopology::count_adjacent_undir`].
     fn count_incident_e_undir(&self) -> usize;
-    /// # Immutable reference to edge attributes
+    /// # Number of parallel edges
     /// 
     /// ## Description
-    /// Get an immutable reference to the [attribute collection][attrs] of the  specific
-    /// edge incident on the vertex associated with this locale.
+    /// Get the number of edges connecting the vertex associated with this  locale  with
+    /// the vertex `id2`.
     /// 
     /// ## Arguments
     /// * `&self` - an immutable reference to the caller.
-    /// * `id2` : `&VertexIdType` - an immutable reference to the  ID  of  the  required
-    /// adjacent vertex.
-    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of  the  required
-    /// edge.
+    /// * `id2` : `&VertexIdType` - an immutable reference to the  ID  of  the  adjacent
+    /// vertex.
     /// 
     /// ## Returns
-    /// * `Option<&EdgeAttributeCollectionType>` -  `Some(value)`  is  returned  if  the
-    /// required edge was found and this locale is responsible for storing its attribute
-    /// collection (see [`Locale::add_e`] for more details).
+    /// * `usize` - the number of edges between both vertices.
     /// 
-    /// [attrs]: crate::attributes::AttributeCollection
-    /// [e_attrs]: crate::BasicImmutableGraph::e_attrs
-    /// [`Locale::add_e`]: #add-e-details
-    fn e_attrs(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType>;
-    /// # Mutable reference to edge attributes
+    /// ## Details
+    /// Edges of all directions are counted. Simple locales always return either 0 or 1.
+    /// The default implementation filters  [`LocaleTopology::iter_incident_e`],  so  it
+    /// takes time proportional to the number of incident edges.
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.iter_incident_e().filter(|x| x.id2 == *id2).count()
+    }
+    /// # Number of neighbours in the union
     /// 
     /// ## Description
-    /// Get a mutable reference to the  [attribute collection][attrs]  of  the  specific
-    /// edge incident on the vertex associated with this locale.
+    /// Get the number of vertices adjacent to the vertex associated with this locale or
+    /// to the vertex associated with `other`.
     /// 
     /// ## Arguments
-    /// * `&mut self` - a mutable reference to the caller.
-    /// * `id2` : `&VertexIdType` - an immutable reference to the  ID  of  the  required
-    /// adjacent vertex.
-    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of  the  required
-    /// edge.
+    /// * `&self` - an immutable reference to the caller.
+    /// * `other` : `&Self` - a locale of another vertex.
     /// 
     /// ## Returns
-    /// * `Option<&mut EdgeAttributeCollectionType>` - `Some(value)` is returned if  the
-    /// required edge was found and this locale is responsible for storing its attribute
-    /// collection (see [`Locale::add_e`] for more details).
+    /// * `usize` - the size of the union of both neighbourhoods.
     /// 
-    /// [attrs]: crate::attributes::AttributeCollection
-    /// [e_attrs_mut]: crate::BasicMutableGraph::e_attrs_mut
-    fn e_attrs_mut(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType>;
+    /// ## Details
+    /// Each adjacent vertex is only counted once, regardless of the number of  parallel
+    /// edges  connecting  it  and  their  direction.  The  result   is   derived   from
+    /// [`LocaleTopology::count_common_adjacent`], hence no intermediate set of vertices
+    /// is ever built.
+    fn count_union_adjacent(&self, other: &Self) -> usize {
+        self.count_adjacent() + other.count_adjacent() - self.count_common_adjacent(other)
+    }
     /// # Iterate over neighbours
     /// 
     /// ## Description
@@ -393,12 +473,254 @@ where
     fn new(associated_vertex_id: VertexIdType) -> Self;
     fn remove_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> bool;
     fn remove_neighbour(&mut self, id2: &VertexIdType) -> bool;
+    /// # Remove edge and take its attributes
+    /// 
+    /// ## Description
+    /// Remove   the   specified   edge   from   this   locale   and   give   away   its
+    /// [attribute collection][attrs] if it's stored in this locale.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id2` : `&VertexIdType` - an immutable  reference  to  the  ID  of  the  other
+    /// vertex.
+    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of the edge.
+    /// 
+    /// ## Returns
+    /// * `Option<Option<EdgeAttributeCollectionType>>` - `None` is returned if the edge
+    /// doesn't exist;  `Some(value)`  is  returned  otherwise,  where  `value`  is  the
+    /// attribute collection of the removed edge if it was stored in this locale.
+    /// 
+    /// ## Details
+    /// The default implementation falls back to [`LocaleTopology::remove_e`] and  never
+    /// gives away the attribute collection, so the graph reports the default collection
+    /// for the removed edge. Locales that store edge attributes should override it.
+    /// 
+    /// [attrs]: crate::attributes::AttributeCollection
+    fn take_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        self.remove_e(id2, edge_id).then_some(None)
+    }
+}
+
+
+
+/// # Attribute storage of a locale
+/// 
+/// ## Description
+/// This trait defines how a [locale][locale]  stores  the  [attributes][attrs]  of  its
+/// associated vertex and of the edges incident on it. See [`LocaleTopology::add_e`]  to
+/// find out which of the two locales of an edge is responsible for its attributes.
+/// 
+/// Locales that  don't  store  any  attributes  can  implement  [`AttributelessLocale`]
+/// instead of this trait.
+/// 
+/// [attrs]: crate::Graph#attributes
+/// [locale]: Locale
+pub trait LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Immutable reference to edge attributes
+    /// 
+    /// ## Description
+    /// Get an immutable reference to the [attribute collection][attrs] of the  specific
+    /// edge incident on the vertex associated with this locale.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id2` : `&VertexIdType` - an immutable reference to the  ID  of  the  required
+    /// adjacent vertex.
+    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of  the  required
+    /// edge.
+    /// 
+    /// ## Returns
+    /// * `Option<&EdgeAttributeCollectionType>` -  `Some(value)`  is  returned  if  the
+    /// required edge was found and this locale is responsible for storing its attribute
+    /// collection (see [`LocaleTopology::add_e`] for more details).
+    /// 
+    /// [attrs]: crate::attributes::AttributeCollection
+    /// [e_attrs]: crate::BasicImmutableGraph::e_attrs
+    /// [`LocaleTopology::add_e`]: LocaleTopology#add-e-details
+    fn e_attrs(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType>;
+    /// # Mutable reference to edge attributes
+    /// 
+    /// ## Description
+    /// Get a mutable reference to the  [attribute collection][attrs]  of  the  specific
+    /// edge incident on the vertex associated with this locale.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id2` : `&VertexIdType` - an immutable reference to the  ID  of  the  required
+    /// adjacent vertex.
+    /// * `edge_id` : `&EdgeIdType` - an immutable reference to the ID of  the  required
+    /// edge.
+    /// 
+    /// ## Returns
+    /// * `Option<&mut EdgeAttributeCollectionType>` - `Some(value)` is returned if  the
+    /// required edge was found and this locale is responsible for storing its attribute
+    /// collection (see [`LocaleTopology::add_e`] for more details).
+    /// 
+    /// [attrs]: crate::attributes::AttributeCollection
+    /// [e_attrs_mut]: crate::BasicMutableGraph::e_attrs_mut
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType>;
+    /// # Immutable reference to vertex attributes
+    /// 
+    /// ## Description
+    /// Get an immutable reference to the [attribute collection][attrs]  of  the  vertex
+    /// associated with this locale.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&VertexAttributeCollectionType` - the vertex attributes.
+    /// 
+    /// [attrs]: crate::attributes::AttributeCollection
     fn v_attrs(&self) -> &VertexAttributeCollectionType;
+    /// # Mutable reference to vertex attributes
+    /// 
+    /// ## Description
+    /// Get a mutable reference  to  the  [attribute collection][attrs]  of  the  vertex
+    /// associated with this locale.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&mut VertexAttributeCollectionType` - the vertex attributes.
+    /// 
+    /// [attrs]: crate::attributes::AttributeCollection
     fn v_attrs_mut(&mut self) -> &mut VertexAttributeCollectionType;
 }
 
 
 
+/// # Vertex and edges incident on it
+/// 
+/// ## Description
+/// This trait defines an interface for **locales**. Locales  are  typically  associated
+/// with each vertex of a [`Graph`][graph]. They capture local topology of  the  network
+/// by storing all vertices adjacent to the given one and, furthermore, they  store  all
+/// [attributes][attrs] of the given vertex and edges incident on it.
+/// 
+/// [Structural features][kinds] may differ from network to  network.  Hence,  it  might
+/// make sense to use a locale with data structures optimised for the specific needs  of
+/// your case.
+/// 
+/// This  trait  has  no  methods  of  its  own:  it  combines  [`LocaleTopology`]   and
+/// [`LocaleAttributes`], and it  is  implemented  automatically  for  every  type  that
+/// implements both of them.
+/// 
+/// [graph]: crate::Graph
+/// [attrs]: crate::Graph#attributes
+/// [kinds]: crate::Graph#different-kinds-of-graphs
+pub trait Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>: LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> + LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+}
+
+// LocaleType::Locale
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for LocaleType
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> + LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+}
+
+
+
+/// # Locale without attributes
+/// 
+/// ## Description
+/// A marker trait for [locales][locale] that don't store  [attributes][attrs]  at  all.
+/// Every type that implements both this trait and [`LocaleTopology`] with `()`  as  its
+/// edge  and  vertex  attribute  collections  gets  [`LocaleAttributes`]  and,   hence,
+/// [`Locale`] for free.
+/// 
+/// ## Details
+/// Edge attributes are reported to be owned by the same locale that would own  them  if
+/// they  were  stored   (see   [`LocaleTopology::add_e`]),   so   such   locales   pass
+/// [`Graph::validate`][validate] as well.
+/// 
+/// [attrs]: crate::Graph#attributes
+/// [locale]: Locale
+/// [validate]: crate::Graph::validate
+pub trait AttributelessLocale {
+}
+
+// LocaleType::LocaleAttributes
+impl<EdgeIdType, LocaleType, VertexIdType> LocaleAttributes<(), EdgeIdType, (), VertexIdType> for LocaleType
+where
+    EdgeIdType: Id,
+    LocaleType: AttributelessLocale + LocaleTopology<(), EdgeIdType, (), VertexIdType>,
+    VertexIdType: Id,
+{
+    #[inline]
+    fn e_attrs(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&()> {
+        match self.e_direction(id2, edge_id)? {
+            EdgeDirection::Directed1to2 => Some(&()),
+            EdgeDirection::Directed2to1 => None,
+            EdgeDirection::Undirected => (self.associated_v() <= id2).then_some(&()),
+        }
+    }
+
+    #[inline]
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&mut ()> {
+        // Boxes of zero-sized types don't allocate, so nothing actually leaks here
+        self.e_attrs(id2, edge_id).map(|_| Box::leak(Box::new(())))
+    }
+
+    #[inline]
+    fn v_attrs(&self) -> &() {
+        &()
+    }
+
+    #[inline]
+    fn v_attrs_mut(&mut self) -> &mut () {
+        Box::leak(Box::new(()))
+    }
+}
+
+
+
+/// # Locales of other kinds of graphs
+/// 
+/// ## Description
+/// This trait links each locale to the locales of the [kinds][kinds] of graphs that the
+/// graph built on top of it can be converted into. It is used by  the  kind  conversion
+/// functions of [`Graph`][graph] such as [`Graph::to_directed`][to_directed].
+/// 
+/// [graph]: crate::Graph
+/// [to_directed]: crate::Graph::to_directed
+/// [kinds]: crate::Graph#different-kinds-of-graphs
+pub trait LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// Locale of the graph that allows directed edges and has the same multiplicity.
+    type Directed: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    /// Locale of the graph that allows only undirected edges and has the same multiplicity.
+    type Undirected: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    /// Locale of the multi-graph with the same directedness.
+    type Multi: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    /// Locale of the simple graph with the same directedness.
+    type Simple: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+}
+
+
+
 pub enum EdgeToVertexRelation {
     Undirected,
     Incoming,
@@ -418,6 +740,30 @@ where
     undirected: HashMap<VertexIdType, Option<EdgeAttributeCollectionType>>,
 }
 
+// SimpleEdgeCollection::SimpleEdgeCollection
+impl<EdgeAttributeCollectionType, VertexIdType> SimpleEdgeCollection<EdgeAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.incoming.contains(id2) || self.outgoing.contains_key(id2) || self.undirected.contains_key(id2)
+    }
+
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        self.incoming
+            .iter()
+            .chain(self.outgoing.keys())
+            .chain(self.undirected.keys())
+            .filter(|x| other.contains_adjacent(x))
+            .count()
+    }
+
+    fn count_adjacent(&self) -> usize {
+        self.incoming.len() + self.outgoing.len() + self.undirected.len()
+    }
+}
+
 
 
 /// # Locale for simple undirected graphs
@@ -439,20 +785,34 @@ where
     edges: HashMap<VertexIdType, Option<EdgeAttributeCollectionType>>,
 }
 
-// SimpleUndirectedLocale::Locale
-impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+// SimpleUndirectedLocale::LocaleTopology
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
 where
     EdgeAttributeCollectionType: AttributeCollection,
     EdgeIdType: Id,
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    const IS_MULTI: bool = false;
+
     #[inline]
-    fn add_e(&mut self, id2: VertexIdType, _relation: EdgeToVertexRelation, _edge_id: Option<EdgeIdType>, store_edge_attributes: bool) -> EdgeIdType {
+    fn add_e(&mut self, id2: VertexIdType, _relation: EdgeToVertexRelation, _edge_id: Option<EdgeIdType>, _store_edge_attributes: bool) -> EdgeIdType {
+        // All edges are undirected here, so the attributes always go to the smaller ID
+        let store_edge_attributes = self.associated_vertex_id <= id2;
         self.edges.insert(id2, if store_edge_attributes { Some(EdgeAttributeCollectionType::new()) } else { None });
         EdgeIdType::default()
     }
 
+    #[inline]
+    fn associated_v(&self) -> &VertexIdType {
+        &self.associated_vertex_id
+    }
+
+    #[inline]
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.edges.contains_key(id2)
+    }
+
     #[inline]
     fn count_adjacent(&self) -> usize {
         self.edges.len()
@@ -473,6 +833,12 @@ where
         self.edges.len()
     }
 
+    #[inline]
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        let (smaller, larger) = if self.edges.len() <= other.edges.len() { (self, other) } else { (other, self) };
+        smaller.edges.keys().filter(|x| larger.edges.contains_key(*x)).count()
+    }
+
     #[inline]
     fn count_incident_e(&self) -> usize {
         self.edges.len()
@@ -494,19 +860,8 @@ where
     }
 
     #[inline]
-    fn e_attrs(&self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
-        match self.edges.get(id2) {
-            Some(value) => value.as_ref(),
-            None => None,
-        }
-    }
-
-    #[inline]
-    fn e_attrs_mut(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
-        match self.edges.get_mut(id2) {
-            Some(value) => value.as_mut(),
-            None => None,
-        }
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.edges.contains_key(id2) as usize
     }
 
     #[inline]
@@ -617,6 +972,36 @@ where
         self.edges.remove(id2).is_some()
     }
 
+    #[inline]
+    fn take_e(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        self.edges.remove(id2)
+    }
+}
+
+// SimpleUndirectedLocale::LocaleAttributes
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    #[inline]
+    fn e_attrs(&self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
+        match self.edges.get(id2) {
+            Some(value) => value.as_ref(),
+            None => None,
+        }
+    }
+
+    #[inline]
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
+        match self.edges.get_mut(id2) {
+            Some(value) => value.as_mut(),
+            None => None,
+        }
+    }
+
     #[inline]
     fn v_attrs(&self) -> &VertexAttributeCollectionType {
         &self.attributes
@@ -649,18 +1034,24 @@ where
     edges: SimpleEdgeCollection<EdgeAttributeCollectionType, VertexIdType>,
 }
 
-// SimpleDirectedLocale::Locale
-impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+// SimpleDirectedLocale::LocaleTopology
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
 where
     EdgeAttributeCollectionType: AttributeCollection,
     EdgeIdType: Id,
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    const IS_MULTI: bool = false;
+
     fn add_e(&mut self, id2: VertexIdType, relation: EdgeToVertexRelation, _edge_id: Option<EdgeIdType>, store_edge_attributes: bool) -> EdgeIdType {
-        self.edges.incoming.remove(&id2);
-        self.edges.outgoing.remove(&id2);
-        self.edges.undirected.remove(&id2);
+        // Both halves of a directed self-loop are stored here, the second one mustn't
+        // replace the first one
+        if !matches!(relation, EdgeToVertexRelation::Incoming) || id2 != self.associated_vertex_id {
+            self.edges.incoming.remove(&id2);
+            self.edges.outgoing.remove(&id2);
+            self.edges.undirected.remove(&id2);
+        }
         match relation {
             EdgeToVertexRelation::Incoming => {
                 self.edges.incoming.insert(id2);
@@ -675,6 +1066,16 @@ where
         EdgeIdType::default()
     }
 
+    #[inline]
+    fn associated_v(&self) -> &VertexIdType {
+        &self.associated_vertex_id
+    }
+
+    #[inline]
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.edges.contains_adjacent(id2)
+    }
+
     #[inline]
     fn count_adjacent(&self) -> usize {
         self.edges.incoming.len() + self.edges.outgoing.len() + self.edges.undirected.len()
@@ -695,6 +1096,15 @@ where
         self.edges.undirected.len()
     }
 
+    #[inline]
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        if self.edges.count_adjacent() <= other.edges.count_adjacent() {
+            self.edges.count_common_adjacent(&other.edges)
+        } else {
+            other.edges.count_common_adjacent(&self.edges)
+        }
+    }
+
     #[inline]
     fn count_incident_e(&self) -> usize {
         self.edges.incoming.len() + self.edges.outgoing.len() + self.edges.undirected.len()
@@ -715,24 +1125,9 @@ where
         self.edges.undirected.len()
     }
 
-    fn e_attrs(&self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
-        if self.edges.outgoing.contains_key(id2) {
-            Some(&self.edges.outgoing[id2])
-        } else if self.edges.undirected.contains_key(id2) {
-            self.edges.undirected[id2].as_ref()
-        } else {
-            None
-        }
-    }
-
-    fn e_attrs_mut(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
-        if self.edges.outgoing.contains_key(id2) {
-            self.edges.outgoing.get_mut(id2)
-        } else if self.edges.undirected.contains_key(id2) {
-            self.edges.undirected.get_mut(id2).unwrap().as_mut()
-        } else {
-            None
-        }
+    #[inline]
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.edges.contains_adjacent(id2) as usize
     }
 
     fn e_direction(&self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<EdgeDirection> {
@@ -836,13 +1231,13 @@ where
             VertexIdType: 'a,
     {
         Box::new(self.edges.outgoing.keys().map(|x| EdgeIteratorItem {
-            direction: EdgeDirection::Undirected,
+            direction: EdgeDirection::Directed1to2,
             edge_id: EdgeIdType::default(),
             id1: self.associated_vertex_id.clone(),
             id2: x.clone()
         })
         .chain(self.edges.undirected.keys().filter(|&x| self.associated_vertex_id <= *x).map(|x| EdgeIteratorItem {
-            direction: EdgeDirection::Directed1to2,
+            direction: EdgeDirection::Undirected,
             edge_id: EdgeIdType::default(),
             id1: self.associated_vertex_id.clone(),
             id2: x.clone()
@@ -910,6 +1305,45 @@ where
         answer
     }
 
+    fn take_e(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        if self.edges.incoming.remove(id2) {
+            Some(None)
+        } else if let Some(value) = self.edges.outgoing.remove(id2) {
+            Some(Some(value))
+        } else {
+            self.edges.undirected.remove(id2)
+        }
+    }
+}
+
+// SimpleDirectedLocale::LocaleAttributes
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn e_attrs(&self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
+        if self.edges.outgoing.contains_key(id2) {
+            Some(&self.edges.outgoing[id2])
+        } else if self.edges.undirected.contains_key(id2) {
+            self.edges.undirected[id2].as_ref()
+        } else {
+            None
+        }
+    }
+
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, _edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
+        if self.edges.outgoing.contains_key(id2) {
+            self.edges.outgoing.get_mut(id2)
+        } else if self.edges.undirected.contains_key(id2) {
+            self.edges.undirected.get_mut(id2).unwrap().as_mut()
+        } else {
+            None
+        }
+    }
+
     fn v_attrs(&self) -> &VertexAttributeCollectionType {
         &self.attributes
     }
@@ -918,3 +1352,707 @@ where
         &mut self.attributes
     }
 }
+
+
+
+#[derive(Clone)]
+struct MultiEdgeCollection<EdgeAttributeCollectionType, EdgeIdType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    incoming: HashMap<VertexIdType, HashSet<EdgeIdType>>,
+    outgoing: HashMap<VertexIdType, HashMap<EdgeIdType, EdgeAttributeCollectionType>>,
+    undirected: HashMap<VertexIdType, HashMap<EdgeIdType, Option<EdgeAttributeCollectionType>>>,
+    // Numbers of incident edges of each direction, so that degrees don't need summing
+    count_e_in: usize,
+    count_e_out: usize,
+    count_e_undir: usize,
+}
+
+// MultiEdgeCollection::MultiEdgeCollection
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexIdType> MultiEdgeCollection<EdgeAttributeCollectionType, EdgeIdType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    fn contains(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> bool {
+        self.incoming.get(id2).is_some_and(|x| x.contains(edge_id))
+            || self.outgoing.get(id2).is_some_and(|x| x.contains_key(edge_id))
+            || self.undirected.get(id2).is_some_and(|x| x.contains_key(edge_id))
+    }
+
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.incoming.contains_key(id2) || self.outgoing.contains_key(id2) || self.undirected.contains_key(id2)
+    }
+
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        // A neighbour may be present in several maps at once, so it's only taken from
+        // the first one containing it
+        self.incoming
+            .keys()
+            .chain(self.outgoing.keys().filter(|x| !self.incoming.contains_key(*x)))
+            .chain(self.undirected.keys().filter(|x| !self.incoming.contains_key(*x) && !self.outgoing.contains_key(*x)))
+            .filter(|x| other.contains_adjacent(x))
+            .count()
+    }
+
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.incoming.get(id2).map_or(0, |x| x.len()) + self.outgoing.get(id2).map_or(0, |x| x.len()) + self.undirected.get(id2).map_or(0, |x| x.len())
+    }
+
+    fn size_hint(&self) -> usize {
+        self.incoming.len() + self.outgoing.len() + self.undirected.len()
+    }
+
+    fn take(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        let mut answer = None;
+        if let Some(parallel_edges) = self.incoming.get_mut(id2) {
+            if parallel_edges.remove(edge_id) {
+                self.count_e_in -= 1;
+                answer = Some(None);
+            }
+            if parallel_edges.is_empty() {
+                self.incoming.remove(id2);
+            }
+        }
+        if let Some(parallel_edges) = self.outgoing.get_mut(id2) {
+            if let Some(value) = parallel_edges.remove(edge_id) {
+                self.count_e_out -= 1;
+                answer = Some(Some(value));
+            }
+            if parallel_edges.is_empty() {
+                self.outgoing.remove(id2);
+            }
+        }
+        if let Some(parallel_edges) = self.undirected.get_mut(id2) {
+            if let Some(value) = parallel_edges.remove(edge_id) {
+                self.count_e_undir -= 1;
+                answer = Some(value);
+            }
+            if parallel_edges.is_empty() {
+                self.undirected.remove(id2);
+            }
+        }
+        answer
+    }
+}
+
+
+
+/// # Locale for undirected multi-graphs
+/// 
+/// ## Description
+/// This locale stores parallel edges of undirected multi-graphs. See [`Graph`] for more
+/// details.
+/// 
+/// [`Graph`]: crate::Graph#different-kinds-of-graphs
+#[derive(Clone)]
+pub struct MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    associated_vertex_id: VertexIdType,
+    attributes: VertexAttributeCollectionType,
+    count_incident_e: usize,
+    edges: HashMap<VertexIdType, HashMap<EdgeIdType, Option<EdgeAttributeCollectionType>>>,
+}
+
+// MultiUndirectedLocale::LocaleTopology
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    const IS_MULTI: bool = true;
+
+    fn add_e(&mut self, id2: VertexIdType, _relation: EdgeToVertexRelation, edge_id: Option<EdgeIdType>, _store_edge_attributes: bool) -> EdgeIdType {
+        // All edges are undirected here, so the attributes always go to the smaller ID
+        let store_edge_attributes = self.associated_vertex_id <= id2;
+        let parallel_edges = self.edges.entry(id2).or_default();
+        let actual_edge_id = match edge_id {
+            Some(value) => value,
+            None => {
+                let mut value = EdgeIdType::default();
+                while parallel_edges.contains_key(&value) {
+                    value.increment();
+                }
+                value
+            },
+        };
+        if parallel_edges.insert(actual_edge_id.clone(), if store_edge_attributes { Some(EdgeAttributeCollectionType::new()) } else { None }).is_none() {
+            self.count_incident_e += 1;
+        }
+        actual_edge_id
+    }
+
+    #[inline]
+    fn associated_v(&self) -> &VertexIdType {
+        &self.associated_vertex_id
+    }
+
+    #[inline]
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.edges.contains_key(id2)
+    }
+
+    #[inline]
+    fn count_adjacent(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    fn count_adjacent_in(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn count_adjacent_out(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn count_adjacent_undir(&self) -> usize {
+        self.edges.len()
+    }
+
+    #[inline]
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        let (smaller, larger) = if self.edges.len() <= other.edges.len() { (self, other) } else { (other, self) };
+        smaller.edges.keys().filter(|x| larger.edges.contains_key(*x)).count()
+    }
+
+    #[inline]
+    fn count_incident_e(&self) -> usize {
+        self.count_incident_e
+    }
+
+    #[inline]
+    fn count_incident_e_in(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn count_incident_e_out(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn count_incident_e_undir(&self) -> usize {
+        self.count_incident_e
+    }
+
+    #[inline]
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.edges.get(id2).map_or(0, |x| x.len())
+    }
+
+    #[inline]
+    fn e_direction(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<EdgeDirection> {
+        if self.edges.get(id2).is_some_and(|x| x.contains_key(edge_id)) {
+            Some(EdgeDirection::Undirected)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn iter_incident_e<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.edges.iter().flat_map(|(x, parallel_edges)| parallel_edges.keys().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Undirected,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        })))
+    }
+
+    #[inline]
+    fn iter_incident_e_in<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(empty())
+    }
+
+    #[inline]
+    fn iter_incident_e_out<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(empty())
+    }
+
+    #[inline]
+    fn iter_incident_e_undir<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        self.iter_incident_e()
+    }
+
+    #[inline]
+    fn iter_incident_e_with_attrs<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.edges.iter().filter(|(x, _)| self.associated_vertex_id <= **x).flat_map(|(x, parallel_edges)| parallel_edges.keys().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Undirected,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        })))
+    }
+
+    #[inline]
+    fn iter_adjacent<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(self.edges.keys().cloned())
+    }
+
+    #[inline]
+    fn iter_adjacent_in<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(empty())
+    }
+
+    #[inline]
+    fn iter_adjacent_out<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(empty())
+    }
+
+    #[inline]
+    fn iter_adjacent_undir<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(self.edges.keys().cloned())
+    }
+
+    #[inline]
+    fn new(associated_vertex_id: VertexIdType) -> Self {
+        MultiUndirectedLocale {
+            associated_vertex_id,
+            attributes: VertexAttributeCollectionType::new(),
+            count_incident_e: 0,
+            edges: HashMap::new(),
+        }
+    }
+
+    #[inline]
+    fn remove_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> bool {
+        self.take_e(id2, edge_id).is_some()
+    }
+
+    #[inline]
+    fn remove_neighbour(&mut self, id2: &VertexIdType) -> bool {
+        match self.edges.remove(id2) {
+            Some(parallel_edges) => {
+                self.count_incident_e -= parallel_edges.len();
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn take_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        let parallel_edges = self.edges.get_mut(id2)?;
+        let answer = parallel_edges.remove(edge_id);
+        if answer.is_some() {
+            self.count_incident_e -= 1;
+        }
+        if parallel_edges.is_empty() {
+            self.edges.remove(id2);
+        }
+        answer
+    }
+}
+
+// MultiUndirectedLocale::LocaleAttributes
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    #[inline]
+    fn e_attrs(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
+        self.edges.get(id2)?.get(edge_id)?.as_ref()
+    }
+
+    #[inline]
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
+        self.edges.get_mut(id2)?.get_mut(edge_id)?.as_mut()
+    }
+
+    #[inline]
+    fn v_attrs(&self) -> &VertexAttributeCollectionType {
+        &self.attributes
+    }
+
+    #[inline]
+    fn v_attrs_mut(&mut self) -> &mut VertexAttributeCollectionType {
+        &mut self.attributes
+    }
+}
+
+
+
+/// # Locale for directed multi-graphs
+/// 
+/// ## Description
+/// This locale stores parallel edges of directed multi-graphs. See [`Graph`]  for  more
+/// details.
+/// 
+/// [`Graph`]: crate::Graph#different-kinds-of-graphs
+#[derive(Clone)]
+pub struct MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    associated_vertex_id: VertexIdType,
+    attributes: VertexAttributeCollectionType,
+    edges: MultiEdgeCollection<EdgeAttributeCollectionType, EdgeIdType, VertexIdType>,
+}
+
+// MultiDirectedLocale::LocaleTopology
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleTopology<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    const IS_MULTI: bool = true;
+
+    fn add_e(&mut self, id2: VertexIdType, relation: EdgeToVertexRelation, edge_id: Option<EdgeIdType>, store_edge_attributes: bool) -> EdgeIdType {
+        let actual_edge_id = match edge_id {
+            Some(value) => {
+                // Both halves of a directed self-loop are stored here, the second one
+                // mustn't replace the first one
+                if !matches!(relation, EdgeToVertexRelation::Incoming) || id2 != self.associated_vertex_id {
+                    self.edges.take(&id2, &value);
+                }
+                value
+            },
+            None => {
+                let mut value = EdgeIdType::default();
+                while self.edges.contains(&id2, &value) {
+                    value.increment();
+                }
+                value
+            },
+        };
+        match relation {
+            EdgeToVertexRelation::Incoming => {
+                self.edges.incoming.entry(id2).or_default().insert(actual_edge_id.clone());
+                self.edges.count_e_in += 1;
+            },
+            EdgeToVertexRelation::Outgoing => {
+                self.edges.outgoing.entry(id2).or_default().insert(actual_edge_id.clone(), EdgeAttributeCollectionType::new());
+                self.edges.count_e_out += 1;
+            },
+            EdgeToVertexRelation::Undirected => {
+                self.edges.undirected.entry(id2).or_default().insert(actual_edge_id.clone(), if store_edge_attributes { Some(EdgeAttributeCollectionType::new()) } else { None });
+                self.edges.count_e_undir += 1;
+            },
+        }
+        actual_edge_id
+    }
+
+    #[inline]
+    fn associated_v(&self) -> &VertexIdType {
+        &self.associated_vertex_id
+    }
+
+    #[inline]
+    fn contains_adjacent(&self, id2: &VertexIdType) -> bool {
+        self.edges.contains_adjacent(id2)
+    }
+
+    #[inline]
+    fn count_adjacent(&self) -> usize {
+        self.edges.incoming.keys().chain(self.edges.outgoing.keys()).chain(self.edges.undirected.keys()).collect::<HashSet<_>>().len()
+    }
+
+    #[inline]
+    fn count_adjacent_in(&self) -> usize {
+        self.edges.incoming.len()
+    }
+
+    #[inline]
+    fn count_adjacent_out(&self) -> usize {
+        self.edges.outgoing.len()
+    }
+
+    #[inline]
+    fn count_adjacent_undir(&self) -> usize {
+        self.edges.undirected.len()
+    }
+
+    #[inline]
+    fn count_common_adjacent(&self, other: &Self) -> usize {
+        if self.edges.size_hint() <= other.edges.size_hint() {
+            self.edges.count_common_adjacent(&other.edges)
+        } else {
+            other.edges.count_common_adjacent(&self.edges)
+        }
+    }
+
+    #[inline]
+    fn count_incident_e(&self) -> usize {
+        self.edges.count_e_in + self.edges.count_e_out + self.edges.count_e_undir
+    }
+
+    #[inline]
+    fn count_incident_e_in(&self) -> usize {
+        self.edges.count_e_in
+    }
+
+    #[inline]
+    fn count_incident_e_out(&self) -> usize {
+        self.edges.count_e_out
+    }
+
+    #[inline]
+    fn count_incident_e_undir(&self) -> usize {
+        self.edges.count_e_undir
+    }
+
+    #[inline]
+    fn count_parallel_e(&self, id2: &VertexIdType) -> usize {
+        self.edges.count_parallel_e(id2)
+    }
+
+    fn e_direction(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<EdgeDirection> {
+        if self.edges.incoming.get(id2).is_some_and(|x| x.contains(edge_id)) {
+            Some(EdgeDirection::Directed2to1)
+        } else if self.edges.outgoing.get(id2).is_some_and(|x| x.contains_key(edge_id)) {
+            Some(EdgeDirection::Directed1to2)
+        } else if self.edges.undirected.get(id2).is_some_and(|x| x.contains_key(edge_id)) {
+            Some(EdgeDirection::Undirected)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn iter_incident_e<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_incident_e_in().chain(self.iter_incident_e_out()).chain(self.iter_incident_e_undir()))
+    }
+
+    #[inline]
+    fn iter_incident_e_in<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.edges.incoming.iter().flat_map(|(x, parallel_edges)| parallel_edges.iter().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Directed2to1,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        })))
+    }
+
+    #[inline]
+    fn iter_incident_e_out<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.edges.outgoing.iter().flat_map(|(x, parallel_edges)| parallel_edges.keys().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Directed1to2,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        })))
+    }
+
+    #[inline]
+    fn iter_incident_e_undir<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.edges.undirected.iter().flat_map(|(x, parallel_edges)| parallel_edges.keys().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Undirected,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        })))
+    }
+
+    #[inline]
+    fn iter_incident_e_with_attrs<'a>(&'a self) -> Box<dyn Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a>
+    where
+        EdgeIdType: 'a,
+        VertexIdType: 'a,
+    {
+        Box::new(self.iter_incident_e_out().chain(self.edges.undirected.iter().filter(|(x, _)| self.associated_vertex_id <= **x).flat_map(|(x, parallel_edges)| parallel_edges.keys().map(|y| EdgeIteratorItem {
+            direction: EdgeDirection::Undirected,
+            edge_id: y.clone(),
+            id1: self.associated_vertex_id.clone(),
+            id2: x.clone(),
+        }))))
+    }
+
+    #[inline]
+    fn iter_adjacent<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        let mut visited = HashSet::new();
+        Box::new(self.edges.incoming.keys().chain(self.edges.outgoing.keys()).chain(self.edges.undirected.keys()).filter(move |x| visited.insert(*x)).cloned())
+    }
+
+    #[inline]
+    fn iter_adjacent_in<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(self.edges.incoming.keys().cloned())
+    }
+
+    #[inline]
+    fn iter_adjacent_out<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(self.edges.outgoing.keys().cloned())
+    }
+
+    #[inline]
+    fn iter_adjacent_undir<'a>(&'a self) -> Box<dyn Iterator<Item = VertexIdType> + 'a> {
+        Box::new(self.edges.undirected.keys().cloned())
+    }
+
+    #[inline]
+    fn new(associated_vertex_id: VertexIdType) -> Self {
+        MultiDirectedLocale {
+            associated_vertex_id,
+            attributes: VertexAttributeCollectionType::new(),
+            edges: MultiEdgeCollection { incoming: HashMap::new(), outgoing: HashMap::new(), undirected: HashMap::new(), count_e_in: 0, count_e_out: 0, count_e_undir: 0 }
+        }
+    }
+
+    #[inline]
+    fn remove_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> bool {
+        self.edges.take(id2, edge_id).is_some()
+    }
+
+    fn remove_neighbour(&mut self, id2: &VertexIdType) -> bool {
+        let answer = self.edges.incoming.contains_key(id2) || self.edges.outgoing.contains_key(id2) || self.edges.undirected.contains_key(id2);
+        self.edges.count_e_in -= self.edges.incoming.remove(id2).map_or(0, |x| x.len());
+        self.edges.count_e_out -= self.edges.outgoing.remove(id2).map_or(0, |x| x.len());
+        self.edges.count_e_undir -= self.edges.undirected.remove(id2).map_or(0, |x| x.len());
+        answer
+    }
+
+    #[inline]
+    fn take_e(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<Option<EdgeAttributeCollectionType>> {
+        self.edges.take(id2, edge_id)
+    }
+}
+
+// MultiDirectedLocale::LocaleAttributes
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleAttributes<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn e_attrs(&self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&EdgeAttributeCollectionType> {
+        if let Some(value) = self.edges.outgoing.get(id2).and_then(|x| x.get(edge_id)) {
+            Some(value)
+        } else {
+            self.edges.undirected.get(id2)?.get(edge_id)?.as_ref()
+        }
+    }
+
+    fn e_attrs_mut(&mut self, id2: &VertexIdType, edge_id: &EdgeIdType) -> Option<&mut EdgeAttributeCollectionType> {
+        if self.edges.outgoing.get(id2).is_some_and(|x| x.contains_key(edge_id)) {
+            self.edges.outgoing.get_mut(id2)?.get_mut(edge_id)
+        } else {
+            self.edges.undirected.get_mut(id2)?.get_mut(edge_id)?.as_mut()
+        }
+    }
+
+    #[inline]
+    fn v_attrs(&self) -> &VertexAttributeCollectionType {
+        &self.attributes
+    }
+
+    #[inline]
+    fn v_attrs_mut(&mut self) -> &mut VertexAttributeCollectionType {
+        &mut self.attributes
+    }
+}
+
+
+
+
+// SimpleUndirectedLocale::LocaleCounterparts
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type Directed = SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>;
+    type Undirected = Self;
+    type Multi = MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    type Simple = Self;
+}
+
+// SimpleDirectedLocale::LocaleCounterparts
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type Directed = Self;
+    type Undirected = SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>;
+    type Multi = MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    type Simple = Self;
+}
+
+// MultiUndirectedLocale::LocaleCounterparts
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type Directed = MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    type Undirected = Self;
+    type Multi = Self;
+    type Simple = SimpleUndirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>;
+}
+
+// MultiDirectedLocale::LocaleCounterparts
+impl<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> for MultiDirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type Directed = Self;
+    type Undirected = MultiUndirectedLocale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>;
+    type Multi = Self;
+    type Simple = SimpleDirectedLocale<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>;
+}
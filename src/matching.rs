@@ -0,0 +1,824 @@
+//! # Matching module
+//! 
+//! ## Description
+//! This module contains [`Matching`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{hash_map::Entry, HashMap};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Matched edges
+/// 
+/// ## Description
+/// A list of edges of a matching, each edge is given by the IDs of its  endpoints  (the
+/// smaller one goes first) and its own ID.
+pub type MatchedEdges<EdgeIdType, VertexIdType> = Vec<(VertexIdType, VertexIdType, EdgeIdType)>;
+
+// The best of the parallel edges between each pair of vertices given by their numbers
+type BestEdges<EdgeIdType> = HashMap<(usize, usize), (f64, EdgeIdType)>;
+
+// Marks the absence of a vertex, an edge endpoint or a blossom
+pub(crate) const NONE: usize = usize::MAX;
+
+
+
+// Edmonds' blossom algorithm over vertices numbered from 0, the state of one search for
+// an augmenting path
+struct BlossomSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    base: Vec<usize>,
+    in_blossom: Vec<bool>,
+    mate: Vec<usize>,
+    parent: Vec<usize>,
+    queue: Vec<usize>,
+    used: Vec<bool>,
+}
+
+impl<'a> BlossomSearch<'a> {
+    fn new(adjacency: &'a [Vec<usize>]) -> Self {
+        let n = adjacency.len();
+        let mut mate = vec![NONE; n];
+        // Greedy initial matching saves most of the searches
+        for v in 0..n {
+            if mate[v] == NONE {
+                if let Some(&w) = adjacency[v].iter().find(|&&w| mate[w] == NONE) {
+                    mate[v] = w;
+                    mate[w] = v;
+                }
+            }
+        }
+        BlossomSearch { adjacency, base: Vec::new(), in_blossom: Vec::new(), mate, parent: Vec::new(), queue: Vec::new(), used: Vec::new() }
+    }
+
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut on_path = vec![false; self.mate.len()];
+        loop {
+            a = self.base[a];
+            on_path[a] = true;
+            if self.mate[a] == NONE {
+                break;
+            }
+            a = self.parent[self.mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if on_path[b] {
+                return b;
+            }
+            b = self.parent[self.mate[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut v: usize, base: usize, mut child: usize) {
+        while self.base[v] != base {
+            self.in_blossom[self.base[v]] = true;
+            self.in_blossom[self.base[self.mate[v]]] = true;
+            self.parent[v] = child;
+            child = self.mate[v];
+            v = self.parent[self.mate[v]];
+        }
+    }
+
+    fn find_augmenting_path(&mut self, root: usize) -> usize {
+        let n = self.mate.len();
+        self.used = vec![false; n];
+        self.parent = vec![NONE; n];
+        self.base = (0..n).collect();
+        self.used[root] = true;
+        self.queue = vec![root];
+        let mut head = 0;
+        while head < self.queue.len() {
+            let v = self.queue[head];
+            head += 1;
+            for &to in self.adjacency[v].iter() {
+                if self.base[v] == self.base[to] || self.mate[v] == to {
+                    continue;
+                }
+                if to == root || (self.mate[to] != NONE && self.parent[self.mate[to]] != NONE) {
+                    // Odd cycle, contract it
+                    let current_base = self.lowest_common_ancestor(v, to);
+                    self.in_blossom = vec![false; n];
+                    self.mark_path(v, current_base, to);
+                    self.mark_path(to, current_base, v);
+                    for i in 0..n {
+                        if self.in_blossom[self.base[i]] {
+                            self.base[i] = current_base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                self.queue.push(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to] == NONE {
+                    self.parent[to] = v;
+                    if self.mate[to] == NONE {
+                        return to;
+                    }
+                    self.used[self.mate[to]] = true;
+                    self.queue.push(self.mate[to]);
+                }
+            }
+        }
+        NONE
+    }
+
+    fn run(mut self) -> Vec<usize> {
+        for root in 0..self.mate.len() {
+            if self.mate[root] != NONE {
+                continue;
+            }
+            let mut v = self.find_augmenting_path(root);
+            while v != NONE {
+                let parent = self.parent[v];
+                let next = self.mate[parent];
+                self.mate[v] = parent;
+                self.mate[parent] = v;
+                v = next;
+            }
+        }
+        self.mate
+    }
+}
+
+
+
+// Primal-dual maximum weight matching over vertices numbered from 0 in O(|V|³), a port
+// of the classical implementation by J. van Rantwijk based on Galil's description of
+// the algorithm. Endpoint `p` is vertex `edges[p / 2].(p % 2)`, dual variables are
+// doubled, so that the slack of an edge is `dual[i] + dual[j] - 2 · weight`.
+pub(crate) struct WeightedBlossomSearch {
+    allow_edge: Vec<bool>,
+    best_edge: Vec<usize>,
+    blossom_base: Vec<usize>,
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    blossom_children: Vec<Vec<usize>>,
+    blossom_endpoints: Vec<Vec<usize>>,
+    blossom_parent: Vec<usize>,
+    dual: Vec<f64>,
+    edges: Vec<(usize, usize, f64)>,
+    endpoint: Vec<usize>,
+    in_blossom: Vec<usize>,
+    label: Vec<i8>,
+    label_end: Vec<usize>,
+    mate: Vec<usize>,
+    neighbour_endpoints: Vec<Vec<usize>>,
+    queue: Vec<usize>,
+    unused_blossoms: Vec<usize>,
+    vertex_count: usize,
+}
+
+impl WeightedBlossomSearch {
+    pub(crate) fn new(vertex_count: usize, edges: Vec<(usize, usize, f64)>) -> Self {
+        let n = vertex_count;
+        let max_weight = edges.iter().map(|x| x.2).fold(0f64, f64::max);
+        let endpoint = (0..2 * edges.len()).map(|p| if p % 2 == 0 { edges[p / 2].0 } else { edges[p / 2].1 }).collect();
+        let mut neighbour_endpoints = vec![Vec::new(); n];
+        for (k, &(i, j, _)) in edges.iter().enumerate() {
+            neighbour_endpoints[i].push(2 * k + 1);
+            neighbour_endpoints[j].push(2 * k);
+        }
+        WeightedBlossomSearch {
+            allow_edge: vec![false; edges.len()],
+            best_edge: vec![NONE; 2 * n],
+            blossom_base: (0..n).chain(std::iter::repeat_n(NONE, n)).collect(),
+            blossom_best_edges: vec![None; 2 * n],
+            blossom_children: vec![Vec::new(); 2 * n],
+            blossom_endpoints: vec![Vec::new(); 2 * n],
+            blossom_parent: vec![NONE; 2 * n],
+            dual: std::iter::repeat_n(max_weight, n).chain(std::iter::repeat_n(0f64, n)).collect(),
+            edges,
+            endpoint,
+            in_blossom: (0..n).collect(),
+            label: vec![0; 2 * n],
+            label_end: vec![NONE; 2 * n],
+            mate: vec![NONE; n],
+            neighbour_endpoints,
+            queue: Vec::new(),
+            unused_blossoms: (n..2 * n).collect(),
+            vertex_count: n,
+        }
+    }
+
+    fn slack(&self, k: usize) -> f64 {
+        let (i, j, weight) = self.edges[k];
+        self.dual[i] + self.dual[j] - 2f64 * weight
+    }
+
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        if b < self.vertex_count {
+            return vec![b];
+        }
+        self.blossom_children[b].iter().flat_map(|&t| self.leaves(t)).collect()
+    }
+
+    // Label the top-level blossom containing vertex `w` as S (1) or T (2), reached
+    // through endpoint `p`
+    fn assign_label(&mut self, w: usize, t: i8, p: usize) {
+        let b = self.in_blossom[w];
+        self.label[w] = t;
+        self.label[b] = t;
+        self.label_end[w] = p;
+        self.label_end[b] = p;
+        self.best_edge[w] = NONE;
+        self.best_edge[b] = NONE;
+        if t == 1 {
+            let leaves = self.leaves(b);
+            self.queue.extend(leaves);
+        } else if t == 2 {
+            let base_mate = self.mate[self.blossom_base[b]];
+            self.assign_label(self.endpoint[base_mate], 1, base_mate ^ 1);
+        }
+    }
+
+    // Trace back from vertices `v` and `w` to find either a new blossom (its base is
+    // returned) or an augmenting path (NONE is returned)
+    fn scan_blossom(&mut self, mut v: usize, mut w: usize) -> usize {
+        let mut path = Vec::new();
+        let mut base = NONE;
+        while v != NONE || w != NONE {
+            let mut b = self.in_blossom[v];
+            if self.label[b] & 4 != 0 {
+                base = self.blossom_base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            if self.label_end[b] == NONE {
+                v = NONE;
+            } else {
+                v = self.endpoint[self.label_end[b]];
+                b = self.in_blossom[v];
+                v = self.endpoint[self.label_end[b]];
+            }
+            if w != NONE {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    // Contract the cycle formed by edge `k` and the alternating trees into a new blossom
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (mut v, mut w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused_blossoms.pop().unwrap();
+        self.blossom_base[b] = base;
+        self.blossom_parent[b] = NONE;
+        self.blossom_parent[bb] = b;
+        let mut path = Vec::new();
+        let mut endpoints = Vec::new();
+        while bv != bb {
+            self.blossom_parent[bv] = b;
+            path.push(bv);
+            endpoints.push(self.label_end[bv]);
+            v = self.endpoint[self.label_end[bv]];
+            bv = self.in_blossom[v];
+        }
+        path.push(bb);
+        path.reverse();
+        endpoints.reverse();
+        endpoints.push(2 * k);
+        while bw != bb {
+            self.blossom_parent[bw] = b;
+            path.push(bw);
+            endpoints.push(self.label_end[bw] ^ 1);
+            w = self.endpoint[self.label_end[bw]];
+            bw = self.in_blossom[w];
+        }
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = 0f64;
+        for leaf in self.leaves_of(&path) {
+            if self.label[self.in_blossom[leaf]] == 2 {
+                self.queue.push(leaf);
+            }
+            self.in_blossom[leaf] = b;
+        }
+        // Least-slack edges from the new blossom to other S-blossoms
+        let mut best_edge_to = vec![NONE; 2 * self.vertex_count];
+        for &child in path.iter() {
+            let edge_lists = match self.blossom_best_edges[child].take() {
+                Some(value) => vec![value],
+                None => self.leaves(child).into_iter().map(|x| self.neighbour_endpoints[x].iter().map(|p| p / 2).collect()).collect(),
+            };
+            for edge_list in edge_lists {
+                for k in edge_list {
+                    let (mut i, mut j, _) = self.edges[k];
+                    if self.in_blossom[j] == b {
+                        std::mem::swap(&mut i, &mut j);
+                    }
+                    let bj = self.in_blossom[j];
+                    if bj != b && self.label[bj] == 1 && (best_edge_to[bj] == NONE || self.slack(k) < self.slack(best_edge_to[bj])) {
+                        best_edge_to[bj] = k;
+                    }
+                }
+            }
+            self.best_edge[child] = NONE;
+        }
+        let best_edges: Vec<usize> = best_edge_to.into_iter().filter(|&x| x != NONE).collect();
+        self.best_edge[b] = NONE;
+        for &k in best_edges.iter() {
+            if self.best_edge[b] == NONE || self.slack(k) < self.slack(self.best_edge[b]) {
+                self.best_edge[b] = k;
+            }
+        }
+        self.blossom_best_edges[b] = Some(best_edges);
+        self.blossom_children[b] = path;
+        self.blossom_endpoints[b] = endpoints;
+    }
+
+    fn leaves_of(&self, blossoms: &[usize]) -> Vec<usize> {
+        blossoms.iter().flat_map(|&x| self.leaves(x)).collect()
+    }
+
+    // Position of `j` (possibly negative) in the list of children of blossom `b`
+    fn child_at(&self, b: usize, j: isize) -> usize {
+        let children = &self.blossom_children[b];
+        children[j.rem_euclid(children.len() as isize) as usize]
+    }
+
+    fn endpoint_at(&self, b: usize, j: isize) -> usize {
+        let endpoints = &self.blossom_endpoints[b];
+        endpoints[j.rem_euclid(endpoints.len() as isize) as usize]
+    }
+
+    // Direction in which the children of blossom `b` are walked from child `t` to the base
+    fn walk_direction(&self, b: usize, t: usize) -> (isize, isize, isize, usize) {
+        let i = self.blossom_children[b].iter().position(|&x| x == t).unwrap();
+        if i % 2 == 1 {
+            (i as isize - self.blossom_children[b].len() as isize, 1, 0, i)
+        } else {
+            (i as isize, -1, 1, i)
+        }
+    }
+
+    // Dissolve blossom `b` into its children
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        for s in self.blossom_children[b].clone() {
+            self.blossom_parent[s] = NONE;
+            if s < self.vertex_count {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == 0f64 {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for leaf in self.leaves(s) {
+                    self.in_blossom[leaf] = s;
+                }
+            }
+        }
+        if !end_stage && self.label[b] == 2 {
+            // Relabel the children on the even-length path from the entry child to the base
+            let entry_child = self.in_blossom[self.endpoint[self.label_end[b] ^ 1]];
+            let (mut j, j_step, endpoint_trick, _) = self.walk_direction(b, entry_child);
+            let mut p = self.label_end[b];
+            while j != 0 {
+                self.label[self.endpoint[p ^ 1]] = 0;
+                let q = self.endpoint_at(b, j - endpoint_trick);
+                self.label[self.endpoint[q ^ endpoint_trick as usize ^ 1]] = 0;
+                self.assign_label(self.endpoint[p ^ 1], 2, p);
+                self.allow_edge[q / 2] = true;
+                j += j_step;
+                p = self.endpoint_at(b, j - endpoint_trick) ^ endpoint_trick as usize;
+                self.allow_edge[p / 2] = true;
+                j += j_step;
+            }
+            let bv = self.child_at(b, j);
+            let entry = self.endpoint[p ^ 1];
+            self.label[entry] = 2;
+            self.label[bv] = 2;
+            self.label_end[entry] = p;
+            self.label_end[bv] = p;
+            self.best_edge[bv] = NONE;
+            j += j_step;
+            while self.child_at(b, j) != entry_child {
+                let bv = self.child_at(b, j);
+                if self.label[bv] == 1 {
+                    j += j_step;
+                    continue;
+                }
+                if let Some(v) = self.leaves(bv).into_iter().find(|&x| self.label[x] != 0) {
+                    self.label[v] = 0;
+                    let base_mate = self.mate[self.blossom_base[bv]];
+                    self.label[self.endpoint[base_mate]] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+                j += j_step;
+            }
+        }
+        self.label[b] = -1;
+        self.label_end[b] = NONE;
+        self.blossom_children[b] = Vec::new();
+        self.blossom_endpoints[b] = Vec::new();
+        self.blossom_base[b] = NONE;
+        self.blossom_best_edges[b] = None;
+        self.best_edge[b] = NONE;
+        self.unused_blossoms.push(b);
+    }
+
+    // Swap matched and unmatched edges along the path from vertex `v` to the base of
+    // blossom `b`, making `v` the new base
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.blossom_parent[t] != b {
+            t = self.blossom_parent[t];
+        }
+        if t >= self.vertex_count {
+            self.augment_blossom(t, v);
+        }
+        let (mut j, j_step, endpoint_trick, i) = self.walk_direction(b, t);
+        while j != 0 {
+            j += j_step;
+            let t = self.child_at(b, j);
+            let p = self.endpoint_at(b, j - endpoint_trick) ^ endpoint_trick as usize;
+            if t >= self.vertex_count {
+                self.augment_blossom(t, self.endpoint[p]);
+            }
+            j += j_step;
+            let t = self.child_at(b, j);
+            if t >= self.vertex_count {
+                self.augment_blossom(t, self.endpoint[p ^ 1]);
+            }
+            self.mate[self.endpoint[p]] = p ^ 1;
+            self.mate[self.endpoint[p ^ 1]] = p;
+        }
+        self.blossom_children[b].rotate_left(i);
+        self.blossom_endpoints[b].rotate_left(i);
+        self.blossom_base[b] = self.blossom_base[self.blossom_children[b][0]];
+    }
+
+    // Augment the matching along the path through edge `k`
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for (mut s, mut p) in [(v, 2 * k + 1), (w, 2 * k)] {
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.vertex_count {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = p;
+                if self.label_end[bs] == NONE {
+                    break;
+                }
+                let t = self.endpoint[self.label_end[bs]];
+                let bt = self.in_blossom[t];
+                s = self.endpoint[self.label_end[bt]];
+                let j = self.endpoint[self.label_end[bt] ^ 1];
+                if bt >= self.vertex_count {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = self.label_end[bt];
+                p = self.label_end[bt] ^ 1;
+            }
+        }
+    }
+
+    // One stage of the algorithm, returns `true` if the matching was augmented
+    fn stage(&mut self) -> bool {
+        let n = self.vertex_count;
+        self.label.iter_mut().for_each(|x| *x = 0);
+        self.best_edge.iter_mut().for_each(|x| *x = NONE);
+        self.blossom_best_edges[n..].iter_mut().for_each(|x| *x = None);
+        self.allow_edge.iter_mut().for_each(|x| *x = false);
+        self.queue.clear();
+        for v in 0..n {
+            if self.mate[v] == NONE && self.label[self.in_blossom[v]] == 0 {
+                self.assign_label(v, 1, NONE);
+            }
+        }
+        loop {
+            while let Some(v) = self.queue.pop() {
+                for p in self.neighbour_endpoints[v].clone() {
+                    let k = p / 2;
+                    let w = self.endpoint[p];
+                    if self.in_blossom[v] == self.in_blossom[w] {
+                        continue;
+                    }
+                    let mut k_slack = 0f64;
+                    if !self.allow_edge[k] {
+                        k_slack = self.slack(k);
+                        if k_slack <= 0f64 {
+                            self.allow_edge[k] = true;
+                        }
+                    }
+                    if self.allow_edge[k] {
+                        if self.label[self.in_blossom[w]] == 0 {
+                            self.assign_label(w, 2, p ^ 1);
+                        } else if self.label[self.in_blossom[w]] == 1 {
+                            let base = self.scan_blossom(v, w);
+                            if base != NONE {
+                                self.add_blossom(base, k);
+                            } else {
+                                self.augment_matching(k);
+                                return true;
+                            }
+                        } else if self.label[w] == 0 {
+                            self.label[w] = 2;
+                            self.label_end[w] = p ^ 1;
+                        }
+                    } else if self.label[self.in_blossom[w]] == 1 {
+                        let b = self.in_blossom[v];
+                        if self.best_edge[b] == NONE || k_slack < self.slack(self.best_edge[b]) {
+                            self.best_edge[b] = k;
+                        }
+                    } else if self.label[w] == 0 && (self.best_edge[w] == NONE || k_slack < self.slack(self.best_edge[w])) {
+                        self.best_edge[w] = k;
+                    }
+                }
+            }
+            // No augmenting path with tight edges, update the dual variables
+            let mut delta_type = 1;
+            let mut delta = self.dual[..n].iter().copied().fold(f64::INFINITY, f64::min);
+            let mut delta_edge = NONE;
+            let mut delta_blossom = NONE;
+            for v in 0..n {
+                if self.label[self.in_blossom[v]] == 0 && self.best_edge[v] != NONE {
+                    let d = self.slack(self.best_edge[v]);
+                    if d < delta {
+                        delta = d;
+                        delta_type = 2;
+                        delta_edge = self.best_edge[v];
+                    }
+                }
+            }
+            for b in 0..2 * n {
+                if self.blossom_parent[b] == NONE && self.label[b] == 1 && self.best_edge[b] != NONE {
+                    let d = self.slack(self.best_edge[b]) / 2f64;
+                    if d < delta {
+                        delta = d;
+                        delta_type = 3;
+                        delta_edge = self.best_edge[b];
+                    }
+                }
+            }
+            for b in n..2 * n {
+                if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE && self.label[b] == 2 && self.dual[b] < delta {
+                    delta = self.dual[b];
+                    delta_type = 4;
+                    delta_blossom = b;
+                }
+            }
+            for v in 0..n {
+                match self.label[self.in_blossom[v]] {
+                    1 => self.dual[v] -= delta,
+                    2 => self.dual[v] += delta,
+                    _ => (),
+                }
+            }
+            for b in n..2 * n {
+                if self.blossom_base[b] != NONE && self.blossom_parent[b] == NONE {
+                    match self.label[b] {
+                        1 => self.dual[b] += delta,
+                        2 => self.dual[b] -= delta,
+                        _ => (),
+                    }
+                }
+            }
+            match delta_type {
+                2 => {
+                    self.allow_edge[delta_edge] = true;
+                    let (i, j, _) = self.edges[delta_edge];
+                    self.queue.push(if self.label[self.in_blossom[i]] == 0 { j } else { i });
+                },
+                3 => {
+                    self.allow_edge[delta_edge] = true;
+                    self.queue.push(self.edges[delta_edge].0);
+                },
+                4 => self.expand_blossom(delta_blossom, false),
+                // Optimum reached
+                _ => return false,
+            }
+        }
+    }
+
+    pub(crate) fn run(mut self) -> Vec<usize> {
+        let n = self.vertex_count;
+        for _ in 0..n {
+            if !self.stage() {
+                break;
+            }
+            for b in n..2 * n {
+                if self.blossom_parent[b] == NONE && self.blossom_base[b] != NONE && self.label[b] == 1 && self.dual[b] == 0f64 {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+        self.mate.iter().map(|&p| if p == NONE { NONE } else { self.endpoint[p] }).collect()
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MATCHING                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Matching
+/// 
+/// ## Description
+/// A matching is a set of edges without common endpoints. All functions of  this  trait
+/// work for arbitrary (not necessarily bipartite)  graphs,  ignore  the  directions  of
+/// edges and never include self-loops.
+pub trait Matching<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Maximum matching
+    /// 
+    /// ## Description
+    /// Find a matching with the largest number of edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `MatchedEdges<EdgeIdType, VertexIdType>` - the edges of the matching sorted by
+    /// their first endpoint.
+    /// 
+    /// ## Details
+    /// Uses                 Edmonds'                 blossom                  algorithm
+    /// [[source](https://doi.org/10.4153/CJM-1965-045-4)] which repeatedly searches for
+    /// augmenting paths contracting odd cycles on the way.  Among  parallel  edges,  an
+    /// arbitrary one is used.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn maximum_matching(&self) -> MatchedEdges<EdgeIdType, VertexIdType>;
+    /// # Maximum weight matching
+    /// 
+    /// ## Description
+    /// Find a matching with the largest total weight of edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// endpoints of the edge (the smaller one goes first) and the ID of the  edge,  and
+    /// returns the weight of the edge.
+    /// 
+    /// ## Returns
+    /// * `(f64, MatchedEdges<EdgeIdType, VertexIdType>)` -  the  total  weight  of  the
+    /// matching and its edges sorted by their first endpoint.
+    /// 
+    /// ## Details
+    /// Uses the  primal-dual  blossom  algorithm  of  Edmonds  in  Galil's  formulation
+    /// [[source](https://doi.org/10.1145/6462.6502)]. The matching isn't required to be
+    /// of maximum cardinality, so edges with non-positive weights are  never  included.
+    /// Among parallel edges, the heaviest one is used. Weights must be finite.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn maximum_weight_matching<WeightFnType>(&self, weight_fn: WeightFnType) -> (f64, MatchedEdges<EdgeIdType, VertexIdType>)
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Sorted vertices and, for each unordered pair of adjacent vertices numbered by their
+    // positions, the best of the parallel edges according to `weight_fn`
+    fn matching_edges<WeightFnType>(&self, weight_fn: WeightFnType) -> (Vec<VertexIdType>, BestEdges<EdgeIdType>)
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut edges = HashMap::new();
+        for edge in self.iter_e() {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            if i == j {
+                continue;
+            }
+            let (i, j) = (i.min(j), i.max(j));
+            let weight = weight_fn(&vertices[i], &vertices[j], &edge.edge_id);
+            match edges.entry((i, j)) {
+                Entry::Vacant(entry) => {
+                    entry.insert((weight, edge.edge_id));
+                },
+                Entry::Occupied(mut entry) => if weight > entry.get().0 {
+                    entry.insert((weight, edge.edge_id));
+                },
+            }
+        }
+        (vertices, edges)
+    }
+}
+
+// Graph::Matching
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Matching<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn maximum_matching(&self) -> MatchedEdges<EdgeIdType, VertexIdType> {
+        let (vertices, mut edges) = self.matching_edges(|_, _, _| 1f64);
+        let mut adjacency = vec![Vec::new(); vertices.len()];
+        for &(i, j) in edges.keys().sorted() {
+            adjacency[i].push(j);
+            adjacency[j].push(i);
+        }
+        let mate = BlossomSearch::new(&adjacency).run();
+        (0..vertices.len())
+            .filter(|&i| mate[i] != NONE && i < mate[i])
+            .map(|i| (vertices[i].clone(), vertices[mate[i]].clone(), edges.remove(&(i, mate[i])).unwrap().1))
+            .collect()
+    }
+
+    fn maximum_weight_matching<WeightFnType>(&self, weight_fn: WeightFnType) -> (f64, MatchedEdges<EdgeIdType, VertexIdType>)
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        let (vertices, mut edges) = self.matching_edges(weight_fn);
+        let positive_edges = edges.iter().filter(|x| x.1.0 > 0f64).map(|(&(i, j), &(weight, _))| (i, j, weight)).sorted_by_key(|x| (x.0, x.1)).collect();
+        let mate = WeightedBlossomSearch::new(vertices.len(), positive_edges).run();
+        let mut total_weight = 0f64;
+        let mut answer = Vec::new();
+        for i in (0..vertices.len()).filter(|&i| mate[i] != NONE && i < mate[i]) {
+            let (weight, edge_id) = edges.remove(&(i, mate[i])).unwrap();
+            total_weight += weight;
+            answer.push((vertices[i].clone(), vertices[mate[i]].clone(), edge_id));
+        }
+        (total_weight, answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn maximum_matching() {
+        // Two triangles joined by a path: a greedy matching would get stuck in a blossom
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..8 {
+            g.add_v(None);
+        }
+        for (x, y) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 6), (6, 7), (7, 5), (0, 0)] {
+            g.add_e(&x, &y, x % 2 == 0, None).unwrap();
+        }
+        g.add_e(&1, &0, false, None).unwrap();
+        let matching = g.maximum_matching();
+        assert_eq!(matching.len(), 4);
+        assert!(matching.iter().all(|(x, y, edge_id)| x < y && g.contains_e(x, y, edge_id).is_some()));
+        let mut matched: Vec<usize> = matching.iter().flat_map(|x| [x.0, x.1]).collect();
+        matched.sort();
+        matched.dedup();
+        assert_eq!(matched.len(), 8);
+    }
+
+    #[test]
+    fn maximum_weight_matching() {
+        // Neither the heaviest edge 1-2 nor the triangle 0-1-2 belongs to the optimum
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        let weights = HashMap::from([((0, 1), 6.0), ((1, 2), 10.0), ((0, 2), 6.0), ((2, 3), 5.5), ((3, 4), 6.0), ((4, 5), -1.0), ((1, 5), 4.5), ((5, 6), 0.25), ((6, 6), 100.0)]);
+        for (x, y) in weights.keys() {
+            g.add_e(x, y, false, None).unwrap();
+        }
+        let (total_weight, matching) = g.maximum_weight_matching(|x, y, _| weights[&(*x, *y)]);
+        assert_eq!(total_weight, 16.5);
+        assert_eq!(matching.iter().map(|x| (x.0, x.1)).collect::<Vec<_>>(), vec![(0, 2), (1, 5), (3, 4)]);
+    }
+}
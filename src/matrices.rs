@@ -0,0 +1,314 @@
+//! # Matrices module
+//! 
+//! ## Description
+//! This module contains [`Matrix`], a dense matrix of `f64` values used to pass numeric
+//! data of  [`Graph`]s  to  numerical  and  machine  learning  pipelines,  e.g.  vertex
+//! attributes assembled by [`Graph::to_attribute_matrix`] or Laplacian  matrices  built
+//! by [`Graph::to_laplacian_matrix`].
+//! 
+//! The values are stored row by row  in  a  single  vector,  which  is  the  layout  of
+//! row-major   arrays   of    the    popular    numerical    crates.    For    example,
+//! `ndarray::Array2<f64>` is built from the shape and the values of the matrix  by  its
+//! `from_shape_vec` function without copying.
+//! 
+//! [`Graph`]: crate::Graph
+use std::{collections::HashMap, ops::{Index, IndexMut}};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, io::AttributeCollectionIO, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MATRIX                                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Dense matrix
+/// 
+/// ## Description
+/// A dense matrix of `f64` values stored row by row.
+/// 
+/// ## Details
+/// Elements are accessed by indexing with the pairs of their row  and  column  indices,
+/// e.g. `matrix[(i, j)]`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matrix {
+    columns: usize,
+    rows: usize,
+    values: Vec<f64>,
+}
+
+// Matrix::Matrix
+impl Matrix {
+    /// # Number of columns
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of columns.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// # Create matrix from values
+    /// 
+    /// ## Arguments
+    /// * `shape` : `(usize, usize)` - the numbers of rows and columns.
+    /// * `values` : `Vec<f64>` - the values of the matrix row by row.
+    /// 
+    /// ## Returns
+    /// * `Option<Matrix>` - `Some(value)` is returned if the number of  values  matches
+    /// the shape, `value` in this case is the matrix; `None` is returned otherwise.
+    pub fn from_shape_vec(shape: (usize, usize), values: Vec<f64>) -> Option<Self> {
+        match shape.0.checked_mul(shape.1) == Some(values.len()) {
+            true => Some(Matrix { columns: shape.1, rows: shape.0, values }),
+            false => None,
+        }
+    }
+
+    /// # Unwrap values
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<f64>` - the values of the matrix row by row.
+    pub fn into_vec(self) -> Vec<f64> {
+        self.values
+    }
+
+    /// # Create zero matrix
+    /// 
+    /// ## Arguments
+    /// * `rows` : `usize` - the number of rows.
+    /// * `columns` : `usize` - the number of columns.
+    /// 
+    /// ## Returns
+    /// * `Matrix` - the matrix filled with zeros.
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Matrix { columns, rows, values: vec![0.0; rows * columns] }
+    }
+
+    /// # Row of matrix
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `row` : `usize` - the index of the row.
+    /// 
+    /// ## Returns
+    /// * `&[f64]` - the values of the row.
+    /// 
+    /// ## Details
+    /// The function panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> &[f64] {
+        assert!(row < self.rows, "Row index {} is out of bounds.", row);
+        &self.values[row * self.columns..(row + 1) * self.columns]
+    }
+
+    /// # Number of rows
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of rows.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// # Shape of matrix
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `(usize, usize)` - the numbers of rows and columns.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+}
+
+// Matrix::Index
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        assert!(index.0 < self.rows && index.1 < self.columns, "Index {:?} is out of bounds.", index);
+        &self.values[index.0 * self.columns + index.1]
+    }
+}
+
+// Matrix::IndexMut
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        assert!(index.0 < self.rows && index.1 < self.columns, "Index {:?} is out of bounds.", index);
+        &mut self.values[index.0 * self.columns + index.1]
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ATTRIBUTE MATRIX                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Assemble vertex attributes into matrix
+    /// 
+    /// ## Description
+    /// Build the matrix with one row for each vertex and one column  for  each  of  the
+    /// given numeric vertex attributes.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `names` : `&[&str]` - the names of the attributes, one for each column.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<(Matrix, Vec<VertexIdType>)>`   -    `Ok((matrix, ids))`    is
+    /// returned if all given attributes are numeric,  `matrix`  in  this  case  is  the
+    /// assembled matrix and `ids` are the IDs of the vertices of its rows; `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Rows follow the vertices in the ascending order of  their  IDs.  Values  of  all
+    /// numeric types are converted into `f64`, vertices that don't  have  some  of  the
+    /// attributes get `f64::NAN` in the respective cells, so that missing values can be
+    /// imputed by the pipeline.
+    pub fn to_attribute_matrix(&self, names: &[&str]) -> CrabNetsResult<(Matrix, Vec<VertexIdType>)> {
+        const FUNCTION_PATH: &str = "Graph::Graph::to_attribute_matrix";
+        let mut ids = self.iter_v().collect::<Vec<_>>();
+        ids.sort();
+        let mut answer = Matrix::new(ids.len(), names.len());
+        for (row, id) in ids.iter().enumerate() {
+            let attributes = self.v_attrs(id)?;
+            for (column, name) in names.iter().enumerate() {
+                answer[(row, column)] = match attributes.io_query_contents(name) {
+                    Some(value) => match value.as_f64() {
+                        Some(value) => value,
+                        None => return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::AttributeTypeMismatch { name: name.to_string() })),
+                    },
+                    None => f64::NAN,
+                };
+            }
+        }
+        Ok((answer, ids))
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * LAPLACIAN MATRIX                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Build Laplacian matrix
+    /// 
+    /// ## Description
+    /// Build the Laplacian matrix L = D - A of the graph,  where  A  is  the  adjacency
+    /// matrix and D is the diagonal matrix of degrees.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `normalised` : `bool` - if `true`, the symmetric normalised Laplacian matrix I
+    /// - D^(-1/2) A D^(-1/2) is built instead.
+    /// 
+    /// ## Returns
+    /// * `(Matrix, Vec<VertexIdType>)` - the  Laplacian  matrix  and  the  IDs  of  the
+    /// vertices of its rows (and columns).
+    /// 
+    /// ## Details
+    /// Rows and columns follow the vertices  in  the  ascending  order  of  their  IDs.
+    /// Directions of edges are ignored, parallel edges add up, self-loops are  ignored.
+    /// Rows and columns of isolated vertices of the  normalised  Laplacian  matrix  are
+    /// filled with zeros.
+    pub fn to_laplacian_matrix(&self, normalised: bool) -> (Matrix, Vec<VertexIdType>) {
+        let mut ids = self.iter_v().collect::<Vec<_>>();
+        ids.sort();
+        let indices = ids.iter().enumerate().map(|(index, id)| (id.clone(), index)).collect::<HashMap<_, _>>();
+        let mut answer = Matrix::new(ids.len(), ids.len());
+        for edge in self.iter_e().filter(|edge| edge.id1 != edge.id2) {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            answer[(i, j)] -= 1.0;
+            answer[(j, i)] -= 1.0;
+            answer[(i, i)] += 1.0;
+            answer[(j, j)] += 1.0;
+        }
+        if normalised {
+            let scales = (0..ids.len()).map(|i| match answer[(i, i)] > 0.0 {
+                true => 1.0 / answer[(i, i)].sqrt(),
+                false => 0.0,
+            }).collect::<Vec<_>>();
+            for i in 0..ids.len() {
+                for j in 0..ids.len() {
+                    answer[(i, j)] = match (i == j, scales[i] > 0.0) {
+                        (true, true) => 1.0,
+                        _ => answer[(i, j)] * scales[i] * scales[j],
+                    };
+                }
+            }
+        }
+        (answer, ids)
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn attribute_matrix() {
+        let g: graph!(A ---X--> A) = "AV I4 Age\nAV F8 Score\nAV S Name\n\nV 2 30 0.5 \"b\"\nV 0 20 1.5 \"a\"\n".parse().unwrap();
+        let (matrix, ids) = g.to_attribute_matrix(&["Score", "Age", "Height"]).unwrap();
+        assert_eq!(ids, vec![0, 2]);
+        assert_eq!(matrix.shape(), (2, 3));
+        assert_eq!(&matrix.row(0)[..2], &[1.5, 20.0]);
+        assert_eq!(&matrix.row(1)[..2], &[0.5, 30.0]);
+        assert!(matrix[(0, 2)].is_nan());
+        assert!(g.to_attribute_matrix(&["Name"]).is_err());
+        assert!(Matrix::from_shape_vec((2, 2), vec![0.0; 3]).is_none());
+        assert_eq!(Matrix::from_shape_vec((1, 2), vec![1.0, 2.0]).unwrap().into_vec(), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn laplacian_matrix() {
+        let g: graph!(A ===X==> A) = "V 0\nV 1\nV 2\nV 3\n\nA 0 1\nE 1 0\nE 1 2\nA 2 2\n".parse().unwrap();
+        let (matrix, ids) = g.to_laplacian_matrix(false);
+        assert_eq!(ids, vec![0, 1, 2, 3]);
+        assert_eq!(matrix.into_vec(), vec![2.0, -2.0, 0.0, 0.0, -2.0, 3.0, -1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let (matrix, _) = g.to_laplacian_matrix(true);
+        assert!((matrix[(0, 1)] + 2.0 / 6.0_f64.sqrt()).abs() < 1e-12);
+        assert_eq!((matrix[(1, 1)], matrix[(3, 3)]), (1.0, 0.0));
+    }
+}
@@ -0,0 +1,865 @@
+//! # Metrics module
+//! 
+//! ## Description
+//! This module contains [`Metrics`] trait and its implementation for [`Graph`].
+//! 
+//! Besides, [`Graph`] gets methods to randomise itself while  keeping  the  degrees  of
+//! vertices and to compute strengths, i.e. weighted degrees, of vertices.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet, VecDeque};
+use rand::Rng;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, EdgeIteratorItem, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Rich-club coefficients of a simple undirected graph over vertices numbered from 0, the
+// k-th value is computed for vertices of degree greater than k as long as there are at
+// least 2 of them
+fn rich_club_of(neighbours: &[HashSet<usize>]) -> Vec<f64> {
+    let degrees: Vec<usize> = neighbours.iter().map(|x| x.len()).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    // Vertices and edges whose smallest degree of an endpoint is exactly k
+    let mut vertex_counts = vec![0usize; max_degree + 1];
+    let mut edge_counts = vec![0usize; max_degree + 1];
+    for (i, adjacent) in neighbours.iter().enumerate() {
+        vertex_counts[degrees[i]] += 1;
+        for &j in adjacent.iter().filter(|&&j| j > i) {
+            edge_counts[degrees[i].min(degrees[j])] += 1;
+        }
+    }
+    let mut answer = Vec::new();
+    let (mut richer_vertices, mut richer_edges) = (neighbours.len(), neighbours.iter().map(|x| x.len()).sum::<usize>() / 2);
+    for k in 0..=max_degree {
+        richer_vertices -= vertex_counts[k];
+        richer_edges -= edge_counts[k];
+        if richer_vertices < 2 {
+            break;
+        }
+        answer.push(2.0 * richer_edges as f64 / (richer_vertices * (richer_vertices - 1)) as f64);
+    }
+    answer
+}
+
+// Core numbers of a simple undirected graph over vertices numbered from 0 and the order
+// in which they were processed (the degeneracy ordering), vertices are processed in
+// ascending order of their current degrees kept in bins
+pub(crate) fn core_decomposition_of(neighbours: &[HashSet<usize>]) -> (Vec<usize>, Vec<usize>) {
+    let mut degrees: Vec<usize> = neighbours.iter().map(|x| x.len()).collect();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+    // `order` is sorted by current degrees, `bins[d]` is the start of degree `d` in it
+    let mut bins = vec![0usize; max_degree + 2];
+    for &degree in degrees.iter() {
+        bins[degree + 1] += 1;
+    }
+    for d in 1..bins.len() {
+        bins[d] += bins[d - 1];
+    }
+    let mut order = vec![0usize; neighbours.len()];
+    let mut positions = vec![0usize; neighbours.len()];
+    let mut next = bins.clone();
+    for (v, &degree) in degrees.iter().enumerate() {
+        positions[v] = next[degree];
+        order[next[degree]] = v;
+        next[degree] += 1;
+    }
+    for i in 0..order.len() {
+        let v = order[i];
+        for &u in neighbours[v].iter() {
+            if degrees[u] > degrees[v] {
+                // Swap `u` with the first vertex of its bin and shrink the bin by one
+                let (pu, pw) = (positions[u], bins[degrees[u]]);
+                let w = order[pw];
+                order.swap(pu, pw);
+                positions[u] = pw;
+                positions[w] = pu;
+                bins[degrees[u]] += 1;
+                degrees[u] -= 1;
+            }
+        }
+    }
+    (degrees, order)
+}
+
+// Eccentricities of all vertices of a simple undirected graph over vertices numbered
+// from 0 found by breadth-first search from each vertex, `None` if the graph is empty
+// or disconnected
+fn eccentricities_of(neighbours: &[HashSet<usize>]) -> Option<Vec<usize>> {
+    if neighbours.is_empty() {
+        return None;
+    }
+    let mut answer = Vec::with_capacity(neighbours.len());
+    let mut distances = vec![usize::MAX; neighbours.len()];
+    for source in 0..neighbours.len() {
+        distances.fill(usize::MAX);
+        distances[source] = 0;
+        let mut unvisited_vertices = VecDeque::from([source]);
+        let (mut visited_count, mut eccentricity) = (0usize, 0usize);
+        while let Some(v) = unvisited_vertices.pop_front() {
+            visited_count += 1;
+            eccentricity = distances[v];
+            for &u in neighbours[v].iter() {
+                if distances[u] == usize::MAX {
+                    distances[u] = distances[v] + 1;
+                    unvisited_vertices.push_back(u);
+                }
+            }
+        }
+        if visited_count < neighbours.len() {
+            return None;
+        }
+        answer.push(eccentricity);
+    }
+    Some(answer)
+}
+
+// Maximum number of attempted swaps per requested swap in `Graph::double_edge_swap`
+const MAX_ATTEMPTS_PER_SWAP: usize = 100;
+
+
+
+// Randomise a simple undirected graph by double edge swaps that keep the degrees of all
+// vertices, swaps creating self-loops or parallel edges are rejected
+fn rewire<R>(neighbours: &mut [HashSet<usize>], attempts: usize, rng: &mut R)
+where
+    R: Rng,
+{
+    let mut edges: Vec<(usize, usize)> = neighbours.iter().enumerate().flat_map(|(i, x)| x.iter().filter(move |&&j| j > i).map(move |&j| (i, j))).collect();
+    edges.sort();
+    if edges.len() < 2 {
+        return;
+    }
+    for _ in 0..attempts {
+        let i = rng.gen_range(0..edges.len());
+        let j = rng.gen_range(0..edges.len() - 1);
+        let j = if j >= i { j + 1 } else { j };
+        let (a, b) = edges[i];
+        let (c, d) = if rng.gen_bool(0.5) { edges[j] } else { (edges[j].1, edges[j].0) };
+        if a == d || c == b || neighbours[a].contains(&d) || neighbours[c].contains(&b) {
+            continue;
+        }
+        neighbours[a].remove(&b);
+        neighbours[b].remove(&a);
+        neighbours[c].remove(&d);
+        neighbours[d].remove(&c);
+        neighbours[a].insert(d);
+        neighbours[d].insert(a);
+        neighbours[c].insert(b);
+        neighbours[b].insert(c);
+        edges[i] = (a, d);
+        edges[j] = (c, b);
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * METRICS                                                                           *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Metrics
+/// 
+/// ## Description
+/// Metrics describing the global organisation  of  a  network:  whether  well-connected
+/// vertices form a densely interconnected rich club, whether the network splits into  a
+/// dense core and a sparse periphery, and which vertices are  the  closest  to  or  the
+/// farthest from all the others.
+/// 
+/// All functions ignore the directions  and  the  multiplicity  of  edges  as  well  as
+/// self-loops, i.e. they treat the graph as simple and undirected.
+pub trait Metrics<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Centre
+    /// 
+    /// ## Description
+    /// Find the vertices with the smallest eccentricity.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<HashSet<VertexIdType>>` - `Some(value)` is returned if  the  graph  is
+    /// non-empty and connected, `value` in this case is the set  of  central  vertices;
+    /// `None` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The eccentricity of a vertex is the largest distance from it to  another  vertex
+    /// (see [`Metrics::eccentricities`]), the smallest eccentricity is  the  radius  of
+    /// the graph. Central vertices are the best locations for a  single  facility  that
+    /// must be close to every vertex.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn center(&self) -> Option<HashSet<VertexIdType>>;
+    /// # Core numbers
+    /// 
+    /// ## Description
+    /// Compute the core number of each vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `HashMap<VertexIdType, usize>` - the core number of each vertex.
+    /// 
+    /// ## Details
+    /// The k-core of a graph is its largest subgraph where every vertex has  degree  at
+    /// least k. The core number of a vertex is the  largest  k  such  that  the  vertex
+    /// belongs  to  the  k-core.  Uses  the  algorithm  of   Batagelj   and   Zaveršnik
+    /// [[source](https://arxiv.org/abs/cs/0310049)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn core_numbers(&self) -> HashMap<VertexIdType, usize>;
+    /// # Core–periphery score
+    /// 
+    /// ## Description
+    /// Split the vertices into a core and a periphery and measure how  well  the  graph
+    /// fits the ideal core–periphery structure.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<(HashSet<VertexIdType>, f64)>` - `Some((core, score))` is returned  if
+    /// the score is defined, `core` in this case  is  the  set  of  core  vertices  and
+    /// `score` lies in [-1, 1]; `None` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The core is the k-core with the largest k (see  [`Metrics::core_numbers`]).  The
+    /// score     is     the     correlation      of      Borgatti      and      Everett
+    /// [[source](https://doi.org/10.1016/S0378-8733(99)00019-2)] between the  adjacency
+    /// matrix and the ideal pattern where exactly the pairs of vertices with  at  least
+    /// one vertex in the core are adjacent. A score  close  to  1  means  a  pronounced
+    /// core–periphery structure.
+    /// 
+    /// The score isn't defined if the graph has fewer than 2 vertices, has no edges, is
+    /// complete or consists of its core only.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn core_periphery(&self) -> Option<(HashSet<VertexIdType>, f64)>;
+    /// # Eccentricities
+    /// 
+    /// ## Description
+    /// Compute the eccentricity of each vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<HashMap<VertexIdType, usize>>` -  `Some(value)`  is  returned  if  the
+    /// graph is non-empty and connected, `value` in this case holds the eccentricity of
+    /// each vertex; `None` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The eccentricity of a vertex is the largest number of edges on the shortest path
+    /// from it to another vertex. The largest eccentricity is the diameter of the graph
+    /// and the smallest one is its radius. Eccentricities are infinite in  disconnected
+    /// graphs, hence `None`.
+    /// 
+    /// Uses breadth-first search from each vertex.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn eccentricities(&self) -> Option<HashMap<VertexIdType, usize>>;
+    /// # Normalised rich-club coefficient
+    /// 
+    /// ## Description
+    /// Compute the rich-club coefficient relative to randomised networks with the  same
+    /// degrees.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `null_models` : `usize` - the number of randomised networks.
+    /// * `swaps_per_edge` : `usize` - the number of attempted edge swaps per edge  when
+    /// randomising the network.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<f64>>` - `Ok(value)` is returned if the computation wasn't
+    /// cancelled,  `value`  in  this  case  has  the  same  length  as  the  result  of
+    /// [`Metrics::rich_club_coefficient`] and its k-th element is the  coefficient  for
+    /// degree k divided by its  average  over  the  randomised  networks;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Randomised networks are obtained by double edge swaps that replace edges (a,  b)
+    /// and (c, d) with (a, d) and (c, b) unless this creates a self-loop or a  parallel
+    /// edge [[source](https://doi.org/10.1038/nphys209)]. Since hubs are likely  to  be
+    /// connected by chance, only values greater than 1 indicate a  rich  club.  If  the
+    /// average coefficient over the randomised networks is 0, the corresponding element
+    /// is NaN.
+    /// 
+    /// The  function  checks  the  given  [`ExecutionControl`]  before  building   each
+    /// randomised network and returns an error of kind [`Cancelled`][cancelled] if  the
+    /// computation is cancelled.
+    /// 
+    /// ## Complexity
+    /// Time: O(`null_models` · (`swaps_per_edge` + Δ) · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    fn normalised_rich_club_coefficient<R>(&self, null_models: usize, swaps_per_edge: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<Vec<f64>>
+    where
+        R: Rng;
+    /// # Periphery
+    /// 
+    /// ## Description
+    /// Find the vertices with the largest eccentricity.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<HashSet<VertexIdType>>` - `Some(value)` is returned if  the  graph  is
+    /// non-empty and connected, `value` in this case is the set of peripheral vertices;
+    /// `None` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The largest eccentricity (see [`Metrics::eccentricities`]) is  the  diameter  of
+    /// the graph, so every peripheral vertex is an endpoint of a longest shortest path.
+    /// Not to be confused with the periphery of [`Metrics::core_periphery`].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn periphery(&self) -> Option<HashSet<VertexIdType>>;
+    /// # Rich-club coefficient
+    /// 
+    /// ## Description
+    /// Compute the rich-club coefficient for all degree thresholds.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<f64>` - the k-th element is the rich-club coefficient for degree  k,  the
+    /// vector ends at the first k with fewer than 2 vertices of degree greater than k.
+    /// 
+    /// ## Details
+    /// The rich-club coefficient for degree k is the density of the subgraph induced by
+    /// the vertices of degree greater than k, i.e. 2 · Eₖ / (Nₖ · (Nₖ - 1)),  where  Nₖ
+    /// is the number of these vertices and Eₖ is  the  number  of  edges  between  them
+    /// [[source](https://doi.org/10.1103/PhysRevLett.87.198701)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn rich_club_coefficient(&self) -> Vec<f64>;
+}
+
+
+
+// Graph::Metrics
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Metrics<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn center(&self) -> Option<HashSet<VertexIdType>> {
+        let eccentricities = self.eccentricities()?;
+        let radius = *eccentricities.values().min().unwrap();
+        Some(eccentricities.into_iter().filter(|x| x.1 == radius).map(|x| x.0).collect())
+    }
+
+    fn core_numbers(&self) -> HashMap<VertexIdType, usize> {
+        let (vertices, structure) = self.motif_structure();
+        vertices.into_iter().zip(core_decomposition_of(&structure.neighbours).0).collect()
+    }
+
+    fn core_periphery(&self) -> Option<(HashSet<VertexIdType>, f64)> {
+        let (vertices, structure) = self.motif_structure();
+        let (core_numbers, _) = core_decomposition_of(&structure.neighbours);
+        let max_core_number = core_numbers.iter().copied().max()?;
+        let is_core: Vec<bool> = core_numbers.iter().map(|x| *x == max_core_number).collect();
+        let periphery_count = is_core.iter().filter(|x| !**x).count();
+        let pair_count = (vertices.len() * vertices.len().saturating_sub(1) / 2) as f64;
+        let pattern_count = pair_count - (periphery_count * periphery_count.saturating_sub(1) / 2) as f64;
+        let (mut edge_count, mut matched_count) = (0usize, 0usize);
+        for (i, adjacent) in structure.neighbours.iter().enumerate() {
+            for &j in adjacent.iter().filter(|&&j| j > i) {
+                edge_count += 1;
+                matched_count += (is_core[i] || is_core[j]) as usize;
+            }
+        }
+        let edge_count = edge_count as f64;
+        let denominator = ((pair_count * edge_count - edge_count * edge_count) * (pair_count * pattern_count - pattern_count * pattern_count)).sqrt();
+        if denominator == 0.0 || denominator.is_nan() {
+            return None;
+        }
+        let core = vertices.into_iter().zip(is_core).filter(|x| x.1).map(|x| x.0).collect();
+        Some((core, (pair_count * matched_count as f64 - edge_count * pattern_count) / denominator))
+    }
+
+    fn eccentricities(&self) -> Option<HashMap<VertexIdType, usize>> {
+        let (vertices, structure) = self.motif_structure();
+        Some(vertices.into_iter().zip(eccentricities_of(&structure.neighbours)?).collect())
+    }
+
+    fn normalised_rich_club_coefficient<R>(&self, null_models: usize, swaps_per_edge: usize, rng: &mut R, control: &ExecutionControl) -> CrabNetsResult<Vec<f64>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::Metrics::normalised_rich_club_coefficient";
+        let (_, structure) = self.motif_structure();
+        let answer = rich_club_of(&structure.neighbours);
+        let edge_count = structure.neighbours.iter().map(|x| x.len()).sum::<usize>() / 2;
+        let mut random_totals = vec![0f64; answer.len()];
+        for _ in 0..null_models {
+            control.check(FUNCTION_PATH)?;
+            let mut neighbours = structure.neighbours.clone();
+            rewire(&mut neighbours, swaps_per_edge * edge_count, rng);
+            for (total, value) in random_totals.iter_mut().zip(rich_club_of(&neighbours)) {
+                *total += value;
+            }
+        }
+        Ok(answer.into_iter().zip(random_totals).map(|(value, total)| if total > 0.0 { value * null_models as f64 / total } else { f64::NAN }).collect())
+    }
+
+    fn periphery(&self) -> Option<HashSet<VertexIdType>> {
+        let eccentricities = self.eccentricities()?;
+        let diameter = *eccentricities.values().max().unwrap();
+        Some(eccentricities.into_iter().filter(|x| x.1 == diameter).map(|x| x.0).collect())
+    }
+
+    fn rich_club_coefficient(&self) -> Vec<f64> {
+        rich_club_of(&self.motif_structure().1.neighbours)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * NULL MODELS                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Double edge swap
+    /// 
+    /// ## Description
+    /// Randomise the graph in place by swaps of the endpoints of edges  that  keep  the
+    /// degrees of all vertices.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `n_swaps` : `usize` - the number of swaps to perform.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if the graph was randomised,
+    /// `value` in this case is the number of  performed  swaps;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Each swap picks two random edges (a, b) and (c, d) of the same kind and replaces
+    /// them with (a, d) and (c, b), undirected edges are randomly flipped before  that.
+    /// Swaps of directed edges keep the in- and out-degrees, swaps of undirected  edges
+    /// keep the undirected degrees of all vertices. Attribute collections  travel  with
+    /// the edges: (a, d) gets the attributes of (a, b) and (c, b) gets those of (c, d).
+    /// Self-loops never take part in swaps.
+    /// 
+    /// Swaps that would create a self-loop are rejected. In simple graphs,  swaps  that
+    /// would connect already adjacent vertices are  rejected  too,  multigraphs  accept
+    /// them and get parallel edges. Rejected swaps are retried, but no  more  than  100
+    /// attempts are made  per  requested  swap,  so  fewer  swaps  than  requested  are
+    /// performed if the graph has too few valid swaps (e.g. a star). A graph randomised
+    /// this way is the standard null model  to  test  whether  an  observed  metric  is
+    /// explained             by             the              degrees              alone
+    /// [[source](https://doi.org/10.1126/science.1065103)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|E| log |E| + `n_swaps` · Δ).
+    /// 
+    /// Space: O(|E|).
+    pub fn double_edge_swap<R>(&mut self, n_swaps: usize, rng: &mut R) -> CrabNetsResult<usize>
+    where
+        R: Rng,
+    {
+        // Endpoints (the source goes first for directed edges), IDs and kinds of edges
+        let mut edges = self.iter_e().filter(|x| x.id1 != x.id2).map(|x| match x.direction {
+            EdgeDirection::Directed2to1 => (x.id2, x.id1, x.edge_id, true),
+            direction => (x.id1, x.id2, x.edge_id, direction == EdgeDirection::Directed1to2),
+        }).collect::<Vec<_>>();
+        edges.sort();
+        if edges.len() < 2 {
+            return Ok(0);
+        }
+        let mut answer = 0;
+        for _ in 0..n_swaps.saturating_mul(MAX_ATTEMPTS_PER_SWAP) {
+            if answer == n_swaps {
+                break;
+            }
+            let i = rng.gen_range(0..edges.len());
+            let j = rng.gen_range(0..edges.len() - 1);
+            let j = if j >= i { j + 1 } else { j };
+            let (a, b, edge_id1, directed) = edges[i].clone();
+            let (c, d, edge_id2, other_directed) = edges[j].clone();
+            if directed != other_directed {
+                continue;
+            }
+            let (c, d) = if !directed && rng.gen_bool(0.5) { (d, c) } else { (c, d) };
+            if a == d || c == b || (!LocaleType::IS_MULTI && (self.e_direction_between(&a, &d).is_some() || self.e_direction_between(&c, &b).is_some())) {
+                continue;
+            }
+            let attributes1 = self.take_e(&a, &b, &edge_id1)?.unwrap();
+            let attributes2 = self.take_e(&c, &d, &edge_id2)?.unwrap();
+            let new_edge_id1 = self.add_e(&a, &d, directed, None)?;
+            *self.e_attrs_mut(&a, &d, &new_edge_id1)? = attributes1;
+            let new_edge_id2 = self.add_e(&c, &b, directed, None)?;
+            *self.e_attrs_mut(&c, &b, &new_edge_id2)? = attributes2;
+            edges[i] = (a, d, new_edge_id1, directed);
+            edges[j] = (c, b, new_edge_id2, directed);
+            answer += 1;
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * STRENGTH                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Total weight of the given edges
+    fn strength_of<EdgesType, WeightFnType>(edges: EdgesType, weight_fn: WeightFnType) -> f64
+    where
+        EdgesType: Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>>,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        edges.map(|x| weight_fn(&x.id1, &x.id2, &x.edge_id)).sum()
+    }
+
+    // Locale of the vertex with the given ID
+    fn strength_locale(&self, id: &VertexIdType, function_path: &str) -> CrabNetsResult<&LocaleType> {
+        self.edge_list.get(id).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() }))
+    }
+
+    /// # Strengths of vertices
+    /// 
+    /// ## Description
+    /// Compute the strength of each vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `weight_fn` : `WeightFnType` - a function that takes the IDs of the  endpoints
+    /// and the ID of an edge and returns its weight.
+    /// 
+    /// ## Returns
+    /// * `HashMap<VertexIdType, f64>`   -   the   strength   of   each   vertex,    see
+    /// [`Graph::v_strength`].
+    /// 
+    /// ## Details
+    /// The values form the strength distribution of the graph, the weighted counterpart
+    /// of its degree distribution.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    pub fn strengths<WeightFnType>(&self, weight_fn: WeightFnType) -> HashMap<VertexIdType, f64>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        self.edge_list.iter().map(|(id, locale)| (id.clone(), Self::strength_of(locale.iter_incident_e(), &weight_fn))).collect()
+    }
+
+    /// # Vertex strength
+    /// 
+    /// ## Description
+    /// Get the total weight of the edges incident on the vertex with the given ID.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// * `weight_fn` : `WeightFnType` - a function that takes the IDs of the  endpoints
+    /// and the ID of an edge and returns its weight.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<f64>` - `Ok(value)` is returned when the vertex with  ID  `id`
+    /// exists, `value` in this case is its strength; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Strength is the weighted counterpart of  [`BasicImmutableGraph::v_degree`]:  the
+    /// weights of the same edges the degree counts are summed up, so with  all  weights
+    /// equal to 1 the strength equals the degree. The first ID passed to `weight_fn` is
+    /// always `id`. Likewise, the strength of a vertex is the sum of its  in-strength,
+    /// out-strength and undirected strength.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg(`id`)).
+    /// 
+    /// Space: O(1).
+    pub fn v_strength<WeightFnType>(&self, id: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<f64>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_strength";
+        Ok(Self::strength_of(self.strength_locale(id, FUNCTION_PATH)?.iter_incident_e(), weight_fn))
+    }
+
+    /// # Vertex in-strength
+    /// 
+    /// ## Description
+    /// Get the total weight of the incoming edges of the vertex with the given ID.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// * `weight_fn` : `WeightFnType` - a function that takes the IDs of the  endpoints
+    /// and the ID of an edge and returns its weight.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<f64>` - `Ok(value)` is returned when the vertex with  ID  `id`
+    /// exists, `value` in this case is its in-strength; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::v_strength`].
+    pub fn v_strength_in<WeightFnType>(&self, id: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<f64>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_strength_in";
+        Ok(Self::strength_of(self.strength_locale(id, FUNCTION_PATH)?.iter_incident_e_in(), weight_fn))
+    }
+
+    /// # Vertex out-strength
+    /// 
+    /// ## Description
+    /// Get the total weight of the outgoing edges of the vertex with the given ID.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// * `weight_fn` : `WeightFnType` - a function that takes the IDs of the  endpoints
+    /// and the ID of an edge and returns its weight.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<f64>` - `Ok(value)` is returned when the vertex with  ID  `id`
+    /// exists,  `value`  in  this  case  is  its  out-strength;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::v_strength`].
+    pub fn v_strength_out<WeightFnType>(&self, id: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<f64>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_strength_out";
+        Ok(Self::strength_of(self.strength_locale(id, FUNCTION_PATH)?.iter_incident_e_out(), weight_fn))
+    }
+
+    /// # Vertex undirected strength
+    /// 
+    /// ## Description
+    /// Get the total weight of the undirected edges incident on  the  vertex  with  the
+    /// given ID.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// * `weight_fn` : `WeightFnType` - a function that takes the IDs of the  endpoints
+    /// and the ID of an edge and returns its weight.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<f64>` - `Ok(value)` is returned when the vertex with  ID  `id`
+    /// exists, `value` in this case is its undirected strength;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// See [`Graph::v_strength`].
+    pub fn v_strength_undir<WeightFnType>(&self, id: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<f64>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::Graph::v_strength_undir";
+        Ok(Self::strength_of(self.strength_locale(id, FUNCTION_PATH)?.iter_incident_e_undir(), weight_fn))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use crate::io::AttributeCollectionIO;
+    use super::*;
+
+    #[test]
+    fn rich_club_coefficient() {
+        // 4-clique with a pendant vertex attached to each clique vertex
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..8 {
+            g.add_v(None);
+        }
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                g.add_e(&i, &j, false, None).unwrap();
+            }
+            g.add_e(&i, &(i + 4), false, None).unwrap();
+        }
+        let coefficients = g.rich_club_coefficient();
+        assert_eq!(coefficients, vec![10.0 / 28.0, 1.0, 1.0, 1.0]);
+        let control = ExecutionControl::new();
+        let mut rng = StdRng::seed_from_u64(7);
+        let normalised = g.normalised_rich_club_coefficient(10, 10, &mut rng, &control).unwrap();
+        assert_eq!(normalised.len(), 4);
+        // Any simple graph with these degrees has the same number of edges
+        assert!((normalised[0] - 1.0).abs() < 1e-12);
+        assert!(normalised[1..].iter().all(|x| *x >= 1.0));
+        control.cancel();
+        assert!(matches!(g.normalised_rich_club_coefficient(1, 1, &mut rng, &control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn core_periphery() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..8 {
+            g.add_v(None);
+        }
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                g.add_e(&i, &j, i % 2 == 0, None).unwrap();
+            }
+            g.add_e(&i, &(i + 4), false, None).unwrap();
+        }
+        g.add_e(&4, &5, true, None).unwrap();
+        g.add_e(&5, &4, true, None).unwrap();
+        g.add_e(&6, &6, false, None).unwrap();
+        let core_numbers = g.core_numbers();
+        assert_eq!((0..8).map(|x| core_numbers[&x]).collect::<Vec<_>>(), vec![3, 3, 3, 3, 2, 2, 1, 1]);
+        let (core, score) = g.core_periphery().unwrap();
+        assert_eq!(core, HashSet::from([0, 1, 2, 3]));
+        // 38 = 28 · 10 - 11 · 22, where 28 pairs, 11 edges, 22 pairs in the pattern and 10
+        // edges matching the pattern
+        assert!((score - 38.0 / (187f64 * 132f64).sqrt()).abs() < 1e-12);
+        let empty: graph!(X ---X--- X) = Graph::new();
+        assert!(empty.core_periphery().is_none());
+    }
+
+    #[test]
+    fn eccentricities() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (2, 1), (2, 3), (3, 4), (2, 5)] {
+            g.add_e(&id1, &id2, true, None).unwrap();
+        }
+        g.add_e(&3, &4, false, None).unwrap();
+        g.add_e(&5, &5, false, None).unwrap();
+        let eccentricities = g.eccentricities().unwrap();
+        assert_eq!((0..6).map(|x| eccentricities[&x]).collect::<Vec<_>>(), vec![4, 3, 2, 3, 4, 3]);
+        assert_eq!(g.center().unwrap(), HashSet::from([2]));
+        assert_eq!(g.periphery().unwrap(), HashSet::from([0, 4]));
+        g.add_v(None);
+        assert!(g.eccentricities().is_none() && g.center().is_none() && g.periphery().is_none());
+        let empty: graph!(X ---X--- X) = Graph::new();
+        assert!(empty.center().is_none());
+    }
+
+    #[test]
+    fn double_edge_swap() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..12 {
+            g.add_v(None);
+        }
+        for i in 0..12 {
+            g.add_e(&i, &((i + 1) % 12), false, None).unwrap();
+            g.add_e(&i, &((i + 5) % 12), true, None).unwrap();
+        }
+        g.add_e(&0, &0, false, None).unwrap();
+        let degrees = |g: &graph!(X ---X--> X)| (0..12).map(|i| (g.v_degree_in(&i).unwrap(), g.v_degree_out(&i).unwrap(), g.v_degree_undir(&i).unwrap())).collect::<Vec<_>>();
+        let (initial_degrees, initial_edges) = (degrees(&g), g.iter_e().map(|x| (x.id1, x.id2)).collect::<HashSet<_>>());
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(g.double_edge_swap(20, &mut rng).unwrap(), 20);
+        assert_eq!(degrees(&g), initial_degrees);
+        assert_eq!(g.iter_e().count(), 25);
+        assert!(g.contains_e(&0, &0, &0).is_some());
+        assert_eq!(g.iter_e().filter(|x| x.id1 == x.id2).count(), 1);
+        assert_ne!(g.iter_e().map(|x| (x.id1, x.id2)).collect::<HashSet<_>>(), initial_edges);
+        // Star has no valid swaps
+        let mut star: graph!(X ---X--- X) = Graph::new();
+        let center = star.add_v(None);
+        for _ in 0..5 {
+            let leaf = star.add_v(None);
+            star.add_e(&center, &leaf, false, None).unwrap();
+        }
+        assert_eq!(star.double_edge_swap(3, &mut rng).unwrap(), 0);
+    }
+
+    #[test]
+    fn strength() {
+        let g: graph!(A ===A==> A) = "AE F8 Weight\n\nV 0\nV 1\nV 2\n\nE 0 1 1.5\nE 0 1 2.0\nA 0 2 4.0\nA 2 0 0.25\nA 1 2 8.0\n".parse().unwrap();
+        let weight_fn = |x: &usize, y: &usize, z: &usize| g.e_attrs(x, y, z).unwrap().io_query_contents("Weight").unwrap().as_f64().unwrap();
+        assert_eq!(g.v_strength(&0, weight_fn).unwrap(), 7.75);
+        assert_eq!(g.v_strength_in(&0, weight_fn).unwrap(), 0.25);
+        assert_eq!(g.v_strength_out(&0, weight_fn).unwrap(), 4.0);
+        assert_eq!(g.v_strength_undir(&0, weight_fn).unwrap(), 3.5);
+        assert_eq!((g.v_strength_in(&2, weight_fn).unwrap(), g.v_strength_out(&2, weight_fn).unwrap()), (12.0, 0.25));
+        assert_eq!(g.strengths(weight_fn), HashMap::from([(0, 7.75), (1, 11.5), (2, 12.25)]));
+        assert!((0..3).all(|x| g.v_strength(&x, |_, _, _| 1.0).unwrap() == g.v_degree(&x).unwrap() as f64));
+        assert!(g.v_strength(&3, weight_fn).is_err());
+    }
+}
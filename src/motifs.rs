@@ -0,0 +1,454 @@
+//! # Motifs module
+//! 
+//! ## Description
+//! This module contains [`Motifs`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::CrabNetsResult, execution::ExecutionControl, BasicImmutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Names of connected triads
+/// 
+/// ## Description
+/// Names of the 13 classes of weakly connected directed subgraphs on 3 vertices in  the
+/// MAN   notation   of   Holland   and   Leinhardt,    in    the    order    used    by
+/// [`Motifs::triad_census`]. The digits are the numbers of mutual, asymmetric and  null
+/// dyads, the letter tells apart classes with the same digits:  D  for  'down',  U  for
+/// 'up', C for 'cyclic' and T for 'transitive'.
+pub const TRIAD_TYPES: [&str; 13] = ["021D", "021U", "021C", "111D", "111U", "030T", "030C", "201", "120D", "120U", "120C", "210", "300"];
+
+/// # Names of connected graphlets
+/// 
+/// ## Description
+/// Names of the classes of connected undirected subgraphs on 3 and 4  vertices  in  the
+/// order used by [`Motifs::graphlet_census`].
+pub const GRAPHLET_TYPES: [&str; 8] = ["3-path", "triangle", "4-path", "3-star", "4-cycle", "paw", "diamond", "4-clique"];
+
+
+
+// Structure of the graph over vertices numbered from 0 with directions and
+// multiplicities of edges dropped (`neighbours`) or only multiplicities dropped (`arcs`)
+pub(crate) struct MotifStructure {
+    pub(crate) arcs: HashSet<(usize, usize)>,
+    pub(crate) neighbours: Vec<HashSet<usize>>,
+}
+
+impl MotifStructure {
+    pub(crate) fn is_adjacent(&self, a: usize, b: usize) -> bool {
+        self.neighbours[a].contains(&b)
+    }
+
+    // Call `visitor` once for each connected vertex subset of the given size containing
+    // `root` as its smallest vertex, the ESU algorithm of Wernicke
+    pub(crate) fn enumerate_subgraphs<VisitorType>(&self, root: usize, size: usize, visitor: &mut VisitorType)
+    where
+        VisitorType: FnMut(&[usize]),
+    {
+        let extension = self.neighbours[root].iter().copied().filter(|&x| x > root).sorted().collect();
+        self.extend_subgraph(&mut vec![root], extension, size, visitor);
+    }
+
+    fn extend_subgraph<VisitorType>(&self, subgraph: &mut Vec<usize>, mut extension: Vec<usize>, size: usize, visitor: &mut VisitorType)
+    where
+        VisitorType: FnMut(&[usize]),
+    {
+        if subgraph.len() == size {
+            visitor(subgraph);
+            return;
+        }
+        let root = subgraph[0];
+        while let Some(w) = extension.pop() {
+            // Only the exclusive neighbours of `w` are added, so each subset is found once
+            let mut next_extension = extension.clone();
+            for &u in self.neighbours[w].iter().sorted() {
+                if u > root && !subgraph.contains(&u) && !next_extension.contains(&u) && subgraph.iter().all(|&x| !self.is_adjacent(x, u)) {
+                    next_extension.push(u);
+                }
+            }
+            subgraph.push(w);
+            self.extend_subgraph(subgraph, next_extension, size, visitor);
+            subgraph.pop();
+        }
+    }
+
+    fn triad_type(&self, vertices: &[usize]) -> usize {
+        let (mut mutual, mut asymmetric) = (0, 0);
+        let mut out_degrees = [0usize; 3];
+        let mut in_degrees = [0usize; 3];
+        for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+            let forward = self.arcs.contains(&(vertices[i], vertices[j]));
+            let backward = self.arcs.contains(&(vertices[j], vertices[i]));
+            match (forward, backward) {
+                (true, true) => mutual += 1,
+                (true, false) => {
+                    asymmetric += 1;
+                    out_degrees[i] += 1;
+                    in_degrees[j] += 1;
+                },
+                (false, true) => {
+                    asymmetric += 1;
+                    out_degrees[j] += 1;
+                    in_degrees[i] += 1;
+                },
+                (false, false) => (),
+            }
+        }
+        match (mutual, asymmetric) {
+            (0, 2) if out_degrees.contains(&2) => 0,
+            (0, 2) if in_degrees.contains(&2) => 1,
+            (0, 2) => 2,
+            // The asymmetric edge enters or leaves the mutual dyad
+            (1, 1) => {
+                let (i, j) = [(0, 1), (0, 2), (1, 2)].into_iter().find(|&(i, j)| self.arcs.contains(&(vertices[i], vertices[j])) != self.arcs.contains(&(vertices[j], vertices[i]))).unwrap();
+                let target = if self.arcs.contains(&(vertices[i], vertices[j])) { j } else { i };
+                let mutual_pair = [(0, 1), (0, 2), (1, 2)].into_iter().find(|&(x, y)| self.arcs.contains(&(vertices[x], vertices[y])) && self.arcs.contains(&(vertices[y], vertices[x]))).unwrap();
+                if target == mutual_pair.0 || target == mutual_pair.1 { 3 } else { 4 }
+            },
+            (0, 3) if out_degrees.contains(&2) => 5,
+            (0, 3) => 6,
+            (2, 0) => 7,
+            // The vertex outside the mutual dyad sends both, receives both or one of each
+            (1, 2) if out_degrees.contains(&2) => 8,
+            (1, 2) if in_degrees.contains(&2) => 9,
+            (1, 2) => 10,
+            (2, 1) => 11,
+            _ => 12,
+        }
+    }
+
+    fn degrees(&self, vertices: &[usize]) -> Vec<usize> {
+        vertices.iter().map(|&x| vertices.iter().filter(|&&y| self.is_adjacent(x, y)).count()).collect()
+    }
+
+    fn graphlet_type(&self, vertices: &[usize], degrees: &[usize]) -> usize {
+        let edge_count = degrees.iter().sum::<usize>() / 2;
+        match (vertices.len(), edge_count) {
+            (3, 2) => 0,
+            (3, _) => 1,
+            (_, 3) if degrees.contains(&3) => 3,
+            (_, 3) => 2,
+            (_, 4) if degrees.contains(&3) => 5,
+            (_, 4) => 4,
+            (_, 5) => 6,
+            _ => 7,
+        }
+    }
+
+    // Within a graphlet, vertices of the same degree are in the same orbit
+    fn orbit(graphlet_type: usize, degree: usize) -> usize {
+        match (graphlet_type, degree) {
+            (0, 1) => 1,
+            (0, _) => 2,
+            (1, _) => 3,
+            (2, 1) => 4,
+            (2, _) => 5,
+            (3, 1) => 6,
+            (3, _) => 7,
+            (4, _) => 8,
+            (5, 1) => 9,
+            (5, 2) => 10,
+            (5, _) => 11,
+            (6, 2) => 12,
+            (6, _) => 13,
+            _ => 14,
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MOTIFS                                                                            *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Motifs
+/// 
+/// ## Description
+/// Motifs are small connected subgraphs. Their counts  (the  census)  characterise  the
+/// local structure of a network, and comparing the census of a network with the ones of
+/// randomised networks reveals over- and under-represented patterns.
+/// 
+/// The census counts induced subgraphs, i.e. every connected set of vertices is counted
+/// exactly once, in the class of  the  subgraph  formed  by  all  edges  between  these
+/// vertices. Self-loops and the multiplicity of edges are ignored.
+/// 
+/// All functions check the given [`ExecutionControl`] after processing each vertex  and
+/// return an error of kind [`Cancelled`][cancelled] if the computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait Motifs<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Graphlet census
+    /// 
+    /// ## Description
+    /// Count connected undirected subgraphs on 3 and, optionally, 4 vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `include_four_vertices` : `bool` - if `true`,  subgraphs  on  4  vertices  are
+    /// counted as well.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<usize>>` - `Ok(value)`  is  returned  if  the  computation
+    /// wasn't cancelled, `value` in this case is the number of subgraphs of each  class
+    /// in  the  order  of   [`GRAPHLET_TYPES`]   (only   the   first   2   classes   if
+    /// `include_four_vertices` is `false`); `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Directions of edges are ignored.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| + k · s²), where s is the largest size  of  counted  subgraphs
+    /// and k is the number of connected subgraphs of size at most s.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn graphlet_census(&self, include_four_vertices: bool, control: &ExecutionControl) -> CrabNetsResult<Vec<usize>>;
+    /// # Graphlet degree vectors
+    /// 
+    /// ## Description
+    /// Compute the graphlet degree vector of each vertex, i.e. the number of times  the
+    /// vertex touches each orbit of connected  undirected  subgraphs  on  2,  3  and  4
+    /// vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, [usize; 15]>>` - `Ok(value)` is returned
+    /// if the computation wasn't cancelled, `value` in this case maps  each  vertex  to
+    /// its graphlet degree vector; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// An orbit is a class of vertices of a graphlet that are mapped onto each other by
+    /// its automorphisms. There are 15  orbits  in  graphlets  on  up  to  4  vertices,
+    /// numbered as by Pržulj [[source](https://doi.org/10.1093/bioinformatics/btl301)]:
+    /// * 0 - edge;
+    /// * 1, 2 - end and middle of a 3-path;
+    /// * 3 - triangle;
+    /// * 4, 5 - end and inner vertex of a 4-path;
+    /// * 6, 7 - leaf and centre of a 3-star;
+    /// * 8 - 4-cycle;
+    /// * 9, 10, 11 - vertices of degree 1, 2 and 3 of a paw (a triangle with a  pendant
+    /// vertex);
+    /// * 12, 13 - vertices of degree 2 and 3 of a diamond;
+    /// * 14 - 4-clique.
+    /// 
+    /// The value at orbit 0 is the number of distinct neighbours. Directions  of  edges
+    /// are ignored. Graphlet degree vectors are commonly used as  topological  features
+    /// of vertices, e.g. to compare  the  local  structure  of  vertices  in  different
+    /// networks.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| + k), where k is the number of connected subgraphs on at  most
+    /// 4 vertices.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn graphlet_degree_vectors(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, [usize; 15]>>;
+    /// # Triad census
+    /// 
+    /// ## Description
+    /// Count connected directed subgraphs on 3 vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<[usize; 13]>` - `Ok(value)` is  returned  if  the  computation
+    /// wasn't cancelled, `value` in this case is the number of subgraphs of each  class
+    /// in the order of [`TRIAD_TYPES`]; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Undirected edges are treated as pairs of opposite directed edges, i.e. as mutual
+    /// dyads. The 3 disconnected classes of the full triad census (003,  012  and  102)
+    /// aren't counted.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| + k), where k is  the  number  of  connected  subgraphs  on  3
+    /// vertices.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn triad_census(&self, control: &ExecutionControl) -> CrabNetsResult<[usize; 13]>;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Vertices are numbered by their positions in ascending order of IDs
+    pub(crate) fn motif_structure(&self) -> (Vec<VertexIdType>, MotifStructure) {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut arcs = HashSet::new();
+        let mut neighbours = vec![HashSet::new(); vertices.len()];
+        for edge in self.iter_e() {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            if i == j {
+                continue;
+            }
+            neighbours[i].insert(j);
+            neighbours[j].insert(i);
+            match edge.direction {
+                EdgeDirection::Directed1to2 => {
+                    arcs.insert((i, j));
+                },
+                EdgeDirection::Directed2to1 => {
+                    arcs.insert((j, i));
+                },
+                EdgeDirection::Undirected => {
+                    arcs.insert((i, j));
+                    arcs.insert((j, i));
+                },
+            }
+        }
+        (vertices, MotifStructure { arcs, neighbours })
+    }
+}
+
+// Graph::Motifs
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Motifs<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn graphlet_census(&self, include_four_vertices: bool, control: &ExecutionControl) -> CrabNetsResult<Vec<usize>> {
+        const FUNCTION_PATH: &str = "Graph::Motifs::graphlet_census";
+        let (vertices, structure) = self.motif_structure();
+        let mut answer = vec![0usize; if include_four_vertices { 8 } else { 2 }];
+        let mut count = |subgraph: &[usize]| answer[structure.graphlet_type(subgraph, &structure.degrees(subgraph))] += 1;
+        for root in 0..vertices.len() {
+            control.check(FUNCTION_PATH)?;
+            structure.enumerate_subgraphs(root, 3, &mut count);
+            if include_four_vertices {
+                structure.enumerate_subgraphs(root, 4, &mut count);
+            }
+        }
+        Ok(answer)
+    }
+
+    fn graphlet_degree_vectors(&self, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, [usize; 15]>> {
+        const FUNCTION_PATH: &str = "Graph::Motifs::graphlet_degree_vectors";
+        let (vertices, structure) = self.motif_structure();
+        let mut answer: Vec<[usize; 15]> = structure.neighbours.iter().map(|x| {
+            let mut vector = [0; 15];
+            vector[0] = x.len();
+            vector
+        }).collect();
+        let mut count = |subgraph: &[usize]| {
+            let degrees = structure.degrees(subgraph);
+            let graphlet_type = structure.graphlet_type(subgraph, &degrees);
+            for (&x, &degree) in subgraph.iter().zip(degrees.iter()) {
+                answer[x][MotifStructure::orbit(graphlet_type, degree)] += 1;
+            }
+        };
+        for root in 0..vertices.len() {
+            control.check(FUNCTION_PATH)?;
+            structure.enumerate_subgraphs(root, 3, &mut count);
+            structure.enumerate_subgraphs(root, 4, &mut count);
+        }
+        Ok(vertices.into_iter().zip(answer).collect())
+    }
+
+    fn triad_census(&self, control: &ExecutionControl) -> CrabNetsResult<[usize; 13]> {
+        const FUNCTION_PATH: &str = "Graph::Motifs::triad_census";
+        let (vertices, structure) = self.motif_structure();
+        let mut answer = [0usize; 13];
+        for root in 0..vertices.len() {
+            control.check(FUNCTION_PATH)?;
+            structure.enumerate_subgraphs(root, 3, &mut |subgraph: &[usize]| answer[structure.triad_type(subgraph)] += 1);
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn triad_census() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        // 0 <-> 1 -> 2, 0 -> 2, 3 -> 2 twice, 4 isolated
+        g.add_e(&0, &1, false, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&3, &2, true, None).unwrap();
+        g.add_e(&3, &2, true, None).unwrap();
+        g.add_e(&3, &3, true, None).unwrap();
+        let census = g.triad_census(&ExecutionControl::new()).unwrap();
+        // {0, 1, 2} is 120U, {0, 2, 3} and {1, 2, 3} are 021U
+        let mut expected = [0; 13];
+        expected[TRIAD_TYPES.iter().position(|x| *x == "120U").unwrap()] = 1;
+        expected[TRIAD_TYPES.iter().position(|x| *x == "021U").unwrap()] = 2;
+        assert_eq!(census, expected);
+        g.add_e(&2, &4, true, None).unwrap();
+        let census = g.triad_census(&ExecutionControl::new()).unwrap();
+        assert_eq!(census[TRIAD_TYPES.iter().position(|x| *x == "021C").unwrap()], 3);
+        assert_eq!(census[TRIAD_TYPES.iter().position(|x| *x == "021D").unwrap()], 0);
+        assert_eq!(census[TRIAD_TYPES.iter().position(|x| *x == "111U").unwrap()], 0);
+    }
+
+    #[test]
+    fn graphlet_census() {
+        // Diamond 0-1-2-3 with chord 0-2, and pendant vertex 4 attached to 3
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        for (x, y) in [(0, 1), (1, 2), (2, 3), (3, 0), (0, 2), (3, 4)] {
+            g.add_e(&x, &y, false, None).unwrap();
+        }
+        let control = ExecutionControl::new();
+        assert_eq!(g.graphlet_census(false, &control).unwrap(), vec![4, 2]);
+        assert_eq!(g.graphlet_census(true, &control).unwrap(), vec![4, 2, 2, 0, 0, 1, 1, 0]);
+        let vectors = g.graphlet_degree_vectors(&control).unwrap();
+        assert_eq!(vectors[&0], [3, 1, 1, 2, 0, 1, 0, 0, 0, 0, 1, 0, 0, 1, 0]);
+        assert_eq!(vectors[&4], [1, 2, 0, 0, 2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0]);
+        // Each graphlet is counted once per vertex in each of its orbits
+        let orbit_sums: Vec<usize> = (0..15).map(|i| vectors.values().map(|x| x[i]).sum()).collect();
+        assert_eq!(orbit_sums, vec![12, 8, 4, 6, 4, 4, 0, 0, 0, 1, 2, 1, 2, 2, 0]);
+        control.cancel();
+        assert!(matches!(g.graphlet_census(true, &control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+}
@@ -0,0 +1,502 @@
+//! # Orderings module
+//! 
+//! ## Description
+//! This module contains [`Orderings`]  trait  and  its  implementation  for  [`Graph`].
+//! Vertex orderings drive  greedy  algorithms  (e.g.  colouring  or  clique  search  in
+//! degeneracy ordering) and reduce  the  bandwidth  of  adjacency  matrices  of  sparse
+//! graphs,  which  makes  traversals   of   graphs   relabelled   in   such   orderings
+//! cache-friendly.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet, VecDeque};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, metrics::core_decomposition_of, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Largest number of passes of adjacent swaps in the linear arrangement heuristic
+const MAX_SWAP_PASSES: usize = 100;
+
+
+
+// Breadth-first ordering of a simple undirected graph over vertices numbered from 0,
+// each component is started from the first unvisited vertex of `starts`, neighbours are
+// visited in ascending order of their keys
+fn breadth_first_of<KeyFnType, KeyType>(neighbours: &[HashSet<usize>], starts: &[usize], key_fn: KeyFnType) -> Vec<usize>
+where
+    KeyFnType: Fn(usize) -> KeyType,
+    KeyType: Ord,
+{
+    let mut visited = vec![false; neighbours.len()];
+    let mut answer = Vec::with_capacity(neighbours.len());
+    for &start in starts {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut queue = VecDeque::from([start]);
+        while let Some(v) = queue.pop_front() {
+            answer.push(v);
+            let unvisited = neighbours[v].iter().copied().filter(|u| !visited[*u]).sorted_by_key(|u| key_fn(*u)).collect::<Vec<_>>();
+            for u in unvisited {
+                visited[u] = true;
+                queue.push_back(u);
+            }
+        }
+    }
+    answer
+}
+
+
+
+// Pseudo-peripheral vertex of the component of `start` and all vertices of this
+// component, the algorithm of Gibbs, Poole and Stockmeyer as refined by George and Liu
+fn pseudo_peripheral_of(neighbours: &[HashSet<usize>], start: usize) -> (usize, Vec<usize>) {
+    let mut current = start;
+    let mut eccentricity = 0;
+    loop {
+        let mut distances = HashMap::from([(current, 0usize)]);
+        let mut queue = VecDeque::from([current]);
+        while let Some(v) = queue.pop_front() {
+            for &u in neighbours[v].iter() {
+                if !distances.contains_key(&u) {
+                    distances.insert(u, distances[&v] + 1);
+                    queue.push_back(u);
+                }
+            }
+        }
+        let farthest = *distances.values().max().unwrap();
+        if farthest <= eccentricity {
+            return (current, distances.into_keys().collect());
+        }
+        eccentricity = farthest;
+        current = distances.iter().filter(|(_, distance)| **distance == farthest).map(|(v, _)| (neighbours[*v].len(), *v)).min().unwrap().1;
+    }
+}
+
+
+
+// Position of every vertex in the ordering of all vertices
+fn positions_of(ordering: &[usize]) -> Vec<usize> {
+    let mut answer = vec![0; ordering.len()];
+    for (position, v) in ordering.iter().enumerate() {
+        answer[*v] = position;
+    }
+    answer
+}
+
+
+
+// Bandwidth and linear arrangement cost of the given positions of the vertices
+fn arrangement_costs_of(neighbours: &[HashSet<usize>], positions: &[usize]) -> (usize, usize) {
+    let mut bandwidth = 0;
+    let mut cost = 0;
+    for (v, adjacent) in neighbours.iter().enumerate() {
+        for &u in adjacent.iter().filter(|u| **u > v) {
+            bandwidth = bandwidth.max(positions[u].abs_diff(positions[v]));
+            cost += positions[u].abs_diff(positions[v]);
+        }
+    }
+    (bandwidth, cost)
+}
+
+
+
+// Orderings of the vertices given by the reverse Cuthill–McKee algorithm started from
+// vertices of the smallest degree and from pseudo-peripheral vertices
+fn cuthill_mckee_candidates_of(neighbours: &[HashSet<usize>]) -> [Vec<usize>; 2] {
+    let degree = |u: usize| (neighbours[u].len(), u);
+    let starts = (0..neighbours.len()).sorted_by_key(|u| degree(*u)).collect::<Vec<_>>();
+    let mut visited = vec![false; neighbours.len()];
+    let mut peripheral_starts = Vec::new();
+    for &start in starts.iter() {
+        if !visited[start] {
+            let (peripheral, component) = pseudo_peripheral_of(neighbours, start);
+            component.into_iter().for_each(|v| visited[v] = true);
+            peripheral_starts.push(peripheral);
+        }
+    }
+    [starts, peripheral_starts].map(|starts| breadth_first_of(neighbours, &starts, degree).into_iter().rev().collect())
+}
+
+
+
+// Improve the linear arrangement cost of the ordering by swapping adjacent vertices
+fn adjacent_swaps_of(neighbours: &[HashSet<usize>], ordering: &mut [usize]) {
+    let mut positions = positions_of(ordering);
+    for _ in 0..MAX_SWAP_PASSES {
+        let mut is_swapped = false;
+        for i in 1..ordering.len() {
+            let (a, b) = (ordering[i - 1], ordering[i]);
+            // Change of the cost after `a` moves to position `i` and `b` to `i - 1`
+            let a_change = neighbours[a].iter().filter(|u| **u != b).map(|u| positions[*u].abs_diff(i) as isize - positions[*u].abs_diff(i - 1) as isize).sum::<isize>();
+            let b_change = neighbours[b].iter().filter(|u| **u != a).map(|u| positions[*u].abs_diff(i - 1) as isize - positions[*u].abs_diff(i) as isize).sum::<isize>();
+            if a_change + b_change < 0 {
+                ordering.swap(i - 1, i);
+                (positions[a], positions[b]) = (i, i - 1);
+                is_swapped = true;
+            }
+        }
+        if !is_swapped {
+            break;
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ORDERINGS                                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Orderings
+/// 
+/// ## Description
+/// Orderings of the vertices of the graph.
+/// 
+/// All orderings are computed for the underlying simple undirected graph: directions of
+/// edges are ignored, parallel edges count  once,  self-loops  are  ignored.  Ties  are
+/// broken by the ascending order of IDs, so the orderings are deterministic.
+pub trait Orderings<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Bandwidth
+    /// 
+    /// ## Description
+    /// Compute the bandwidth of the adjacency  matrix  of  the  graph  whose  rows  and
+    /// columns follow the given ordering.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `ordering` : `&[VertexIdType]` - the ordering of the vertices.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if `ordering` contains every
+    /// vertex of the graph exactly once, `value` in this case is the  largest  distance
+    /// between the positions  of  the  endpoints  of  an  edge;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn bandwidth(&self, ordering: &[VertexIdType]) -> CrabNetsResult<usize>;
+    /// # Breadth-first ordering
+    /// 
+    /// ## Description
+    /// Order the vertices by breadth-first search.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<VertexIdType>` - the vertices in the order of their discovery.
+    /// 
+    /// ## Details
+    /// Each connected component is searched from  its  vertex  with  the  smallest  ID,
+    /// components follow each other in ascending order of  these  IDs,  neighbours  are
+    /// discovered in ascending order of their IDs.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn bfs_ordering(&self) -> Vec<VertexIdType>;
+    /// # Degeneracy ordering
+    /// 
+    /// ## Description
+    /// Order the vertices by repeatedly removing a vertex of the smallest  degree  from
+    /// the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<VertexIdType>` - the vertices in the order of their removal.
+    /// 
+    /// ## Details
+    /// Every vertex has at most d neighbours that follow it in the ordering, where d is
+    /// the  degeneracy  of   the   graph,   i.e.   the   largest   core   number   (see
+    /// [`Metrics::core_numbers`][core_numbers]).
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [core_numbers]: crate::metrics::Metrics::core_numbers
+    fn degeneracy_ordering(&self) -> Vec<VertexIdType>;
+    /// # Descending-degree ordering
+    /// 
+    /// ## Description
+    /// Order the vertices by their degrees from the largest to the smallest.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<VertexIdType>` - the vertices in descending order of their degrees.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn descending_degree_ordering(&self) -> Vec<VertexIdType>;
+    /// # Linear arrangement cost
+    /// 
+    /// ## Description
+    /// Compute the total length of the edges of the graph when its vertices are  placed
+    /// on a line in the given ordering.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `ordering` : `&[VertexIdType]` - the ordering of the vertices.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if `ordering` contains every
+    /// vertex of the graph exactly once, `value`  in  this  case  is  the  sum  of  the
+    /// distances between the positions of the  endpoints  of  all  edges;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn linear_arrangement_cost(&self, ordering: &[VertexIdType]) -> CrabNetsResult<usize>;
+    /// # Minimum bandwidth ordering
+    /// 
+    /// ## Description
+    /// Find an ordering of the vertices with a small bandwidth.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `(Vec<VertexIdType>, usize)` - the ordering and its bandwidth.
+    /// 
+    /// ## Details
+    /// Finding the minimum bandwidth (see [`Orderings::bandwidth`]) is NP-hard, so  the
+    /// function returns the better of two reverse Cuthill–McKee  orderings.  The  first
+    /// one is that of  [`Orderings::reverse_cuthill_mckee_ordering`],  the  second  one
+    /// searches each component from a pseudo-peripheral vertex, i.e. an endpoint  of  a
+    /// long shortest path, found by  the  algorithm  of  Gibbs,  Poole  and  Stockmeyer
+    /// [[source](https://doi.org/10.1137/0713023)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)) in the worst case, O((|V| + |E|) log |V|) in  typical
+    /// cases.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn minimum_bandwidth_ordering(&self) -> (Vec<VertexIdType>, usize);
+    /// # Minimum linear arrangement ordering
+    /// 
+    /// ## Description
+    /// Find an ordering of the vertices with  a  small  linear  arrangement  cost  (see
+    /// [`Orderings::linear_arrangement_cost`]).
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `(Vec<VertexIdType>, usize)` - the ordering and its linear arrangement cost.
+    /// 
+    /// ## Details
+    /// Finding the minimum linear arrangement is NP-hard, so  the  function  takes  the
+    /// cheapest of the breadth-first ordering  and  the  two  orderings  considered  by
+    /// [`Orderings::minimum_bandwidth_ordering`], then it swaps adjacent vertices while
+    /// the cost decreases (at most 100 passes over the ordering).
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|)) in the worst case.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn minimum_linear_arrangement_ordering(&self) -> (Vec<VertexIdType>, usize);
+    /// # Reverse Cuthill–McKee ordering
+    /// 
+    /// ## Description
+    /// Order the vertices with the reverse Cuthill–McKee  algorithm  that  reduces  the
+    /// bandwidth of the adjacency matrix of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<VertexIdType>` - the vertices in the reverse Cuthill–McKee order.
+    /// 
+    /// ## Details
+    /// Each connected component is  searched  breadth-first  from  its  vertex  of  the
+    /// smallest degree, neighbours are discovered in ascending order of their  degrees,
+    /// the resulting ordering is reversed.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E| log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn reverse_cuthill_mckee_ordering(&self) -> Vec<VertexIdType>;
+}
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Position of every vertex in the ordering that must contain every vertex once
+    fn positions_in<'a>(&self, ordering: &'a [VertexIdType], function_path: &str) -> CrabNetsResult<HashMap<&'a VertexIdType, usize>> {
+        let mut positions = HashMap::with_capacity(ordering.len());
+        for (position, id) in ordering.iter().enumerate() {
+            if !self.contains_v(id) {
+                return Err(CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() }));
+            }
+            positions.insert(id, position);
+        }
+        if positions.len() != ordering.len() || positions.len() != self.count_v() {
+            return Err(CrabNetsError::new(function_path, ErrorKind::InvalidArgument { name: "ordering".to_string(), reason: "The ordering must contain every vertex exactly once.".to_string() }));
+        }
+        Ok(positions)
+    }
+}
+
+// Graph::Orderings
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Orderings<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn bandwidth(&self, ordering: &[VertexIdType]) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "Graph::Orderings::bandwidth";
+        let positions = self.positions_in(ordering, FUNCTION_PATH)?;
+        Ok(self.iter_e().map(|edge| positions[&edge.id1].abs_diff(positions[&edge.id2])).max().unwrap_or(0))
+    }
+
+    fn bfs_ordering(&self) -> Vec<VertexIdType> {
+        let (vertices, structure) = self.motif_structure();
+        let starts = (0..vertices.len()).collect::<Vec<_>>();
+        breadth_first_of(&structure.neighbours, &starts, |u| u).into_iter().map(|i| vertices[i].clone()).collect()
+    }
+
+    fn degeneracy_ordering(&self) -> Vec<VertexIdType> {
+        let (vertices, structure) = self.motif_structure();
+        core_decomposition_of(&structure.neighbours).1.into_iter().map(|i| vertices[i].clone()).collect()
+    }
+
+    fn descending_degree_ordering(&self) -> Vec<VertexIdType> {
+        let (vertices, structure) = self.motif_structure();
+        (0..vertices.len()).sorted_by_key(|i| (usize::MAX - structure.neighbours[*i].len(), *i)).map(|i| vertices[i].clone()).collect()
+    }
+
+    fn linear_arrangement_cost(&self, ordering: &[VertexIdType]) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "Graph::Orderings::linear_arrangement_cost";
+        let positions = self.positions_in(ordering, FUNCTION_PATH)?;
+        let (vertices, structure) = self.motif_structure();
+        Ok(arrangement_costs_of(&structure.neighbours, &vertices.iter().map(|id| positions[id]).collect::<Vec<_>>()).1)
+    }
+
+    fn minimum_bandwidth_ordering(&self) -> (Vec<VertexIdType>, usize) {
+        let (vertices, structure) = self.motif_structure();
+        let (ordering, (bandwidth, _)) = cuthill_mckee_candidates_of(&structure.neighbours).into_iter()
+            .map(|ordering| {
+                let costs = arrangement_costs_of(&structure.neighbours, &positions_of(&ordering));
+                (ordering, costs)
+            })
+            .min_by_key(|(_, (bandwidth, _))| *bandwidth)
+            .unwrap();
+        (ordering.into_iter().map(|i| vertices[i].clone()).collect(), bandwidth)
+    }
+
+    fn minimum_linear_arrangement_ordering(&self) -> (Vec<VertexIdType>, usize) {
+        let (vertices, structure) = self.motif_structure();
+        let starts = (0..vertices.len()).collect::<Vec<_>>();
+        let mut ordering = cuthill_mckee_candidates_of(&structure.neighbours).into_iter()
+            .chain([breadth_first_of(&structure.neighbours, &starts, |u| u)])
+            .min_by_key(|ordering| arrangement_costs_of(&structure.neighbours, &positions_of(ordering)).1)
+            .unwrap();
+        adjacent_swaps_of(&structure.neighbours, &mut ordering);
+        let cost = arrangement_costs_of(&structure.neighbours, &positions_of(&ordering)).1;
+        (ordering.into_iter().map(|i| vertices[i].clone()).collect(), cost)
+    }
+
+    fn reverse_cuthill_mckee_ordering(&self) -> Vec<VertexIdType> {
+        let (vertices, structure) = self.motif_structure();
+        let degree = |u: usize| (structure.neighbours[u].len(), u);
+        let starts = (0..vertices.len()).sorted_by_key(|u| degree(*u)).collect::<Vec<_>>();
+        breadth_first_of(&structure.neighbours, &starts, degree).into_iter().rev().map(|i| vertices[i].clone()).collect()
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn orderings() {
+        // Path 0 - 3 - 1 - 4 - 2 - 5
+        let g: graph!(X ---X--- X) = "V 0\nV 1\nV 2\nV 3\nV 4\nV 5\n\nE 0 3\nE 3 1\nE 1 4\nE 4 2\nE 2 5\n".parse().unwrap();
+        assert_eq!(g.bandwidth(&[0, 1, 2, 3, 4, 5]).unwrap(), 3);
+        assert_eq!(g.bfs_ordering(), vec![0, 3, 1, 4, 2, 5]);
+        assert_eq!(g.reverse_cuthill_mckee_ordering(), vec![5, 2, 4, 1, 3, 0]);
+        assert_eq!(g.bandwidth(&g.reverse_cuthill_mckee_ordering()).unwrap(), 1);
+        assert!(g.bandwidth(&[0, 1, 2, 3, 4]).is_err() && g.bandwidth(&[0, 1, 2, 3, 4, 4]).is_err() && g.bandwidth(&[0, 1, 2, 3, 4, 9]).is_err());
+        // Triangle 0 - 1 - 2, pendant vertex 3 attached to 0 and isolated vertex 4
+        let g: graph!(X ---X--- X) = "V 0\nV 1\nV 2\nV 3\nV 4\n\nE 0 1\nE 1 2\nE 2 0\nE 0 3\n".parse().unwrap();
+        assert_eq!(g.descending_degree_ordering(), vec![0, 1, 2, 3, 4]);
+        let ordering = g.degeneracy_ordering();
+        assert_eq!(ordering.iter().copied().sorted().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(ordering.iter().position(|x| *x == 3) < ordering.iter().position(|x| *x == 0));
+        // Every vertex has at most 2 neighbours after it
+        for (position, id) in ordering.iter().enumerate() {
+            assert!(g.iter_adjacent(id).unwrap().filter(|x| ordering[position + 1..].contains(x)).count() <= 2);
+        }
+        assert_eq!(g.bfs_ordering(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn arrangements() {
+        // Path 0 - 3 - 1 - 4 - 2 - 5
+        let g: graph!(X ---X--- X) = "V 0\nV 1\nV 2\nV 3\nV 4\nV 5\n\nE 0 3\nE 3 1\nE 1 4\nE 4 2\nE 2 5\n".parse().unwrap();
+        assert_eq!(g.linear_arrangement_cost(&[0, 1, 2, 3, 4, 5]).unwrap(), 13);
+        assert!(g.linear_arrangement_cost(&[0, 1, 2, 3, 4]).is_err());
+        let (ordering, bandwidth) = g.minimum_bandwidth_ordering();
+        assert_eq!((bandwidth, g.bandwidth(&ordering).unwrap()), (1, 1));
+        let (ordering, cost) = g.minimum_linear_arrangement_ordering();
+        assert_eq!((cost, g.linear_arrangement_cost(&ordering).unwrap()), (5, 5));
+        // Star with centre 0 and leaves 1 - 6, the centre is best placed in the middle
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        for i in 1..7 {
+            g.add_e(&0, &i, i % 2 == 0, None).unwrap();
+        }
+        let (ordering, bandwidth) = g.minimum_bandwidth_ordering();
+        assert_eq!(g.bandwidth(&ordering).unwrap(), bandwidth);
+        let (ordering, cost) = g.minimum_linear_arrangement_ordering();
+        assert_eq!((cost, g.linear_arrangement_cost(&ordering).unwrap()), (12, 12));
+        let rcm_cost = g.linear_arrangement_cost(&g.reverse_cuthill_mckee_ordering()).unwrap();
+        assert!(cost < rcm_cost);
+    }
+}
@@ -0,0 +1,548 @@
+//! # Partitioning module
+//! 
+//! ## Description
+//! This module contains [`Partitioning`] trait and its  implementation  for  [`Graph`].
+//! Balanced partitioning splits the vertices of the graph into parts  of  nearly  equal
+//! sizes with as few edges between the parts as  possible,  which  is  how  graphs  are
+//! sharded for distributed processing.
+//! 
+//! The coarsening that the partitioning is built upon is also available on its  own  as
+//! [`Graph::coarsen`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::{cmp::Reverse, collections::{HashMap, VecDeque}};
+use itertools::Itertools;
+use crate::{attributes::{AttributeCollection, StaticDispatchAttributeValue}, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, io::{AttributeCollectionIO, AttributeToken}, locales::LocaleCounterparts, BasicImmutableGraph, BasicMutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Coarsening stops once the graph has at most this many vertices per part
+const COARSEST_SIZE_PER_PART: usize = 20;
+
+// Coarsening stops once a level removes less than this fraction of vertices
+const COARSENING_MIN_REDUCTION: f64 = 0.05;
+
+// Largest number of refinement passes at each level
+const REFINEMENT_PASSES: usize = 10;
+
+
+// Undirected graph over vertices numbered from 0 with weighted vertices and edges
+struct WeightedGraph {
+    adjacency: Vec<HashMap<usize, usize>>,
+    weights: Vec<usize>,
+}
+
+impl WeightedGraph {
+    fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    // Coarser graph obtained by merging the endpoints of a heavy-edge matching and the
+    // vertex of the coarser graph for every vertex of this graph
+    fn coarsen(&self, max_vertex_weight: usize) -> (WeightedGraph, Vec<usize>) {
+        let mut mapping = vec![usize::MAX; self.len()];
+        let mut count = 0;
+        for v in (0..self.len()).sorted_by_key(|v| (self.weights[*v], *v)) {
+            if mapping[v] != usize::MAX {
+                continue;
+            }
+            let mate = self.adjacency[v].iter()
+                .filter(|(u, _)| mapping[**u] == usize::MAX && self.weights[v] + self.weights[**u] <= max_vertex_weight)
+                .max_by_key(|(u, weight)| (**weight, Reverse(**u)))
+                .map(|(u, _)| *u);
+            mapping[v] = count;
+            if let Some(u) = mate {
+                mapping[u] = count;
+            }
+            count += 1;
+        }
+        let mut adjacency = vec![HashMap::new(); count];
+        let mut weights = vec![0; count];
+        for v in 0..self.len() {
+            weights[mapping[v]] += self.weights[v];
+            for (u, weight) in self.adjacency[v].iter().filter(|(u, _)| mapping[**u] != mapping[v]) {
+                *adjacency[mapping[v]].entry(mapping[*u]).or_insert(0) += weight;
+            }
+        }
+        (WeightedGraph { adjacency, weights }, mapping)
+    }
+
+    // Initial partition: vertices are taken in breadth-first order and each vertex goes
+    // to the part that covers the middle of its weight on the cumulative weight scale
+    fn grow_parts(&self, parts: usize) -> Vec<usize> {
+        let total_weight = self.weights.iter().sum::<usize>();
+        let mut answer = vec![usize::MAX; self.len()];
+        let mut cumulative_weight = 0;
+        for start in 0..self.len() {
+            if answer[start] != usize::MAX {
+                continue;
+            }
+            answer[start] = 0;
+            let mut queue = VecDeque::from([start]);
+            while let Some(v) = queue.pop_front() {
+                answer[v] = ((2 * cumulative_weight + self.weights[v]) * parts / (2 * total_weight)).min(parts - 1);
+                cumulative_weight += self.weights[v];
+                for u in self.adjacency[v].keys().copied().sorted() {
+                    if answer[u] == usize::MAX {
+                        answer[u] = 0;
+                        queue.push_back(u);
+                    }
+                }
+            }
+        }
+        answer
+    }
+
+    // Move vertices out of overweight parts, then greedily move boundary vertices to
+    // the parts they are connected to the most
+    fn refine(&self, assignment: &mut [usize], parts: usize, max_part_weight: usize) {
+        let mut part_weights = vec![0; parts];
+        for v in 0..self.len() {
+            part_weights[assignment[v]] += self.weights[v];
+        }
+        let connectivity_of = |v: usize, assignment: &[usize]| {
+            let mut answer = HashMap::new();
+            for (u, weight) in self.adjacency[v].iter() {
+                *answer.entry(assignment[*u]).or_insert(0isize) += *weight as isize;
+            }
+            answer
+        };
+        for p in 0..parts {
+            while part_weights[p] > max_part_weight {
+                let mut best = None;
+                for v in (0..self.len()).filter(|v| assignment[*v] == p) {
+                    let connectivity = connectivity_of(v, assignment);
+                    let internal = connectivity.get(&p).copied().unwrap_or(0);
+                    for q in (0..parts).filter(|q| *q != p && part_weights[*q] + self.weights[v] <= max_part_weight) {
+                        best = best.max(Some((connectivity.get(&q).copied().unwrap_or(0) - internal, Reverse(part_weights[q]), Reverse(v), Reverse(q))));
+                    }
+                }
+                match best {
+                    Some((_, _, Reverse(v), Reverse(q))) => {
+                        assignment[v] = q;
+                        part_weights[p] -= self.weights[v];
+                        part_weights[q] += self.weights[v];
+                    },
+                    None => break,
+                }
+            }
+        }
+        for _ in 0..REFINEMENT_PASSES {
+            let mut is_moved = false;
+            for v in 0..self.len() {
+                let p = assignment[v];
+                let connectivity = connectivity_of(v, assignment);
+                let internal = connectivity.get(&p).copied().unwrap_or(0);
+                // Moves that keep the cut but improve the balance are allowed too
+                let best = connectivity.iter()
+                    .filter(|(q, _)| **q != p && part_weights[**q] + self.weights[v] <= max_part_weight)
+                    .map(|(q, external)| (external - internal, Reverse(part_weights[*q]), Reverse(*q)))
+                    .filter(|(gain, Reverse(weight), _)| *gain > 0 || (*gain == 0 && weight + self.weights[v] < part_weights[p]))
+                    .max();
+                if let Some((_, _, Reverse(q))) = best {
+                    assignment[v] = q;
+                    part_weights[p] -= self.weights[v];
+                    part_weights[q] += self.weights[v];
+                    is_moved = true;
+                }
+            }
+            if !is_moved {
+                break;
+            }
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PARTITIONING                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Partitioning
+/// 
+/// ## Description
+/// Balanced k-way partitioning of the vertices of the graph.
+/// 
+/// Directions of edges are ignored,  parallel  edges  count  with  their  multiplicity,
+/// self-loops are ignored.
+pub trait Partitioning<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Edge cut
+    /// 
+    /// ## Description
+    /// Count the edges whose endpoints lie in different parts.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `partition` : `&HashMap<VertexIdType, usize>` - the part of every vertex.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if every vertex of the graph
+    /// has a part, `value` in this case is the number of edges between different parts;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(1).
+    fn edge_cut(&self, partition: &HashMap<VertexIdType, usize>) -> CrabNetsResult<usize>;
+    /// # Partition
+    /// 
+    /// ## Description
+    /// Split the vertices into the given number of parts of nearly equal sizes with  as
+    /// few edges between the parts as possible.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `parts` : `usize` - the number of parts.
+    /// * `imbalance` : `f64` - the allowed relative excess of the size of a  part  over
+    /// the average size, e.g. 0.03 allows parts up to 3% larger than |V| / `parts`.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, usize>>` - `Ok(value)`  is  returned  if
+    /// `parts` is positive, `imbalance` is  non-negative  and  the  computation  wasn't
+    /// cancelled, `value` in this case  maps  every  vertex  to  its  part,  parts  are
+    /// numbered from 0; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Every part has at most ⌈(1 + `imbalance`) · |V| / `parts`⌉ vertices. Some  parts
+    /// are empty if `parts` exceeds |V|.
+    /// 
+    /// Uses the multilevel scheme of METIS \[[source][metis]\]: the graph is repeatedly
+    /// coarsened by merging the endpoints of heavy-edge matchings, the  coarsest  graph
+    /// is split by growing the parts breadth-first, and the partition is projected back
+    /// level by level and refined by greedy moves of boundary vertices at  each  level.
+    /// The result is deterministic but not necessarily optimal.
+    /// 
+    /// The given [`ExecutionControl`] is checked  at  each  level,  an  error  of  kind
+    /// [`Cancelled`][cancelled] is returned if the computation is cancelled.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|) in typical cases.
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    /// [metis]: https://doi.org/10.1137/S1064827595287997
+    fn partition(&self, parts: usize, imbalance: f64, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, usize>>;
+}
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Sorted vertices and the weighted graph over their indices, where all vertices
+    // have weight 1 and edges have the multiplicities of the pairs of vertices as
+    // weights
+    fn weighted_graph(&self) -> (Vec<VertexIdType>, WeightedGraph) {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut adjacency = vec![HashMap::new(); vertices.len()];
+        for edge in self.iter_e() {
+            let (i, j) = (indices[&edge.id1], indices[&edge.id2]);
+            if i != j {
+                *adjacency[i].entry(j).or_insert(0) += 1;
+                *adjacency[j].entry(i).or_insert(0) += 1;
+            }
+        }
+        let weights = vec![1; vertices.len()];
+        (vertices, WeightedGraph { adjacency, weights })
+    }
+}
+
+// Graph::Partitioning
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Partitioning<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn edge_cut(&self, partition: &HashMap<VertexIdType, usize>) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "Graph::Partitioning::edge_cut";
+        if let Some(id) = self.iter_v().find(|id| !partition.contains_key(id)) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "partition".to_string(), reason: format!("Vertex {} has no part.", id) }));
+        }
+        Ok(self.iter_e().filter(|edge| partition[&edge.id1] != partition[&edge.id2]).count())
+    }
+
+    fn partition(&self, parts: usize, imbalance: f64, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, usize>> {
+        const FUNCTION_PATH: &str = "Graph::Partitioning::partition";
+        if parts == 0 {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "parts".to_string(), reason: "The number of parts must be positive.".to_string() }));
+        }
+        if imbalance.is_nan() || imbalance < 0f64 {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "imbalance".to_string(), reason: format!("The imbalance must be non-negative, {} given.", imbalance) }));
+        }
+        let (vertices, finest) = self.weighted_graph();
+        if vertices.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let max_part_weight = (((1f64 + imbalance) * vertices.len() as f64 / parts as f64).ceil() as usize).max(vertices.len().div_ceil(parts));
+        let max_vertex_weight = ((1.5 * vertices.len() as f64 / (parts * COARSEST_SIZE_PER_PART) as f64).ceil() as usize).max(2).min(max_part_weight);
+        let mut levels = vec![finest];
+        let mut mappings = Vec::new();
+        while levels.last().unwrap().len() > parts * COARSEST_SIZE_PER_PART {
+            control.check(FUNCTION_PATH)?;
+            let finer = levels.last().unwrap();
+            let (coarser, mapping) = finer.coarsen(max_vertex_weight);
+            if (coarser.len() as f64) > (1f64 - COARSENING_MIN_REDUCTION) * finer.len() as f64 {
+                break;
+            }
+            levels.push(coarser);
+            mappings.push(mapping);
+        }
+        let coarsest = levels.pop().unwrap();
+        let mut assignment = coarsest.grow_parts(parts);
+        coarsest.refine(&mut assignment, parts, max_part_weight);
+        while let (Some(level), Some(mapping)) = (levels.pop(), mappings.pop()) {
+            control.check(FUNCTION_PATH)?;
+            assignment = mapping.into_iter().map(|v| assignment[v]).collect();
+            level.refine(&mut assignment, parts, max_part_weight);
+        }
+        Ok(vertices.into_iter().zip(assignment).collect())
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * COARSENING                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Coarsening
+/// 
+/// ## Description
+/// The result of [`Graph::coarsen`]: the coarse graph and  the  prolongation  map  from
+/// every vertex of the original graph to the vertex of the coarse  graph  it  has  been
+/// merged into.
+/// 
+/// [`Graph::coarsen`]: crate::Graph::coarsen
+pub type Coarsening<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> = (Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>, HashMap<VertexIdType, VertexIdType>);
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollectionIO,
+    EdgeIdType: Id,
+    LocaleType: LocaleCounterparts<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollectionIO,
+    VertexIdType: Id,
+{
+    /// # Coarsen graph
+    /// 
+    /// ## Description
+    /// Build a smaller graph by repeatedly merging pairs of adjacent vertices joined by
+    /// the heaviest edges.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `target_count_v` : `usize` - the number of vertices at  which  the  coarsening
+    /// stops.
+    /// * `name` : `&str` - the name of the vertex and edge attribute to  write  weights
+    /// into.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Coarsening<...>>`    -    `Ok(value)`    is    returned     if
+    /// `target_count_v` is positive and the computation wasn't  cancelled,  `value`  in
+    /// this case holds the coarse graph and the prolongation map from every  vertex  of
+    /// the caller to the vertex of the coarse graph it has been merged  into;  `Err(_)`
+    /// is returned otherwise.
+    /// 
+    /// ## Details
+    /// Each level of coarsening finds a heavy-edge matching, where vertices are visited
+    /// from the lightest one and each of them is matched with the  unmatched  neighbour
+    /// joined by the heaviest edge, and merges the endpoints  of  every  matched  edge.
+    /// Levels are added until the graph has at  most  `target_count_v`  vertices  or  a
+    /// level removes less than 5% of vertices, e.g. because the remaining vertices  are
+    /// isolated. No vertex of the coarse graph may  absorb  more  than  ⌈1.5  ·  |V|  /
+    /// `target_count_v`⌉ vertices of the caller, so that  the  merged  groups  stay  of
+    /// comparable sizes. This is the coarsening phase of the multilevel scheme of METIS
+    /// \[[source][metis]\];  since  merged  vertices  are  close  to  each  other,  the
+    /// distances in the coarse graph approximate the distances between  the  respective
+    /// groups in the caller.
+    /// 
+    /// The coarse graph is an [undirected][kinds] graph with the same  multiplicity  as
+    /// the caller. Each of its vertices has the smallest ID among the vertices  of  the
+    /// caller merged into it and the attribute `name` of type `u64`  that  holds  their
+    /// number. A pair of vertices of the coarse graph is joined by a single edge if any
+    /// edges join their groups in the caller, the attribute `name` of the edge of  type
+    /// `u64` holds the number of such edges. Directions of edges are ignored,  parallel
+    /// edges add up and self-loops are ignored. The result is deterministic.
+    /// 
+    /// The given [`ExecutionControl`] is checked  at  each  level,  an  error  of  kind
+    /// [`Cancelled`][cancelled] is returned if the computation is cancelled.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|) in typical cases.
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [cancelled]: crate::errors::ErrorKind::Cancelled
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    /// [metis]: https://doi.org/10.1137/S1064827595287997
+    pub fn coarsen(&self, target_count_v: usize, name: &str, control: &ExecutionControl) -> CrabNetsResult<Coarsening<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Undirected, VertexAttributeCollectionType, VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::coarsen";
+        if target_count_v == 0 {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "target_count_v".to_string(), reason: "The target number of vertices must be positive.".to_string() }));
+        }
+        let (vertices, mut coarse) = self.weighted_graph();
+        let max_vertex_weight = ((1.5 * vertices.len() as f64 / target_count_v as f64).ceil() as usize).max(2);
+        // The vertex of the current level for every vertex of the caller
+        let mut mapping: Vec<usize> = (0..vertices.len()).collect();
+        while coarse.len() > target_count_v {
+            control.check(FUNCTION_PATH)?;
+            let (coarser, level_mapping) = coarse.coarsen(max_vertex_weight);
+            if (coarser.len() as f64) > (1f64 - COARSENING_MIN_REDUCTION) * coarse.len() as f64 {
+                break;
+            }
+            mapping.iter_mut().for_each(|x| *x = level_mapping[*x]);
+            coarse = coarser;
+        }
+        // Vertices are sorted, so the first vertex mapped to a coarse vertex is the
+        // smallest one
+        let mut representatives: Vec<Option<&VertexIdType>> = vec![None; coarse.len()];
+        for (id, v) in vertices.iter().zip(mapping.iter()) {
+            representatives[*v].get_or_insert(id);
+        }
+        let representatives: Vec<&VertexIdType> = representatives.into_iter().map(|x| x.unwrap()).collect();
+        let mut answer: Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType::Undirected, VertexAttributeCollectionType, VertexIdType> = Graph::new();
+        for (v, id) in representatives.iter().enumerate() {
+            answer.add_v(Some((*id).clone()));
+            answer.v_attrs_mut(id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value: StaticDispatchAttributeValue::UInt64(coarse.weights[v] as u64) });
+        }
+        for (v, adjacent) in coarse.adjacency.iter().enumerate() {
+            for (u, weight) in adjacent.iter().filter(|(u, _)| **u > v).sorted() {
+                let edge_id = answer.add_e(representatives[v], representatives[*u], false, None)?;
+                answer.e_attrs_mut(representatives[v], representatives[*u], &edge_id)?.io_reader_callback::<EdgeIdType, VertexIdType>(AttributeToken { name, value: StaticDispatchAttributeValue::UInt64(*weight as u64) });
+            }
+        }
+        let prolongation = vertices.iter().zip(mapping).map(|(id, v)| (id.clone(), representatives[v].clone())).collect();
+        Ok((answer, prolongation))
+    }
+}
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::attributes::StaticDispatchAttributeValue;
+    use crate::io::AttributeCollectionIO;
+    use super::*;
+
+    #[test]
+    fn coarsen() {
+        let control = ExecutionControl::new();
+        // Grid 10 × 10 with doubled horizontal edges and an isolated vertex
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for _ in 0..101 {
+            g.add_v(None);
+        }
+        for i in 0..10 {
+            for j in 0..10 {
+                if i + 1 < 10 {
+                    g.add_e(&(10 * i + j + 10), &(10 * i + j), true, None).unwrap();
+                }
+                if j + 1 < 10 {
+                    g.add_e(&(10 * i + j), &(10 * i + j + 1), false, None).unwrap();
+                    g.add_e(&(10 * i + j), &(10 * i + j + 1), false, None).unwrap();
+                }
+            }
+        }
+        let (coarse, prolongation) = g.coarsen(30, "Weight", &control).unwrap();
+        let weight = |x: StaticDispatchAttributeValue| match x {
+            StaticDispatchAttributeValue::UInt64(x) => x,
+            _ => panic!("Weights must be of type u64."),
+        };
+        assert!(coarse.count_v() <= 30 && coarse.count_v() > 2);
+        assert_eq!(prolongation.len(), 101);
+        assert!(prolongation.iter().all(|(id, coarse_id)| coarse_id <= id && prolongation[coarse_id] == *coarse_id));
+        assert_eq!(coarse.iter_v().map(|x| weight(coarse.v_attrs(&x).unwrap().io_query_contents("Weight").unwrap())).sum::<u64>(), 101);
+        assert!(coarse.iter_v().all(|x| weight(coarse.v_attrs(&x).unwrap().io_query_contents("Weight").unwrap()) <= 6));
+        // Every edge of the grid either lies within a group or is counted in the coarse
+        // graph
+        let cut = g.iter_e().filter(|x| prolongation[&x.id1] != prolongation[&x.id2]).count() as u64;
+        assert_eq!(coarse.iter_e().map(|x| weight(coarse.e_attrs(&x.id1, &x.id2, &x.edge_id).unwrap().io_query_contents("Weight").unwrap())).sum::<u64>(), cut);
+        assert!(coarse.iter_e().all(|x| x.direction == EdgeDirection::Undirected));
+        assert_eq!(prolongation[&100], 100);
+        // Heavy edges are merged first, so the first level only merges horizontal pairs
+        let (coarse, prolongation) = g.coarsen(60, "Weight", &control).unwrap();
+        assert_eq!(coarse.count_v(), 51);
+        assert!((0..100).all(|x| prolongation[&x] == x - x % 2));
+        assert!(g.coarsen(0, "Weight", &control).is_err());
+        control.cancel();
+        assert!(matches!(g.coarsen(30, "Weight", &control).err().unwrap().kind(), ErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn partition() {
+        let control = ExecutionControl::new();
+        // Cliques 0 - 4 and 5 - 9 joined by edge 4 - 5
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..10 {
+            g.add_v(None);
+        }
+        for (i, j) in (0..5).tuple_combinations().chain((5..10).tuple_combinations()).chain([(4, 5)]) {
+            g.add_e(&i, &j, false, None).unwrap();
+        }
+        let partition = g.partition(2, 0.0, &control).unwrap();
+        assert_eq!((0..10).map(|id| partition[&id]).collect::<Vec<_>>(), vec![0, 0, 0, 0, 0, 1, 1, 1, 1, 1]);
+        assert_eq!(g.edge_cut(&partition).unwrap(), 1);
+        assert!(g.partition(0, 0.0, &control).is_err() && g.partition(2, -0.5, &control).is_err());
+        assert!(g.edge_cut(&HashMap::from([(0, 0)])).is_err());
+        // Grid 30 × 30, coarsened before partitioning
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..900 {
+            g.add_v(None);
+        }
+        for i in 0..30 {
+            for j in 0..30 {
+                if i + 1 < 30 {
+                    g.add_e(&(30 * i + j), &(30 * i + j + 30), false, None).unwrap();
+                }
+                if j + 1 < 30 {
+                    g.add_e(&(30 * i + j), &(30 * i + j + 1), false, None).unwrap();
+                }
+            }
+        }
+        let partition = g.partition(4, 0.05, &control).unwrap();
+        let sizes = partition.values().counts();
+        assert_eq!(sizes.len(), 4);
+        assert!(sizes.into_iter().all(|(part, size)| *part < 4 && size <= 237));
+        // Four quadrants cut 60 edges, four strips cut 90 edges
+        assert!(g.edge_cut(&partition).unwrap() <= 120);
+    }
+}
@@ -0,0 +1,325 @@
+//! # Path module
+//! 
+//! ## Description
+//! This module contains [`Path`], the common representation of paths  returned  by  the
+//! algorithms of CrabNets.
+use std::fmt::Display;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, EdgeDirection, Id};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PATH                                                                              *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Path
+/// 
+/// ## Description
+/// A sequence of edges where each edge starts at the vertex the previous one  ends  at.
+/// The path is stored as its first vertex followed by the steps, each step being the ID
+/// of the traversed edge and the ID of the vertex it leads to.
+/// 
+/// Edges are identified by their IDs, so paths are meaningful both  for  simple  graphs
+/// and multi-graphs. A path without edges consists of a single vertex.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Path<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    source: VertexIdType,
+    steps: Vec<(EdgeIdType, VertexIdType)>,
+}
+
+// Path::Path
+impl<EdgeIdType, VertexIdType> Path<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Create a path
+    /// 
+    /// ## Description
+    /// Create a path without edges that consists of a single vertex.
+    /// 
+    /// ## Arguments
+    /// * `source` : `VertexIdType` - the ID of the only vertex of the path.
+    /// 
+    /// ## Returns
+    /// * `Path` - a new path.
+    pub fn new(source: VertexIdType) -> Self {
+        Path { source, steps: Vec::new() }
+    }
+
+    /// # Concatenate paths
+    /// 
+    /// ## Description
+    /// Append all edges of another path to the end of the caller.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `other` : `Path` - the path to append.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>` - `Ok(())` is returned if `other` starts  at  the  vertex
+    /// the caller ends at; `Err(_)` is returned otherwise, in  which  case  the  caller
+    /// remains unchanged.
+    /// 
+    /// ## Complexity
+    /// Time: O(|`other`|).
+    /// 
+    /// Space: O(|`other`|).
+    pub fn append(&mut self, other: Self) -> CrabNetsResult<()> {
+        const FUNCTION_PATH: &str = "Path::Path::append";
+        if *self.target() != other.source {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "other".to_string(), reason: format!("Path ending at vertex {} can't be continued by path starting at vertex {}.", self.target(), other.source) }));
+        }
+        self.steps.extend(other.steps);
+        Ok(())
+    }
+
+    /// # Concatenate paths
+    /// 
+    /// ## Description
+    /// Build the path that goes along the caller and then along another path.
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `other` : `Path` - the path to append.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Path>` - `Ok(value)` is returned  if  `other`  starts  at  the
+    /// vertex the caller ends at, `value`  in  this  case  is  the  concatenated  path;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|`other`|).
+    /// 
+    /// Space: O(|`other`|).
+    pub fn concat(mut self, other: Self) -> CrabNetsResult<Self> {
+        self.append(other)?;
+        Ok(self)
+    }
+
+    /// # Iterate over edges
+    /// 
+    /// ## Description
+    /// Iterate over the edges of the path in their order.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = (&VertexIdType, &VertexIdType, &EdgeIdType)>>`  -  an
+    /// iterator over the edges, each edge is  given  by  the  ID  of  the  vertex  it's
+    /// traversed from, the ID of the vertex it's traversed to and its own ID.
+    pub fn edges(&self) -> Box<dyn Iterator<Item = (&VertexIdType, &VertexIdType, &EdgeIdType)> + '_> {
+        Box::new(self.vertices().zip(self.steps.iter()).map(|(id1, (edge_id, id2))| (id1, id2, edge_id)))
+    }
+
+    /// # Check if path has no edges
+    /// 
+    /// ## Description
+    /// Check if the path consists of a single vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the path has  no  edges,  `false`  is  returned
+    /// otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// # Check if path exists in graph
+    /// 
+    /// ## Description
+    /// Check if the path can be followed in the given graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&G` - an immutable reference to the graph.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if all vertices and edges of the path exist in the
+    /// graph and each directed edge is traversed from its source to its target, `false`
+    /// is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|`self`|).
+    /// 
+    /// Space: O(1).
+    pub fn is_valid_in<G, EdgeAttributeCollectionType, VertexAttributeCollectionType>(&self, graph: &G) -> bool
+    where
+        G: BasicImmutableGraph<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        EdgeAttributeCollectionType: AttributeCollection,
+        VertexAttributeCollectionType: AttributeCollection,
+    {
+        graph.contains_v(&self.source) && self.edges().all(|(id1, id2, edge_id)| matches!(graph.contains_e(id1, id2, edge_id), Some(EdgeDirection::Undirected | EdgeDirection::Directed1to2)))
+    }
+
+    /// # Number of edges
+    /// 
+    /// ## Description
+    /// Count the edges of the path.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of edges in the path.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// # Extend path
+    /// 
+    /// ## Description
+    /// Append one edge to the end of the path.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `edge_id` : `EdgeIdType` - the ID of the appended edge.
+    /// * `id` : `VertexIdType` - the ID of the vertex the appended edge leads to.
+    /// 
+    /// ## Returns
+    /// None.
+    #[inline]
+    pub fn push(&mut self, edge_id: EdgeIdType, id: VertexIdType) {
+        self.steps.push((edge_id, id));
+    }
+
+    /// # First vertex
+    /// 
+    /// ## Description
+    /// Get the vertex the path starts at.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&VertexIdType` - an immutable reference to the ID of the first vertex.
+    #[inline]
+    pub fn source(&self) -> &VertexIdType {
+        &self.source
+    }
+
+    /// # Last vertex
+    /// 
+    /// ## Description
+    /// Get the vertex the path ends at.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&VertexIdType` - an immutable reference to the ID of the last vertex.
+    #[inline]
+    pub fn target(&self) -> &VertexIdType {
+        self.steps.last().map_or(&self.source, |x| &x.1)
+    }
+
+    /// # Total cost of path
+    /// 
+    /// ## Description
+    /// Sum the weights of all edges of the path.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// 
+    /// ## Returns
+    /// * `f64` - the total weight of the edges, 0 for paths without edges.
+    /// 
+    /// ## Complexity
+    /// Time: O(|`self`|).
+    /// 
+    /// Space: O(1).
+    pub fn total_cost<WeightFnType>(&self, weight_fn: WeightFnType) -> f64
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        self.edges().map(|(id1, id2, edge_id)| weight_fn(id1, id2, edge_id)).sum()
+    }
+
+    /// # Iterate over vertices
+    /// 
+    /// ## Description
+    /// Iterate over the vertices of the path in their order.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = &VertexIdType>>` - an  iterator  over  the  vertices,
+    /// both endpoints included. Vertices visited  several  times  are  yielded  several
+    /// times.
+    pub fn vertices(&self) -> Box<dyn Iterator<Item = &VertexIdType> + '_> {
+        Box::new(std::iter::once(&self.source).chain(self.steps.iter().map(|x| &x.1)))
+    }
+}
+
+// Path::Display
+impl<EdgeIdType, VertexIdType> Display for Path<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)?;
+        for (edge_id, id) in self.steps.iter() {
+            write!(f, " -[{}]- {}", edge_id, id)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::Path;
+
+    #[test]
+    fn path_operations() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&3, &2, true, None).unwrap();
+        let mut p = Path::new(0);
+        assert!(p.is_empty() && p.is_valid_in(&g));
+        p.push(1, 1);
+        p.push(0, 2);
+        assert_eq!(p.len(), 2);
+        assert_eq!(p.vertices().cloned().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(p.total_cost(|x, _, z| (*x + *z) as f64), 2.0);
+        assert!(p.is_valid_in(&g));
+        assert_eq!(p.to_string(), "0 -[1]- 1 -[0]- 2");
+        let mut q = Path::new(2);
+        q.push(0, 3);
+        assert!(!q.is_valid_in(&g));
+        assert!(q.clone().concat(p.clone()).is_err());
+        let r = p.concat(q).unwrap();
+        assert_eq!(r.source(), &0);
+        assert_eq!(r.target(), &3);
+        assert_eq!(r.len(), 3);
+        assert!(!r.is_valid_in(&g));
+        assert!(!Path::new(5).is_valid_in(&g));
+    }
+}
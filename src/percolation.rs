@@ -0,0 +1,307 @@
+//! # Percolation module
+//! 
+//! ## Description
+//! This module contains [`Percolation`] trait and  its  implementation  for  [`Graph`].
+//! Percolation analysis removes vertices or edges one by one and tracks the size of the
+//! largest connected component, which  shows  how  robust  the  network  is  to  random
+//! failures and to targeted attacks.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet};
+use itertools::Itertools;
+use rand::{seq::SliceRandom, Rng};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, union_find::UnionFind, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Removal strategy
+/// 
+/// ## Description
+/// Order in which the functions of [`Percolation`] remove vertices or edges.
+/// 
+/// ## Variants
+/// * `HighestDegree` - vertices are removed in descending order of their degrees in the
+/// original graph, edges are removed in descending order of the sums of the degrees  of
+/// their endpoints.
+/// * `HighestScore` - vertices are removed in descending order  of  the  given  scores
+/// (e.g. betweenness), edges are removed in descending order of the sums of the  scores
+/// of their endpoints, vertices missing from the map are treated as having  the  lowest
+/// score.
+/// * `Random` - vertices or edges are removed in uniformly random order.
+/// 
+/// Ties are broken by the ascending order of IDs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RemovalStrategy<'a, VertexIdType>
+where
+    VertexIdType: Id,
+{
+    HighestDegree,
+    HighestScore(&'a HashMap<VertexIdType, f64>),
+    Random,
+}
+
+/// # Outcome of percolation
+/// 
+/// ## Description
+/// Result of [`Percolation::edge_percolation`] and [`Percolation::vertex_percolation`].
+/// 
+/// ## Fields
+/// * `giant_component_sizes` - the k-th element  is  the  number  of  vertices  in  the
+/// largest connected component after the first k  removals,  i.e.  the  vector  is  one
+/// element longer than `removed`.
+/// * `removed` - the removed items in the order  of  removal,  vertex  IDs  for  vertex
+/// percolation and `(id1, id2, edge_id)` triples for  edge  percolation,  they  can  be
+/// passed  to  [`BasicMutableGraph::remove_v`]  or  [`BasicMutableGraph::remove_e`]  to
+/// apply the removal to the graph.
+/// 
+/// [`BasicMutableGraph::remove_v`]: crate::BasicMutableGraph::remove_v
+/// [`BasicMutableGraph::remove_e`]: crate::BasicMutableGraph::remove_e
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PercolationOutcome<ItemType> {
+    pub giant_component_sizes: Vec<usize>,
+    pub removed: Vec<ItemType>,
+}
+
+
+
+// Number of items to remove given the fraction of items
+fn removal_count(fraction: f64, item_count: usize, function_path: &str) -> CrabNetsResult<usize> {
+    match (0.0..=1.0).contains(&fraction) {
+        true => Ok((fraction * item_count as f64).floor() as usize),
+        false => Err(CrabNetsError::new(function_path, ErrorKind::InvalidArgument { name: "fraction".to_string(), reason: format!("The fraction of removed items must be between 0 and 1, {} given.", fraction) })),
+    }
+}
+
+
+
+// Positions of the items in the order of removal: random or descending order of their
+// keys, NaN keys go last, ties keep the original order of the items
+fn removal_order<R>(item_count: usize, keys: Option<Vec<f64>>, rng: &mut R) -> Vec<usize>
+where
+    R: Rng,
+{
+    match keys {
+        Some(keys) => {
+            let keys = keys.into_iter().map(|x| if x.is_nan() { f64::NEG_INFINITY } else { x }).collect::<Vec<_>>();
+            (0..item_count).sorted_by(|i, j| keys[*j].total_cmp(&keys[*i])).collect()
+        },
+        None => {
+            let mut answer = (0..item_count).collect::<Vec<_>>();
+            answer.shuffle(rng);
+            answer
+        },
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PERCOLATION                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Percolation
+/// 
+/// ## Description
+/// Robustness analysis by removal of vertices or edges.
+/// 
+/// Connected components ignore the  directions  of  edges.  The  graph  itself  is  not
+/// changed: the functions return the removed items, so that the removal can be  applied
+/// afterwards if needed. Vertices and edges are sorted by their IDs before  shuffling,
+/// so a seeded random number generator always gives the same outcome.
+/// 
+/// ## Details
+/// The whole trajectory is computed at the cost of a single connectivity analysis:  the
+/// items are removed at once and then restored in reverse order  while  components  are
+/// merged with a [`UnionFind`] [[source](https://doi.org/10.1103/PhysRevLett.85.4104)].
+pub trait Percolation<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Edge percolation
+    /// 
+    /// ## Description
+    /// Remove a fraction  of  edges  and  track  the  size  of  the  largest  connected
+    /// component.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `strategy` : `RemovalStrategy` - the order of removal.
+    /// * `fraction` : `f64` - the fraction of edges to remove, from 0 to 1.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<PercolationOutcome<(VertexIdType, VertexIdType, EdgeIdType)>>`:
+    /// `Ok(value)` is returned if `fraction` is between 0 and 1, `value` in  this  case
+    /// holds the removed edges and the trajectory of the size of the largest component;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// ⌊`fraction` · |E|⌋ edges are removed, each parallel edge is removed separately.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E| log |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn edge_percolation<R>(&self, strategy: RemovalStrategy<'_, VertexIdType>, fraction: f64, rng: &mut R) -> CrabNetsResult<PercolationOutcome<(VertexIdType, VertexIdType, EdgeIdType)>>
+    where
+        R: Rng;
+    /// # Vertex percolation
+    /// 
+    /// ## Description
+    /// Remove a fraction of vertices and  track  the  size  of  the  largest  connected
+    /// component.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `strategy` : `RemovalStrategy` - the order of removal.
+    /// * `fraction` : `f64` - the fraction of vertices to remove, from 0 to 1.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<PercolationOutcome<VertexIdType>>` - `Ok(value)`  is  returned
+    /// if `fraction` is between 0 and  1,  `value`  in  this  case  holds  the  removed
+    /// vertices and the trajectory of the size of the largest  component;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// ⌊`fraction` · |V|⌋ vertices are removed together with their incident edges.  The
+    /// degrees used by [`RemovalStrategy::HighestDegree`] are  those  of  the  original
+    /// graph, they aren't recomputed after each removal.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn vertex_percolation<R>(&self, strategy: RemovalStrategy<'_, VertexIdType>, fraction: f64, rng: &mut R) -> CrabNetsResult<PercolationOutcome<VertexIdType>>
+    where
+        R: Rng;
+}
+
+// Graph::Percolation
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Percolation<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn edge_percolation<R>(&self, strategy: RemovalStrategy<'_, VertexIdType>, fraction: f64, rng: &mut R) -> CrabNetsResult<PercolationOutcome<(VertexIdType, VertexIdType, EdgeIdType)>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::Percolation::edge_percolation";
+        let edges = self.iter_e().map(|x| (x.id1, x.id2, x.edge_id)).sorted().collect::<Vec<_>>();
+        let count = removal_count(fraction, edges.len(), FUNCTION_PATH)?;
+        let keys = match strategy {
+            RemovalStrategy::HighestDegree => Some(edges.iter().map(|(id1, id2, _)| (self.v_degree(id1).unwrap() + self.v_degree(id2).unwrap()) as f64).collect()),
+            RemovalStrategy::HighestScore(scores) => Some(edges.iter().map(|(id1, id2, _)| scores.get(id1).copied().unwrap_or(f64::NEG_INFINITY) + scores.get(id2).copied().unwrap_or(f64::NEG_INFINITY)).collect()),
+            RemovalStrategy::Random => None,
+        };
+        let order = removal_order(edges.len(), keys, rng);
+        let mut is_removed = vec![false; edges.len()];
+        order[..count].iter().for_each(|x| is_removed[*x] = true);
+        let mut sets: UnionFind<VertexIdType> = self.iter_v().collect();
+        for (id1, id2, _) in edges.iter().zip(is_removed).filter(|x| !x.1).map(|x| x.0) {
+            sets.union(id1, id2).unwrap();
+        }
+        let mut giant_component_size = sets.sets().iter().map(|x| x.len()).max().unwrap_or(0);
+        let mut giant_component_sizes = vec![giant_component_size];
+        for (id1, id2, _) in order[..count].iter().rev().map(|x| &edges[*x]) {
+            sets.union(id1, id2).unwrap();
+            giant_component_size = giant_component_size.max(sets.set_size(id1).unwrap());
+            giant_component_sizes.push(giant_component_size);
+        }
+        giant_component_sizes.reverse();
+        Ok(PercolationOutcome { giant_component_sizes, removed: order[..count].iter().map(|x| edges[*x].clone()).collect() })
+    }
+
+    fn vertex_percolation<R>(&self, strategy: RemovalStrategy<'_, VertexIdType>, fraction: f64, rng: &mut R) -> CrabNetsResult<PercolationOutcome<VertexIdType>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::Percolation::vertex_percolation";
+        let vertices = self.iter_v().sorted().collect::<Vec<_>>();
+        let count = removal_count(fraction, vertices.len(), FUNCTION_PATH)?;
+        let keys = match strategy {
+            RemovalStrategy::HighestDegree => Some(vertices.iter().map(|id| self.v_degree(id).unwrap() as f64).collect()),
+            RemovalStrategy::HighestScore(scores) => Some(vertices.iter().map(|id| scores.get(id).copied().unwrap_or(f64::NEG_INFINITY)).collect()),
+            RemovalStrategy::Random => None,
+        };
+        let removed = removal_order(vertices.len(), keys, rng)[..count].iter().map(|x| vertices[*x].clone()).collect::<Vec<_>>();
+        let removed_set = removed.iter().collect::<HashSet<_>>();
+        let mut sets: UnionFind<VertexIdType> = vertices.iter().filter(|x| !removed_set.contains(x)).cloned().collect();
+        for edge in self.iter_e() {
+            if sets.contains(&edge.id1) && sets.contains(&edge.id2) {
+                sets.union(&edge.id1, &edge.id2).unwrap();
+            }
+        }
+        let mut giant_component_size = sets.sets().iter().map(|x| x.len()).max().unwrap_or(0);
+        let mut giant_component_sizes = vec![giant_component_size];
+        for id in removed.iter().rev() {
+            sets.insert(id.clone());
+            for adjacent_id in self.iter_adjacent(id).unwrap() {
+                if sets.contains(&adjacent_id) {
+                    sets.union(id, &adjacent_id).unwrap();
+                }
+            }
+            giant_component_size = giant_component_size.max(sets.set_size(id).unwrap());
+            giant_component_sizes.push(giant_component_size);
+        }
+        giant_component_sizes.reverse();
+        Ok(PercolationOutcome { giant_component_sizes, removed })
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn percolation() {
+        // Path 0 - 1 - 2 - 3 - 4 - 5 - 6 - 7 - 8 - 9
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..10 {
+            g.add_v(None);
+        }
+        for i in 0..9 {
+            g.add_e(&i, &(i + 1), i % 2 == 0, None).unwrap();
+        }
+        let mut rng = StdRng::seed_from_u64(5);
+        let outcome = g.vertex_percolation(RemovalStrategy::HighestDegree, 0.2, &mut rng).unwrap();
+        assert_eq!(outcome, PercolationOutcome { giant_component_sizes: vec![10, 8, 7], removed: vec![1, 2] });
+        let scores = HashMap::from([(4, 2.0), (7, 1.0), (0, f64::NAN)]);
+        let outcome = g.vertex_percolation(RemovalStrategy::HighestScore(&scores), 0.3, &mut rng).unwrap();
+        assert_eq!(outcome, PercolationOutcome { giant_component_sizes: vec![10, 5, 4, 3], removed: vec![4, 7, 0] });
+        let outcome = g.edge_percolation(RemovalStrategy::Random, 1.0, &mut rng).unwrap();
+        assert_eq!(outcome.removed.len(), 9);
+        assert_eq!((outcome.giant_component_sizes[0], outcome.giant_component_sizes[9]), (10, 1));
+        assert!(outcome.giant_component_sizes.windows(2).all(|x| x[0] >= x[1]));
+        // Removing the same edges directly gives the same largest component
+        let outcome = g.edge_percolation(RemovalStrategy::Random, 0.5, &mut rng).unwrap();
+        for (id1, id2, edge_id) in outcome.removed.iter() {
+            g.remove_e(id1, id2, edge_id).unwrap();
+        }
+        assert_eq!(g.components_union_find().sets().iter().map(|x| x.len()).max(), outcome.giant_component_sizes.last().copied());
+        assert!(g.vertex_percolation(RemovalStrategy::Random, 1.5, &mut rng).is_err());
+    }
+}
@@ -1,6 +1,112 @@
 #[allow(unused_imports)]
 pub use crate::*;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::aggregation::{AttributeStats, NeighbourDirection};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::anonymization::AnonymizationPolicy;
 #[allow(unused_imports)]
 pub use crate::attributes::{AttributeCollection, DynamicDispatchAttributeMap, StaticDispatchAttributeValue};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::canonization::{CanonicalForm, Canonization};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::centrality::Centrality;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::communities::{Communities, Partition};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::conversions::{EdgeMergePolicy, EdgeReducer, IdMapping, VertexMergePolicy, VertexReducer};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::covers::Covers;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::degree_sequences::is_graphical;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::diffusion::{Diffusion, DiffusionOperator};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::distance_oracle::DistanceOracle;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::dynamics::{CascadeModel, Dynamics, EpidemicModel, SpreadingOutcome, SpreadingState};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::edge_colouring::{EdgeColouring, EdgeColours};
+#[allow(unused_imports)]
+pub use crate::errors::{CrabNetsError, CrabNetsResult, ErrorKind};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::execution::ExecutionControl;
+#[allow(unused_imports)]
+pub use crate::handles::VertexHandle;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::layout::{Coordinates, Layout};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::limits::GuardedGraph;
 #[allow(unused_imports)]
 pub use crate::locales::*;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::matching::{MatchedEdges, Matching};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::matrices::Matrix;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::metrics::Metrics;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::motifs::{Motifs, GRAPHLET_TYPES, TRIAD_TYPES};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::orderings::Orderings;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::partitioning::Partitioning;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::path::Path;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::percolation::{Percolation, PercolationOutcome, RemovalStrategy};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::query::{DirectionConstraint, Pattern, Query, VertexPredicate};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::random_walks::{Node2VecParameters, RandomWalk, RandomWalks};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::rng::CrabNetsRng;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::rooted_tree::RootedTree;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::shortest_paths::ShortestPaths;
+#[cfg(feature = "linalg")]
+#[allow(unused_imports)]
+pub use crate::spectral::Spectral;
+#[cfg(feature = "testing")]
+#[allow(unused_imports)]
+pub use crate::testing::{assert_roundtrip, EdgeKinds, GraphStrategy};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::traversal::{Traversal, TraversalOrder, VisitControl, Visitor};
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::travelling_salesman::TravellingSalesman;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::union_find::UnionFind;
+#[cfg(feature = "std")]
+#[allow(unused_imports)]
+pub use crate::workspace::Workspace;
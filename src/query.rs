@@ -0,0 +1,747 @@
+//! # Query module
+//! 
+//! ## Description
+//! This module contains [`Pattern`] used to describe small subgraphs  of  interest  and
+//! [`Query`] trait that finds all occurrences of such patterns in a [`Graph`].  Besides
+//! single edges, patterns may contain paths of variable length which  labels  follow  a
+//! regular expression.
+//! 
+//! [`Graph`]: crate::Graph
+use std::{collections::HashSet, ops::RangeInclusive};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, EdgeDirection, EdgeIteratorItem, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PATTERNS                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Vertex predicate
+/// 
+/// ## Description
+/// A boxed function that receives the attribute collection of  a  vertex  of  the  host
+/// graph and decides whether this  vertex  may  play  the  role  of  a  vertex  of  the
+/// [`Pattern`].
+pub type VertexPredicate<'a, VertexAttributeCollectionType> = Box<dyn Fn(&VertexAttributeCollectionType) -> bool + 'a>;
+
+
+
+/// # Direction constraint
+/// 
+/// ## Description
+/// The kind of edges of the host graph that match an edge of the [`Pattern`].
+/// 
+/// ## Variants
+/// * `Any` - any edge matches regardless of its direction.
+/// * `Directed` - only a directed edge going from the host vertex matched to the  first
+/// endpoint of the pattern edge to the host vertex matched to the second one matches.
+/// * `Undirected` - only an undirected edge matches.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DirectionConstraint {
+    Any,
+    Directed,
+    Undirected,
+}
+
+
+
+/// # Pattern
+/// 
+/// ## Description
+/// A small graph which occurrences are looked for with  [`Query::query`].  Vertices  of
+/// the pattern are numbered by consecutive integers starting from 0 in the  order  they
+/// are added, each of them carries a predicate on the attributes of the  host  vertices
+/// it can be matched to. Edges of the pattern connect vertices  by  their  numbers  and
+/// carry [direction constraints][DirectionConstraint]. Paths  of  the  pattern  connect
+/// vertices through any number of host edges within the given range, and the labels  of
+/// these edges may be required to follow a regular expression.
+/// 
+/// A pattern is built with chained  calls  of  [`Pattern::with_v`]  and
+/// [`Pattern::with_e`] starting from [`Pattern::new`].
+pub struct Pattern<'a, EdgeAttributeCollectionType, VertexAttributeCollectionType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    VertexAttributeCollectionType: AttributeCollection,
+{
+    edges: Vec<(usize, usize, DirectionConstraint)>,
+    label_fn: Box<dyn Fn(&EdgeAttributeCollectionType) -> String + 'a>,
+    paths: Vec<PatternPath>,
+    vertices: Vec<VertexPredicate<'a, VertexAttributeCollectionType>>,
+}
+
+// Pattern::Pattern
+impl<'a, EdgeAttributeCollectionType, VertexAttributeCollectionType> Pattern<'a, EdgeAttributeCollectionType, VertexAttributeCollectionType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    VertexAttributeCollectionType: AttributeCollection,
+{
+    /// # Create pattern
+    /// 
+    /// ## Description
+    /// Create an empty pattern.
+    /// 
+    /// ## Returns
+    /// * `Pattern` - the pattern without vertices and edges.
+    pub fn new() -> Self {
+        Pattern { edges: Vec::new(), label_fn: Box::new(|_| String::new()), paths: Vec::new(), vertices: Vec::new() }
+    }
+
+    /// # Count vertices
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of vertices in the pattern.
+    pub fn count_v(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// # Add edge
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `index1` : `usize` - the number of the first endpoint.
+    /// * `index2` : `usize` - the number of the second endpoint.
+    /// * `constraint` : `DirectionConstraint` - the kind of host edges that  match  the
+    /// new edge.
+    /// 
+    /// ## Returns
+    /// * `Pattern` - the updated pattern.
+    /// 
+    /// ## Details
+    /// Endpoints may  be  added  after  the  edge,  the  pattern  is  only  checked  by
+    /// [`Query::query`].
+    pub fn with_e(mut self, index1: usize, index2: usize, constraint: DirectionConstraint) -> Self {
+        self.edges.push((index1, index2, constraint));
+        self
+    }
+
+    /// # Set labels of edges
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `label_fn` : `LabelFnType` - a function that receives the attribute collection
+    /// of a host edge and returns its label.
+    /// 
+    /// ## Returns
+    /// * `Pattern` - the updated pattern.
+    /// 
+    /// ## Details
+    /// Labels are only used by the paths added with [`Pattern::with_path`]. By default,
+    /// all edges have the empty label, which no label expression matches.
+    pub fn with_labels<LabelFnType>(mut self, label_fn: LabelFnType) -> Self
+    where
+        LabelFnType: Fn(&EdgeAttributeCollectionType) -> String + 'a,
+    {
+        self.label_fn = Box::new(label_fn);
+        self
+    }
+
+    /// # Add path
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `index1` : `usize` - the number of the first endpoint.
+    /// * `index2` : `usize` - the number of the second endpoint.
+    /// * `constraint` : `DirectionConstraint` - the kind of host  edges  the  path  may
+    /// consist of.
+    /// * `lengths` : `RangeInclusive<usize>` - the allowed  numbers  of  edges  in  the
+    /// path.
+    /// * `labels` : `Option<&str>` - the regular expression the sequence of edge labels
+    /// of the path must match, `None` allows any labels.
+    /// 
+    /// ## Returns
+    /// * `Pattern` - the updated pattern.
+    /// 
+    /// ## Details
+    /// The new path is matched if the host  graph  contains  a  walk  from  the  vertex
+    /// matched to `index1` to the vertex matched to `index2`, which  length  is  within
+    /// `lengths` and which edges satisfy `constraint`  (`DirectionConstraint::Directed`
+    /// stands for the edges directed along the walk). Vertices and edges  of  the  walk
+    /// may repeat and may be matched to other parts of the pattern.
+    /// 
+    /// Thus, the path `a -[1..3]-> b` is written as follows.
+    /// ```ignore
+    /// with_path(a, b, DirectionConstraint::Directed, 1..=3, None)
+    /// ```
+    /// 
+    /// The labels of edges are given by the function set with [`Pattern::with_labels`].
+    /// Label expressions are written in the syntax of SPARQL property paths:
+    /// * `label` matches an edge with this label,  labels  consist  of  any  characters
+    /// except whitespaces and `/|*+?()`;
+    /// * `x/y` matches `x` followed by `y`;
+    /// * `x|y` matches either `x` or `y`;
+    /// * `x*`, `x+` and `x?` match `x` repeated any number of times, at least  once  or
+    /// at most once respectively;
+    /// * parentheses group subexpressions.
+    /// 
+    /// For example, `"knows+/likes"` matches the walks that follow at least one `knows`
+    /// edge and then a single `likes` edge. Whitespaces between the tokens are ignored.
+    pub fn with_path(mut self, index1: usize, index2: usize, constraint: DirectionConstraint, lengths: RangeInclusive<usize>, labels: Option<&str>) -> Self {
+        self.paths.push(PatternPath { constraint, index1, index2, labels: labels.map(|x| x.to_string()), lengths });
+        self
+    }
+
+    /// # Add vertex
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `predicate` :  `PredicateType`  -  a  function  that  receives  the  attribute
+    /// collection of a host vertex and returns `true` if the new vertex may be  matched
+    /// to it.
+    /// 
+    /// ## Returns
+    /// * `Pattern` - the updated pattern.
+    /// 
+    /// ## Details
+    /// The new vertex gets the number equal to the number of vertices added before it.
+    pub fn with_v<PredicateType>(mut self, predicate: PredicateType) -> Self
+    where
+        PredicateType: Fn(&VertexAttributeCollectionType) -> bool + 'a,
+    {
+        self.vertices.push(Box::new(predicate));
+        self
+    }
+}
+
+// Pattern::Default
+impl<'a, EdgeAttributeCollectionType, VertexAttributeCollectionType> Default for Pattern<'a, EdgeAttributeCollectionType, VertexAttributeCollectionType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    VertexAttributeCollectionType: AttributeCollection,
+{
+    fn default() -> Self {
+        Pattern::new()
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * PATH EXPRESSIONS                                                                  *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+struct PatternPath {
+    constraint: DirectionConstraint,
+    index1: usize,
+    index2: usize,
+    labels: Option<String>,
+    lengths: RangeInclusive<usize>,
+}
+
+
+
+// Nondeterministic automaton over edge labels built with Thompson's construction
+struct LabelAutomaton {
+    accepting_state: usize,
+    // States reachable from each state without reading any labels (including the state itself)
+    closures: Vec<Vec<usize>>,
+    epsilon_transitions: Vec<Vec<usize>>,
+    initial_state: usize,
+    label_transitions: Vec<Vec<(String, usize)>>,
+}
+
+// LabelAutomaton::LabelAutomaton
+impl LabelAutomaton {
+    fn new(expression: &str, function_path: &str) -> CrabNetsResult<Self> {
+        let tokens = expression.char_indices().filter(|(_, x)| !x.is_whitespace()).collect::<Vec<_>>();
+        let mut automaton = LabelAutomaton { accepting_state: 0, closures: Vec::new(), epsilon_transitions: Vec::new(), initial_state: 0, label_transitions: Vec::new() };
+        let mut curr_token = 0usize;
+        let (initial_state, accepting_state) = automaton.parse_alternative(expression, &tokens, &mut curr_token, function_path)?;
+        if curr_token < tokens.len() {
+            return Err(LabelAutomaton::parse_error(expression, &tokens, curr_token, "'/', '|' or the end of the expression", function_path));
+        }
+        automaton.initial_state = initial_state;
+        automaton.accepting_state = accepting_state;
+        automaton.closures = (0..automaton.label_transitions.len()).map(|x| {
+            let mut closure = vec![x];
+            let mut curr_index = 0usize;
+            while curr_index < closure.len() {
+                for next_state in automaton.epsilon_transitions[closure[curr_index]].iter() {
+                    if !closure.contains(next_state) {
+                        closure.push(*next_state);
+                    }
+                }
+                curr_index += 1;
+            }
+            closure
+        }).collect();
+        Ok(automaton)
+    }
+
+    fn add_state(&mut self) -> usize {
+        self.epsilon_transitions.push(Vec::new());
+        self.label_transitions.push(Vec::new());
+        self.label_transitions.len() - 1
+    }
+
+    fn parse_error(expression: &str, tokens: &[(usize, char)], curr_token: usize, expected: &str, function_path: &str) -> CrabNetsError {
+        let (column, found) = match tokens.get(curr_token) {
+            Some((offset, token)) => (expression[..*offset].chars().count() + 1, Some(format!("'{}'", token))),
+            None => (expression.chars().count() + 1, None),
+        };
+        CrabNetsError::new(function_path, ErrorKind::ParseError { line: 1, column: Some(column), expected: expected.to_string(), found })
+    }
+
+    // alternative := sequence ('|' sequence)*
+    fn parse_alternative(&mut self, expression: &str, tokens: &[(usize, char)], curr_token: &mut usize, function_path: &str) -> CrabNetsResult<(usize, usize)> {
+        let mut answer = self.parse_sequence(expression, tokens, curr_token, function_path)?;
+        while tokens.get(*curr_token).is_some_and(|x| x.1 == '|') {
+            *curr_token += 1;
+            let other = self.parse_sequence(expression, tokens, curr_token, function_path)?;
+            let (initial_state, accepting_state) = (self.add_state(), self.add_state());
+            self.epsilon_transitions[initial_state].extend([answer.0, other.0]);
+            self.epsilon_transitions[answer.1].push(accepting_state);
+            self.epsilon_transitions[other.1].push(accepting_state);
+            answer = (initial_state, accepting_state);
+        }
+        Ok(answer)
+    }
+
+    // sequence := repetition ('/' repetition)*
+    fn parse_sequence(&mut self, expression: &str, tokens: &[(usize, char)], curr_token: &mut usize, function_path: &str) -> CrabNetsResult<(usize, usize)> {
+        let mut answer = self.parse_repetition(expression, tokens, curr_token, function_path)?;
+        while tokens.get(*curr_token).is_some_and(|x| x.1 == '/') {
+            *curr_token += 1;
+            let other = self.parse_repetition(expression, tokens, curr_token, function_path)?;
+            self.epsilon_transitions[answer.1].push(other.0);
+            answer = (answer.0, other.1);
+        }
+        Ok(answer)
+    }
+
+    // repetition := atom ('*' | '+' | '?')*
+    fn parse_repetition(&mut self, expression: &str, tokens: &[(usize, char)], curr_token: &mut usize, function_path: &str) -> CrabNetsResult<(usize, usize)> {
+        let mut answer = self.parse_atom(expression, tokens, curr_token, function_path)?;
+        while let Some((_, operator @ ('*' | '+' | '?'))) = tokens.get(*curr_token) {
+            *curr_token += 1;
+            let (initial_state, accepting_state) = (self.add_state(), self.add_state());
+            self.epsilon_transitions[initial_state].push(answer.0);
+            self.epsilon_transitions[answer.1].push(accepting_state);
+            if *operator != '?' {
+                self.epsilon_transitions[answer.1].push(answer.0);
+            }
+            if *operator != '+' {
+                self.epsilon_transitions[initial_state].push(accepting_state);
+            }
+            answer = (initial_state, accepting_state);
+        }
+        Ok(answer)
+    }
+
+    // atom := label | '(' alternative ')'
+    fn parse_atom(&mut self, expression: &str, tokens: &[(usize, char)], curr_token: &mut usize, function_path: &str) -> CrabNetsResult<(usize, usize)> {
+        match tokens.get(*curr_token) {
+            Some((_, '(')) => {
+                *curr_token += 1;
+                let answer = self.parse_alternative(expression, tokens, curr_token, function_path)?;
+                if tokens.get(*curr_token).is_none_or(|x| x.1 != ')') {
+                    return Err(LabelAutomaton::parse_error(expression, tokens, *curr_token, "')'", function_path));
+                }
+                *curr_token += 1;
+                Ok(answer)
+            },
+            Some((_, token)) if !"/|*+?)".contains(*token) => {
+                let mut label = String::new();
+                let mut prev_offset = None;
+                // Labels end at operators and at whitespaces, which are absent from `tokens`
+                while let Some((offset, token)) = tokens.get(*curr_token) {
+                    if "/|*+?()".contains(*token) || prev_offset.is_some_and(|x: usize| expression[x..*offset].chars().count() > 1) {
+                        break;
+                    }
+                    label.push(*token);
+                    prev_offset = Some(*offset);
+                    *curr_token += 1;
+                }
+                let (initial_state, accepting_state) = (self.add_state(), self.add_state());
+                self.label_transitions[initial_state].push((label, accepting_state));
+                Ok((initial_state, accepting_state))
+            },
+            _ => Err(LabelAutomaton::parse_error(expression, tokens, *curr_token, "a label or '('", function_path)),
+        }
+    }
+
+    fn initial_states(&self) -> &[usize] {
+        &self.closures[self.initial_state]
+    }
+
+    fn is_accepting(&self, state: usize) -> bool {
+        state == self.accepting_state
+    }
+
+    fn step<'a>(&'a self, state: usize, label: &'a str) -> impl Iterator<Item = usize> + 'a {
+        self.label_transitions[state].iter().filter(move |(x, _)| x == label).flat_map(|(_, y)| self.closures[*y].iter().copied())
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * MATCHING                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Whether a walk may go along the given edge from `edge.id1` to `edge.id2`
+fn path_edge_matches<EdgeIdType: Id, VertexIdType: Id>(edge: &EdgeIteratorItem<EdgeIdType, VertexIdType>, constraint: DirectionConstraint) -> bool {
+    match constraint {
+        DirectionConstraint::Any => true,
+        DirectionConstraint::Directed => edge.direction == EdgeDirection::Directed1to2,
+        DirectionConstraint::Undirected => edge.direction == EdgeDirection::Undirected,
+    }
+}
+
+
+
+// Backtracking search that assigns host vertices to pattern vertices one by one
+struct PatternMatches<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    assigned_vertices: Vec<VertexIdType>,
+    automata: Vec<Option<LabelAutomaton>>,
+    candidates: Vec<(Vec<VertexIdType>, usize)>,
+    // For each position in `order`: pattern edges to the vertices at the same or earlier
+    // positions given as (position, constraint, whether the current vertex is the first endpoint)
+    constraints: Vec<Vec<(usize, DirectionConstraint, bool)>>,
+    graph: &'a Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>,
+    is_started: bool,
+    label_fn: &'a (dyn Fn(&EdgeAttributeCollectionType) -> String + 'a),
+    order: Vec<usize>,
+    // The same as `constraints` but for paths given as (position, path index, whether the current vertex is the first endpoint)
+    path_constraints: Vec<Vec<(usize, usize, bool)>>,
+    paths: &'a [PatternPath],
+    predicates: Vec<&'a (dyn Fn(&VertexAttributeCollectionType) -> bool + 'a)>,
+    used_vertices: HashSet<VertexIdType>,
+}
+
+// PatternMatches::PatternMatches
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> PatternMatches<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn new(graph: &'a Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>, pattern: &'a Pattern<'_, EdgeAttributeCollectionType, VertexAttributeCollectionType>, automata: Vec<Option<LabelAutomaton>>) -> Self {
+        // Vertices with more edges to the already ordered ones go first to prune the search early
+        let connections = pattern.edges.iter().map(|x| (x.0, x.1)).chain(pattern.paths.iter().map(|x| (x.index1, x.index2))).collect::<Vec<_>>();
+        let mut order = Vec::with_capacity(pattern.count_v());
+        let mut positions = vec![usize::MAX; pattern.count_v()];
+        while order.len() < pattern.count_v() {
+            let next_index = (0..pattern.count_v())
+                .filter(|x| positions[*x] == usize::MAX)
+                .max_by_key(|x| (connections.iter().filter(|(y, z)| (y == x && positions[*z] != usize::MAX) || (z == x && positions[*y] != usize::MAX)).count(), usize::MAX - x))
+                .unwrap();
+            positions[next_index] = order.len();
+            order.push(next_index);
+        }
+        let mut constraints = vec![Vec::new(); order.len()];
+        for (index1, index2, constraint) in pattern.edges.iter() {
+            let (position1, position2) = (positions[*index1], positions[*index2]);
+            if position1 >= position2 {
+                constraints[position1].push((position2, *constraint, true));
+            } else {
+                constraints[position2].push((position1, *constraint, false));
+            }
+        }
+        let mut path_constraints = vec![Vec::new(); order.len()];
+        for (path_index, path) in pattern.paths.iter().enumerate() {
+            let (position1, position2) = (positions[path.index1], positions[path.index2]);
+            if position1 >= position2 {
+                path_constraints[position1].push((position2, path_index, true));
+            } else {
+                path_constraints[position2].push((position1, path_index, false));
+            }
+        }
+        PatternMatches {
+            assigned_vertices: Vec::with_capacity(order.len()),
+            automata,
+            candidates: Vec::with_capacity(order.len()),
+            constraints,
+            graph,
+            is_started: false,
+            label_fn: pattern.label_fn.as_ref(),
+            order,
+            path_constraints,
+            paths: &pattern.paths,
+            predicates: pattern.vertices.iter().map(|x| x.as_ref()).collect(),
+            used_vertices: HashSet::with_capacity(pattern.count_v()),
+        }
+    }
+
+    fn next_candidates(&self) -> Vec<VertexIdType> {
+        let position = self.assigned_vertices.len();
+        match self.constraints[position].iter().find(|(x, _, _)| *x < position) {
+            Some((earlier_position, _, _)) => self.graph.iter_adjacent(&self.assigned_vertices[*earlier_position]).unwrap().sorted().dedup().collect(),
+            None => self.graph.iter_v().sorted().collect(),
+        }
+    }
+
+    fn edge_matches(&self, source: &VertexIdType, target: &VertexIdType, constraint: DirectionConstraint) -> bool {
+        match constraint {
+            DirectionConstraint::Any => self.graph.e_direction_between(source, target).is_some(),
+            DirectionConstraint::Directed => self.graph.contains_directed_e(source, target),
+            DirectionConstraint::Undirected => self.graph.iter_adjacent_undir(source).unwrap().any(|x| x == *target),
+        }
+    }
+
+    fn is_feasible(&self, id: &VertexIdType) -> bool {
+        let position = self.assigned_vertices.len();
+        !self.used_vertices.contains(id)
+            && (self.predicates[self.order[position]])(self.graph.v_attrs(id).unwrap())
+            && self.constraints[position].iter().all(|(other_position, constraint, is_first)| {
+                let other_id = if *other_position == position { id } else { &self.assigned_vertices[*other_position] };
+                if *is_first { self.edge_matches(id, other_id, *constraint) } else { self.edge_matches(other_id, id, *constraint) }
+            })
+            && self.path_constraints[position].iter().all(|(other_position, path_index, is_first)| {
+                let other_id = if *other_position == position { id } else { &self.assigned_vertices[*other_position] };
+                if *is_first { self.path_matches(*path_index, id, other_id) } else { self.path_matches(*path_index, other_id, id) }
+            })
+    }
+
+    // Breadth-first search over the pairs (vertex, state of the automaton) layer by layer
+    fn path_matches(&self, path_index: usize, source: &VertexIdType, target: &VertexIdType) -> bool {
+        let path = &self.paths[path_index];
+        let automaton = self.automata[path_index].as_ref();
+        let mut curr_pairs = automaton.map_or(vec![0], |x| x.initial_states().to_vec()).into_iter().map(|x| (source.clone(), x)).collect::<HashSet<_>>();
+        let mut visited_pairs = HashSet::new();
+        for length in 0..=*path.lengths.end() {
+            if length >= *path.lengths.start() && curr_pairs.iter().any(|(x, y)| x == target && automaton.is_none_or(|z| z.is_accepting(*y))) {
+                return true;
+            }
+            if length == *path.lengths.end() {
+                break;
+            }
+            let mut next_pairs = HashSet::new();
+            for (curr_vertex_id, curr_state) in curr_pairs {
+                for edge in self.graph.edge_list.get(&curr_vertex_id).unwrap().iter_incident_e().filter(|x| path_edge_matches(x, path.constraint)) {
+                    match automaton {
+                        Some(value) => {
+                            let label = (self.label_fn)(self.graph.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap());
+                            next_pairs.extend(value.step(curr_state, &label).map(|x| (edge.id2.clone(), x)));
+                        },
+                        None => {
+                            next_pairs.insert((edge.id2, curr_state));
+                        },
+                    }
+                }
+            }
+            // Once the walk is long enough, revisiting a pair can't give anything new
+            if length + 1 >= *path.lengths.start() {
+                next_pairs.retain(|x| visited_pairs.insert(x.clone()));
+            }
+            if next_pairs.is_empty() {
+                break;
+            }
+            curr_pairs = next_pairs;
+        }
+        false
+    }
+}
+
+// PatternMatches::Iterator
+impl<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Iterator for PatternMatches<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    type Item = Vec<VertexIdType>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.is_started {
+            self.is_started = true;
+            if self.order.is_empty() {
+                return Some(Vec::new());
+            }
+            self.candidates.push((self.next_candidates(), 0));
+        }
+        while let Some((curr_candidates, curr_index)) = self.candidates.last_mut() {
+            if *curr_index == curr_candidates.len() {
+                self.candidates.pop();
+                if let Some(id) = self.assigned_vertices.pop() {
+                    self.used_vertices.remove(&id);
+                }
+                continue;
+            }
+            let id = curr_candidates[*curr_index].clone();
+            *curr_index += 1;
+            if !self.is_feasible(&id) {
+                continue;
+            }
+            if self.assigned_vertices.len() + 1 == self.order.len() {
+                let mut answer = vec![id.clone(); self.order.len()];
+                for (position, assigned_id) in self.assigned_vertices.iter().enumerate() {
+                    answer[self.order[position]] = assigned_id.clone();
+                }
+                return Some(answer);
+            }
+            self.used_vertices.insert(id.clone());
+            self.assigned_vertices.push(id);
+            self.candidates.push((self.next_candidates(), 0));
+        }
+        None
+    }
+}
+
+
+
+/// # Query
+/// 
+/// ## Description
+/// Queries find all occurrences of a [`Pattern`] in the graph, which makes it  possible
+/// to ask questions like 'which users follow each other and both like the  same  page?'
+/// in a declarative manner.
+pub trait Query<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Find occurrences of pattern
+    /// 
+    /// ## Description
+    /// Iterate over all assignments of distinct vertices of the graph to  the  vertices
+    /// of the pattern such that each assigned vertex satisfies  the  predicate  of  its
+    /// pattern vertex and each edge of the pattern corresponds to an edge of the  graph
+    /// that satisfies the direction constraint.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `pattern` : `&Pattern<VertexAttributeCollectionType>` - an immutable reference
+    /// to the pattern.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Box<dyn Iterator<Item = Vec<VertexIdType>>>>` - `Ok(value)` is
+    /// returned if all edges of the pattern connect its existing vertices,  `value`  in
+    /// this case is an iterator over the matches, the `i`-th element of each  match  is
+    /// the ID of the vertex assigned to the pattern  vertex  number  `i`;  `Err(_)`  is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// The pattern doesn't have to be induced: the assigned vertices may  be  connected
+    /// by edges that are absent from the pattern. Each edge of the graph may correspond
+    /// to several edges of the pattern.
+    /// 
+    /// Symmetric patterns match the  same  set  of  vertices  several  times,  e.g.  an
+    /// undirected triangle is reported 6  times,  once  per  each  permutation  of  its
+    /// vertices. Add predicates that tell the vertices apart to avoid this.
+    /// 
+    /// Matches are produced lazily with  backtracking,  vertices  of  the  pattern  are
+    /// processed in the order that keeps each next vertex  connected  to  the  previous
+    /// ones whenever possible. The order of matches is deterministic.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|^k) in the worst case, where k is  the  number  of  vertices  in  the
+    /// pattern, though the predicates and the edges of the pattern  usually  prune  the
+    /// search much earlier.
+    /// 
+    /// Space: O(|V| * k).
+    fn query<'a>(&'a self, pattern: &'a Pattern<'_, EdgeAttributeCollectionType, VertexAttributeCollectionType>) -> CrabNetsResult<Box<dyn Iterator<Item = Vec<VertexIdType>> + 'a>>;
+}
+
+
+
+// Graph::Query
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Query<EdgeAttributeCollectionType, VertexAttributeCollectionType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn query<'a>(&'a self, pattern: &'a Pattern<'_, EdgeAttributeCollectionType, VertexAttributeCollectionType>) -> CrabNetsResult<Box<dyn Iterator<Item = Vec<VertexIdType>> + 'a>> {
+        const FUNCTION_PATH: &str = "Graph::Query::query";
+        if let Some((index1, index2, _)) = pattern.edges.iter().find(|(x, y, _)| *x >= pattern.count_v() || *y >= pattern.count_v()) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "pattern".to_string(), reason: format!("The edge between the pattern vertices {} and {} refers to a vertex that doesn't exist, the pattern has {} vertices.", index1, index2, pattern.count_v()) }));
+        }
+        if let Some(path) = pattern.paths.iter().find(|x| x.index1 >= pattern.count_v() || x.index2 >= pattern.count_v()) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "pattern".to_string(), reason: format!("The path between the pattern vertices {} and {} refers to a vertex that doesn't exist, the pattern has {} vertices.", path.index1, path.index2, pattern.count_v()) }));
+        }
+        let automata = pattern.paths.iter().map(|x| x.labels.as_ref().map(|y| LabelAutomaton::new(y, FUNCTION_PATH)).transpose()).collect::<CrabNetsResult<Vec<_>>>()?;
+        Ok(Box::new(PatternMatches::new(self, pattern, automata)))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn query() {
+        let mut g: graph!(A ===X==> A) = Graph::new();
+        for id in 0..5 {
+            g.add_v(None);
+            g.v_attrs_mut(&id).unwrap().insert("kind".to_string(), Box::new(if id < 3 { "user" } else { "page" }));
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &0, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&0, &3, false, None).unwrap();
+        g.add_e(&1, &3, false, None).unwrap();
+        g.add_e(&2, &4, false, None).unwrap();
+        let is_kind = |kind: &'static str| move |x: &DynamicDispatchAttributeMap<String>| x.get(&"kind".to_string()).and_then(|y| y.downcast::<&str>()) == Some(&kind);
+        let pattern = Pattern::new()
+            .with_v(is_kind("user"))
+            .with_v(is_kind("user"))
+            .with_v(is_kind("page"))
+            .with_e(0, 1, DirectionConstraint::Directed)
+            .with_e(1, 0, DirectionConstraint::Directed)
+            .with_e(0, 2, DirectionConstraint::Undirected)
+            .with_e(2, 1, DirectionConstraint::Undirected);
+        assert_eq!(g.query(&pattern).unwrap().collect::<Vec<_>>(), vec![vec![0, 1, 3], vec![1, 0, 3]]);
+        let pattern = Pattern::new().with_v(|_| true).with_v(|_| true).with_e(0, 1, DirectionConstraint::Directed);
+        assert_eq!(g.query(&pattern).unwrap().collect::<Vec<_>>(), vec![vec![0, 1], vec![1, 0], vec![1, 2]]);
+        let pattern = Pattern::new().with_v(|_| true).with_v(|_| true).with_v(|_| true).with_e(0, 1, DirectionConstraint::Any).with_e(1, 2, DirectionConstraint::Any).with_e(2, 0, DirectionConstraint::Any);
+        assert_eq!(g.query(&pattern).unwrap().count(), 6);
+        assert_eq!(g.query(&Pattern::new()).unwrap().count(), 1);
+        assert!(g.query(&Pattern::new().with_v(|_| true).with_e(0, 1, DirectionConstraint::Any)).is_err());
+    }
+
+    #[test]
+    fn path_query() {
+        let mut g: graph!(A ===A==> A) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        for (id1, id2, kind) in [(0, 1, "knows"), (1, 2, "knows"), (2, 3, "likes"), (0, 4, "likes")] {
+            g.add_e(&id1, &id2, true, None).unwrap();
+            let edge_id = g.iter_e().find(|x| x.id1 == id1 && x.id2 == id2).unwrap().edge_id;
+            g.e_attrs_mut(&id1, &id2, &edge_id).unwrap().insert("type".to_string(), Box::new(kind));
+        }
+        let pattern = || Pattern::new().with_v(|_| true).with_v(|_| true).with_labels(|x: &DynamicDispatchAttributeMap<String>| x.get(&"type".to_string()).unwrap().downcast::<&str>().unwrap().to_string());
+        let matches = |pattern| g.query(&pattern).unwrap().collect::<Vec<_>>();
+        assert_eq!(matches(pattern().with_path(0, 1, DirectionConstraint::Directed, 2..=3, None)), vec![vec![0, 2], vec![0, 3], vec![1, 3]]);
+        assert_eq!(matches(pattern().with_path(0, 1, DirectionConstraint::Directed, 1..=5, Some("knows+ / likes"))), vec![vec![0, 3], vec![1, 3]]);
+        assert_eq!(matches(pattern().with_path(0, 1, DirectionConstraint::Directed, 0..=5, Some("(knows|likes)?"))).len(), 4);
+        assert_eq!(matches(pattern().with_path(0, 1, DirectionConstraint::Any, 1..=1, Some("likes")).with_v(|_| true).with_e(0, 2, DirectionConstraint::Directed)), vec![vec![0, 4, 1]]);
+        assert!(g.query(&pattern().with_path(0, 1, DirectionConstraint::Any, 1..=2, Some("knows/(likes"))).is_err());
+        assert!(g.query(&pattern().with_path(0, 1, DirectionConstraint::Any, 1..=2, Some("knows//likes"))).is_err());
+    }
+}
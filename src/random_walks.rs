@@ -0,0 +1,486 @@
+//! # Random walks module
+//! 
+//! ## Description
+//! This module  contains  [`RandomWalks`]  trait,  its  implementation  for  [`Graph`],
+//! [`RandomWalk`] iterator it produces and [`Node2VecParameters`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::HashSet;
+use itertools::Itertools;
+use rand::Rng;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, rng::CrabNetsRng, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * RANDOM WALK                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// For the given vertex, the vertices one step away and the weights of the edges leading to them
+type StepFn<'a, VertexIdType> = Box<dyn Fn(&VertexIdType) -> Vec<(VertexIdType, f64)> + 'a>;
+
+
+
+/// # Random walk
+/// 
+/// ## Description
+/// An infinite iterator over the vertices visited by a random walk. The  first  yielded
+/// vertex is the start of the walk, each following one is reached from the previous one
+/// by a random traversable edge.
+/// 
+/// If the walk has a restart probability, before each step it jumps back to  its  start
+/// with this probability instead of following an edge. Dead ends, i.e. vertices without
+/// traversable edges, end the walk if the restart probability is 0 and lead back to the
+/// start otherwise.
+/// 
+/// Random walks are created by the functions of  [`RandomWalks`].  The  walk  owns  its
+/// random number generator, pass a seeded one (e.g. [`CrabNetsRng::new(seed)`][rng]) or
+/// a mutable reference to one to get reproducible walks.
+/// 
+/// [rng]: crate::rng::CrabNetsRng::new
+pub struct RandomWalk<'a, R, VertexIdType>
+where
+    R: Rng,
+    VertexIdType: Id,
+{
+    next: Option<VertexIdType>,
+    restart_probability: f64,
+    rng: R,
+    start: VertexIdType,
+    step_fn: StepFn<'a, VertexIdType>,
+}
+
+// RandomWalk::RandomWalk
+impl<'a, R, VertexIdType> RandomWalk<'a, R, VertexIdType>
+where
+    R: Rng,
+    VertexIdType: Id,
+{
+    fn new(start: VertexIdType, rng: R, step_fn: StepFn<'a, VertexIdType>) -> Self {
+        RandomWalk { next: Some(start.clone()), restart_probability: 0f64, rng, start, step_fn }
+    }
+
+    /// # Set restart probability
+    /// 
+    /// ## Description
+    /// Make the walk jump back to its start with  the  given  probability  before  each
+    /// step, as in the random surfer model of PageRank.
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `probability` : `f64` - the probability of the restart.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RandomWalk>` - `Ok(value)` is returned if  `probability`  lies
+    /// in [0, 1], `value` in this case  is  the  updated  walk;  `Err(_)`  is  returned
+    /// otherwise.
+    pub fn with_restart(mut self, probability: f64) -> CrabNetsResult<Self> {
+        const FUNCTION_PATH: &str = "RandomWalk::RandomWalk::with_restart";
+        if !(0f64..=1f64).contains(&probability) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "probability".to_string(), reason: format!("Restart probability must lie in [0, 1], got {}.", probability) }));
+        }
+        self.restart_probability = probability;
+        Ok(self)
+    }
+}
+
+// RandomWalk::Iterator
+impl<'a, R, VertexIdType> Iterator for RandomWalk<'a, R, VertexIdType>
+where
+    R: Rng,
+    VertexIdType: Id,
+{
+    type Item = VertexIdType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let curr_vertex_id = self.next.take()?;
+        let restart = self.restart_probability > 0f64;
+        self.next = if restart && self.rng.gen_bool(self.restart_probability) {
+            Some(self.start.clone())
+        } else {
+            match choose_weighted(&mut self.rng, (self.step_fn)(&curr_vertex_id)) {
+                Some(value) => Some(value),
+                None if restart => Some(self.start.clone()),
+                None => None,
+            }
+        };
+        Some(curr_vertex_id)
+    }
+}
+
+
+
+// Choose one of the candidates with probability proportional to its weight, candidates
+// with non-positive or NaN weights are never chosen
+pub(crate) fn choose_weighted<R, T>(rng: &mut R, candidates: Vec<(T, f64)>) -> Option<T>
+where
+    R: Rng,
+{
+    let total_weight: f64 = candidates.iter().filter(|x| x.1 > 0f64).map(|x| x.1).sum();
+    if !(total_weight > 0f64 && total_weight.is_finite()) {
+        return None;
+    }
+    let mut threshold = rng.gen_range(0f64..total_weight);
+    let mut last = None;
+    for (candidate, weight) in candidates.into_iter().filter(|x| x.1 > 0f64) {
+        if threshold < weight {
+            return Some(candidate);
+        }
+        threshold -= weight;
+        last = Some(candidate);
+    }
+    // Rounding errors may leave a tiny remainder
+    last
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * NODE2VEC                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Node2vec parameters
+/// 
+/// ## Description
+/// Parameters of the biased random walks generated by [`RandomWalks::node2vec_corpus`].
+/// 
+/// ## Fields
+/// * `p` - the return parameter, the larger it is, the  less  likely  the  walk  is  to
+/// immediately go back to the vertex it came from.
+/// * `q` - the in-out parameter, values larger than 1 keep the walk close to the vertex
+/// it came from (breadth-first-like behaviour), values smaller  than  1  push  it  away
+/// (depth-first-like behaviour).
+/// * `threads` - the number of threads that generate the walks, 0 stands for the number
+/// of available CPUs.
+/// * `walk_length` - the maximum number of vertices in each walk.
+/// * `walks_per_vertex` - the number of walks started at each vertex.
+/// 
+/// `Node2VecParameters::default()` corresponds to unbiased walks (`p` =  `q`  =  1)  of
+/// length 80, 10 walks per vertex, on all available CPUs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node2VecParameters {
+    pub p: f64,
+    pub q: f64,
+    pub threads: usize,
+    pub walk_length: usize,
+    pub walks_per_vertex: usize,
+}
+
+// Node2VecParameters::Default
+impl Default for Node2VecParameters {
+    fn default() -> Self {
+        Node2VecParameters { p: 1f64, q: 1f64, threads: 0, walk_length: 80, walks_per_vertex: 10 }
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn node2vec_walk<R>(&self, start: &VertexIdType, parameters: &Node2VecParameters, rng: &mut R) -> Vec<VertexIdType>
+    where
+        R: Rng,
+    {
+        let mut walk = Vec::with_capacity(parameters.walk_length);
+        if parameters.walk_length == 0 {
+            return walk;
+        }
+        walk.push(start.clone());
+        let mut previous: Option<(VertexIdType, HashSet<VertexIdType>)> = None;
+        while walk.len() < parameters.walk_length {
+            let curr_vertex_id = walk.last().unwrap().clone();
+            let candidates = self.iter_traversable_e(&curr_vertex_id, true).map(|x| {
+                let weight = match &previous {
+                    None => 1f64,
+                    Some((previous_id, _)) if x.id2 == *previous_id => 1f64 / parameters.p,
+                    Some((_, previous_adjacent)) if previous_adjacent.contains(&x.id2) => 1f64,
+                    Some(_) => 1f64 / parameters.q,
+                };
+                (x.id2, weight)
+            }).collect();
+            match choose_weighted(rng, candidates) {
+                Some(next_vertex_id) => {
+                    previous = Some((curr_vertex_id.clone(), self.iter_traversable_e(&curr_vertex_id, true).map(|x| x.id2).collect()));
+                    walk.push(next_vertex_id);
+                },
+                None => break,
+            }
+        }
+        walk
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * RANDOM WALKS                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Random walks
+/// 
+/// ## Description
+/// Random walks follow the directions of edges: a directed edge can only  be  traversed
+/// from its source to its target,  an  undirected  one  can  be  traversed  both  ways.
+/// Parallel edges are separate candidates, so in multi-graphs  a  vertex  connected  by
+/// several edges is proportionally more likely to be chosen.
+pub trait RandomWalks<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Node2vec walk corpus
+    /// 
+    /// ## Description
+    /// Generate biased random walks from every vertex of the graph, as needed to  train
+    /// node2vec embeddings [[source](https://doi.org/10.1145/2939672.2939754)].
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `parameters`  :  `&Node2VecParameters`  -  an  immutable  reference   to   the
+    /// parameters of the walks.
+    /// * `seed` : `u64` - the seed of the  random  number  generators,  the  same  seed
+    /// always gives the same corpus regardless of the number of threads.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<Vec<VertexIdType>>>` -  `Ok(value)`  is  returned  if  the
+    /// parameters are valid and the computation wasn't cancelled, `value` in this  case
+    /// is the list of walks, each walk being the list of IDs of the  visited  vertices;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The corpus consists of `walks_per_vertex` rounds, each round contains  one  walk
+    /// per vertex in ascending order of their IDs. Each walk starts  with  its  vertex,
+    /// the first step is uniform. Then, if the walk came to vertex `v` from vertex `t`,
+    /// an edge from `v` to `x` is chosen with weight 1/`p` if `x` is `t`, with weight 1
+    /// if `x` can be reached from `t` by an edge, and with weight  1/`q`  otherwise.  A
+    /// walk ends early if it gets to a dead end.
+    /// 
+    /// Both `p` and `q` must be positive and finite.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · `walks_per_vertex` ·  `walk_length`  ·  deg),  where  deg  is  the
+    /// maximum degree of a vertex.
+    /// 
+    /// Space: O(|V| · `walks_per_vertex` · `walk_length`).
+    fn node2vec_corpus(&self, parameters: &Node2VecParameters, seed: u64, control: &ExecutionControl) -> CrabNetsResult<Vec<Vec<VertexIdType>>>
+    where
+        Self: Sync,
+        VertexIdType: Send + Sync;
+    /// # Uniform random walk
+    /// 
+    /// ## Description
+    /// Start a random walk that chooses each traversable edge with equal probability.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `start` : `&VertexIdType` - an immutable reference to the ID of the start.
+    /// * `rng` : `R` - the random number generator.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RandomWalk<R, VertexIdType>>` -  `Ok(value)`  is  returned  if
+    /// `start` exists,  `value`  in  this  case  is  the  walk;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg) per step, where deg is the degree of the current vertex.
+    /// 
+    /// Space: O(deg).
+    fn random_walk<R>(&self, start: &VertexIdType, rng: R) -> CrabNetsResult<RandomWalk<'_, R, VertexIdType>>
+    where
+        R: Rng;
+    /// # Weighted random walk
+    /// 
+    /// ## Description
+    /// Start a  random  walk  that  chooses  each  traversable  edge  with  probability
+    /// proportional to its weight.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `start` : `&VertexIdType` - an immutable reference to the ID of the start.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `rng` : `R` - the random number generator.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RandomWalk<R, VertexIdType>>` -  `Ok(value)`  is  returned  if
+    /// `start` exists,  `value`  in  this  case  is  the  walk;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Edges with non-positive or NaN weights are never traversed.
+    /// 
+    /// ## Complexity
+    /// Time: O(deg) per step, where deg is the degree of the current vertex.
+    /// 
+    /// Space: O(deg).
+    fn weighted_random_walk<'a, R, WeightFnType>(&'a self, start: &VertexIdType, weight_fn: WeightFnType, rng: R) -> CrabNetsResult<RandomWalk<'a, R, VertexIdType>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64 + 'a;
+}
+
+
+
+// Graph::RandomWalks
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> RandomWalks<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn node2vec_corpus(&self, parameters: &Node2VecParameters, seed: u64, control: &ExecutionControl) -> CrabNetsResult<Vec<Vec<VertexIdType>>>
+    where
+        Self: Sync,
+        VertexIdType: Send + Sync,
+    {
+        const FUNCTION_PATH: &str = "Graph::RandomWalks::node2vec_corpus";
+        if !(parameters.p > 0f64 && parameters.p.is_finite() && parameters.q > 0f64 && parameters.q.is_finite()) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "parameters".to_string(), reason: format!("Parameters p and q must be positive and finite, got p = {} and q = {}.", parameters.p, parameters.q) }));
+        }
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let walk_count = vertices.len() * parameters.walks_per_vertex;
+        let threads = match parameters.threads {
+            0 => std::thread::available_parallelism().map_or(1, |x| x.get()),
+            value => value,
+        };
+        let chunk_size = walk_count.div_ceil(threads).max(1);
+        // Walk number `i` starts at `vertices[i % |V|]` and has its own RNG, so the
+        // corpus doesn't depend on how the walks are distributed among the threads
+        let chunks = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..walk_count).step_by(chunk_size).map(|first| {
+                let vertices = &vertices;
+                scope.spawn(move || -> CrabNetsResult<Vec<Vec<VertexIdType>>> {
+                    let mut walks = Vec::with_capacity(chunk_size);
+                    for i in first..walk_count.min(first + chunk_size) {
+                        control.check(FUNCTION_PATH)?;
+                        let mut rng = CrabNetsRng::new(seed.wrapping_add(i as u64));
+                        walks.push(self.node2vec_walk(&vertices[i % vertices.len()], parameters, &mut rng));
+                    }
+                    Ok(walks)
+                })
+            }).collect();
+            handles.into_iter().map(|x| x.join().unwrap()).collect::<Vec<_>>()
+        });
+        let mut answer = Vec::with_capacity(walk_count);
+        for chunk in chunks {
+            answer.extend(chunk?);
+        }
+        Ok(answer)
+    }
+
+    fn random_walk<R>(&self, start: &VertexIdType, rng: R) -> CrabNetsResult<RandomWalk<'_, R, VertexIdType>>
+    where
+        R: Rng,
+    {
+        const FUNCTION_PATH: &str = "Graph::RandomWalks::random_walk";
+        if !self.edge_list.contains_key(start) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: start.to_string() }));
+        }
+        Ok(RandomWalk::new(start.clone(), rng, Box::new(move |id| self.iter_traversable_e(id, true).map(|x| (x.id2, 1f64)).collect())))
+    }
+
+    fn weighted_random_walk<'a, R, WeightFnType>(&'a self, start: &VertexIdType, weight_fn: WeightFnType, rng: R) -> CrabNetsResult<RandomWalk<'a, R, VertexIdType>>
+    where
+        R: Rng,
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64 + 'a,
+    {
+        const FUNCTION_PATH: &str = "Graph::RandomWalks::weighted_random_walk";
+        if !self.edge_list.contains_key(start) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: start.to_string() }));
+        }
+        Ok(RandomWalk::new(start.clone(), rng, Box::new(move |id| self.iter_traversable_e(id, true).map(|x| {
+            let weight = weight_fn(id, &x.id2, &x.edge_id);
+            (x.id2, weight)
+        }).collect())))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn node2vec_corpus() {
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        for i in 0..5 {
+            g.add_e(&i, &(i + 1), false, None).unwrap();
+        }
+        let control = ExecutionControl::new();
+        let mut parameters = Node2VecParameters { walk_length: 6, walks_per_vertex: 3, threads: 1, ..Default::default() };
+        let corpus = g.node2vec_corpus(&parameters, 42, &control).unwrap();
+        assert_eq!(corpus.len(), 18);
+        assert!(corpus.iter().enumerate().all(|(i, x)| x.len() == 6 && x[0] == i % 6));
+        assert!(corpus.iter().all(|x| x.windows(2).all(|y| y[0].abs_diff(y[1]) == 1)));
+        parameters.threads = 4;
+        assert_eq!(g.node2vec_corpus(&parameters, 42, &control).unwrap(), corpus);
+        // Returning is almost impossible, so walks from the ends go straight through the path
+        parameters.p = 1e9;
+        let corpus = g.node2vec_corpus(&parameters, 0, &control).unwrap();
+        assert_eq!(corpus[0], vec![0, 1, 2, 3, 4, 5]);
+        parameters.q = 0.0;
+        assert!(g.node2vec_corpus(&parameters, 0, &control).is_err());
+        parameters.q = 1.0;
+        control.cancel();
+        assert!(matches!(g.node2vec_corpus(&parameters, 0, &control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+
+    #[test]
+    fn random_walks() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&1, &0, true, None).unwrap();
+        g.add_e(&2, &3, true, None).unwrap();
+        assert!(matches!(g.random_walk(&4, StdRng::seed_from_u64(0)).err().unwrap().kind(), ErrorKind::VertexNotFound { .. }));
+        // Only 0 -> 1 -> 0 -> ... is possible if edges to 2 have zero weight
+        let walk: Vec<_> = g.weighted_random_walk(&0, |_, y, _| if *y == 2 { 0.0 } else { 1.0 }, StdRng::seed_from_u64(0)).unwrap().take(5).collect();
+        assert_eq!(walk, vec![0, 1, 0, 1, 0]);
+        // Vertex 3 is a dead end
+        assert_eq!(g.random_walk(&2, StdRng::seed_from_u64(0)).unwrap().collect::<Vec<_>>(), vec![2, 3]);
+        let walk: Vec<_> = g.random_walk(&2, StdRng::seed_from_u64(0)).unwrap().with_restart(0.5).unwrap().take(20).collect();
+        assert!(walk.len() == 20 && walk.iter().all(|x| *x == 2 || *x == 3));
+        assert!(g.random_walk(&2, StdRng::seed_from_u64(0)).unwrap().with_restart(1.5).is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidArgument { name, .. } if name == "probability")));
+        // The same seed gives the same walk
+        let mut rng = StdRng::seed_from_u64(7);
+        let walk1: Vec<_> = g.random_walk(&0, &mut rng).unwrap().take(10).collect();
+        let walk2: Vec<_> = g.random_walk(&0, StdRng::seed_from_u64(7)).unwrap().take(10).collect();
+        assert_eq!(walk1, walk2);
+    }
+}
@@ -0,0 +1,156 @@
+//! # Random number generation module
+//! 
+//! ## Description
+//! This module contains [`CrabNetsRng`], the seedable random number  generator  of  the
+//! crate.
+//! 
+//! Every randomised feature of  the  crate,  e.g.  null  models,  sampling  of  pivots,
+//! simulations of spreading processes and random  walks,  receives  its  random  number
+//! generator from the caller as a generic  parameter  bounded  by  [`Rng`],  either  as
+//! `rng: &mut R` or, for iterators that outlive the call, as `rng: R`.  Features  never
+//! create generators seeded from the system entropy, so they are reproducible  as  long
+//! as the given generator is: any generator of the `rand`  crate  can  be  passed,  and
+//! [`CrabNetsRng`] is the one seeded from a single `u64`. Features that run in  several
+//! threads take a `seed: u64` instead and seed a [`CrabNetsRng`] per independent  task,
+//! so that their results don't depend on the scheduling of threads.
+use rand::{rngs::StdRng, Error, Rng, RngCore, SeedableRng};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CRABNETS RNG                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # CrabNets random number generator
+/// 
+/// ## Description
+/// A cryptographically strong pseudo-random number generator seeded from a `u64`.
+/// 
+/// ## Details
+/// The generator wraps [`StdRng`], so it gives the same sequence for the same  seed  on
+/// all platforms within a version of the `rand` crate. It  implements  [`RngCore`]  and
+/// [`SeedableRng`] and, hence, [`Rng`], so it can be passed to every randomised feature
+/// of the crate.
+#[derive(Clone, Debug)]
+pub struct CrabNetsRng {
+    rng: StdRng,
+}
+
+// CrabNetsRng::CrabNetsRng
+impl CrabNetsRng {
+    /// # Create generator
+    /// 
+    /// ## Arguments
+    /// * `seed` : `u64` - the seed.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsRng` - the generator, the same seed always gives the same generator.
+    pub fn new(seed: u64) -> Self {
+        CrabNetsRng { rng: StdRng::seed_from_u64(seed) }
+    }
+
+    /// # Fork generator
+    /// 
+    /// ## Description
+    /// Create a new generator seeded from the next number of this one.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsRng` - the new generator.
+    /// 
+    /// ## Details
+    /// Forks give independent streams of numbers to parts of  a  computation,  e.g.  to
+    /// each of several simulations, so that the stream of a part doesn't depend on  how
+    /// many numbers the other parts consume. Forking  is  reproducible:  the  forks  of
+    /// generators created with the same seed coincide.
+    pub fn fork(&mut self) -> Self {
+        CrabNetsRng::new(self.rng.gen())
+    }
+}
+
+// CrabNetsRng::RngCore
+impl RngCore for CrabNetsRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// CrabNetsRng::SeedableRng
+impl SeedableRng for CrabNetsRng {
+    type Seed = <StdRng as SeedableRng>::Seed;
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        CrabNetsRng { rng: StdRng::from_seed(seed) }
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        CrabNetsRng::new(state)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use rand::seq::SliceRandom;
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn crabnets_rng() {
+        let numbers = |mut rng: CrabNetsRng| (0..8).map(|_| rng.gen_range(0..1000)).collect::<Vec<u32>>();
+        assert_eq!(numbers(CrabNetsRng::new(42)), numbers(CrabNetsRng::new(42)));
+        assert_eq!(numbers(CrabNetsRng::new(42)), numbers(CrabNetsRng::seed_from_u64(42)));
+        assert_ne!(numbers(CrabNetsRng::new(42)), numbers(CrabNetsRng::new(43)));
+        let (mut rng1, mut rng2) = (CrabNetsRng::new(7), CrabNetsRng::new(7));
+        let (fork1, fork2) = (rng1.fork(), rng2.fork());
+        assert_eq!(numbers(fork1), numbers(fork2));
+        assert_eq!(numbers(rng1.clone()), numbers(rng2));
+        assert_ne!(numbers(rng1.fork()), numbers(rng1));
+        let mut items: Vec<usize> = (0..20).collect();
+        items.shuffle(&mut CrabNetsRng::new(1));
+        assert_eq!(items.iter().copied().collect::<HashSet<_>>().len(), 20);
+        // Randomised features are reproducible with the same seed
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..10 {
+            g.add_v(None);
+        }
+        for i in 0..10 {
+            g.add_e(&i, &((i + 1) % 10), false, None).unwrap();
+            g.add_e(&i, &((i + 3) % 10), false, None).unwrap();
+        }
+        let edges = |seed| {
+            let mut h = g.clone();
+            h.double_edge_swap(20, &mut CrabNetsRng::new(seed)).unwrap();
+            let mut answer: Vec<_> = h.iter_e().map(|x| (x.id1.min(x.id2), x.id1.max(x.id2))).collect();
+            answer.sort();
+            answer
+        };
+        assert_eq!(edges(5), edges(5));
+    }
+}
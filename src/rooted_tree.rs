@@ -0,0 +1,270 @@
+//! # Rooted tree module
+//! 
+//! ## Description
+//! This module contains [`RootedTree`], the view of  a  tree-shaped  [`Graph`]  hanging
+//! from one of its vertices,  and  [`Graph::rooted_tree`]  that  builds  it.  The  view
+//! answers queries about parents, children, depths and subtree sizes in constant time.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, VecDeque};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, topology_tests::TopologyTests, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Everything the rooted tree knows about a single vertex
+#[derive(Clone, Debug, PartialEq)]
+struct RootedTreeNode<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    children: Vec<VertexIdType>,
+    depth: usize,
+    parent: Option<VertexIdType>,
+    subtree_size: usize,
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ROOTED TREE                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Rooted tree
+/// 
+/// ## Description
+/// Tree with one of its vertices chosen as the root, built by [`Graph::rooted_tree`].
+/// 
+/// ## Details
+/// The parent of each vertex other than the root is its neighbour on the  path  to  the
+/// root, the children of a vertex are all its other neighbours. Directions of edges are
+/// ignored. The rooted tree keeps its own copy of the structure of  the  graph,  so  it
+/// stays valid even if the graph is changed or dropped afterwards.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RootedTree<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    height: usize,
+    nodes: HashMap<VertexIdType, RootedTreeNode<VertexIdType>>,
+    root: VertexIdType,
+}
+
+// RootedTree::RootedTree
+impl<VertexIdType> RootedTree<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    // Look up the node of the given vertex
+    fn node(&self, id: &VertexIdType, function_path: &str) -> CrabNetsResult<&RootedTreeNode<VertexIdType>> {
+        self.nodes.get(id).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() }))
+    }
+
+    /// # Children of vertex
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<&[VertexIdType]>` - `Ok(value)`  is  returned  if  the  vertex
+    /// belongs to the tree, `value` in this case contains the IDs of  its  children  in
+    /// ascending order; `Err(_)` is returned otherwise.
+    pub fn children(&self, id: &VertexIdType) -> CrabNetsResult<&[VertexIdType]> {
+        const FUNCTION_PATH: &str = "RootedTree::RootedTree::children";
+        Ok(&self.node(id, FUNCTION_PATH)?.children)
+    }
+
+    /// # Number of vertices
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of vertices in the tree.
+    pub fn count_v(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// # Depth of vertex
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if the vertex belongs to the
+    /// tree, `value` in this case is the number of edges between  the  vertex  and  the
+    /// root; `Err(_)` is returned otherwise.
+    pub fn depth(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "RootedTree::RootedTree::depth";
+        Ok(self.node(id, FUNCTION_PATH)?.depth)
+    }
+
+    /// # Height of tree
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the largest depth of a vertex of the tree.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// # Parent of vertex
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<&VertexIdType>>` - `Ok(Some(value))` is returned if the
+    /// vertex belongs to the tree and isn't the root, `value` in this case is the ID of
+    /// its parent; `Ok(None)` is returned for the root; `Err(_)`  is  returned  if  the
+    /// vertex doesn't belong to the tree.
+    pub fn parent(&self, id: &VertexIdType) -> CrabNetsResult<Option<&VertexIdType>> {
+        const FUNCTION_PATH: &str = "RootedTree::RootedTree::parent";
+        Ok(self.node(id, FUNCTION_PATH)?.parent.as_ref())
+    }
+
+    /// # Root of tree
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `&VertexIdType` - the ID of the root.
+    pub fn root(&self) -> &VertexIdType {
+        &self.root
+    }
+
+    /// # Size of subtree
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of interest.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if the vertex belongs to the
+    /// tree, `value` in this case is the number of vertices in the subtree hanging from
+    /// it, the vertex itself included; `Err(_)` is returned otherwise.
+    pub fn subtree_size(&self, id: &VertexIdType) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "RootedTree::RootedTree::subtree_size";
+        Ok(self.node(id, FUNCTION_PATH)?.subtree_size)
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Rooted tree
+    /// 
+    /// ## Description
+    /// Hang the tree from the given vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `root` : `&VertexIdType` - an immutable reference to the ID of the root.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<RootedTree<VertexIdType>>` - `Ok(value)` is  returned  if  the
+    /// graph is a tree and contains `root`, `value` in this case is  the  rooted  tree;
+    /// `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The graph is verified with [`TopologyTests::is_tree`] first. Directions of edges
+    /// are ignored.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V|).
+    pub fn rooted_tree(&self, root: &VertexIdType) -> CrabNetsResult<RootedTree<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Graph::rooted_tree";
+        if !self.contains_v(root) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: root.to_string() }));
+        }
+        if !self.is_tree() {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::UnsupportedGraph { reason: String::from("The graph is not a tree.") }));
+        }
+        let mut nodes = HashMap::with_capacity(self.count_v());
+        nodes.insert(root.clone(), RootedTreeNode { children: Vec::new(), depth: 0, parent: None, subtree_size: 1 });
+        // Vertices in breadth-first order, so that parents precede their children
+        let mut order = Vec::with_capacity(self.count_v());
+        let mut unvisited_vertices = VecDeque::from([root.clone()]);
+        while let Some(curr_vertex_id) = unvisited_vertices.pop_front() {
+            let depth = nodes[&curr_vertex_id].depth + 1;
+            let mut children: Vec<VertexIdType> = self.iter_adjacent(&curr_vertex_id)?.filter(|x| !nodes.contains_key(x)).collect();
+            children.sort();
+            children.dedup();
+            for child in children.iter() {
+                nodes.insert(child.clone(), RootedTreeNode { children: Vec::new(), depth, parent: Some(curr_vertex_id.clone()), subtree_size: 1 });
+                unvisited_vertices.push_back(child.clone());
+            }
+            nodes.get_mut(&curr_vertex_id).unwrap().children = children;
+            order.push(curr_vertex_id);
+        }
+        for curr_vertex_id in order.iter().rev() {
+            if let Some(parent) = nodes[curr_vertex_id].parent.clone() {
+                let subtree_size = nodes[curr_vertex_id].subtree_size;
+                nodes.get_mut(&parent).unwrap().subtree_size += subtree_size;
+            }
+        }
+        let height = nodes.values().map(|x| x.depth).max().unwrap();
+        Ok(RootedTree { height, nodes, root: root.clone() })
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn rooted_tree() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&2, &0, false, None).unwrap();
+        g.add_e(&1, &3, false, None).unwrap();
+        g.add_e(&4, &1, true, None).unwrap();
+        g.add_e(&4, &5, false, None).unwrap();
+        let tree = g.rooted_tree(&1).unwrap();
+        assert_eq!((*tree.root(), tree.count_v(), tree.height()), (1, 6, 2));
+        assert_eq!(tree.parent(&1).unwrap(), None);
+        assert_eq!(tree.parent(&5).unwrap(), Some(&4));
+        assert_eq!(tree.children(&1).unwrap(), &[0, 3, 4]);
+        assert_eq!(tree.children(&5).unwrap(), &[] as &[usize]);
+        assert_eq!((0..6).map(|x| tree.depth(&x).unwrap()).collect::<Vec<_>>(), vec![1, 0, 2, 1, 1, 2]);
+        assert_eq!((0..6).map(|x| tree.subtree_size(&x).unwrap()).collect::<Vec<_>>(), vec![2, 6, 1, 1, 2, 1]);
+        assert!(tree.depth(&6).is_err() && g.rooted_tree(&6).is_err());
+        g.add_e(&5, &2, true, None).unwrap();
+        assert!(g.rooted_tree(&1).is_err());
+    }
+}
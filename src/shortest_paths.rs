@@ -0,0 +1,944 @@
+//! # Shortest paths module
+//! 
+//! ## Description
+//! This module contains [`ShortestPaths`] trait and its implementation for [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::{cmp::Ordering, collections::{BinaryHeap, HashMap, HashSet}};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, handles::VertexHandle, matrices::Matrix, path::Path, workspace::Workspace, BasicImmutableGraph, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// The vertex each visited vertex was reached from and the ID of the edge used for that
+type Parents<EdgeIdType, VertexIdType> = HashMap<VertexIdType, (VertexIdType, EdgeIdType)>;
+
+
+
+// Entries of the priority queue in Dijkstra's algorithm, the smallest distance goes first
+pub(crate) struct HeapItem<VertexIdType> {
+    pub(crate) distance: f64,
+    pub(crate) id: VertexIdType,
+}
+
+impl<VertexIdType> PartialEq for HeapItem<VertexIdType> {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl<VertexIdType> Eq for HeapItem<VertexIdType> {}
+
+impl<VertexIdType> PartialOrd for HeapItem<VertexIdType> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<VertexIdType> Ord for HeapItem<VertexIdType> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+
+
+// Arcs of the flow network of Suurballe's algorithm are stored in pairs of an arc and
+// its residual counterpart, so that the counterpart of arc `i` is arc `i ^ 1`, the two
+// arcs of an undirected edge are each other's twins
+struct FlowArc<EdgeIdType, VertexIdType> {
+    capacity: usize,
+    cost: f64,
+    edge: Option<(VertexIdType, VertexIdType, EdgeIdType)>,
+    from: usize,
+    to: usize,
+    twin: Option<usize>,
+}
+
+fn push_arc<EdgeIdType, VertexIdType>(arcs: &mut Vec<FlowArc<EdgeIdType, VertexIdType>>, outgoing: &mut [Vec<usize>], from: usize, to: usize, cost: f64, edge: Option<(VertexIdType, VertexIdType, EdgeIdType)>) -> usize {
+    outgoing[from].push(arcs.len());
+    arcs.push(FlowArc { capacity: 1, cost, edge, from, to, twin: None });
+    outgoing[to].push(arcs.len());
+    arcs.push(FlowArc { capacity: 0, cost: -cost, edge: None, from: to, to: from, twin: None });
+    arcs.len() - 2
+}
+
+
+
+fn join_halves<EdgeIdType, VertexIdType>(meeting_id: VertexIdType, forward_parents: &Parents<EdgeIdType, VertexIdType>, backward_parents: &Parents<EdgeIdType, VertexIdType>) -> Path<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    let mut forward_steps = Vec::new();
+    let mut curr_vertex_id = &meeting_id;
+    while let Some((parent_id, edge_id)) = forward_parents.get(curr_vertex_id) {
+        forward_steps.push((edge_id.clone(), curr_vertex_id.clone()));
+        curr_vertex_id = parent_id;
+    }
+    let mut answer = Path::new(curr_vertex_id.clone());
+    for (edge_id, id) in forward_steps.into_iter().rev() {
+        answer.push(edge_id, id);
+    }
+    curr_vertex_id = &meeting_id;
+    while let Some((parent_id, edge_id)) = backward_parents.get(curr_vertex_id) {
+        answer.push(edge_id.clone(), parent_id.clone());
+        curr_vertex_id = parent_id;
+    }
+    answer
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * SHORTEST PATHS                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Shortest paths
+/// 
+/// ## Description
+/// Functions that look for the shortest paths between vertices of  the  graph.  All  of
+/// them respect the directions of edges: a directed edge can only be traversed from its
+/// source to its target, an undirected edge can be traversed in both directions.
+pub trait ShortestPaths<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Bidirectional breadth-first search
+    /// 
+    /// ## Description
+    /// Find the path with the smallest number of edges from one vertex to another.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `target` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<Path<EdgeIdType, VertexIdType>>>`  -  `Ok(Some(value))`
+    /// is returned if the target is reachable from the source, `value` in this case  is
+    /// the shortest path from `source` to  `target`;  `Ok(None)`  is  returned  if  the
+    /// target is unreachable; `Err(_)` is returned if either of  the  vertices  doesn't
+    /// exist.
+    /// 
+    /// ## Details
+    /// Two searches are run simultaneously:  a  forward  one  from  the  source  and  a
+    /// backward one from the target. At each step, the search with the smaller frontier
+    /// is advanced by one layer, and the process stops as soon as  the  searches  meet.
+    /// For single-pair queries, this explores far  fewer  vertices  than  the  ordinary
+    /// breadth-first search.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn bidirectional_bfs(&self, source: &VertexIdType, target: &VertexIdType) -> CrabNetsResult<Option<Path<EdgeIdType, VertexIdType>>>;
+    /// # Bidirectional Dijkstra's algorithm
+    /// 
+    /// ## Description
+    /// Find the path with the smallest total weight from one vertex to another.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `target` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>`              -
+    /// `Ok(Some(value))` is returned if  the  target  is  reachable  from  the  source,
+    /// `value` in this case is the total weight of  the  shortest  path  and  the  path
+    /// itself from `source` to `target`;  `Ok(None)`  is  returned  if  the  target  is
+    /// unreachable; `Err(_)` is returned if either of the vertices doesn't exist  or  a
+    /// negative or NaN weight is encountered.
+    /// 
+    /// ## Details
+    /// Two instances of Dijkstra's algorithm are run simultaneously: a forward one from
+    /// the source and a backward one from the target. The search stops as soon  as  the
+    /// sum of the smallest tentative distances of both searches is at least the  weight
+    /// of the best path found so far.
+    /// 
+    /// All weights must be non-negative. Among parallel  edges,  the  lightest  one  is
+    /// used.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|V|).
+    fn bidirectional_dijkstra<WeightFnType>(&self, source: &VertexIdType, target: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+    /// # Dijkstra's algorithm in workspace
+    /// 
+    /// ## Description
+    /// Find the smallest total weight of a path from the given vertex to each vertex of
+    /// the graph reusing the buffers of the given workspace.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `workspace`   :   `&mut Workspace`   -   a   mutable    reference    to    the
+    /// [workspace][workspace] to store the results in.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  the  source  exists,  the
+    /// distances can be read from  `workspace`  in  this  case;  `Err(_)`  is  returned
+    /// otherwise or if a negative or NaN weight is encountered.
+    /// 
+    /// ## Details
+    /// Vertices unreachable from the source  are  not  visited.  All  weights  must  be
+    /// non-negative. The workspace doesn't allocate memory once it's large  enough,  so
+    /// this function suits inner loops that run Dijkstra's algorithm many times.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|E|) in addition to the workspace.
+    /// 
+    /// [workspace]: crate::workspace::Workspace
+    fn dijkstra_with<WeightFnType>(&self, source: &VertexIdType, weight_fn: WeightFnType, workspace: &mut Workspace) -> CrabNetsResult<()>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+    /// # Disjoint shortest paths
+    /// 
+    /// ## Description
+    /// Find several paths from one vertex  to  another  that  share  no  edges  (or  no
+    /// vertices) and have the smallest total weight.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// * `target` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// * `k` : `usize` - the largest number of paths to find.
+    /// * `vertex_disjoint` : `bool` - whether the paths must share  no  vertices  other
+    /// than the source and the target, otherwise they must only share no edges.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Vec<(f64, Path<EdgeIdType, VertexIdType>)>>` - `Ok(value)`  is
+    /// returned if both vertices exist and differ, `value` in this case holds the found
+    /// paths with their total weights  in  ascending  order  of  weights;  `Err(_)`  is
+    /// returned otherwise or if a negative or NaN weight is encountered.
+    /// 
+    /// ## Details
+    /// If fewer than `k` disjoint paths exist, all of them are returned, e.g. an  empty
+    /// vector is returned if the target is unreachable. The sum of the weights  of  the
+    /// returned paths is the smallest possible for their number, which is not the  same
+    /// as taking the shortest path and then the shortest path that avoids it, since the
+    /// shortest path may block all other paths.
+    /// 
+    /// Each undirected edge is used by at most one path in one direction.  All  weights
+    /// must be non-negative.
+    /// 
+    /// Uses  Suurballe's  algorithm  [[source](https://doi.org/10.1002/net.3230040204)]
+    /// generalised to `k` paths, i.e. `k` successive shortest augmenting paths  in  the
+    /// residual network with Dijkstra's algorithm on reduced weights.
+    /// 
+    /// ## Complexity
+    /// Time: O(k · (|V| + |E|) log |V|).
+    /// 
+    /// Space: O(|V| + |E|).
+    fn disjoint_shortest_paths<WeightFnType>(&self, source: &VertexIdType, target: &VertexIdType, k: usize, vertex_disjoint: bool, weight_fn: WeightFnType) -> CrabNetsResult<Vec<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+    /// # Girth
+    /// 
+    /// ## Description
+    /// Find the shortest cycle of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Option<(usize, Path<EdgeIdType, VertexIdType>)>` - `Some(value)` is  returned
+    /// if the graph contains a cycle, `value.0` in this case is the number of edges  in
+    /// the shortest cycle and `value.1` is the cycle itself, a  path  that  starts  and
+    /// ends at the same vertex; `None` is returned if the graph is [acyclic][acyclic].
+    /// 
+    /// ## Details
+    /// Cycles follow the same rules as in [`TopologyTests::is_acyclic`][acyclic]: edges
+    /// are traversed according to their directions and no edge  can  be  passed  twice.
+    /// Thus, a self-loop is a cycle of length 1,  2  parallel  undirected  edges  or  2
+    /// directed edges going in opposite ways form a cycle of length 2, while  a  single
+    /// undirected edge passed there and back is not a  cycle.  For  [undirected][kinds]
+    /// simple graphs, the result is the girth in its usual sense.
+    /// 
+    /// For each edge, looks for the shortest path back from its end to its  start  that
+    /// avoids the edge itself with breadth-first search, the search stops as soon as it
+    /// can't improve the shortest cycle found so far.
+    /// 
+    /// ## Complexity
+    /// Time: O(|E| · (|V| + |E|)).
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [acyclic]: crate::topology_tests::TopologyTests::is_acyclic
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn girth(&self) -> Option<(usize, Path<EdgeIdType, VertexIdType>)>;
+    /// # Pairwise distances within subset
+    /// 
+    /// ## Description
+    /// Find the number of edges on the shortest paths between all ordered pairs of  the
+    /// given vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `subset` : `&[VertexIdType]` - IDs of the vertices of interest.
+    /// * `max_hops` : `Option<usize>` - if `Some(value)` is passed, paths of more  than
+    /// `value` edges are not explored.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Matrix>` - `Ok(value)` is returned  if  all  vertices  of  the
+    /// subset exist, `value[(i, j)]` in this case is the distance from  `subset[i]`  to
+    /// `subset[j]`  or  `f64::INFINITY`  if  `subset[j]`  can't  be   reached   (within
+    /// `max_hops` edges); `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// One breadth-first search is run from each vertex of the subset, and it stops  as
+    /// soon as all vertices of the subset are reached or `max_hops`  is  exceeded.  For
+    /// small subsets of large graphs this is far cheaper than computing  the  distances
+    /// between all pairs of vertices, e.g. to build the metric  closure  of  a  set  of
+    /// terminals.
+    /// 
+    /// ## Complexity
+    /// Time: O(k · (|V| + |E|)), where k is the size of the subset.
+    /// 
+    /// Space: O(k² + |V|).
+    fn pairwise_distances(&self, subset: &[VertexIdType], max_hops: Option<usize>) -> CrabNetsResult<Matrix>;
+    /// # Pairwise weighted distances within subset
+    /// 
+    /// ## Description
+    /// Find the smallest total weights of paths between all ordered pairs of the  given
+    /// vertices.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `subset` : `&[VertexIdType]` - IDs of the vertices of interest.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `max_distance` : `Option<f64>` - if `Some(value)`  is  passed,  paths  heavier
+    /// than `value` are not explored.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Matrix>` - `Ok(value)` is returned  if  all  vertices  of  the
+    /// subset exist, `value[(i, j)]` in this case is the distance from  `subset[i]`  to
+    /// `subset[j]`  or  `f64::INFINITY`  if  `subset[j]`  can't  be   reached   (within
+    /// `max_distance`); `Err(_)` is returned otherwise or if a negative or  NaN  weight
+    /// is encountered.
+    /// 
+    /// ## Details
+    /// Works  like  [`ShortestPaths::pairwise_distances`]  with  Dijkstra's   algorithm
+    /// instead of breadth-first search. All weights must be non-negative.
+    /// 
+    /// ## Complexity
+    /// Time: O(k · (|V| + |E|) log |V|), where k is the size of the subset.
+    /// 
+    /// Space: O(k² + |V| + |E|).
+    fn pairwise_weighted_distances<WeightFnType>(&self, subset: &[VertexIdType], weight_fn: WeightFnType, max_distance: Option<f64>) -> CrabNetsResult<Matrix>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn check_endpoints(&self, source: &VertexIdType, target: &VertexIdType, function_path: &str) -> CrabNetsResult<()> {
+        for id in [source, target] {
+            if !self.edge_list.contains_key(id) {
+                return Err(CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() }));
+            }
+        }
+        Ok(())
+    }
+
+    fn subset_handles(&self, subset: &[VertexIdType], function_path: &str) -> CrabNetsResult<Vec<VertexHandle>> {
+        subset.iter().map(|id| self.v_handle(id).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() }))).collect()
+    }
+
+    // Fill the row of the matrix with the distances found in the workspace
+    fn fill_distance_row(matrix: &mut Matrix, row: usize, handles: &[VertexHandle], workspace: &Workspace) {
+        for (column, handle) in handles.iter().enumerate() {
+            matrix[(row, column)] = workspace.distance(*handle).unwrap_or(f64::INFINITY);
+        }
+    }
+}
+
+// Graph::ShortestPaths
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> ShortestPaths<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn bidirectional_bfs(&self, source: &VertexIdType, target: &VertexIdType) -> CrabNetsResult<Option<Path<EdgeIdType, VertexIdType>>> {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::bidirectional_bfs";
+        self.check_endpoints(source, target, FUNCTION_PATH)?;
+        if source == target {
+            return Ok(Some(Path::new(source.clone())));
+        }
+        let mut distances = [HashMap::from([(source.clone(), 0usize)]), HashMap::from([(target.clone(), 0usize)])];
+        let mut parents: [Parents<EdgeIdType, VertexIdType>; 2] = [HashMap::new(), HashMap::new()];
+        let mut frontiers = [vec![source.clone()], vec![target.clone()]];
+        while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+            let side = if frontiers[0].len() <= frontiers[1].len() { 0 } else { 1 };
+            let mut next_frontier = Vec::new();
+            let mut best_meeting: Option<(usize, VertexIdType)> = None;
+            for curr_vertex_id in std::mem::take(&mut frontiers[side]) {
+                let next_distance = distances[side][&curr_vertex_id] + 1;
+                for (adjacent_id, edge_id) in self.iter_traversable_e(&curr_vertex_id, side == 0).map(|x| (x.id2, x.edge_id)) {
+                    if distances[side].contains_key(&adjacent_id) {
+                        continue;
+                    }
+                    distances[side].insert(adjacent_id.clone(), next_distance);
+                    parents[side].insert(adjacent_id.clone(), (curr_vertex_id.clone(), edge_id.clone()));
+                    if let Some(other_distance) = distances[1 - side].get(&adjacent_id) {
+                        if best_meeting.as_ref().is_none_or(|x| next_distance + other_distance < x.0) {
+                            best_meeting = Some((next_distance + other_distance, adjacent_id.clone()));
+                        }
+                    }
+                    next_frontier.push(adjacent_id);
+                }
+            }
+            if let Some((_, meeting_id)) = best_meeting {
+                return Ok(Some(join_halves(meeting_id, &parents[0], &parents[1])));
+            }
+            frontiers[side] = next_frontier;
+        }
+        Ok(None)
+    }
+
+    fn bidirectional_dijkstra<WeightFnType>(&self, source: &VertexIdType, target: &VertexIdType, weight_fn: WeightFnType) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::bidirectional_dijkstra";
+        self.check_endpoints(source, target, FUNCTION_PATH)?;
+        let mut distances = [HashMap::from([(source.clone(), 0f64)]), HashMap::from([(target.clone(), 0f64)])];
+        let mut parents: [Parents<EdgeIdType, VertexIdType>; 2] = [HashMap::new(), HashMap::new()];
+        let mut settled: [HashSet<VertexIdType>; 2] = [HashSet::new(), HashSet::new()];
+        let mut heaps = [BinaryHeap::from([HeapItem { distance: 0f64, id: source.clone() }]), BinaryHeap::from([HeapItem { distance: 0f64, id: target.clone() }])];
+        let mut best: Option<(f64, VertexIdType)> = if source == target { Some((0f64, source.clone())) } else { None };
+        while let (Some(forward_top), Some(backward_top)) = (heaps[0].peek(), heaps[1].peek()) {
+            if best.as_ref().is_some_and(|x| forward_top.distance + backward_top.distance >= x.0) {
+                break;
+            }
+            let side = if forward_top.distance <= backward_top.distance { 0 } else { 1 };
+            let HeapItem { distance, id: curr_vertex_id } = heaps[side].pop().unwrap();
+            if !settled[side].insert(curr_vertex_id.clone()) {
+                continue;
+            }
+            for (adjacent_id, edge_id) in self.iter_traversable_e(&curr_vertex_id, side == 0).map(|x| (x.id2, x.edge_id)) {
+                let weight = if side == 0 { weight_fn(&curr_vertex_id, &adjacent_id, &edge_id) } else { weight_fn(&adjacent_id, &curr_vertex_id, &edge_id) };
+                if weight.is_nan() || weight < 0f64 {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: curr_vertex_id.to_string(), id2: adjacent_id.to_string(), edge_id: edge_id.to_string(), weight }));
+                }
+                let next_distance = distance + weight;
+                if distances[side].get(&adjacent_id).is_none_or(|x| next_distance < *x) {
+                    distances[side].insert(adjacent_id.clone(), next_distance);
+                    parents[side].insert(adjacent_id.clone(), (curr_vertex_id.clone(), edge_id.clone()));
+                    heaps[side].push(HeapItem { distance: next_distance, id: adjacent_id.clone() });
+                    if let Some(other_distance) = distances[1 - side].get(&adjacent_id) {
+                        if best.as_ref().is_none_or(|x| next_distance + other_distance < x.0) {
+                            best = Some((next_distance + other_distance, adjacent_id));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(best.map(|(total_weight, meeting_id)| (total_weight, join_halves(meeting_id, &parents[0], &parents[1]))))
+    }
+
+    fn dijkstra_with<WeightFnType>(&self, source: &VertexIdType, weight_fn: WeightFnType, workspace: &mut Workspace) -> CrabNetsResult<()>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::dijkstra_with";
+        workspace.reset(self.handle_bound());
+        let source_handle = self.v_handle(source).ok_or_else(|| CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: source.to_string() }))?;
+        workspace.visit(source_handle, 0f64);
+        workspace.heap.push(HeapItem { distance: 0f64, id: source_handle });
+        while let Some(HeapItem { distance, id: curr_handle }) = workspace.heap.pop() {
+            // Outdated entries of the heap are skipped instead of being removed
+            if distance > workspace.distance(curr_handle).unwrap() {
+                continue;
+            }
+            let curr_vertex_id = self.v_id(curr_handle).unwrap();
+            for edge in self.iter_traversable_e(curr_vertex_id, true) {
+                let weight = weight_fn(curr_vertex_id, &edge.id2, &edge.edge_id);
+                if weight.is_nan() || weight < 0f64 {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: curr_vertex_id.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                }
+                let next_distance = distance + weight;
+                let adjacent_handle = self.v_handle(&edge.id2).unwrap();
+                if workspace.distance(adjacent_handle).is_none_or(|x| next_distance < x) {
+                    workspace.visit(adjacent_handle, next_distance);
+                    workspace.heap.push(HeapItem { distance: next_distance, id: adjacent_handle });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn disjoint_shortest_paths<WeightFnType>(&self, source: &VertexIdType, target: &VertexIdType, k: usize, vertex_disjoint: bool, weight_fn: WeightFnType) -> CrabNetsResult<Vec<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::disjoint_shortest_paths";
+        self.check_endpoints(source, target, FUNCTION_PATH)?;
+        if source == target {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "target".to_string(), reason: "The source and the target must be different vertices.".to_string() }));
+        }
+        let mut ids: Vec<VertexIdType> = self.iter_v().collect();
+        ids.sort();
+        let indices: HashMap<&VertexIdType, usize> = ids.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        // For vertex-disjoint paths, vertex `i` is entered through node `i` and left
+        // through node `n + i`, the two nodes are joined by an arc of capacity 1
+        let n = ids.len();
+        let out_node = |i: usize| if vertex_disjoint { n + i } else { i };
+        let (source_node, target_node) = (out_node(indices[source]), indices[target]);
+        let mut arcs = Vec::new();
+        let mut outgoing = vec![Vec::new(); if vertex_disjoint { 2 * n } else { n }];
+        if vertex_disjoint {
+            for i in (0..n).filter(|i| *i != indices[source] && *i != indices[target]) {
+                push_arc(&mut arcs, &mut outgoing, i, n + i, 0f64, None);
+            }
+        }
+        for edge in self.iter_e().filter(|x| x.id1 != x.id2) {
+            let traversals = match edge.direction {
+                EdgeDirection::Directed1to2 => vec![(&edge.id1, &edge.id2)],
+                EdgeDirection::Directed2to1 => vec![(&edge.id2, &edge.id1)],
+                EdgeDirection::Undirected => vec![(&edge.id1, &edge.id2), (&edge.id2, &edge.id1)],
+            };
+            let mut added = Vec::with_capacity(2);
+            for (from, to) in traversals {
+                let weight = weight_fn(from, to, &edge.edge_id);
+                if weight.is_nan() || weight < 0f64 {
+                    return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: from.to_string(), id2: to.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                }
+                added.push(push_arc(&mut arcs, &mut outgoing, out_node(indices[from]), indices[to], weight, Some((from.clone(), to.clone(), edge.edge_id.clone()))));
+            }
+            if let [a, b] = added[..] {
+                (arcs[a].twin, arcs[b].twin) = (Some(b), Some(a));
+            }
+        }
+        // Successive shortest augmenting paths, potentials keep reduced weights
+        // non-negative
+        let mut potentials = vec![0f64; outgoing.len()];
+        let mut path_count = 0;
+        while path_count < k {
+            let mut distances = vec![f64::INFINITY; outgoing.len()];
+            let mut parent_arcs = vec![usize::MAX; outgoing.len()];
+            distances[source_node] = 0f64;
+            let mut heap = BinaryHeap::from([HeapItem { distance: 0f64, id: source_node }]);
+            while let Some(HeapItem { distance, id }) = heap.pop() {
+                if distance > distances[id] {
+                    continue;
+                }
+                for &a in outgoing[id].iter().filter(|a| arcs[**a].capacity > 0) {
+                    let to = arcs[a].to;
+                    let next_distance = distance + (arcs[a].cost + potentials[id] - potentials[to]).max(0f64);
+                    if next_distance < distances[to] {
+                        distances[to] = next_distance;
+                        parent_arcs[to] = a;
+                        heap.push(HeapItem { distance: next_distance, id: to });
+                    }
+                }
+            }
+            if distances[target_node].is_infinite() {
+                break;
+            }
+            for (potential, distance) in potentials.iter_mut().zip(distances.iter()) {
+                *potential += distance.min(distances[target_node]);
+            }
+            let mut node = target_node;
+            while node != source_node {
+                let a = parent_arcs[node];
+                arcs[a].capacity -= 1;
+                arcs[a ^ 1].capacity += 1;
+                node = arcs[a].from;
+            }
+            path_count += 1;
+        }
+        // An undirected edge traversed in both directions is not used at all
+        for a in (0..arcs.len()).step_by(2) {
+            if let Some(b) = arcs[a].twin {
+                if arcs[a ^ 1].capacity > 0 && arcs[b ^ 1].capacity > 0 {
+                    for c in [a, b] {
+                        (arcs[c].capacity, arcs[c ^ 1].capacity) = (1, 0);
+                    }
+                }
+            }
+        }
+        // Split the flow into paths, cycles of zero weight are dropped
+        let mut answer = Vec::with_capacity(path_count);
+        for _ in 0..path_count {
+            let mut steps: Vec<usize> = Vec::new();
+            let mut positions = HashMap::from([(source_node, 0)]);
+            let mut node = source_node;
+            while node != target_node {
+                let a = outgoing[node].iter().copied().find(|a| a % 2 == 0 && arcs[a ^ 1].capacity > 0).unwrap();
+                arcs[a ^ 1].capacity -= 1;
+                steps.push(a);
+                node = arcs[a].to;
+                match positions.get(&node) {
+                    Some(&position) => {
+                        for removed in steps.split_off(position) {
+                            positions.remove(&arcs[removed].to);
+                        }
+                        positions.insert(node, position);
+                    },
+                    None => {
+                        positions.insert(node, steps.len());
+                    },
+                }
+            }
+            let mut path = Path::new(source.clone());
+            let mut total_weight = 0f64;
+            for a in steps {
+                if let Some((_, to, edge_id)) = &arcs[a].edge {
+                    path.push(edge_id.clone(), to.clone());
+                    total_weight += arcs[a].cost;
+                }
+            }
+            answer.push((total_weight, path));
+        }
+        answer.sort_by(|x, y| x.0.total_cmp(&y.0));
+        Ok(answer)
+    }
+
+    fn girth(&self) -> Option<(usize, Path<EdgeIdType, VertexIdType>)> {
+        if let Some(edge) = self.iter_e().find(|x| x.id1 == x.id2) {
+            let mut cycle = Path::new(edge.id1);
+            cycle.push(edge.edge_id, edge.id2);
+            return Some((1, cycle));
+        }
+        let mut answer: Option<(usize, Path<EdgeIdType, VertexIdType>)> = None;
+        for edge in self.iter_e().filter(|x| x.direction == EdgeDirection::Directed1to2 || (x.direction == EdgeDirection::Undirected && x.id1 <= x.id2)) {
+            // Breadth-first search from the end of the edge back to its start, only
+            // paths of at most `max_length` edges can give a shorter cycle
+            let max_length = answer.as_ref().map_or(usize::MAX, |x| x.0 - 2);
+            let mut parents: Parents<EdgeIdType, VertexIdType> = HashMap::new();
+            let mut visited_vertices = HashSet::from([edge.id2.clone()]);
+            let mut frontier = vec![edge.id2.clone()];
+            let mut length = 0;
+            let mut is_found = false;
+            while !is_found && !frontier.is_empty() && length < max_length {
+                length += 1;
+                let mut next_frontier = Vec::new();
+                'frontier: for curr_vertex_id in frontier {
+                    for adjacent_edge in self.iter_traversable_e(&curr_vertex_id, true) {
+                        if curr_vertex_id == edge.id2 && adjacent_edge.id2 == edge.id1 && adjacent_edge.edge_id == edge.edge_id {
+                            continue;
+                        }
+                        if visited_vertices.insert(adjacent_edge.id2.clone()) {
+                            parents.insert(adjacent_edge.id2.clone(), (curr_vertex_id.clone(), adjacent_edge.edge_id));
+                            if adjacent_edge.id2 == edge.id1 {
+                                is_found = true;
+                                break 'frontier;
+                            }
+                            next_frontier.push(adjacent_edge.id2);
+                        }
+                    }
+                }
+                frontier = next_frontier;
+            }
+            if is_found {
+                let mut steps = Vec::with_capacity(length);
+                let mut curr_vertex_id = &edge.id1;
+                while let Some((parent_id, edge_id)) = parents.get(curr_vertex_id) {
+                    steps.push((edge_id.clone(), curr_vertex_id.clone()));
+                    curr_vertex_id = parent_id;
+                }
+                let mut cycle = Path::new(edge.id1.clone());
+                cycle.push(edge.edge_id.clone(), edge.id2.clone());
+                for (edge_id, id) in steps.into_iter().rev() {
+                    cycle.push(edge_id, id);
+                }
+                answer = Some((length + 1, cycle));
+            }
+        }
+        answer
+    }
+
+    fn pairwise_distances(&self, subset: &[VertexIdType], max_hops: Option<usize>) -> CrabNetsResult<Matrix> {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::pairwise_distances";
+        let handles = self.subset_handles(subset, FUNCTION_PATH)?;
+        let targets: HashSet<VertexHandle> = handles.iter().copied().collect();
+        let max_hops = max_hops.map_or(f64::INFINITY, |x| x as f64);
+        let mut answer = Matrix::new(handles.len(), handles.len());
+        let mut workspace = Workspace::with_capacity(self.handle_bound());
+        for (row, source_handle) in handles.iter().enumerate() {
+            workspace.reset(self.handle_bound());
+            workspace.visit(*source_handle, 0f64);
+            // The source is reached right away, only the other targets need a search
+            let mut remaining_count = targets.len() - 1;
+            if remaining_count > 0 {
+                workspace.queue.push_back(*source_handle);
+            }
+            'search: while let Some(curr_handle) = workspace.queue.pop_front() {
+                let next_distance = workspace.distance(curr_handle).unwrap() + 1f64;
+                if next_distance > max_hops {
+                    break;
+                }
+                for edge in self.iter_traversable_e(self.v_id(curr_handle).unwrap(), true) {
+                    let adjacent_handle = self.v_handle(&edge.id2).unwrap();
+                    if !workspace.is_visited(adjacent_handle) {
+                        workspace.visit(adjacent_handle, next_distance);
+                        if targets.contains(&adjacent_handle) {
+                            remaining_count -= 1;
+                            if remaining_count == 0 {
+                                break 'search;
+                            }
+                        }
+                        workspace.queue.push_back(adjacent_handle);
+                    }
+                }
+            }
+            Self::fill_distance_row(&mut answer, row, &handles, &workspace);
+        }
+        Ok(answer)
+    }
+
+    fn pairwise_weighted_distances<WeightFnType>(&self, subset: &[VertexIdType], weight_fn: WeightFnType, max_distance: Option<f64>) -> CrabNetsResult<Matrix>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::ShortestPaths::pairwise_weighted_distances";
+        let handles = self.subset_handles(subset, FUNCTION_PATH)?;
+        let targets: HashSet<VertexHandle> = handles.iter().copied().collect();
+        let max_distance = max_distance.unwrap_or(f64::INFINITY);
+        let mut answer = Matrix::new(handles.len(), handles.len());
+        let mut workspace = Workspace::with_capacity(self.handle_bound());
+        for (row, source_handle) in handles.iter().enumerate() {
+            workspace.reset(self.handle_bound());
+            workspace.visit(*source_handle, 0f64);
+            workspace.heap.push(HeapItem { distance: 0f64, id: *source_handle });
+            let mut remaining_count = targets.len();
+            while let Some(HeapItem { distance, id: curr_handle }) = workspace.heap.pop() {
+                if distance > workspace.distance(curr_handle).unwrap() {
+                    continue;
+                }
+                // Distances of the targets are final once they leave the heap
+                if targets.contains(&curr_handle) {
+                    remaining_count -= 1;
+                    if remaining_count == 0 {
+                        break;
+                    }
+                }
+                let curr_vertex_id = self.v_id(curr_handle).unwrap();
+                for edge in self.iter_traversable_e(curr_vertex_id, true) {
+                    let weight = weight_fn(curr_vertex_id, &edge.id2, &edge.edge_id);
+                    if weight.is_nan() || weight < 0f64 {
+                        return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidWeight { id1: curr_vertex_id.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                    }
+                    let next_distance = distance + weight;
+                    let adjacent_handle = self.v_handle(&edge.id2).unwrap();
+                    if next_distance <= max_distance && workspace.distance(adjacent_handle).is_none_or(|x| next_distance < x) {
+                        workspace.visit(adjacent_handle, next_distance);
+                        workspace.heap.push(HeapItem { distance: next_distance, id: adjacent_handle });
+                    }
+                }
+            }
+            Self::fill_distance_row(&mut answer, row, &handles, &workspace);
+        }
+        Ok(answer)
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn bidirectional_bfs() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&2, &3, true, None).unwrap();
+        g.add_e(&0, &4, false, None).unwrap();
+        g.add_e(&4, &3, true, None).unwrap();
+        g.add_e(&3, &5, true, None).unwrap();
+        assert_eq!(g.bidirectional_bfs(&0, &5).unwrap().unwrap().vertices().cloned().collect::<Vec<_>>(), vec![0, 4, 3, 5]);
+        assert_eq!(g.bidirectional_bfs(&4, &0).unwrap().unwrap().vertices().cloned().collect::<Vec<_>>(), vec![4, 0]);
+        assert_eq!(g.bidirectional_bfs(&2, &2).unwrap(), Some(Path::new(2)));
+        assert_eq!(g.bidirectional_bfs(&5, &0).unwrap(), None);
+        assert_eq!(g.bidirectional_bfs(&0, &6).unwrap(), None);
+        assert!(g.bidirectional_bfs(&0, &9).is_err());
+    }
+
+    #[test]
+    fn bidirectional_dijkstra() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        let weights = std::collections::HashMap::from([((0, 1, 0), 1.0), ((1, 2, 0), 1.0), ((2, 3, 0), 1.0), ((0, 3, 0), 5.0), ((0, 3, 1), 3.5), ((3, 4, 0), 0.5)]);
+        for (id1, id2, _) in weights.keys() {
+            g.add_e(id1, id2, true, None).unwrap();
+        }
+        let weight_fn = |x: &usize, y: &usize, z: &usize| weights[&(*x, *y, *z)];
+        let (total_weight, path) = g.bidirectional_dijkstra(&0, &4, weight_fn).unwrap().unwrap();
+        assert_eq!(total_weight, 3.5);
+        assert_eq!(path.vertices().cloned().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        assert!(path.is_valid_in(&g));
+        assert_eq!(path.total_cost(weight_fn), total_weight);
+        assert_eq!(g.bidirectional_dijkstra(&1, &1, weight_fn).unwrap(), Some((0.0, Path::new(1))));
+        assert_eq!(g.bidirectional_dijkstra(&4, &0, weight_fn).unwrap(), None);
+        assert!(g.bidirectional_dijkstra(&0, &4, |_, _, _| -1.0).is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidWeight { .. })));
+    }
+
+    #[test]
+    fn disjoint_shortest_paths() {
+        // The shortest path 0 - 1 - 2 - 3 blocks both other paths
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        let weights = std::collections::HashMap::from([((0, 1), 1.0), ((1, 2), 1.0), ((2, 3), 1.0), ((0, 2), 2.0), ((1, 3), 2.0)]);
+        for (id1, id2) in weights.keys() {
+            g.add_e(id1, id2, true, None).unwrap();
+        }
+        let weight_fn = |x: &usize, y: &usize, _: &u8| weights.get(&(*x, *y)).or(weights.get(&(*y, *x))).copied().unwrap();
+        for vertex_disjoint in [false, true] {
+            let paths = g.disjoint_shortest_paths(&0, &3, 3, vertex_disjoint, weight_fn).unwrap();
+            let mut vertices = paths.iter().map(|(weight, path)| (*weight, path.vertices().cloned().collect::<Vec<_>>())).collect::<Vec<_>>();
+            vertices.sort_by(|x, y| x.1.cmp(&y.1));
+            assert_eq!(vertices, vec![(3.0, vec![0, 1, 3]), (3.0, vec![0, 2, 3])]);
+            assert!(paths.iter().all(|(weight, path)| path.is_valid_in(&g) && path.total_cost(weight_fn) == *weight));
+        }
+        assert_eq!(g.disjoint_shortest_paths(&3, &0, 2, false, weight_fn).unwrap(), Vec::new());
+        assert!(g.disjoint_shortest_paths(&0, &0, 2, false, weight_fn).is_err_and(|x| matches!(x.kind(), ErrorKind::InvalidArgument { .. })));
+        assert!(g.disjoint_shortest_paths(&0, &9, 2, false, weight_fn).is_err());
+        assert!(g.disjoint_shortest_paths(&0, &3, 2, false, |_, _, _| -1.0).is_err());
+        // The same graph with undirected edges: the second path cancels the edge 1 - 2
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        for (id1, id2) in weights.keys() {
+            g.add_e(id1, id2, false, None).unwrap();
+        }
+        let paths = g.disjoint_shortest_paths(&0, &3, 2, false, weight_fn).unwrap();
+        assert_eq!(paths.iter().map(|(weight, _)| *weight).sum::<f64>(), 6.0);
+        assert!(paths.iter().all(|(_, path)| !path.edges().any(|(x, y, _)| (*x, *y) == (1, 2) || (*x, *y) == (2, 1))));
+        // Two diamonds 0 - {1, 2} - 3 and 3 - {4, 5} - 6 share the cut vertex 3
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (3, 5), (4, 6), (5, 6)] {
+            g.add_e(&id1, &id2, false, None).unwrap();
+        }
+        assert_eq!(g.disjoint_shortest_paths(&0, &6, 3, false, |_, _, _| 1.0).unwrap().len(), 2);
+        assert_eq!(g.disjoint_shortest_paths(&0, &6, 3, true, |_, _, _| 1.0).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn pairwise_distances() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)] {
+            g.add_e(&id1, &id2, true, None).unwrap();
+        }
+        let distances = g.pairwise_distances(&[0, 2, 4], None).unwrap();
+        assert_eq!(distances.row(0), &[0.0, 2.0, f64::INFINITY]);
+        assert_eq!(distances.row(1), &[2.0, 0.0, f64::INFINITY]);
+        assert_eq!(distances.row(2), &[f64::INFINITY, f64::INFINITY, 0.0]);
+        let distances = g.pairwise_distances(&[0, 3, 0], Some(2)).unwrap();
+        assert_eq!(distances.row(0), &[0.0, f64::INFINITY, 0.0]);
+        assert_eq!(distances.row(1), &[1.0, 0.0, 1.0]);
+        assert_eq!(g.pairwise_distances(&[], None).unwrap().shape(), (0, 0));
+        assert!(g.pairwise_distances(&[0, 9], None).is_err());
+        let weight_fn = |id1: &usize, _: &usize, _: &u8| (*id1 + 1) as f64;
+        let distances = g.pairwise_weighted_distances(&[0, 2, 3], weight_fn, None).unwrap();
+        assert_eq!(distances.row(0), &[0.0, 3.0, 6.0]);
+        assert_eq!(distances.row(2), &[4.0, 7.0, 0.0]);
+        let distances = g.pairwise_weighted_distances(&[0, 2, 3], weight_fn, Some(5.0)).unwrap();
+        assert_eq!(distances.row(0), &[0.0, 3.0, f64::INFINITY]);
+        assert!(g.pairwise_weighted_distances(&[0, 2], |_, _, _| f64::NAN, None).is_err());
+    }
+
+    #[test]
+    fn girth() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)] {
+            g.add_e(&id1, &id2, false, None).unwrap();
+        }
+        let check = |g: &graph!(X ===X==> X), length: usize| {
+            let (girth, cycle) = g.girth().unwrap();
+            assert!(girth == length && cycle.len() == length && cycle.source() == cycle.target() && cycle.is_valid_in(g));
+        };
+        check(&g, 5);
+        g.add_e(&2, &5, false, None).unwrap();
+        g.add_e(&0, &5, false, None).unwrap();
+        check(&g, 3);
+        g.add_e(&1, &2, false, None).unwrap();
+        check(&g, 2);
+        g.add_e(&3, &3, false, None).unwrap();
+        check(&g, 1);
+        // Directed edges must be passed in their directions
+        let mut h: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..4 {
+            h.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (1, 2), (0, 2), (2, 3)] {
+            h.add_e(&id1, &id2, true, None).unwrap();
+        }
+        assert_eq!(h.girth(), None);
+        h.add_e(&3, &0, true, None).unwrap();
+        check(&h, 3);
+        h.add_e(&3, &2, false, None).unwrap();
+        check(&h, 2);
+        // A single undirected edge passed there and back is not a cycle
+        let mut f: graph!(X ---X--- X) = Graph::new();
+        for _ in 0..3 {
+            f.add_v(None);
+        }
+        f.add_e(&0, &1, false, None).unwrap();
+        f.add_e(&1, &2, false, None).unwrap();
+        assert_eq!(f.girth(), None);
+    }
+}
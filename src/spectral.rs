@@ -0,0 +1,242 @@
+//! # Spectral module
+//! 
+//! ## Description
+//! This module contains  [`Spectral`]  trait  and  its  implementation  for  [`Graph`].
+//! Spectral methods use the eigenvectors of the  [Laplacian matrix][laplacian]  of  the
+//! graph to place its  vertices  into  a  low-dimensional  space  where  well-connected
+//! vertices are close to each other.
+//! 
+//! [`Graph`]: crate::Graph
+//! [laplacian]: crate::Graph::to_laplacian_matrix
+use std::collections::HashMap;
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, layout::symmetric_eigen, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY FUNCTIONS                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Maximum number of iterations of the k-means algorithm
+const MAX_K_MEANS_ITERATIONS: usize = 300;
+
+
+
+// Eigenvectors of the symmetric normalised Laplacian matrix sorted by their eigenvalues in
+// the ascending order along with the IDs of the vertices of their components, the first
+// non-zero component of each eigenvector is made positive
+fn laplacian_eigenvectors<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>(graph: &Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<(Vec<Vec<f64>>, Vec<VertexIdType>)>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    let (laplacian, ids) = graph.to_laplacian_matrix(true);
+    let (values, vectors) = symmetric_eigen((0..ids.len()).map(|row| laplacian.row(row).to_vec()).collect(), control, function_path)?;
+    let answer = (0..ids.len()).sorted_by(|i, j| values[*i].total_cmp(&values[*j])).map(|k| {
+        let mut vector = vectors.iter().map(|row| row[k]).collect::<Vec<_>>();
+        if vector.iter().find(|x| x.abs() > 1e-9).is_some_and(|x| *x < 0.0) {
+            vector.iter_mut().for_each(|x| *x = -*x);
+        }
+        vector
+    }).collect();
+    Ok((answer, ids))
+}
+
+
+
+// Split the points into k clusters with the k-means algorithm, the initial centres are chosen
+// deterministically by the farthest-point heuristic
+fn k_means(points: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let distance = |x: &[f64], y: &[f64]| x.iter().zip(y).map(|(a, b)| (a - b) * (a - b)).sum::<f64>();
+    let mut centres = vec![points[0].clone()];
+    while centres.len() < k {
+        let farthest = (0..points.len()).max_by(|i, j| {
+            let d = |index: usize| centres.iter().map(|centre| distance(&points[index], centre)).fold(f64::INFINITY, f64::min);
+            d(*i).total_cmp(&d(*j)).then(j.cmp(i))
+        }).unwrap_or(0);
+        centres.push(points[farthest].clone());
+    }
+    let mut answer = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_K_MEANS_ITERATIONS {
+        let labels = points.iter().map(|point| (0..k).min_by(|i, j| distance(point, &centres[*i]).total_cmp(&distance(point, &centres[*j])).then(i.cmp(j))).unwrap_or(0)).collect::<Vec<_>>();
+        if labels == answer {
+            break;
+        }
+        answer = labels;
+        for (label, centre) in centres.iter_mut().enumerate() {
+            let members = points.iter().zip(answer.iter()).filter(|(_, x)| **x == label).map(|(point, _)| point).collect::<Vec<_>>();
+            if !members.is_empty() {
+                for (coordinate, value) in centre.iter_mut().enumerate() {
+                    *value = members.iter().map(|point| point[coordinate]).sum::<f64>() / members.len() as f64;
+                }
+            }
+        }
+    }
+    answer
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * SPECTRAL                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Spectral methods
+/// 
+/// ## Description
+/// Spectral methods treat graphs  as  undirected:  directions  of  edges  are  ignored,
+/// parallel edges strengthen the connection between  their  endpoints,  self-loops  are
+/// ignored. Both methods use the symmetric normalised Laplacian matrix of the graph.
+/// 
+/// Eigenvectors are found by the cyclic Jacobi method, all functions  check  the  given
+/// [`ExecutionControl`] before each sweep of the method and return  an  error  of  kind
+/// [`Cancelled`][cancelled] if the computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait Spectral<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Spectral clustering
+    /// 
+    /// ## Description
+    /// Split the vertices into `k` groups of well-connected vertices with the algorithm
+    /// of Ng, Jordan and Weiss.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `k` : `usize` - the number of groups.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, usize>>` - `Ok(value)`  is  returned  if
+    /// `k` is between  1  and  the  number  of  vertices  and  the  computation  wasn't
+    /// cancelled, `value` in this case maps each vertex to the label of its group  from
+    /// `0` to `k - 1`; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// The rows of the eigenvectors of the `k` smallest eigenvalues are normalised  and
+    /// split into groups with the  k-means  algorithm.  The  result  is  deterministic:
+    /// labels are numbered in the order of the smallest vertex IDs of their groups.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³) per sweep, the number of sweeps is usually below 10.
+    /// 
+    /// Space: O(|V|²).
+    fn spectral_clustering(&self, k: usize, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, usize>>;
+    /// # Spectral embedding
+    /// 
+    /// ## Description
+    /// Compute the Laplacian eigenmap of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `dimensions` : `usize` - the number of coordinates of each vertex.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, Vec<f64>>>` - `Ok(value)` is returned if
+    /// `dimensions` is less than the number of  vertices  and  the  computation  wasn't
+    /// cancelled, `value` in this case maps each vertex to its coordinates; `Err(_)` is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// The coordinates  are  the  eigenvectors  of  the  smallest  eigenvalues  of  the
+    /// generalised  problem  L  y  =  λ  D  y  except  for  the  trivial  constant  one
+    /// [[source](https://doi.org/10.1162/089976603321780317)]. Eigenvectors are defined
+    /// up to their sign, the first vertex in the ascending order of IDs gets a positive
+    /// coordinate on each axis unless it's zero.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|³) per sweep, the number of sweeps is usually below 10.
+    /// 
+    /// Space: O(|V|²).
+    fn spectral_embedding(&self, dimensions: usize, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, Vec<f64>>>;
+}
+
+// Graph::Spectral
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Spectral<VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn spectral_clustering(&self, k: usize, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, usize>> {
+        const FUNCTION_PATH: &str = "Graph::Spectral::spectral_clustering";
+        let vertex_count = self.count_v();
+        if k == 0 || k > vertex_count {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "k".to_string(), reason: format!("The number of groups must be between 1 and the number of vertices {}, {} given.", vertex_count, k) }));
+        }
+        let (vectors, ids) = laplacian_eigenvectors(self, control, FUNCTION_PATH)?;
+        let points = (0..ids.len()).map(|row| {
+            let point = vectors[..k].iter().map(|vector| vector[row]).collect::<Vec<_>>();
+            let norm = point.iter().map(|x| x * x).sum::<f64>().sqrt();
+            match norm > 0.0 {
+                true => point.iter().map(|x| x / norm).collect(),
+                false => point,
+            }
+        }).collect::<Vec<_>>();
+        let mut renumbering = HashMap::new();
+        Ok(ids.into_iter().zip(k_means(&points, k)).map(|(id, label)| {
+            let next_label = renumbering.len();
+            (id, *renumbering.entry(label).or_insert(next_label))
+        }).collect())
+    }
+
+    fn spectral_embedding(&self, dimensions: usize, control: &ExecutionControl) -> CrabNetsResult<HashMap<VertexIdType, Vec<f64>>> {
+        const FUNCTION_PATH: &str = "Graph::Spectral::spectral_embedding";
+        let vertex_count = self.count_v();
+        if dimensions >= vertex_count {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::InvalidArgument { name: "dimensions".to_string(), reason: format!("The number of dimensions must be less than the number of vertices {}, {} given.", vertex_count, dimensions) }));
+        }
+        let (vectors, ids) = laplacian_eigenvectors(self, control, FUNCTION_PATH)?;
+        let (degrees, _) = self.to_laplacian_matrix(false);
+        Ok(ids.into_iter().enumerate().map(|(row, id)| {
+            let scale = match degrees[(row, row)] > 0.0 {
+                true => 1.0 / degrees[(row, row)].sqrt(),
+                false => 0.0,
+            };
+            (id, vectors[1..=dimensions].iter().map(|vector| vector[row] * scale).collect())
+        }).collect())
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn spectral() {
+        // Two triangles joined by a single edge
+        let g: graph!(X ---X--- X) = "V 0\nV 1\nV 2\nV 3\nV 4\nV 5\n\nE 0 1\nE 1 2\nE 2 0\nE 3 4\nE 4 5\nE 5 3\nE 2 3\n".parse().unwrap();
+        let control = ExecutionControl::new();
+        let labels = g.spectral_clustering(2, &control).unwrap();
+        assert_eq!((0..6).map(|id| labels[&id]).collect::<Vec<_>>(), vec![0, 0, 0, 1, 1, 1]);
+        assert!(g.spectral_clustering(0, &control).is_err() && g.spectral_clustering(7, &control).is_err());
+        let embedding = g.spectral_embedding(1, &control).unwrap();
+        // The first component of the eigenvector is positive
+        assert!((0..3).all(|id| embedding[&id][0] > 0.0) && (3..6).all(|id| embedding[&id][0] < 0.0));
+        assert!(g.spectral_embedding(6, &control).is_err());
+    }
+}
@@ -0,0 +1,585 @@
+//! # Testing module
+//! 
+//! ## Description
+//! This  module  contains  [`GraphStrategy`]   that   generates   random   graphs   for
+//! property-based testing and shrinks the graphs that violate the tested property,  and
+//! [`assert_roundtrip`] that checks reading and writing of graphs against golden files.
+//! The module is only available with `testing` feature.
+//! 
+//! The generation and shrinking don't depend on any property-based  testing  framework.
+//! With `quickcheck` feature,  graphs  implement  quickcheck's  `Arbitrary`,  and  with
+//! `proptest` feature, `GraphStrategy::into_proptest` turns a strategy into a  proptest
+//! `Strategy`.     Both      delegate      to      [`GraphStrategy::generate`]      and
+//! [`GraphStrategy::shrink`].
+use std::{env, fs, ops::RangeInclusive, process, sync::atomic::{AtomicUsize, Ordering}};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, io::{SupportedFormats, IO}, BasicImmutableGraph, BasicMutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Kinds of edges
+/// 
+/// ## Description
+/// The kinds of edges a [`GraphStrategy`] generates.
+/// 
+/// ## Variants
+/// * `Directed` - all edges are directed, each direction is equally likely.
+/// * `Mixed` - each edge is undirected or  directed  in  either  direction  with  equal
+/// probabilities.
+/// * `Undirected` - all edges are undirected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EdgeKinds {
+    Directed,
+    Mixed,
+    Undirected,
+}
+
+
+
+/// # Result of a property check
+/// 
+/// ## Description
+/// The result of [`GraphStrategy::check`]: `Ok(())` if all generated graphs have  the
+/// property, or the shrunk counterexample otherwise. The counterexample is  boxed  to
+/// keep the `Err` variant small.
+pub type CheckResult<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> = Result<(), Box<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>>;
+
+
+
+// Makes the names of temporary files unique within the process
+static TEMPORARY_FILES_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn temporary_file_name(format: SupportedFormats) -> String {
+    let file_number = TEMPORARY_FILES_COUNT.fetch_add(1, Ordering::Relaxed);
+    env::temp_dir().join(format!("crabnets_roundtrip_{}_{}.{}", process::id(), file_number, format.extension())).to_string_lossy().into_owned()
+}
+
+fn write_and_load<G>(graph: &G, format: SupportedFormats) -> (Vec<u8>, G)
+where
+    G: IO,
+{
+    let file_name = temporary_file_name(format);
+    graph.into_file(&file_name).unwrap_or_else(|error| panic!("Failed to write the graph into '{}': {}", file_name, error));
+    let contents = fs::read(&file_name).unwrap_or_else(|error| panic!("Failed to read '{}': {}", file_name, error));
+    let loaded_graph = G::from_file(&file_name).unwrap_or_else(|error| panic!("Failed to read the graph from '{}': {}", file_name, error));
+    let _ = fs::remove_file(&file_name);
+    (contents, loaded_graph)
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * GRAPH STRATEGY                                                                    *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Graph strategy
+/// 
+/// ## Description
+/// A description of random graphs: the number of vertices is chosen uniformly from  the
+/// given range, and then each  pair  of  vertices  gets  each  of  its  possible  edges
+/// independently with the given probability, as in the Erdős–Rényi model.
+/// 
+/// Vertices and edges get automatic IDs and  default  attribute  collections.  Parallel
+/// edges and self-loops are only generated if they are enabled, and parallel edges also
+/// require a locale that supports them (e.g. `===` ones in [`graph!`][graph]).
+/// 
+/// [graph]: crate::graph
+#[derive(Clone, Debug)]
+pub struct GraphStrategy {
+    count_v: RangeInclusive<usize>,
+    edge_kinds: EdgeKinds,
+    edge_probability: f64,
+    max_multiplicity: usize,
+    with_self_loops: bool,
+}
+
+// GraphStrategy::GraphStrategy
+impl GraphStrategy {
+    /// # Create graph strategy
+    /// 
+    /// ## Description
+    /// Create a strategy for undirected graphs without parallel edges  and  self-loops,
+    /// where each pair of vertices is adjacent with probability 0.5.
+    /// 
+    /// ## Arguments
+    /// * `count_v` : `RangeInclusive<usize>` - the allowed numbers of vertices.
+    /// 
+    /// ## Returns
+    /// * `GraphStrategy` - the strategy.
+    pub fn new(count_v: RangeInclusive<usize>) -> Self {
+        GraphStrategy { count_v, edge_kinds: EdgeKinds::Undirected, edge_probability: 0.5, max_multiplicity: 1, with_self_loops: false }
+    }
+
+    /// # Set kinds of edges
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `edge_kinds` : `EdgeKinds` - the kinds of generated edges.
+    /// 
+    /// ## Returns
+    /// * `GraphStrategy` - the updated strategy.
+    pub fn with_edge_kinds(mut self, edge_kinds: EdgeKinds) -> Self {
+        self.edge_kinds = edge_kinds;
+        self
+    }
+
+    /// # Set probability of edges
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `edge_probability` : `f64` - the probability of  each  possible  edge,  it  is
+    /// clamped to [0, 1].
+    /// 
+    /// ## Returns
+    /// * `GraphStrategy` - the updated strategy.
+    pub fn with_edge_probability(mut self, edge_probability: f64) -> Self {
+        self.edge_probability = edge_probability.clamp(0f64, 1f64);
+        self
+    }
+
+    /// # Set multiplicity of edges
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `max_multiplicity` : `usize` - the largest number of  parallel  edges  between
+    /// two vertices, each of the parallel edges is generated independently.
+    /// 
+    /// ## Returns
+    /// * `GraphStrategy` - the updated strategy.
+    pub fn with_multiplicity(mut self, max_multiplicity: usize) -> Self {
+        self.max_multiplicity = max_multiplicity;
+        self
+    }
+
+    /// # Enable or disable self-loops
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// * `with_self_loops` : `bool` - if `true`, self-loops may be generated.
+    /// 
+    /// ## Returns
+    /// * `GraphStrategy` - the updated strategy.
+    pub fn with_self_loops(mut self, with_self_loops: bool) -> Self {
+        self.with_self_loops = with_self_loops;
+        self
+    }
+
+    /// # Check property
+    /// 
+    /// ## Description
+    /// Check the property on random graphs and find a small counterexample if it fails.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `cases` : `usize` - the number of graphs to check.
+    /// * `seed` : `u64` - the seed of the random number generator, the same seed  gives
+    /// the same graphs.
+    /// * `property` : `PropertyFnType` - a function that receives a graph  and  returns
+    /// `true` if the graph has the property.
+    /// 
+    /// ## Returns
+    /// * `CheckResult` -  `Ok(())`  is  returned  if  all  graphs  have  the  property;
+    /// `Err(value)` is returned otherwise, where  `value`  is  a  boxed  counterexample
+    /// shrunk as much as possible.
+    /// 
+    /// ## Details
+    /// The first generated graph without the property is replaced by the first  of  its
+    /// [shrunk versions][shrink] that doesn't have the property either until no  shrunk
+    /// version fails.
+    /// 
+    /// [shrink]: GraphStrategy::shrink
+    pub fn check<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType, PropertyFnType>(&self, cases: usize, seed: u64, property: PropertyFnType) -> CheckResult<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+    where
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+        Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>: Clone,
+        PropertyFnType: Fn(&Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>) -> bool,
+    {
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..cases {
+            let mut counterexample = self.generate(&mut rng);
+            if property(&counterexample) {
+                continue;
+            }
+            loop {
+                let smaller_counterexample = self.shrink(&counterexample).find(|x| !property(x));
+                match smaller_counterexample {
+                    Some(value) => counterexample = value,
+                    None => return Err(Box::new(counterexample)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// # Generate graph
+    /// 
+    /// ## Description
+    /// Generate a random graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `rng` : `&mut R` - a mutable reference to the random number generator.
+    /// 
+    /// ## Returns
+    /// * `Graph` - the generated graph.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|² · m), where m is the largest multiplicity of edges.
+    /// 
+    /// Space: O(|V| + |E|).
+    pub fn generate<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType, R>(&self, rng: &mut R) -> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+    where
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+        R: Rng,
+    {
+        let mut answer = Graph::new();
+        let count_v = if self.count_v.is_empty() { 0 } else { rng.gen_range(self.count_v.clone()) };
+        let vertices: Vec<VertexIdType> = (0..count_v).map(|_| answer.add_v(None)).collect();
+        for (i, id1) in vertices.iter().enumerate() {
+            for id2 in vertices[i..].iter().skip(if self.with_self_loops { 0 } else { 1 }) {
+                for _ in 0..self.max_multiplicity {
+                    if !rng.gen_bool(self.edge_probability) {
+                        continue;
+                    }
+                    let is_swapped = rng.gen_bool(0.5);
+                    let is_directed = match self.edge_kinds {
+                        EdgeKinds::Directed => true,
+                        EdgeKinds::Mixed => rng.gen_ratio(2, 3),
+                        EdgeKinds::Undirected => false,
+                    };
+                    let (from, to) = if is_swapped { (id2, id1) } else { (id1, id2) };
+                    answer.add_e(from, to, is_directed, None).unwrap();
+                }
+            }
+        }
+        answer
+    }
+
+    /// # Shrink graph
+    /// 
+    /// ## Description
+    /// Iterate over simpler versions of the given graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `graph` : `&Graph` - an immutable reference to the graph to shrink.
+    /// 
+    /// ## Returns
+    /// * `Box<dyn Iterator<Item = Graph>>` - an iterator over copies of `graph` without
+    /// one of its vertices or one of its edges.
+    /// 
+    /// ## Details
+    /// Vertices are removed first, since this simplifies  the  graph  faster.  Vertices
+    /// aren't removed if the graph would get fewer vertices than the strategy allows.
+    /// 
+    /// Vertices and edges are removed in the order of their IDs, so  shrinking  doesn't
+    /// depend on the order of iteration over the graph and [`GraphStrategy::check`]  is
+    /// reproducible.
+    pub fn shrink<'a, EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>(&self, graph: &'a Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>) -> Box<dyn Iterator<Item = Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>> + 'a>
+    where
+        EdgeAttributeCollectionType: AttributeCollection,
+        EdgeIdType: Id,
+        LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+        VertexAttributeCollectionType: AttributeCollection,
+        VertexIdType: Id,
+        Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>: Clone,
+    {
+        let removable_vertices = if graph.count_v() > *self.count_v.start() { graph.iter_v().sorted().collect() } else { Vec::new() };
+        Box::new(removable_vertices.into_iter()
+            .map(|x| {
+                let mut answer = graph.clone();
+                answer.remove_v(&x);
+                answer
+            })
+            .chain(graph.iter_e().sorted_by(|x, y| (&x.id1, &x.id2, &x.edge_id).cmp(&(&y.id1, &y.id2, &y.edge_id))).map(|x| {
+                let mut answer = graph.clone();
+                answer.remove_e(&x.id1, &x.id2, &x.edge_id).unwrap();
+                answer
+            })))
+    }
+
+    /// # Convert into proptest strategy
+    /// 
+    /// ## Arguments
+    /// * `self` - the caller.
+    /// 
+    /// ## Returns
+    /// * `ProptestGraphStrategy<GraphType>` - a proptest strategy that generates graphs
+    /// of type `GraphType`  with  [`GraphStrategy::generate`]  and  shrinks  them  with
+    /// [`GraphStrategy::shrink`].
+    #[cfg(feature = "proptest")]
+    pub fn into_proptest<GraphType>(self) -> ProptestGraphStrategy<GraphType> {
+        ProptestGraphStrategy { phantom: std::marker::PhantomData, strategy: self }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ADAPTERS                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::quickcheck::Arbitrary
+#[cfg(feature = "quickcheck")]
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> quickcheck::Arbitrary for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection + 'static,
+    EdgeIdType: Id + 'static,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType> + 'static,
+    VertexAttributeCollectionType: AttributeCollection + 'static,
+    VertexIdType: Id + 'static,
+    Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>: Clone,
+{
+    // Graphs on at most `g.size()` vertices from `GraphStrategy::new`, seeded from `g`
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let mut rng = StdRng::seed_from_u64(<u64 as quickcheck::Arbitrary>::arbitrary(g));
+        GraphStrategy::new(0..=g.size()).generate(&mut rng)
+    }
+
+    // The iterator can't borrow the caller, so the shrunk graphs are collected first
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(GraphStrategy::new(0..=self.count_v()).shrink(self).collect_vec().into_iter())
+    }
+}
+
+
+
+/// # Proptest strategy
+/// 
+/// ## Description
+/// A [`GraphStrategy`] that generates graphs of type `GraphType` for  proptest.  It  is
+/// created by [`GraphStrategy::into_proptest`].
+#[cfg(feature = "proptest")]
+#[derive(Clone)]
+pub struct ProptestGraphStrategy<GraphType> {
+    phantom: std::marker::PhantomData<fn() -> GraphType>,
+    strategy: GraphStrategy,
+}
+
+// ProptestGraphStrategy::Debug
+#[cfg(feature = "proptest")]
+impl<GraphType> std::fmt::Debug for ProptestGraphStrategy<GraphType> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProptestGraphStrategy").field("strategy", &self.strategy).finish()
+    }
+}
+
+// ProptestGraphStrategy::proptest::Strategy
+#[cfg(feature = "proptest")]
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> proptest::strategy::Strategy for ProptestGraphStrategy<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+    Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>: Clone,
+{
+    type Tree = ProptestGraphTree<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>;
+    type Value = Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>;
+
+    fn new_tree(&self, runner: &mut proptest::test_runner::TestRunner) -> proptest::strategy::NewTree<Self> {
+        let mut rng = StdRng::seed_from_u64(proptest::prelude::Rng::next_u64(runner.rng()));
+        Ok(ProptestGraphTree { candidates: Vec::new().into_iter(), current: self.strategy.generate(&mut rng), last_failed: None, strategy: self.strategy.clone() })
+    }
+}
+
+
+
+/// # Proptest value tree
+/// 
+/// ## Description
+/// A   graph    generated    by    [`ProptestGraphStrategy`]    together    with    its
+/// [shrunk versions][shrink] that are still to be tried.
+/// 
+/// [shrink]: GraphStrategy::shrink
+#[cfg(feature = "proptest")]
+pub struct ProptestGraphTree<GraphType> {
+    candidates: std::vec::IntoIter<GraphType>,
+    current: GraphType,
+    last_failed: Option<GraphType>,
+    strategy: GraphStrategy,
+}
+
+// ProptestGraphTree::proptest::ValueTree
+#[cfg(feature = "proptest")]
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> proptest::strategy::ValueTree for ProptestGraphTree<Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+    Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>: Clone,
+{
+    type Value = Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>;
+
+    fn current(&self) -> Self::Value {
+        self.current.clone()
+    }
+
+    // The current graph has failed, so the next candidates are its own shrunk versions
+    fn simplify(&mut self) -> bool {
+        let mut candidates = self.strategy.shrink(&self.current).collect_vec().into_iter();
+        match candidates.next() {
+            Some(value) => {
+                self.last_failed = Some(std::mem::replace(&mut self.current, value));
+                self.candidates = candidates;
+                true
+            },
+            None => false,
+        }
+    }
+
+    // The current graph has passed, so the next candidate is its sibling, or the last
+    // failed graph if there are no siblings left
+    fn complicate(&mut self) -> bool {
+        match self.candidates.next() {
+            Some(value) => {
+                self.current = value;
+                true
+            },
+            None => {
+                if let Some(value) = self.last_failed.take() {
+                    self.current = value;
+                }
+                false
+            },
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * ROUND TRIPS                                                                       *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Assert round trip
+/// 
+/// ## Description
+/// Check that a graph survives writing into a file and reading from it.
+/// 
+/// ## Arguments
+/// * `path` : `&str` - the name of the golden file.
+/// * `format` : `SupportedFormats` - the format to check.
+/// 
+/// ## Returns
+/// None.
+/// 
+/// ## Details
+/// The graph is read from the golden file, written into a temporary file in  the  given
+/// format, read back and written once again. The function panics if any of these  steps
+/// fails or if the two written files differ. If the golden file is in the given format,
+/// the function also panics if the first written file differs from the golden  one,  so
+/// such golden files must be in the exact form produced by [`IO::into_file`].
+/// 
+/// Graphs are read and written only through [`IO`], so the function also checks  custom
+/// attribute collections that implement [`AttributeCollectionIO`][attrs].
+/// 
+/// [attrs]: crate::io::AttributeCollectionIO
+pub fn assert_roundtrip<G>(path: &str, format: SupportedFormats)
+where
+    G: IO,
+{
+    let golden_graph = G::from_file(path).unwrap_or_else(|error| panic!("Failed to read the graph from the golden file '{}': {}", path, error));
+    let (first_contents, loaded_graph) = write_and_load(&golden_graph, format);
+    let (second_contents, _) = write_and_load(&loaded_graph, format);
+    assert_eq!(first_contents, second_contents, "The graph from '{}' changed after being written and read back", path);
+    if path.to_lowercase().ends_with(&format!(".{}", format.extension())) {
+        let golden_contents = fs::read(path).unwrap_or_else(|error| panic!("Failed to read the golden file '{}': {}", path, error));
+        assert_eq!(first_contents, golden_contents, "The graph from '{}' was written differently from the golden file", path);
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn graph_strategy() {
+        let strategy = GraphStrategy::new(3..=8).with_edge_kinds(EdgeKinds::Mixed).with_edge_probability(0.7).with_multiplicity(2).with_self_loops(true);
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..20 {
+            let g: graph!(X ===X==> X) = strategy.generate(&mut rng);
+            assert!((3..=8).contains(&g.count_v()));
+            assert!(g.iter_e().count() <= g.count_v() * (g.count_v() + 1));
+        }
+        let g: graph!(X ---X--- X) = GraphStrategy::new(5..=5).with_edge_probability(1f64).generate(&mut rng);
+        assert_eq!(g.iter_e().count(), 10);
+        // Every graph on at least 3 vertices has a vertex of degree at most 1, which is false
+        let property = |x: &graph!(X ---X--- X)| x.iter_v().any(|y| x.v_degree(&y).unwrap() <= 1);
+        let counterexample = GraphStrategy::new(3..=10).check(100, 42, property).unwrap_err();
+        assert!(!property(&counterexample) && GraphStrategy::new(3..=10).shrink(&counterexample).all(|x| property(&x)));
+        let another_counterexample = GraphStrategy::new(3..=10).check(100, 42, property).unwrap_err();
+        assert_eq!(another_counterexample.iter_e().sorted_by_key(|x| (x.id1, x.id2)).map(|x| (x.id1, x.id2)).collect_vec(), counterexample.iter_e().sorted_by_key(|x| (x.id1, x.id2)).map(|x| (x.id1, x.id2)).collect_vec());
+        assert!(GraphStrategy::new(0..=10).check(100, 42, |x: &graph!(X ---X--- X)| x.iter_e().count() <= 45).is_ok());
+    }
+
+    #[cfg(feature = "quickcheck")]
+    #[test]
+    fn quickcheck_adapter() {
+        use quickcheck::{Arbitrary, Gen, QuickCheck};
+        let mut g = Gen::new(8);
+        for _ in 0..20 {
+            let h: graph!(X ---X--- X) = Arbitrary::arbitrary(&mut g);
+            assert!(h.count_v() <= 8);
+            assert_eq!(h.shrink().count(), h.count_v() + h.iter_e().count());
+        }
+        fn handshake(g: graph!(X ---X--- X)) -> bool {
+            g.iter_v().map(|x| g.v_degree(&x).unwrap()).sum::<usize>() == 2 * g.iter_e().count()
+        }
+        QuickCheck::new().gen(Gen::new(10)).tests(50).quickcheck(handshake as fn(graph!(X ---X--- X)) -> bool);
+    }
+
+    #[cfg(feature = "proptest")]
+    #[test]
+    fn proptest_adapter() {
+        use proptest::{prop_assert, test_runner::{TestError, TestRunner}};
+        // Every graph on at least 3 vertices has a vertex of degree at most 1, which is false
+        let property = |x: &graph!(X ---X--- X)| x.iter_v().any(|y| x.v_degree(&y).unwrap() <= 1);
+        let strategy = GraphStrategy::new(3..=10).into_proptest::<graph!(X ---X--- X)>();
+        let result = TestRunner::deterministic().run(&strategy, |x| {
+            prop_assert!(property(&x));
+            Ok(())
+        });
+        match result {
+            Err(TestError::Fail(_, counterexample)) => assert!(!property(&counterexample) && GraphStrategy::new(3..=10).shrink(&counterexample).all(|x| property(&x))),
+            _ => panic!("The property must fail."),
+        }
+    }
+}
@@ -4,10 +4,15 @@
 //! This module contains [`TopologyTests`] trait and its  implementation  for  [`Graph`]
 //! and [`ImmutableGraphContainer`].
 //! 
+//! It also  contains  [`Graph::verify_hints`]  that  uses  these  tests  to  check  the
+//! [hints][hints] given to adaptive algorithms.
+//! 
 //! [`Graph`]: crate::Graph
+//! [`Graph::verify_hints`]: crate::Graph::verify_hints
+//! [hints]: crate::Hints
 //! [`ImmutableGraphContainer`]: crate::ImmutableGraphContainer
 use std::collections::{HashMap, HashSet, VecDeque};
-use crate::{attributes::AttributeCollection, BasicImmutableGraph, Graph, Hints, Id, Locale};
+use crate::{attributes::AttributeCollection, BasicImmutableGraph, EdgeDirection, Graph, Hints, Id, Locale, union_find::UnionFind};
 
 
 
@@ -23,6 +28,66 @@ use crate::{attributes::AttributeCollection, BasicImmutableGraph, Graph, Hints,
 
 
 
+// Strongly connected components of a directed graph over vertices numbered from 0 found
+// by Tarjan's algorithm without recursion: the component of each vertex and the number
+// of components. Components are numbered in reverse topological order, i.e. every edge
+// between different components goes from a larger number to a smaller one
+fn strong_components_of(adjacency: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let mut indices = vec![usize::MAX; adjacency.len()];
+    let mut back_links = vec![0usize; adjacency.len()];
+    let mut is_on_stack = vec![false; adjacency.len()];
+    let mut stack = Vec::new();
+    let mut components = vec![usize::MAX; adjacency.len()];
+    let (mut curr_index, mut component_count) = (0usize, 0usize);
+    for start in 0..adjacency.len() {
+        if indices[start] != usize::MAX {
+            continue;
+        }
+        // Vertices of the current depth-first search branch with their next arcs
+        let mut dfs_stack = vec![(start, 0usize)];
+        indices[start] = curr_index;
+        back_links[start] = curr_index;
+        curr_index += 1;
+        stack.push(start);
+        is_on_stack[start] = true;
+        while let Some(&(v, next_arc)) = dfs_stack.last() {
+            if next_arc < adjacency[v].len() {
+                dfs_stack.last_mut().unwrap().1 += 1;
+                let u = adjacency[v][next_arc];
+                if indices[u] == usize::MAX {
+                    indices[u] = curr_index;
+                    back_links[u] = curr_index;
+                    curr_index += 1;
+                    stack.push(u);
+                    is_on_stack[u] = true;
+                    dfs_stack.push((u, 0));
+                } else if is_on_stack[u] {
+                    back_links[v] = back_links[v].min(indices[u]);
+                }
+                continue;
+            }
+            dfs_stack.pop();
+            if let Some(&(parent, _)) = dfs_stack.last() {
+                back_links[parent] = back_links[parent].min(back_links[v]);
+            }
+            if back_links[v] == indices[v] {
+                loop {
+                    let u = stack.pop().unwrap();
+                    is_on_stack[u] = false;
+                    components[u] = component_count;
+                    if u == v {
+                        break;
+                    }
+                }
+                component_count += 1;
+            }
+        }
+    }
+    (components, component_count)
+}
+
+
+
 
 
 // * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
@@ -37,6 +102,107 @@ use crate::{attributes::AttributeCollection, BasicImmutableGraph, Graph, Hints,
 /// Topology tests are functions that check whether the  graph  has  certain  structural
 /// properties or not. For this reason, all these functions return `bool`.
 pub trait TopologyTests {
+    /// # Check if graph has self-loops
+    /// 
+    /// ## Description
+    /// Check if the given graph contains an edge that  starts  and  ends  at  the  same
+    /// vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph has at least one  self-loop,  `false`
+    /// is returned otherwise.
+    /// 
+    /// ## Details
+    /// Stops at the first self-loop found.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(1).
+    fn has_self_loops(&self) -> bool;
+    /// # Check if graph is acyclic
+    /// 
+    /// ## Description
+    /// Check if the given graph contains no cycles.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the  graph  is  acyclic,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Cycles respect the directions of edges: a directed edge can only be passed  from
+    /// its source to its target, an undirected edge can be passed in  both  directions,
+    /// but no edge can  be  passed  twice.  Thus,  for  [directed][kinds]  graphs  this
+    /// function checks whether the graph is a DAG, and for  [undirected][kinds]  graphs
+    /// it checks whether the graph is a  forest.  Self-loops  and  parallel  undirected
+    /// edges always form cycles.
+    /// 
+    /// For mixed graphs, undirected edges must form a forest and  contracting  each  of
+    /// its trees into a single vertex must give a DAG.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn is_acyclic(&self) -> bool;
+    /// # Check if graph is bipartite
+    /// 
+    /// ## Description
+    /// Check if the vertices of the given graph can be split into 2 parts so that  each
+    /// edge connects vertices from different parts.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph is  bipartite,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Uses breadth-first search to colour the vertices in  2  colours.  Directions  of
+    /// edges are ignored. Graphs with self-loops are never bipartite.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn is_bipartite(&self) -> bool;
+    /// # Check if graph is complete
+    /// 
+    /// ## Description
+    /// Check if every vertex of the given graph can be reached from every other  vertex
+    /// by passing a single edge.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph  is  complete,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Edges respect their directions, so a [directed][kinds] graph is complete only if
+    /// every pair  of  distinct  vertices  is  connected  by  edges  going  both  ways.
+    /// Self-loops and parallel edges are allowed. Graphs with  at  most  1  vertex  are
+    /// always complete.
+    /// 
+    /// Stops at the first vertex that lacks an edge to some other vertex.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn is_complete(&self) -> bool;
     /// # Check if graph is connected
     /// 
     /// ## Description
@@ -70,6 +236,85 @@ pub trait TopologyTests {
     /// 
     /// [kinds]: crate::Graph#different-kinds-of-graphs
     fn is_connected(&self) -> bool;
+    /// # Check if graph is DAG
+    /// 
+    /// ## Description
+    /// Check if all edges of the given graph are directed and  the  graph  contains  no
+    /// directed cycles.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph is a directed acyclic graph,  `false`
+    /// is returned otherwise.
+    /// 
+    /// ## Details
+    /// Stops     at     the     first     undirected     edge     found,      otherwise
+    /// `g.is_dag() == g.is_acyclic()`. Empty graphs and graphs without edges are always
+    /// DAGs.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V|).
+    fn is_dag(&self) -> bool;
+    /// # Check if graph is forest
+    /// 
+    /// ## Description
+    /// Check if the given graph contains no cycles when the directions of its edges are
+    /// ignored.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph is  a  forest,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// In general, `g.is_forest() == true` always implies `g.is_acyclic() == true`.
+    /// 
+    /// If     the      underlying      graph      `g`      is      [undirected][kinds],
+    /// `g.is_forest() == g.is_acyclic()`.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn is_forest(&self) -> bool;
+    /// # Check if graph is regular
+    /// 
+    /// ## Description
+    /// Check if all vertices of the given graph have the same degree.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the  graph  is  regular,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Degrees  are  compared  with  [`v_degree_in`][in],   [`v_degree_out`][out]   and
+    /// [`v_degree_undir`][undir] separately, so a [directed][kinds]  graph  is  regular
+    /// only if all in-degrees are equal and all out-degrees are equal.  Parallel  edges
+    /// are counted separately. Empty graphs are always regular.
+    /// 
+    /// Stops at the first vertex whose degrees differ from those of another vertex.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V|).
+    /// 
+    /// Space: O(1).
+    /// 
+    /// [in]: crate::BasicImmutableGraph::v_degree_in
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    /// [out]: crate::BasicImmutableGraph::v_degree_out
+    /// [undir]: crate::BasicImmutableGraph::v_degree_undir
+    fn is_regular(&self) -> bool;
     /// # Check if graph is strongly connected
     /// 
     /// ## Description
@@ -113,6 +358,66 @@ pub trait TopologyTests {
     /// 
     /// [kinds]: crate::Graph#different-kinds-of-graphs
     fn is_strongly_connected(&self) -> bool;
+    /// # Check if graph is tree
+    /// 
+    /// ## Description
+    /// Check if the given graph is connected and contains no cycles when the directions
+    /// of its edges are ignored.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the  graph  is  a  tree,  `false`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// `g.is_tree() == g.is_forest() && g.is_connected()`, which matches the meaning of
+    /// [`Hints::TREE`][hints]. In particular, empty graphs are always trees.
+    /// 
+    /// Use [`Graph::rooted_tree`] to query parents, children and depths of the vertices
+    /// of a tree.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [hints]: crate::Hints::TREE
+    fn is_tree(&self) -> bool;
+    /// # Check if graph is unilaterally connected
+    /// 
+    /// ## Description
+    /// Check if for every pair of vertices of the given graph at least one of them  can
+    /// be reached from the other.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if the graph is unilaterally connected, `false` is
+    /// returned otherwise.
+    /// 
+    /// ## Details
+    /// Finds the strongly connected components with Tarjan's algorithm and checks  that
+    /// the graph of components has a path through all of them,  i.e.  that  consecutive
+    /// components in topological order are joined by an edge.
+    /// 
+    /// In         general,         `g.is_strongly_connected() == true`          implies
+    /// `g.is_unilaterally_connected() == true`,     which     in      turn      implies
+    /// `g.is_connected() == true`. If the underlying graph `g` is  [undirected][kinds],
+    /// all three are equal. See [`Graph::connectivity`]  for  the  strongest  of  these
+    /// properties the graph has.
+    /// 
+    /// Empty graphs are always unilaterally connected.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn is_unilaterally_connected(&self) -> bool;
 }
 
 
@@ -126,6 +431,76 @@ where
     VertexAttributeCollectionType: AttributeCollection,
     VertexIdType: Id,
 {
+    fn has_self_loops(&self) -> bool {
+        self.iter_e().any(|x| x.id1 == x.id2)
+    }
+
+    fn is_acyclic(&self) -> bool {
+        let mut sets: UnionFind<VertexIdType> = self.iter_v().collect();
+        for edge in self.iter_e().filter(|x| x.direction == EdgeDirection::Undirected && x.id1 <= x.id2) {
+            if !sets.union(&edge.id1, &edge.id2).unwrap() {
+                return false;
+            }
+        }
+        let mut in_degrees: HashMap<VertexIdType, usize> = HashMap::with_capacity(self.count_v());
+        let mut out_edges: HashMap<VertexIdType, Vec<VertexIdType>> = HashMap::with_capacity(self.count_v());
+        for edge in self.iter_e().filter(|x| x.direction == EdgeDirection::Directed1to2) {
+            let source = sets.find(&edge.id1).unwrap();
+            let target = sets.find(&edge.id2).unwrap();
+            if source == target {
+                return false;
+            }
+            *in_degrees.entry(target.clone()).or_default() += 1;
+            out_edges.entry(source).or_default().push(target);
+        }
+        let mut unvisited_vertices = self.iter_v().filter(|x| sets.find(x).unwrap() == *x && !in_degrees.contains_key(x)).collect::<VecDeque<_>>();
+        while let Some(curr_vertex_id) = unvisited_vertices.pop_front() {
+            for target in out_edges.remove(&curr_vertex_id).unwrap_or_default() {
+                let in_degree = in_degrees.get_mut(&target).unwrap();
+                *in_degree -= 1;
+                if *in_degree == 0 {
+                    in_degrees.remove(&target);
+                    unvisited_vertices.push_back(target);
+                }
+            }
+        }
+        in_degrees.is_empty()
+    }
+
+    fn is_bipartite(&self) -> bool {
+        let mut sides = HashMap::with_capacity(self.count_v());
+        for start in self.iter_v() {
+            if sides.contains_key(&start) {
+                continue;
+            }
+            sides.insert(start.clone(), false);
+            let mut unvisited_vertices = VecDeque::from([start]);
+            while let Some(curr_vertex_id) = unvisited_vertices.pop_front() {
+                let curr_side = sides[&curr_vertex_id];
+                for adjacent_id in self.edge_list.get(&curr_vertex_id).unwrap().iter_adjacent() {
+                    match sides.get(&adjacent_id) {
+                        Some(adjacent_side) => if *adjacent_side == curr_side {
+                            return false;
+                        },
+                        None => {
+                            sides.insert(adjacent_id.clone(), !curr_side);
+                            unvisited_vertices.push_back(adjacent_id);
+                        },
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        let vertex_count = self.count_v();
+        self.iter_v().all(|id| {
+            let adjacent_ids: HashSet<VertexIdType> = self.iter_adjacent_out(&id).unwrap().chain(self.iter_adjacent_undir(&id).unwrap()).filter(|x| *x != id).collect();
+            adjacent_ids.len() + 1 == vertex_count
+        })
+    }
+
     fn is_connected(&self) -> bool {
         let mut unvisited_vertices = VecDeque::from(match self.iter_v().next() {
             Some(value) => [value],
@@ -140,6 +515,30 @@ where
         visited_vertices.len() == self.count_v()
     }
 
+    fn is_dag(&self) -> bool {
+        self.iter_e().all(|x| x.direction != EdgeDirection::Undirected) && self.is_acyclic()
+    }
+
+    fn is_forest(&self) -> bool {
+        let mut sets: UnionFind<VertexIdType> = self.iter_v().collect();
+        for edge in self.iter_e().filter(|x| x.direction == EdgeDirection::Directed1to2 || (x.direction == EdgeDirection::Undirected && x.id1 <= x.id2)) {
+            if !sets.union(&edge.id1, &edge.id2).unwrap() {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn is_regular(&self) -> bool {
+        let degrees_of = |id: &VertexIdType| (self.v_degree_in(id).unwrap(), self.v_degree_out(id).unwrap(), self.v_degree_undir(id).unwrap());
+        let mut vertex_ids = self.iter_v();
+        let first_degrees = match vertex_ids.next() {
+            Some(value) => degrees_of(&value),
+            None => return true,
+        };
+        vertex_ids.all(|x| degrees_of(&x) == first_degrees)
+    }
+
     fn is_strongly_connected(&self) -> bool {
         let mut dfs_stack = VecDeque::from(match self.iter_v().next() {
             Some(value) => [value],
@@ -178,6 +577,189 @@ where
         }
         visited_vertices.len() == self.count_v()
     }
+
+    fn is_tree(&self) -> bool {
+        self.is_forest() && self.is_connected()
+    }
+
+    fn is_unilaterally_connected(&self) -> bool {
+        let mut vertex_ids = self.iter_v().collect::<Vec<_>>();
+        vertex_ids.sort();
+        let indices: HashMap<&VertexIdType, usize> = vertex_ids.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let adjacency: Vec<Vec<usize>> = vertex_ids.iter().map(|id| self.iter_adjacent_out(id).unwrap().chain(self.iter_adjacent_undir(id).unwrap()).map(|x| indices[&x]).collect()).collect();
+        let (components, component_count) = strong_components_of(&adjacency);
+        // Components joined by an edge with the next component in topological order
+        let mut joined_components = HashSet::with_capacity(component_count);
+        for (v, adjacent) in adjacency.iter().enumerate() {
+            for &u in adjacent.iter().filter(|&&u| components[v] == components[u] + 1) {
+                joined_components.insert(components[u]);
+            }
+        }
+        joined_components.len() + 1 >= component_count
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * CONNECTIVITY                                                                      *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Connectivity
+/// 
+/// ## Description
+/// The strongest kind of connectivity a graph has, returned by [`Graph::connectivity`].
+/// 
+/// ## Variants
+/// * `Disconnected` - some vertices can't be  reached  from  each  other  even  if  the
+/// directions of edges are ignored.
+/// * `Weak` - the graph is connected only if the directions of edges are ignored.
+/// * `Unilateral` - for every pair of vertices, at least one of  them  can  be  reached
+/// from the other.
+/// * `Strong` - every vertex can be reached from every other vertex.
+/// 
+/// ## Details
+/// Variants  are  ordered  from  the  weakest  to  the  strongest,  so,  for   example,
+/// `g.connectivity() >= Connectivity::Unilateral` checks whether the graph is at  least
+/// unilaterally connected.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Connectivity {
+    Disconnected,
+    Weak,
+    Unilateral,
+    Strong,
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Classify connectivity
+    /// 
+    /// ## Description
+    /// Find out whether the graph is strongly,  unilaterally  or  weakly  connected  or
+    /// disconnected.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Connectivity` - the strongest kind of connectivity the graph has.
+    /// 
+    /// ## Details
+    /// Checks  strong,  unilateral  and  weak  connectivity  in  this  order  with  the
+    /// respective functions of [`TopologyTests`] and stops at the first one that holds.
+    /// [Undirected][kinds] graphs are either strongly connected or disconnected.  Empty
+    /// graphs are strongly connected.
+    /// 
+    /// The weakly  connected  components  of  a  disconnected  graph  are  returned  by
+    /// [`Traversal::weakly_connected_components`][components].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| log |V| + |E|).
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [components]: crate::traversal::Traversal::weakly_connected_components
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    pub fn connectivity(&self) -> Connectivity {
+        if self.is_strongly_connected() {
+            Connectivity::Strong
+        } else if self.is_unilaterally_connected() {
+            Connectivity::Unilateral
+        } else if self.is_connected() {
+            Connectivity::Weak
+        } else {
+            Connectivity::Disconnected
+        }
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * HINTS                                                                             *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Verify hints
+    /// 
+    /// ## Description
+    /// Find out which of the given [hints][hints] can be relied upon.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `given_hints` : `Hints` - the hints given by the user.
+    /// * `that_are_forced` : `bool` -  whether  the  hints  must  be  followed  without
+    /// verification.
+    /// 
+    /// ## Returns
+    /// * `Hints` - `given_hints` without the properties the graph doesn't have.
+    /// 
+    /// ## Details
+    /// Each  property  hinted  at  is  checked  with   the   respective   function   of
+    /// [`TopologyTests`]. If the check fails, only this property is  removed,  so,  for
+    /// example,   `Hints::TREE`   given   for   a   directed   tree   is   reduced   to
+    /// `Hints::FOREST | Hints::CONNECTED`. Planarity and low treewidth are kept without
+    /// any checks.
+    /// 
+    /// If `that_are_forced` is `true`,  `given_hints`  is  returned  as  is.  In  debug
+    /// builds, the hints are verified anyway and this function panics if any of them is
+    /// misleading.
+    /// 
+    /// This   function   is   meant   to   be    called    at    the    beginning    of
+    /// [adaptive algorithms][hints] that  accept  `given_hints`  and  `that_are_forced`
+    /// arguments.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E| * α(|V|)), where α is the inverse Ackermann function.
+    /// 
+    /// Space: O(|V| + |E|).
+    /// 
+    /// [hints]: crate::Hints
+    pub fn verify_hints(&self, given_hints: Hints, that_are_forced: bool) -> Hints {
+        if that_are_forced {
+            debug_assert_eq!(self.verify_hints(given_hints, false), given_hints, "The forced hints are misleading.");
+            return given_hints;
+        }
+        let checks = [
+            (1 << 0, Self::is_acyclic as fn(&Self) -> bool),
+            (1 << 1, Self::is_connected),
+            (1 << 2, Self::is_forest),
+            (1 << 5, Self::is_strongly_connected),
+            (1 << 6, Self::is_bipartite),
+        ];
+        let mut answer = given_hints.bits();
+        for (bit, check) in checks {
+            if answer & bit != 0 && !check(self) {
+                answer &= !bit;
+            }
+        }
+        Hints::from_bits_retain(answer)
+    }
 }
 
 
@@ -189,6 +771,103 @@ mod topology_tests_tests {
     use crate::*;
     use super::*;
 
+    #[test]
+    fn connectivity() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        assert_eq!(g.connectivity(), Connectivity::Strong);
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        assert_eq!(g.connectivity(), Connectivity::Disconnected);
+        // Strongly connected components {0, 1}, {2}, {3, 4}
+        g.add_e(&0, &1, false, None).unwrap();
+        g.add_e(&2, &1, true, None).unwrap();
+        let edge_id = g.add_e(&3, &2, true, None).unwrap();
+        g.add_e(&3, &4, true, None).unwrap();
+        g.add_e(&4, &3, true, None).unwrap();
+        assert!(g.is_unilaterally_connected() && !g.is_strongly_connected());
+        assert_eq!(g.connectivity(), Connectivity::Unilateral);
+        assert!(g.remove_e(&3, &2, &edge_id).unwrap());
+        g.add_e(&3, &1, true, None).unwrap();
+        assert!(!g.is_unilaterally_connected() && g.is_connected());
+        assert_eq!(g.connectivity(), Connectivity::Weak);
+        assert!(Connectivity::Weak < Connectivity::Unilateral);
+        g.add_e(&1, &3, true, None).unwrap();
+        assert_eq!(g.connectivity(), Connectivity::Unilateral);
+        g.add_e(&1, &2, false, None).unwrap();
+        assert_eq!(g.connectivity(), Connectivity::Strong);
+    }
+
+    #[test]
+    fn has_self_loops() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        assert!(!g.has_self_loops());
+        for _ in 0..2 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        assert!(!g.has_self_loops());
+        g.add_e(&1, &1, false, None).unwrap();
+        assert!(g.has_self_loops());
+    }
+
+    #[test]
+    fn is_acyclic() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&3, &2, false, None).unwrap();
+        g.add_e(&0, &3, true, None).unwrap();
+        assert!(g.is_acyclic() && !g.is_forest() && g.is_bipartite());
+        g.add_e(&2, &4, true, None).unwrap();
+        g.add_e(&4, &1, true, None).unwrap();
+        assert!(!g.is_acyclic() && !g.is_bipartite());
+        g.remove_e(&4, &1, &5).unwrap();
+        g.add_e(&4, &4, false, None).unwrap();
+        assert!(!g.is_acyclic() && !g.is_bipartite());
+        let mut h: graph!(X ===X=== X) = Graph::new();
+        for _ in 0..3 {
+            h.add_v(None);
+        }
+        h.add_e(&0, &1, false, None).unwrap();
+        h.add_e(&1, &2, false, None).unwrap();
+        assert!(h.is_acyclic() && h.is_forest() && h.is_bipartite());
+        h.add_e(&2, &1, false, None).unwrap();
+        assert!(!h.is_acyclic() && !h.is_forest() && h.is_bipartite());
+    }
+
+    #[test]
+    fn is_complete() {
+        let mut g: graph!(X ---X--- X) = Graph::new();
+        assert!(g.is_complete() && g.is_regular());
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (0, 2), (0, 3), (1, 2), (1, 3)] {
+            g.add_e(&id1, &id2, false, None).unwrap();
+        }
+        assert!(!g.is_complete() && !g.is_regular());
+        g.add_e(&2, &3, false, None).unwrap();
+        assert!(g.is_complete() && g.is_regular());
+        let mut h: graph!(X ===X==> X) = Graph::new();
+        for _ in 0..3 {
+            h.add_v(None);
+        }
+        for (id1, id2) in [(0, 1), (1, 2), (2, 0)] {
+            h.add_e(&id1, &id2, true, None).unwrap();
+        }
+        assert!(!h.is_complete() && h.is_regular());
+        for (id1, id2) in [(1, 0), (2, 1)] {
+            h.add_e(&id1, &id2, true, None).unwrap();
+        }
+        assert!(!h.is_complete() && !h.is_regular());
+        h.add_e(&0, &2, true, None).unwrap();
+        assert!(h.is_complete() && h.is_regular());
+    }
+
     #[test]
     fn is_connected() {
         let mut g: graph!(X ---X--- X) = Graph::new();
@@ -200,6 +879,27 @@ mod topology_tests_tests {
         assert_eq!(g.is_connected(), true);
     }
 
+    #[test]
+    fn is_dag() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        assert!(g.is_dag());
+        for _ in 0..3 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        assert!(g.is_dag() && !g.is_forest());
+        let edge_id = g.add_e(&2, &0, true, None).unwrap();
+        assert!(!g.is_dag());
+        assert!(g.remove_e(&2, &0, &edge_id).unwrap());
+        let edge_id = g.add_e(&2, &2, false, None).unwrap();
+        assert!(!g.is_dag());
+        assert!(g.remove_e(&2, &2, &edge_id).unwrap());
+        g.add_e(&1, &0, false, None).unwrap();
+        assert!(!g.is_dag());
+    }
+
     #[test]
     fn is_strongly_connected() {
         let mut g: graph!(X ---X--> X) = Graph::new();
@@ -227,4 +927,37 @@ mod topology_tests_tests {
         g.remove_v(&id4);
         assert_eq!(g.is_strongly_connected(), true);
     }
+
+    #[test]
+    fn is_tree() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        assert!(g.is_tree());
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&2, &1, false, None).unwrap();
+        assert!(g.is_forest() && !g.is_tree());
+        g.add_e(&3, &1, true, None).unwrap();
+        assert!(g.is_tree());
+        g.add_e(&3, &0, true, None).unwrap();
+        assert!(g.is_acyclic() && !g.is_forest() && !g.is_tree());
+    }
+
+    #[test]
+    fn verify_hints() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..4 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&2, &3, true, None).unwrap();
+        assert_eq!(g.verify_hints(Hints::TREE | Hints::BIPARTITE, false), Hints::FOREST | Hints::CONNECTED | Hints::BIPARTITE);
+        assert_eq!(g.verify_hints(Hints::PLANAR, false), Hints::PLANAR);
+        assert!(g.verify_hints(Hints::empty(), false).is_empty());
+        g.add_e(&3, &0, true, None).unwrap();
+        assert_eq!(g.verify_hints(Hints::ACYCLIC | Hints::STRONGLY_CONNECTED, false), Hints::CONNECTED | Hints::LOW_TREEWIDTH);
+        assert_eq!(g.verify_hints(Hints::CONNECTED | Hints::PLANAR, true), Hints::CONNECTED | Hints::PLANAR);
+    }
 }
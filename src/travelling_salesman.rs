@@ -0,0 +1,418 @@
+//! # Travelling salesman module
+//! 
+//! ## Description
+//! This  module  contains  [`TravellingSalesman`]  trait  and  its  implementation  for
+//! [`Graph`].
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{BinaryHeap, HashMap};
+use itertools::Itertools;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, execution::ExecutionControl, matching::{WeightedBlossomSearch, NONE}, path::Path, shortest_paths::HeapItem, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * AUXILIARY ITEMS                                                                   *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// For each source, the vertex each other vertex was reached from and the ID of the edge
+// used for that, vertices are given by their numbers
+type ClosureParents<EdgeIdType> = Vec<Vec<Option<(usize, EdgeIdType)>>>;
+
+
+
+// Shortest-path distances between all pairs of vertices numbered by their positions in
+// `vertices`, tours are built over these distances and then expanded into real paths
+struct MetricClosure<EdgeIdType, VertexIdType> {
+    distances: Vec<Vec<f64>>,
+    parents: ClosureParents<EdgeIdType>,
+    vertices: Vec<VertexIdType>,
+}
+
+impl<EdgeIdType, VertexIdType> MetricClosure<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    fn tour_cost(&self, tour: &[usize]) -> f64 {
+        tour.iter().zip(tour.iter().cycle().skip(1)).map(|(&a, &b)| self.distances[a][b]).sum()
+    }
+
+    fn expand(&self, tour: &[usize]) -> Path<EdgeIdType, VertexIdType> {
+        let mut answer = Path::new(self.vertices[tour[0]].clone());
+        if tour.len() == 1 {
+            return answer;
+        }
+        for (&a, &b) in tour.iter().zip(tour.iter().cycle().skip(1)) {
+            let mut steps = Vec::new();
+            let mut curr_vertex = b;
+            while let Some((parent, edge_id)) = &self.parents[a][curr_vertex] {
+                steps.push((edge_id.clone(), self.vertices[curr_vertex].clone()));
+                curr_vertex = *parent;
+            }
+            for (edge_id, id) in steps.into_iter().rev() {
+                answer.push(edge_id, id);
+            }
+        }
+        answer
+    }
+}
+
+
+
+// Improve the tour by reversing its segments while it gets shorter
+fn two_opt(distances: &[Vec<f64>], tour: &mut [usize], control: &ExecutionControl, function_path: &str) -> CrabNetsResult<()> {
+    let n = tour.len();
+    let mut improved = true;
+    while improved {
+        control.check(function_path)?;
+        improved = false;
+        for i in 0..n.saturating_sub(2) {
+            for j in i + 2..n {
+                let (a, b, c, d) = (tour[i], tour[i + 1], tour[j], tour[(j + 1) % n]);
+                if a == d {
+                    continue;
+                }
+                let old_length = distances[a][b] + distances[c][d];
+                // The tolerance prevents endless swaps caused by rounding errors
+                if distances[a][c] + distances[b][d] < old_length - 1e-12 * old_length {
+                    tour[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+
+
+// Visit the vertices of the multi-graph given by its edges in the order of an Eulerian
+// circuit starting at vertex 0, skipping the vertices that were already visited
+fn shortcut_euler_circuit(vertex_count: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut incident_edges = vec![Vec::new(); vertex_count];
+    for (k, &(a, b)) in edges.iter().enumerate() {
+        incident_edges[a].push(k);
+        incident_edges[b].push(k);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut visited = vec![false; vertex_count];
+    let mut answer = Vec::with_capacity(vertex_count);
+    let mut stack = vec![0];
+    // Hierholzer's algorithm, vertices leave the stack in the order of the circuit
+    while let Some(&v) = stack.last() {
+        match incident_edges[v].pop() {
+            Some(k) if !used[k] => {
+                used[k] = true;
+                stack.push(if edges[k].0 == v { edges[k].1 } else { edges[k].0 });
+            },
+            Some(_) => (),
+            None => {
+                stack.pop();
+                if !visited[v] {
+                    visited[v] = true;
+                    answer.push(v);
+                }
+            },
+        }
+    }
+    answer
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * TRAVELLING SALESMAN                                                               *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Travelling salesman
+/// 
+/// ## Description
+/// Heuristics for the symmetric travelling salesman problem: find the  shortest  closed
+/// path that visits every vertex of the graph.
+/// 
+/// All functions ignore the directions of edges. They first compute the metric  closure
+/// of the graph, i.e. the shortest-path distances between all pairs of vertices, so the
+/// graph doesn't have to be complete and its weights don't have to satisfy the triangle
+/// inequality. The tour is built over the closure and then expanded into a path in  the
+/// graph, which therefore may pass through some vertices more than once.
+/// 
+/// All functions check the given [`ExecutionControl`] periodically and return an  error
+/// of kind [`Cancelled`][cancelled] if the computation is cancelled.
+/// 
+/// [cancelled]: crate::errors::ErrorKind::Cancelled
+pub trait TravellingSalesman<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Christofides' tour
+    /// 
+    /// ## Description
+    /// Find a short tour using Christofides' algorithm.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>`              -
+    /// `Ok(Some(value))` is returned if the graph is connected and not  empty,  `value`
+    /// in this case is the total weight of the tour and the tour itself, a closed  path
+    /// starting at the vertex with the smallest ID; `Ok(None)` is returned if the graph
+    /// is disconnected or empty; `Err(_)` is returned if a negative or  NaN  weight  is
+    /// encountered or the computation is cancelled.
+    /// 
+    /// ## Details
+    /// The minimum spanning tree of the closure is complemented with the minimum weight
+    /// perfect matching of its odd-degree vertices, and the  Eulerian  circuit  of  the
+    /// result is shortcut into a tour. The tour is at most 1.5 times  as  long  as  the
+    /// optimal one [[source](https://doi.org/10.1007/s43069-021-00101-z)].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|) log |V| + |V|³).
+    /// 
+    /// Space: O(|V|² + |E|).
+    fn christofides_tour<WeightFnType>(&self, weight_fn: WeightFnType, control: &ExecutionControl) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+    /// # Nearest neighbour tour
+    /// 
+    /// ## Description
+    /// Find a short tour using the nearest  neighbour  heuristic  improved  with  2-opt
+    /// moves.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `weight_fn` : `WeightFnType` -  a  function  that  receives  the  IDs  of  the
+    /// vertices the edge is traversed from and to and the ID of the edge,  and  returns
+    /// the weight of the edge.
+    /// * `control` : `&ExecutionControl` - an  immutable  reference  to  the  execution
+    /// control.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>`              -
+    /// `Ok(Some(value))` is returned if the graph is connected and not  empty,  `value`
+    /// in this case is the total weight of the tour and the tour itself, a closed  path
+    /// starting at the vertex with the smallest ID; `Ok(None)` is returned if the graph
+    /// is disconnected or empty; `Err(_)` is returned if a negative or  NaN  weight  is
+    /// encountered or the computation is cancelled.
+    /// 
+    /// ## Details
+    /// The initial tour starts at the vertex with the smallest ID and  always  goes  to
+    /// the nearest unvisited vertex. Then, while reversing a segment of the tour  makes
+    /// it shorter, such segments are reversed. The result has no guarantee  of  quality
+    /// but is usually better than Christofides' tour in practice.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| · (|V| + |E|) log |V| + k · |V|²), where k is the number  of  passes
+    /// over all segments until no improvement is possible.
+    /// 
+    /// Space: O(|V|² + |E|).
+    fn nearest_neighbour_tour<WeightFnType>(&self, weight_fn: WeightFnType, control: &ExecutionControl) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // `None` is returned if the graph is disconnected or empty
+    fn metric_closure<WeightFnType>(&self, weight_fn: WeightFnType, control: &ExecutionControl, function_path: &str) -> CrabNetsResult<Option<MetricClosure<EdgeIdType, VertexIdType>>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        let vertices: Vec<VertexIdType> = self.iter_v().sorted().collect();
+        let n = vertices.len();
+        if n == 0 {
+            return Ok(None);
+        }
+        let indices: HashMap<&VertexIdType, usize> = vertices.iter().enumerate().map(|(i, x)| (x, i)).collect();
+        let mut adjacency = vec![Vec::new(); n];
+        for (i, id) in vertices.iter().enumerate() {
+            for edge in self.edge_list[id].iter_incident_e().filter(|x| x.id2 != *id) {
+                let weight = weight_fn(id, &edge.id2, &edge.edge_id);
+                if weight.is_nan() || weight < 0f64 {
+                    return Err(CrabNetsError::new(function_path, ErrorKind::InvalidWeight { id1: id.to_string(), id2: edge.id2.to_string(), edge_id: edge.edge_id.to_string(), weight }));
+                }
+                adjacency[i].push((indices[&edge.id2], weight, edge.edge_id));
+            }
+        }
+        let mut distances = Vec::with_capacity(n);
+        let mut parents = Vec::with_capacity(n);
+        for source in 0..n {
+            control.check(function_path)?;
+            let mut source_distances = vec![f64::INFINITY; n];
+            let mut source_parents = vec![None; n];
+            let mut heap = BinaryHeap::from([HeapItem { distance: 0f64, id: source }]);
+            source_distances[source] = 0f64;
+            while let Some(HeapItem { distance, id: curr_vertex }) = heap.pop() {
+                if distance > source_distances[curr_vertex] {
+                    continue;
+                }
+                for (adjacent_vertex, weight, edge_id) in adjacency[curr_vertex].iter() {
+                    let next_distance = distance + weight;
+                    if next_distance < source_distances[*adjacent_vertex] {
+                        source_distances[*adjacent_vertex] = next_distance;
+                        source_parents[*adjacent_vertex] = Some((curr_vertex, edge_id.clone()));
+                        heap.push(HeapItem { distance: next_distance, id: *adjacent_vertex });
+                    }
+                }
+            }
+            if source_distances.iter().any(|x| x.is_infinite()) {
+                return Ok(None);
+            }
+            distances.push(source_distances);
+            parents.push(source_parents);
+        }
+        Ok(Some(MetricClosure { distances, parents, vertices }))
+    }
+}
+
+// Graph::TravellingSalesman
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> TravellingSalesman<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn christofides_tour<WeightFnType>(&self, weight_fn: WeightFnType, control: &ExecutionControl) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::TravellingSalesman::christofides_tour";
+        let closure = match self.metric_closure(weight_fn, control, FUNCTION_PATH)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let distances = &closure.distances;
+        let n = distances.len();
+        // Prim's algorithm on the complete graph
+        let mut in_tree = vec![false; n];
+        let mut keys = vec![f64::INFINITY; n];
+        let mut tree_parents = vec![NONE; n];
+        let mut circuit_edges = Vec::with_capacity(3 * n / 2);
+        keys[0] = 0f64;
+        for _ in 0..n {
+            let v = (0..n).filter(|&x| !in_tree[x]).min_by(|&x, &y| keys[x].total_cmp(&keys[y])).unwrap();
+            in_tree[v] = true;
+            if tree_parents[v] != NONE {
+                circuit_edges.push((tree_parents[v], v));
+            }
+            for w in (0..n).filter(|&x| !in_tree[x]) {
+                if distances[v][w] < keys[w] {
+                    keys[w] = distances[v][w];
+                    tree_parents[w] = v;
+                }
+            }
+        }
+        control.check(FUNCTION_PATH)?;
+        // Minimum weight perfect matching of odd-degree vertices as the maximum weight
+        // matching with complemented weights, large enough to force perfection
+        let mut degrees = vec![0usize; n];
+        for &(a, b) in circuit_edges.iter() {
+            degrees[a] += 1;
+            degrees[b] += 1;
+        }
+        let odd_vertices: Vec<usize> = (0..n).filter(|&x| degrees[x] % 2 == 1).collect();
+        let max_distance = odd_vertices.iter().cartesian_product(odd_vertices.iter()).map(|(&a, &b)| distances[a][b]).fold(0f64, f64::max);
+        let offset = (odd_vertices.len() / 2 + 1) as f64 * max_distance + 1f64;
+        let matching_edges = (0..odd_vertices.len()).tuple_combinations().map(|(i, j)| (i, j, offset - distances[odd_vertices[i]][odd_vertices[j]])).collect();
+        let mate = WeightedBlossomSearch::new(odd_vertices.len(), matching_edges).run();
+        circuit_edges.extend((0..odd_vertices.len()).filter(|&i| i < mate[i] && mate[i] != NONE).map(|i| (odd_vertices[i], odd_vertices[mate[i]])));
+        control.check(FUNCTION_PATH)?;
+        let tour = shortcut_euler_circuit(n, &circuit_edges);
+        Ok(Some((closure.tour_cost(&tour), closure.expand(&tour))))
+    }
+
+    fn nearest_neighbour_tour<WeightFnType>(&self, weight_fn: WeightFnType, control: &ExecutionControl) -> CrabNetsResult<Option<(f64, Path<EdgeIdType, VertexIdType>)>>
+    where
+        WeightFnType: Fn(&VertexIdType, &VertexIdType, &EdgeIdType) -> f64,
+    {
+        const FUNCTION_PATH: &str = "Graph::TravellingSalesman::nearest_neighbour_tour";
+        let closure = match self.metric_closure(weight_fn, control, FUNCTION_PATH)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let distances = &closure.distances;
+        let n = distances.len();
+        let mut visited = vec![false; n];
+        let mut tour = Vec::with_capacity(n);
+        let mut curr_vertex = 0;
+        for _ in 0..n {
+            visited[curr_vertex] = true;
+            tour.push(curr_vertex);
+            if let Some(next_vertex) = (0..n).filter(|&x| !visited[x]).min_by(|&x, &y| distances[curr_vertex][x].total_cmp(&distances[curr_vertex][y])) {
+                curr_vertex = next_vertex;
+            }
+        }
+        two_opt(distances, &mut tour, control, FUNCTION_PATH)?;
+        // 2-opt may move the first vertex
+        let start = tour.iter().position(|&x| x == 0).unwrap();
+        tour.rotate_left(start);
+        Ok(Some((closure.tour_cost(&tour), closure.expand(&tour))))
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::*;
+    use crate::execution::ExecutionControl;
+    use super::*;
+
+    #[test]
+    fn travelling_salesman() {
+        // Square 0-1-2-3 with short sides and long diagonals, and vertex 4 hanging off 2
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        let mut weights = HashMap::from([((0, 1), 1.0), ((1, 2), 1.0), ((2, 3), 1.0), ((3, 0), 1.0), ((0, 2), 3.0), ((1, 3), 3.0), ((2, 4), 0.5)]);
+        for ((x, y), weight) in weights.clone() {
+            g.add_e(&x, &y, x == 3, None).unwrap();
+            weights.insert((y, x), weight);
+        }
+        let weight_fn = |x: &usize, y: &usize, _: &u8| weights[&(*x, *y)];
+        let control = ExecutionControl::new();
+        for (total_weight, tour) in [g.christofides_tour(weight_fn, &control).unwrap().unwrap(), g.nearest_neighbour_tour(weight_fn, &control).unwrap().unwrap()] {
+            assert!(total_weight <= 1.5 * 5.0);
+            assert_eq!(tour.total_cost(weight_fn), total_weight);
+            assert_eq!(tour.source(), &0);
+            assert_eq!(tour.target(), &0);
+            assert_eq!(tour.vertices().unique().count(), 5);
+        }
+        assert_eq!(g.nearest_neighbour_tour(weight_fn, &control).unwrap().unwrap().0, 5.0);
+        g.add_v(None);
+        assert_eq!(g.christofides_tour(weight_fn, &control).unwrap(), None);
+        assert!(g.nearest_neighbour_tour(|_, _, _| -1.0, &control).is_err());
+        control.cancel();
+        assert!(matches!(g.christofides_tour(weight_fn, &control).unwrap_err().kind(), ErrorKind::Cancelled));
+    }
+}
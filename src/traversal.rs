@@ -0,0 +1,675 @@
+//! # Traversal module
+//! 
+//! ## Description
+//! This module contains [`Traversal`] trait and its  implementation  for  [`Graph`]  as
+//! well as [`Visitor`] trait used to customise traversals.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::{HashMap, HashSet, VecDeque};
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, workspace::Workspace, BasicImmutableGraph, BasicMutableGraph, EdgeDirection, EdgeIteratorItem, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * VISITORS                                                                          *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Order of traversal
+/// 
+/// ## Description
+/// The order in which [`Traversal::traverse`] discovers vertices.
+/// 
+/// ## Variants
+/// * `BreadthFirst` - vertices are discovered in the order of their distance  from  the
+/// start.
+/// * `DepthFirst` - each discovered vertex is explored  completely  before  the  search
+/// backtracks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraversalOrder {
+    BreadthFirst,
+    DepthFirst,
+}
+
+
+
+/// # Control flow of traversal
+/// 
+/// ## Description
+/// The decision returned by the methods of [`Visitor`].
+/// 
+/// ## Variants
+/// * `Continue` - proceed normally.
+/// * `Prune` - don't go any further from the current vertex or along the current  edge.
+/// The rest of the graph is traversed as usual.
+/// * `Stop` - terminate the traversal immediately.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VisitControl {
+    Continue,
+    Prune,
+    Stop,
+}
+
+
+
+/// # Visitor
+/// 
+/// ## Description
+/// Visitors receive events from [`Traversal::traverse`] and decide  how  the  traversal
+/// proceeds. All methods do nothing and return [`VisitControl::Continue`]  by  default,
+/// so implementors only override the events they need.
+pub trait Visitor<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Vertex discovered
+    /// 
+    /// ## Description
+    /// This function is called when the vertex is reached for the first time.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of the vertex.
+    /// 
+    /// ## Returns
+    /// * `VisitControl` - `Prune` prevents the traversal from leaving the  vertex,  its
+    /// edges are not examined and the vertex is finished immediately.
+    fn discover_v(&mut self, _id: &VertexIdType) -> VisitControl {
+        VisitControl::Continue
+    }
+    /// # Edge examined
+    /// 
+    /// ## Description
+    /// This function is called for each edge that can  be  traversed  from  the  vertex
+    /// being explored, `edge.id1` is the ID of this vertex and `edge.id2` is the ID  of
+    /// the vertex the edge leads to.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `edge`  :   `&EdgeIteratorItem<EdgeIdType, VertexIdType>`   -   an   immutable
+    /// reference to the edge.
+    /// 
+    /// ## Returns
+    /// * `VisitControl` - `Prune` prevents the traversal from following the edge.
+    fn examine_e(&mut self, _edge: &EdgeIteratorItem<EdgeIdType, VertexIdType>) -> VisitControl {
+        VisitControl::Continue
+    }
+    /// # Vertex finished
+    /// 
+    /// ## Description
+    /// This function is called when all edges of the vertex have been examined (and, in
+    /// the depth-first order, all vertices discovered through them have been finished).
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID of the vertex.
+    /// 
+    /// ## Returns
+    /// * `VisitControl` - `Prune` has the same effect as `Continue`.
+    fn finish_v(&mut self, _id: &VertexIdType) -> VisitControl {
+        VisitControl::Continue
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * TRAVERSAL                                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Traversal
+/// 
+/// ## Description
+/// Traversal functions explore the graph starting from the given  vertices  and  report
+/// which vertices can be reached and how far they are. They serve  as  building  blocks
+/// for influence and coverage analyses.
+/// 
+/// All these functions respect the directions of edges: a directed  edge  can  only  be
+/// traversed from its source to its target, an undirected edge can be traversed in both
+/// directions.
+pub trait Traversal<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Id,
+    VertexIdType: Id,
+{
+    /// # Apply function to each connected component
+    /// 
+    /// ## Description
+    /// Call the given function for the subgraph  induced  by  each  (weakly)  connected
+    /// component of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `component_fn` : `ComponentFnType` - a function  that  receives  an  immutable
+    /// reference to the subgraph induced by a component.
+    /// 
+    /// ## Details
+    /// Each subgraph is a graph of the  same  type  as  the  caller,  it  contains  the
+    /// vertices of a single component together with  their  attributes  and  all  edges
+    /// between them with their IDs, directions and attributes. Subgraphs are built  one
+    /// at a time right before they are passed to `component_fn` and are dropped as soon
+    /// as it returns, so at most one of them exists at any moment.
+    /// 
+    /// Edge directions are ignored  when  components  are  determined.  Components  are
+    /// processed in the ascending order of their smallest vertex IDs.
+    /// 
+    /// This function makes it easy to apply algorithms that assume  connectivity  (e.g.
+    /// eccentricities or spanning trees) to graphs that consist of several pieces.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| * log(|V|) + |E|) plus the time spent by `component_fn`.
+    /// 
+    /// Space: O(|V| + |E|).
+    fn for_each_component<ComponentFnType>(&self, component_fn: ComponentFnType)
+    where
+        ComponentFnType: FnMut(&Self);
+    /// # Multi-source breadth-first search
+    /// 
+    /// ## Description
+    /// Find the distance from the nearest of the given sources to each  vertex  of  the
+    /// graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `sources` : `&[VertexIdType]` - IDs of the vertices to start the search from.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashMap<VertexIdType, usize>>` - `Ok(value)`  is  returned  if
+    /// all sources exist, `value` in this case maps each vertex reachable from at least
+    /// one source to the number of edges on the shortest path from the  nearest  source
+    /// to it; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Sources themselves are at distance 0. Vertices that can't be  reached  from  any
+    /// source are absent from the returned map.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn multi_source_bfs(&self, sources: &[VertexIdType]) -> CrabNetsResult<HashMap<VertexIdType, usize>>;
+    /// # Multi-source breadth-first search in workspace
+    /// 
+    /// ## Description
+    /// Find the distance from the nearest of the given sources to each  vertex  of  the
+    /// graph reusing the buffers of the given workspace.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `sources` : `&[VertexIdType]` - IDs of the vertices to start the search from.
+    /// * `workspace`   :   `&mut Workspace`   -   a   mutable    reference    to    the
+    /// [workspace][workspace] to store the results in.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<()>`  -  `Ok(())`  is  returned  if  all  sources  exist,  the
+    /// distances can be read from  `workspace`  in  this  case;  `Err(_)`  is  returned
+    /// otherwise.
+    /// 
+    /// ## Details
+    /// Works like [`Traversal::multi_source_bfs`] but doesn't allocate memory once  the
+    /// workspace is large enough, which pays off when many searches are run in  a  row.
+    /// The distances are kept by [vertex handles][handles].
+    /// 
+    /// ## Complexity
+    /// Time: O(number of reached vertices and their incident edges).
+    /// 
+    /// Space: O(1) in addition to the workspace.
+    /// 
+    /// [handles]: crate::handles::VertexHandle
+    /// [workspace]: Workspace
+    fn multi_source_bfs_with(&self, sources: &[VertexIdType], workspace: &mut Workspace) -> CrabNetsResult<()>;
+    /// # Vertices reachable from the given vertex
+    /// 
+    /// ## Description
+    /// Find all vertices that can be reached from the given vertex.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `source` : `&VertexIdType` - an immutable reference to the ID of the source.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashSet<VertexIdType>>`  -  `Ok(value)`  is  returned  if  the
+    /// vertex with the given ID exists, `value` in this case is the set of all vertices
+    /// reachable from it (including the source itself); `Err(_)` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn reachable_from(&self, source: &VertexIdType) -> CrabNetsResult<HashSet<VertexIdType>>;
+    /// # Vertices that reach the given vertex
+    /// 
+    /// ## Description
+    /// Find all vertices from which the given vertex can be reached.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `target` : `&VertexIdType` - an immutable reference to the ID of the target.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<HashSet<VertexIdType>>`  -  `Ok(value)`  is  returned  if  the
+    /// vertex with the given ID exists, `value` in this case is the set of all vertices
+    /// that reach it (including the target itself); `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// For    [undirected][kinds]    graphs,    `g.reaches(&id)`    is     equal     to
+    /// `g.reachable_from(&id)`.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [kinds]: crate::Graph#different-kinds-of-graphs
+    fn reaches(&self, target: &VertexIdType) -> CrabNetsResult<HashSet<VertexIdType>>;
+    /// # Traverse graph with visitor
+    /// 
+    /// ## Description
+    /// Explore the graph starting from the given vertex and report each  event  to  the
+    /// visitor.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `start` : `&VertexIdType` - an immutable reference to the ID of the start.
+    /// * `order` : `TraversalOrder` - the order of traversal.
+    /// * `visitor`   :   `&mut VisitorType`   -   a   mutable    reference    to    the
+    /// [visitor][visitor].
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<bool>` - `Ok(value)` is returned if the start exists,  `value`
+    /// in this case is `true` if the  traversal  was  terminated  by  the  visitor  and
+    /// `false` if it ran to completion; `Err(_)` is returned otherwise.
+    /// 
+    /// ## Details
+    /// Only the vertices reachable  from  the  start  are  visited.  Each  of  them  is
+    /// discovered and finished exactly once, even if several edges lead to it.
+    /// 
+    /// This function takes care  of  the  plumbing  of  breadth-first  and  depth-first
+    /// search, so custom algorithms only need to implement [`Visitor`].
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|) plus the time spent by the visitor.
+    /// 
+    /// Space: O(|V|).
+    /// 
+    /// [visitor]: Visitor
+    fn traverse<VisitorType>(&self, start: &VertexIdType, order: TraversalOrder, visitor: &mut VisitorType) -> CrabNetsResult<bool>
+    where
+        VisitorType: Visitor<EdgeIdType, VertexIdType>;
+    /// # Weakly connected components
+    /// 
+    /// ## Description
+    /// Split the vertices of the graph into (weakly) connected components.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<Vec<VertexIdType>>` - the IDs of the vertices of each component.
+    /// 
+    /// ## Details
+    /// Edge directions are ignored  when  components  are  determined.  Components  are
+    /// listed in the ascending order of  their  smallest  vertex  IDs,  each  component
+    /// starts  with  its  smallest  vertex  ID  followed  by  the  other  vertices   in
+    /// breadth-first order.
+    /// 
+    /// Use [`Traversal::for_each_component`]  to  get  the  subgraphs  induced  by  the
+    /// components instead of the IDs of their vertices.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| * log(|V|) + |E|).
+    /// 
+    /// Space: O(|V|).
+    fn weakly_connected_components(&self) -> Vec<Vec<VertexIdType>>;
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    // Edges that can be traversed from the given vertex (if `forward`) or to it (otherwise)
+    pub(crate) fn iter_traversable_e<'a>(&'a self, id: &VertexIdType, forward: bool) -> impl Iterator<Item = EdgeIteratorItem<EdgeIdType, VertexIdType>> + 'a {
+        self.edge_list.get(id).unwrap().iter_incident_e().filter(move |edge| match edge.direction {
+            EdgeDirection::Undirected => true,
+            EdgeDirection::Directed1to2 => forward,
+            EdgeDirection::Directed2to1 => !forward,
+        })
+    }
+
+    // Weakly connected components in the ascending order of their smallest vertex IDs
+    fn iter_weak_components(&self) -> impl Iterator<Item = Vec<VertexIdType>> + '_ {
+        let mut vertex_ids = self.iter_v().collect::<Vec<_>>();
+        vertex_ids.sort();
+        let mut discovered_vertices = HashSet::with_capacity(self.count_v());
+        vertex_ids.into_iter().filter_map(move |start| {
+            if !discovered_vertices.insert(start.clone()) {
+                return None;
+            }
+            let mut component = vec![start];
+            let mut next_index = 0usize;
+            while next_index < component.len() {
+                let locale = self.edge_list.get(&component[next_index]).unwrap();
+                for adjacent_id in locale.iter_adjacent() {
+                    if discovered_vertices.insert(adjacent_id.clone()) {
+                        component.push(adjacent_id);
+                    }
+                }
+                next_index += 1;
+            }
+            Some(component)
+        })
+    }
+
+    pub(crate) fn bfs_distances(&self, sources: &[VertexIdType], forward: bool, function_path: &str) -> CrabNetsResult<HashMap<VertexIdType, usize>> {
+        let mut workspace = Workspace::new();
+        self.bfs_in_workspace(sources, forward, &mut workspace, function_path)?;
+        Ok(workspace.iter_visited().map(|x| (self.v_id(x).unwrap().clone(), workspace.distance(x).unwrap() as usize)).collect())
+    }
+
+    pub(crate) fn bfs_in_workspace(&self, sources: &[VertexIdType], forward: bool, workspace: &mut Workspace, function_path: &str) -> CrabNetsResult<()> {
+        workspace.reset(self.handle_bound());
+        for source in sources {
+            let handle = self.v_handle(source).ok_or_else(|| CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: source.to_string() }))?;
+            if workspace.visit(handle, 0f64) {
+                workspace.queue.push_back(handle);
+            }
+        }
+        while let Some(curr_handle) = workspace.queue.pop_front() {
+            let next_distance = workspace.distance(curr_handle).unwrap() + 1f64;
+            let locale = self.edge_list.get(self.v_id(curr_handle).unwrap()).unwrap();
+            let adjacent_ids = if forward { locale.iter_adjacent_out() } else { locale.iter_adjacent_in() };
+            for adjacent_id in adjacent_ids.chain(locale.iter_adjacent_undir()) {
+                let adjacent_handle = self.v_handle(&adjacent_id).unwrap();
+                if !workspace.is_visited(adjacent_handle) {
+                    workspace.visit(adjacent_handle, next_distance);
+                    workspace.queue.push_back(adjacent_handle);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Graph::Traversal
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Traversal<EdgeIdType, VertexIdType> for Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    fn for_each_component<ComponentFnType>(&self, mut component_fn: ComponentFnType)
+    where
+        ComponentFnType: FnMut(&Self),
+    {
+        for component in self.iter_weak_components() {
+            let mut subgraph = Self::new();
+            for id in component.iter() {
+                subgraph.add_v(Some(id.clone()));
+                *subgraph.v_attrs_mut(id).unwrap() = self.v_attrs(id).unwrap().clone();
+            }
+            for id in component.iter() {
+                for edge in self.edge_list.get(id).unwrap().iter_incident_e() {
+                    if subgraph.contains_e(&edge.id1, &edge.id2, &edge.edge_id).is_some() {
+                        continue;
+                    }
+                    let (source, target, directed) = match edge.direction {
+                        EdgeDirection::Directed2to1 => (&edge.id2, &edge.id1, true),
+                        EdgeDirection::Directed1to2 => (&edge.id1, &edge.id2, true),
+                        EdgeDirection::Undirected => (&edge.id1, &edge.id2, false),
+                    };
+                    subgraph.add_e(source, target, directed, Some(edge.edge_id.clone())).unwrap();
+                    *subgraph.e_attrs_mut(source, target, &edge.edge_id).unwrap() = self.e_attrs(&edge.id1, &edge.id2, &edge.edge_id).unwrap().clone();
+                }
+            }
+            component_fn(&subgraph);
+        }
+    }
+
+    fn multi_source_bfs(&self, sources: &[VertexIdType]) -> CrabNetsResult<HashMap<VertexIdType, usize>> {
+        const FUNCTION_PATH: &str = "Graph::Traversal::multi_source_bfs";
+        self.bfs_distances(sources, true, FUNCTION_PATH)
+    }
+
+    fn multi_source_bfs_with(&self, sources: &[VertexIdType], workspace: &mut Workspace) -> CrabNetsResult<()> {
+        const FUNCTION_PATH: &str = "Graph::Traversal::multi_source_bfs_with";
+        self.bfs_in_workspace(sources, true, workspace, FUNCTION_PATH)
+    }
+
+    fn reachable_from(&self, source: &VertexIdType) -> CrabNetsResult<HashSet<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Traversal::reachable_from";
+        Ok(self.bfs_distances(std::slice::from_ref(source), true, FUNCTION_PATH)?.into_keys().collect())
+    }
+
+    fn reaches(&self, target: &VertexIdType) -> CrabNetsResult<HashSet<VertexIdType>> {
+        const FUNCTION_PATH: &str = "Graph::Traversal::reaches";
+        Ok(self.bfs_distances(std::slice::from_ref(target), false, FUNCTION_PATH)?.into_keys().collect())
+    }
+
+    fn traverse<VisitorType>(&self, start: &VertexIdType, order: TraversalOrder, visitor: &mut VisitorType) -> CrabNetsResult<bool>
+    where
+        VisitorType: Visitor<EdgeIdType, VertexIdType>,
+    {
+        const FUNCTION_PATH: &str = "Graph::Traversal::traverse";
+        if !self.edge_list.contains_key(start) {
+            return Err(CrabNetsError::new(FUNCTION_PATH, ErrorKind::VertexNotFound { id: start.to_string() }));
+        }
+        let mut discovered_vertices = HashSet::with_capacity(self.count_v());
+        discovered_vertices.insert(start.clone());
+        match visitor.discover_v(start) {
+            VisitControl::Continue => (),
+            VisitControl::Prune => return Ok(visitor.finish_v(start) == VisitControl::Stop),
+            VisitControl::Stop => return Ok(true),
+        }
+        match order {
+            TraversalOrder::BreadthFirst => {
+                let mut unvisited_vertices = VecDeque::from([start.clone()]);
+                while let Some(curr_vertex_id) = unvisited_vertices.pop_front() {
+                    for edge in self.iter_traversable_e(&curr_vertex_id, true) {
+                        match visitor.examine_e(&edge) {
+                            VisitControl::Continue => (),
+                            VisitControl::Prune => continue,
+                            VisitControl::Stop => return Ok(true),
+                        }
+                        if discovered_vertices.insert(edge.id2.clone()) {
+                            match visitor.discover_v(&edge.id2) {
+                                VisitControl::Continue => unvisited_vertices.push_back(edge.id2),
+                                VisitControl::Prune => if visitor.finish_v(&edge.id2) == VisitControl::Stop {
+                                    return Ok(true);
+                                },
+                                VisitControl::Stop => return Ok(true),
+                            }
+                        }
+                    }
+                    if visitor.finish_v(&curr_vertex_id) == VisitControl::Stop {
+                        return Ok(true);
+                    }
+                }
+            },
+            TraversalOrder::DepthFirst => {
+                let mut dfs_stack = vec![(start.clone(), self.iter_traversable_e(start, true))];
+                while let Some((curr_vertex_id, incident_edges)) = dfs_stack.last_mut() {
+                    let edge = match incident_edges.next() {
+                        Some(value) => value,
+                        None => {
+                            if visitor.finish_v(curr_vertex_id) == VisitControl::Stop {
+                                return Ok(true);
+                            }
+                            dfs_stack.pop();
+                            continue;
+                        },
+                    };
+                    match visitor.examine_e(&edge) {
+                        VisitControl::Continue => (),
+                        VisitControl::Prune => continue,
+                        VisitControl::Stop => return Ok(true),
+                    }
+                    if discovered_vertices.insert(edge.id2.clone()) {
+                        match visitor.discover_v(&edge.id2) {
+                            VisitControl::Continue => dfs_stack.push((edge.id2.clone(), self.iter_traversable_e(&edge.id2, true))),
+                            VisitControl::Prune => if visitor.finish_v(&edge.id2) == VisitControl::Stop {
+                                return Ok(true);
+                            },
+                            VisitControl::Stop => return Ok(true),
+                        }
+                    }
+                }
+            },
+        }
+        Ok(false)
+    }
+
+    fn weakly_connected_components(&self) -> Vec<Vec<VertexIdType>> {
+        self.iter_weak_components().collect()
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn for_each_component() {
+        let mut g: graph!(A ===X==> A) = Graph::new();
+        for id in 0..7 {
+            g.add_v(None);
+            g.v_attrs_mut(&id).unwrap().insert("label".to_string(), Box::new(id * 10));
+        }
+        g.add_e(&1, &0, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&5, &3, true, None).unwrap();
+        g.add_e(&3, &5, true, None).unwrap();
+        let mut components = Vec::new();
+        g.for_each_component(|subgraph| {
+            let mut vertex_ids = subgraph.iter_v().collect::<Vec<_>>();
+            vertex_ids.sort();
+            for id in vertex_ids.iter() {
+                assert_eq!(subgraph.v_attrs(id).unwrap().get(&"label".to_string()).unwrap().downcast::<usize>(), Some(&(id * 10)));
+            }
+            components.push((vertex_ids, subgraph.count_e()));
+        });
+        assert_eq!(components, vec![(vec![0, 1, 2], 2), (vec![3, 5], 2), (vec![4], 0), (vec![6], 0)]);
+        g.for_each_component(|subgraph| if subgraph.contains_v(&0) {
+            assert!(subgraph.contains_e(&0, &1, &0) == Some(EdgeDirection::Directed2to1));
+            assert_eq!(subgraph.reachable_from(&1).unwrap(), HashSet::from([0, 1, 2]));
+        });
+    }
+
+    #[test]
+    fn multi_source_bfs() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..6 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&2, &3, false, None).unwrap();
+        g.add_e(&4, &3, true, None).unwrap();
+        assert_eq!(g.multi_source_bfs(&[0]).unwrap(), HashMap::from([(0, 0), (1, 1), (2, 2), (3, 3)]));
+        assert_eq!(g.multi_source_bfs(&[0, 4, 4]).unwrap(), HashMap::from([(0, 0), (1, 1), (2, 2), (3, 1), (4, 0)]));
+        assert!(g.multi_source_bfs(&[]).unwrap().is_empty());
+        assert!(matches!(g.multi_source_bfs(&[0, 9]).unwrap_err().kind(), ErrorKind::VertexNotFound { .. }));
+    }
+
+    #[test]
+    fn reachability() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, false, None).unwrap();
+        g.add_e(&3, &2, true, None).unwrap();
+        assert_eq!(g.reachable_from(&0).unwrap(), HashSet::from([0, 1, 2]));
+        assert_eq!(g.reachable_from(&2).unwrap(), HashSet::from([1, 2]));
+        assert_eq!(g.reaches(&2).unwrap(), HashSet::from([0, 1, 2, 3]));
+        assert_eq!(g.reaches(&4).unwrap(), HashSet::from([4]));
+        assert!(g.reaches(&7).is_err());
+    }
+
+    #[test]
+    fn traverse() {
+        #[derive(Default)]
+        struct Recorder {
+            events: Vec<String>,
+            pruned: usize,
+            stop_at: Option<usize>,
+        }
+        impl Visitor<u8, usize> for Recorder {
+            fn discover_v(&mut self, id: &usize) -> VisitControl {
+                self.events.push(format!("d{}", id));
+                if *id == self.pruned { VisitControl::Prune } else { VisitControl::Continue }
+            }
+            fn examine_e(&mut self, edge: &EdgeIteratorItem<u8, usize>) -> VisitControl {
+                if Some(edge.id2) == self.stop_at { VisitControl::Stop } else { VisitControl::Continue }
+            }
+            fn finish_v(&mut self, id: &usize) -> VisitControl {
+                self.events.push(format!("f{}", id));
+                VisitControl::Continue
+            }
+        }
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&0, &3, true, None).unwrap();
+        g.add_e(&3, &4, true, None).unwrap();
+        let mut visitor = Recorder { pruned: 3, ..Default::default() };
+        assert!(!g.traverse(&0, TraversalOrder::BreadthFirst, &mut visitor).unwrap());
+        assert_eq!(visitor.events.len(), 8);
+        assert_eq!(visitor.events[0], "d0");
+        assert!(visitor.events.contains(&"f3".to_string()));
+        assert!(!visitor.events.contains(&"d4".to_string()));
+        let mut visitor = Recorder { pruned: 5, ..Default::default() };
+        assert!(!g.traverse(&0, TraversalOrder::DepthFirst, &mut visitor).unwrap());
+        let position = |x: &str| visitor.events.iter().position(|y| y == x).unwrap();
+        assert!(position("d2") < position("f2") && position("f2") < position("f1") && position("f1") < position("f0"));
+        assert!(position("f4") < position("f3"));
+        assert_eq!(visitor.events.last().unwrap(), "f0");
+        let mut visitor = Recorder { pruned: 5, stop_at: Some(4), ..Default::default() };
+        assert!(g.traverse(&0, TraversalOrder::DepthFirst, &mut visitor).unwrap());
+        assert!(!visitor.events.contains(&"d4".to_string()));
+        assert!(g.traverse(&9, TraversalOrder::BreadthFirst, &mut visitor).is_err());
+    }
+
+    #[test]
+    fn weakly_connected_components() {
+        let mut g: graph!(X ===X==> X) = Graph::new();
+        assert!(g.weakly_connected_components().is_empty());
+        for _ in 0..7 {
+            g.add_v(None);
+        }
+        g.add_e(&6, &2, true, None).unwrap();
+        g.add_e(&2, &0, true, None).unwrap();
+        g.add_e(&5, &3, false, None).unwrap();
+        g.add_e(&1, &1, false, None).unwrap();
+        let mut components = g.weakly_connected_components();
+        assert_eq!(components.iter().map(|x| x[0]).collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+        components.iter_mut().for_each(|x| x.sort());
+        assert_eq!(components, vec![vec![0, 2, 6], vec![1], vec![3, 5], vec![4]]);
+    }
+}
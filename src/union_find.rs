@@ -0,0 +1,336 @@
+//! # Union-find module
+//! 
+//! ## Description
+//! This module contains [`UnionFind`], the disjoint-set data structure over vertex IDs,
+//! and [`Graph::components_union_find`] that fills it with the connected components  of
+//! the graph. The structure is the building block  of  custom  clustering  and  merging
+//! procedures: sets are merged one pair of elements at a time  and  every  set  can  be
+//! identified by its representative at any moment.
+//! 
+//! [`Graph`]: crate::Graph
+use std::collections::HashMap;
+use crate::{attributes::AttributeCollection, errors::{CrabNetsError, CrabNetsResult, ErrorKind}, BasicImmutableGraph, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * UNION-FIND                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Union-find
+/// 
+/// ## Description
+/// Partition of a set of vertex IDs into disjoint sets, also known as the  disjoint-set
+/// forest.
+/// 
+/// ## Details
+/// Sets are merged by size and paths are compressed on every lookup, so any sequence of
+/// m operations over n elements takes O(m α(n)) time, where α is the inverse  Ackermann
+/// function. The representative of a set may change when the set is merged with another
+/// one.
+/// 
+/// `UnionFind` can also be collected from an  iterator  of  IDs,  every  ID  becomes  a
+/// separate set.
+#[derive(Clone, Debug)]
+pub struct UnionFind<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    parents: HashMap<VertexIdType, VertexIdType>,
+    set_count: usize,
+    // Sizes of the sets kept for their representatives only
+    sizes: HashMap<VertexIdType, usize>,
+}
+
+// UnionFind::UnionFind
+impl<VertexIdType> UnionFind<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    /// # Number of sets
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the number of disjoint sets.
+    pub fn count_sets(&self) -> usize {
+        self.set_count
+    }
+
+    /// # Check if element exists
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if `id` belongs to one of the sets, `false` otherwise.
+    pub fn contains(&self, id: &VertexIdType) -> bool {
+        self.parents.contains_key(id)
+    }
+
+    /// # Find representative
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<VertexIdType>` - `Ok(value)` is returned if  `id`  belongs  to
+    /// one of the sets, `value` in this case is the representative of its set; `Err(_)`
+    /// is returned otherwise.
+    pub fn find(&mut self, id: &VertexIdType) -> CrabNetsResult<VertexIdType> {
+        const FUNCTION_PATH: &str = "UnionFind::UnionFind::find";
+        self.find_root(id, FUNCTION_PATH)
+    }
+
+    // Find the representative of the set and compress the path to it
+    fn find_root(&mut self, id: &VertexIdType, function_path: &str) -> CrabNetsResult<VertexIdType> {
+        let mut root = match self.parents.get(id) {
+            Some(parent) => parent.clone(),
+            None => return Err(CrabNetsError::new(function_path, ErrorKind::VertexNotFound { id: id.to_string() })),
+        };
+        while self.parents[&root] != root {
+            root = self.parents[&root].clone();
+        }
+        let mut curr_vertex_id = id.clone();
+        while curr_vertex_id != root {
+            curr_vertex_id = self.parents.insert(curr_vertex_id, root.clone()).unwrap();
+        }
+        Ok(root)
+    }
+
+    /// # Add element
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `VertexIdType` - the ID to add as a new set of one element.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if the ID was added, `false` if it already belongs to  one  of
+    /// the sets, nothing is changed in this case.
+    pub fn insert(&mut self, id: VertexIdType) -> bool {
+        if self.parents.contains_key(&id) {
+            return false;
+        }
+        self.parents.insert(id.clone(), id.clone());
+        self.sizes.insert(id, 1);
+        self.set_count += 1;
+        true
+    }
+
+    /// # Check if there are no elements
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if no ID belongs to the sets, `false` otherwise.
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// # Number of elements
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `usize` - the total number of IDs in all sets.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// # Create union-find
+    /// 
+    /// ## Returns
+    /// * `UnionFind<VertexIdType>` - a new union-find without elements.
+    pub fn new() -> Self {
+        UnionFind { parents: HashMap::new(), set_count: 0, sizes: HashMap::new() }
+    }
+
+    /// # Check if elements are in the same set
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id1` : `&VertexIdType` - an immutable reference to the first ID.
+    /// * `id2` : `&VertexIdType` - an immutable reference to the second ID.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<bool>` - `Ok(value)` is returned if both  IDs  belong  to  the
+    /// sets, `value` in this case is `true` if they belong to the same set and  `false`
+    /// otherwise; `Err(_)` is returned otherwise.
+    pub fn same_set(&mut self, id1: &VertexIdType, id2: &VertexIdType) -> CrabNetsResult<bool> {
+        const FUNCTION_PATH: &str = "UnionFind::UnionFind::same_set";
+        Ok(self.find_root(id1, FUNCTION_PATH)? == self.find_root(id2, FUNCTION_PATH)?)
+    }
+
+    /// # Size of set
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id` : `&VertexIdType` - an immutable reference to the ID.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<usize>` - `Ok(value)` is returned if `id` belongs  to  one  of
+    /// the sets, `value` in this case is the number of elements of its set; `Err(_)` is
+    /// returned otherwise.
+    pub fn set_size(&mut self, id: &VertexIdType) -> CrabNetsResult<usize> {
+        const FUNCTION_PATH: &str = "UnionFind::UnionFind::set_size";
+        let root = self.find_root(id, FUNCTION_PATH)?;
+        Ok(self.sizes[&root])
+    }
+
+    /// # Collect sets
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `Vec<Vec<VertexIdType>>` - all sets, elements of each set are sorted, sets are
+    /// sorted by their smallest elements.
+    pub fn sets(&mut self) -> Vec<Vec<VertexIdType>> {
+        const FUNCTION_PATH: &str = "UnionFind::UnionFind::sets";
+        let mut ids = self.parents.keys().cloned().collect::<Vec<_>>();
+        ids.sort();
+        let mut positions = HashMap::with_capacity(self.set_count);
+        let mut answer: Vec<Vec<VertexIdType>> = Vec::with_capacity(self.set_count);
+        for id in ids {
+            let root = self.find_root(&id, FUNCTION_PATH).unwrap();
+            let position = *positions.entry(root).or_insert(answer.len());
+            if position == answer.len() {
+                answer.push(Vec::new());
+            }
+            answer[position].push(id);
+        }
+        answer
+    }
+
+    /// # Merge sets
+    /// 
+    /// ## Description
+    /// Merge the sets that contain the given IDs.
+    /// 
+    /// ## Arguments
+    /// * `&mut self` - a mutable reference to the caller.
+    /// * `id1` : `&VertexIdType` - an immutable reference to the first ID.
+    /// * `id2` : `&VertexIdType` - an immutable reference to the second ID.
+    /// 
+    /// ## Returns
+    /// * `CrabNetsResult<bool>` - `Ok(value)` is returned if both  IDs  belong  to  the
+    /// sets, `value` in this case is `true` if  two  different  sets  were  merged  and
+    /// `false` if the IDs already were in the same set; `Err(_)` is returned otherwise.
+    pub fn union(&mut self, id1: &VertexIdType, id2: &VertexIdType) -> CrabNetsResult<bool> {
+        const FUNCTION_PATH: &str = "UnionFind::UnionFind::union";
+        let (root1, root2) = (self.find_root(id1, FUNCTION_PATH)?, self.find_root(id2, FUNCTION_PATH)?);
+        if root1 == root2 {
+            return Ok(false);
+        }
+        let (smaller, larger) = match self.sizes[&root1] < self.sizes[&root2] {
+            true => (root1, root2),
+            false => (root2, root1),
+        };
+        let size = self.sizes.remove(&smaller).unwrap();
+        *self.sizes.get_mut(&larger).unwrap() += size;
+        self.parents.insert(smaller, larger);
+        self.set_count -= 1;
+        Ok(true)
+    }
+}
+
+// UnionFind::Default
+impl<VertexIdType> Default for UnionFind<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// UnionFind::FromIterator
+impl<VertexIdType> FromIterator<VertexIdType> for UnionFind<VertexIdType>
+where
+    VertexIdType: Id,
+{
+    fn from_iter<T: IntoIterator<Item = VertexIdType>>(iter: T) -> Self {
+        let mut answer = Self::new();
+        for id in iter {
+            answer.insert(id);
+        }
+        answer
+    }
+}
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Connected components as union-find
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `UnionFind<VertexIdType>` - the union-find that contains all vertices  of  the
+    /// graph, two vertices belong to the same set if and only  if  they  are  connected
+    /// when the directions of edges are ignored.
+    /// 
+    /// ## Details
+    /// The result can be extended with further merges, e.g. to join the  components  of
+    /// the graph by some external criterion.
+    /// 
+    /// ## Complexity
+    /// Time: O((|V| + |E|) α(|V|)).
+    /// 
+    /// Space: O(|V|).
+    pub fn components_union_find(&self) -> UnionFind<VertexIdType> {
+        let mut answer: UnionFind<VertexIdType> = self.iter_v().collect();
+        for edge in self.iter_e() {
+            answer.union(&edge.id1, &edge.id2).unwrap();
+        }
+        answer
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::*;
+
+    #[test]
+    fn union_find() {
+        let mut sets: UnionFind<usize> = (0..6).collect();
+        assert_eq!((sets.len(), sets.count_sets()), (6, 6));
+        assert!(sets.union(&0, &1).unwrap() && sets.union(&2, &1).unwrap() && sets.union(&4, &5).unwrap());
+        assert!(!sets.union(&0, &2).unwrap());
+        assert!(sets.same_set(&0, &2).unwrap() && !sets.same_set(&0, &3).unwrap());
+        assert_eq!(sets.set_size(&1).unwrap(), 3);
+        assert_eq!(sets.count_sets(), 3);
+        assert_eq!(sets.sets(), vec![vec![0, 1, 2], vec![3], vec![4, 5]]);
+        assert!(sets.find(&6).is_err() && sets.union(&0, &6).is_err());
+        assert!(sets.insert(6) && !sets.insert(6) && sets.contains(&6));
+        assert_eq!(sets.count_sets(), 4);
+        let g: graph!(X ---X--> X) = "V 0\nV 1\nV 2\nV 3\nV 4\n\nE 1 0\nE 3 4\n".parse().unwrap();
+        let mut components = g.components_union_find();
+        assert_eq!(components.sets(), vec![vec![0, 1], vec![2], vec![3, 4]]);
+        assert!(components.union(&0, &2).unwrap());
+        assert_eq!(components.set_size(&2).unwrap(), 3);
+    }
+}
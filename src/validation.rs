@@ -0,0 +1,259 @@
+//! # Validation module
+//! 
+//! ## Description
+//! This module contains [`Graph::validate`] that  checks  the  internal  invariants  of
+//! [`Graph`] and the types that describe its results.
+//! 
+//! [`Graph`]: crate::Graph
+//! [`Graph::validate`]: crate::Graph::validate
+use alloc::vec::Vec;
+use core::fmt::Display;
+use crate::{attributes::AttributeCollection, EdgeDirection, Graph, Id, Locale};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * VALIDATION REPORT                                                                 *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Violation of an internal invariant
+/// 
+/// ## Description
+/// Each   variant   describes   one   broken   invariant   of   [`Graph`]   found    by
+/// [`Graph::validate`].
+/// 
+/// ## Variants
+/// * `DanglingEdge` - the locale of vertex `id1` stores an edge leading to vertex `id2`
+/// that doesn't exist.
+/// * `AsymmetricEdge` - the edge is stored in the locale of vertex `id1` but is  either
+/// missing or has a mismatching direction in the locale of vertex `id2`.
+/// * `EdgeAttributesOwnership` - the attribute collection of the edge is stored `count`
+/// times instead of exactly once.
+/// * `MinFreeVertexId` - the smallest  free  vertex  ID  remembered  by  the  graph  is
+/// `actual` while it should be `expected`.
+/// * `VertexHandle` - vertex `id` has no [handle][handle] or  its  handle  leads  to  a
+/// different vertex.
+/// * `VertexHandleCount` - the graph remembers `actual` vertex  handles  while  it  has
+/// `expected` vertices.
+/// 
+/// [handle]: crate::handles::VertexHandle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvariantViolation<EdgeIdType, VertexIdType> {
+    DanglingEdge { id1: VertexIdType, id2: VertexIdType, edge_id: EdgeIdType },
+    AsymmetricEdge { id1: VertexIdType, id2: VertexIdType, edge_id: EdgeIdType },
+    EdgeAttributesOwnership { id1: VertexIdType, id2: VertexIdType, edge_id: EdgeIdType, count: usize },
+    MinFreeVertexId { expected: VertexIdType, actual: VertexIdType },
+    VertexHandle { id: VertexIdType },
+    VertexHandleCount { expected: usize, actual: usize },
+}
+
+// InvariantViolation::Display
+impl<EdgeIdType, VertexIdType> Display for InvariantViolation<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Display,
+    VertexIdType: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            InvariantViolation::DanglingEdge { id1, id2, edge_id } => write!(f, "Vertex {} stores edge {} leading to non-existing vertex {}.", id1, edge_id, id2),
+            InvariantViolation::AsymmetricEdge { id1, id2, edge_id } => write!(f, "Edge {} between vertices {} and {} is not stored symmetrically.", edge_id, id1, id2),
+            InvariantViolation::EdgeAttributesOwnership { id1, id2, edge_id, count } => write!(f, "Attributes of edge {} between vertices {} and {} are stored {} times.", edge_id, id1, id2, count),
+            InvariantViolation::MinFreeVertexId { expected, actual } => write!(f, "Minimum free vertex ID is {} but {} is remembered.", expected, actual),
+            InvariantViolation::VertexHandle { id } => write!(f, "Vertex {} has no valid handle.", id),
+            InvariantViolation::VertexHandleCount { expected, actual } => write!(f, "Graph has {} vertices but {} handles are remembered.", expected, actual),
+        }
+    }
+}
+
+
+
+/// # Validation report
+/// 
+/// ## Description
+/// The result of [`Graph::validate`][validate], a list of all broken invariants.
+/// 
+/// [validate]: crate::Graph::validate
+#[derive(Clone, Debug)]
+pub struct ValidationReport<EdgeIdType, VertexIdType> {
+    pub violations: Vec<InvariantViolation<EdgeIdType, VertexIdType>>,
+}
+
+// ValidationReport::ValidationReport
+impl<EdgeIdType, VertexIdType> ValidationReport<EdgeIdType, VertexIdType> {
+    /// # Check if no invariants are broken
+    /// 
+    /// ## Description
+    /// Check if the validated graph is consistent.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` is returned if no violations were found, `false`  is  returned
+    /// otherwise.
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+// ValidationReport::Display
+impl<EdgeIdType, VertexIdType> Display for ValidationReport<EdgeIdType, VertexIdType>
+where
+    EdgeIdType: Display,
+    VertexIdType: Display,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.is_valid() {
+            return f.write_str("No invariant violations found.");
+        }
+        write!(f, "{} invariant violation(s) found:", self.violations.len())?;
+        for violation in self.violations.iter() {
+            write!(f, "\n* {}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * VALIDATION                                                                        *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+// Graph::Graph
+impl<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType> Graph<EdgeAttributeCollectionType, EdgeIdType, LocaleType, VertexAttributeCollectionType, VertexIdType>
+where
+    EdgeAttributeCollectionType: AttributeCollection,
+    EdgeIdType: Id,
+    LocaleType: Locale<EdgeAttributeCollectionType, EdgeIdType, VertexAttributeCollectionType, VertexIdType>,
+    VertexAttributeCollectionType: AttributeCollection,
+    VertexIdType: Id,
+{
+    /// # Validate the graph
+    /// 
+    /// ## Description
+    /// Check the internal invariants of the graph.
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `ValidationReport` - the list of all broken invariants.
+    /// 
+    /// ## Details
+    /// The following invariants are checked:
+    /// * each edge is stored in the locales of both of its vertices and its  directions
+    /// in these locales agree;
+    /// * the attribute collection of each edge is stored exactly once;
+    /// * the smallest free vertex ID remembered by the graph  is  indeed  the  smallest
+    /// free vertex ID.
+    /// 
+    /// Graphs modified only via the public API must always be valid. This  function  is
+    /// mostly useful to debug custom [locales][locale]. If the crate is  compiled  with
+    /// feature `validate` and debug assertions enabled, the graph  is  validated  after
+    /// each mutation, and a panic is triggered as soon as any invariant is broken.
+    /// 
+    /// ## Complexity
+    /// Time: O(|V| + |E|).
+    /// 
+    /// Space: O(1) in addition to the report.
+    /// 
+    /// [locale]: crate::locales::Locale
+    pub fn validate(&self) -> ValidationReport<EdgeIdType, VertexIdType> {
+        let mut violations = Vec::new();
+        for (id, locale) in self.edge_list.iter() {
+            for edge in locale.iter_incident_e() {
+                let counterpart = match self.edge_list.get(&edge.id2) {
+                    Some(value) => value,
+                    None => {
+                        violations.push(InvariantViolation::DanglingEdge { id1: edge.id1, id2: edge.id2, edge_id: edge.edge_id });
+                        continue;
+                    },
+                };
+                let expected_direction = match edge.direction {
+                    EdgeDirection::Undirected => EdgeDirection::Undirected,
+                    EdgeDirection::Directed1to2 => EdgeDirection::Directed2to1,
+                    EdgeDirection::Directed2to1 => EdgeDirection::Directed1to2,
+                };
+                let is_symmetric = if edge.id1 == edge.id2 && edge.direction != EdgeDirection::Undirected {
+                    // Both halves of a directed self-loop are stored in the same locale,
+                    // and `e_direction` can only report one of them
+                    locale.iter_incident_e().any(|x| x.id2 == edge.id2 && x.edge_id == edge.edge_id && x.direction == expected_direction)
+                } else {
+                    counterpart.e_direction(id, &edge.edge_id) == Some(expected_direction)
+                };
+                if !is_symmetric {
+                    violations.push(InvariantViolation::AsymmetricEdge { id1: edge.id1, id2: edge.id2, edge_id: edge.edge_id });
+                    continue;
+                }
+                // Each edge is seen from both of its ends, so ownership is checked only once
+                if edge.id1 <= edge.id2 {
+                    let count = locale.e_attrs(&edge.id2, &edge.edge_id).is_some() as usize
+                        + if edge.id1 != edge.id2 { counterpart.e_attrs(&edge.id1, &edge.edge_id).is_some() as usize } else { 0 };
+                    if count != 1 {
+                        violations.push(InvariantViolation::EdgeAttributesOwnership { id1: edge.id1, id2: edge.id2, edge_id: edge.edge_id, count });
+                    }
+                }
+            }
+        }
+        let mut expected = VertexIdType::default();
+        while self.edge_list.contains_key(&expected) {
+            expected.increment();
+        }
+        if expected != self.min_free_vertex_id {
+            violations.push(InvariantViolation::MinFreeVertexId { expected, actual: self.min_free_vertex_id.clone() });
+        }
+        for id in self.edge_list.keys() {
+            if self.v_handle(id).and_then(|x| self.v_id(x)) != Some(id) {
+                violations.push(InvariantViolation::VertexHandle { id: id.clone() });
+            }
+        }
+        if self.handles.len() != self.edge_list.len() {
+            violations.push(InvariantViolation::VertexHandleCount { expected: self.edge_list.len(), actual: self.handles.len() });
+        }
+        ValidationReport { violations }
+    }
+
+    #[cfg(all(feature = "validate", debug_assertions))]
+    pub(crate) fn debug_validate(&self) {
+        let report = self.validate();
+        assert!(report.is_valid(), "{}", report);
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use super::InvariantViolation;
+
+    #[test]
+    fn validate() {
+        let mut g: graph!(X ---A--> X) = Graph::new();
+        let id1 = g.add_v(None);
+        let id2 = g.add_v(None);
+        let id3 = g.add_v(None);
+        g.add_e(&id1, &id2, true, None).unwrap();
+        g.add_e(&id3, &id2, false, None).unwrap();
+        g.remove_v(&id1);
+        assert!(g.validate().is_valid());
+        g.edge_list.get_mut(&id2).unwrap().remove_e(&id3, &0);
+        g.min_free_vertex_id = 5;
+        let report = g.validate();
+        assert_eq!(report.violations.len(), 2);
+        assert!(report.violations.contains(&InvariantViolation::AsymmetricEdge { id1: id3, id2, edge_id: 0 }));
+        assert!(report.violations.contains(&InvariantViolation::MinFreeVertexId { expected: id1, actual: 5 }));
+    }
+}
@@ -0,0 +1,196 @@
+//! # Workspace module
+//! 
+//! ## Description
+//! This module contains [`Workspace`], the reusable buffers  of  searches  over  graphs
+//! indexed by [vertex handles][handles].
+//! 
+//! [handles]: crate::handles::VertexHandle
+use std::collections::{BinaryHeap, VecDeque};
+use crate::{handles::VertexHandle, shortest_paths::HeapItem};
+
+
+
+
+
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+// * WORKSPACE                                                                         *
+// * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * * *
+
+
+
+/// # Workspace
+/// 
+/// ## Description
+/// Buffers of a search (a  bitmap  of  visited  vertices,  a  queue  and  an  array  of
+/// distances) that survive between calls, so that algorithms that run many searches  in
+/// a row don't allocate memory for each of them.
+/// 
+/// ## Details
+/// Pass   the   same   workspace   to   [`Traversal::multi_source_bfs_with`][bfs]    or
+/// [`ShortestPaths::dijkstra_with`][dijkstra]  as  many  times  as  needed.  Each  call
+/// forgets the results of the previous one, and  its  own  results  can  be  read  with
+/// [`Workspace::distance`] and [`Workspace::iter_visited`] until the next call.
+/// 
+/// The   buffers   only   grow,   their   size   is   determined   by    the    largest
+/// [`Graph::handle_bound`][bound] among the graphs the workspace has been used with.  A
+/// workspace can be shared by different graphs, but its results refer to the handles of
+/// the graph searched last.
+/// 
+/// [bfs]: crate::traversal::Traversal::multi_source_bfs_with
+/// [bound]: crate::Graph::handle_bound
+/// [dijkstra]: crate::shortest_paths::ShortestPaths::dijkstra_with
+#[derive(Default)]
+pub struct Workspace {
+    distances: Vec<f64>,
+    pub(crate) heap: BinaryHeap<HeapItem<VertexHandle>>,
+    pub(crate) queue: VecDeque<VertexHandle>,
+    // Visited vertices in the order of their discovery, so that only their bits have
+    // to be cleared before the next search
+    reached: Vec<VertexHandle>,
+    visited: Vec<u64>,
+}
+
+// Workspace::Workspace
+impl Workspace {
+    /// # Create empty workspace
+    /// 
+    /// ## Returns
+    /// * `Workspace` - a workspace that hasn't allocated any memory yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Create workspace with capacity
+    /// 
+    /// ## Arguments
+    /// * `handle_bound` : `usize` - the [handle bound][bound] of the largest graph  the
+    /// workspace will be used with.
+    /// 
+    /// ## Returns
+    /// * `Workspace` - a workspace that won't allocate  memory  while  it's  used  with
+    /// graphs whose handle bounds don't exceed `handle_bound`, except for the queues.
+    /// 
+    /// [bound]: crate::Graph::handle_bound
+    pub fn with_capacity(handle_bound: usize) -> Self {
+        let mut answer = Self::new();
+        answer.reset(handle_bound);
+        answer
+    }
+
+    /// # Distance to vertex
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `handle` : `VertexHandle` - the handle of the vertex.
+    /// 
+    /// ## Returns
+    /// * `Option<f64>` - `Some(value)` is returned  if  the  last  search  reached  the
+    /// vertex, `value` in this case is the distance to it  (the  number  of  edges  for
+    /// breadth-first search); `None` is returned otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    #[inline]
+    pub fn distance(&self, handle: VertexHandle) -> Option<f64> {
+        if self.is_visited(handle) { Some(self.distances[handle.index()]) } else { None }
+    }
+
+    /// # Check if vertex is visited
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// * `handle` : `VertexHandle` - the handle of the vertex.
+    /// 
+    /// ## Returns
+    /// * `bool` - `true` if the last search reached the vertex, `false` otherwise.
+    /// 
+    /// ## Complexity
+    /// Time: O(1).
+    /// 
+    /// Space: O(1).
+    #[inline]
+    pub fn is_visited(&self, handle: VertexHandle) -> bool {
+        let index = handle.index();
+        self.visited.get(index / 64).is_some_and(|x| x & (1 << (index % 64)) != 0)
+    }
+
+    /// # Iterate over visited vertices
+    /// 
+    /// ## Arguments
+    /// * `&self` - an immutable reference to the caller.
+    /// 
+    /// ## Returns
+    /// * `impl Iterator<Item = VertexHandle>` - the handles of all vertices reached  by
+    /// the last search in the order of their discovery.
+    /// 
+    /// ## Complexity
+    /// Time: O(number of visited vertices) for the whole iteration.
+    /// 
+    /// Space: O(1).
+    pub fn iter_visited(&self) -> impl Iterator<Item = VertexHandle> + '_ {
+        self.reached.iter().copied()
+    }
+
+    // Forget the previous search and make room for handles below `handle_bound`
+    pub(crate) fn reset(&mut self, handle_bound: usize) {
+        for handle in self.reached.drain(..) {
+            self.visited[handle.index() / 64] = 0;
+        }
+        if self.distances.len() < handle_bound {
+            self.distances.resize(handle_bound, 0f64);
+            self.visited.resize(handle_bound.div_ceil(64), 0);
+        }
+        self.heap.clear();
+        self.queue.clear();
+    }
+
+    // Update the distance to the vertex, returns `true` if it wasn't visited before
+    #[inline]
+    pub(crate) fn visit(&mut self, handle: VertexHandle, distance: f64) -> bool {
+        let index = handle.index();
+        self.distances[index] = distance;
+        let is_new = self.visited[index / 64] & (1 << (index % 64)) == 0;
+        if is_new {
+            self.visited[index / 64] |= 1 << (index % 64);
+            self.reached.push(handle);
+        }
+        is_new
+    }
+}
+
+
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use crate::{shortest_paths::ShortestPaths, traversal::Traversal};
+    use super::*;
+
+    #[test]
+    fn workspace() {
+        let mut g: graph!(X ---X--> X) = Graph::new();
+        for _ in 0..5 {
+            g.add_v(None);
+        }
+        g.add_e(&0, &1, true, None).unwrap();
+        g.add_e(&1, &2, true, None).unwrap();
+        g.add_e(&0, &2, true, None).unwrap();
+        g.add_e(&3, &4, true, None).unwrap();
+        let mut workspace = Workspace::new();
+        g.multi_source_bfs_with(&[0], &mut workspace).unwrap();
+        let distances: Vec<_> = (0..5).map(|x| workspace.distance(g.v_handle(&x).unwrap())).collect();
+        assert_eq!(distances, vec![Some(0.0), Some(1.0), Some(1.0), None, None]);
+        assert_eq!(workspace.iter_visited().count(), 3);
+        g.dijkstra_with(&0, |x, y, _| (x + y) as f64, &mut workspace).unwrap();
+        assert_eq!(workspace.distance(g.v_handle(&2).unwrap()), Some(2.0));
+        g.multi_source_bfs_with(&[3], &mut workspace).unwrap();
+        assert!(!workspace.is_visited(g.v_handle(&0).unwrap()));
+        assert_eq!(workspace.distance(g.v_handle(&4).unwrap()), Some(1.0));
+        assert!(g.multi_source_bfs_with(&[7], &mut workspace).is_err());
+        assert!(g.dijkstra_with(&0, |_, _, _| -1.0, &mut workspace).is_err());
+    }
+}
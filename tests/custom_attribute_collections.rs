@@ -1,3 +1,4 @@
+#![cfg(feature = "std")]
 use std::iter::once;
 use crabnets::{prelude::*, io::{AttributeCollectionIO, AttributeToken}};
 
@@ -0,0 +1,78 @@
+#![cfg(feature = "testing")]
+use std::iter::once;
+use crabnets::{prelude::*, io::{AttributeCollectionIO, AttributeToken, SupportedFormats}};
+
+
+
+
+
+#[derive(Clone, Default)]
+struct VertexCoordinates {
+    pub x: u8,
+    pub y: u8,
+}
+
+// VertexCoordinates::AttributeCollection
+impl AttributeCollection for VertexCoordinates {
+    fn new() -> Self {
+        VertexCoordinates { x: 0, y: 0 }
+    }
+}
+
+// VertexCoordinates::AttributeCollectionIO
+impl AttributeCollectionIO for VertexCoordinates {
+    fn io_iter_contents<'a>(&'a self) -> Box<dyn Iterator<Item = AttributeToken<'a>> + 'a> {
+        Box::new(
+            once(AttributeToken { name: "x", value: StaticDispatchAttributeValue::UInt8(self.x) })
+            .chain(once(AttributeToken { name: "y", value: StaticDispatchAttributeValue::UInt8(self.y) }))
+        )
+    }
+
+    fn io_query_contents(&self, attribute_name: &str) -> Option<StaticDispatchAttributeValue> {
+        match attribute_name {
+            "x" => Some(StaticDispatchAttributeValue::UInt8(self.x)),
+            "y" => Some(StaticDispatchAttributeValue::UInt8(self.y)),
+            _ => None,
+        }
+    }
+
+    fn io_reader_callback<'a, EdgeIdType, VertexIdType>(&mut self, token: AttributeToken<'a>)
+        where
+            EdgeIdType: Id,
+            VertexIdType: Id,
+    {
+        match (token.name, token.value) {
+            ("x", StaticDispatchAttributeValue::UInt8(value)) => self.x = value,
+            ("y", StaticDispatchAttributeValue::UInt8(value)) => self.y = value,
+            _ => (),
+        }
+    }
+}
+
+
+
+
+
+#[test]
+fn gnbs_roundtrip() {
+    for file_name in ["tests/fixtures/attributes.gnbs", "tests/fixtures/empty.gnbs", "tests/fixtures/plain.gnbs"] {
+        assert_roundtrip::<graph!(A ===A==> A)>(file_name, SupportedFormats::GNBS);
+    }
+    assert_roundtrip::<graph!(X ---X--- X)>("tests/fixtures/plain.gnbs", SupportedFormats::GNBS);
+    assert_roundtrip::<graph!(A ---X--- A with VertexAttributeCollectionType = VertexCoordinates)>("tests/fixtures/coordinates.gnbs", SupportedFormats::GNBS);
+}
+
+#[test]
+#[should_panic(expected = "written differently")]
+fn gnbs_roundtrip_loses_attributes() {
+    // Attributes are dropped when the graph doesn't store them
+    assert_roundtrip::<graph!(X ===X==> X)>("tests/fixtures/attributes.gnbs", SupportedFormats::GNBS);
+}
+
+#[test]
+fn protobuf_roundtrip() {
+    for file_name in ["tests/fixtures/attributes.gnbs", "tests/fixtures/empty.gnbs", "tests/fixtures/plain.gnbs"] {
+        assert_roundtrip::<graph!(A ===A==> A)>(file_name, SupportedFormats::Protobuf);
+    }
+    assert_roundtrip::<graph!(A ---X--- A with VertexAttributeCollectionType = VertexCoordinates)>("tests/fixtures/coordinates.gnbs", SupportedFormats::Protobuf);
+}